@@ -1,7 +1,9 @@
+use crate::cashaddr::{CashAddress, CashAddressType};
 use crate::format::BitcoinFormat;
 use crate::network::BitcoinNetwork;
 use crate::private_key::BitcoinPrivateKey;
 use crate::public_key::BitcoinPublicKey;
+use crate::script::BitcoinOutputType;
 use crate::witness_program::WitnessProgram;
 use wagyu_model::{
     crypto::{checksum, hash160},
@@ -10,7 +12,7 @@ use wagyu_model::{
 use wagyu_model::no_std::*;
 
 use base58::{FromBase58, ToBase58};
-use bech32::{u5, Bech32, FromBase32, ToBase32};
+use bech32::{u5, Bech32, ToBase32};
 use core::{convert::TryFrom, fmt, marker::PhantomData, str::FromStr};
 use sha2::{Digest, Sha256};
 
@@ -37,6 +39,7 @@ impl<N: BitcoinNetwork> Address for BitcoinAddress<N> {
             BitcoinFormat::P2PKH => Self::p2pkh(&public_key),
             BitcoinFormat::P2WSH => return Err(AddressError::IncompatibleFormats(String::from("non-script"), String::from("p2wsh address"))),
             BitcoinFormat::P2SH_P2WPKH => Self::p2sh_p2wpkh(&public_key),
+            BitcoinFormat::P2SH => return Err(AddressError::IncompatibleFormats(String::from("non-script"), String::from("p2sh address"))),
             BitcoinFormat::Bech32 => Self::bech32(&public_key),
         }
     }
@@ -47,6 +50,7 @@ impl<N: BitcoinNetwork> Address for BitcoinAddress<N> {
             BitcoinFormat::P2PKH => Self::p2pkh(public_key),
             BitcoinFormat::P2WSH => return Err(AddressError::IncompatibleFormats(String::from("non-script"), String::from("p2wsh address"))),
             BitcoinFormat::P2SH_P2WPKH => Self::p2sh_p2wpkh(public_key),
+            BitcoinFormat::P2SH => return Err(AddressError::IncompatibleFormats(String::from("non-script"), String::from("p2sh address"))),
             BitcoinFormat::Bech32 => Self::bech32(public_key),
         }
     }
@@ -60,22 +64,12 @@ impl<N: BitcoinNetwork> BitcoinAddress<N> {
             false => public_key.to_secp256k1_public_key().serialize().to_vec(),
         };
 
-        let mut address = [0u8; 25];
-        address[0] = N::to_address_prefix(&BitcoinFormat::P2PKH)[0];
-        address[1..21].copy_from_slice(&hash160(&public_key));
-
-        let sum = &checksum(&address[0..21])[0..4];
-        address[21..25].copy_from_slice(sum);
-
-        Ok(Self {
-            address: address.to_base58(),
-            format: BitcoinFormat::P2PKH,
-            _network: PhantomData,
-        })
+        Self::from_hash(&hash160(&public_key), &BitcoinFormat::P2PKH)
     }
 
     // Returns a P2WSH address in Bech32 format from a given Bitcoin script
     pub fn p2wsh(original_script: &Vec<u8>) -> Result<Self, AddressError> {
+        Self::check_segwit(&BitcoinFormat::P2WSH)?;
         let script = Sha256::digest(&original_script).to_vec();
 
         // Organize as a hash
@@ -97,6 +91,7 @@ impl<N: BitcoinNetwork> BitcoinAddress<N> {
 
     /// Returns a P2SH_P2WPKH address from a given Bitcoin public key.
     pub fn p2sh_p2wpkh(public_key: &<Self as Address>::PublicKey) -> Result<Self, AddressError> {
+        Self::check_segwit(&BitcoinFormat::P2SH_P2WPKH)?;
         let mut address = [0u8; 25];
         address[0] = N::to_address_prefix(&BitcoinFormat::P2SH_P2WPKH)[0];
         address[1..21].copy_from_slice(&hash160(&Self::create_redeem_script(public_key)));
@@ -113,6 +108,7 @@ impl<N: BitcoinNetwork> BitcoinAddress<N> {
 
     /// Returns a Bech32 address from a given Bitcoin public key.
     pub fn bech32(public_key: &<Self as Address>::PublicKey) -> Result<Self, AddressError> {
+        Self::check_segwit(&BitcoinFormat::Bech32)?;
         let redeem_script = Self::create_redeem_script(public_key);
         let version = u5::try_from_u8(redeem_script[0])?;
 
@@ -128,6 +124,113 @@ impl<N: BitcoinNetwork> BitcoinAddress<N> {
         })
     }
 
+    /// Returns the address paid to by the given script public key.
+    /// P2PK, bare multisig, OP_RETURN, and nonstandard outputs have no address representation.
+    pub fn from_script_pub_key(script_pub_key: &[u8]) -> Result<Self, AddressError> {
+        match BitcoinOutputType::from_script_pub_key(script_pub_key) {
+            BitcoinOutputType::P2PKH(hash) => Self::from_hash(&hash, &BitcoinFormat::P2PKH),
+            BitcoinOutputType::P2SH(hash) => Self::from_hash(&hash, &Self::script_hash_format()),
+            BitcoinOutputType::P2WSH(_) => {
+                Self::from_witness_program(&WitnessProgram::from_scriptpubkey(script_pub_key)?, &BitcoinFormat::P2WSH)
+            }
+            BitcoinOutputType::P2WPKH(_) | BitcoinOutputType::P2TR(_) | BitcoinOutputType::WitnessUnknown(_) => {
+                Self::from_witness_program(&WitnessProgram::from_scriptpubkey(script_pub_key)?, &BitcoinFormat::Bech32)
+            }
+            output_type => Err(AddressError::IncompatibleFormats(
+                output_type.to_string(),
+                String::from("address"),
+            )),
+        }
+    }
+
+    /// Returns a Base58Check or CashAddr address from a given public key hash or script hash.
+    pub fn from_hash(hash: &[u8], format: &BitcoinFormat) -> Result<Self, AddressError> {
+        if hash.len() != 20 {
+            return Err(AddressError::InvalidByteLength(hash.len()));
+        }
+
+        let address = match N::CASHADDR_PREFIX {
+            Some(prefix) => {
+                let address_type = match format {
+                    BitcoinFormat::P2PKH => CashAddressType::P2PKH,
+                    BitcoinFormat::P2SH => CashAddressType::P2SH,
+                    _ => return Err(AddressError::IncompatibleFormats(format.to_string(), "cashaddr".into())),
+                };
+                CashAddress::new(prefix, address_type, hash)?.to_string()
+            }
+            None => Self::to_base58check(hash, format),
+        };
+
+        Ok(Self {
+            address,
+            format: format.clone(),
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns the public key hash or script hash of a P2PKH or P2SH address.
+    pub fn to_hash(&self) -> Result<Vec<u8>, AddressError> {
+        match self.format {
+            BitcoinFormat::P2PKH | BitcoinFormat::P2SH_P2WPKH | BitcoinFormat::P2SH => (),
+            _ => return Err(AddressError::IncompatibleFormats(self.format.to_string(), "hash".into())),
+        };
+
+        match N::CASHADDR_PREFIX {
+            Some(prefix) => Ok(CashAddress::from_str(&self.address, prefix)?.hash),
+            None => {
+                let data = self.address.from_base58()?;
+                Ok(data[1..21].to_vec())
+            }
+        }
+    }
+
+    /// Returns the legacy Base58Check encoding of a P2PKH or P2SH address.
+    /// For networks without CashAddr, this is the address itself.
+    pub fn to_legacy_address(&self) -> Result<String, AddressError> {
+        Ok(Self::to_base58check(&self.to_hash()?, &self.format))
+    }
+
+    /// Returns the Base58Check encoding of a given public key hash or script hash.
+    fn to_base58check(hash: &[u8], format: &BitcoinFormat) -> String {
+        let mut address = [0u8; 25];
+        address[0] = N::to_address_prefix(format)[0];
+        address[1..21].copy_from_slice(hash);
+
+        let sum = &checksum(&address[0..21])[0..4];
+        address[21..25].copy_from_slice(sum);
+        address.to_base58()
+    }
+
+    /// Returns a Bech32 or Bech32m address from a given witness program.
+    fn from_witness_program(witness_program: &WitnessProgram, format: &BitcoinFormat) -> Result<Self, AddressError> {
+        Self::check_segwit(format)?;
+        let hrp = String::from_utf8(N::to_address_prefix(&BitcoinFormat::Bech32))?;
+        Ok(Self {
+            address: witness_program.to_address(&hrp)?,
+            format: format.clone(),
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns an error if the given SegWit format is not supported by the network.
+    fn check_segwit(format: &BitcoinFormat) -> Result<(), AddressError> {
+        match N::SEGWIT {
+            true => Ok(()),
+            false => Err(AddressError::IncompatibleFormats(
+                format.to_string(),
+                format!("{} address without segwit support", N::NAME),
+            )),
+        }
+    }
+
+    /// Returns the format of script hash addresses, which are plain P2SH on networks without SegWit.
+    fn script_hash_format() -> BitcoinFormat {
+        match N::SEGWIT {
+            true => BitcoinFormat::P2SH_P2WPKH,
+            false => BitcoinFormat::P2SH,
+        }
+    }
+
     /// Returns the format of the Bitcoin address.
     pub fn format(&self) -> BitcoinFormat {
         self.format.clone()
@@ -158,30 +261,47 @@ impl<N: BitcoinNetwork> FromStr for BitcoinAddress<N> {
             return Err(AddressError::InvalidCharacterLength(address.len()));
         }
 
-        let prefix = &address.to_lowercase()[0..2];
-
-        if let Ok(format) = BitcoinFormat::from_address_prefix(prefix.as_bytes()) {
-            if BitcoinFormat::Bech32 == format {
-                let bech32 = Bech32::from_str(&address)?;
-                if bech32.data().is_empty() {
-                    return Err(AddressError::InvalidAddress(address.to_owned()));
+        // CashAddr addresses are normalized to their lowercase encoding with the network prefix.
+        if let Some(prefix) = N::CASHADDR_PREFIX {
+            if let Ok(cash_address) = CashAddress::from_str(address, prefix) {
+                if cash_address.prefix != prefix {
+                    return Err(AddressError::InvalidPrefix(cash_address.prefix.into_bytes()));
                 }
+                let format = match cash_address.address_type {
+                    CashAddressType::P2PKH => BitcoinFormat::P2PKH,
+                    CashAddressType::P2SH => BitcoinFormat::P2SH,
+                };
+                return Self::from_hash(&cash_address.hash, &format);
+            }
+        }
 
-                let data = bech32.data();
-                let version = data[0].to_u8();
-                let mut program = Vec::from_base32(&data[1..])?;
-
-                let mut data = vec![version, program.len() as u8];
-                data.append(&mut program);
+        // Bech32 addresses are single-case, which distinguishes them from Base58 addresses
+        // that share the same leading characters (e.g. Litecoin `Lt...` and `ltc1...`).
+        let lowercase = address.to_lowercase();
+        let separator = match address == lowercase || address == address.to_uppercase() {
+            true => lowercase.rfind('1'),
+            false => None,
+        };
 
-                // Check that the witness program is valid.
-                let _ = WitnessProgram::new(data.as_slice())?;
+        if let Some(separator) = separator {
+            let prefix = &lowercase[0..separator];
+            if let Ok(BitcoinFormat::Bech32) = BitcoinFormat::from_address_prefix(prefix.as_bytes()) {
+                // Check that the witness program and its checksum variant are valid.
+                let (hrp, witness_program) = WitnessProgram::from_address(address)?;
                 // Check that the address prefix corresponds to the correct network.
                 let _ = N::from_address_prefix(prefix.as_bytes())?;
+                if hrp.as_bytes() != N::to_address_prefix(&BitcoinFormat::Bech32).as_slice() {
+                    return Err(AddressError::InvalidPrefix(hrp.into_bytes()));
+                }
+
+                let format = match (witness_program.version, witness_program.program.len()) {
+                    (0, 32) => BitcoinFormat::P2WSH,
+                    _ => BitcoinFormat::Bech32,
+                };
 
                 return Ok(Self {
                     address: address.to_owned(),
-                    format: BitcoinFormat::Bech32,
+                    format,
                     _network: PhantomData,
                 });
             }
@@ -194,7 +314,16 @@ impl<N: BitcoinNetwork> FromStr for BitcoinAddress<N> {
 
         // Check that the address prefix corresponds to the correct network.
         let _ = N::from_address_prefix(&data[0..2])?;
-        let format = BitcoinFormat::from_address_prefix(&data[0..2])?;
+        let format = match BitcoinFormat::from_address_prefix(&data[0..2])? {
+            BitcoinFormat::Bech32 => return Err(AddressError::InvalidPrefix(data[0..2].to_vec())),
+            BitcoinFormat::P2SH_P2WPKH => Self::script_hash_format(),
+            format => format,
+        };
+
+        // Legacy addresses are converted to CashAddr on networks that use it.
+        if N::CASHADDR_PREFIX.is_some() {
+            return Self::from_hash(&data[1..21], &format);
+        }
 
         Ok(Self {
             address: address.into(),
@@ -864,4 +993,799 @@ mod tests {
         }
     }
 
+    mod p2pkh_litecoin_mainnet {
+        use super::*;
+
+        type N = LitecoinMainnet;
+
+        const KEYPAIRS: [(&str, &str); 3] = [
+            (
+                "T8dNsE4uH287ao1sKRSRrffRJdXPSs2G12YvkQXEq3y2rPjerEDG",
+                "LahtypZmxDqgUrE8Ugj4ft3Uw7pzS38jqX",
+            ),
+            (
+                "T6ZbNmP5JnnJzp3c1tyzmmuZaeKA9jN5bfmiv7DFRTQ6m6NXye9L",
+                "LcFpxmnufjHKgJTd1uqMAKmWhoYdZ5KuUn",
+            ),
+            (
+                "T8CPzBC8mj9621XpjX6FTnhbqVZur5G96YLiwoqRubsiDjp3cN8N",
+                "LMgavR26UEvE5Ezj59nJp4qat4C6Cy1Hyt",
+            ),
+        ];
+
+        #[test]
+        fn from_private_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                test_from_private_key(address, &private_key, &BitcoinFormat::P2PKH);
+            });
+        }
+
+        #[test]
+        fn from_public_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                let public_key = BitcoinPublicKey::<N>::from_private_key(&private_key);
+                test_from_public_key(address, &public_key, &BitcoinFormat::P2PKH);
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(_, address)| {
+                test_from_str::<N>(address, &BitcoinFormat::P2PKH);
+            });
+        }
+
+        #[test]
+        fn to_str() {
+            KEYPAIRS.iter().for_each(|(_, expected_address)| {
+                let address = BitcoinAddress::<N>::from_str(expected_address).unwrap();
+                test_to_str(expected_address, &address);
+            });
+        }
+    }
+
+    mod p2sh_p2wpkh_litecoin_mainnet {
+        use super::*;
+
+        type N = LitecoinMainnet;
+
+        const KEYPAIRS: [(&str, &str); 3] = [
+            (
+                "T8dNsE4uH287ao1sKRSRrffRJdXPSs2G12YvkQXEq3y2rPjerEDG",
+                "MAy5Rkqh1rymErixyYvb9vwGLhR926HmwY",
+            ),
+            (
+                "T6ZbNmP5JnnJzp3c1tyzmmuZaeKA9jN5bfmiv7DFRTQ6m6NXye9L",
+                "MQZL7nitVSE42D28ppCmxz5ys39uC2WGx9",
+            ),
+            (
+                "T8CPzBC8mj9621XpjX6FTnhbqVZur5G96YLiwoqRubsiDjp3cN8N",
+                "M83KXmxgkwCNJFWf29dDKMhkUtHH2Yp3Dn",
+            ),
+        ];
+
+        #[test]
+        fn from_private_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                test_from_private_key(address, &private_key, &BitcoinFormat::P2SH_P2WPKH);
+            });
+        }
+
+        #[test]
+        fn from_public_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                let public_key = BitcoinPublicKey::<N>::from_private_key(&private_key);
+                test_from_public_key(address, &public_key, &BitcoinFormat::P2SH_P2WPKH);
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(_, address)| {
+                test_from_str::<N>(address, &BitcoinFormat::P2SH_P2WPKH);
+            });
+        }
+
+        #[test]
+        fn to_str() {
+            KEYPAIRS.iter().for_each(|(_, expected_address)| {
+                let address = BitcoinAddress::<N>::from_str(expected_address).unwrap();
+                test_to_str(expected_address, &address);
+            });
+        }
+    }
+
+    mod bech32_litecoin_mainnet {
+        use super::*;
+
+        type N = LitecoinMainnet;
+
+        const KEYPAIRS: [(&str, &str); 3] = [
+            (
+                "T8dNsE4uH287ao1sKRSRrffRJdXPSs2G12YvkQXEq3y2rPjerEDG",
+                "ltc1q48fvkgjpf7m2fxkle6t5kafwd5edy79uh6ftlx",
+            ),
+            (
+                "T6ZbNmP5JnnJzp3c1tyzmmuZaeKA9jN5bfmiv7DFRTQ6m6NXye9L",
+                "ltc1qht2wq0v65m8c00ehfeayz4qvsnkqwemuhtlkxw",
+            ),
+            (
+                "T8CPzBC8mj9621XpjX6FTnhbqVZur5G96YLiwoqRubsiDjp3cN8N",
+                "ltc1qrtu64m89r90qxvckm3qu7s3k4yfznyhp3fnl6f",
+            ),
+        ];
+
+        #[test]
+        fn from_private_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                test_from_private_key(address, &private_key, &BitcoinFormat::Bech32);
+            });
+        }
+
+        #[test]
+        fn from_public_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                let public_key = BitcoinPublicKey::<N>::from_private_key(&private_key);
+                test_from_public_key(address, &public_key, &BitcoinFormat::Bech32);
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(_, address)| {
+                test_from_str::<N>(address, &BitcoinFormat::Bech32);
+            });
+        }
+
+        #[test]
+        fn to_str() {
+            KEYPAIRS.iter().for_each(|(_, expected_address)| {
+                let address = BitcoinAddress::<N>::from_str(expected_address).unwrap();
+                test_to_str(expected_address, &address);
+            });
+        }
+    }
+
+    mod p2sh_p2wpkh_litecoin_testnet {
+        use super::*;
+
+        type N = LitecoinTestnet;
+
+        const KEYPAIRS: [(&str, &str); 3] = [
+            (
+                "cTA6tPmaJhqmyPrGACJh1dd6z1BV3E74Fro912MCmCSsbFErH9L7",
+                "QPfuJdDzhJgmnKqfAub92w7ZNjUgjTY7wm",
+            ),
+            (
+                "cR6KPw5kLUVyPQszrfrFvjsFG1yFk6SsrW1wAj3DMbswVwtqcDxJ",
+                "QdG9zf7CAsw4Zg8q2AsKqzGGu5DSsWtg1h",
+            ),
+            (
+                "cSj81LtooQrkQcNDaHxWckfHWsE1SSLwMNawCRfPqkMYxbH9csxy",
+                "QLk9QeLzSNuNqidMDWHmCMt3WvLpitBT7C",
+            ),
+        ];
+
+        #[test]
+        fn from_private_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                test_from_private_key(address, &private_key, &BitcoinFormat::P2SH_P2WPKH);
+            });
+        }
+
+        #[test]
+        fn from_public_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                let public_key = BitcoinPublicKey::<N>::from_private_key(&private_key);
+                test_from_public_key(address, &public_key, &BitcoinFormat::P2SH_P2WPKH);
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(_, address)| {
+                test_from_str::<N>(address, &BitcoinFormat::P2SH_P2WPKH);
+            });
+        }
+
+        #[test]
+        fn to_str() {
+            KEYPAIRS.iter().for_each(|(_, expected_address)| {
+                let address = BitcoinAddress::<N>::from_str(expected_address).unwrap();
+                test_to_str(expected_address, &address);
+            });
+        }
+    }
+
+    mod bech32_litecoin_testnet {
+        use super::*;
+
+        type N = LitecoinTestnet;
+
+        const KEYPAIRS: [(&str, &str); 3] = [
+            (
+                "cTA6tPmaJhqmyPrGACJh1dd6z1BV3E74Fro912MCmCSsbFErH9L7",
+                "tltc1q48fvkgjpf7m2fxkle6t5kafwd5edy79uqg2zvv",
+            ),
+            (
+                "cR6KPw5kLUVyPQszrfrFvjsFG1yFk6SsrW1wAj3DMbswVwtqcDxJ",
+                "tltc1qht2wq0v65m8c00ehfeayz4qvsnkqwemuqeul4y",
+            ),
+            (
+                "cSj81LtooQrkQcNDaHxWckfHWsE1SSLwMNawCRfPqkMYxbH9csxy",
+                "tltc1qrtu64m89r90qxvckm3qu7s3k4yfznyhpxmskfr",
+            ),
+        ];
+
+        #[test]
+        fn from_private_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                test_from_private_key(address, &private_key, &BitcoinFormat::Bech32);
+            });
+        }
+
+        #[test]
+        fn from_public_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                let public_key = BitcoinPublicKey::<N>::from_private_key(&private_key);
+                test_from_public_key(address, &public_key, &BitcoinFormat::Bech32);
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(_, address)| {
+                test_from_str::<N>(address, &BitcoinFormat::Bech32);
+            });
+        }
+
+        #[test]
+        fn to_str() {
+            KEYPAIRS.iter().for_each(|(_, expected_address)| {
+                let address = BitcoinAddress::<N>::from_str(expected_address).unwrap();
+                test_to_str(expected_address, &address);
+            });
+        }
+    }
+
+    mod p2pkh_dogecoin_mainnet {
+        use super::*;
+
+        type N = DogecoinMainnet;
+
+        const KEYPAIRS: [(&str, &str); 3] = [
+            (
+                "QUC2aKai8Fde3Un3N4LMXXxepoueRL9B55LagEHXzSQDnS5DFrHm",
+                "DLd3FsCbAyVum3ia38jKwd9Kc3C1aftCva",
+            ),
+            (
+                "QS8F5rttA2HqTVon4XsvSeCo6phR8CUzfiZNqvyYaqqHh8hM8hiB",
+                "DNAyEpRitUwYxVx4aMqcS4sMNiuefcEbDi",
+            ),
+            (
+                "QTm3hGhwcxecUhHzn9zB8ezqMfxApYP4Ab8Nsdbj4zJu9nDsKaWz",
+                "D7bjCTeugzaTMSVAdbna5owRYyZ7SKe8iL",
+            ),
+        ];
+
+        #[test]
+        fn from_private_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                test_from_private_key(address, &private_key, &BitcoinFormat::P2PKH);
+            });
+        }
+
+        #[test]
+        fn from_public_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                let public_key = BitcoinPublicKey::<N>::from_private_key(&private_key);
+                test_from_public_key(address, &public_key, &BitcoinFormat::P2PKH);
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(_, address)| {
+                test_from_str::<N>(address, &BitcoinFormat::P2PKH);
+            });
+        }
+
+        #[test]
+        fn to_str() {
+            KEYPAIRS.iter().for_each(|(_, expected_address)| {
+                let address = BitcoinAddress::<N>::from_str(expected_address).unwrap();
+                test_to_str(expected_address, &address);
+            });
+        }
+    }
+
+    mod p2pkh_dogecoin_testnet {
+        use super::*;
+
+        type N = DogecoinTestnet;
+
+        const KEYPAIRS: [(&str, &str); 3] = [
+            (
+                "ckKN2iVNf1g3vRsoGM6Ubyp5Usani4eWak3Q7w3Pk9xJ9ue5bWQY",
+                "njg6yswW6wxde2Hm4xNnC2jcquaJfiXFtL",
+            ),
+            (
+                "ciFaYFoYgnLFLSuXxpe3X64DktNZQvzLBPGCHdjQLZPN4cEZrkzW",
+                "nmE2xqAdpTQGqUXFcBV4gUTecbHwjec3oH",
+            ),
+            (
+                "cjtP9fcc9ih2MePkgSkJD6rG1jdK7GtPgFqCKLMaphryXFfV6zcC",
+                "nWenvUPpcy3BER4MfRS2LDXinqwQSF8npH",
+            ),
+        ];
+
+        #[test]
+        fn from_private_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                test_from_private_key(address, &private_key, &BitcoinFormat::P2PKH);
+            });
+        }
+
+        #[test]
+        fn from_public_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                let public_key = BitcoinPublicKey::<N>::from_private_key(&private_key);
+                test_from_public_key(address, &public_key, &BitcoinFormat::P2PKH);
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(_, address)| {
+                test_from_str::<N>(address, &BitcoinFormat::P2PKH);
+            });
+        }
+
+        #[test]
+        fn to_str() {
+            KEYPAIRS.iter().for_each(|(_, expected_address)| {
+                let address = BitcoinAddress::<N>::from_str(expected_address).unwrap();
+                test_to_str(expected_address, &address);
+            });
+        }
+    }
+
+    mod p2pkh_bitcoin_cash_mainnet {
+        use super::*;
+
+        type N = BitcoinCashMainnet;
+
+        const KEYPAIRS: [(&str, &str, &str); 3] = [
+            (
+                "L2o7RUmise9WoxNzmnVZeK83Mmt5Nn1NBpeftbthG5nsLWCzSKVg",
+                "bitcoincash:qz5a9jezg98mdfy6ml8fwjm49ekn95nchseh23me8p",
+                "1GUwicFwsZbdE3XyJYjmPryiiuTiK7mZgS",
+            ),
+            (
+                "KzjKw25tuQoiDyQjUG38ZRNBdnfr5eMBnTsU4JahrVDwFCpRZP1J",
+                "bitcoincash:qzadfcpan2nvlpalxa885s25pjzwcpm80s8hf2mwg2",
+                "1J2shZV5b53GRVmTqmr3tJhkVbBML29C1z",
+            ),
+            (
+                "L2N8YRtxNMAVFAtxBt9PFSADtdvbmzFFHLSU61CtLdhYhrCGPfWh",
+                "bitcoincash:qqd0n2hvu5v4uqenzmwyrn6zx653y2vjuymgzh25dh",
+                "13TdfCiGPagApSJZu1o1Y3mpfqpp6oK2GB",
+            ),
+        ];
+
+        #[test]
+        fn from_private_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address, _)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                test_from_private_key(address, &private_key, &BitcoinFormat::P2PKH);
+            });
+        }
+
+        #[test]
+        fn from_public_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address, _)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                let public_key = BitcoinPublicKey::<N>::from_private_key(&private_key);
+                test_from_public_key(address, &public_key, &BitcoinFormat::P2PKH);
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(_, address, _)| {
+                test_from_str::<N>(address, &BitcoinFormat::P2PKH);
+            });
+        }
+
+        #[test]
+        fn from_str_without_prefix() {
+            KEYPAIRS.iter().for_each(|(_, expected_address, _)| {
+                let address = BitcoinAddress::<N>::from_str(&expected_address["bitcoincash:".len()..]).unwrap();
+                assert_eq!(*expected_address, address.to_string());
+            });
+        }
+
+        #[test]
+        fn from_legacy_address() {
+            KEYPAIRS.iter().for_each(|(_, expected_address, legacy_address)| {
+                let address = BitcoinAddress::<N>::from_str(legacy_address).unwrap();
+                assert_eq!(*expected_address, address.to_string());
+            });
+        }
+
+        #[test]
+        fn to_legacy_address() {
+            KEYPAIRS.iter().for_each(|(_, address, expected_legacy_address)| {
+                let address = BitcoinAddress::<N>::from_str(address).unwrap();
+                assert_eq!(*expected_legacy_address, address.to_legacy_address().unwrap());
+            });
+        }
+    }
+
+    mod p2pkh_bitcoin_cash_testnet {
+        use super::*;
+
+        type N = BitcoinCashTestnet;
+
+        const KEYPAIRS: [(&str, &str, &str); 3] = [
+            (
+                "cTA6tPmaJhqmyPrGACJh1dd6z1BV3E74Fro912MCmCSsbFErH9L7",
+                "bchtest:qz5a9jezg98mdfy6ml8fwjm49ekn95nchsa9wkewqa",
+                "mvzu1fLvgb2t1A1b27i9DnC3au4RABq3cj",
+            ),
+            (
+                "cR6KPw5kLUVyPQszrfrFvjsFG1yFk6SsrW1wAj3DMbswVwtqcDxJ",
+                "bchtest:qzadfcpan2nvlpalxa885s25pjzwcpm80sr9ddee0k",
+                "mxYpzca4Q6UXCcF5ZLpRiDv5Man4FEmzkD",
+            ),
+            (
+                "cSj81LtooQrkQcNDaHxWckfHWsE1SSLwMNawCRfPqkMYxbH9csxy",
+                "bchtest:qqd0n2hvu5v4uqenzmwyrn6zx653y2vjuyl6xsgr2t",
+                "mhyaxFoFCc7RbYnBcamPMxz9XqRWzCT9L2",
+            ),
+        ];
+
+        #[test]
+        fn from_private_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address, _)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                test_from_private_key(address, &private_key, &BitcoinFormat::P2PKH);
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(_, address, _)| {
+                test_from_str::<N>(address, &BitcoinFormat::P2PKH);
+            });
+        }
+
+        #[test]
+        fn from_legacy_address() {
+            KEYPAIRS.iter().for_each(|(_, expected_address, legacy_address)| {
+                let address = BitcoinAddress::<N>::from_str(legacy_address).unwrap();
+                assert_eq!(*expected_address, address.to_string());
+            });
+        }
+    }
+
+    mod p2sh_bitcoin_cash_mainnet {
+        use super::*;
+
+        type N = BitcoinCashMainnet;
+
+        // https://github.com/bitcoincashorg/bitcoincash.org/blob/master/spec/cashaddr.md#examples-of-address-translation
+        const ADDRESSES: [(&str, &str); 2] = [
+            (
+                "bitcoincash:ppm2qsznhks23z7629mms6s4cwef74vcwvn0h829pq",
+                "3CWFddi6m4ndiGyKqzYvsFYagqDLPVMTzC",
+            ),
+            (
+                "bitcoincash:pr95sy3j9xwd2ap32xkykttr4cvcu7as4yc93ky28e",
+                "3LDsS579y7sruadqu11beEJoTjdFiFCdX4",
+            ),
+        ];
+
+        #[test]
+        fn from_str() {
+            ADDRESSES.iter().for_each(|(address, _)| {
+                test_from_str::<N>(address, &BitcoinFormat::P2SH);
+            });
+        }
+
+        #[test]
+        fn from_legacy_address() {
+            ADDRESSES.iter().for_each(|(expected_address, legacy_address)| {
+                let address = BitcoinAddress::<N>::from_str(legacy_address).unwrap();
+                assert_eq!(*expected_address, address.to_string());
+            });
+        }
+
+        #[test]
+        fn to_legacy_address() {
+            ADDRESSES.iter().for_each(|(address, expected_legacy_address)| {
+                let address = BitcoinAddress::<N>::from_str(address).unwrap();
+                assert_eq!(*expected_legacy_address, address.to_legacy_address().unwrap());
+            });
+        }
+    }
+
+    mod altcoin_prefixes {
+        use super::*;
+
+        #[test]
+        fn litecoin_legacy_p2sh() {
+            // Legacy Bitcoin-style P2SH prefixes are still accepted on Litecoin.
+            test_from_str::<LitecoinMainnet>("3P14159f73E4gFr7JterCCQh9QjiTjiZrG", &BitcoinFormat::P2SH_P2WPKH);
+            test_from_str::<LitecoinTestnet>("2N9uPseEx9msyRVNnQ4qJmHpqkgmcycmLJL", &BitcoinFormat::P2SH_P2WPKH);
+        }
+
+        #[test]
+        fn litecoin_base58_with_bech32_prefix() {
+            // A Base58 address starting with `LT` must not be mistaken for a `ltc1` Bech32 address.
+            test_from_str::<LitecoinMainnet>("LT111111111111111111111111R1op2WvN", &BitcoinFormat::P2PKH);
+        }
+
+        #[test]
+        fn invalid_network() {
+            assert!(BitcoinAddress::<LitecoinMainnet>::from_str("1GUwicFwsZbdE3XyJYjmPryiiuTiK7mZgS").is_err());
+            assert!(BitcoinAddress::<LitecoinMainnet>::from_str("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4").is_err());
+            assert!(BitcoinAddress::<Mainnet>::from_str("LahtypZmxDqgUrE8Ugj4ft3Uw7pzS38jqX").is_err());
+            assert!(BitcoinAddress::<Mainnet>::from_str("ltc1q48fvkgjpf7m2fxkle6t5kafwd5edy79uh6ftlx").is_err());
+            assert!(BitcoinAddress::<DogecoinMainnet>::from_str("LahtypZmxDqgUrE8Ugj4ft3Uw7pzS38jqX").is_err());
+            assert!(BitcoinAddress::<DogecoinTestnet>::from_str("tltc1q48fvkgjpf7m2fxkle6t5kafwd5edy79uqg2zvv").is_err());
+        }
+
+        #[test]
+        fn dogecoin_segwit() {
+            let private_key =
+                BitcoinPrivateKey::<DogecoinMainnet>::from_str("QUC2aKai8Fde3Un3N4LMXXxepoueRL9B55LagEHXzSQDnS5DFrHm")
+                    .unwrap();
+            assert!(BitcoinAddress::from_private_key(&private_key, &BitcoinFormat::P2SH_P2WPKH).is_err());
+            assert!(BitcoinAddress::from_private_key(&private_key, &BitcoinFormat::Bech32).is_err());
+            assert!(BitcoinAddress::<DogecoinMainnet>::p2wsh(&vec![0x51]).is_err());
+        }
+
+        #[test]
+        fn bitcoin_cash_invalid_network() {
+            assert!(BitcoinAddress::<BitcoinCashMainnet>::from_str("bchtest:qz5a9jezg98mdfy6ml8fwjm49ekn95nchsa9wkewqa").is_err());
+            assert!(BitcoinAddress::<BitcoinCashMainnet>::from_str("mvzu1fLvgb2t1A1b27i9DnC3au4RABq3cj").is_err());
+            assert!(BitcoinAddress::<BitcoinCashMainnet>::from_str("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4").is_err());
+            assert!(BitcoinAddress::<BitcoinCashTestnet>::from_str("bitcoincash:qz5a9jezg98mdfy6ml8fwjm49ekn95nchseh23me8p").is_err());
+        }
+
+        #[test]
+        fn bitcoin_cash_segwit() {
+            let private_key =
+                BitcoinPrivateKey::<BitcoinCashMainnet>::from_str("L2o7RUmise9WoxNzmnVZeK83Mmt5Nn1NBpeftbthG5nsLWCzSKVg")
+                    .unwrap();
+            assert!(BitcoinAddress::from_private_key(&private_key, &BitcoinFormat::P2SH_P2WPKH).is_err());
+            assert!(BitcoinAddress::from_private_key(&private_key, &BitcoinFormat::Bech32).is_err());
+        }
+
+        #[test]
+        fn bitcoin_cash_p2sh_from_script_pub_key() {
+            let script_pub_key = hex::decode("a91476a04053bda0a88bda5177b86a15c3b29f55987387").unwrap();
+            let address = BitcoinAddress::<BitcoinCashMainnet>::from_script_pub_key(&script_pub_key).unwrap();
+            assert_eq!("bitcoincash:ppm2qsznhks23z7629mms6s4cwef74vcwvn0h829pq", address.to_string());
+        }
+
+        #[test]
+        fn dogecoin_p2sh_from_script_pub_key() {
+            let script_pub_key = hex::decode("a914e9c3dd0c07aac76179ebc76a6c78d4d67c6c160a87").unwrap();
+            let address = BitcoinAddress::<DogecoinMainnet>::from_script_pub_key(&script_pub_key).unwrap();
+            assert_eq!("ADkJjvDZB76xadDaj2KGSL34qz7kbr8Soz", address.to_string());
+        }
+    }
+
+    mod segwit_mainnet {
+        use super::*;
+        use crate::transaction::create_script_pub_key;
+
+        type N = Mainnet;
+
+        // https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki#test-vectors-for-v0-v16-native-segregated-witness-addresses
+        const SCRIPTPAIRS: [(&str, &str); 5] = [
+            (
+                "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+                "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+            ),
+            (
+                "5128751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6",
+                "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
+            ),
+            ("6002751e", "BC1SW50QGDZ25J"),
+            (
+                "5210751e76e8199196d454941c45d1b3a323",
+                "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs",
+            ),
+            (
+                "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+            ),
+        ];
+
+        const INVALID: [&str; 11] = [
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd", // Bech32 instead of Bech32m
+            "BC1S0XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ54WELL", // Bech32 instead of Bech32m
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",                    // Bech32m instead of Bech32
+            "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4", // invalid character
+            "BC130XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ7ZWS8R", // invalid witness version
+            "bc1pw5dgrnzv",                                                   // invalid program length
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v8n0nx0muaewav253zgeav", // invalid program length
+            "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P",                        // invalid program length for witness version 0
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v07qwwzcrf", // zero padding of more than 4 bits
+            "bc1gmk9yu",                                                      // empty data section
+            "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq47zagq", // testnet address
+        ];
+
+        #[test]
+        fn from_script_pub_key() {
+            SCRIPTPAIRS.iter().for_each(|(script_pub_key, expected_address)| {
+                let address = BitcoinAddress::<N>::from_script_pub_key(&hex::decode(script_pub_key).unwrap()).unwrap();
+                assert_eq!(expected_address.to_lowercase(), address.to_string());
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            SCRIPTPAIRS.iter().for_each(|(_, address)| {
+                let address = BitcoinAddress::<N>::from_str(address).unwrap();
+                assert_eq!(BitcoinFormat::Bech32, address.format());
+            });
+        }
+
+        #[test]
+        fn to_script_pub_key() {
+            SCRIPTPAIRS.iter().for_each(|(expected_script_pub_key, address)| {
+                let address = BitcoinAddress::<N>::from_str(address).unwrap();
+                let script_pub_key = create_script_pub_key(&address).unwrap();
+                assert_eq!(*expected_script_pub_key, hex::encode(script_pub_key));
+            });
+        }
+
+        #[test]
+        fn from_invalid_address() {
+            INVALID.iter().for_each(|invalid_address| {
+                assert_eq!(true, BitcoinAddress::<N>::from_str(invalid_address).is_err());
+            });
+        }
+    }
+
+    mod segwit_testnet {
+        use super::*;
+        use crate::transaction::create_script_pub_key;
+
+        type N = Testnet;
+
+        // https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki#test-vectors-for-v0-v16-native-segregated-witness-addresses
+        const SCRIPTPAIRS: [(&str, &str, BitcoinFormat); 3] = [
+            (
+                "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                BitcoinFormat::P2WSH,
+            ),
+            (
+                "0020000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+                "tb1qqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesrxh6hy",
+                BitcoinFormat::P2WSH,
+            ),
+            (
+                "5120000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+                "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+                BitcoinFormat::Bech32,
+            ),
+        ];
+
+        const INVALID: [&str; 4] = [
+            "tc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq5zuyut", // invalid hrp
+            "tb1z0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqglt7rf", // Bech32 instead of Bech32m
+            "tb1q0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq24jc47", // Bech32m instead of Bech32
+            "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vpggkg4j", // non-zero padding in 8-to-5 conversion
+        ];
+
+        #[test]
+        fn from_script_pub_key() {
+            SCRIPTPAIRS.iter().for_each(|(script_pub_key, expected_address, expected_format)| {
+                let address = BitcoinAddress::<N>::from_script_pub_key(&hex::decode(script_pub_key).unwrap()).unwrap();
+                assert_eq!(*expected_address, address.to_string());
+                assert_eq!(*expected_format, address.format());
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            SCRIPTPAIRS.iter().for_each(|(_, address, expected_format)| {
+                test_from_str::<N>(address, expected_format);
+            });
+        }
+
+        #[test]
+        fn to_script_pub_key() {
+            SCRIPTPAIRS.iter().for_each(|(expected_script_pub_key, address, _)| {
+                let address = BitcoinAddress::<N>::from_str(address).unwrap();
+                let script_pub_key = create_script_pub_key(&address).unwrap();
+                assert_eq!(*expected_script_pub_key, hex::encode(script_pub_key));
+            });
+        }
+
+        #[test]
+        fn from_invalid_address() {
+            INVALID.iter().for_each(|invalid_address| {
+                assert_eq!(true, BitcoinAddress::<N>::from_str(invalid_address).is_err());
+            });
+        }
+    }
+
+    mod script_pub_key_mainnet {
+        use super::*;
+        use crate::transaction::create_script_pub_key;
+
+        type N = Mainnet;
+
+        const SCRIPTPAIRS: [(&str, &str); 4] = [
+            (
+                "76a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1888ac",
+                "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa",
+            ),
+            (
+                "a914e9c3dd0c07aac76179ebc76a6c78d4d67c6c160a87",
+                "3P14159f73E4gFr7JterCCQh9QjiTjiZrG",
+            ),
+            (
+                "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            ),
+            (
+                "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+                "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3",
+            ),
+        ];
+
+        const NON_ADDRESS: [&str; 3] = [
+            "210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac", // P2PK
+            "6a0b68656c6c6f20776f726c64",                                           // OP_RETURN
+            "51210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f8179851ae", // bare multisig
+        ];
+
+        #[test]
+        fn from_script_pub_key() {
+            SCRIPTPAIRS.iter().for_each(|(script_pub_key, expected_address)| {
+                let address = BitcoinAddress::<N>::from_script_pub_key(&hex::decode(script_pub_key).unwrap()).unwrap();
+                assert_eq!(*expected_address, address.to_string());
+                assert_eq!(BitcoinAddress::<N>::from_str(expected_address).unwrap(), address);
+            });
+        }
+
+        #[test]
+        fn to_script_pub_key() {
+            SCRIPTPAIRS.iter().for_each(|(expected_script_pub_key, address)| {
+                let address = BitcoinAddress::<N>::from_str(address).unwrap();
+                let script_pub_key = create_script_pub_key(&address).unwrap();
+                assert_eq!(*expected_script_pub_key, hex::encode(script_pub_key));
+            });
+        }
+
+        #[test]
+        fn from_non_address_script_pub_key() {
+            NON_ADDRESS.iter().for_each(|script_pub_key| {
+                let script_pub_key = hex::decode(script_pub_key).unwrap();
+                assert!(BitcoinAddress::<N>::from_script_pub_key(&script_pub_key).is_err());
+            });
+        }
+    }
 }
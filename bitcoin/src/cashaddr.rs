@@ -0,0 +1,332 @@
+//!
+//! CashAddr
+//!
+//! This module contains the CashAddr address encoding used by Bitcoin Cash.
+//! https://github.com/bitcoincashorg/bitcoincash.org/blob/master/spec/cashaddr.md
+//!
+//! A CashAddr address consists of a prefix (e.g. `bitcoincash`), a `:` separator, and a base32
+//! payload. The payload is a version byte, the hash, and a 40-bit BCH checksum over the prefix and
+//! the payload. The prefix may be omitted when it is implied by the network.
+//!
+//! The version byte encodes the address type in bits 3-6 and the hash size in bits 0-2:
+//! - Type 0 is pay-to-public-key-hash (P2PKH), encoded with a leading `q` for 20-byte hashes.
+//! - Type 1 is pay-to-script-hash (P2SH), encoded with a leading `p` for 20-byte hashes.
+//!
+
+use wagyu_model::{AddressError, TransactionError};
+use wagyu_model::no_std::*;
+
+use bech32::{u5, FromBase32, ToBase32};
+use core::fmt;
+
+/// The CashAddr character set for encoding.
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// The supported hash sizes, indexed by their size bits in the version byte.
+const HASH_SIZES: [usize; 8] = [20, 24, 28, 32, 40, 48, 56, 64];
+
+#[derive(Debug, Fail, PartialEq, Eq)]
+pub enum CashAddressError {
+    #[fail(display = "invalid address encoding: {}", _0)]
+    InvalidAddressEncoding(String),
+
+    #[fail(display = "invalid address type {}", _0)]
+    InvalidAddressType(u8),
+
+    #[fail(display = "invalid checksum")]
+    InvalidChecksum,
+
+    #[fail(display = "invalid hash length {}", _0)]
+    InvalidHashLength(usize),
+
+    #[fail(display = "invalid prefix {}", _0)]
+    InvalidPrefix(String),
+}
+
+impl From<CashAddressError> for AddressError {
+    fn from(error: CashAddressError) -> Self {
+        AddressError::Crate("CashAddress", format!("{:?}", error))
+    }
+}
+
+impl From<CashAddressError> for TransactionError {
+    fn from(error: CashAddressError) -> Self {
+        TransactionError::Crate("CashAddress", format!("{:?}", error))
+    }
+}
+
+/// Represents the type of a CashAddr address
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CashAddressType {
+    P2PKH = 0,
+    P2SH = 1,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CashAddress {
+    /// The address prefix
+    pub prefix: String,
+    /// The address type
+    pub address_type: CashAddressType,
+    /// The public key hash or script hash
+    pub hash: Vec<u8>,
+}
+
+impl CashAddress {
+    /// Returns a new CashAddr address given a prefix, address type, and hash.
+    pub fn new(prefix: &str, address_type: CashAddressType, hash: &[u8]) -> Result<Self, CashAddressError> {
+        if !HASH_SIZES.contains(&hash.len()) {
+            return Err(CashAddressError::InvalidHashLength(hash.len()));
+        }
+        if prefix.is_empty() || prefix.bytes().any(|byte| !byte.is_ascii_alphanumeric()) {
+            return Err(CashAddressError::InvalidPrefix(prefix.into()));
+        }
+
+        Ok(Self {
+            prefix: prefix.to_lowercase(),
+            address_type,
+            hash: hash.to_vec(),
+        })
+    }
+
+    /// Returns the CashAddr address of the given string. If the address does not include a
+    /// prefix, the given default prefix is used to verify the checksum.
+    pub fn from_str(address: &str, default_prefix: &str) -> Result<Self, CashAddressError> {
+        if address.to_lowercase() != address && address.to_uppercase() != address {
+            return Err(CashAddressError::InvalidAddressEncoding("mixed case".into()));
+        }
+
+        let address = address.to_lowercase();
+        let (prefix, encoded) = match address.rfind(':') {
+            Some(separator) => (&address[..separator], &address[separator + 1..]),
+            None => (default_prefix, address.as_str()),
+        };
+
+        if prefix.is_empty() || prefix.bytes().any(|byte| !byte.is_ascii_alphanumeric()) {
+            return Err(CashAddressError::InvalidPrefix(prefix.into()));
+        }
+        if encoded.len() < 9 {
+            return Err(CashAddressError::InvalidAddressEncoding(format!(
+                "invalid length {}",
+                encoded.len()
+            )));
+        }
+
+        let mut data = Vec::with_capacity(encoded.len());
+        for character in encoded.bytes() {
+            match CHARSET.iter().position(|value| *value == character) {
+                Some(value) => data.push(value as u8),
+                None => {
+                    return Err(CashAddressError::InvalidAddressEncoding(format!(
+                        "invalid character {}",
+                        character as char
+                    )))
+                }
+            }
+        }
+
+        let mut values = prefix_expand(prefix);
+        values.extend_from_slice(&data);
+        if polymod(&values) != 0 {
+            return Err(CashAddressError::InvalidChecksum);
+        }
+
+        data.truncate(data.len() - 8);
+        let data = data
+            .iter()
+            .map(|value| u5::try_from_u8(*value))
+            .collect::<Result<Vec<u5>, _>>()
+            .map_err(|error| CashAddressError::InvalidAddressEncoding(format!("{:?}", error)))?;
+        let payload = Vec::from_base32(&data)
+            .map_err(|error| CashAddressError::InvalidAddressEncoding(format!("{:?}", error)))?;
+        if payload.is_empty() {
+            return Err(CashAddressError::InvalidAddressEncoding("empty payload".into()));
+        }
+
+        let version = payload[0];
+        if version & 0x80 != 0 {
+            return Err(CashAddressError::InvalidAddressEncoding("reserved version bit".into()));
+        }
+
+        let address_type = match version >> 3 {
+            0 => CashAddressType::P2PKH,
+            1 => CashAddressType::P2SH,
+            address_type => return Err(CashAddressError::InvalidAddressType(address_type)),
+        };
+
+        let hash = payload[1..].to_vec();
+        if hash.len() != HASH_SIZES[(version & 0x07) as usize] {
+            return Err(CashAddressError::InvalidHashLength(hash.len()));
+        }
+
+        Self::new(prefix, address_type, &hash)
+    }
+}
+
+impl fmt::Display for CashAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let size = HASH_SIZES.iter().position(|size| *size == self.hash.len()).unwrap_or(0) as u8;
+        let mut payload = vec![(self.address_type as u8) << 3 | size];
+        payload.extend_from_slice(&self.hash);
+        let data = payload.to_base32().iter().map(|value| value.to_u8()).collect::<Vec<u8>>();
+
+        let mut values = prefix_expand(&self.prefix);
+        values.extend_from_slice(&data);
+        values.extend_from_slice(&[0u8; 8]);
+        let checksum = polymod(&values);
+
+        write!(f, "{}:", self.prefix)?;
+        for value in data {
+            write!(f, "{}", CHARSET[value as usize] as char)?;
+        }
+        for i in 0..8 {
+            write!(f, "{}", CHARSET[((checksum >> (5 * (7 - i))) & 0x1f) as usize] as char)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the CashAddr checksum polynomial of the given values.
+/// https://github.com/bitcoincashorg/bitcoincash.org/blob/master/spec/cashaddr.md#checksum
+fn polymod(values: &[u8]) -> u64 {
+    const GENERATOR: [u64; 5] = [0x98f2bc8e61, 0x79b76d99e2, 0xf33e5fb3c4, 0xae2eabe2a8, 0x1e4f43e470];
+
+    let mut checksum: u64 = 1;
+    for value in values {
+        let top = checksum >> 35;
+        checksum = (checksum & 0x07ffffffff) << 5 ^ (*value as u64);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum ^ 1
+}
+
+/// Returns the expanded prefix for the checksum computation.
+fn prefix_expand(prefix: &str) -> Vec<u8> {
+    let mut values: Vec<u8> = prefix.bytes().map(|byte| byte & 0x1f).collect();
+    values.push(0);
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_to_string(prefix: &str, address_type: CashAddressType, hash: &str, expected_address: &str) {
+        let address = CashAddress::new(prefix, address_type, &hex::decode(hash).unwrap()).unwrap();
+        assert_eq!(expected_address, address.to_string());
+    }
+
+    fn test_from_str(prefix: &str, address_type: CashAddressType, hash: &str, address: &str) {
+        let cash_address = CashAddress::from_str(address, "bitcoincash").unwrap();
+        assert_eq!(prefix, cash_address.prefix);
+        assert_eq!(address_type, cash_address.address_type);
+        assert_eq!(hex::decode(hash).unwrap(), cash_address.hash);
+    }
+
+    mod valid_addresses {
+        use super::*;
+
+        // https://github.com/bitcoincashorg/bitcoincash.org/blob/master/spec/cashaddr.md#examples-of-address-translation
+        const ADDRESSES: [(&str, CashAddressType, &str, &str); 7] = [
+            (
+                "bitcoincash",
+                CashAddressType::P2PKH,
+                "F5BF48B397DAE70BE82B3CCA4793F8EB2B6CDAC9",
+                "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2",
+            ),
+            (
+                "bchtest",
+                CashAddressType::P2SH,
+                "F5BF48B397DAE70BE82B3CCA4793F8EB2B6CDAC9",
+                "bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t",
+            ),
+            (
+                "pref",
+                CashAddressType::P2SH,
+                "F5BF48B397DAE70BE82B3CCA4793F8EB2B6CDAC9",
+                "pref:pr6m7j9njldwwzlg9v7v53unlr4jkmx6ey65nvtks5",
+            ),
+            (
+                "bitcoincash",
+                CashAddressType::P2PKH,
+                "7ADBF6C17084BC86C1706827B41A56F5CA32865925E946EA",
+                "bitcoincash:q9adhakpwzztepkpwp5z0dq62m6u5v5xtyj7j3h2ws4mr9g0",
+            ),
+            (
+                "bchtest",
+                CashAddressType::P2SH,
+                "7ADBF6C17084BC86C1706827B41A56F5CA32865925E946EA",
+                "bchtest:p9adhakpwzztepkpwp5z0dq62m6u5v5xtyj7j3h2u94tsynr",
+            ),
+            (
+                "bitcoincash",
+                CashAddressType::P2SH,
+                "3A84F9CF51AAE98A3BB3A78BF16A6183790B18719126325BFC0C075B",
+                "bitcoincash:pgagf7w02x4wnz3mkwnchut2vxphjzccwxgjvvjmlsxqwkc7j9urhp9",
+            ),
+            (
+                "bitcoincash",
+                CashAddressType::P2PKH,
+                "3D92D9FBEB8D4D0C06AF0E00A1D2D68CC6E9B6C2B27BC2CE8B1A67D25EE5A77E",
+                "bitcoincash:qv7e9k0mawx56rqx4u8qpgwj66xvd6dkc2e8hskw3vdx05j7uknhuz2p82p3f",
+            ),
+        ];
+
+        #[test]
+        fn to_string() {
+            ADDRESSES.iter().for_each(|(prefix, address_type, hash, address)| {
+                test_to_string(prefix, *address_type, hash, address);
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            ADDRESSES.iter().for_each(|(prefix, address_type, hash, address)| {
+                test_from_str(prefix, *address_type, hash, address);
+            });
+        }
+
+        #[test]
+        fn from_str_uppercase() {
+            ADDRESSES.iter().for_each(|(prefix, address_type, hash, address)| {
+                test_from_str(prefix, *address_type, hash, &address.to_uppercase());
+            });
+        }
+
+        #[test]
+        fn from_str_without_prefix() {
+            let cash_address = CashAddress::from_str("qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2", "bitcoincash").unwrap();
+            assert_eq!("bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2", cash_address.to_string());
+        }
+    }
+
+    mod invalid_addresses {
+        use super::*;
+
+        const INVALID_ADDRESSES: [&str; 6] = [
+            // Invalid checksum
+            "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg3",
+            // Mixed case
+            "bitcoincash:Qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2",
+            // Prefix does not match the checksum
+            "bchtest:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2",
+            // Missing prefix with a checksum over a different prefix
+            "pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t",
+            // Invalid character
+            "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekgb",
+            // Truncated payload
+            "bitcoincash:qr6m7j9n",
+        ];
+
+        #[test]
+        fn from_str() {
+            INVALID_ADDRESSES.iter().for_each(|address| {
+                assert!(CashAddress::from_str(address, "bitcoincash").is_err());
+            });
+        }
+    }
+}
@@ -15,6 +15,8 @@ pub enum BitcoinFormat {
     P2WSH,
     /// SegWit Pay-to-Witness-Public-Key Hash, e.g. 34AgLJhwXrvmkZS1o5TrcdeevMt22Nar53
     P2SH_P2WPKH,
+    /// Pay-to-Script Hash on networks without SegWit, e.g. bitcoincash:ppm2qsznhks23z7629mms6s4cwef74vcwvn0h829pq
+    P2SH,
     /// Bech32, e.g. bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7k7grplx
     Bech32,
 }
@@ -28,14 +30,18 @@ impl BitcoinFormat {
     }
 
     /// Returns the format of the given address prefix.
+    /// Base58 prefixes are matched on their version byte, and Bech32 prefixes on their full human-readable part.
     pub fn from_address_prefix(prefix: &[u8]) -> Result<Self, AddressError> {
         if prefix.len() < 2 {
             return Err(AddressError::InvalidPrefix(prefix.to_vec()));
         }
+        match prefix {
+            b"bc" | b"tb" | b"ltc" | b"tltc" => return Ok(BitcoinFormat::Bech32),
+            _ => (),
+        };
         match (prefix[0], prefix[1]) {
-            (0x00, _) | (0x6F, _) => Ok(BitcoinFormat::P2PKH),
-            (0x05, _) | (0xC4, _) => Ok(BitcoinFormat::P2SH_P2WPKH),
-            (0x62, 0x63) | (0x74, 0x62) => Ok(BitcoinFormat::Bech32),
+            (0x00, _) | (0x6F, _) | (0x30, _) | (0x1E, _) | (0x71, _) => Ok(BitcoinFormat::P2PKH),
+            (0x05, _) | (0xC4, _) | (0x32, _) | (0x3A, _) | (0x16, _) => Ok(BitcoinFormat::P2SH_P2WPKH),
             _ => return Err(AddressError::InvalidPrefix(prefix.to_vec())),
         }
     }
@@ -44,8 +50,12 @@ impl BitcoinFormat {
     /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
     pub fn from_extended_private_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPrivateKeyError> {
         match prefix[0..4] {
-            [0x04, 0x88, 0xAD, 0xE4] | [0x04, 0x35, 0x83, 0x94] => Ok(BitcoinFormat::P2PKH),
-            [0x04, 0x9D, 0x78, 0x78] | [0x04, 0x4A, 0x4E, 0x28] => Ok(BitcoinFormat::P2SH_P2WPKH),
+            [0x04, 0x88, 0xAD, 0xE4] | [0x04, 0x35, 0x83, 0x94] | [0x01, 0x9D, 0x9C, 0xFE] | [0x02, 0xFA, 0xC3, 0x98] => {
+                Ok(BitcoinFormat::P2PKH)
+            }
+            [0x04, 0x9D, 0x78, 0x78] | [0x04, 0x4A, 0x4E, 0x28] | [0x01, 0xB2, 0x67, 0x92] => {
+                Ok(BitcoinFormat::P2SH_P2WPKH)
+            }
             _ => Err(ExtendedPrivateKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
@@ -54,8 +64,12 @@ impl BitcoinFormat {
     /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
     pub fn from_extended_public_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPublicKeyError> {
         match prefix[0..4] {
-            [0x04, 0x88, 0xB2, 0x1E] | [0x04, 0x35, 0x87, 0xCF] => Ok(BitcoinFormat::P2PKH),
-            [0x04, 0x9D, 0x7C, 0xB2] | [0x04, 0x4A, 0x52, 0x62] => Ok(BitcoinFormat::P2SH_P2WPKH),
+            [0x04, 0x88, 0xB2, 0x1E] | [0x04, 0x35, 0x87, 0xCF] | [0x01, 0x9D, 0xA4, 0x62] | [0x02, 0xFA, 0xCA, 0xFD] => {
+                Ok(BitcoinFormat::P2PKH)
+            }
+            [0x04, 0x9D, 0x7C, 0xB2] | [0x04, 0x4A, 0x52, 0x62] | [0x01, 0xB2, 0x6E, 0xF6] => {
+                Ok(BitcoinFormat::P2SH_P2WPKH)
+            }
             _ => Err(ExtendedPublicKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
@@ -67,6 +81,7 @@ impl fmt::Display for BitcoinFormat {
             BitcoinFormat::P2PKH => write!(f, "p2pkh"),
             BitcoinFormat::P2WSH => write!(f, "p2wsh"),
             BitcoinFormat::P2SH_P2WPKH => write!(f, "p2sh_p2wpkh"),
+            BitcoinFormat::P2SH => write!(f, "p2sh"),
             BitcoinFormat::Bech32 => write!(f, "bech32"),
         }
     }
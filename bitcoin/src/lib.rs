@@ -15,6 +15,9 @@ pub use self::address::*;
 pub mod amount;
 pub use self::amount::*;
 
+pub mod cashaddr;
+pub use self::cashaddr::*;
+
 pub mod derivation_path;
 pub use self::derivation_path::*;
 
@@ -39,10 +42,19 @@ pub use self::private_key::*;
 pub mod public_key;
 pub use self::public_key::*;
 
+pub mod script;
+pub use self::script::*;
+
 pub mod transaction;
 pub use self::transaction::*;
 
-mod witness_program;
+#[cfg(feature = "std")]
+pub mod vanity;
+#[cfg(feature = "std")]
+pub use self::vanity::*;
+
+pub mod witness_program;
+pub use self::witness_program::*;
 
 pub mod wordlist;
 pub use self::wordlist::*;
@@ -0,0 +1,107 @@
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use wagyu_model::{
+    AddressError, ChildIndex, ExtendedPrivateKeyError, ExtendedPublicKeyError, Network, NetworkError, PrivateKeyError,
+};
+use wagyu_model::no_std::*;
+
+use core::{fmt, str::FromStr};
+use serde::Serialize;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct LitecoinMainnet;
+
+impl Network for LitecoinMainnet {
+    const NAME: &'static str = "mainnet";
+}
+
+impl BitcoinNetwork for LitecoinMainnet {
+    const HD_COIN_TYPE: ChildIndex = ChildIndex::Hardened(2);
+
+    /// Returns the address prefix of the given network.
+    fn to_address_prefix(format: &BitcoinFormat) -> Vec<u8> {
+        match format {
+            BitcoinFormat::P2PKH => vec![0x30],
+            BitcoinFormat::P2WSH => vec![0x00],
+            BitcoinFormat::P2SH_P2WPKH | BitcoinFormat::P2SH => vec![0x32],
+            BitcoinFormat::Bech32 => vec![0x6C, 0x74, 0x63],
+        }
+    }
+
+    /// Returns the network of the given address prefix.
+    /// Legacy P2SH addresses using the Bitcoin `3` prefix are accepted, but never generated.
+    fn from_address_prefix(prefix: &[u8]) -> Result<Self, AddressError> {
+        match (prefix[0], prefix[1]) {
+            (0x30, _) | (0x32, _) | (0x05, _) | (0x6C, 0x74) => Ok(Self),
+            _ => Err(AddressError::InvalidPrefix(prefix.to_owned())),
+        }
+    }
+
+    /// Returns the wif prefix of the given network.
+    fn to_private_key_prefix() -> u8 {
+        0xB0
+    }
+
+    /// Returns the network of the given wif prefix.
+    fn from_private_key_prefix(prefix: u8) -> Result<Self, PrivateKeyError> {
+        match prefix {
+            0xB0 => Ok(Self),
+            _ => Err(PrivateKeyError::InvalidPrefix(vec![prefix])),
+        }
+    }
+
+    /// Returns the extended private key version bytes of the given network.
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+    fn to_extended_private_key_version_bytes(format: &BitcoinFormat) -> Result<Vec<u8>, ExtendedPrivateKeyError> {
+        match format {
+            BitcoinFormat::P2PKH => Ok(vec![0x01, 0x9D, 0x9C, 0xFE]), // Ltpv
+            BitcoinFormat::P2SH_P2WPKH => Ok(vec![0x01, 0xB2, 0x67, 0x92]), // Mtpv
+            _ => Err(ExtendedPrivateKeyError::UnsupportedFormat(format.to_string())),
+        }
+    }
+
+    /// Returns the network of the given extended private key version bytes.
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+    fn from_extended_private_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPrivateKeyError> {
+        match prefix[0..4] {
+            [0x01, 0x9D, 0x9C, 0xFE] | [0x01, 0xB2, 0x67, 0x92] => Ok(Self),
+            _ => Err(ExtendedPrivateKeyError::InvalidVersionBytes(prefix.to_vec())),
+        }
+    }
+
+    /// Returns the extended public key version bytes of the given network.
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+    fn to_extended_public_key_version_bytes(format: &BitcoinFormat) -> Result<Vec<u8>, ExtendedPublicKeyError> {
+        match format {
+            BitcoinFormat::P2PKH => Ok(vec![0x01, 0x9D, 0xA4, 0x62]), // Ltub
+            BitcoinFormat::P2SH_P2WPKH => Ok(vec![0x01, 0xB2, 0x6E, 0xF6]), // Mtub
+            _ => Err(ExtendedPublicKeyError::UnsupportedFormat(format.to_string())),
+        }
+    }
+
+    /// Returns the network of the given extended public key version bytes.
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+    fn from_extended_public_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPublicKeyError> {
+        match prefix[0..4] {
+            [0x01, 0x9D, 0xA4, 0x62] | [0x01, 0xB2, 0x6E, 0xF6] => Ok(Self),
+            _ => Err(ExtendedPublicKeyError::InvalidVersionBytes(prefix.to_vec())),
+        }
+    }
+}
+
+impl FromStr for LitecoinMainnet {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            Self::NAME => Ok(Self),
+            _ => Err(NetworkError::InvalidNetwork(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for LitecoinMainnet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Self::NAME)
+    }
+}
@@ -23,7 +23,7 @@ impl BitcoinNetwork for Mainnet {
         match format {
             BitcoinFormat::P2PKH => vec![0x00],
             BitcoinFormat::P2WSH => vec![0x00],
-            BitcoinFormat::P2SH_P2WPKH => vec![0x05],
+            BitcoinFormat::P2SH_P2WPKH | BitcoinFormat::P2SH => vec![0x05],
             BitcoinFormat::Bech32 => vec![0x62, 0x63],
         }
     }
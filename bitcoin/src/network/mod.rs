@@ -4,6 +4,24 @@ use wagyu_model::{
 };
 use wagyu_model::no_std::*;
 
+pub mod bitcoin_cash_mainnet;
+pub use self::bitcoin_cash_mainnet::*;
+
+pub mod bitcoin_cash_testnet;
+pub use self::bitcoin_cash_testnet::*;
+
+pub mod dogecoin_mainnet;
+pub use self::dogecoin_mainnet::*;
+
+pub mod dogecoin_testnet;
+pub use self::dogecoin_testnet::*;
+
+pub mod litecoin_mainnet;
+pub use self::litecoin_mainnet::*;
+
+pub mod litecoin_testnet;
+pub use self::litecoin_testnet::*;
+
 pub mod mainnet;
 pub use self::mainnet::*;
 
@@ -13,6 +31,12 @@ pub use self::testnet::*;
 /// The interface for a Bitcoin network.
 pub trait BitcoinNetwork: Network {
     const HD_COIN_TYPE: ChildIndex;
+    /// Whether the network supports segregated witness outputs.
+    const SEGWIT: bool = true;
+    /// The CashAddr prefix of the network, if addresses are encoded with CashAddr.
+    const CASHADDR_PREFIX: Option<&'static str> = None;
+    /// The fork id committed to in signature hashes, if the network uses SIGHASH_FORKID replay protection.
+    const FORK_ID: Option<u32> = None;
 
     /// Returns the address prefix of the given network.
     fn to_address_prefix(format: &BitcoinFormat) -> Vec<u8>;
@@ -23,7 +23,7 @@ impl BitcoinNetwork for Testnet {
         match format {
             BitcoinFormat::P2PKH => vec![0x6F],
             BitcoinFormat::P2WSH => vec![0x00],
-            BitcoinFormat::P2SH_P2WPKH => vec![0xC4],
+            BitcoinFormat::P2SH_P2WPKH | BitcoinFormat::P2SH => vec![0xC4],
             BitcoinFormat::Bech32 => vec![0x74, 0x62],
         }
     }
@@ -0,0 +1,366 @@
+use crate::transaction::Opcode;
+use crate::witness_program::WitnessProgram;
+use wagyu_model::no_std::*;
+
+use core::fmt;
+
+/// Returns the script push operation of the given data.
+/// https://en.bitcoin.it/wiki/Script#Constants
+pub fn push_data(data: &[u8]) -> Vec<u8> {
+    let mut script = vec![];
+    match data.len() {
+        0..=75 => script.push(data.len() as u8),
+        76..=255 => {
+            script.push(Opcode::OP_PUSHDATA1 as u8);
+            script.push(data.len() as u8);
+        }
+        256..=65535 => {
+            script.push(Opcode::OP_PUSHDATA2 as u8);
+            script.extend(&(data.len() as u16).to_le_bytes());
+        }
+        _ => {
+            script.push(Opcode::OP_PUSHDATA4 as u8);
+            script.extend(&(data.len() as u32).to_le_bytes());
+        }
+    };
+    script.extend(data);
+    script
+}
+
+/// Returns the opcode and pushed data (if any) of the instruction at the given position of the script,
+/// and advances the position to the next instruction.
+fn read_instruction<'a>(script: &'a [u8], position: &mut usize) -> Option<(u8, Option<&'a [u8]>)> {
+    let opcode = *script.get(*position)?;
+    *position += 1;
+
+    let length = match opcode {
+        0x00..=0x4b => opcode as usize,
+        0x4c => {
+            let length = *script.get(*position)? as usize;
+            *position += 1;
+            length
+        }
+        0x4d => {
+            let bytes = script.get(*position..*position + 2)?;
+            *position += 2;
+            u16::from_le_bytes([bytes[0], bytes[1]]) as usize
+        }
+        0x4e => {
+            let bytes = script.get(*position..*position + 4)?;
+            *position += 4;
+            u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize
+        }
+        _ => return Some((opcode, None)),
+    };
+
+    let data = script.get(*position..position.checked_add(length)?)?;
+    *position += length;
+    Some((opcode, Some(data)))
+}
+
+/// Returns `true` if the given bytes have the size and prefix of a serialized public key.
+fn is_public_key(public_key: &[u8]) -> bool {
+    match public_key.len() {
+        33 => public_key[0] == 0x02 || public_key[0] == 0x03,
+        65 => public_key[0] == 0x04,
+        _ => false,
+    }
+}
+
+/// Represents the standard output types of a Bitcoin script public key
+/// https://github.com/bitcoin/bitcoin/blob/master/src/script/solver.h
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[allow(non_camel_case_types)]
+pub enum BitcoinOutputType {
+    /// Pay-to-Pubkey, e.g. <public key> OP_CHECKSIG
+    P2PK(Vec<u8>),
+    /// Pay-to-Pubkey Hash, e.g. OP_DUP OP_HASH160 <20-byte hash> OP_EQUALVERIFY OP_CHECKSIG
+    P2PKH(Vec<u8>),
+    /// Pay-to-Script Hash, e.g. OP_HASH160 <20-byte hash> OP_EQUAL
+    P2SH(Vec<u8>),
+    /// Pay-to-Witness-Public-Key Hash, e.g. OP_0 <20-byte hash>
+    P2WPKH(Vec<u8>),
+    /// Pay-to-Witness-Script Hash, e.g. OP_0 <32-byte hash>
+    P2WSH(Vec<u8>),
+    /// Pay-to-Taproot, e.g. OP_1 <32-byte output key>
+    P2TR(Vec<u8>),
+    /// Bare multisig with the number of required signatures and the public keys,
+    /// e.g. OP_1 <public key> <public key> OP_2 OP_CHECKMULTISIG
+    Multisig(u8, Vec<Vec<u8>>),
+    /// Provably unspendable data carrier, e.g. OP_RETURN <data>
+    NullData(Vec<u8>),
+    /// Witness program with a version that is not yet defined, e.g. OP_2 <2 to 40 bytes>
+    WitnessUnknown(WitnessProgram),
+    /// Any script that does not match a standard template
+    NonStandard,
+}
+
+impl BitcoinOutputType {
+    /// Returns the output type of the given script public key.
+    pub fn from_script_pub_key(script: &[u8]) -> Self {
+        // OP_DUP OP_HASH160 <20-byte hash> OP_EQUALVERIFY OP_CHECKSIG
+        if script.len() == 25
+            && script[0] == Opcode::OP_DUP as u8
+            && script[1] == Opcode::OP_HASH160 as u8
+            && script[2] == 0x14
+            && script[23] == Opcode::OP_EQUALVERIFY as u8
+            && script[24] == Opcode::OP_CHECKSIG as u8
+        {
+            return BitcoinOutputType::P2PKH(script[3..23].to_vec());
+        }
+
+        // OP_HASH160 <20-byte hash> OP_EQUAL
+        if script.len() == 23
+            && script[0] == Opcode::OP_HASH160 as u8
+            && script[1] == 0x14
+            && script[22] == Opcode::OP_EQUAL as u8
+        {
+            return BitcoinOutputType::P2SH(script[2..22].to_vec());
+        }
+
+        // <version> <2 to 40 bytes>
+        if let Ok(witness_program) = WitnessProgram::from_scriptpubkey(script) {
+            return match (witness_program.version, witness_program.program.len()) {
+                (0, 20) => BitcoinOutputType::P2WPKH(witness_program.program),
+                (0, 32) => BitcoinOutputType::P2WSH(witness_program.program),
+                (1, 32) => BitcoinOutputType::P2TR(witness_program.program),
+                (_, _) => BitcoinOutputType::WitnessUnknown(witness_program),
+            };
+        }
+
+        // OP_RETURN <data>
+        if !script.is_empty() && script[0] == Opcode::OP_RETURN as u8 {
+            let mut data = vec![];
+            let mut position = 1;
+            while position < script.len() {
+                match read_instruction(script, &mut position) {
+                    Some((_, Some(push))) => data.extend(push),
+                    // OP_1NEGATE and OP_1 through OP_16 are push operations
+                    Some((0x4f, None)) | Some((0x51..=0x60, None)) => (),
+                    _ => return BitcoinOutputType::NonStandard,
+                }
+            }
+            return BitcoinOutputType::NullData(data);
+        }
+
+        // <public key> OP_CHECKSIG
+        if (script.len() == 35 || script.len() == 67)
+            && script[0] as usize == script.len() - 2
+            && script[script.len() - 1] == Opcode::OP_CHECKSIG as u8
+            && is_public_key(&script[1..script.len() - 1])
+        {
+            return BitcoinOutputType::P2PK(script[1..script.len() - 1].to_vec());
+        }
+
+        // OP_m <public key> ... <public key> OP_n OP_CHECKMULTISIG
+        if script.len() >= 37
+            && (Opcode::OP_1 as u8..=Opcode::OP_16 as u8).contains(&script[0])
+            && (Opcode::OP_1 as u8..=Opcode::OP_16 as u8).contains(&script[script.len() - 2])
+            && script[script.len() - 1] == Opcode::OP_CHECKMULTISIG as u8
+        {
+            let required = script[0] - 0x50;
+            let total = script[script.len() - 2] - 0x50;
+
+            let mut public_keys = vec![];
+            let mut position = 1;
+            while position < script.len() - 2 {
+                match read_instruction(&script[..script.len() - 2], &mut position) {
+                    Some((_, Some(public_key))) if is_public_key(public_key) => public_keys.push(public_key.to_vec()),
+                    _ => return BitcoinOutputType::NonStandard,
+                }
+            }
+
+            if public_keys.len() == total as usize && required <= total {
+                return BitcoinOutputType::Multisig(required, public_keys);
+            }
+        }
+
+        BitcoinOutputType::NonStandard
+    }
+}
+
+impl fmt::Display for BitcoinOutputType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BitcoinOutputType::P2PK(_) => write!(f, "p2pk"),
+            BitcoinOutputType::P2PKH(_) => write!(f, "p2pkh"),
+            BitcoinOutputType::P2SH(_) => write!(f, "p2sh"),
+            BitcoinOutputType::P2WPKH(_) => write!(f, "p2wpkh"),
+            BitcoinOutputType::P2WSH(_) => write!(f, "p2wsh"),
+            BitcoinOutputType::P2TR(_) => write!(f, "p2tr"),
+            BitcoinOutputType::Multisig(_, _) => write!(f, "multisig"),
+            BitcoinOutputType::NullData(_) => write!(f, "nulldata"),
+            BitcoinOutputType::WitnessUnknown(_) => write!(f, "witness_unknown"),
+            BitcoinOutputType::NonStandard => write!(f, "nonstandard"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_from_script_pub_key(script_pub_key: &str, expected_output_type: &BitcoinOutputType) {
+        let output_type = BitcoinOutputType::from_script_pub_key(&hex::decode(script_pub_key).unwrap());
+        assert_eq!(*expected_output_type, output_type);
+    }
+
+    fn test_push_data(data_length: usize, expected_prefix: &[u8]) {
+        let data = vec![0xab; data_length];
+        let script = push_data(&data);
+        assert_eq!(expected_prefix, &script[..expected_prefix.len()]);
+        assert_eq!(data, script[expected_prefix.len()..].to_vec());
+
+        let mut position = 0;
+        let (_, push) = read_instruction(&script, &mut position).unwrap();
+        assert_eq!(Some(&data[..]), push);
+        assert_eq!(script.len(), position);
+    }
+
+    mod standard {
+        use super::*;
+
+        #[test]
+        fn p2pk() {
+            let public_key = "0496b538e853519c726a2c91e61ec11600ae1390813a627c66fb8be7947be63c52da7589379515d4e0a604f8141781e62294721166bf621e73a82cbf2342c858ee";
+            test_from_script_pub_key(
+                &format!("41{}ac", public_key),
+                &BitcoinOutputType::P2PK(hex::decode(public_key).unwrap()),
+            );
+
+            let public_key = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+            test_from_script_pub_key(
+                &format!("21{}ac", public_key),
+                &BitcoinOutputType::P2PK(hex::decode(public_key).unwrap()),
+            );
+        }
+
+        #[test]
+        fn p2pkh() {
+            test_from_script_pub_key(
+                "76a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1888ac",
+                &BitcoinOutputType::P2PKH(hex::decode("62e907b15cbf27d5425399ebf6f0fb50ebb88f18").unwrap()),
+            );
+        }
+
+        #[test]
+        fn p2sh() {
+            test_from_script_pub_key(
+                "a914748284390f9e263a4b766a75d0633c50426eb87587",
+                &BitcoinOutputType::P2SH(hex::decode("748284390f9e263a4b766a75d0633c50426eb875").unwrap()),
+            );
+        }
+
+        #[test]
+        fn p2wpkh() {
+            test_from_script_pub_key(
+                "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+                &BitcoinOutputType::P2WPKH(hex::decode("751e76e8199196d454941c45d1b3a323f1433bd6").unwrap()),
+            );
+        }
+
+        #[test]
+        fn p2wsh() {
+            test_from_script_pub_key(
+                "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+                &BitcoinOutputType::P2WSH(
+                    hex::decode("1863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262").unwrap(),
+                ),
+            );
+        }
+
+        #[test]
+        fn p2tr() {
+            test_from_script_pub_key(
+                "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                &BitcoinOutputType::P2TR(
+                    hex::decode("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap(),
+                ),
+            );
+        }
+
+        #[test]
+        fn multisig() {
+            let public_keys = [
+                "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+                "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            ];
+            test_from_script_pub_key(
+                &format!("5221{}21{}21{}53ae", public_keys[0], public_keys[1], public_keys[2]),
+                &BitcoinOutputType::Multisig(
+                    2,
+                    public_keys.iter().map(|key| hex::decode(key).unwrap()).collect(),
+                ),
+            );
+        }
+
+        #[test]
+        fn null_data() {
+            test_from_script_pub_key("6a", &BitcoinOutputType::NullData(vec![]));
+            test_from_script_pub_key(
+                "6a0b68656c6c6f20776f726c64",
+                &BitcoinOutputType::NullData(b"hello world".to_vec()),
+            );
+            test_from_script_pub_key(
+                "6a4c50000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                &BitcoinOutputType::NullData(vec![0u8; 80]),
+            );
+        }
+
+        #[test]
+        fn witness_unknown() {
+            test_from_script_pub_key(
+                "5210751e76e8199196d454941c45d1b3a323",
+                &BitcoinOutputType::WitnessUnknown(WitnessProgram {
+                    version: 2,
+                    program: hex::decode("751e76e8199196d454941c45d1b3a323").unwrap(),
+                }),
+            );
+            test_from_script_pub_key(
+                "6002751e",
+                &BitcoinOutputType::WitnessUnknown(WitnessProgram {
+                    version: 16,
+                    program: hex::decode("751e").unwrap(),
+                }),
+            );
+        }
+    }
+
+    mod nonstandard {
+        use super::*;
+
+        const NONSTANDARD: [&str; 8] = [
+            "",
+            "76a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1887",                   // P2PKH with OP_EQUAL
+            "0015751e76e8199196d454941c45d1b3a323f1433bd600",                     // version 0 with 21 bytes
+            "6a4c",                                                               // truncated push
+            "6aac",                                                               // OP_RETURN OP_CHECKSIG
+            "21ff79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac", // invalid public key
+            "5121ff79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f8179851ae", // invalid multisig key
+            "53210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f8179851ae", // 3 of 1 multisig
+        ];
+
+        #[test]
+        fn from_script_pub_key() {
+            NONSTANDARD.iter().for_each(|script_pub_key| {
+                test_from_script_pub_key(script_pub_key, &BitcoinOutputType::NonStandard);
+            });
+        }
+    }
+
+    mod push {
+        use super::*;
+
+        #[test]
+        fn push_data() {
+            test_push_data(0, &[0x00]);
+            test_push_data(20, &[0x14]);
+            test_push_data(75, &[0x4b]);
+            test_push_data(76, &[0x4c, 0x4c]);
+            test_push_data(255, &[0x4c, 0xff]);
+            test_push_data(256, &[0x4d, 0x00, 0x01]);
+            test_push_data(65536, &[0x4e, 0x00, 0x00, 0x01, 0x00]);
+        }
+    }
+}
@@ -4,12 +4,11 @@ use crate::format::BitcoinFormat;
 use crate::network::BitcoinNetwork;
 use crate::private_key::BitcoinPrivateKey;
 use crate::public_key::BitcoinPublicKey;
+use crate::script::{push_data, BitcoinOutputType};
 use crate::witness_program::WitnessProgram;
-use wagyu_model::{PrivateKey, Transaction, TransactionError, TransactionId};
+use wagyu_model::{crypto::hash160, AddressError, PrivateKey, Transaction, TransactionError, TransactionId};
 use wagyu_model::no_std::{*, io::Read};
 
-use base58::FromBase58;
-use bech32::{Bech32, FromBase32};
 use core::{fmt, str::FromStr};
 use secp256k1;
 use serde::Serialize;
@@ -94,8 +93,7 @@ impl BitcoinVector {
 pub fn create_script_pub_key<N: BitcoinNetwork>(address: &BitcoinAddress<N>) -> Result<Vec<u8>, TransactionError> {
     match address.format() {
         BitcoinFormat::P2PKH => {
-            let bytes = &address.to_string().from_base58()?;
-            let pub_key_hash = bytes[1..(bytes.len() - 4)].to_vec();
+            let pub_key_hash = address.to_hash()?;
 
             let mut script = vec![];
             script.push(Opcode::OP_DUP as u8);
@@ -107,16 +105,11 @@ pub fn create_script_pub_key<N: BitcoinNetwork>(address: &BitcoinAddress<N>) ->
             Ok(script)
         }
         BitcoinFormat::P2WSH => {
-            let bech32 = Bech32::from_str(&address.to_string())?;
-            let (v, script) = bech32.data().split_at(1);
-            let script = Vec::from_base32(script)?;
-            let mut script_bytes = vec![v[0].to_u8(), script.len() as u8];
-            script_bytes.extend(script);
-            Ok(script_bytes)
+            let (_, witness_program) = WitnessProgram::from_address(&address.to_string())?;
+            Ok(witness_program.to_scriptpubkey())
         }
-        BitcoinFormat::P2SH_P2WPKH => {
-            let script_bytes = &address.to_string().from_base58()?;
-            let script_hash = script_bytes[1..(script_bytes.len() - 4)].to_vec();
+        BitcoinFormat::P2SH_P2WPKH | BitcoinFormat::P2SH => {
+            let script_hash = address.to_hash()?;
 
             let mut script = vec![];
             script.push(Opcode::OP_HASH160 as u8);
@@ -126,17 +119,16 @@ pub fn create_script_pub_key<N: BitcoinNetwork>(address: &BitcoinAddress<N>) ->
             Ok(script)
         }
         BitcoinFormat::Bech32 => {
-            let bech32 = Bech32::from_str(&address.to_string())?;
-            let (v, program) = bech32.data().split_at(1);
-            let program = Vec::from_base32(program)?;
-            let mut program_bytes = vec![v[0].to_u8(), program.len() as u8];
-            program_bytes.extend(program);
-
-            Ok(WitnessProgram::new(&program_bytes)?.to_scriptpubkey())
+            let (_, witness_program) = WitnessProgram::from_address(&address.to_string())?;
+            Ok(witness_program.to_scriptpubkey())
         }
     }
 }
 
+/// The signature hash flag of replay-protected signatures on networks with a fork id
+/// https://github.com/bitcoincashorg/bitcoincash.org/blob/master/spec/replay-protected-sighash.md
+pub const SIGHASH_FORKID: u32 = 0x40;
+
 /// Represents a Bitcoin signature hash
 /// https://en.bitcoin.it/wiki/OP_CHECKSIG
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
@@ -177,15 +169,30 @@ impl fmt::Display for SignatureHash {
 }
 
 impl SignatureHash {
-    fn from_byte(byte: &u8) -> Self {
-        match byte {
-            0x01 => SignatureHash::SIGHASH_ALL,
-            0x02 => SignatureHash::SIGHASH_NONE,
-            0x03 => SignatureHash::SIGHASH_SINGLE,
-            0x81 => SignatureHash::SIGHASH_ALL_SIGHASH_ANYONECANPAY,
-            0x82 => SignatureHash::SIGHASH_NONE_SIGHASH_ANYONECANPAY,
-            0x83 => SignatureHash::SIGHASH_SINGLE_SIGHASH_ANYONECANPAY,
-            _ => SignatureHash::SIGHASH_ALL,
+    /// Returns the signature hash of the given byte, which must set SIGHASH_FORKID
+    /// exactly when the network uses replay protection.
+    fn from_byte<N: BitcoinNetwork>(byte: &u8) -> Result<Self, TransactionError> {
+        let fork_id = byte & SIGHASH_FORKID as u8 != 0;
+        if fork_id != N::FORK_ID.is_some() {
+            return Err(TransactionError::InvalidSignatureHash(*byte));
+        }
+
+        match byte & !(SIGHASH_FORKID as u8) {
+            0x01 => Ok(SignatureHash::SIGHASH_ALL),
+            0x02 => Ok(SignatureHash::SIGHASH_NONE),
+            0x03 => Ok(SignatureHash::SIGHASH_SINGLE),
+            0x81 => Ok(SignatureHash::SIGHASH_ALL_SIGHASH_ANYONECANPAY),
+            0x82 => Ok(SignatureHash::SIGHASH_NONE_SIGHASH_ANYONECANPAY),
+            0x83 => Ok(SignatureHash::SIGHASH_SINGLE_SIGHASH_ANYONECANPAY),
+            _ => Err(TransactionError::InvalidSignatureHash(*byte)),
+        }
+    }
+
+    /// Returns the signature hash of the given pushed data, if it is a DER signature.
+    fn from_signature<N: BitcoinNetwork>(data: &[u8]) -> Result<Option<Self>, TransactionError> {
+        match (data.first(), data.last()) {
+            (Some(0x30), Some(byte)) if data.len() > 1 => Ok(Some(Self::from_byte::<N>(byte)?)),
+            _ => Ok(None),
         }
     }
 }
@@ -194,9 +201,17 @@ impl SignatureHash {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 #[allow(non_camel_case_types)]
 pub enum Opcode {
+    OP_0 = 0x00,
+    OP_PUSHDATA1 = 0x4c,
+    OP_PUSHDATA2 = 0x4d,
+    OP_PUSHDATA4 = 0x4e,
+    OP_1 = 0x51,
+    OP_16 = 0x60,
+    OP_RETURN = 0x6a,
     OP_DUP = 0x76,
     OP_HASH160 = 0xa9,
     OP_CHECKSIG = 0xac,
+    OP_CHECKMULTISIG = 0xae,
     OP_EQUAL = 0x87,
     OP_EQUALVERIFY = 0x88,
 }
@@ -204,9 +219,17 @@ pub enum Opcode {
 impl fmt::Display for Opcode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Opcode::OP_0 => write!(f, "OP_0"),
+            Opcode::OP_PUSHDATA1 => write!(f, "OP_PUSHDATA1"),
+            Opcode::OP_PUSHDATA2 => write!(f, "OP_PUSHDATA2"),
+            Opcode::OP_PUSHDATA4 => write!(f, "OP_PUSHDATA4"),
+            Opcode::OP_1 => write!(f, "OP_1"),
+            Opcode::OP_16 => write!(f, "OP_16"),
+            Opcode::OP_RETURN => write!(f, "OP_RETURN"),
             Opcode::OP_DUP => write!(f, "OP_DUP"),
             Opcode::OP_HASH160 => write!(f, "OP_HASH160"),
             Opcode::OP_CHECKSIG => write!(f, "OP_CHECKSIG"),
+            Opcode::OP_CHECKMULTISIG => write!(f, "OP_CHECKMULTISIG"),
             Opcode::OP_EQUAL => write!(f, "OP_EQUAL"),
             Opcode::OP_EQUALVERIFY => write!(f, "OP_EQUALVERIFY"),
         }
@@ -242,6 +265,16 @@ impl<N: BitcoinNetwork> Outpoint<N> {
     ) -> Result<Self, TransactionError> {
         let (script_pub_key, redeem_script) = match address.clone() {
             Some(address) => {
+                // Networks without SegWit can only spend P2PKH outpoints, and P2SH outpoints with replay protection.
+                let is_fork_id_p2sh = N::FORK_ID.is_some() && address.format() == BitcoinFormat::P2SH;
+                if !N::SEGWIT && address.format() != BitcoinFormat::P2PKH && !is_fork_id_p2sh {
+                    return Err(AddressError::IncompatibleFormats(
+                        address.format().to_string(),
+                        format!("{} input without segwit support", N::NAME),
+                    )
+                    .into());
+                }
+
                 let script_pub_key = script_pub_key.unwrap_or(create_script_pub_key::<N>(&address)?);
                 let redeem_script = match address.format() {
                     BitcoinFormat::P2PKH => match redeem_script {
@@ -272,6 +305,15 @@ impl<N: BitcoinNetwork> Outpoint<N> {
                         },
                         None => return Err(TransactionError::InvalidInputs("P2SH_P2WPKH".into())),
                     },
+                    BitcoinFormat::P2SH => match redeem_script {
+                        Some(redeem_script) => match script_pub_key[0] != Opcode::OP_HASH160 as u8
+                            && script_pub_key[script_pub_key.len() - 1] != Opcode::OP_EQUAL as u8
+                        {
+                            true => return Err(TransactionError::InvalidScriptPubKey("P2SH".into())),
+                            false => Some(redeem_script),
+                        },
+                        None => return Err(TransactionError::InvalidInputs("P2SH".into())),
+                    },
                     BitcoinFormat::Bech32 => match redeem_script.is_some() {
                         true => return Err(TransactionError::InvalidInputs("Bech32".into())),
                         false => None,
@@ -387,10 +429,10 @@ impl<N: BitcoinNetwork> BitcoinTransactionInput<N> {
         reader.read(&mut sequence)?;
 
         let script_sig_len = read_variable_length_integer(&script_sig[..])?;
-        let sighash_code = SignatureHash::from_byte(&match script_sig_len {
-            0 => 0x01,
-            length => script_sig[length],
-        });
+        let sighash_code = match script_sig.get(1..=script_sig_len) {
+            Some(data) => SignatureHash::from_signature::<N>(data)?.unwrap_or(SignatureHash::SIGHASH_ALL),
+            None => SignatureHash::SIGHASH_ALL,
+        };
 
         Ok(Self {
             outpoint,
@@ -461,6 +503,27 @@ impl BitcoinTransactionOutput {
         })
     }
 
+    /// Returns a Bitcoin transaction output that carries the given data with OP_RETURN.
+    pub fn new_data(data: &[u8]) -> Result<Self, TransactionError> {
+        let mut script_pub_key = vec![Opcode::OP_RETURN as u8];
+        script_pub_key.extend(push_data(data));
+
+        Ok(Self {
+            amount: BitcoinAmount::from_satoshi(0)?,
+            script_pub_key,
+        })
+    }
+
+    /// Returns the output type of the public key script.
+    pub fn to_output_type(&self) -> BitcoinOutputType {
+        BitcoinOutputType::from_script_pub_key(&self.script_pub_key)
+    }
+
+    /// Returns the address this output pays to, if the public key script has an address representation.
+    pub fn to_address<N: BitcoinNetwork>(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        BitcoinAddress::<N>::from_script_pub_key(&self.script_pub_key)
+    }
+
     /// Read and output a Bitcoin transaction output
     pub fn read<R: Read>(mut reader: &mut R) -> Result<Self, TransactionError> {
         let mut amount = [0u8; 8];
@@ -557,7 +620,15 @@ impl<N: BitcoinNetwork> BitcoinTransactionParameters<N> {
                 })?;
 
                 if witnesses.len() > 0 {
-                    input.sighash_code = SignatureHash::from_byte(&witnesses[0][&witnesses[0].len() - 1]);
+                    for witness in &witnesses {
+                        let size = read_variable_length_integer(&witness[..])?;
+                        if let Some(sighash_code) =
+                            SignatureHash::from_signature::<N>(&witness[witness.len() - size..])?
+                        {
+                            input.sighash_code = sighash_code;
+                            break;
+                        }
+                    }
                     input.is_signed = true;
                 }
                 input.witnesses = witnesses;
@@ -619,13 +690,21 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
                     let c_address = BitcoinAddress::<N>::p2wsh(&input_script)?;
                     address == &c_address
                 },
+                BitcoinFormat::P2SH => {
+                    let input_script = match &input.outpoint.redeem_script {
+                        Some(redeem_script) => redeem_script.clone(),
+                        None => return Err(TransactionError::InvalidInputs("P2SH".into())),
+                    };
+                    address == &BitcoinAddress::<N>::from_hash(&hash160(&input_script), &BitcoinFormat::P2SH)?
+                },
                 _ => address == &private_key.to_address(&address.format())?
             };
 
             if address_is_valid && !transaction.parameters.inputs[vin].is_signed {
                 // Transaction hash
-                let preimage = match &address.format() {
-                    BitcoinFormat::P2PKH => transaction.p2pkh_hash_preimage(vin, input.sighash_code)?,
+                let preimage = match (&address.format(), N::FORK_ID) {
+                    (_, Some(_)) => transaction.fork_id_hash_preimage(vin, input.sighash_code)?,
+                    (BitcoinFormat::P2PKH, None) => transaction.p2pkh_hash_preimage(vin, input.sighash_code)?,
                     _ => transaction.segwit_hash_preimage(vin, input.sighash_code)?,
                 };
                 let transaction_hash = Sha256::digest(&Sha256::digest(&preimage));
@@ -636,7 +715,11 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
                         &private_key.to_secp256k1_secret_key(),
                     );
                 let mut signature = signature.serialize_der().as_ref().to_vec();
-                signature.push((input.sighash_code as u32).to_le_bytes()[0]);
+                let sighash_flag = match N::FORK_ID {
+                    Some(_) => input.sighash_code as u32 | SIGHASH_FORKID,
+                    None => input.sighash_code as u32,
+                };
+                signature.push(sighash_flag.to_le_bytes()[0]);
                 let signature = [variable_length_integer(signature.len() as u64)?, signature].concat();
 
                 // Public key
@@ -685,6 +768,38 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
                             .append(&mut witness_field);
                         transaction.parameters.inputs[vin].is_signed = true;
                     }
+                    BitcoinFormat::P2SH => {
+                        let input_script = match &input.outpoint.redeem_script {
+                            Some(redeem_script) => redeem_script.clone(),
+                            None => return Err(TransactionError::InvalidInputs("P2SH".into())),
+                        };
+
+                        // OP_CHECKMULTISIG pops an extra stack element
+                        let mut script_sig = vec![];
+                        if input_script.last() == Some(&(Opcode::OP_CHECKMULTISIG as u8)) {
+                            script_sig.push(Opcode::OP_0 as u8);
+                        }
+                        // Determine whether to append or prepend other signature(s)
+                        match transaction.parameters.inputs[vin].additional_witness.clone() {
+                            Some((other_signature, true)) => {
+                                script_sig.extend(other_signature);
+                                script_sig.extend(&signature);
+                            }
+                            Some((other_signature, false)) => {
+                                script_sig.extend(&signature);
+                                script_sig.extend(other_signature);
+                            }
+                            None => script_sig.extend(&signature),
+                        };
+                        // Append script args (before the redeem script)
+                        if let Some(script_data) = &transaction.parameters.inputs[vin].witness_script_data {
+                            script_sig.extend(push_data(script_data));
+                        }
+                        script_sig.extend(push_data(&input_script));
+
+                        transaction.parameters.inputs[vin].script_sig = script_sig;
+                        transaction.parameters.inputs[vin].is_signed = true;
+                    }
                     BitcoinFormat::P2SH_P2WPKH => {
                         let input_script = match &input.outpoint.redeem_script {
                             Some(redeem_script) => redeem_script.clone(),
@@ -826,7 +941,7 @@ impl<N: BitcoinNetwork> BitcoinTransaction<N> {
                 Some(redeem_script) => redeem_script[1..].to_vec(),
                 None => return Err(TransactionError::InvalidInputs("P2SH_P2WPKH".into())),
             },
-            BitcoinFormat::P2PKH => return Err(TransactionError::UnsupportedPreimage("P2PKH".into())),
+            format => return Err(TransactionError::UnsupportedPreimage(format.to_string())),
         };
 
         let mut script_code = vec![];
@@ -864,6 +979,87 @@ impl<N: BitcoinNetwork> BitcoinTransaction<N> {
         Ok(preimage)
     }
 
+    /// Return the replay-protected (SIGHASH_FORKID) hash preimage of the raw transaction.
+    /// https://github.com/bitcoincashorg/bitcoincash.org/blob/master/spec/replay-protected-sighash.md
+    pub fn fork_id_hash_preimage(&self, vin: usize, sighash: SignatureHash) -> Result<Vec<u8>, TransactionError> {
+        let fork_id = match N::FORK_ID {
+            Some(fork_id) => fork_id,
+            None => return Err(TransactionError::UnsupportedPreimage(N::NAME.into())),
+        };
+
+        let anyone_can_pay = sighash as u32 & 0x80 != 0;
+        let base_sighash = sighash as u32 & 0x1f;
+        let (sighash_none, sighash_single) = (SignatureHash::SIGHASH_NONE as u32, SignatureHash::SIGHASH_SINGLE as u32);
+
+        let mut prev_outputs = vec![];
+        let mut prev_sequences = vec![];
+        for input in &self.parameters.inputs {
+            prev_outputs.extend(&input.outpoint.reverse_transaction_id);
+            prev_outputs.extend(&input.outpoint.index.to_le_bytes());
+            prev_sequences.extend(&input.sequence);
+        }
+
+        let hash_prev_outputs = match anyone_can_pay {
+            true => vec![0u8; 32],
+            false => Sha256::digest(&Sha256::digest(&prev_outputs)).to_vec(),
+        };
+        let hash_sequence = match anyone_can_pay || base_sighash == sighash_none || base_sighash == sighash_single {
+            true => vec![0u8; 32],
+            false => Sha256::digest(&Sha256::digest(&prev_sequences)).to_vec(),
+        };
+        let hash_outputs = match base_sighash {
+            sighash if sighash == sighash_single && vin < self.parameters.outputs.len() => {
+                Sha256::digest(&Sha256::digest(&self.parameters.outputs[vin].serialize()?)).to_vec()
+            }
+            sighash if sighash == sighash_single || sighash == sighash_none => vec![0u8; 32],
+            _ => {
+                let mut outputs = vec![];
+                for output in &self.parameters.outputs {
+                    outputs.extend(&output.serialize()?);
+                }
+                Sha256::digest(&Sha256::digest(&outputs)).to_vec()
+            }
+        };
+
+        let input = &self.parameters.inputs[vin];
+        let format = match &input.outpoint.address {
+            Some(address) => address.format(),
+            None => return Err(TransactionError::MissingOutpointAddress),
+        };
+
+        let script_code = match format {
+            BitcoinFormat::P2PKH => match &input.outpoint.script_pub_key {
+                Some(script) => script.to_vec(),
+                None => return Err(TransactionError::MissingOutpointScriptPublicKey),
+            },
+            BitcoinFormat::P2SH => match &input.outpoint.redeem_script {
+                Some(redeem_script) => redeem_script.to_vec(),
+                None => return Err(TransactionError::InvalidInputs("P2SH".into())),
+            },
+            format => return Err(TransactionError::UnsupportedPreimage(format.to_string())),
+        };
+        let script_code = [variable_length_integer(script_code.len() as u64)?, script_code].concat();
+        let outpoint_amount = match &input.outpoint.amount {
+            Some(amount) => amount.0.to_le_bytes(),
+            None => return Err(TransactionError::MissingOutpointAmount),
+        };
+
+        let mut preimage = vec![];
+        preimage.extend(&self.parameters.version.to_le_bytes());
+        preimage.extend(hash_prev_outputs);
+        preimage.extend(hash_sequence);
+        preimage.extend(&input.outpoint.reverse_transaction_id);
+        preimage.extend(&input.outpoint.index.to_le_bytes());
+        preimage.extend(&script_code);
+        preimage.extend(&outpoint_amount);
+        preimage.extend(&input.sequence);
+        preimage.extend(hash_outputs);
+        preimage.extend(&self.parameters.lock_time.to_le_bytes());
+        preimage.extend(&(sighash as u32 | SIGHASH_FORKID | fork_id << 8).to_le_bytes());
+
+        Ok(preimage)
+    }
+
     /// Returns the transaction with the traditional serialization (no witness).
     fn to_transaction_bytes_without_witness(&self) -> Result<Vec<u8>, TransactionError> {
         let mut transaction = self.parameters.version.to_le_bytes().to_vec();
@@ -909,7 +1105,7 @@ impl<N: BitcoinNetwork> FromStr for BitcoinTransaction<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Mainnet;
+    use crate::{BitcoinCashMainnet, DogecoinMainnet, LitecoinMainnet, Mainnet};
     use wagyu_model::crypto::hash160;
 
     pub struct TransactionTestCase<'a> {
@@ -1430,7 +1626,7 @@ mod tests {
                 ],
                 outputs: &[
                     Output {
-                        address: "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7k7grplx", // witness version 1
+                        address: "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y", // witness version 1
                         amount: BitcoinAmount(100000000)
                     },
                     Output {
@@ -1516,6 +1712,348 @@ mod tests {
         }
     }
 
+    mod test_valid_altcoin_transactions {
+        use super::*;
+
+        // Litecoin and Dogecoin share the Bitcoin transaction format, so the same keys
+        // and outputs on these networks produce the Bitcoin test transactions byte for byte.
+        const LITECOIN_TRANSACTIONS: [TransactionTestCase; 2] = [
+            TransactionTestCase { // p2pkh to p2pkh - the bitcoinjs P2PKH example with Litecoin keys
+                version: 1,
+                lock_time: 0,
+                inputs: &[
+                    Input {
+                        private_key: "T7kFQq8eJeq6JhQjRZENW33NT1Pdhwpuw95SyJVeMXPckupR26ch",
+                        address_format: BitcoinFormat::P2PKH,
+                        transaction_id: "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d",
+                        index: 0,
+                        redeem_script: None,
+                        script_pub_key: None,
+                        utxo_amount: BitcoinAmount(0),
+                        sequence: None,
+                        sighash_code: SignatureHash::SIGHASH_ALL
+                    },
+                ],
+                outputs: &[
+                    Output {
+                        address: "LKqJxEKxN7SnCEpj3ia3Z9DuAD1HNjh1hx",
+                        amount: BitcoinAmount(12000)
+                    },
+                ],
+                expected_signed_transaction: "01000000019d344070eac3fe6e394a16d06d7704a7d5c0a10eb2a2c16bc98842b7cc20d561000000006b48304502210088828c0bdfcdca68d8ae0caeb6ec62cd3fd5f9b2191848edae33feb533df35d302202e0beadd35e17e7f83a733f5277028a9b453d525553e3f5d2d7a7aa8010a81d60121029f50f51d63b345039a290c94bffd3180c99ed659ff6ea6b1242bca47eb93b59fffffffff01e02e0000000000001976a91406afd46bcdfd22ef94ac122aa11f241244a37ecc88ac00000000",
+                expected_transaction_id: "7a68099c3f338fa61696a3c54404c88491e3b249e85574d6bbba01ac00ae33ff",
+            },
+            TransactionTestCase { // p2sh_p2wpkh to p2pkh - the BIP143 P2SH-P2WPKH example with Litecoin keys
+                version: 1,
+                lock_time: 1170,
+                inputs: &[
+                    Input {
+                        private_key: "6vuhKvZ9fti2EqBzB3eRSkxGRu1f53oW2kRuiBvo6V8ksz9ifBv",
+                        address_format: BitcoinFormat::P2SH_P2WPKH,
+                        transaction_id: "77541aeb3c4dac9260b68f74f44c973081a9d4cb2ebe8038b2d70faa201b6bdb",
+                        index: 1,
+                        redeem_script: None,
+                        script_pub_key: None,
+                        utxo_amount: BitcoinAmount(1000000000),
+                        sequence: Some([0xfe, 0xff, 0xff, 0xff]),
+                        sighash_code: SignatureHash::SIGHASH_ALL
+                    },
+                ],
+                outputs: &[
+                    Output {
+                        address: "LaCvA5Qg6iTsVXM8rYhg4Y2EqoNQJKeoNy",
+                        amount: BitcoinAmount(199996600)
+                    },
+                    Output {
+                        address: "LiJVzXoZAdeZXyt39NAt3mjauFySAL76eC",
+                        amount: BitcoinAmount(800000000)
+                    },
+                ],
+                expected_signed_transaction: "01000000000101db6b1b20aa0fd7b23880be2ecbd4a98130974cf4748fb66092ac4d3ceb1a5477010000001716001479091972186c449eb1ded22b78e40d009bdf0089feffffff02b8b4eb0b000000001976a914a457b684d7f0d539a46a45bbc043f35b59d0d96388ac0008af2f000000001976a914fd270b1ee6abcaea97fea7ad0402e8bd8ad6d77c88ac02473044022047ac8e878352d3ebbde1c94ce3a10d057c24175747116f8288e5d794d12d482f0220217f36a485cae903c713331d877c1f64677e3622ad4010726870540656fe9dcb012103ad1d8e89212f0b92c74d23bb710c00662ad1470198ac48c43f7d6f93a2a2687392040000",
+                expected_transaction_id: "ef48d9d0f595052e0f8cdcf825f7a5e50b6a388a81f206f3f4846e5ecd7a0c23",
+            },
+        ];
+
+        const DOGECOIN_TRANSACTIONS: [TransactionTestCase; 1] = [
+            TransactionTestCase { // p2pkh to p2pkh - the bitcoinjs P2PKH example with Dogecoin keys
+                version: 1,
+                lock_time: 0,
+                inputs: &[
+                    Input {
+                        private_key: "QTJu7veT9tLcmPAuUC8JAuLbyBmtgQwq1Bs6u8FwWupogxCMGKVU",
+                        address_format: BitcoinFormat::P2PKH,
+                        transaction_id: "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d",
+                        index: 0,
+                        redeem_script: None,
+                        script_pub_key: None,
+                        utxo_amount: BitcoinAmount(0),
+                        sequence: None,
+                        sighash_code: SignatureHash::SIGHASH_ALL
+                    },
+                ],
+                outputs: &[
+                    Output {
+                        address: "D5kTEGxmas71USKAcAaJptKjq8NJc6EK8C",
+                        amount: BitcoinAmount(12000)
+                    },
+                ],
+                expected_signed_transaction: "01000000019d344070eac3fe6e394a16d06d7704a7d5c0a10eb2a2c16bc98842b7cc20d561000000006b48304502210088828c0bdfcdca68d8ae0caeb6ec62cd3fd5f9b2191848edae33feb533df35d302202e0beadd35e17e7f83a733f5277028a9b453d525553e3f5d2d7a7aa8010a81d60121029f50f51d63b345039a290c94bffd3180c99ed659ff6ea6b1242bca47eb93b59fffffffff01e02e0000000000001976a91406afd46bcdfd22ef94ac122aa11f241244a37ecc88ac00000000",
+                expected_transaction_id: "7a68099c3f338fa61696a3c54404c88491e3b249e85574d6bbba01ac00ae33ff",
+            },
+        ];
+
+        #[test]
+        fn test_litecoin_transactions() {
+            LITECOIN_TRANSACTIONS.iter().for_each(|transaction| {
+                test_transaction::<LitecoinMainnet>(
+                    transaction.version,
+                    transaction.lock_time,
+                    transaction.inputs.to_vec(),
+                    transaction.outputs.to_vec(),
+                    transaction.expected_signed_transaction,
+                    transaction.expected_transaction_id,
+                );
+            });
+        }
+
+        #[test]
+        fn test_reconstructed_litecoin_transactions() {
+            LITECOIN_TRANSACTIONS.iter().for_each(|transaction| {
+                test_reconstructed_transaction::<LitecoinMainnet>(
+                    transaction.version,
+                    transaction.lock_time,
+                    transaction.inputs.to_vec(),
+                    transaction.outputs.to_vec(),
+                    transaction.expected_signed_transaction,
+                    transaction.expected_transaction_id,
+                );
+            });
+        }
+
+        #[test]
+        fn test_dogecoin_transactions() {
+            DOGECOIN_TRANSACTIONS.iter().for_each(|transaction| {
+                test_transaction::<DogecoinMainnet>(
+                    transaction.version,
+                    transaction.lock_time,
+                    transaction.inputs.to_vec(),
+                    transaction.outputs.to_vec(),
+                    transaction.expected_signed_transaction,
+                    transaction.expected_transaction_id,
+                );
+            });
+        }
+
+        #[test]
+        fn test_reconstructed_dogecoin_transactions() {
+            DOGECOIN_TRANSACTIONS.iter().for_each(|transaction| {
+                test_reconstructed_transaction::<DogecoinMainnet>(
+                    transaction.version,
+                    transaction.lock_time,
+                    transaction.inputs.to_vec(),
+                    transaction.outputs.to_vec(),
+                    transaction.expected_signed_transaction,
+                    transaction.expected_transaction_id,
+                );
+            });
+        }
+
+        #[test]
+        fn test_dogecoin_segwit_input() {
+            let address = BitcoinAddress::<DogecoinMainnet>::from_str("ADkJjvDZB76xadDaj2KGSL34qz7kbr8Soz").unwrap();
+            let transaction_id =
+                hex::decode("61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d").unwrap();
+            let input = BitcoinTransactionInput::<DogecoinMainnet>::new(
+                transaction_id,
+                0,
+                Some(address),
+                Some(BitcoinAmount(0)),
+                Some(vec![0x51]),
+                None,
+                None,
+                SignatureHash::SIGHASH_ALL,
+            );
+            assert!(input.is_err());
+        }
+    }
+
+    mod test_valid_bitcoin_cash_transactions {
+        use super::*;
+
+        const BITCOIN_CASH_TRANSACTIONS: [TransactionTestCase; 2] = [
+            TransactionTestCase { // p2pkh to cashaddr p2pkh and legacy p2sh
+                version: 2,
+                lock_time: 0,
+                inputs: &[
+                    Input {
+                        private_key: "L1uyy5qTuGrVXrmrsvHWHgVzW9kKdrp27wBC7Vs6nZDTF2BRUVwy",
+                        address_format: BitcoinFormat::P2PKH,
+                        transaction_id: "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d",
+                        index: 0,
+                        redeem_script: None,
+                        script_pub_key: None,
+                        utxo_amount: BitcoinAmount(50000),
+                        sequence: None,
+                        sighash_code: SignatureHash::SIGHASH_ALL
+                    },
+                ],
+                outputs: &[
+                    Output {
+                        address: "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2",
+                        amount: BitcoinAmount(40000)
+                    },
+                    Output {
+                        address: "3CWFddi6m4ndiGyKqzYvsFYagqDLPVMTzC",
+                        amount: BitcoinAmount(9000)
+                    },
+                ],
+                expected_signed_transaction: "02000000019d344070eac3fe6e394a16d06d7704a7d5c0a10eb2a2c16bc98842b7cc20d561000000006a47304402203725b5bc6a5716866771170d8705486e86c70367644a2ddbf27743eecd07293902200d52f3693979e57a44df97f286aa76d5024562716bade9ca86f64fe317b23ab84121029f50f51d63b345039a290c94bffd3180c99ed659ff6ea6b1242bca47eb93b59fffffffff02409c0000000000001976a914f5bf48b397dae70be82b3cca4793f8eb2b6cdac988ac282300000000000017a91476a04053bda0a88bda5177b86a15c3b29f5598738700000000",
+                expected_transaction_id: "a1bce9eb8aa8497172d0d97f24ca11ef85340d18b3fc786a838ab0018626b31d",
+            },
+            TransactionTestCase { // p2pkh with SIGHASH_ALL and SIGHASH_SINGLE | SIGHASH_ANYONECANPAY inputs
+                version: 2,
+                lock_time: 600000,
+                inputs: &[
+                    Input {
+                        private_key: "L1uyy5qTuGrVXrmrsvHWHgVzW9kKdrp27wBC7Vs6nZDTF2BRUVwy",
+                        address_format: BitcoinFormat::P2PKH,
+                        transaction_id: "a6fd4a5e2c2ee5a6c53d1a18f5e2b5d7c8f1e0c9b4a3928176655443322110ff",
+                        index: 1,
+                        redeem_script: None,
+                        script_pub_key: None,
+                        utxo_amount: BitcoinAmount(30000),
+                        sequence: None,
+                        sighash_code: SignatureHash::SIGHASH_ALL
+                    },
+                    Input {
+                        private_key: "L1uyy5qTuGrVXrmrsvHWHgVzW9kKdrp27wBC7Vs6nZDTF2BRUVwy",
+                        address_format: BitcoinFormat::P2PKH,
+                        transaction_id: "0f1e2d3c4b5a69788796a5b4c3d2e1f00112233445566778899aabbccddeeff0",
+                        index: 0,
+                        redeem_script: None,
+                        script_pub_key: None,
+                        utxo_amount: BitcoinAmount(20000),
+                        sequence: Some([0xfe, 0xff, 0xff, 0xff]),
+                        sighash_code: SignatureHash::SIGHASH_SINGLE_SIGHASH_ANYONECANPAY
+                    },
+                ],
+                outputs: &[
+                    Output {
+                        address: "1BpEi6DfDAUFd7GtittLSdBeYJvcoaVggu",
+                        amount: BitcoinAmount(25000)
+                    },
+                    Output {
+                        address: "bitcoincash:ppm2qsznhks23z7629mms6s4cwef74vcwvn0h829pq",
+                        amount: BitcoinAmount(24000)
+                    },
+                ],
+                expected_signed_transaction: "0200000002ff102132435465768192a3b4c9e0f1c8d7b5e2f5181a3dc5a6e52e2c5e4afda6010000006b483045022100bbf99cd872cba756d005510636dd4336a9b711d97eb9a41b78f980f42904a14d0220126099dc3c817e4052f5cb17c9b4604d605704bc2c35c6439a00aca792b377094121029f50f51d63b345039a290c94bffd3180c99ed659ff6ea6b1242bca47eb93b59ffffffffff0efdecdbcab9a897867564534231201f0e1d2c3b4a5968778695a4b3c2d1e0f000000006b483045022100f0a3a71a7d7ddc93924c14b230a978a42f930f5f6024f22f372abd570a93905802201af726e1fc679f7bee414bb95aaf45b97da0833b52076a8c61e350002d87f199c321029f50f51d63b345039a290c94bffd3180c99ed659ff6ea6b1242bca47eb93b59ffeffffff02a8610000000000001976a91476a04053bda0a88bda5177b86a15c3b29f55987388acc05d00000000000017a91476a04053bda0a88bda5177b86a15c3b29f55987387c0270900",
+                expected_transaction_id: "312b6f45c49821b5e349c8fcf89f177d44bc1a0e752e683dd031d107852a1e26",
+            },
+        ];
+
+        #[test]
+        fn test_bitcoin_cash_transactions() {
+            BITCOIN_CASH_TRANSACTIONS.iter().for_each(|transaction| {
+                test_transaction::<BitcoinCashMainnet>(
+                    transaction.version,
+                    transaction.lock_time,
+                    transaction.inputs.to_vec(),
+                    transaction.outputs.to_vec(),
+                    transaction.expected_signed_transaction,
+                    transaction.expected_transaction_id,
+                );
+            });
+        }
+
+        #[test]
+        fn test_reconstructed_bitcoin_cash_transactions() {
+            // Raw transactions do not carry the signature hash of unsigned inputs.
+            BITCOIN_CASH_TRANSACTIONS[..1].iter().for_each(|transaction| {
+                test_reconstructed_transaction::<BitcoinCashMainnet>(
+                    transaction.version,
+                    transaction.lock_time,
+                    transaction.inputs.to_vec(),
+                    transaction.outputs.to_vec(),
+                    transaction.expected_signed_transaction,
+                    transaction.expected_transaction_id,
+                );
+            });
+        }
+
+        #[test]
+        fn test_bitcoin_cash_p2sh_transaction() {
+            // 1-of-1 multisig redeem script
+            let redeem_script =
+                hex::decode("5121029f50f51d63b345039a290c94bffd3180c99ed659ff6ea6b1242bca47eb93b59f51ae").unwrap();
+            let address =
+                BitcoinAddress::<BitcoinCashMainnet>::from_str("bitcoincash:pqhgt9mh2hs02f7r2pl2s8rzv66sa0jnuyftyjs4km")
+                    .unwrap();
+            assert_eq!(BitcoinFormat::P2SH, address.format());
+            let transaction_id =
+                hex::decode("3e7d6b2a8f0c1d2e3f405162738495a6b7c8d9eafb0c1d2e3f40516273849500").unwrap();
+            let input = BitcoinTransactionInput::<BitcoinCashMainnet>::new(
+                transaction_id,
+                2,
+                Some(address),
+                Some(BitcoinAmount(100000)),
+                Some(redeem_script),
+                None,
+                None,
+                SignatureHash::SIGHASH_ALL,
+            )
+            .unwrap();
+
+            let recipient =
+                BitcoinAddress::<BitcoinCashMainnet>::from_str("bitcoincash:qprcvtlpvhnpyxhcp4wau8ktg78dzuzktvetlc7g9s")
+                    .unwrap();
+            let output = BitcoinTransactionOutput::new(&recipient, BitcoinAmount(99000)).unwrap();
+
+            let transaction_parameters = BitcoinTransactionParameters::<BitcoinCashMainnet> {
+                version: 1,
+                inputs: vec![input],
+                outputs: vec![output],
+                lock_time: 0,
+                segwit_flag: false,
+            };
+
+            let private_key = BitcoinPrivateKey::from_str("L1uyy5qTuGrVXrmrsvHWHgVzW9kKdrp27wBC7Vs6nZDTF2BRUVwy").unwrap();
+            let transaction = BitcoinTransaction::<BitcoinCashMainnet>::new(&transaction_parameters)
+                .unwrap()
+                .sign(&private_key)
+                .unwrap();
+
+            assert_eq!(
+                "0100000001009584736251403f2e1d0cfbead9c8b7a69584736251403f2e1d0c8f2a6b7d3e020000006f0047304402204b75b9a3eaa96e815c5dc3062b7086c27f45ec9cc71ce3ed57add48b1c10da28022020d8bc16f5614c37c7a153b89d50af683817025fad4cd0f3c1326c796fb5ff1241255121029f50f51d63b345039a290c94bffd3180c99ed659ff6ea6b1242bca47eb93b59f51aeffffffff01b8820100000000001976a91447862fe165e6121af80d5dde1ecb478ed170565b88ac00000000",
+                hex::encode(transaction.to_transaction_bytes().unwrap())
+            );
+            assert_eq!(
+                "1caddf4d83c1493f8b9d8bb7a6f2a0dff11ffd2212599efe553b26d4c771baf2",
+                hex::encode(transaction.to_transaction_id().unwrap().txid)
+            );
+        }
+
+        #[test]
+        fn test_bitcoin_cash_rejects_segwit_input() {
+            let address =
+                BitcoinAddress::<BitcoinCashMainnet>::from_str("bitcoincash:qprcvtlpvhnpyxhcp4wau8ktg78dzuzktvetlc7g9s")
+                    .unwrap();
+            let transaction_id =
+                hex::decode("61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d").unwrap();
+            let input = BitcoinTransactionInput::<BitcoinCashMainnet>::new(
+                transaction_id,
+                0,
+                Some(address),
+                Some(BitcoinAmount(50000)),
+                Some(vec![0x00, 0x14]),
+                None,
+                None,
+                SignatureHash::SIGHASH_ALL,
+            );
+            assert!(input.is_err());
+        }
+    }
+
     mod test_real_mainnet_transactions {
         use super::*;
         type N = Mainnet;
@@ -1960,5 +2498,65 @@ mod tests {
                 assert_eq!(hex::encode(pruned_expected_output), hex::encode(&variable_length_int));
             });
         }
+
+        #[test]
+        fn test_signature_hash_from_byte() {
+            assert_eq!(SignatureHash::SIGHASH_ALL, SignatureHash::from_byte::<Mainnet>(&0x01).unwrap());
+            assert_eq!(
+                SignatureHash::SIGHASH_SINGLE_SIGHASH_ANYONECANPAY,
+                SignatureHash::from_byte::<Mainnet>(&0x83).unwrap()
+            );
+            assert_eq!(SignatureHash::SIGHASH_ALL, SignatureHash::from_byte::<BitcoinCashMainnet>(&0x41).unwrap());
+            assert_eq!(
+                SignatureHash::SIGHASH_NONE_SIGHASH_ANYONECANPAY,
+                SignatureHash::from_byte::<BitcoinCashMainnet>(&0xc2).unwrap()
+            );
+
+            // SIGHASH_FORKID is only valid on networks with replay protection
+            assert!(SignatureHash::from_byte::<Mainnet>(&0x41).is_err());
+            assert!(SignatureHash::from_byte::<BitcoinCashMainnet>(&0x01).is_err());
+
+            // Unknown signature hash types
+            assert!(SignatureHash::from_byte::<Mainnet>(&0x00).is_err());
+            assert!(SignatureHash::from_byte::<Mainnet>(&0x04).is_err());
+            assert!(SignatureHash::from_byte::<BitcoinCashMainnet>(&0x44).is_err());
+        }
+    }
+
+    mod test_outputs {
+        use super::*;
+
+        const DATA_OUTPUTS: [(&[u8], &str); 3] = [
+            (b"", "0000000000000000026a00"),
+            (
+                b"hello world",
+                "00000000000000000d6a0b68656c6c6f20776f726c64",
+            ),
+            (
+                &[0xff; 80],
+                "0000000000000000536a4c50ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            ),
+        ];
+
+        #[test]
+        fn test_new_data() {
+            DATA_OUTPUTS.iter().for_each(|(data, expected_output)| {
+                let output = BitcoinTransactionOutput::new_data(data).unwrap();
+                assert_eq!(*expected_output, hex::encode(output.serialize().unwrap()));
+                assert_eq!(BitcoinOutputType::NullData(data.to_vec()), output.to_output_type());
+                assert!(output.to_address::<Mainnet>().is_err());
+            });
+        }
+
+        #[test]
+        fn test_to_address() {
+            let address = BitcoinAddress::<Mainnet>::from_str("bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0").unwrap();
+            let output = BitcoinTransactionOutput::new(&address, BitcoinAmount(1000)).unwrap();
+            assert_eq!(address, output.to_address::<Mainnet>().unwrap());
+            assert_eq!(
+                BitcoinOutputType::P2TR(hex::decode("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap()),
+                output.to_output_type()
+            );
+        }
     }
 }
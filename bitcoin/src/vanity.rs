@@ -0,0 +1,296 @@
+use crate::address::BitcoinAddress;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use crate::public_key::BitcoinPublicKey;
+use wagyu_model::{vanity_search, Address, PrivateKey, VanityError, VanityProgress};
+
+use core::marker::PhantomData;
+use rand::Rng;
+
+/// The alphabet of Base58 addresses
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The alphabet of Bech32 addresses
+const BECH32_ALPHABET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Represents a search for Bitcoin addresses that begin with a given prefix
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitcoinVanity<N: BitcoinNetwork> {
+    /// The prefix of the addresses
+    prefix: String,
+    /// The format of the addresses
+    format: BitcoinFormat,
+    /// The expected number of keys to try for a match
+    difficulty: f64,
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: BitcoinNetwork> BitcoinVanity<N> {
+    /// Returns a search for addresses of the given format that begin with the given prefix.
+    /// P2PKH and P2SH-P2WPKH prefixes are case sensitive, and Bech32 prefixes are not.
+    pub fn new(prefix: &str, format: &BitcoinFormat) -> Result<Self, VanityError> {
+        let invalid_prefix = |message: &str| VanityError::InvalidPrefix(prefix.into(), message.into());
+
+        if N::CASHADDR_PREFIX.is_some() {
+            return Err(invalid_prefix("CashAddr addresses are not supported"));
+        }
+
+        let (prefix, difficulty) = match format {
+            BitcoinFormat::P2PKH | BitcoinFormat::P2SH_P2WPKH => {
+                let difficulty = base58_difficulty(prefix, &N::to_address_prefix(format)).map_err(invalid_prefix)?;
+                (prefix.to_string(), difficulty)
+            }
+            BitcoinFormat::Bech32 => {
+                let prefix = prefix.to_lowercase();
+                let difficulty = bech32_difficulty(&prefix, &N::to_address_prefix(format)).map_err(invalid_prefix)?;
+                (prefix, difficulty)
+            }
+            BitcoinFormat::P2WSH => return Err(invalid_prefix("P2WSH addresses are derived from scripts")),
+            BitcoinFormat::P2SH => return Err(invalid_prefix("P2SH addresses are derived from scripts")),
+        };
+
+        Ok(Self {
+            prefix,
+            format: format.clone(),
+            difficulty,
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns the expected number of keys to try for a match.
+    pub fn difficulty(&self) -> f64 {
+        self.difficulty
+    }
+
+    /// Returns `true` if the given address begins with the prefix.
+    pub fn is_match(&self, address: &BitcoinAddress<N>) -> bool {
+        address.to_string().starts_with(&self.prefix)
+    }
+
+    /// Returns a private key whose address begins with the prefix, and the number of keys tried.
+    pub fn search<R: Rng, P: FnMut(&VanityProgress)>(
+        &self,
+        rng: &mut R,
+        threads: usize,
+        progress: P,
+    ) -> Result<(BitcoinPrivateKey<N>, u64), VanityError> {
+        let (secret_key, attempts) = vanity_search(rng, None, threads, self.matcher(), progress)?;
+        Ok((
+            BitcoinPrivateKey::from_secp256k1_secret_key(&secret_key, true),
+            attempts,
+        ))
+    }
+
+    /// Returns a partial private key whose public key, added to the given public key,
+    /// has an address that begins with the prefix, and the number of keys tried.
+    /// The owner of the given public key adds the partial private key to their private key
+    /// with `combine` to obtain the private key of the address.
+    pub fn search_with_public_key<R: Rng, P: FnMut(&VanityProgress)>(
+        &self,
+        public_key: &BitcoinPublicKey<N>,
+        rng: &mut R,
+        threads: usize,
+        progress: P,
+    ) -> Result<(BitcoinPrivateKey<N>, u64), VanityError> {
+        let public_key = public_key.to_secp256k1_public_key();
+        let (secret_key, attempts) = vanity_search(rng, Some(&public_key), threads, self.matcher(), progress)?;
+        Ok((
+            BitcoinPrivateKey::from_secp256k1_secret_key(&secret_key, true),
+            attempts,
+        ))
+    }
+
+    /// Returns the sum of the given private key and a partial private key found by a split-key search.
+    /// The sum is compressed, as the search matches the addresses of compressed public keys.
+    pub fn combine(
+        private_key: &BitcoinPrivateKey<N>,
+        partial_private_key: &BitcoinPrivateKey<N>,
+    ) -> Result<BitcoinPrivateKey<N>, VanityError> {
+        let mut secret_key = private_key.to_secp256k1_secret_key();
+        secret_key.tweak_add_assign(&partial_private_key.to_secp256k1_secret_key())?;
+        Ok(BitcoinPrivateKey::from_secp256k1_secret_key(&secret_key, true))
+    }
+
+    /// Returns the sum of the given public key and the public key of a partial private key
+    /// found by a split-key search, which is the public key of the address that was found.
+    pub fn combine_public_key(
+        public_key: &BitcoinPublicKey<N>,
+        partial_private_key: &BitcoinPrivateKey<N>,
+    ) -> Result<BitcoinPublicKey<N>, VanityError> {
+        let public_key = secp256k1::PublicKey::combine(&[
+            public_key.to_secp256k1_public_key(),
+            partial_private_key.to_public_key().to_secp256k1_public_key(),
+        ])?;
+        Ok(BitcoinPublicKey::from_secp256k1_public_key(public_key, true))
+    }
+
+    /// Returns the matcher of compressed public keys whose address begins with the prefix.
+    fn matcher(&self) -> impl Fn(&secp256k1::PublicKey) -> bool + Send + Sync + 'static {
+        let vanity = self.clone();
+        move |public_key: &secp256k1::PublicKey| {
+            let public_key = BitcoinPublicKey::<N>::from_secp256k1_public_key(public_key.clone(), true);
+            match BitcoinAddress::from_public_key(&public_key, &vanity.format) {
+                Ok(address) => vanity.is_match(&address),
+                Err(_) => false,
+            }
+        }
+    }
+}
+
+/// Returns the difficulty of a Base58Check address prefix for the given version bytes.
+/// The payload of the address is the version, a 20-byte hash, and a 4-byte checksum,
+/// and each leading zero byte of the version is encoded as a leading '1'.
+fn base58_difficulty(prefix: &str, version: &[u8]) -> Result<f64, &'static str> {
+    let zeros = version.iter().take_while(|byte| **byte == 0).count();
+    let ones = prefix.chars().take_while(|c| *c == '1').count();
+    let digits = &prefix[ones..];
+
+    match (ones == zeros, digits.is_empty()) {
+        (_, true) if ones <= zeros => return Ok(1.0),
+        (true, false) => (),
+        _ => return Err("the number of leading '1' characters does not match the address version"),
+    };
+
+    let mut value = 0f64;
+    for c in digits.chars() {
+        match BASE58_ALPHABET.find(c) {
+            Some(digit) => value = value * 58.0 + digit as f64,
+            None => return Err("invalid Base58 character"),
+        }
+    }
+
+    // The range of payloads with the version, as integers without the leading zero bytes.
+    let version_value = version[zeros..]
+        .iter()
+        .fold(0f64, |value, byte| value * 256.0 + *byte as f64);
+    let range = 2f64.powi(8 * 24);
+    let (low, high) = (version_value * range, (version_value + 1.0) * range);
+
+    // Sums the payloads of each address length whose leading digits are the prefix.
+    let mut count = 0f64;
+    let mut scale = 1f64;
+    while value * scale < high {
+        count += (high.min((value + 1.0) * scale) - low.max(value * scale)).max(0.0);
+        scale *= 58.0;
+    }
+
+    match count > 0.0 {
+        true => Ok((high - low) / count),
+        false => Err("no address of this format begins with the prefix"),
+    }
+}
+
+/// Returns the difficulty of a Bech32 P2WPKH address prefix for the given human-readable part.
+/// The data of the address is the witness version 0, encoded as 'q', a 20-byte program
+/// in 32 characters, and a 6-character checksum.
+fn bech32_difficulty(prefix: &str, hrp: &[u8]) -> Result<f64, &'static str> {
+    let header = format!("{}1q", String::from_utf8_lossy(hrp));
+    if prefix.len() <= header.len() {
+        return match header.starts_with(prefix) {
+            true => Ok(1.0),
+            false => Err("the prefix does not begin with the network and witness version"),
+        };
+    }
+    if !prefix.starts_with(&header) {
+        return Err("the prefix does not begin with the network and witness version");
+    }
+
+    let data = &prefix[header.len()..];
+    if data.len() > 32 + 6 {
+        return Err("the prefix is longer than the address");
+    }
+    if !data.chars().all(|c| BECH32_ALPHABET.contains(c)) {
+        return Err("invalid Bech32 character");
+    }
+    Ok(32f64.powi(data.len() as i32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use wagyu_model::{PrivateKey, PublicKey};
+
+    fn test_search<N: BitcoinNetwork>(prefix: &str, format: &BitcoinFormat) {
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        let vanity = BitcoinVanity::<N>::new(prefix, format).unwrap();
+        let (private_key, attempts) = vanity.search(&mut rng, 2, |_| ()).unwrap();
+
+        let address = private_key.to_address(format).unwrap();
+        assert!(address.to_string().starts_with(prefix));
+        assert!(attempts > 0);
+    }
+
+    fn test_search_with_public_key<N: BitcoinNetwork>(prefix: &str, format: &BitcoinFormat) {
+        let mut rng = XorShiftRng::seed_from_u64(1);
+        let private_key = BitcoinPrivateKey::<N>::new(&mut rng).unwrap();
+        let public_key = private_key.to_public_key();
+
+        let vanity = BitcoinVanity::<N>::new(prefix, format).unwrap();
+        let (partial_private_key, _) = vanity.search_with_public_key(&public_key, &mut rng, 2, |_| ()).unwrap();
+
+        let combined_public_key = BitcoinVanity::combine_public_key(&public_key, &partial_private_key).unwrap();
+        let private_key = BitcoinVanity::combine(&private_key, &partial_private_key).unwrap();
+        let address = private_key.to_address(format).unwrap();
+        assert!(address.to_string().starts_with(prefix));
+        assert_eq!(address, combined_public_key.to_address(format).unwrap());
+    }
+
+    #[test]
+    fn search() {
+        test_search::<Mainnet>("1Ab", &BitcoinFormat::P2PKH);
+        test_search::<Mainnet>("3Ab", &BitcoinFormat::P2SH_P2WPKH);
+        test_search::<Mainnet>("bc1qa7", &BitcoinFormat::Bech32);
+        test_search::<Testnet>("tb1qa7", &BitcoinFormat::Bech32);
+    }
+
+    #[test]
+    fn search_with_public_key() {
+        test_search_with_public_key::<Mainnet>("1Ab", &BitcoinFormat::P2PKH);
+        test_search_with_public_key::<Mainnet>("bc1qa7", &BitcoinFormat::Bech32);
+    }
+
+    #[test]
+    fn difficulty() {
+        // (prefix, format, difficulty)
+        const DIFFICULTIES: [(&str, BitcoinFormat, f64); 8] = [
+            ("1", BitcoinFormat::P2PKH, 1.0),
+            ("1A", BitcoinFormat::P2PKH, 22.9356),
+            ("1Abc", BitcoinFormat::P2PKH, 77_155.38),
+            ("1z", BitcoinFormat::P2PKH, 1_330.265),
+            ("3", BitcoinFormat::P2SH_P2WPKH, 1.0),
+            ("3Abc", BitcoinFormat::P2SH_P2WPKH, 78_508.98),
+            ("bc1q", BitcoinFormat::Bech32, 1.0),
+            ("bc1qxyz", BitcoinFormat::Bech32, 32_768.0),
+        ];
+
+        DIFFICULTIES.iter().for_each(|(prefix, format, expected_difficulty)| {
+            let difficulty = BitcoinVanity::<Mainnet>::new(prefix, format).unwrap().difficulty();
+            assert!((difficulty - expected_difficulty).abs() / expected_difficulty < 0.0001);
+        });
+    }
+
+    #[test]
+    fn invalid_prefix() {
+        const PREFIXES: [(&str, BitcoinFormat); 8] = [
+            ("1O", BitcoinFormat::P2PKH),
+            ("11A", BitcoinFormat::P2PKH),
+            ("2A", BitcoinFormat::P2PKH),
+            ("1A", BitcoinFormat::P2SH_P2WPKH),
+            ("3z", BitcoinFormat::P2SH_P2WPKH),
+            ("bc1pab", BitcoinFormat::Bech32),
+            ("bc1qb", BitcoinFormat::Bech32),
+            ("tb1q", BitcoinFormat::Bech32),
+        ];
+
+        PREFIXES.iter().for_each(|(prefix, format)| {
+            assert!(BitcoinVanity::<Mainnet>::new(prefix, format).is_err());
+        });
+        assert!(BitcoinVanity::<Mainnet>::new("bc1q", &BitcoinFormat::P2WSH).is_err());
+        assert!(BitcoinVanity::<BitcoinCashMainnet>::new("1A", &BitcoinFormat::P2PKH).is_err());
+    }
+}
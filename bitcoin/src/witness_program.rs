@@ -19,12 +19,28 @@
 //!
 //! If the version byte is 0, but the witness program is neither 20 nor 32 bytes, the script must fail.
 //!
+//! If the version byte is 1, and the witness program is 32 bytes:
+//! - It is interpreted as a pay-to-taproot (P2TR) program, where the program is the x-only output key.
+//!
+//! Version 0 witness programs are encoded as addresses with Bech32 (BIP173), while version 1
+//! and higher witness programs are encoded with Bech32m (BIP350).
+//!
 
 use wagyu_model::{AddressError, TransactionError};
 use wagyu_model::no_std::*;
 
+use bech32::{u5, FromBase32, ToBase32};
 use core::str::FromStr;
 
+/// The Bech32 character set for encoding.
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// The checksum constant of Bech32 (BIP173).
+const BECH32_CONST: u32 = 0x01;
+
+/// The checksum constant of Bech32m (BIP350).
+const BECH32M_CONST: u32 = 0x2bc830a3;
+
 #[derive(Debug, Fail, PartialEq, Eq)]
 pub enum WitnessProgramError {
     #[fail(display = "invalid program length {}", _0)]
@@ -41,6 +57,12 @@ pub enum WitnessProgramError {
 
     #[fail(display = "error decoding program from hex string")]
     ProgramDecodingError,
+
+    #[fail(display = "invalid address checksum for script version {}", _0)]
+    InvalidChecksumForVersion(u8),
+
+    #[fail(display = "invalid address encoding: {}", _0)]
+    InvalidAddressEncoding(String),
 }
 
 impl From<WitnessProgramError> for AddressError {
@@ -55,7 +77,7 @@ impl From<WitnessProgramError> for TransactionError {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct WitnessProgram {
     /// The version byte
     pub version: u8,
@@ -122,6 +144,147 @@ impl WitnessProgram {
         output.extend_from_slice(&self.program);
         output
     }
+
+    /// Returns the witness program of the given scriptpubkey.
+    pub fn from_scriptpubkey(script_pub_key: &[u8]) -> Result<Self, WitnessProgramError> {
+        if script_pub_key.len() < 4 || script_pub_key.len() > 42 {
+            return Err(WitnessProgramError::InvalidProgramLength(script_pub_key.len()));
+        }
+
+        let version = match script_pub_key[0] {
+            0x00 => 0,
+            // OP_1 through OP_16
+            0x51..=0x60 => script_pub_key[0] - 0x50,
+            version => return Err(WitnessProgramError::InvalidVersion(version)),
+        };
+
+        let mut program = vec![version];
+        program.extend_from_slice(&script_pub_key[1..]);
+        Self::new(&program)
+    }
+
+    /// Returns the address of the witness program for the given human-readable part.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki#addresses-for-segregated-witness-outputs
+    pub fn to_address(&self, hrp: &str) -> Result<String, WitnessProgramError> {
+        self.validate()?;
+
+        let version = match u5::try_from_u8(self.version) {
+            Ok(version) => version,
+            Err(_) => return Err(WitnessProgramError::InvalidVersion(self.version)),
+        };
+        let mut data = vec![version];
+        data.extend_from_slice(&self.program.to_base32());
+        let data = data.iter().map(|value| value.to_u8()).collect::<Vec<u8>>();
+
+        let constant = match self.version {
+            0 => BECH32_CONST,
+            _ => BECH32M_CONST,
+        };
+
+        let hrp = hrp.to_lowercase();
+        let mut values = hrp_expand(hrp.as_bytes());
+        values.extend_from_slice(&data);
+        values.extend_from_slice(&[0u8; 6]);
+        let checksum = polymod(&values) ^ constant;
+
+        let mut address = hrp;
+        address.push('1');
+        data.iter().for_each(|value| address.push(CHARSET[*value as usize] as char));
+        (0..6).for_each(|i| address.push(CHARSET[((checksum >> (5 * (5 - i))) & 0x1f) as usize] as char));
+        Ok(address)
+    }
+
+    /// Returns the human-readable part and witness program of the given address.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki#addresses-for-segregated-witness-outputs
+    pub fn from_address(address: &str) -> Result<(String, Self), WitnessProgramError> {
+        if address.len() < 8 || address.len() > 90 {
+            return Err(WitnessProgramError::InvalidAddressEncoding(format!(
+                "invalid length {}",
+                address.len()
+            )));
+        }
+        if address.to_lowercase() != address && address.to_uppercase() != address {
+            return Err(WitnessProgramError::InvalidAddressEncoding("mixed case".into()));
+        }
+
+        let address = address.to_lowercase();
+        let separator = match address.rfind('1') {
+            Some(separator) if separator > 0 && separator + 7 <= address.len() => separator,
+            _ => return Err(WitnessProgramError::InvalidAddressEncoding("invalid separator".into())),
+        };
+        let (hrp, encoded) = (&address[..separator], &address[separator + 1..]);
+
+        if hrp.bytes().any(|byte| byte < 33 || byte > 126) {
+            return Err(WitnessProgramError::InvalidAddressEncoding("invalid prefix".into()));
+        }
+
+        let mut data = Vec::with_capacity(encoded.len());
+        for character in encoded.bytes() {
+            match CHARSET.iter().position(|value| *value == character) {
+                Some(value) => data.push(value as u8),
+                None => {
+                    return Err(WitnessProgramError::InvalidAddressEncoding(format!(
+                        "invalid character {}",
+                        character as char
+                    )))
+                }
+            }
+        }
+
+        let mut values = hrp_expand(hrp.as_bytes());
+        values.extend_from_slice(&data);
+        let constant = polymod(&values);
+
+        data.truncate(data.len() - 6);
+        if data.is_empty() {
+            return Err(WitnessProgramError::InvalidAddressEncoding("empty data".into()));
+        }
+
+        let version = data[0];
+        match (version, constant) {
+            (0, BECH32_CONST) => (),
+            (v, BECH32M_CONST) if v > 0 => (),
+            _ => return Err(WitnessProgramError::InvalidChecksumForVersion(version)),
+        };
+
+        let data = data[1..]
+            .iter()
+            .map(|value| u5::try_from_u8(*value))
+            .collect::<Result<Vec<u5>, _>>()
+            .map_err(|error| WitnessProgramError::InvalidAddressEncoding(format!("{:?}", error)))?;
+        let program = Vec::from_base32(&data)
+            .map_err(|error| WitnessProgramError::InvalidAddressEncoding(format!("{:?}", error)))?;
+
+        let witness_program = Self { version, program };
+        witness_program.validate()?;
+        Ok((hrp.to_owned(), witness_program))
+    }
+}
+
+/// Returns the Bech32 checksum polynomial of the given values.
+/// https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki#checksum
+fn polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+    let mut checksum: u32 = 1;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = (checksum & 0x1ffffff) << 5 ^ (*value as u32);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+/// Returns the expanded human-readable part for the checksum computation.
+fn hrp_expand(hrp: &[u8]) -> Vec<u8> {
+    let mut values: Vec<u8> = hrp.iter().map(|byte| byte >> 5).collect();
+    values.push(0);
+    values.extend(hrp.iter().map(|byte| byte & 0x1f));
+    values
 }
 
 impl FromStr for WitnessProgram {
@@ -0,0 +1,304 @@
+use crate::abi::{AbiError, ParamType, Token};
+use crate::address::EthereumAddress;
+
+use ethereum_types::U256;
+use std::str::FromStr;
+
+/// Returns the ABI encoding of the given tokens, encoded as the members of a tuple.
+pub fn encode(tokens: &[Token]) -> Vec<u8> {
+    let heads: Vec<bool> = tokens.iter().map(is_dynamic).collect();
+    let encodings: Vec<Vec<u8>> = tokens.iter().map(encode_token).collect();
+
+    // Dynamic members are referenced in the head by their offset from the start of the tuple.
+    let head_size: usize = heads
+        .iter()
+        .zip(encodings.iter())
+        .map(|(dynamic, encoding)| if *dynamic { 32 } else { encoding.len() })
+        .sum();
+
+    let mut head = vec![];
+    let mut tail = vec![];
+    for (dynamic, encoding) in heads.into_iter().zip(encodings.into_iter()) {
+        match dynamic {
+            true => {
+                head.extend(encode_word(U256::from(head_size + tail.len())));
+                tail.extend(encoding);
+            }
+            false => head.extend(encoding),
+        }
+    }
+    [head, tail].concat()
+}
+
+/// Returns the tokens of the given types from the given ABI encoding of a tuple.
+pub fn decode(param_types: &[ParamType], data: &[u8]) -> Result<Vec<Token>, AbiError> {
+    let mut tokens = vec![];
+    let mut offset = 0;
+    for param_type in param_types {
+        let token = match param_type.is_dynamic() {
+            true => {
+                let tail = read_usize(data, offset)?;
+                decode_token(param_type, data.get(tail..).ok_or_else(|| out_of_bounds(tail))?)?
+            }
+            false => decode_token(param_type, data.get(offset..).ok_or_else(|| out_of_bounds(offset))?)?,
+        };
+        tokens.push(token);
+        offset += param_type.head_size();
+    }
+    Ok(tokens)
+}
+
+/// Returns `true` if the encoding of the given token is dynamically-sized.
+fn is_dynamic(token: &Token) -> bool {
+    match token {
+        Token::Bytes(_) | Token::String(_) | Token::Array(_) => true,
+        Token::FixedArray(tokens) | Token::Tuple(tokens) => tokens.iter().any(is_dynamic),
+        _ => false,
+    }
+}
+
+/// Returns the ABI encoding of the given token.
+fn encode_token(token: &Token) -> Vec<u8> {
+    match token {
+        Token::Address(address) => {
+            let mut word = [0u8; 32];
+            word[12..].copy_from_slice(&hex::decode(&address.to_string()[2..]).unwrap_or(vec![0u8; 20]));
+            word.to_vec()
+        }
+        Token::Bool(boolean) => encode_word(U256::from(*boolean as u8)),
+        Token::Int(value) | Token::Uint(value) => encode_word(*value),
+        Token::FixedBytes(bytes) => pad_right(bytes),
+        Token::Bytes(bytes) => [encode_word(U256::from(bytes.len())), pad_right(bytes)].concat(),
+        Token::String(string) => [encode_word(U256::from(string.len())), pad_right(string.as_bytes())].concat(),
+        Token::Array(tokens) => [encode_word(U256::from(tokens.len())), encode(tokens)].concat(),
+        Token::FixedArray(tokens) | Token::Tuple(tokens) => encode(tokens),
+    }
+}
+
+/// Returns the token of the given type from the given data, which starts at the encoding of the token.
+fn decode_token(param_type: &ParamType, data: &[u8]) -> Result<Token, AbiError> {
+    let invalid_data = || AbiError::InvalidData(format!("invalid encoding of {}", param_type));
+
+    match param_type {
+        ParamType::Address => {
+            let word = read_word(data, 0)?;
+            match word[..12].iter().all(|byte| *byte == 0) {
+                true => Ok(Token::Address(EthereumAddress::from_str(&hex::encode(&word[12..]))?)),
+                false => Err(invalid_data()),
+            }
+        }
+        ParamType::Bool => match U256::from(read_word(data, 0)?) {
+            value if value.is_zero() => Ok(Token::Bool(false)),
+            value if value == U256::one() => Ok(Token::Bool(true)),
+            _ => Err(invalid_data()),
+        },
+        ParamType::Int(_) => {
+            let token = Token::Int(U256::from(read_word(data, 0)?));
+            match token.type_check(param_type) {
+                true => Ok(token),
+                false => Err(invalid_data()),
+            }
+        }
+        ParamType::Uint(_) => {
+            let token = Token::Uint(U256::from(read_word(data, 0)?));
+            match token.type_check(param_type) {
+                true => Ok(token),
+                false => Err(invalid_data()),
+            }
+        }
+        ParamType::FixedBytes(length) => {
+            let word = read_word(data, 0)?;
+            match word[*length..].iter().all(|byte| *byte == 0) {
+                true => Ok(Token::FixedBytes(word[..*length].to_vec())),
+                false => Err(invalid_data()),
+            }
+        }
+        ParamType::Bytes => Ok(Token::Bytes(read_bytes(data)?)),
+        ParamType::String => match String::from_utf8(read_bytes(data)?) {
+            Ok(string) => Ok(Token::String(string)),
+            Err(_) => Err(invalid_data()),
+        },
+        ParamType::Array(param_type) => {
+            let length = read_usize(data, 0)?;
+            // Each element occupies at least one word, which bounds the length by the remaining data.
+            if length > (data.len() - 32) / 32 {
+                return Err(invalid_data());
+            }
+            Ok(Token::Array(decode(&vec![*param_type.clone(); length], &data[32..])?))
+        }
+        ParamType::FixedArray(param_type, length) => {
+            if *length > data.len() / 32 {
+                return Err(invalid_data());
+            }
+            Ok(Token::FixedArray(decode(&vec![*param_type.clone(); *length], data)?))
+        }
+        ParamType::Tuple(param_types) => Ok(Token::Tuple(decode(param_types, data)?)),
+    }
+}
+
+/// Returns the 32-byte big-endian encoding of the given value.
+pub(crate) fn encode_word(value: U256) -> Vec<u8> {
+    let mut word = [0u8; 32];
+    value.to_big_endian(&mut word);
+    word.to_vec()
+}
+
+/// Returns the given bytes right-padded with zeros to a multiple of 32 bytes.
+fn pad_right(bytes: &[u8]) -> Vec<u8> {
+    let padding = (32 - bytes.len() % 32) % 32;
+    [bytes, &vec![0u8; padding][..]].concat()
+}
+
+/// Returns the error for a read beyond the end of the data.
+fn out_of_bounds(offset: usize) -> AbiError {
+    AbiError::InvalidData(format!("offset {} is out of bounds", offset))
+}
+
+/// Returns the 32-byte word at the given offset of the data.
+fn read_word(data: &[u8], offset: usize) -> Result<&[u8], AbiError> {
+    match offset.checked_add(32) {
+        Some(end) if end <= data.len() => Ok(&data[offset..end]),
+        _ => Err(out_of_bounds(offset)),
+    }
+}
+
+/// Returns the word at the given offset of the data as a length or an offset.
+fn read_usize(data: &[u8], offset: usize) -> Result<usize, AbiError> {
+    let value = U256::from(read_word(data, offset)?);
+    match value.bits() <= 32 {
+        true => Ok(value.low_u64() as usize),
+        false => Err(AbiError::InvalidData(format!("invalid length or offset {}", value))),
+    }
+}
+
+/// Returns the length-prefixed bytes at the start of the data.
+fn read_bytes(data: &[u8]) -> Result<Vec<u8>, AbiError> {
+    let length = read_usize(data, 0)?;
+    match data.get(32..32 + length) {
+        Some(bytes) => Ok(bytes.to_vec()),
+        None => Err(out_of_bounds(32 + length)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn test_encode(param_types: &[ParamType], tokens: &[Token], expected_encoding: &str) {
+        assert_eq!(expected_encoding, hex::encode(encode(tokens)));
+        tokens
+            .iter()
+            .zip(param_types.iter())
+            .for_each(|(token, param_type)| assert!(token.type_check(param_type)));
+    }
+
+    fn test_decode(param_types: &[ParamType], expected_tokens: &[Token], encoding: &str) {
+        let tokens = decode(param_types, &hex::decode(encoding).unwrap()).unwrap();
+        assert_eq!(expected_tokens, tokens.as_slice());
+    }
+
+    fn to_param_types(param_types: &str) -> Vec<ParamType> {
+        match ParamType::from_str(&format!("({})", param_types)).unwrap() {
+            ParamType::Tuple(param_types) => param_types,
+            _ => unreachable!(),
+        }
+    }
+
+    fn to_tokens(param_types: &[ParamType], arguments: &str) -> Vec<Token> {
+        let arguments: Vec<Value> = serde_json::from_str(arguments).unwrap();
+        param_types
+            .iter()
+            .zip(arguments.iter())
+            .map(|(param_type, argument)| Token::from_json(param_type, argument).unwrap())
+            .collect()
+    }
+
+    /// (types, arguments, encoding)
+    /// https://docs.soliditylang.org/en/latest/abi-spec.html#examples
+    const ENCODINGS: [(&str, &str, &str); 6] = [
+        (
+            "uint32,bool",
+            "[69, true]",
+            "00000000000000000000000000000000000000000000000000000000000000450000000000000000000000000000000000000000000000000000000000000001",
+        ),
+        (
+            "bytes3[2]",
+            r#"[["0x616263", "0x646566"]]"#,
+            "61626300000000000000000000000000000000000000000000000000000000006465660000000000000000000000000000000000000000000000000000000000",
+        ),
+        (
+            "bytes,bool,uint256[]",
+            r#"["0x64617665", true, [1, 2, 3]]"#,
+            "0000000000000000000000000000000000000000000000000000000000000060000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000000464617665000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003",
+        ),
+        (
+            "uint256,uint32[],bytes10,bytes",
+            r#"["0x123", ["0x456", "0x789"], "0x31323334353637383930", "0x48656c6c6f2c20776f726c6421"]"#,
+            "00000000000000000000000000000000000000000000000000000000000001230000000000000000000000000000000000000000000000000000000000000080313233343536373839300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e0000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000004560000000000000000000000000000000000000000000000000000000000000789000000000000000000000000000000000000000000000000000000000000000d48656c6c6f2c20776f726c642100000000000000000000000000000000000000",
+        ),
+        (
+            "uint256[][],string[]",
+            r#"[[[1, 2], [3]], ["one", "two", "three"]]"#,
+            "000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000e000000000000000000000000000000000000000000000000000000000000000036f6e650000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000374776f000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000057468726565000000000000000000000000000000000000000000000000000000",
+        ),
+        (
+            "(uint256,string)[2],int8,(address,bool)",
+            r#"[[[7, "seven"], ["8", ""]], -3, ["0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65", false]]"#,
+            "0000000000000000000000000000000000000000000000000000000000000080fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffd000000000000000000000000b5d590a6abf5e349c1b6c511bc87ceabfb3d7e650000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000c0000000000000000000000000000000000000000000000000000000000000000700000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000005736576656e000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000000",
+        ),
+    ];
+
+    #[test]
+    fn encode_tuples() {
+        ENCODINGS.iter().for_each(|(param_types, arguments, encoding)| {
+            let param_types = to_param_types(param_types);
+            let tokens = to_tokens(&param_types, arguments);
+            test_encode(&param_types, &tokens, encoding);
+        });
+    }
+
+    #[test]
+    fn decode_tuples() {
+        ENCODINGS.iter().for_each(|(param_types, arguments, encoding)| {
+            let param_types = to_param_types(param_types);
+            let tokens = to_tokens(&param_types, arguments);
+            test_decode(&param_types, &tokens, encoding);
+        });
+    }
+
+    #[test]
+    fn decode_invalid_data() {
+        let word = |value: &str| format!("{:0>64}", value);
+        let invalid_encodings = [
+            // Dirty address padding
+            (
+                "address",
+                format!("01{}", &word("b5d590a6abf5e349c1b6c511bc87ceabfb3d7e65")[2..]),
+            ),
+            // Invalid boolean
+            ("bool", word("2")),
+            // Integer out of range
+            ("uint8", word("100")),
+            ("int8", word("80")),
+            // Dirty fixed bytes padding
+            ("bytes1", word("0101")),
+            // Truncated data
+            ("uint256", "00".repeat(31)),
+            ("bytes", format!("{}{}", word("20"), word("4"))),
+            (
+                "string",
+                format!("{}{}{}", word("20"), word("2"), format!("{:0<64}", "ff")),
+            ),
+            // Offset out of bounds
+            ("bytes", word("40")),
+            ("bytes", "ff".repeat(32)),
+            // Array length beyond the data
+            ("uint256[]", format!("{}{}", word("20"), word("ffffffff"))),
+        ];
+        invalid_encodings.iter().for_each(|(param_type, encoding)| {
+            let param_types = [ParamType::from_str(param_type).unwrap()];
+            assert!(decode(&param_types, &hex::decode(encoding).unwrap()).is_err());
+        });
+    }
+}
@@ -0,0 +1,527 @@
+use crate::abi::{decode, encode, split_components, AbiError, ParamType, Token};
+use crate::network::EthereumNetwork;
+use crate::transaction::EthereumTransaction;
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use tiny_keccak::keccak256;
+
+/// Represents a parameter of a function or an event in a JSON ABI
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct AbiParam {
+    /// The name of the parameter
+    #[serde(default)]
+    pub name: String,
+    /// The type of the parameter, where tuples are denoted by `tuple`
+    #[serde(rename = "type")]
+    pub param_type: String,
+    /// The components of a tuple parameter
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<AbiParam>,
+    /// Whether the event parameter is indexed in the log topics
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub indexed: bool,
+}
+
+impl AbiParam {
+    /// Returns the type of the parameter, resolving tuples from their components.
+    pub fn to_param_type(&self) -> Result<ParamType, AbiError> {
+        match self.param_type.starts_with("tuple") {
+            true => {
+                let components = self
+                    .components
+                    .iter()
+                    .map(|component| Ok(component.to_param_type()?.to_string()))
+                    .collect::<Result<Vec<String>, AbiError>>()?;
+                ParamType::from_str(&format!("({}){}", components.join(","), &self.param_type[5..]))
+            }
+            false => ParamType::from_str(&self.param_type),
+        }
+    }
+}
+
+/// Returns the types of the given parameters.
+fn to_param_types(params: &[AbiParam]) -> Result<Vec<ParamType>, AbiError> {
+    params.iter().map(AbiParam::to_param_type).collect()
+}
+
+/// Returns the canonical signature of the given name and parameters, such as `transfer(address,uint256)`.
+fn to_signature(name: &str, params: &[AbiParam]) -> Result<String, AbiError> {
+    let param_types: Vec<String> = to_param_types(params)?.iter().map(ParamType::to_string).collect();
+    Ok(format!("{}({})", name, param_types.join(",")))
+}
+
+/// Represents a contract function
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct AbiFunction {
+    /// The name of the function
+    pub name: String,
+    /// The input parameters of the function
+    #[serde(default)]
+    pub inputs: Vec<AbiParam>,
+    /// The output parameters of the function
+    #[serde(default)]
+    pub outputs: Vec<AbiParam>,
+}
+
+impl AbiFunction {
+    /// Returns a function given its signature, such as `transfer(address,uint256)`.
+    /// The parameters of the returned function are unnamed, and it has no outputs.
+    pub fn from_signature(signature: &str) -> Result<Self, AbiError> {
+        let invalid_signature = || AbiError::InvalidType(signature.into());
+
+        let index = signature.find('(').ok_or_else(invalid_signature)?;
+        let name = signature[..index].trim();
+        if name.is_empty() || !signature.ends_with(')') {
+            return Err(invalid_signature());
+        }
+
+        let parameters = &signature[index + 1..signature.len() - 1];
+        let inputs = match parameters.is_empty() {
+            true => vec![],
+            false => split_components(parameters)
+                .ok_or_else(invalid_signature)?
+                .iter()
+                .map(|param_type| {
+                    Ok(AbiParam {
+                        name: String::new(),
+                        param_type: ParamType::from_str(param_type.trim())?.to_string(),
+                        components: vec![],
+                        indexed: false,
+                    })
+                })
+                .collect::<Result<_, AbiError>>()?,
+        };
+
+        Ok(Self {
+            name: name.into(),
+            inputs,
+            outputs: vec![],
+        })
+    }
+
+    /// Returns the canonical signature of the function.
+    pub fn signature(&self) -> Result<String, AbiError> {
+        to_signature(&self.name, &self.inputs)
+    }
+
+    /// Returns the function selector, the first 4 bytes of the hash of the signature.
+    pub fn selector(&self) -> Result<[u8; 4], AbiError> {
+        let mut selector = [0u8; 4];
+        selector.copy_from_slice(&keccak256(self.signature()?.as_bytes())[..4]);
+        Ok(selector)
+    }
+
+    /// Returns the calldata of a call to the function with the given arguments.
+    pub fn encode_input(&self, tokens: &[Token]) -> Result<Vec<u8>, AbiError> {
+        let param_types = to_param_types(&self.inputs)?;
+        if tokens.len() != param_types.len() {
+            return Err(AbiError::InvalidData(format!(
+                "expected {} arguments, found {}",
+                param_types.len(),
+                tokens.len()
+            )));
+        }
+        for (token, param_type) in tokens.iter().zip(param_types.iter()) {
+            if !token.type_check(param_type) {
+                return Err(AbiError::InvalidToken(param_type.to_string(), token.to_string()));
+            }
+        }
+
+        Ok([self.selector()?.to_vec(), encode(tokens)].concat())
+    }
+
+    /// Returns the arguments of the given calldata of a call to the function.
+    pub fn decode_input(&self, data: &[u8]) -> Result<Vec<Token>, AbiError> {
+        let selector = self.selector()?;
+        match data.get(..4) {
+            Some(data_selector) if data_selector == selector => decode(&to_param_types(&self.inputs)?, &data[4..]),
+            _ => Err(AbiError::InvalidSelector(hex::encode(&data[..data.len().min(4)]))),
+        }
+    }
+
+    /// Returns the return values of the given output data of a call to the function.
+    pub fn decode_output(&self, data: &[u8]) -> Result<Vec<Token>, AbiError> {
+        decode(&to_param_types(&self.outputs)?, data)
+    }
+}
+
+/// Represents a contract event
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct AbiEvent {
+    /// The name of the event
+    pub name: String,
+    /// The parameters of the event
+    #[serde(default)]
+    pub inputs: Vec<AbiParam>,
+    /// Whether the event omits its topic from the log topics
+    #[serde(default)]
+    pub anonymous: bool,
+}
+
+impl AbiEvent {
+    /// Returns the canonical signature of the event.
+    pub fn signature(&self) -> Result<String, AbiError> {
+        to_signature(&self.name, &self.inputs)
+    }
+
+    /// Returns the event topic, the hash of the signature.
+    pub fn topic(&self) -> Result<[u8; 32], AbiError> {
+        Ok(keccak256(self.signature()?.as_bytes()))
+    }
+
+    /// Returns the parameters of the event from the given log topics and data.
+    /// Indexed parameters of dynamic types are returned as the 32-byte hash of their value.
+    pub fn decode_log(&self, topics: &[[u8; 32]], data: &[u8]) -> Result<Vec<Token>, AbiError> {
+        let topics = match self.anonymous {
+            true => topics,
+            false => match topics.split_first() {
+                Some((topic, topics)) if *topic == self.topic()? => topics,
+                _ => return Err(AbiError::UnknownEvent(self.signature()?)),
+            },
+        };
+
+        let param_types = to_param_types(&self.inputs)?;
+        let indexed_count = self.inputs.iter().filter(|input| input.indexed).count();
+        if topics.len() != indexed_count {
+            return Err(AbiError::InvalidData(format!(
+                "expected {} indexed topics, found {}",
+                indexed_count,
+                topics.len()
+            )));
+        }
+
+        let data_types: Vec<ParamType> = self
+            .inputs
+            .iter()
+            .zip(param_types.iter())
+            .filter(|(input, _)| !input.indexed)
+            .map(|(_, param_type)| param_type.clone())
+            .collect();
+        let mut data_tokens = decode(&data_types, data)?.into_iter();
+        let mut topics = topics.iter();
+
+        let mut tokens = vec![];
+        for (input, param_type) in self.inputs.iter().zip(param_types.iter()) {
+            let token = match (input.indexed, param_type.is_dynamic()) {
+                (true, false) => decode(
+                    &[param_type.clone()],
+                    topics.next().map(|topic| &topic[..]).unwrap_or(&[]),
+                )?
+                .remove(0),
+                (true, true) => Token::FixedBytes(topics.next().map(|topic| topic.to_vec()).unwrap_or_default()),
+                (false, _) => data_tokens
+                    .next()
+                    .ok_or_else(|| AbiError::InvalidData("missing event data".into()))?,
+            };
+            tokens.push(token);
+        }
+        Ok(tokens)
+    }
+}
+
+/// Represents an entry of a JSON ABI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum AbiEntry {
+    Function(AbiFunction),
+    Event(AbiEvent),
+    Constructor {
+        #[serde(default)]
+        inputs: Vec<AbiParam>,
+    },
+    Error(AbiEvent),
+    Fallback {},
+    Receive {},
+}
+
+/// Represents the ABI of a contract
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EthereumAbi {
+    /// The functions of the contract
+    pub functions: Vec<AbiFunction>,
+    /// The events of the contract
+    pub events: Vec<AbiEvent>,
+    /// The constructor parameters of the contract
+    pub constructor: Vec<AbiParam>,
+}
+
+impl EthereumAbi {
+    /// Returns the first function with the given name or signature.
+    pub fn function(&self, name: &str) -> Result<&AbiFunction, AbiError> {
+        self.functions
+            .iter()
+            .find(|function| function.name == name || function.signature().ok().as_deref() == Some(name))
+            .ok_or_else(|| AbiError::UnknownFunction(name.into()))
+    }
+
+    /// Returns the first event with the given name or signature.
+    pub fn event(&self, name: &str) -> Result<&AbiEvent, AbiError> {
+        self.events
+            .iter()
+            .find(|event| event.name == name || event.signature().ok().as_deref() == Some(name))
+            .ok_or_else(|| AbiError::UnknownEvent(name.into()))
+    }
+
+    /// Returns the constructor arguments to be appended to the contract bytecode.
+    pub fn encode_constructor(&self, tokens: &[Token]) -> Result<Vec<u8>, AbiError> {
+        let param_types = to_param_types(&self.constructor)?;
+        match tokens.len() == param_types.len()
+            && tokens
+                .iter()
+                .zip(param_types.iter())
+                .all(|(token, param_type)| token.type_check(param_type))
+        {
+            true => Ok(encode(tokens)),
+            false => Err(AbiError::InvalidData("invalid constructor arguments".into())),
+        }
+    }
+
+    /// Returns the function called by the given calldata and its arguments.
+    pub fn decode_input(&self, data: &[u8]) -> Result<(&AbiFunction, Vec<Token>), AbiError> {
+        let selector = data
+            .get(..4)
+            .ok_or_else(|| AbiError::InvalidSelector(hex::encode(data)))?;
+        for function in &self.functions {
+            if function.selector()? == selector {
+                return Ok((function, function.decode_input(data)?));
+            }
+        }
+        Err(AbiError::InvalidSelector(hex::encode(selector)))
+    }
+
+    /// Returns the function called by the given transaction and its arguments.
+    pub fn decode_transaction<N: EthereumNetwork>(
+        &self,
+        transaction: &EthereumTransaction<N>,
+    ) -> Result<(&AbiFunction, Vec<Token>), AbiError> {
+        self.decode_input(&transaction.parameters().data)
+    }
+}
+
+impl FromStr for EthereumAbi {
+    type Err = AbiError;
+
+    fn from_str(abi: &str) -> Result<Self, Self::Err> {
+        let entries: Vec<AbiEntry> = serde_json::from_str(abi)?;
+
+        let mut contract = Self::default();
+        for entry in entries {
+            match entry {
+                AbiEntry::Function(function) => contract.functions.push(function),
+                AbiEntry::Event(event) => contract.events.push(event),
+                AbiEntry::Constructor { inputs } => contract.constructor = inputs,
+                AbiEntry::Error(_) | AbiEntry::Fallback {} | AbiEntry::Receive {} => (),
+            }
+        }
+        Ok(contract)
+    }
+}
+
+impl fmt::Display for EthereumAbi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut entries: Vec<AbiEntry> = vec![];
+        if !self.constructor.is_empty() {
+            entries.push(AbiEntry::Constructor {
+                inputs: self.constructor.clone(),
+            });
+        }
+        entries.extend(self.functions.iter().cloned().map(AbiEntry::Function));
+        entries.extend(self.events.iter().cloned().map(AbiEntry::Event));
+
+        match serde_json::to_string(&entries) {
+            Ok(abi) => write!(f, "{}", abi),
+            _ => Err(fmt::Error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        EthereumAddress, EthereumAmount, EthereumFormat, EthereumPrivateKey, EthereumTransactionParameters,
+        EthereumTransactionType, Mainnet, ERC20,
+    };
+    use ethereum_types::U256;
+    use wagyu_model::{PrivateKey, Transaction};
+
+    const ERC20_ABI: &str = r#"[
+        {"type":"constructor","inputs":[{"name":"name_","type":"string"},{"name":"symbol_","type":"string"}]},
+        {"type":"function","name":"transfer","stateMutability":"nonpayable","inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],"outputs":[{"name":"","type":"bool"}]},
+        {"type":"function","name":"approve","stateMutability":"nonpayable","inputs":[{"name":"spender","type":"address"},{"name":"amount","type":"uint256"}],"outputs":[{"name":"","type":"bool"}]},
+        {"type":"function","name":"transferFrom","stateMutability":"nonpayable","inputs":[{"name":"from","type":"address"},{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],"outputs":[{"name":"","type":"bool"}]},
+        {"type":"function","name":"balanceOf","stateMutability":"view","inputs":[{"name":"account","type":"address"}],"outputs":[{"name":"","type":"uint256"}]},
+        {"type":"event","name":"Transfer","anonymous":false,"inputs":[{"indexed":true,"name":"from","type":"address"},{"indexed":true,"name":"to","type":"address"},{"indexed":false,"name":"value","type":"uint256"}]},
+        {"type":"error","name":"InsufficientBalance","inputs":[{"name":"needed","type":"uint256"}]},
+        {"type":"receive","stateMutability":"payable"}
+    ]"#;
+
+    const OWNER: &str = "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65";
+    const RECEIVER: &str = "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6";
+
+    fn address(address: &str) -> EthereumAddress {
+        EthereumAddress::from_str(address).unwrap()
+    }
+
+    #[test]
+    fn from_str() {
+        let abi = EthereumAbi::from_str(ERC20_ABI).unwrap();
+        assert_eq!(4, abi.functions.len());
+        assert_eq!(1, abi.events.len());
+        assert_eq!(2, abi.constructor.len());
+
+        let signatures = [
+            ("transfer", "transfer(address,uint256)", "a9059cbb"),
+            ("approve", "approve(address,uint256)", "095ea7b3"),
+            ("transferFrom", "transferFrom(address,address,uint256)", "23b872dd"),
+            ("balanceOf(address)", "balanceOf(address)", "70a08231"),
+        ];
+        signatures.iter().for_each(|(name, signature, selector)| {
+            let function = abi.function(name).unwrap();
+            assert_eq!(*signature, function.signature().unwrap());
+            assert_eq!(*selector, hex::encode(function.selector().unwrap()));
+        });
+
+        let abi = EthereumAbi::from_str(&abi.to_string()).unwrap();
+        assert_eq!(EthereumAbi::from_str(ERC20_ABI).unwrap(), abi);
+    }
+
+    #[test]
+    fn tuple_params() {
+        let abi = r#"[{"type":"function","name":"submit","inputs":[
+            {"name":"orders","type":"tuple[]","components":[
+                {"name":"maker","type":"address"},
+                {"name":"amounts","type":"uint256[2]"},
+                {"name":"fee","type":"tuple","components":[{"name":"bps","type":"uint16"},{"name":"recipient","type":"address"}]}
+            ]},
+            {"name":"signature","type":"bytes"}
+        ]}]"#;
+        let abi = EthereumAbi::from_str(abi).unwrap();
+        let function = abi.function("submit").unwrap();
+        assert_eq!(
+            "submit((address,uint256[2],(uint16,address))[],bytes)",
+            function.signature().unwrap()
+        );
+        assert_eq!(
+            AbiFunction::from_signature("submit((address,uint256[2],(uint16,address))[],bytes)")
+                .unwrap()
+                .selector()
+                .unwrap(),
+            function.selector().unwrap()
+        );
+    }
+
+    #[test]
+    fn encode_input() {
+        let function = AbiFunction::from_signature("transfer(address,uint)").unwrap();
+        let tokens = [Token::Address(address(RECEIVER)), Token::Uint(U256::from(1_000_000))];
+        let data = function.encode_input(&tokens).unwrap();
+        assert_eq!(ERC20::transfer(&address(RECEIVER), U256::from(1_000_000)), data);
+        assert_eq!(tokens.to_vec(), function.decode_input(&data).unwrap());
+
+        assert!(function.encode_input(&tokens[..1]).is_err());
+        assert!(function.encode_input(&[tokens[1].clone(), tokens[0].clone()]).is_err());
+    }
+
+    #[test]
+    fn decode_input() {
+        let abi = EthereumAbi::from_str(ERC20_ABI).unwrap();
+        let data = ERC20::transfer_from(&address(OWNER), &address(RECEIVER), U256::exp10(18));
+        let (function, tokens) = abi.decode_input(&data).unwrap();
+        assert_eq!("transferFrom", function.name);
+        assert_eq!(
+            vec![
+                Token::Address(address(OWNER)),
+                Token::Address(address(RECEIVER)),
+                Token::Uint(U256::exp10(18)),
+            ],
+            tokens
+        );
+
+        // Unknown selector
+        assert!(abi.decode_input(&hex::decode("deadbeef").unwrap()).is_err());
+        // Truncated arguments
+        assert!(abi.decode_input(&data[..data.len() - 1]).is_err());
+        // Missing selector
+        assert!(abi.decode_input(&data[..3]).is_err());
+    }
+
+    #[test]
+    fn decode_output() {
+        let abi = EthereumAbi::from_str(ERC20_ABI).unwrap();
+        let output = hex::decode("0000000000000000000000000000000000000000000000000de0b6b3a7640000").unwrap();
+        assert_eq!(
+            vec![Token::Uint(U256::exp10(18))],
+            abi.function("balanceOf").unwrap().decode_output(&output).unwrap()
+        );
+    }
+
+    #[test]
+    fn decode_log() {
+        let abi = EthereumAbi::from_str(ERC20_ABI).unwrap();
+        let event = abi.event("Transfer").unwrap();
+        let topic = event.topic().unwrap();
+        assert_eq!(
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+            hex::encode(topic)
+        );
+
+        let mut from = [0u8; 32];
+        from[12..].copy_from_slice(&hex::decode(&OWNER[2..]).unwrap());
+        let mut to = [0u8; 32];
+        to[12..].copy_from_slice(&hex::decode(&RECEIVER[2..]).unwrap());
+        let data = hex::decode("00000000000000000000000000000000000000000000000000000000000f4240").unwrap();
+
+        assert_eq!(
+            vec![
+                Token::Address(address(OWNER)),
+                Token::Address(address(RECEIVER)),
+                Token::Uint(U256::from(1_000_000)),
+            ],
+            event.decode_log(&[topic, from, to], &data).unwrap()
+        );
+
+        assert!(event.decode_log(&[from, to], &data).is_err());
+        assert!(event.decode_log(&[topic, from], &data).is_err());
+        assert!(event.decode_log(&[topic, from, to], &data[..31]).is_err());
+    }
+
+    #[test]
+    fn decode_transaction() {
+        let abi = EthereumAbi::from_str(ERC20_ABI).unwrap();
+        let parameters = EthereumTransactionParameters {
+            receiver: Some(address(RECEIVER)),
+            amount: EthereumAmount::from_wei("0").unwrap(),
+            gas: U256::from(60_000),
+            gas_price: EthereumAmount::from_wei("0").unwrap(),
+            nonce: U256::from(7),
+            data: ERC20::approve(&address(OWNER), U256::max_value()),
+            transaction_type: EthereumTransactionType::FeeMarket,
+            max_fee_per_gas: EthereumAmount::from_wei("20000000000").unwrap(),
+            max_priority_fee_per_gas: EthereumAmount::from_wei("1000000000").unwrap(),
+            access_list: vec![],
+            chain_id: None,
+        };
+        let private_key =
+            EthereumPrivateKey::from_str("6cff516706e4eef887c3906f279efa86ac2eeb669b1a2a9f009e85c362fb640c").unwrap();
+        let transaction = EthereumTransaction::<Mainnet>::new(&parameters)
+            .unwrap()
+            .sign(&private_key)
+            .unwrap();
+        let transaction =
+            EthereumTransaction::<Mainnet>::from_transaction_bytes(&transaction.to_transaction_bytes().unwrap())
+                .unwrap();
+
+        let (function, tokens) = abi.decode_transaction(&transaction).unwrap();
+        assert_eq!("approve", function.name);
+        assert_eq!(
+            vec![Token::Address(address(OWNER)), Token::Uint(U256::max_value())],
+            tokens
+        );
+        assert_eq!(
+            private_key.to_address(&EthereumFormat::Standard).unwrap(),
+            transaction.sender().unwrap()
+        );
+    }
+}
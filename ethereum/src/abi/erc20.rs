@@ -0,0 +1,79 @@
+use crate::abi::{encode, Token};
+use crate::address::EthereumAddress;
+
+use ethereum_types::U256;
+
+/// The selector of `transfer(address,uint256)`
+const TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+
+/// The selector of `approve(address,uint256)`
+const APPROVE_SELECTOR: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+
+/// The selector of `transferFrom(address,address,uint256)`
+const TRANSFER_FROM_SELECTOR: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+
+/// Represents the calls of an ERC-20 token contract
+/// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-20.md
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ERC20;
+
+impl ERC20 {
+    /// Returns the calldata to transfer the given amount of tokens to the given receiver.
+    pub fn transfer(receiver: &EthereumAddress, amount: U256) -> Vec<u8> {
+        let arguments = [Token::Address(receiver.clone()), Token::Uint(amount)];
+        [TRANSFER_SELECTOR.to_vec(), encode(&arguments)].concat()
+    }
+
+    /// Returns the calldata to allow the given spender to withdraw up to the given amount of tokens.
+    pub fn approve(spender: &EthereumAddress, amount: U256) -> Vec<u8> {
+        let arguments = [Token::Address(spender.clone()), Token::Uint(amount)];
+        [APPROVE_SELECTOR.to_vec(), encode(&arguments)].concat()
+    }
+
+    /// Returns the calldata to transfer the given amount of tokens from the given owner to the given receiver.
+    pub fn transfer_from(owner: &EthereumAddress, receiver: &EthereumAddress, amount: U256) -> Vec<u8> {
+        let arguments = [
+            Token::Address(owner.clone()),
+            Token::Address(receiver.clone()),
+            Token::Uint(amount),
+        ];
+        [TRANSFER_FROM_SELECTOR.to_vec(), encode(&arguments)].concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const OWNER: &str = "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65";
+    const RECEIVER: &str = "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6";
+
+    #[test]
+    fn transfer() {
+        let receiver = EthereumAddress::from_str(RECEIVER).unwrap();
+        assert_eq!(
+            "a9059cbb00000000000000000000000052c3a8a79a521d10b25569847cb1a3ffb66550d600000000000000000000000000000000000000000000000000000000000f4240",
+            hex::encode(ERC20::transfer(&receiver, U256::from(1_000_000)))
+        );
+    }
+
+    #[test]
+    fn approve() {
+        let spender = EthereumAddress::from_str(RECEIVER).unwrap();
+        assert_eq!(
+            "095ea7b300000000000000000000000052c3a8a79a521d10b25569847cb1a3ffb66550d6ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            hex::encode(ERC20::approve(&spender, U256::max_value()))
+        );
+    }
+
+    #[test]
+    fn transfer_from() {
+        let owner = EthereumAddress::from_str(OWNER).unwrap();
+        let receiver = EthereumAddress::from_str(RECEIVER).unwrap();
+        assert_eq!(
+            "23b872dd000000000000000000000000b5d590a6abf5e349c1b6c511bc87ceabfb3d7e6500000000000000000000000052c3a8a79a521d10b25569847cb1a3ffb66550d60000000000000000000000000000000000000000000000000de0b6b3a7640000",
+            hex::encode(ERC20::transfer_from(&owner, &receiver, U256::exp10(18)))
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::abi::{encode, Token};
+use crate::address::EthereumAddress;
+
+use ethereum_types::U256;
+
+/// The selector of `safeTransferFrom(address,address,uint256)`
+const SAFE_TRANSFER_FROM_SELECTOR: [u8; 4] = [0x42, 0x84, 0x2e, 0x0e];
+
+/// The selector of `safeTransferFrom(address,address,uint256,bytes)`
+const SAFE_TRANSFER_FROM_WITH_DATA_SELECTOR: [u8; 4] = [0xb8, 0x8d, 0x4f, 0xde];
+
+/// The selector of `approve(address,uint256)`
+const APPROVE_SELECTOR: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+
+/// Represents the calls of an ERC-721 non-fungible token contract
+/// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-721.md
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ERC721;
+
+impl ERC721 {
+    /// Returns the calldata to transfer the given token from the given owner to the given receiver,
+    /// which must accept the token if it is a contract.
+    pub fn safe_transfer_from(owner: &EthereumAddress, receiver: &EthereumAddress, token_id: U256) -> Vec<u8> {
+        let arguments = [
+            Token::Address(owner.clone()),
+            Token::Address(receiver.clone()),
+            Token::Uint(token_id),
+        ];
+        [SAFE_TRANSFER_FROM_SELECTOR.to_vec(), encode(&arguments)].concat()
+    }
+
+    /// Returns the calldata to transfer the given token from the given owner to the given receiver,
+    /// passing the given data to the receiver if it is a contract.
+    pub fn safe_transfer_from_with_data(
+        owner: &EthereumAddress,
+        receiver: &EthereumAddress,
+        token_id: U256,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let arguments = [
+            Token::Address(owner.clone()),
+            Token::Address(receiver.clone()),
+            Token::Uint(token_id),
+            Token::Bytes(data.to_vec()),
+        ];
+        [SAFE_TRANSFER_FROM_WITH_DATA_SELECTOR.to_vec(), encode(&arguments)].concat()
+    }
+
+    /// Returns the calldata to allow the given address to transfer the given token.
+    pub fn approve(approved: &EthereumAddress, token_id: U256) -> Vec<u8> {
+        let arguments = [Token::Address(approved.clone()), Token::Uint(token_id)];
+        [APPROVE_SELECTOR.to_vec(), encode(&arguments)].concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const OWNER: &str = "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65";
+    const RECEIVER: &str = "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6";
+
+    #[test]
+    fn safe_transfer_from() {
+        let owner = EthereumAddress::from_str(OWNER).unwrap();
+        let receiver = EthereumAddress::from_str(RECEIVER).unwrap();
+        assert_eq!(
+            "42842e0e000000000000000000000000b5d590a6abf5e349c1b6c511bc87ceabfb3d7e6500000000000000000000000052c3a8a79a521d10b25569847cb1a3ffb66550d600000000000000000000000000000000000000000000000000000000000004d2",
+            hex::encode(ERC721::safe_transfer_from(&owner, &receiver, U256::from(1234)))
+        );
+    }
+
+    #[test]
+    fn safe_transfer_from_with_data() {
+        let owner = EthereumAddress::from_str(OWNER).unwrap();
+        let receiver = EthereumAddress::from_str(RECEIVER).unwrap();
+        assert_eq!(
+            "b88d4fde000000000000000000000000b5d590a6abf5e349c1b6c511bc87ceabfb3d7e6500000000000000000000000052c3a8a79a521d10b25569847cb1a3ffb66550d600000000000000000000000000000000000000000000000000000000000004d200000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000002cafe000000000000000000000000000000000000000000000000000000000000",
+            hex::encode(ERC721::safe_transfer_from_with_data(&owner, &receiver, U256::from(1234), &[0xca, 0xfe]))
+        );
+    }
+
+    #[test]
+    fn approve() {
+        let approved = EthereumAddress::from_str(RECEIVER).unwrap();
+        assert_eq!(
+            "095ea7b300000000000000000000000052c3a8a79a521d10b25569847cb1a3ffb66550d600000000000000000000000000000000000000000000000000000000000004d2",
+            hex::encode(ERC721::approve(&approved, U256::from(1234)))
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//!
+//! Solidity ABI
+//!
+//! This module contains the encoding and decoding of Solidity contract ABI values.
+//! https://docs.soliditylang.org/en/latest/abi-spec.html
+//!
+
+use wagyu_model::AddressError;
+
+pub mod codec;
+pub use self::codec::*;
+
+pub mod contract;
+pub use self::contract::*;
+
+pub mod erc20;
+pub use self::erc20::*;
+
+pub mod erc721;
+pub use self::erc721::*;
+
+pub mod param_type;
+pub use self::param_type::*;
+
+pub mod token;
+pub use self::token::*;
+
+#[derive(Debug, Fail)]
+pub enum AbiError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "invalid data: {}", _0)]
+    InvalidData(String),
+
+    #[fail(display = "invalid JSON: {}", _0)]
+    InvalidJson(String),
+
+    #[fail(display = "invalid selector {}", _0)]
+    InvalidSelector(String),
+
+    #[fail(display = "invalid token for type {}: {}", _0, _1)]
+    InvalidToken(String, String),
+
+    #[fail(display = "invalid type {}", _0)]
+    InvalidType(String),
+
+    #[fail(display = "unknown event {}", _0)]
+    UnknownEvent(String),
+
+    #[fail(display = "unknown function {}", _0)]
+    UnknownFunction(String),
+}
+
+impl From<AddressError> for AbiError {
+    fn from(error: AddressError) -> Self {
+        AbiError::AddressError(error)
+    }
+}
+
+impl From<hex::FromHexError> for AbiError {
+    fn from(error: hex::FromHexError) -> Self {
+        AbiError::InvalidData(format!("{:?}", error))
+    }
+}
+
+impl From<serde_json::error::Error> for AbiError {
+    fn from(error: serde_json::error::Error) -> Self {
+        AbiError::InvalidJson(format!("{:?}", error))
+    }
+}
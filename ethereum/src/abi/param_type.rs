@@ -0,0 +1,263 @@
+use crate::abi::AbiError;
+
+use std::{fmt, str::FromStr};
+
+/// Represents a Solidity ABI type
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ParamType {
+    /// A 20-byte address
+    Address,
+    /// A boolean
+    Bool,
+    /// A signed integer of the given number of bits
+    Int(usize),
+    /// An unsigned integer of the given number of bits
+    Uint(usize),
+    /// A byte array of the given fixed length
+    FixedBytes(usize),
+    /// A dynamically-sized byte array
+    Bytes,
+    /// A dynamically-sized UTF-8 string
+    String,
+    /// A dynamically-sized array of the given type
+    Array(Box<ParamType>),
+    /// A fixed-size array of the given type and length
+    FixedArray(Box<ParamType>, usize),
+    /// A tuple of the given types
+    Tuple(Vec<ParamType>),
+}
+
+impl ParamType {
+    /// Returns `true` if the encoding of the type is dynamically-sized.
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+            ParamType::FixedArray(param_type, _) => param_type.is_dynamic(),
+            ParamType::Tuple(param_types) => param_types.iter().any(ParamType::is_dynamic),
+            _ => false,
+        }
+    }
+
+    /// Returns the number of bytes the type occupies in the head of an enclosing tuple.
+    pub fn head_size(&self) -> usize {
+        match self {
+            _ if self.is_dynamic() => 32,
+            ParamType::FixedArray(param_type, length) => param_type.head_size() * length,
+            ParamType::Tuple(param_types) => param_types.iter().map(ParamType::head_size).sum(),
+            _ => 32,
+        }
+    }
+}
+
+impl FromStr for ParamType {
+    type Err = AbiError;
+
+    /// Returns the type of the given canonical type string, such as `uint256[]` or `(address,bytes)`.
+    fn from_str(param_type: &str) -> Result<Self, Self::Err> {
+        let invalid_type = || AbiError::InvalidType(param_type.into());
+
+        // Array suffixes apply to the type preceding the last opening bracket.
+        if param_type.ends_with(']') {
+            let index = param_type.rfind('[').ok_or_else(invalid_type)?;
+            let inner = Box::new(Self::from_str(&param_type[..index])?);
+            return match &param_type[index + 1..param_type.len() - 1] {
+                "" => Ok(ParamType::Array(inner)),
+                length => match parse_size(length) {
+                    Some(length) if length > 0 => Ok(ParamType::FixedArray(inner, length)),
+                    _ => Err(invalid_type()),
+                },
+            };
+        }
+
+        if param_type.starts_with('(') && param_type.ends_with(')') {
+            let components = &param_type[1..param_type.len() - 1];
+            if components.is_empty() {
+                return Ok(ParamType::Tuple(vec![]));
+            }
+            return Ok(ParamType::Tuple(
+                split_components(components)
+                    .ok_or_else(invalid_type)?
+                    .iter()
+                    .map(|component| Self::from_str(component))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ));
+        }
+
+        match param_type {
+            "address" => Ok(ParamType::Address),
+            "bool" => Ok(ParamType::Bool),
+            "bytes" => Ok(ParamType::Bytes),
+            "string" => Ok(ParamType::String),
+            "int" => Ok(ParamType::Int(256)),
+            "uint" => Ok(ParamType::Uint(256)),
+            _ if param_type.starts_with("int") => match parse_size(&param_type[3..]) {
+                Some(bits) if bits > 0 && bits <= 256 && bits % 8 == 0 => Ok(ParamType::Int(bits)),
+                _ => Err(invalid_type()),
+            },
+            _ if param_type.starts_with("uint") => match parse_size(&param_type[4..]) {
+                Some(bits) if bits > 0 && bits <= 256 && bits % 8 == 0 => Ok(ParamType::Uint(bits)),
+                _ => Err(invalid_type()),
+            },
+            _ if param_type.starts_with("bytes") => match parse_size(&param_type[5..]) {
+                Some(length) if length > 0 && length <= 32 => Ok(ParamType::FixedBytes(length)),
+                _ => Err(invalid_type()),
+            },
+            _ => Err(invalid_type()),
+        }
+    }
+}
+
+impl fmt::Display for ParamType {
+    /// Writes the canonical type string used in function and event signatures.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParamType::Address => write!(f, "address"),
+            ParamType::Bool => write!(f, "bool"),
+            ParamType::Int(bits) => write!(f, "int{}", bits),
+            ParamType::Uint(bits) => write!(f, "uint{}", bits),
+            ParamType::FixedBytes(length) => write!(f, "bytes{}", length),
+            ParamType::Bytes => write!(f, "bytes"),
+            ParamType::String => write!(f, "string"),
+            ParamType::Array(param_type) => write!(f, "{}[]", param_type),
+            ParamType::FixedArray(param_type, length) => write!(f, "{}[{}]", param_type, length),
+            ParamType::Tuple(param_types) => {
+                let components: Vec<String> = param_types.iter().map(ParamType::to_string).collect();
+                write!(f, "({})", components.join(","))
+            }
+        }
+    }
+}
+
+/// Returns the decimal size suffix of a type, rejecting leading zeros.
+fn parse_size(suffix: &str) -> Option<usize> {
+    match suffix.starts_with('0') {
+        true => None,
+        false => suffix.parse::<usize>().ok(),
+    }
+}
+
+/// Splits the given tuple components at the commas that are not nested in parentheses.
+pub(crate) fn split_components(components: &str) -> Option<Vec<&str>> {
+    let mut result = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, character) in components.char_indices() {
+        match character {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                result.push(&components[start..index]);
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+    match depth {
+        0 => {
+            result.push(&components[start..]);
+            Some(result)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        let param_types = [
+            ("address", ParamType::Address),
+            ("bool", ParamType::Bool),
+            ("int", ParamType::Int(256)),
+            ("int8", ParamType::Int(8)),
+            ("uint", ParamType::Uint(256)),
+            ("uint112", ParamType::Uint(112)),
+            ("bytes", ParamType::Bytes),
+            ("bytes32", ParamType::FixedBytes(32)),
+            ("string", ParamType::String),
+            ("uint256[]", ParamType::Array(Box::new(ParamType::Uint(256)))),
+            (
+                "bytes3[2][]",
+                ParamType::Array(Box::new(ParamType::FixedArray(Box::new(ParamType::FixedBytes(3)), 2))),
+            ),
+            (
+                "(address,(bool,string[]))[3]",
+                ParamType::FixedArray(
+                    Box::new(ParamType::Tuple(vec![
+                        ParamType::Address,
+                        ParamType::Tuple(vec![ParamType::Bool, ParamType::Array(Box::new(ParamType::String))]),
+                    ])),
+                    3,
+                ),
+            ),
+            ("()", ParamType::Tuple(vec![])),
+        ];
+        param_types.iter().for_each(|(string, param_type)| {
+            assert_eq!(*param_type, ParamType::from_str(string).unwrap());
+        });
+    }
+
+    #[test]
+    fn to_string() {
+        let param_types = [
+            ("int", "int256"),
+            ("uint", "uint256"),
+            ("uint[2][]", "uint256[2][]"),
+            ("(int,(uint,bytes))", "(int256,(uint256,bytes))"),
+            ("address[]", "address[]"),
+        ];
+        param_types.iter().for_each(|(param_type, expected_param_type)| {
+            assert_eq!(
+                *expected_param_type,
+                ParamType::from_str(param_type).unwrap().to_string()
+            );
+        });
+    }
+
+    #[test]
+    fn is_dynamic() {
+        let param_types = [
+            ("uint256", false, 32),
+            ("bytes32[4]", false, 128),
+            ("(address,uint8[2])", false, 96),
+            ("bytes", true, 32),
+            ("string[2]", true, 32),
+            ("(uint256,bytes)", true, 32),
+            ("uint256[]", true, 32),
+        ];
+        param_types.iter().for_each(|(param_type, dynamic, head_size)| {
+            let param_type = ParamType::from_str(param_type).unwrap();
+            assert_eq!(*dynamic, param_type.is_dynamic());
+            assert_eq!(*head_size, param_type.head_size());
+        });
+    }
+
+    #[test]
+    fn invalid_from_str() {
+        let invalid_param_types = [
+            "",
+            "uint7",
+            "uint0",
+            "uint264",
+            "uint08",
+            "int-8",
+            "bytes0",
+            "bytes33",
+            "bytes01",
+            "address[0]",
+            "address[01]",
+            "address]",
+            "(uint256",
+            "uint256)",
+            "(uint256,(bool)",
+            "(,)",
+            "function",
+            "tuple",
+        ];
+        invalid_param_types.iter().for_each(|param_type| {
+            assert!(ParamType::from_str(param_type).is_err());
+        });
+    }
+}
@@ -0,0 +1,186 @@
+use crate::abi::{AbiError, ParamType};
+use crate::address::EthereumAddress;
+
+use ethereum_types::U256;
+use serde_json::Value;
+use std::{fmt, str::FromStr};
+
+/// Represents a Solidity ABI value
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Token {
+    /// An address
+    Address(EthereumAddress),
+    /// A boolean
+    Bool(bool),
+    /// A signed integer, in two's complement
+    Int(U256),
+    /// An unsigned integer
+    Uint(U256),
+    /// A fixed-length byte array
+    FixedBytes(Vec<u8>),
+    /// A dynamically-sized byte array
+    Bytes(Vec<u8>),
+    /// A string
+    String(String),
+    /// A dynamically-sized array
+    Array(Vec<Token>),
+    /// A fixed-size array
+    FixedArray(Vec<Token>),
+    /// A tuple
+    Tuple(Vec<Token>),
+}
+
+impl Token {
+    /// Returns a signed integer token of the given value.
+    pub fn from_i128(value: i128) -> Self {
+        match value < 0 {
+            true => Token::Int(negate(U256::from(value.unsigned_abs()))),
+            false => Token::Int(U256::from(value as u128)),
+        }
+    }
+
+    /// Returns `true` if the token is a valid value of the given type.
+    pub fn type_check(&self, param_type: &ParamType) -> bool {
+        match (self, param_type) {
+            (Token::Address(_), ParamType::Address) => true,
+            (Token::Bool(_), ParamType::Bool) => true,
+            (Token::Int(value), ParamType::Int(bits)) => {
+                // The value must be the sign extension of its lowest `bits` bits.
+                let bound = U256::one() << (*bits - 1);
+                match value.bit(255) {
+                    true => negate(*value) <= bound,
+                    false => *value < bound,
+                }
+            }
+            (Token::Uint(value), ParamType::Uint(bits)) => value.bits() <= *bits,
+            (Token::FixedBytes(bytes), ParamType::FixedBytes(length)) => bytes.len() == *length,
+            (Token::Bytes(_), ParamType::Bytes) => true,
+            (Token::String(_), ParamType::String) => true,
+            (Token::Array(tokens), ParamType::Array(param_type)) => {
+                tokens.iter().all(|token| token.type_check(param_type))
+            }
+            (Token::FixedArray(tokens), ParamType::FixedArray(param_type, length)) => {
+                tokens.len() == *length && tokens.iter().all(|token| token.type_check(param_type))
+            }
+            (Token::Tuple(tokens), ParamType::Tuple(param_types)) => {
+                tokens.len() == param_types.len()
+                    && tokens
+                        .iter()
+                        .zip(param_types.iter())
+                        .all(|(token, param_type)| token.type_check(param_type))
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the token of the given type from the given JSON value.
+    /// Integers may be JSON numbers or strings of decimal or 0x-prefixed hex numbers,
+    /// byte arrays are 0x-prefixed hex strings, and arrays and tuples are JSON arrays.
+    pub fn from_json(param_type: &ParamType, value: &Value) -> Result<Self, AbiError> {
+        let invalid_token = || AbiError::InvalidToken(param_type.to_string(), value.to_string());
+
+        let token = match (param_type, value) {
+            (ParamType::Address, Value::String(address)) => Token::Address(EthereumAddress::from_str(address)?),
+            (ParamType::Bool, Value::Bool(boolean)) => Token::Bool(*boolean),
+            (ParamType::Int(_), _) => match parse_integer(value).ok_or_else(invalid_token)? {
+                (true, magnitude) => Token::Int(negate(magnitude)),
+                (false, magnitude) => Token::Int(magnitude),
+            },
+            (ParamType::Uint(_), _) => match parse_integer(value).ok_or_else(invalid_token)? {
+                (false, magnitude) => Token::Uint(magnitude),
+                (true, _) => return Err(invalid_token()),
+            },
+            (ParamType::FixedBytes(_), Value::String(bytes)) => Token::FixedBytes(decode_hex(bytes)?),
+            (ParamType::Bytes, Value::String(bytes)) => Token::Bytes(decode_hex(bytes)?),
+            (ParamType::String, Value::String(string)) => Token::String(string.clone()),
+            (ParamType::Array(param_type), Value::Array(values)) => Token::Array(
+                values
+                    .iter()
+                    .map(|value| Self::from_json(param_type, value))
+                    .collect::<Result<_, _>>()?,
+            ),
+            (ParamType::FixedArray(param_type, _), Value::Array(values)) => Token::FixedArray(
+                values
+                    .iter()
+                    .map(|value| Self::from_json(param_type, value))
+                    .collect::<Result<_, _>>()?,
+            ),
+            (ParamType::Tuple(param_types), Value::Array(values)) if param_types.len() == values.len() => Token::Tuple(
+                param_types
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(param_type, value)| Self::from_json(param_type, value))
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => return Err(invalid_token()),
+        };
+
+        match token.type_check(param_type) {
+            true => Ok(token),
+            false => Err(invalid_token()),
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn join(tokens: &[Token]) -> String {
+            tokens.iter().map(Token::to_string).collect::<Vec<String>>().join(",")
+        }
+
+        match self {
+            Token::Address(address) => write!(f, "{}", address),
+            Token::Bool(boolean) => write!(f, "{}", boolean),
+            Token::Int(value) if value.bit(255) => write!(f, "-{}", negate(*value)),
+            Token::Int(value) | Token::Uint(value) => write!(f, "{}", value),
+            Token::FixedBytes(bytes) | Token::Bytes(bytes) => write!(f, "0x{}", hex::encode(bytes)),
+            Token::String(string) => write!(f, "{:?}", string),
+            Token::Array(tokens) | Token::FixedArray(tokens) => write!(f, "[{}]", join(tokens)),
+            Token::Tuple(tokens) => write!(f, "({})", join(tokens)),
+        }
+    }
+}
+
+/// Returns the two's complement negation of the given value.
+pub(crate) fn negate(value: U256) -> U256 {
+    (!value).overflowing_add(U256::one()).0
+}
+
+/// Returns the bytes of the given 0x-prefixed hex string.
+fn decode_hex(bytes: &str) -> Result<Vec<u8>, AbiError> {
+    match bytes.starts_with("0x") {
+        true => Ok(hex::decode(&bytes[2..])?),
+        false => Err(AbiError::InvalidData(bytes.into())),
+    }
+}
+
+/// Returns the sign and magnitude of the given integer value.
+/// The value is a JSON number, or a string of a decimal or 0x-prefixed hex number.
+pub(crate) fn parse_integer(value: &Value) -> Option<(bool, U256)> {
+    match value {
+        Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(integer), _) => Some((false, U256::from(integer))),
+            (None, Some(integer)) => Some((true, U256::from(integer.unsigned_abs()))),
+            _ => None,
+        },
+        Value::String(string) => {
+            let (negative, string) = match string.starts_with('-') {
+                true => (true, &string[1..]),
+                false => (false, &string[..]),
+            };
+            let magnitude = match string.starts_with("0x") {
+                true if string.len() > 2 && string.len() <= 66 => {
+                    let hex = match string.len() % 2 {
+                        0 => string[2..].to_string(),
+                        _ => format!("0{}", &string[2..]),
+                    };
+                    hex::decode(hex).ok().map(|bytes| U256::from(bytes.as_slice()))
+                }
+                true => None,
+                false => U256::from_dec_str(string).ok(),
+            }?;
+            Some((negative && !magnitude.is_zero(), magnitude))
+        }
+        _ => None,
+    }
+}
@@ -3,7 +3,9 @@ use crate::private_key::EthereumPrivateKey;
 use crate::public_key::EthereumPublicKey;
 use wagyu_model::{to_hex_string, Address, AddressError, PrivateKey};
 
+use ethereum_types::U256;
 use regex::Regex;
+use rlp::RlpStream;
 use serde::Serialize;
 use std::{convert::TryFrom, fmt, str::FromStr};
 use tiny_keccak::keccak256;
@@ -18,13 +20,20 @@ impl Address for EthereumAddress {
     type PublicKey = EthereumPublicKey;
 
     /// Returns the address corresponding to the given private key.
-    fn from_private_key(private_key: &Self::PrivateKey, _format: &Self::Format) -> Result<Self, AddressError> {
-        Self::from_public_key(&private_key.to_public_key(), _format)
+    fn from_private_key(private_key: &Self::PrivateKey, format: &Self::Format) -> Result<Self, AddressError> {
+        Self::from_public_key(&private_key.to_public_key(), format)
     }
 
     /// Returns the address corresponding to the given public key.
-    fn from_public_key(public_key: &Self::PublicKey, _: &Self::Format) -> Result<Self, AddressError> {
-        Ok(Self::checksum_address(public_key))
+    /// ICAP is only a display form of an address, see `to_icap`.
+    fn from_public_key(public_key: &Self::PublicKey, format: &Self::Format) -> Result<Self, AddressError> {
+        match format {
+            EthereumFormat::Standard => Ok(Self::checksum_address(public_key)),
+            EthereumFormat::ICAP => Err(AddressError::IncompatibleFormats(
+                String::from("icap"),
+                String::from("derived address"),
+            )),
+        }
     }
 }
 
@@ -33,20 +42,144 @@ impl EthereumAddress {
     /// Adheres to EIP-55 (https://eips.ethereum.org/EIPS/eip-55).
     pub fn checksum_address(public_key: &EthereumPublicKey) -> Self {
         let hash = keccak256(&public_key.to_secp256k1_public_key().serialize()[1..]);
-        let address = to_hex_string(&hash[12..]).to_lowercase();
-
-        let hash = to_hex_string(&keccak256(address.as_bytes()));
-        let mut checksum_address = "0x".to_string();
-        for c in 0..40 {
-            let ch = match &hash[c..=c] {
-                "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" => address[c..=c].to_lowercase(),
-                _ => address[c..=c].to_uppercase(),
+        EthereumAddress(to_checksum(&to_hex_string(&hash[12..]).to_lowercase(), None))
+    }
+
+    /// Returns the address with the checksum of the given chain id.
+    /// Adheres to EIP-1191 (https://eips.ethereum.org/EIPS/eip-1191), as used by RSK.
+    pub fn to_checksum_with_chain_id(&self, chain_id: u64) -> String {
+        to_checksum(&self.0[2..].to_lowercase(), Some(chain_id))
+    }
+
+    /// Returns the address of the given string, verifying its EIP-55 checksum,
+    /// or its EIP-1191 checksum if a chain id is given.
+    /// Addresses in a single case carry no checksum and are accepted.
+    pub fn from_checksum_str(address: &str, chain_id: Option<u64>) -> Result<Self, AddressError> {
+        let ethereum_address = Self::from_str(address)?;
+        let hex = address.trim_start_matches("0x");
+        if hex == hex.to_lowercase() || hex == hex.to_uppercase() {
+            return Ok(ethereum_address);
+        }
+
+        let expected = to_checksum(&hex.to_lowercase(), chain_id);
+        match expected[2..] == *hex {
+            true => Ok(ethereum_address),
+            false => Err(AddressError::InvalidChecksum(expected, address.into())),
+        }
+    }
+
+    /// Returns the ICAP representation of the address, an IBAN-style "XE" code of the address in base 36.
+    /// Addresses with a leading zero byte fit the 34-character Direct ICAP, and others use 35 characters.
+    /// https://github.com/ethereum/wiki/wiki/Inter-exchange-Client-Address-Protocol-(ICAP)
+    pub fn to_icap(&self) -> Result<String, AddressError> {
+        let mut value = U256::from(&self.to_bytes()?[..]);
+        let mut bban = vec![];
+        while !value.is_zero() {
+            bban.push(BASE36_ALPHABET[(value % 36).as_usize()]);
+            value = value / 36;
+        }
+        while bban.len() < 30 {
+            bban.push(b'0');
+        }
+        bban.reverse();
+
+        let bban = String::from_utf8(bban)?;
+        Ok(format!("XE{:02}{}", 98 - icap_mod97(&format!("{}XE00", bban)), bban))
+    }
+
+    /// Returns the address of the given ICAP representation, verifying its check digits.
+    pub fn from_icap(icap: &str) -> Result<Self, AddressError> {
+        let icap = icap.to_uppercase();
+        if !icap.starts_with("XE") {
+            return Err(AddressError::InvalidPrefix(
+                icap.as_bytes().iter().take(2).cloned().collect(),
+            ));
+        }
+        if icap.len() != 34 && icap.len() != 35 {
+            return Err(AddressError::InvalidCharacterLength(icap.len()));
+        }
+
+        // The check digits are valid if the BBAN followed by the first four characters is 1 mod 97.
+        let bban = &icap[4..];
+        if icap_mod97(&format!("{}{}", bban, &icap[..4])) != 1 {
+            let expected = format!("XE{:02}{}", 98 - icap_mod97(&format!("{}XE00", bban)), bban);
+            return Err(AddressError::InvalidChecksum(expected, icap));
+        }
+
+        let mut value = U256::zero();
+        for c in bban.bytes() {
+            let digit = match BASE36_ALPHABET.iter().position(|d| *d == c) {
+                Some(digit) => digit,
+                None => return Err(AddressError::InvalidAddress(icap)),
             };
-            checksum_address.push_str(&ch);
+            // Base 36 values of 31 characters may exceed 160 bits.
+            value = value * 36 + digit;
+            if value.bits() > 160 {
+                return Err(AddressError::InvalidAddress(icap));
+            }
         }
 
-        EthereumAddress(checksum_address)
+        let mut bytes = [0u8; 32];
+        value.to_big_endian(&mut bytes);
+        Self::from_str(&hex::encode(&bytes[12..]))
+    }
+
+    /// Returns the address of the contract created by the given sender and nonce with CREATE,
+    /// the last 20 bytes of the hash of the RLP encoding of the sender and its nonce.
+    pub fn create_address(sender: &Self, nonce: U256) -> Result<Self, AddressError> {
+        let mut create_rlp = RlpStream::new();
+        create_rlp.begin_list(2);
+        create_rlp.append(&sender.to_bytes()?);
+        create_rlp.append(&nonce);
+        Self::from_str(&hex::encode(&keccak256(&create_rlp.out())[12..]))
     }
+
+    /// Returns the address of the contract created by the given deployer, salt, and hash of the init code with CREATE2.
+    /// Adheres to EIP-1014 (https://eips.ethereum.org/EIPS/eip-1014).
+    pub fn create2_address(deployer: &Self, salt: &[u8; 32], init_code_hash: &[u8; 32]) -> Result<Self, AddressError> {
+        let preimage = [&[0xff], &deployer.to_bytes()?[..], &salt[..], &init_code_hash[..]].concat();
+        Self::from_str(&hex::encode(&keccak256(&preimage)[12..]))
+    }
+
+    /// Returns the 20 bytes of the address.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, AddressError> {
+        Ok(hex::decode(&self.0[2..])?)
+    }
+}
+
+/// The digits and uppercase letters of base 36, as used by ICAP
+const BASE36_ALPHABET: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Returns the mixed-case checksum encoding of the given lowercase hex address.
+/// The hash is of the address alone for EIP-55, or prefixed with the chain id for EIP-1191.
+fn to_checksum(address: &str, chain_id: Option<u64>) -> String {
+    let preimage = match chain_id {
+        Some(chain_id) => format!("{}0x{}", chain_id, address),
+        None => address.to_string(),
+    };
+    let hash = to_hex_string(&keccak256(preimage.as_bytes()));
+
+    let mut checksum_address = "0x".to_string();
+    for c in 0..40 {
+        let ch = match &hash[c..=c] {
+            "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" => address[c..=c].to_lowercase(),
+            _ => address[c..=c].to_uppercase(),
+        };
+        checksum_address.push_str(&ch);
+    }
+    checksum_address
+}
+
+/// Returns the ISO 7064 mod 97 remainder of the given ICAP characters,
+/// with letters replaced by their base 36 values.
+fn icap_mod97(characters: &str) -> u32 {
+    characters
+        .chars()
+        .filter_map(|c| c.to_digit(36))
+        .fold(0, |remainder, digit| match digit < 10 {
+            true => (remainder * 10 + digit) % 97,
+            false => (remainder * 100 + digit) % 97,
+        })
 }
 
 impl<'a> TryFrom<&'a str> for EthereumAddress {
@@ -61,6 +194,10 @@ impl FromStr for EthereumAddress {
     type Err = AddressError;
 
     fn from_str(address: &str) -> Result<Self, Self::Err> {
+        if address.starts_with("XE") || address.starts_with("xe") {
+            return Self::from_icap(address);
+        }
+
         let regex = Regex::new(r"^0x").unwrap();
         let address = address.to_lowercase();
         let address = regex.replace_all(&address, "").to_string();
@@ -69,17 +206,7 @@ impl FromStr for EthereumAddress {
             return Err(AddressError::InvalidCharacterLength(address.len()));
         }
 
-        let hash = to_hex_string(&keccak256(address.as_bytes()));
-        let mut checksum_address = "0x".to_string();
-        for c in 0..40 {
-            let ch = match &hash[c..=c] {
-                "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" => address[c..=c].to_lowercase(),
-                _ => address[c..=c].to_uppercase(),
-            };
-            checksum_address.push_str(&ch);
-        }
-
-        Ok(EthereumAddress(checksum_address))
+        Ok(EthereumAddress(to_checksum(&address, None)))
     }
 }
 
@@ -172,6 +299,240 @@ mod tests {
         }
     }
 
+    mod create_address {
+        use super::*;
+
+        const SENDER: &str = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0";
+
+        // (nonce, address)
+        const ADDRESSES: [(u64, &str); 8] = [
+            (0, "0xcd234A471b72ba2F1Ccf0A70FCABA648a5eeCD8d"),
+            (1, "0x343c43A37D37dfF08AE8C4A11544c718AbB4fCF8"),
+            (2, "0xf778B86FA74E846c4f0a1fBd1335FE81c00a0C91"),
+            (3, "0xffFd933A0bC612844eaF0C6Fe3E5b8E9B6C1d19c"),
+            (127, "0x06d9a77f5E4b311Bae8D559DB9CDB4dF94104aA0"),
+            (128, "0x08e190dcB7b73F5fcDAbb43e102215c83659A76D"),
+            (256, "0x3837C1Ae70354f670550C746580199Ac6a73Cb0a"),
+            (4294967296, "0xf4bf328880432064068338F915C49f817dC4Ce18"),
+        ];
+
+        #[test]
+        fn create_address() {
+            let sender = EthereumAddress::from_str(SENDER).unwrap();
+            ADDRESSES.iter().for_each(|(nonce, expected_address)| {
+                let address = EthereumAddress::create_address(&sender, U256::from(*nonce)).unwrap();
+                assert_eq!(*expected_address, address.to_string());
+            });
+        }
+    }
+
+    mod create2_address {
+        use super::*;
+
+        // (deployer, salt, init code, address)
+        // https://eips.ethereum.org/EIPS/eip-1014
+        const ADDRESSES: [(&str, &str, &str, &str); 7] = [
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "00",
+                "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38",
+            ),
+            (
+                "0xdeadbeef00000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "00",
+                "0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3",
+            ),
+            (
+                "0xdeadbeef00000000000000000000000000000000",
+                "000000000000000000000000feed000000000000000000000000000000000000",
+                "00",
+                "0xD04116cDd17beBE565EB2422F2497E06cC1C9833",
+            ),
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "deadbeef",
+                "0x70f2b2914A2a4b783FaEFb75f459A580616Fcb5e",
+            ),
+            (
+                "0x00000000000000000000000000000000deadbeef",
+                "00000000000000000000000000000000000000000000000000000000cafebabe",
+                "deadbeef",
+                "0x60f3f640a8508fC6a86d45DF051962668E1e8AC7",
+            ),
+            (
+                "0x00000000000000000000000000000000deadbeef",
+                "00000000000000000000000000000000000000000000000000000000cafebabe",
+                "deadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef",
+                "0x1d8bfDC5D46DC4f61D6b6115972536eBE6A8854C",
+            ),
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "",
+                "0xE33C0C7F7df4809055C3ebA6c09CFe4BaF1BD9e0",
+            ),
+        ];
+
+        #[test]
+        fn create2_address() {
+            ADDRESSES
+                .iter()
+                .for_each(|(deployer, salt, init_code, expected_address)| {
+                    let deployer = EthereumAddress::from_str(deployer).unwrap();
+                    let mut salt_bytes = [0u8; 32];
+                    salt_bytes.copy_from_slice(&hex::decode(salt).unwrap());
+                    let init_code_hash = keccak256(&hex::decode(init_code).unwrap());
+
+                    let address = EthereumAddress::create2_address(&deployer, &salt_bytes, &init_code_hash).unwrap();
+                    assert_eq!(*expected_address, address.to_string());
+                });
+        }
+    }
+
+    mod checksum_with_chain_id {
+        use super::*;
+
+        // (chain id, addresses)
+        // https://eips.ethereum.org/EIPS/eip-1191
+        const ADDRESSES: [(u64, [&str; 4]); 3] = [
+            (
+                30,
+                [
+                    "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD",
+                    "0xFb6916095cA1Df60bb79ce92cE3EA74c37c5d359",
+                    "0xDBF03B407c01E7CD3cBea99509D93F8Dddc8C6FB",
+                    "0xD1220A0Cf47c7B9BE7a2e6ba89F429762E7B9adB",
+                ],
+            ),
+            (
+                31,
+                [
+                    "0x5aAeb6053F3e94c9b9A09F33669435E7EF1BEaEd",
+                    "0xFb6916095CA1dF60bb79CE92ce3Ea74C37c5D359",
+                    "0xdbF03B407C01E7cd3cbEa99509D93f8dDDc8C6fB",
+                    "0xd1220a0CF47c7B9Be7A2E6Ba89f429762E7b9adB",
+                ],
+            ),
+            (
+                1,
+                [
+                    "0x5aaeB6053f3E94C9b9A09f33669435e7Ef1bEAed",
+                    "0xfb6916095Ca1dF60bB79cE92ce3ea74C37c5D359",
+                    "0xdBf03b407c01e7cD3CBea99509d93f8dDDC8C6fB",
+                    "0xd1220a0CF47C7B9Be7A2E6ba89F429762E7B9Adb",
+                ],
+            ),
+        ];
+
+        #[test]
+        fn to_checksum_with_chain_id() {
+            ADDRESSES.iter().for_each(|(chain_id, addresses)| {
+                addresses.iter().for_each(|expected_address| {
+                    let address = EthereumAddress::from_str(expected_address).unwrap();
+                    assert_eq!(*expected_address, address.to_checksum_with_chain_id(*chain_id));
+                });
+            });
+        }
+
+        #[test]
+        fn from_checksum_str() {
+            ADDRESSES.iter().for_each(|(chain_id, addresses)| {
+                addresses.iter().for_each(|address| {
+                    let expected_address = EthereumAddress::from_str(address).unwrap();
+                    let checksum_address = EthereumAddress::from_checksum_str(address, Some(*chain_id)).unwrap();
+                    assert_eq!(expected_address, checksum_address);
+
+                    // The EIP-55 checksum does not match the EIP-1191 checksum
+                    assert!(EthereumAddress::from_checksum_str(address, None).is_err());
+                    assert!(EthereumAddress::from_checksum_str(&address.to_lowercase(), None).is_ok());
+                });
+            });
+        }
+
+        #[test]
+        fn from_checksum_str_eip55() {
+            let address = "0x9141B7539E7902872095C408BfA294435e2b8c8a";
+            assert!(EthereumAddress::from_checksum_str(address, None).is_ok());
+
+            let address = "0x9141b7539E7902872095C408BfA294435e2b8c8a";
+            assert!(EthereumAddress::from_checksum_str(address, None).is_err());
+        }
+    }
+
+    mod icap {
+        use super::*;
+
+        // (address, ICAP)
+        const ADDRESSES: [(&str, &str); 5] = [
+            (
+                "0x00c5496aEe77C1bA1f0854206A26DdA82a81D6D8",
+                "XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS",
+            ),
+            (
+                "0x0000000000000000000000000000000000000000",
+                "XE50000000000000000000000000000000",
+            ),
+            (
+                "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+                "XE96ALC63SZ321UA5GPT42T9M6PT9FDD3AL",
+            ),
+            (
+                "0x11c5496AEE77c1bA1f0854206a26dDa82A81D6D8",
+                "XE1222Q908LN1QBBU6XUQSO1OHWJIOS46OO",
+            ),
+            (
+                "0xFFfFfFffFFfffFFfFFfFFFFFffFFFffffFfFFFfF",
+                "XE95TWJ4YIDKW7A8PN4G709KZMFOAOL3X8F",
+            ),
+        ];
+
+        #[test]
+        fn to_icap() {
+            ADDRESSES.iter().for_each(|(address, expected_icap)| {
+                let address = EthereumAddress::from_str(address).unwrap();
+                assert_eq!(*expected_icap, address.to_icap().unwrap());
+            });
+        }
+
+        #[test]
+        fn from_icap() {
+            ADDRESSES.iter().for_each(|(expected_address, icap)| {
+                assert_eq!(*expected_address, EthereumAddress::from_icap(icap).unwrap().to_string());
+                assert_eq!(*expected_address, EthereumAddress::from_str(icap).unwrap().to_string());
+                assert_eq!(
+                    *expected_address,
+                    EthereumAddress::from_icap(&icap.to_lowercase()).unwrap().to_string()
+                );
+            });
+        }
+
+        #[test]
+        fn invalid_icap() {
+            // Invalid check digits
+            assert!(EthereumAddress::from_icap("XE7438O073KYGTWWZN0F2WZ0R8PX5ZPPZS").is_err());
+            // Invalid character
+            assert!(EthereumAddress::from_icap("XE7338O073KYGTWWZN0F2WZ0R8PX5ZPP-S").is_err());
+            // Invalid country code
+            assert!(EthereumAddress::from_icap("GB7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS").is_err());
+            // Invalid length
+            assert!(EthereumAddress::from_icap("XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZ").is_err());
+            // Exceeds 160 bits
+            assert!(EthereumAddress::from_icap("XE54ZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZ").is_err());
+        }
+
+        #[test]
+        fn invalid_icap_format() {
+            let private_key =
+                EthereumPrivateKey::from_str("208065a247edbe5df4d86fbdc0171303f23a76961be9f6013850dd2bdc759bbb")
+                    .unwrap();
+            assert!(EthereumAddress::from_private_key(&private_key, &EthereumFormat::ICAP).is_err());
+            assert!(EthereumAddress::from_public_key(&private_key.to_public_key(), &EthereumFormat::ICAP).is_err());
+        }
+    }
+
     #[test]
     fn test_checksum_address_invalid() {
         // Mismatched keypair
@@ -0,0 +1,258 @@
+//!
+//! Ethereum Name Service
+//!
+//! This module contains the name hashing of ENS and the calldata of its registry and resolver calls.
+//! https://eips.ethereum.org/EIPS/eip-137
+//!
+
+use crate::abi::{encode, Token};
+use crate::address::EthereumAddress;
+
+use ethereum_types::U256;
+use tiny_keccak::keccak256;
+
+/// The address of the ENS registry on mainnet and its testnets
+pub const ENS_REGISTRY: &str = "0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e";
+
+/// The selector of `resolver(bytes32)`
+const RESOLVER_SELECTOR: [u8; 4] = [0x01, 0x78, 0xb8, 0xbf];
+
+/// The selector of `addr(bytes32)`
+const ADDR_SELECTOR: [u8; 4] = [0x3b, 0x3b, 0x57, 0xde];
+
+/// The selector of `addr(bytes32,uint256)`
+const ADDR_WITH_COIN_TYPE_SELECTOR: [u8; 4] = [0xf1, 0xcb, 0x7e, 0x06];
+
+/// The selector of `name(bytes32)`
+const NAME_SELECTOR: [u8; 4] = [0x69, 0x1f, 0x34, 0x31];
+
+/// The selector of `text(bytes32,string)`
+const TEXT_SELECTOR: [u8; 4] = [0x59, 0xd1, 0xd4, 0x3c];
+
+#[derive(Debug, Fail)]
+pub enum EnsError {
+    #[fail(display = "invalid character {:?} in name {}", _0, _1)]
+    InvalidCharacter(char, String),
+
+    #[fail(display = "invalid empty label in name {}", _0)]
+    InvalidEmptyLabel(String),
+}
+
+/// Returns the normalized form of the given name.
+/// Applies the UTS-46 mapping of case, full-width forms, and ideographic full stops,
+/// and rejects spaces, control characters, and ASCII symbols other than hyphens and underscores.
+/// Unicode compositions are not normalized, so names should be given in NFC.
+pub fn normalize(name: &str) -> Result<String, EnsError> {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.chars() {
+        let c = match c {
+            // Ideographic, full-width, and half-width full stops
+            '\u{3002}' | '\u{ff0e}' | '\u{ff61}' => '.',
+            // Full-width forms of ASCII
+            '\u{ff01}'..='\u{ff5e}' => std::char::from_u32(c as u32 - 0xfee0).unwrap_or(c),
+            _ => c,
+        };
+
+        match c {
+            'a'..='z' | '0'..='9' | '-' | '_' | '.' => normalized.push(c),
+            'A'..='Z' => normalized.push(c.to_ascii_lowercase()),
+            _ if c.is_ascii() || c.is_whitespace() || c.is_control() => {
+                return Err(EnsError::InvalidCharacter(c, name.into()))
+            }
+            _ => normalized.extend(c.to_lowercase()),
+        }
+    }
+
+    match normalized.is_empty() || normalized.split('.').all(|label| !label.is_empty()) {
+        true => Ok(normalized),
+        false => Err(EnsError::InvalidEmptyLabel(name.into())),
+    }
+}
+
+/// Returns the hash of the given label, which is expected to be normalized.
+pub fn labelhash(label: &str) -> [u8; 32] {
+    keccak256(label.as_bytes())
+}
+
+/// Returns the namehash of the given name, after normalizing it.
+/// The namehash of the empty name is zero, and the namehash of a name is the hash
+/// of the namehash of its parent followed by the labelhash of its first label.
+pub fn namehash(name: &str) -> Result<[u8; 32], EnsError> {
+    let normalized = normalize(name)?;
+    let mut node = [0u8; 32];
+    if !normalized.is_empty() {
+        for label in normalized.rsplit('.') {
+            node = keccak256(&[&node[..], &labelhash(label)[..]].concat());
+        }
+    }
+    Ok(node)
+}
+
+/// Represents the calls of the ENS registry and its public resolver
+/// https://docs.ens.domains/contract-api-reference/publicresolver
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ENS;
+
+impl ENS {
+    /// Returns the reverse name of the given address, which resolves to the primary name of the address.
+    pub fn reverse_name(address: &EthereumAddress) -> String {
+        format!("{}.addr.reverse", address.to_string()[2..].to_lowercase())
+    }
+
+    /// Returns the registry calldata to look up the resolver of the given name.
+    pub fn resolver(name: &str) -> Result<Vec<u8>, EnsError> {
+        Ok([RESOLVER_SELECTOR.to_vec(), namehash(name)?.to_vec()].concat())
+    }
+
+    /// Returns the resolver calldata to look up the Ethereum address of the given name.
+    pub fn addr(name: &str) -> Result<Vec<u8>, EnsError> {
+        Ok([ADDR_SELECTOR.to_vec(), namehash(name)?.to_vec()].concat())
+    }
+
+    /// Returns the resolver calldata to look up the address of the given name for the given SLIP-44 coin type.
+    /// Adheres to EIP-2304 (https://eips.ethereum.org/EIPS/eip-2304).
+    pub fn addr_with_coin_type(name: &str, coin_type: u64) -> Result<Vec<u8>, EnsError> {
+        let arguments = [
+            Token::FixedBytes(namehash(name)?.to_vec()),
+            Token::Uint(U256::from(coin_type)),
+        ];
+        Ok([ADDR_WITH_COIN_TYPE_SELECTOR.to_vec(), encode(&arguments)].concat())
+    }
+
+    /// Returns the resolver calldata to look up the text record of the given name and key.
+    /// Adheres to EIP-634 (https://eips.ethereum.org/EIPS/eip-634).
+    pub fn text(name: &str, key: &str) -> Result<Vec<u8>, EnsError> {
+        let arguments = [Token::FixedBytes(namehash(name)?.to_vec()), Token::String(key.into())];
+        Ok([TEXT_SELECTOR.to_vec(), encode(&arguments)].concat())
+    }
+
+    /// Returns the resolver calldata to look up the primary name of the given address.
+    pub fn name(address: &EthereumAddress) -> Result<Vec<u8>, EnsError> {
+        Ok([NAME_SELECTOR.to_vec(), namehash(&Self::reverse_name(address))?.to_vec()].concat())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const NAMEHASHES: [(&str, &str); 6] = [
+        ("", "0000000000000000000000000000000000000000000000000000000000000000"),
+        (
+            "eth",
+            "93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae",
+        ),
+        (
+            "foo.eth",
+            "de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f",
+        ),
+        (
+            "vitalik.eth",
+            "ee6c4522aab0003e8d14cd40a6af439055fd2577951148c14b6cea9a53475835",
+        ),
+        (
+            "sub.alice.eth",
+            "74d7e317f83d8c977da609d1997d9b4e15e081392c4c5959c7bf3f42c9f857a0",
+        ),
+        (
+            "wallet.ricmoo.eth",
+            "23cda1a00e999293bb3340534dd2e8c9f54890b620ad7aacb1ca0750a7659a18",
+        ),
+    ];
+
+    const ADDRESS: &str = "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65";
+
+    #[test]
+    fn test_namehash() {
+        NAMEHASHES.iter().for_each(|(name, expected_namehash)| {
+            assert_eq!(*expected_namehash, hex::encode(namehash(name).unwrap()));
+        });
+    }
+
+    #[test]
+    fn test_labelhash() {
+        assert_eq!(
+            "4f5b812789fc606be1b3b16908db13fc7a9adf7ca72641f84d75b47069d3d7f0",
+            hex::encode(labelhash("eth"))
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        const NAMES: [(&str, &str); 5] = [
+            ("Foo.ETH", "foo.eth"),
+            ("ｆｏｏ。ｅｔｈ", "foo.eth"),
+            ("vitalik．eth", "vitalik.eth"),
+            ("_dmarc.Foo-Bar.eth", "_dmarc.foo-bar.eth"),
+            ("ÖBB.eth", "öbb.eth"),
+        ];
+
+        NAMES.iter().for_each(|(name, expected_name)| {
+            assert_eq!(*expected_name, normalize(name).unwrap());
+        });
+        assert_eq!(namehash("foo.eth").unwrap(), namehash("ＦＯＯ.eth").unwrap());
+    }
+
+    #[test]
+    fn test_invalid_name() {
+        const NAMES: [&str; 6] = [
+            "foo..eth",
+            ".eth",
+            "foo.eth.",
+            "foo bar.eth",
+            "foo$.eth",
+            "foo\u{0}.eth",
+        ];
+
+        NAMES.iter().for_each(|name| {
+            assert!(normalize(name).is_err());
+            assert!(namehash(name).is_err());
+        });
+    }
+
+    #[test]
+    fn resolver() {
+        assert_eq!(
+            "0178b8bfee6c4522aab0003e8d14cd40a6af439055fd2577951148c14b6cea9a53475835",
+            hex::encode(ENS::resolver("vitalik.eth").unwrap())
+        );
+    }
+
+    #[test]
+    fn addr() {
+        assert_eq!(
+            "3b3b57deee6c4522aab0003e8d14cd40a6af439055fd2577951148c14b6cea9a53475835",
+            hex::encode(ENS::addr("Vitalik.eth").unwrap())
+        );
+    }
+
+    #[test]
+    fn addr_with_coin_type() {
+        assert_eq!(
+            "f1cb7e06787192fc5378cc32aa956ddfdedbf26b24e8d78e40109add0eea2c1a012c3dec0000000000000000000000000000000000000000000000000000000000000000",
+            hex::encode(ENS::addr_with_coin_type("alice.eth", 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            "59d1d43cee6c4522aab0003e8d14cd40a6af439055fd2577951148c14b6cea9a534758350000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000000375726c0000000000000000000000000000000000000000000000000000000000",
+            hex::encode(ENS::text("vitalik.eth", "url").unwrap())
+        );
+    }
+
+    #[test]
+    fn name() {
+        let address = EthereumAddress::from_str(ADDRESS).unwrap();
+        assert_eq!(
+            "b5d590a6abf5e349c1b6c511bc87ceabfb3d7e65.addr.reverse",
+            ENS::reverse_name(&address)
+        );
+        assert_eq!(
+            "691f34313936b801c63e72aefb8ca9ed0c752adab53686ddcf95fa3627b70a811df0e6da",
+            hex::encode(ENS::name(&address).unwrap())
+        );
+    }
+}
@@ -6,7 +6,10 @@ use std::fmt;
 /// Represents the format of a Ethereum address
 #[derive(Serialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum EthereumFormat {
+    /// EIP-55 mixed-case checksum hex, e.g. 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed
     Standard,
+    /// Inter exchange Client Address Protocol, e.g. XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS
+    ICAP,
 }
 
 impl Format for EthereumFormat {}
@@ -4,6 +4,12 @@
 #![warn(unused_extern_crates)]
 #![forbid(unsafe_code)]
 
+#[macro_use]
+extern crate failure;
+
+pub mod abi;
+pub use self::abi::*;
+
 pub mod address;
 pub use self::address::*;
 
@@ -13,6 +19,9 @@ pub use self::amount::*;
 pub mod derivation_path;
 pub use self::derivation_path::*;
 
+pub mod ens;
+pub use self::ens::*;
+
 pub mod extended_private_key;
 pub use self::extended_private_key::*;
 
@@ -34,8 +43,25 @@ pub use self::private_key::*;
 pub mod public_key;
 pub use self::public_key::*;
 
+pub mod safe;
+pub use self::safe::*;
+
+pub mod siwe;
+pub use self::siwe::*;
+
 pub mod transaction;
 pub use self::transaction::*;
 
+pub mod typed_data;
+pub use self::typed_data::*;
+
+pub mod user_operation;
+pub use self::user_operation::*;
+
+#[cfg(feature = "std")]
+pub mod vanity;
+#[cfg(feature = "std")]
+pub use self::vanity::*;
+
 pub mod wordlist;
 pub use self::wordlist::*;
@@ -0,0 +1,36 @@
+use crate::network::EthereumNetwork;
+use wagyu_model::{ChildIndex, Network, NetworkError};
+
+use serde::Serialize;
+use std::{fmt, str::FromStr};
+
+/// Represents an EVM-compatible network, whose chain id is given by the transaction parameters.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct CustomChain;
+
+impl Network for CustomChain {
+    const NAME: &'static str = "custom";
+}
+
+impl EthereumNetwork for CustomChain {
+    const CHAIN_ID: u64 = 0;
+    const NETWORK_ID: u64 = 0;
+    const HD_COIN_TYPE: ChildIndex = ChildIndex::Hardened(60);
+}
+
+impl FromStr for CustomChain {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            Self::NAME => Ok(Self),
+            _ => Err(NetworkError::InvalidNetwork(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for CustomChain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Self::NAME)
+    }
+}
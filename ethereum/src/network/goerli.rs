@@ -13,8 +13,8 @@ impl Network for Goerli {
 }
 
 impl EthereumNetwork for Goerli {
-    const CHAIN_ID: u32 = 5;
-    const NETWORK_ID: u32 = 5;
+    const CHAIN_ID: u64 = 5;
+    const NETWORK_ID: u64 = 5;
     const HD_COIN_TYPE: ChildIndex = ChildIndex::Hardened(1);
 }
 
@@ -0,0 +1,36 @@
+use crate::network::EthereumNetwork;
+use wagyu_model::{ChildIndex, Network, NetworkError};
+
+use serde::Serialize;
+use std::{fmt, str::FromStr};
+
+/// Represents an Ethereum test network (PoS).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Holesky;
+
+impl Network for Holesky {
+    const NAME: &'static str = "holesky";
+}
+
+impl EthereumNetwork for Holesky {
+    const CHAIN_ID: u64 = 17000;
+    const NETWORK_ID: u64 = 17000;
+    const HD_COIN_TYPE: ChildIndex = ChildIndex::Hardened(1);
+}
+
+impl FromStr for Holesky {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            Self::NAME => Ok(Self),
+            _ => Err(NetworkError::InvalidNetwork(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for Holesky {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Self::NAME)
+    }
+}
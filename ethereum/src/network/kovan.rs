@@ -13,8 +13,8 @@ impl Network for Kovan {
 }
 
 impl EthereumNetwork for Kovan {
-    const CHAIN_ID: u32 = 42;
-    const NETWORK_ID: u32 = 42;
+    const CHAIN_ID: u64 = 42;
+    const NETWORK_ID: u64 = 42;
     const HD_COIN_TYPE: ChildIndex = ChildIndex::Hardened(1);
 }
 
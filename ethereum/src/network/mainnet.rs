@@ -13,8 +13,8 @@ impl Network for Mainnet {
 }
 
 impl EthereumNetwork for Mainnet {
-    const CHAIN_ID: u32 = 1;
-    const NETWORK_ID: u32 = 1;
+    const CHAIN_ID: u64 = 1;
+    const NETWORK_ID: u64 = 1;
     const HD_COIN_TYPE: ChildIndex = ChildIndex::Hardened(60);
 }
 
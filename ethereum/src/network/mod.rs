@@ -1,8 +1,14 @@
 use wagyu_model::{ChildIndex, Network};
 
+pub mod custom_chain;
+pub use self::custom_chain::*;
+
 pub mod goerli;
 pub use self::goerli::*;
 
+pub mod holesky;
+pub use self::holesky::*;
+
 pub mod kovan;
 pub use self::kovan::*;
 
@@ -15,10 +21,14 @@ pub use self::rinkeby::*;
 pub mod ropsten;
 pub use self::ropsten::*;
 
+pub mod sepolia;
+pub use self::sepolia::*;
+
 /// The interface for an Ethereum network.
 pub trait EthereumNetwork: Network {
-    const CHAIN_ID: u32;
-    const NETWORK_ID: u32;
+    /// The EIP-155 chain id, or 0 if the chain id is given by the transaction parameters.
+    const CHAIN_ID: u64;
+    const NETWORK_ID: u64;
     const HD_PURPOSE: ChildIndex = ChildIndex::Hardened(44);
     const HD_COIN_TYPE: ChildIndex;
 }
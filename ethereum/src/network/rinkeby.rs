@@ -13,8 +13,8 @@ impl Network for Rinkeby {
 }
 
 impl EthereumNetwork for Rinkeby {
-    const CHAIN_ID: u32 = 4;
-    const NETWORK_ID: u32 = 4;
+    const CHAIN_ID: u64 = 4;
+    const NETWORK_ID: u64 = 4;
     const HD_COIN_TYPE: ChildIndex = ChildIndex::Hardened(1);
 }
 
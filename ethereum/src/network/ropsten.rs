@@ -13,8 +13,8 @@ impl Network for Ropsten {
 }
 
 impl EthereumNetwork for Ropsten {
-    const CHAIN_ID: u32 = 3;
-    const NETWORK_ID: u32 = 3;
+    const CHAIN_ID: u64 = 3;
+    const NETWORK_ID: u64 = 3;
     const HD_COIN_TYPE: ChildIndex = ChildIndex::Hardened(1);
 }
 
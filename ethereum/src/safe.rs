@@ -0,0 +1,498 @@
+//!
+//! Safe
+//!
+//! This module contains the hashing and signing of transactions of Safe (formerly Gnosis Safe) multisig accounts.
+//! https://github.com/safe-global/safe-smart-account
+//!
+//! A Safe transaction is signed as the EIP-712 hash of its `SafeTx` struct in the domain of the chain id and the
+//! Safe address, as in Safe v1.3.0 and later. The owner signatures are concatenated in ascending order of owner
+//! address and submitted with the transaction in a call to `execTransaction` of the Safe.
+//!
+
+use crate::abi::{encode, Token};
+use crate::address::EthereumAddress;
+use crate::format::EthereumFormat;
+use crate::private_key::EthereumPrivateKey;
+use crate::public_key::EthereumPublicKey;
+use crate::siwe::personal_message_hash;
+use wagyu_model::{AddressError, PrivateKey, PublicKey};
+
+use ethereum_types::U256;
+use std::{fmt, str::FromStr};
+use tiny_keccak::keccak256;
+
+/// The type of the EIP-712 domain of a Safe
+const DOMAIN_TYPE: &str = "EIP712Domain(uint256 chainId,address verifyingContract)";
+
+/// The type of the EIP-712 struct of a Safe transaction
+const SAFE_TX_TYPE: &str = "SafeTx(address to,uint256 value,bytes data,uint8 operation,uint256 safeTxGas,uint256 baseGas,uint256 gasPrice,address gasToken,address refundReceiver,uint256 nonce)";
+
+/// The selector of `execTransaction(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,bytes)`
+const EXEC_TRANSACTION_SELECTOR: [u8; 4] = [0x6a, 0x76, 0x12, 0x02];
+
+/// The offset added to the recovery id of signatures of the EIP-191 message of the hash
+const ETH_SIGN_V_OFFSET: u8 = 4;
+
+#[derive(Debug, Fail)]
+pub enum SafeError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "duplicate signature of owner {}", _0)]
+    DuplicateOwner(String),
+
+    #[fail(display = "invalid signature: {}", _0)]
+    InvalidSignature(String),
+}
+
+impl From<AddressError> for SafeError {
+    fn from(error: AddressError) -> Self {
+        SafeError::AddressError(error)
+    }
+}
+
+impl From<secp256k1::Error> for SafeError {
+    fn from(error: secp256k1::Error) -> Self {
+        SafeError::InvalidSignature(format!("{:?}", error))
+    }
+}
+
+/// Represents the operation of a Safe transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafeOperation {
+    Call = 0,
+    DelegateCall = 1,
+}
+
+/// Represents the scheme of an owner signature of a Safe transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafeSignatureType {
+    /// An ECDSA signature of the Safe transaction hash, with v of 27 or 28
+    Ecdsa,
+    /// An ECDSA signature of the EIP-191 message of the hash, as produced by `eth_sign`, with v of 31 or 32
+    EthSign,
+    /// A hash approved on-chain by the owner with `approveHash`, or the sender of the transaction, with v of 1
+    ApprovedHash,
+}
+
+/// Represents an owner signature of a Safe transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafeSignature {
+    /// The owner who produced the signature
+    pub owner: EthereumAddress,
+    /// The scheme of the signature
+    pub signature_type: SafeSignatureType,
+    /// The 65-byte signature (r || s || v) as verified by the Safe
+    pub signature: Vec<u8>,
+}
+
+impl SafeSignature {
+    /// Returns the signature of the given owner of an approved hash, which carries the owner in r.
+    pub fn approved_hash(owner: &EthereumAddress) -> Result<Self, SafeError> {
+        let mut signature = vec![0u8; 12];
+        signature.extend(owner.to_bytes()?);
+        signature.extend_from_slice(&[0u8; 32]);
+        signature.push(1);
+        Ok(Self {
+            owner: owner.clone(),
+            signature_type: SafeSignatureType::ApprovedHash,
+            signature,
+        })
+    }
+}
+
+impl fmt::Display for SafeSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(&self.signature))
+    }
+}
+
+/// Returns the concatenation of the given owner signatures in ascending order of owner address, as
+/// required by `checkSignatures` of the Safe.
+pub fn encode_safe_signatures(signatures: &[SafeSignature]) -> Result<Vec<u8>, SafeError> {
+    let mut signatures: Vec<(Vec<u8>, &SafeSignature)> = signatures
+        .iter()
+        .map(|signature| Ok((signature.owner.to_bytes()?, signature)))
+        .collect::<Result<_, SafeError>>()?;
+    signatures.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    for pair in signatures.windows(2) {
+        if pair[0].0 == pair[1].0 {
+            return Err(SafeError::DuplicateOwner(pair[0].1.owner.to_string()));
+        }
+    }
+    Ok(signatures
+        .iter()
+        .flat_map(|(_, signature)| signature.signature.clone())
+        .collect())
+}
+
+/// Represents a transaction of a Safe multisig account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafeTransaction {
+    /// The address of the Safe
+    pub safe: EthereumAddress,
+    /// The chain id of the Safe
+    pub chain_id: u64,
+    /// The destination of the call of the Safe
+    pub to: EthereumAddress,
+    /// The amount of the call (in wei)
+    pub value: U256,
+    /// The data of the call
+    pub data: Vec<u8>,
+    /// The operation of the call
+    pub operation: SafeOperation,
+    /// The gas limit of the call, or zero for all available gas
+    pub safe_tx_gas: U256,
+    /// The gas of the transaction outside of the call, refunded with the call gas
+    pub base_gas: U256,
+    /// The gas price of the refund, or zero for no refund
+    pub gas_price: U256,
+    /// The token of the refund, or the zero address for ether
+    pub gas_token: EthereumAddress,
+    /// The receiver of the refund, or the zero address for the sender of the transaction
+    pub refund_receiver: EthereumAddress,
+    /// The nonce of the Safe
+    pub nonce: U256,
+}
+
+impl SafeTransaction {
+    /// Returns a call of the given Safe without a gas refund.
+    pub fn new(
+        safe: &EthereumAddress,
+        chain_id: u64,
+        to: &EthereumAddress,
+        value: U256,
+        data: &[u8],
+        nonce: U256,
+    ) -> Result<Self, SafeError> {
+        let zero_address = EthereumAddress::from_str(&hex::encode([0u8; 20]))?;
+        Ok(Self {
+            safe: safe.clone(),
+            chain_id,
+            to: to.clone(),
+            value,
+            data: data.to_vec(),
+            operation: SafeOperation::Call,
+            safe_tx_gas: U256::zero(),
+            base_gas: U256::zero(),
+            gas_price: U256::zero(),
+            gas_token: zero_address.clone(),
+            refund_receiver: zero_address,
+            nonce,
+        })
+    }
+
+    /// Returns the EIP-712 domain separator of the Safe.
+    pub fn domain_separator(&self) -> [u8; 32] {
+        keccak256(&encode(&[
+            Token::FixedBytes(keccak256(DOMAIN_TYPE.as_bytes()).to_vec()),
+            Token::Uint(U256::from(self.chain_id)),
+            Token::Address(self.safe.clone()),
+        ]))
+    }
+
+    /// Returns the EIP-712 struct hash of the transaction.
+    pub fn struct_hash(&self) -> [u8; 32] {
+        keccak256(&encode(&[
+            Token::FixedBytes(keccak256(SAFE_TX_TYPE.as_bytes()).to_vec()),
+            Token::Address(self.to.clone()),
+            Token::Uint(self.value),
+            Token::FixedBytes(keccak256(&self.data).to_vec()),
+            Token::Uint(U256::from(self.operation as u8)),
+            Token::Uint(self.safe_tx_gas),
+            Token::Uint(self.base_gas),
+            Token::Uint(self.gas_price),
+            Token::Address(self.gas_token.clone()),
+            Token::Address(self.refund_receiver.clone()),
+            Token::Uint(self.nonce),
+        ]))
+    }
+
+    /// Returns the hash of the transaction, as computed by `getTransactionHash` of the Safe.
+    pub fn to_safe_tx_hash(&self) -> [u8; 32] {
+        keccak256(&[&[0x19, 0x01], &self.domain_separator()[..], &self.struct_hash()[..]].concat())
+    }
+
+    /// Returns the owner signature of the transaction hash with the given private key.
+    pub fn sign(&self, private_key: &EthereumPrivateKey) -> Result<SafeSignature, SafeError> {
+        Ok(SafeSignature {
+            owner: private_key.to_address(&EthereumFormat::Standard)?,
+            signature_type: SafeSignatureType::Ecdsa,
+            signature: sign_hash(&self.to_safe_tx_hash(), private_key, 27),
+        })
+    }
+
+    /// Returns the owner signature of the EIP-191 message of the transaction hash with the given private key,
+    /// as produced by wallets which only support `eth_sign`.
+    pub fn sign_eth_sign(&self, private_key: &EthereumPrivateKey) -> Result<SafeSignature, SafeError> {
+        Ok(SafeSignature {
+            owner: private_key.to_address(&EthereumFormat::Standard)?,
+            signature_type: SafeSignatureType::EthSign,
+            signature: sign_hash(
+                &personal_message_hash(&self.to_safe_tx_hash()),
+                private_key,
+                27 + ETH_SIGN_V_OFFSET,
+            ),
+        })
+    }
+
+    /// Returns the owner signature of the given 65-byte signature (r || s || v) of the transaction,
+    /// recovering the owner from its v as verified by `checkSignatures` of the Safe.
+    pub fn recover_signature(&self, signature: &[u8]) -> Result<SafeSignature, SafeError> {
+        if signature.len() != 65 {
+            return Err(SafeError::InvalidSignature(format!(
+                "invalid signature length {}",
+                signature.len()
+            )));
+        }
+
+        let hash = self.to_safe_tx_hash();
+        let (owner, signature_type) = match signature[64] {
+            1 if signature[..12] == [0u8; 12] => (
+                EthereumAddress::from_str(&hex::encode(&signature[12..32]))?,
+                SafeSignatureType::ApprovedHash,
+            ),
+            27 | 28 => (
+                recover_hash(&hash, &signature[..64], signature[64] - 27)?,
+                SafeSignatureType::Ecdsa,
+            ),
+            31 | 32 => (
+                recover_hash(
+                    &personal_message_hash(&hash),
+                    &signature[..64],
+                    signature[64] - 27 - ETH_SIGN_V_OFFSET,
+                )?,
+                SafeSignatureType::EthSign,
+            ),
+            v => return Err(SafeError::InvalidSignature(format!("unsupported signature type {}", v))),
+        };
+        Ok(SafeSignature {
+            owner,
+            signature_type,
+            signature: signature.to_vec(),
+        })
+    }
+
+    /// Returns the calldata of `execTransaction` of the transaction with the given owner signatures,
+    /// to be sent to the Safe as the data of an Ethereum transaction.
+    pub fn to_exec_transaction_data(&self, signatures: &[SafeSignature]) -> Result<Vec<u8>, SafeError> {
+        let arguments = [
+            Token::Address(self.to.clone()),
+            Token::Uint(self.value),
+            Token::Bytes(self.data.clone()),
+            Token::Uint(U256::from(self.operation as u8)),
+            Token::Uint(self.safe_tx_gas),
+            Token::Uint(self.base_gas),
+            Token::Uint(self.gas_price),
+            Token::Address(self.gas_token.clone()),
+            Token::Address(self.refund_receiver.clone()),
+            Token::Bytes(encode_safe_signatures(signatures)?),
+        ];
+        Ok([EXEC_TRANSACTION_SELECTOR.to_vec(), encode(&arguments)].concat())
+    }
+}
+
+/// Returns the 65-byte signature (r || s || v) of the given hash, with v offset by the given value.
+fn sign_hash(hash: &[u8; 32], private_key: &EthereumPrivateKey, v_offset: u8) -> Vec<u8> {
+    let (signature, recovery_id) =
+        secp256k1::sign(&secp256k1::Message::parse(hash), &private_key.to_secp256k1_secret_key());
+
+    let mut signature = signature.serialize().to_vec();
+    signature.push(Into::<i32>::into(recovery_id) as u8 + v_offset);
+    signature
+}
+
+/// Returns the address that produced the given 64-byte signature (r || s) of the given hash.
+fn recover_hash(hash: &[u8; 32], signature: &[u8], recovery_id: u8) -> Result<EthereumAddress, SafeError> {
+    let public_key = secp256k1::recover(
+        &secp256k1::Message::parse(hash),
+        &secp256k1::Signature::parse_slice(signature)?,
+        &secp256k1::RecoveryId::parse(recovery_id)?,
+    )?;
+    Ok(EthereumPublicKey::from_secp256k1_public_key(public_key).to_address(&EthereumFormat::Standard)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::ERC20;
+    use crate::typed_data::EthereumTypedData;
+
+    const SAFE: &str = "0x5afE000000000000000000000000000000000001";
+
+    /// The private keys of the owners, in the order of their signature types: ECDSA, eth_sign and approved hash
+    const OWNERS: [&str; 3] = [
+        "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+        "5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a",
+    ];
+
+    pub struct SafeTestCase {
+        transaction: fn() -> SafeTransaction,
+        domain_separator: &'static str,
+        safe_tx_hash: &'static str,
+        signatures: [&'static str; 3],
+        exec_transaction_data: &'static str,
+    }
+
+    fn erc20_transfer() -> SafeTransaction {
+        let receiver = EthereumAddress::from_str("0x70997970C51812dc3A010C7d01b50e0d17dc79C8").unwrap();
+        SafeTransaction::new(
+            &EthereumAddress::from_str(SAFE).unwrap(),
+            1,
+            &EthereumAddress::from_str("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap(),
+            U256::zero(),
+            &ERC20::transfer(&receiver, U256::from(1_000_000)),
+            U256::from(7),
+        )
+        .unwrap()
+    }
+
+    fn refunded_transfer() -> SafeTransaction {
+        let mut transaction = SafeTransaction::new(
+            &EthereumAddress::from_str(SAFE).unwrap(),
+            11155111,
+            &EthereumAddress::from_str("0x70997970C51812dc3A010C7d01b50e0d17dc79C8").unwrap(),
+            U256::from(1_000_000_000_000_000_000u64),
+            &[],
+            U256::zero(),
+        )
+        .unwrap();
+        transaction.safe_tx_gas = U256::from(50000);
+        transaction.base_gas = U256::from(21000);
+        transaction.gas_price = U256::from(1_000_000_000);
+        transaction.refund_receiver = EthereumAddress::from_str("0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC").unwrap();
+        transaction
+    }
+
+    const TEST_CASES: [SafeTestCase; 2] = [
+        SafeTestCase {
+            transaction: erc20_transfer,
+            domain_separator: "d77bbce169dd754ef0f1b1c36ffabcf9e6b8c356a2935e071498fc0eae2b8183",
+            safe_tx_hash: "70867176a5cb609a21b984a2735ca7516791150bd4d00b4d74da5c0087c44693",
+            signatures: [
+                "6751946d501d363d1f707aa13bddd2620a5b9e9a48a6ee2fefc4955642535bf3716261257f5a9cdfdf14f638e19d31fb3d08b96f4b16c5a9b767d2c33536c3d21b",
+                "9979c66a6f063ebe17eb677f6141e38762444e577f0c86a7677e534fc7962b574f378dbf8efcbdf153d225fdfa83cf4e245efd439b027be952cf93517d98be8d1f",
+                "0000000000000000000000003c44cdddb6a900fa2b585dd299e03d12fa4293bc000000000000000000000000000000000000000000000000000000000000000001",
+            ],
+            exec_transaction_data: "6a761202000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb480000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000014000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001c00000000000000000000000000000000000000000000000000000000000000044a9059cbb00000000000000000000000070997970c51812dc3a010c7d01b50e0d17dc79c800000000000000000000000000000000000000000000000000000000000f42400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c30000000000000000000000003c44cdddb6a900fa2b585dd299e03d12fa4293bc0000000000000000000000000000000000000000000000000000000000000000019979c66a6f063ebe17eb677f6141e38762444e577f0c86a7677e534fc7962b574f378dbf8efcbdf153d225fdfa83cf4e245efd439b027be952cf93517d98be8d1f6751946d501d363d1f707aa13bddd2620a5b9e9a48a6ee2fefc4955642535bf3716261257f5a9cdfdf14f638e19d31fb3d08b96f4b16c5a9b767d2c33536c3d21b0000000000000000000000000000000000000000000000000000000000",
+        },
+        SafeTestCase {
+            transaction: refunded_transfer,
+            domain_separator: "6890ba378c24d87d78203c63dddf71c82ace210ec5c331d38127281de3c645a4",
+            safe_tx_hash: "41beb291db7c71ad64150bba5231e2a15f928eb3f84b57de82cb44bcbab20c0e",
+            signatures: [
+                "89826b3506ff5893f999d3a146c20751547f4f9f8ff026f14c42857deafd94f41cf3ef3e033ca309d68bb41938d42abb81af5d7325832a78d3afbe15a22b81ef1c",
+                "c94b3bd29a780b7bedd7bc42750f0884da0bacb10f68bbb81b64776561310e2d6c406d2346ae6694440a6a34018debc53fb39ddf1d4c07155447af93bae670c320",
+                "0000000000000000000000003c44cdddb6a900fa2b585dd299e03d12fa4293bc000000000000000000000000000000000000000000000000000000000000000001",
+            ],
+            exec_transaction_data: "6a76120200000000000000000000000070997970c51812dc3a010c7d01b50e0d17dc79c80000000000000000000000000000000000000000000000000de0b6b3a764000000000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c3500000000000000000000000000000000000000000000000000000000000005208000000000000000000000000000000000000000000000000000000003b9aca0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003c44cdddb6a900fa2b585dd299e03d12fa4293bc0000000000000000000000000000000000000000000000000000000000000160000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c30000000000000000000000003c44cdddb6a900fa2b585dd299e03d12fa4293bc000000000000000000000000000000000000000000000000000000000000000001c94b3bd29a780b7bedd7bc42750f0884da0bacb10f68bbb81b64776561310e2d6c406d2346ae6694440a6a34018debc53fb39ddf1d4c07155447af93bae670c32089826b3506ff5893f999d3a146c20751547f4f9f8ff026f14c42857deafd94f41cf3ef3e033ca309d68bb41938d42abb81af5d7325832a78d3afbe15a22b81ef1c0000000000000000000000000000000000000000000000000000000000",
+        },
+    ];
+
+    fn signatures(transaction: &SafeTransaction) -> Vec<SafeSignature> {
+        let private_key = |index: usize| EthereumPrivateKey::from_str(OWNERS[index]).unwrap();
+        vec![
+            transaction.sign(&private_key(0)).unwrap(),
+            transaction.sign_eth_sign(&private_key(1)).unwrap(),
+            SafeSignature::approved_hash(&private_key(2).to_address(&EthereumFormat::Standard).unwrap()).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn to_safe_tx_hash() {
+        TEST_CASES.iter().for_each(|test_case| {
+            let transaction = (test_case.transaction)();
+            assert_eq!(test_case.domain_separator, hex::encode(transaction.domain_separator()));
+            assert_eq!(test_case.safe_tx_hash, hex::encode(transaction.to_safe_tx_hash()));
+        });
+    }
+
+    #[test]
+    fn to_safe_tx_hash_typed_data() {
+        let transaction = erc20_transfer();
+        let typed_data = format!(
+            r#"{{"types":{{"EIP712Domain":[{{"name":"chainId","type":"uint256"}},{{"name":"verifyingContract","type":"address"}}],"SafeTx":[{{"name":"to","type":"address"}},{{"name":"value","type":"uint256"}},{{"name":"data","type":"bytes"}},{{"name":"operation","type":"uint8"}},{{"name":"safeTxGas","type":"uint256"}},{{"name":"baseGas","type":"uint256"}},{{"name":"gasPrice","type":"uint256"}},{{"name":"gasToken","type":"address"}},{{"name":"refundReceiver","type":"address"}},{{"name":"nonce","type":"uint256"}}]}},"primaryType":"SafeTx","domain":{{"chainId":1,"verifyingContract":"{}"}},"message":{{"to":"{}","value":"0","data":"0x{}","operation":0,"safeTxGas":"0","baseGas":"0","gasPrice":"0","gasToken":"{}","refundReceiver":"{}","nonce":7}}}}"#,
+            transaction.safe,
+            transaction.to,
+            hex::encode(&transaction.data),
+            transaction.gas_token,
+            transaction.refund_receiver
+        );
+        let typed_data = EthereumTypedData::from_str(&typed_data).unwrap();
+        assert_eq!(typed_data.to_signing_hash().unwrap(), transaction.to_safe_tx_hash());
+    }
+
+    #[test]
+    fn sign() {
+        TEST_CASES.iter().for_each(|test_case| {
+            let transaction = (test_case.transaction)();
+            signatures(&transaction)
+                .iter()
+                .zip(test_case.signatures.iter())
+                .for_each(|(signature, expected_signature)| {
+                    assert_eq!(format!("0x{}", expected_signature), signature.to_string());
+                });
+        });
+    }
+
+    #[test]
+    fn recover_signature() {
+        TEST_CASES.iter().for_each(|test_case| {
+            let transaction = (test_case.transaction)();
+            signatures(&transaction).iter().for_each(|signature| {
+                assert_eq!(*signature, transaction.recover_signature(&signature.signature).unwrap());
+            });
+        });
+    }
+
+    #[test]
+    fn to_exec_transaction_data() {
+        TEST_CASES.iter().for_each(|test_case| {
+            let transaction = (test_case.transaction)();
+            let mut signatures = signatures(&transaction);
+            let data = transaction.to_exec_transaction_data(&signatures).unwrap();
+            assert_eq!(test_case.exec_transaction_data, hex::encode(&data));
+
+            // The signatures are sorted by owner, regardless of their order.
+            signatures.reverse();
+            assert_eq!(data, transaction.to_exec_transaction_data(&signatures).unwrap());
+        });
+    }
+
+    mod invalid {
+        use super::*;
+
+        #[test]
+        fn encode_safe_signatures_duplicate_owner() {
+            let transaction = erc20_transfer();
+            let private_key = EthereumPrivateKey::from_str(OWNERS[0]).unwrap();
+            let signatures = [
+                transaction.sign(&private_key).unwrap(),
+                transaction.sign_eth_sign(&private_key).unwrap(),
+            ];
+            assert!(encode_safe_signatures(&signatures).is_err());
+            assert!(transaction.to_exec_transaction_data(&signatures).is_err());
+        }
+
+        #[test]
+        fn recover_signature_invalid() {
+            let transaction = erc20_transfer();
+            let signature = hex::decode(TEST_CASES[0].signatures[0]).unwrap();
+            assert!(transaction.recover_signature(&signature[..64]).is_err());
+
+            let mut invalid_signature = signature.clone();
+            invalid_signature[64] = 0;
+            assert!(transaction.recover_signature(&invalid_signature).is_err());
+
+            // A signature of another transaction recovers another owner.
+            let other = refunded_transfer();
+            let owner = EthereumPrivateKey::from_str(OWNERS[0])
+                .unwrap()
+                .to_address(&EthereumFormat::Standard)
+                .unwrap();
+            assert_ne!(owner, other.recover_signature(&signature).unwrap().owner);
+        }
+    }
+}
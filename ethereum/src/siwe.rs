@@ -0,0 +1,776 @@
+//!
+//! Sign-In with Ethereum
+//!
+//! This module contains the construction, parsing, signing and verification of EIP-4361 messages.
+//! https://eips.ethereum.org/EIPS/eip-4361
+//!
+//! Messages are signed as EIP-191 personal messages, `keccak256("\x19Ethereum Signed Message:\n" || len || message)`.
+//! Parsing is strict: the message must follow the ABNF of the EIP exactly, including the EIP-55 checksum
+//! of the address, so that the string which was signed is the string which is displayed.
+//!
+
+use crate::address::EthereumAddress;
+use crate::format::EthereumFormat;
+use crate::private_key::EthereumPrivateKey;
+use crate::public_key::EthereumPublicKey;
+use wagyu_model::{AddressError, PublicKey};
+
+use rand::{distributions::Alphanumeric, Rng};
+use std::{fmt, iter::Peekable, str::FromStr};
+use tiny_keccak::keccak256;
+
+/// The version of the message format
+pub const SIWE_VERSION: &str = "1";
+
+/// The suffix of the first line of the message, after the domain
+const PREAMBLE: &str = " wants you to sign in with your Ethereum account:";
+
+/// The length of the nonces generated by `generate_nonce`
+const NONCE_LENGTH: usize = 17;
+
+/// The minimum length of a nonce
+const MIN_NONCE_LENGTH: usize = 8;
+
+#[derive(Debug, Fail)]
+pub enum SiweError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "expected domain {}, found {}", _0, _1)]
+    DomainMismatch(String, String),
+
+    #[fail(display = "message expired at {}", _0)]
+    ExpiredMessage(String),
+
+    #[fail(display = "invalid {}: {}", _0, _1)]
+    InvalidField(&'static str, String),
+
+    #[fail(display = "invalid signature: {}", _0)]
+    InvalidSignature(String),
+
+    #[fail(display = "expected signer {}, found {}", _0, _1)]
+    InvalidSigner(String, String),
+
+    #[fail(display = "missing field {}", _0)]
+    MissingField(&'static str),
+
+    #[fail(display = "message is not valid before {}", _0)]
+    NotYetValid(String),
+
+    #[fail(display = "unexpected line {:?}", _0)]
+    UnexpectedLine(String),
+}
+
+impl From<AddressError> for SiweError {
+    fn from(error: AddressError) -> Self {
+        SiweError::AddressError(error)
+    }
+}
+
+impl From<secp256k1::Error> for SiweError {
+    fn from(error: secp256k1::Error) -> Self {
+        SiweError::InvalidSignature(format!("{:?}", error))
+    }
+}
+
+/// Returns the EIP-191 hash of the given personal message, as signed by `personal_sign`.
+/// https://eips.ethereum.org/EIPS/eip-191
+pub fn personal_message_hash(message: &[u8]) -> [u8; 32] {
+    let mut data = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    data.extend_from_slice(message);
+    keccak256(&data)
+}
+
+/// Returns a random alphanumeric nonce for a message.
+pub fn generate_nonce<R: Rng>(rng: &mut R) -> String {
+    (0..NONCE_LENGTH).map(|_| rng.sample(Alphanumeric)).collect()
+}
+
+/// Returns the RFC 3339 representation of the given unix timestamp in UTC, e.g. 2021-09-30T16:25:24Z.
+pub fn format_timestamp(timestamp: i64) -> String {
+    let (days, seconds) = (timestamp.div_euclid(86400), timestamp.rem_euclid(86400));
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Returns the unix timestamp of the given RFC 3339 date-time, with fractional seconds truncated.
+pub fn parse_timestamp(timestamp: &str) -> Result<i64, SiweError> {
+    let invalid = || SiweError::InvalidField("timestamp", timestamp.into());
+    let bytes = timestamp.as_bytes();
+    let number = |start: usize, length: usize| -> Result<i64, SiweError> {
+        match bytes.get(start..start + length) {
+            Some(digits) if digits.iter().all(u8::is_ascii_digit) => Ok(digits
+                .iter()
+                .fold(0, |number, digit| number * 10 + (digit - b'0') as i64)),
+            _ => Err(invalid()),
+        }
+    };
+    let separator = |index: usize, expected: &[u8]| -> Result<(), SiweError> {
+        match bytes.get(index) {
+            Some(byte) if expected.contains(byte) => Ok(()),
+            _ => Err(invalid()),
+        }
+    };
+
+    let (year, month, day) = (number(0, 4)?, number(5, 2)?, number(8, 2)?);
+    let (hour, minute, second) = (number(11, 2)?, number(14, 2)?, number(17, 2)?);
+    separator(4, b"-")?;
+    separator(7, b"-")?;
+    separator(10, b"Tt")?;
+    separator(13, b":")?;
+    separator(16, b":")?;
+
+    let mut index = 19;
+    if bytes.get(index) == Some(&b'.') {
+        index += 1;
+        let digits = bytes[index..].iter().take_while(|byte| byte.is_ascii_digit()).count();
+        if digits == 0 {
+            return Err(invalid());
+        }
+        index += digits;
+    }
+
+    let offset = match bytes.get(index) {
+        Some(b'Z') | Some(b'z') if bytes.len() == index + 1 => 0,
+        Some(sign @ b'+') | Some(sign @ b'-') if bytes.len() == index + 6 => {
+            separator(index + 3, b":")?;
+            let (offset_hour, offset_minute) = (number(index + 1, 2)?, number(index + 4, 2)?);
+            if offset_hour > 23 || offset_minute > 59 {
+                return Err(invalid());
+            }
+            let offset = offset_hour * 3600 + offset_minute * 60;
+            match sign {
+                b'+' => offset,
+                _ => -offset,
+            }
+        }
+        _ => return Err(invalid()),
+    };
+
+    let days_in_month = match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    if month < 1 || month > 12 || day < 1 || day > days_in_month || hour > 23 || minute > 59 || second > 60 {
+        return Err(invalid());
+    }
+
+    Ok(days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second - offset)
+}
+
+/// Returns the number of days since 1970-01-01 of the given proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Returns the proleptic Gregorian date of the given number of days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Returns `true` if the given character is an RFC 3986 unreserved character.
+fn is_unreserved(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "-._~".contains(c)
+}
+
+/// Returns `true` if the given character is an RFC 3986 sub-delimiter.
+fn is_sub_delim(c: char) -> bool {
+    "!$&'()*+,;=".contains(c)
+}
+
+/// Returns `true` if the given string is a valid URI scheme, ALPHA *( ALPHA / DIGIT / "+" / "-" / "." ).
+fn is_scheme(scheme: &str) -> bool {
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+}
+
+/// Returns `true` if the given string is an RFC 3986 URI, a scheme followed by printable ASCII.
+fn is_uri(uri: &str) -> bool {
+    match uri.find(':') {
+        Some(index) => is_scheme(&uri[..index]) && uri.chars().all(|c| c.is_ascii_graphic()),
+        None => false,
+    }
+}
+
+/// Consumes the next line of the message, which must be empty.
+fn blank_line<'a, I: Iterator<Item = &'a str>>(lines: &mut Peekable<I>) -> Result<(), SiweError> {
+    match lines.next() {
+        Some("") => Ok(()),
+        Some(line) => Err(SiweError::UnexpectedLine(line.into())),
+        None => Err(SiweError::MissingField("URI")),
+    }
+}
+
+/// Consumes the next line of the message and returns its value if the line starts with the given tag.
+fn field<'a, I: Iterator<Item = &'a str>>(lines: &mut Peekable<I>, tag: &str) -> Option<String> {
+    match lines.peek() {
+        Some(line) if line.starts_with(tag) => lines.next().map(|line| line[tag.len()..].to_string()),
+        _ => None,
+    }
+}
+
+/// Represents a Sign-In with Ethereum message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiweMessage {
+    /// The URI scheme of the origin of the request, if not https
+    pub scheme: Option<String>,
+    /// The RFC 3986 authority requesting the signing
+    pub domain: String,
+    /// The address performing the signing
+    pub address: EthereumAddress,
+    /// The human-readable assertion that the user will sign
+    pub statement: Option<String>,
+    /// The RFC 3986 URI referring to the subject of the signing
+    pub uri: String,
+    /// The EIP-155 chain id of the address
+    pub chain_id: u64,
+    /// The random token against replay attacks, at least 8 alphanumeric characters
+    pub nonce: String,
+    /// The RFC 3339 time at which the message was generated
+    pub issued_at: String,
+    /// The RFC 3339 time at which the signed message expires
+    pub expiration_time: Option<String>,
+    /// The RFC 3339 time from which the signed message becomes valid
+    pub not_before: Option<String>,
+    /// The system-specific identifier of the request
+    pub request_id: Option<String>,
+    /// The RFC 3986 URIs of the resources the user wishes to have resolved
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    /// Returns a message of the required fields, with the issuance time given as a unix timestamp.
+    pub fn new(
+        domain: &str,
+        address: &EthereumAddress,
+        uri: &str,
+        chain_id: u64,
+        nonce: &str,
+        issued_at: i64,
+    ) -> Result<Self, SiweError> {
+        let message = Self {
+            scheme: None,
+            domain: domain.into(),
+            address: address.clone(),
+            statement: None,
+            uri: uri.into(),
+            chain_id,
+            nonce: nonce.into(),
+            issued_at: format_timestamp(issued_at),
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: vec![],
+        };
+        message.validate()?;
+        Ok(message)
+    }
+
+    /// Returns an error if any field of the message does not follow the grammar of EIP-4361.
+    pub fn validate(&self) -> Result<(), SiweError> {
+        if let Some(scheme) = &self.scheme {
+            if !is_scheme(scheme) {
+                return Err(SiweError::InvalidField("scheme", scheme.clone()));
+            }
+        }
+        if self.domain.is_empty()
+            || !self
+                .domain
+                .chars()
+                .all(|c| is_unreserved(c) || is_sub_delim(c) || ":@[]%".contains(c))
+        {
+            return Err(SiweError::InvalidField("domain", self.domain.clone()));
+        }
+        if let Some(statement) = &self.statement {
+            if statement.is_empty()
+                || !statement
+                    .chars()
+                    .all(|c| is_unreserved(c) || is_sub_delim(c) || ":/?#[]@ ".contains(c))
+            {
+                return Err(SiweError::InvalidField("statement", statement.clone()));
+            }
+        }
+        if !is_uri(&self.uri) {
+            return Err(SiweError::InvalidField("URI", self.uri.clone()));
+        }
+        if self.nonce.len() < MIN_NONCE_LENGTH || !self.nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(SiweError::InvalidField("nonce", self.nonce.clone()));
+        }
+        parse_timestamp(&self.issued_at)?;
+        if let Some(expiration_time) = &self.expiration_time {
+            parse_timestamp(expiration_time)?;
+        }
+        if let Some(not_before) = &self.not_before {
+            parse_timestamp(not_before)?;
+        }
+        if let Some(request_id) = &self.request_id {
+            if !request_id
+                .chars()
+                .all(|c| is_unreserved(c) || is_sub_delim(c) || ":@%".contains(c))
+            {
+                return Err(SiweError::InvalidField("request ID", request_id.clone()));
+            }
+        }
+        if let Some(resource) = self.resources.iter().find(|resource| !is_uri(resource)) {
+            return Err(SiweError::InvalidField("resource", resource.clone()));
+        }
+        Ok(())
+    }
+
+    /// Returns the EIP-191 hash of the message.
+    pub fn to_signing_hash(&self) -> [u8; 32] {
+        personal_message_hash(self.to_string().as_bytes())
+    }
+
+    /// Returns the 65-byte signature (r || s || v) of the message with the given private key.
+    pub fn sign(&self, private_key: &EthereumPrivateKey) -> Result<Vec<u8>, SiweError> {
+        self.validate()?;
+        let message = secp256k1::Message::parse(&self.to_signing_hash());
+        let (signature, recovery_id) = secp256k1::sign(&message, &private_key.to_secp256k1_secret_key());
+
+        let mut signature = signature.serialize().to_vec();
+        signature.push(Into::<i32>::into(recovery_id) as u8 + 27);
+        Ok(signature)
+    }
+
+    /// Returns the address that produced the given 65-byte signature (r || s || v) of the message.
+    pub fn recover_address(&self, signature: &[u8]) -> Result<EthereumAddress, SiweError> {
+        if signature.len() != 65 {
+            return Err(SiweError::InvalidSignature(format!(
+                "invalid signature length {}",
+                signature.len()
+            )));
+        }
+
+        let recovery_id = match signature[64] {
+            0 | 1 => signature[64],
+            27 | 28 => signature[64] - 27,
+            v => return Err(SiweError::InvalidSignature(format!("invalid recovery id {}", v))),
+        };
+        let message = secp256k1::Message::parse(&self.to_signing_hash());
+        let public_key = secp256k1::recover(
+            &message,
+            &secp256k1::Signature::parse_slice(&signature[0..64])?,
+            &secp256k1::RecoveryId::parse(recovery_id)?,
+        )?;
+        Ok(EthereumPublicKey::from_secp256k1_public_key(public_key).to_address(&EthereumFormat::Standard)?)
+    }
+
+    /// Verifies that the given signature was produced by the address of the message, that the message
+    /// was requested by the given domain, and that the message is valid at the given unix timestamp.
+    pub fn verify(&self, signature: &[u8], domain: &str, timestamp: i64) -> Result<(), SiweError> {
+        self.validate()?;
+
+        let signer = self.recover_address(signature)?;
+        if signer != self.address {
+            return Err(SiweError::InvalidSigner(self.address.to_string(), signer.to_string()));
+        }
+        if self.domain != domain {
+            return Err(SiweError::DomainMismatch(domain.into(), self.domain.clone()));
+        }
+        if let Some(expiration_time) = &self.expiration_time {
+            if timestamp >= parse_timestamp(expiration_time)? {
+                return Err(SiweError::ExpiredMessage(expiration_time.clone()));
+            }
+        }
+        if let Some(not_before) = &self.not_before {
+            if timestamp < parse_timestamp(not_before)? {
+                return Err(SiweError::NotYetValid(not_before.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SiweMessage {
+    type Err = SiweError;
+
+    fn from_str(message: &str) -> Result<Self, Self::Err> {
+        let mut lines = message.split('\n').peekable();
+
+        let origin = match lines.next() {
+            Some(line) if line.ends_with(PREAMBLE) => &line[..line.len() - PREAMBLE.len()],
+            Some(line) => return Err(SiweError::UnexpectedLine(line.into())),
+            None => return Err(SiweError::MissingField("domain")),
+        };
+        let (scheme, domain) = match origin.find("://") {
+            Some(index) => (Some(origin[..index].to_string()), origin[index + 3..].to_string()),
+            None => (None, origin.to_string()),
+        };
+
+        let address = lines.next().ok_or(SiweError::MissingField("address"))?;
+        let address = match EthereumAddress::from_str(address)? {
+            checksum_address if checksum_address.to_string() == address => checksum_address,
+            _ => return Err(SiweError::InvalidField("address", address.into())),
+        };
+
+        blank_line(&mut lines)?;
+        let statement = match lines.next() {
+            Some("") => None,
+            Some(statement) => {
+                blank_line(&mut lines)?;
+                Some(statement.to_string())
+            }
+            None => return Err(SiweError::MissingField("URI")),
+        };
+
+        let uri = field(&mut lines, "URI: ").ok_or(SiweError::MissingField("URI"))?;
+        let version = field(&mut lines, "Version: ").ok_or(SiweError::MissingField("version"))?;
+        let chain_id = field(&mut lines, "Chain ID: ").ok_or(SiweError::MissingField("chain ID"))?;
+        let nonce = field(&mut lines, "Nonce: ").ok_or(SiweError::MissingField("nonce"))?;
+        let issued_at = field(&mut lines, "Issued At: ").ok_or(SiweError::MissingField("issued at"))?;
+        let expiration_time = field(&mut lines, "Expiration Time: ");
+        let not_before = field(&mut lines, "Not Before: ");
+        let request_id = field(&mut lines, "Request ID: ");
+
+        if version != SIWE_VERSION {
+            return Err(SiweError::InvalidField("version", version));
+        }
+        if chain_id.is_empty() || !chain_id.chars().all(|c| c.is_ascii_digit()) {
+            return Err(SiweError::InvalidField("chain ID", chain_id));
+        }
+        let chain_id = u64::from_str(&chain_id).map_err(|_| SiweError::InvalidField("chain ID", chain_id))?;
+
+        let mut resources = vec![];
+        if let Some(line) = lines.next() {
+            if line != "Resources:" {
+                return Err(SiweError::UnexpectedLine(line.into()));
+            }
+            for line in lines {
+                match line.starts_with("- ") {
+                    true => resources.push(line[2..].to_string()),
+                    false => return Err(SiweError::UnexpectedLine(line.into())),
+                }
+            }
+        }
+
+        let message = Self {
+            scheme,
+            domain,
+            address,
+            statement,
+            uri,
+            chain_id,
+            nonce,
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        };
+        message.validate()?;
+        Ok(message)
+    }
+}
+
+impl fmt::Display for SiweMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(scheme) = &self.scheme {
+            write!(f, "{}://", scheme)?;
+        }
+        write!(f, "{}{}\n{}\n\n", self.domain, PREAMBLE, self.address)?;
+        if let Some(statement) = &self.statement {
+            write!(f, "{}\n", statement)?;
+        }
+        write!(f, "\nURI: {}", self.uri)?;
+        write!(f, "\nVersion: {}", SIWE_VERSION)?;
+        write!(f, "\nChain ID: {}", self.chain_id)?;
+        write!(f, "\nNonce: {}", self.nonce)?;
+        write!(f, "\nIssued At: {}", self.issued_at)?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\nExpiration Time: {}", expiration_time)?;
+        }
+        if let Some(not_before) = &self.not_before {
+            write!(f, "\nNot Before: {}", not_before)?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\nRequest ID: {}", request_id)?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\nResources:")?;
+            for resource in self.resources.iter() {
+                write!(f, "\n- {}", resource)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    /// The example message of EIP-4361
+    const EIP_MESSAGE: &str = "example.com wants you to sign in with your Ethereum account:
+0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2
+
+I accept the ExampleOrg Terms of Service: https://example.com/tos
+
+URI: https://example.com/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    /// (message, signing hash, signature)
+    const MESSAGES: [(&str, &str, &str); 2] = [
+        (
+            "example.com wants you to sign in with your Ethereum account:
+0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266
+
+I accept the ExampleOrg Terms of Service: https://example.com/tos
+
+URI: https://example.com/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Expiration Time: 2021-10-01T16:25:24Z
+Not Before: 2021-09-30T16:25:24Z
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json",
+            "37e58f4e347a82e7ad89218c87418e864b62b9c642e970206c8689299906c423",
+            "75decf45eb1b06a213d0317eac117ed9589b2e7e2e9e7451f947c09ac81759eb7844d8d51f963b849bde89b256a3be56929295acd252be9901d8ca990e11660d1c",
+        ),
+        (
+            "https://localhost:4361 wants you to sign in with your Ethereum account:
+0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266
+
+
+URI: https://localhost:4361
+Version: 1
+Chain ID: 11155111
+Nonce: kEWepMt9knR6lWJ6A
+Issued At: 2021-09-30T16:25:24.000Z
+Request ID: request-1",
+            "a349d0f62a0324157e8f4017f9a434d1d46eaafd70772a74ee3ad0fc99d4a37f",
+            "378c7dd556589cc976d1c3ca1f711aaa4d56c598a0c5fd1a5a2c963ecafb01d3130ab4bfda345d25ecda647c70fa6f3e5e1d89930f76dabcf268f45386cd13391c",
+        ),
+    ];
+
+    #[test]
+    fn personal_message_hash() {
+        assert_eq!(
+            "a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2",
+            hex::encode(super::personal_message_hash(b"Hello World"))
+        );
+    }
+
+    #[test]
+    fn timestamp() {
+        const TIMESTAMPS: [(&str, i64); 5] = [
+            ("2021-09-30T16:25:24Z", 1633019124),
+            ("2021-10-01T16:25:24Z", 1633105524),
+            ("2000-02-29T23:59:59Z", 951868799),
+            ("1969-12-31T23:59:59Z", -1),
+            ("1970-01-01T00:00:00Z", 0),
+        ];
+
+        TIMESTAMPS.iter().for_each(|(string, timestamp)| {
+            assert_eq!(*timestamp, parse_timestamp(string).unwrap());
+            assert_eq!(*string, format_timestamp(*timestamp));
+        });
+        assert_eq!(1709247600, parse_timestamp("2024-03-01T01:30:00+02:30").unwrap());
+        assert_eq!(1633019124, parse_timestamp("2021-09-30t16:25:24.123456z").unwrap());
+    }
+
+    #[test]
+    fn invalid_timestamp() {
+        const TIMESTAMPS: [&str; 8] = [
+            "2021-09-30T16:25:24",
+            "2021-09-30 16:25:24Z",
+            "2021-09-30T16:25:24.Z",
+            "2021-02-29T16:25:24Z",
+            "2021-13-01T16:25:24Z",
+            "2021-09-30T24:00:00Z",
+            "2021-09-30T16:25:24+0200",
+            "21-09-30T16:25:24Z",
+        ];
+
+        TIMESTAMPS.iter().for_each(|timestamp| {
+            assert!(parse_timestamp(timestamp).is_err());
+        });
+    }
+
+    #[test]
+    fn from_str() {
+        let message = SiweMessage::from_str(EIP_MESSAGE).unwrap();
+        assert_eq!(None, message.scheme);
+        assert_eq!("example.com", message.domain);
+        assert_eq!(
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+            message.address.to_string()
+        );
+        assert_eq!(
+            Some("I accept the ExampleOrg Terms of Service: https://example.com/tos"),
+            message.statement.as_deref()
+        );
+        assert_eq!("https://example.com/login", message.uri);
+        assert_eq!(1, message.chain_id);
+        assert_eq!("32891756", message.nonce);
+        assert_eq!("2021-09-30T16:25:24Z", message.issued_at);
+        assert_eq!(2, message.resources.len());
+        assert_eq!(EIP_MESSAGE, message.to_string());
+
+        MESSAGES.iter().for_each(|(message, _, _)| {
+            assert_eq!(*message, SiweMessage::from_str(message).unwrap().to_string());
+        });
+
+        let message = SiweMessage::from_str(MESSAGES[1].0).unwrap();
+        assert_eq!(Some("https"), message.scheme.as_deref());
+        assert_eq!("localhost:4361", message.domain);
+        assert_eq!(None, message.statement);
+        assert_eq!(11155111, message.chain_id);
+        assert_eq!(Some("request-1"), message.request_id.as_deref());
+    }
+
+    #[test]
+    fn new() {
+        let address = EthereumAddress::from_str("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2").unwrap();
+        let mut message = SiweMessage::new(
+            "example.com",
+            &address,
+            "https://example.com/login",
+            1,
+            "32891756",
+            1633019124,
+        )
+        .unwrap();
+        message.statement = Some("I accept the ExampleOrg Terms of Service: https://example.com/tos".into());
+        message.resources = vec![
+            "ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/".into(),
+            "https://example.com/my-web2-claim.json".into(),
+        ];
+        assert_eq!(EIP_MESSAGE, message.to_string());
+
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let nonce = generate_nonce(rng);
+        assert_eq!(NONCE_LENGTH, nonce.len());
+        assert!(SiweMessage::new("example.com", &address, "https://example.com", 1, &nonce, 0).is_ok());
+    }
+
+    #[test]
+    fn sign() {
+        let private_key = EthereumPrivateKey::from_str(PRIVATE_KEY).unwrap();
+        MESSAGES.iter().for_each(|(message, signing_hash, signature)| {
+            let message = SiweMessage::from_str(message).unwrap();
+            assert_eq!(*signing_hash, hex::encode(message.to_signing_hash()));
+            assert_eq!(*signature, hex::encode(message.sign(&private_key).unwrap()));
+        });
+    }
+
+    #[test]
+    fn verify() {
+        MESSAGES.iter().for_each(|(message, _, signature)| {
+            let message = SiweMessage::from_str(message).unwrap();
+            let signature = hex::decode(signature).unwrap();
+            assert_eq!(message.address, message.recover_address(&signature).unwrap());
+            assert!(message.verify(&signature, &message.domain, 1633019124).is_ok());
+        });
+    }
+
+    mod invalid {
+        use super::*;
+
+        #[test]
+        fn verify_invalid() {
+            let message = SiweMessage::from_str(MESSAGES[0].0).unwrap();
+            let signature = hex::decode(MESSAGES[0].2).unwrap();
+            let other_signature = hex::decode(MESSAGES[1].2).unwrap();
+
+            assert!(message.verify(&signature, "example.com", 1633105523).is_ok());
+            match message.verify(&signature, "example.com", 1633105524) {
+                Err(SiweError::ExpiredMessage(_)) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+            match message.verify(&signature, "example.com", 1633019123) {
+                Err(SiweError::NotYetValid(_)) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+            match message.verify(&signature, "evil.com", 1633019124) {
+                Err(SiweError::DomainMismatch(_, _)) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+            match message.verify(&other_signature, "example.com", 1633019124) {
+                Err(SiweError::InvalidSigner(_, _)) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+            assert!(message.verify(&signature[..64], "example.com", 1633019124).is_err());
+        }
+
+        #[test]
+        fn from_str_invalid() {
+            let replacements = [
+                ("example.com wants", "example.com requests"),
+                (
+                    "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                    "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                ),
+                (
+                    "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                    "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc",
+                ),
+                ("account:\n0x", "account:\n\n0x"),
+                ("Cc2\n\n", "Cc2\n"),
+                ("/tos\n\n", "/tos\n"),
+                ("Terms of Service", "Terms of Service™"),
+                ("URI: https://example.com/login", "URI: example.com/login"),
+                ("Version: 1", "Version: 2"),
+                ("Chain ID: 1", "Chain ID: 0x1"),
+                ("Nonce: 32891756", "Nonce: 3289175"),
+                ("Nonce: 32891756", "Nonce: 32891756!"),
+                ("Issued At: 2021-09-30T16:25:24Z", "Issued At: 2021-09-30"),
+                ("Nonce: 32891756\n", ""),
+                ("Resources:", "Resources"),
+                ("- https://example.com", "-https://example.com"),
+                (
+                    "- https://example.com/my-web2-claim.json",
+                    "- https://example.com/my web2 claim.json",
+                ),
+                ("my-web2-claim.json", "my-web2-claim.json\n"),
+            ];
+
+            replacements.iter().for_each(|(from, to)| {
+                let message = EIP_MESSAGE.replacen(from, to, 1);
+                assert_ne!(EIP_MESSAGE, message);
+                assert!(SiweMessage::from_str(&message).is_err(), "{}", message);
+            });
+        }
+    }
+}
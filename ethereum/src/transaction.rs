@@ -7,56 +7,174 @@ use crate::public_key::EthereumPublicKey;
 use wagyu_model::{PrivateKey, PublicKey, Transaction, TransactionError, TransactionId};
 
 use ethereum_types::U256;
-use rlp::{decode_list, RlpStream};
+use rlp::{decode_list, Rlp, RlpStream};
 use secp256k1;
 use std::{fmt, marker::PhantomData, str::FromStr};
 use tiny_keccak::keccak256;
 
-pub fn to_bytes(value: u32) -> Result<Vec<u8>, TransactionError> {
-    match value {
-        // bounded by u8::max_value()
-        0..=255 => Ok(vec![value as u8]),
-        // bounded by u16::max_value()
-        256..=65535 => Ok((value as u16).to_le_bytes().to_vec()),
-        // bounded by u32::max_value()
-        _ => Ok(value.to_le_bytes().to_vec()),
-    }
+/// Returns the big-endian bytes of the given integer without leading zeros, as required by RLP.
+pub fn to_bytes(value: u64) -> Result<Vec<u8>, TransactionError> {
+    Ok(trim_leading_zeros(&value.to_be_bytes()))
 }
 
-pub fn from_bytes(value: &Vec<u8>) -> Result<u32, TransactionError> {
+/// Returns the integer of the given big-endian bytes.
+pub fn from_bytes(value: &Vec<u8>) -> Result<u64, TransactionError> {
     match value.len() {
-        0 => Ok(0u32),
-        1 => Ok(u32::from_le_bytes([value[0], 0, 0, 0])),
-        2 => Ok(u32::from_le_bytes([value[0], value[1], 0, 0])),
-        3 => Ok(u32::from_le_bytes([value[0], value[1], value[2], 0])),
-        4 => Ok(u32::from_le_bytes([value[0], value[1], value[2], value[3]])),
+        0..=8 => Ok(value.iter().fold(0u64, |result, byte| (result << 8) | *byte as u64)),
         _ => Err(TransactionError::Message(
-            "invalid byte length for u32 value".to_string(),
+            "invalid byte length for u64 value".to_string(),
         )),
     }
 }
 
+/// Returns the given big-endian integer bytes without leading zeros, as required by RLP.
+fn trim_leading_zeros(value: &[u8]) -> Vec<u8> {
+    value.iter().skip_while(|byte| **byte == 0).cloned().collect()
+}
+
+/// Returns the given big-endian integer bytes left-padded with zeros to 32 bytes.
+fn pad_to_32_bytes(value: &[u8]) -> Result<Vec<u8>, TransactionError> {
+    match value.len() {
+        0..=32 => Ok([vec![0u8; 32 - value.len()], value.to_vec()].concat()),
+        length => Err(TransactionError::Message(format!(
+            "invalid signature value length {}",
+            length
+        ))),
+    }
+}
+
+/// Returns the RLP value of the given receiver, which is empty for a contract creation.
+fn encode_receiver(receiver: &Option<EthereumAddress>) -> Result<Vec<u8>, TransactionError> {
+    match receiver {
+        Some(receiver) => Ok(receiver.to_bytes()?),
+        None => Ok(vec![]),
+    }
+}
+
+/// Returns the receiver of the given RLP value, which is empty for a contract creation.
+fn decode_receiver(receiver: &[u8]) -> Result<Option<EthereumAddress>, TransactionError> {
+    match receiver.len() {
+        0 => Ok(None),
+        20 => Ok(Some(EthereumAddress::from_str(&hex::encode(receiver))?)),
+        length => Err(TransactionError::Message(format!("invalid receiver length {}", length))),
+    }
+}
+
+/// Returns the chain id of the given transaction parameters on the network.
+/// Custom chains require the chain id in the parameters, other networks use their own chain id.
+fn to_chain_id<N: EthereumNetwork>(parameters: &EthereumTransactionParameters) -> Result<u64, TransactionError> {
+    match (N::CHAIN_ID, parameters.chain_id) {
+        (0, Some(chain_id)) if chain_id != 0 => Ok(chain_id),
+        (0, chain_id) => Err(TransactionError::InvalidChainId(chain_id.unwrap_or(0))),
+        (network_chain_id, None) => Ok(network_chain_id),
+        (network_chain_id, Some(chain_id)) if chain_id == network_chain_id => Ok(chain_id),
+        (_, Some(chain_id)) => Err(TransactionError::InvalidChainId(chain_id)),
+    }
+}
+
+/// Returns the chain id of the transaction parameters given the chain id of an encoded transaction.
+/// Custom chains accept any chain id, other networks only accept their own chain id.
+fn from_chain_id<N: EthereumNetwork>(chain_id: u64) -> Result<Option<u64>, TransactionError> {
+    match N::CHAIN_ID {
+        0 if chain_id != 0 => Ok(Some(chain_id)),
+        network_chain_id if network_chain_id != 0 && chain_id == network_chain_id => Ok(None),
+        _ => Err(TransactionError::InvalidChainId(chain_id)),
+    }
+}
+
+/// Represents an Ethereum transaction type
+/// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2718.md
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EthereumTransactionType {
+    /// A legacy transaction with an EIP-155 signature
+    Legacy = 0x00,
+    /// An EIP-2930 transaction with an access list
+    AccessList = 0x01,
+    /// An EIP-1559 transaction with a priority fee
+    FeeMarket = 0x02,
+}
+
+impl EthereumTransactionType {
+    /// Returns the transaction type of the given type byte.
+    pub fn from_byte(byte: u8) -> Result<Self, TransactionError> {
+        match byte {
+            0x00 => Ok(EthereumTransactionType::Legacy),
+            0x01 => Ok(EthereumTransactionType::AccessList),
+            0x02 => Ok(EthereumTransactionType::FeeMarket),
+            _ => Err(TransactionError::Message(format!(
+                "unsupported transaction type {}",
+                byte
+            ))),
+        }
+    }
+
+    /// Returns the number of RLP fields of an unsigned transaction of this type.
+    fn unsigned_field_count(&self) -> usize {
+        match self {
+            EthereumTransactionType::Legacy => 9,
+            EthereumTransactionType::AccessList => 8,
+            EthereumTransactionType::FeeMarket => 9,
+        }
+    }
+}
+
+impl Default for EthereumTransactionType {
+    fn default() -> Self {
+        EthereumTransactionType::Legacy
+    }
+}
+
+impl fmt::Display for EthereumTransactionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EthereumTransactionType::Legacy => write!(f, "legacy"),
+            EthereumTransactionType::AccessList => write!(f, "EIP-2930"),
+            EthereumTransactionType::FeeMarket => write!(f, "EIP-1559"),
+        }
+    }
+}
+
+/// Represents an entry of an EIP-2930 access list
+/// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2930.md
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EthereumAccessListItem {
+    /// The address to be accessed
+    pub address: EthereumAddress,
+    /// The storage keys to be accessed at the address
+    pub storage_keys: Vec<[u8; 32]>,
+}
+
 /// Represents the parameters for an Ethereum transaction
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct EthereumTransactionParameters {
-    /// The address of the receiver
-    pub receiver: EthereumAddress,
+    /// The address of the receiver, or `None` for a contract creation
+    pub receiver: Option<EthereumAddress>,
     /// The amount (in wei)
     pub amount: EthereumAmount,
     /// The transaction gas limit
     pub gas: U256,
-    /// The transaction gas price in wei
+    /// The transaction gas price in wei (legacy and EIP-2930 transactions)
     pub gas_price: EthereumAmount,
     /// The nonce of the Ethereum account
     pub nonce: U256,
     /// The transaction data
     pub data: Vec<u8>,
+    /// The transaction type
+    pub transaction_type: EthereumTransactionType,
+    /// The maximum fee per gas in wei, including the priority fee (EIP-1559 transactions)
+    pub max_fee_per_gas: EthereumAmount,
+    /// The maximum priority fee per gas in wei (EIP-1559 transactions)
+    pub max_priority_fee_per_gas: EthereumAmount,
+    /// The addresses and storage keys the transaction plans to access (EIP-2930 and EIP-1559 transactions)
+    pub access_list: Vec<EthereumAccessListItem>,
+    /// The chain id of a custom chain, or `None` for the chain id of the network
+    pub chain_id: Option<u64>,
 }
 
 /// Represents an Ethereum transaction signature
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct EthereumTransactionSignature {
-    /// The V field of the signature protected with a chain_id
+    /// The V field of the signature protected with a chain_id (the y-parity for typed transactions)
     v: Vec<u8>,
     /// The R field of the signature
     r: Vec<u8>,
@@ -101,6 +219,7 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
 
     /// Returns an unsigned transaction given the transaction parameters.
     fn new(parameters: &Self::TransactionParameters) -> Result<Self, TransactionError> {
+        to_chain_id::<N>(parameters)?;
         Ok(Self {
             sender: None,
             parameters: parameters.clone(),
@@ -111,23 +230,38 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
 
     /// Returns a signed transaction given the private key of the sender.
     /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-155.md
+    /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2718.md
     fn sign(&self, private_key: &Self::PrivateKey) -> Result<Self, TransactionError> {
         match (&self.sender, &self.signature) {
             (Some(_), Some(_)) => Ok(self.clone()),
             (Some(_), None) | (None, Some(_)) => Err(TransactionError::InvalidTransactionState),
             (None, None) => {
-                let (signature, v) = secp256k1::sign(
+                let (signature, recovery_id) = secp256k1::sign(
                     &secp256k1::Message::parse_slice(&self.to_transaction_id()?.txid)?,
                     &private_key.to_secp256k1_secret_key(),
                 );
                 let signature = signature.serialize();
+                let recovery_id = Into::<i32>::into(recovery_id) as u32;
+
+                // Legacy transactions protect V with the chain id (EIP-155), typed transactions use the y-parity.
+                let v = match self.parameters.transaction_type {
+                    EthereumTransactionType::Legacy => {
+                        let chain_id = to_chain_id::<N>(&self.parameters)?;
+                        let v = chain_id
+                            .checked_mul(2)
+                            .and_then(|v| v.checked_add(recovery_id as u64 + 35))
+                            .ok_or(TransactionError::InvalidChainId(chain_id))?;
+                        to_bytes(v)?
+                    }
+                    _ => trim_leading_zeros(&[recovery_id as u8]),
+                };
 
                 let mut transaction = self.clone();
                 transaction.sender = Some(private_key.to_address(&EthereumFormat::Standard)?);
                 transaction.signature = Some(EthereumTransactionSignature {
-                    v: to_bytes(Into::<i32>::into(v) as u32 + N::CHAIN_ID * 2 + 35)?, // EIP155
-                    r: signature[0..32].to_vec(),
-                    s: signature[32..64].to_vec(),
+                    v,
+                    r: trim_leading_zeros(&signature[0..32]),
+                    s: trim_leading_zeros(&signature[32..64]),
                 });
                 Ok(transaction)
             }
@@ -136,77 +270,21 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
 
     /// Returns a transaction given the transaction bytes.
     /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-155.md
+    /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2718.md
     fn from_transaction_bytes(transaction: &Vec<u8>) -> Result<Self, TransactionError> {
-        let list: Vec<Vec<u8>> = decode_list(&transaction);
-        if list.len() != 9 {
-            return Err(TransactionError::InvalidRlpLength(list.len()));
-        }
-
-        let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(&hex::encode(&list[3]))?,
-            amount: match list[4].is_empty() {
-                true => EthereumAmount::from_u256(U256::zero()),
-                false => EthereumAmount::from_u256(U256::from(list[4].as_slice())),
-            },
-            gas: match list[2].is_empty() {
-                true => U256::zero(),
-                false => U256::from(list[2].as_slice()),
-            },
-            gas_price: match list[1].is_empty() {
-                true => EthereumAmount::from_u256(U256::zero()),
-                false => EthereumAmount::from_u256(U256::from(list[1].as_slice())),
-            },
-            nonce: match list[0].is_empty() {
-                true => U256::zero(),
-                false => U256::from(list[0].as_slice()),
-            },
-            data: list[5].clone(),
-        };
-
-        match list[7].is_empty() && list[8].is_empty() {
-            true => {
-                // Raw transaction
-                Ok(Self {
-                    sender: None,
-                    parameters,
-                    signature: None,
-                    _network: PhantomData,
-                })
-            }
-            false => {
-                // Signed transaction
-                let v = from_bytes(&list[6])?;
-                let recovery_id = secp256k1::RecoveryId::parse((v - N::CHAIN_ID * 2 - 35) as u8)?;
-                let mut signature = list[7].clone();
-                signature.extend_from_slice(&list[8]);
-
-                let raw_transaction = Self {
-                    sender: None,
-                    parameters: parameters.clone(),
-                    signature: None,
-                    _network: PhantomData,
-                };
-                let message = secp256k1::Message::parse_slice(&raw_transaction.to_transaction_id()?.txid)?;
-                let public_key = EthereumPublicKey::from_secp256k1_public_key(
-                    secp256k1::recover(&message, &secp256k1::Signature::parse_slice(signature.as_slice())?, &recovery_id)?,
-                );
-
-                Ok(Self {
-                    sender: Some(public_key.to_address(&EthereumFormat::Standard)?),
-                    parameters,
-                    signature: Some(EthereumTransactionSignature {
-                        v: list[6].clone(),
-                        r: list[7].clone(),
-                        s: list[8].clone(),
-                    }),
-                    _network: PhantomData,
-                })
+        match transaction.first() {
+            // Typed transactions start with a type byte, while legacy transactions start with an RLP list.
+            Some(byte) if *byte < 0x80 => {
+                Self::from_typed_transaction_bytes(EthereumTransactionType::from_byte(*byte)?, &transaction[1..])
             }
+            Some(_) => Self::from_legacy_transaction_bytes(transaction),
+            None => Err(TransactionError::InvalidRlpLength(0)),
         }
     }
 
     /// Returns the transaction in bytes.
     /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-155.md
+    /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2718.md
     fn to_transaction_bytes(&self) -> Result<Vec<u8>, TransactionError> {
         // Returns an encoded transaction in Recursive Length Prefix (RLP) format.
         // https://github.com/ethereum/wiki/wiki/RLP
@@ -217,7 +295,7 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
             transaction_rlp.append(&parameters.nonce);
             transaction_rlp.append(&parameters.gas_price.0);
             transaction_rlp.append(&parameters.gas);
-            transaction_rlp.append(&hex::decode(&parameters.receiver.to_string()[2..])?);
+            transaction_rlp.append(&encode_receiver(&parameters.receiver)?);
             transaction_rlp.append(&parameters.amount.0);
             transaction_rlp.append(&parameters.data);
             Ok(())
@@ -230,7 +308,7 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
             let mut transaction_rlp = RlpStream::new();
             transaction_rlp.begin_list(9);
             encode_transaction(&mut transaction_rlp, parameters)?;
-            transaction_rlp.append(&to_bytes(N::CHAIN_ID)?);
+            transaction_rlp.append(&to_bytes(to_chain_id::<N>(parameters)?)?);
             transaction_rlp.append(&0u8);
             transaction_rlp.append(&0u8);
             Ok(transaction_rlp)
@@ -250,9 +328,62 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
             Ok(transaction_rlp)
         }
 
-        match &self.signature {
-            Some(signature) => Ok(signed_transaction(&self.parameters, signature)?.out()),
-            None => Ok(raw_transaction::<N>(&self.parameters)?.out()),
+        // Returns the typed transaction envelope (type byte followed by the RLP payload).
+        // https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2930.md
+        // https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1559.md
+        fn typed_transaction<N: EthereumNetwork>(
+            parameters: &EthereumTransactionParameters,
+            signature: &Option<EthereumTransactionSignature>,
+        ) -> Result<Vec<u8>, TransactionError> {
+            let transaction_type = parameters.transaction_type;
+            let field_count = transaction_type.unsigned_field_count();
+
+            let mut transaction_rlp = RlpStream::new();
+            transaction_rlp.begin_list(match signature {
+                Some(_) => field_count + 3,
+                None => field_count,
+            });
+            transaction_rlp.append(&to_chain_id::<N>(parameters)?);
+            transaction_rlp.append(&parameters.nonce);
+            match transaction_type {
+                EthereumTransactionType::FeeMarket => {
+                    transaction_rlp.append(&parameters.max_priority_fee_per_gas.0);
+                    transaction_rlp.append(&parameters.max_fee_per_gas.0);
+                }
+                _ => {
+                    transaction_rlp.append(&parameters.gas_price.0);
+                }
+            };
+            transaction_rlp.append(&parameters.gas);
+            transaction_rlp.append(&encode_receiver(&parameters.receiver)?);
+            transaction_rlp.append(&parameters.amount.0);
+            transaction_rlp.append(&parameters.data);
+
+            transaction_rlp.begin_list(parameters.access_list.len());
+            for item in &parameters.access_list {
+                transaction_rlp.begin_list(2);
+                transaction_rlp.append(&hex::decode(&item.address.to_string()[2..])?);
+                transaction_rlp.begin_list(item.storage_keys.len());
+                for storage_key in &item.storage_keys {
+                    transaction_rlp.append(&storage_key.to_vec());
+                }
+            }
+
+            if let Some(signature) = signature {
+                transaction_rlp.append(&signature.v);
+                transaction_rlp.append(&signature.r);
+                transaction_rlp.append(&signature.s);
+            }
+
+            Ok([vec![transaction_type as u8], transaction_rlp.out()].concat())
+        }
+
+        match (self.parameters.transaction_type, &self.signature) {
+            (EthereumTransactionType::Legacy, Some(signature)) => {
+                Ok(signed_transaction(&self.parameters, signature)?.out())
+            }
+            (EthereumTransactionType::Legacy, None) => Ok(raw_transaction::<N>(&self.parameters)?.out()),
+            (_, signature) => typed_transaction::<N>(&self.parameters, signature),
         }
     }
 
@@ -265,6 +396,227 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
     }
 }
 
+impl<N: EthereumNetwork> EthereumTransaction<N> {
+    /// Returns the address of the sender, if the transaction is signed.
+    pub fn sender(&self) -> Option<EthereumAddress> {
+        self.sender.clone()
+    }
+
+    /// Returns the transaction parameters.
+    pub fn parameters(&self) -> EthereumTransactionParameters {
+        self.parameters.clone()
+    }
+
+    /// Returns the chain id of the transaction, or `None` for an unprotected legacy transaction (pre EIP-155).
+    pub fn chain_id(&self) -> Option<u64> {
+        match (&self.parameters.transaction_type, &self.signature) {
+            (EthereumTransactionType::Legacy, Some(signature)) if signature.v == [27] || signature.v == [28] => None,
+            _ => to_chain_id::<N>(&self.parameters).ok(),
+        }
+    }
+
+    /// Returns the address of the created contract, if the transaction is a signed contract creation.
+    pub fn contract_address(&self) -> Result<Option<EthereumAddress>, TransactionError> {
+        match (&self.parameters.receiver, &self.sender) {
+            (None, Some(sender)) => Ok(Some(EthereumAddress::create_address(sender, self.parameters.nonce)?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns a legacy transaction given the transaction bytes.
+    fn from_legacy_transaction_bytes(transaction: &Vec<u8>) -> Result<Self, TransactionError> {
+        let list: Vec<Vec<u8>> = decode_list(&transaction);
+        if list.len() != 9 {
+            return Err(TransactionError::InvalidRlpLength(list.len()));
+        }
+
+        // Raw transactions are network agnostic, unless the chain id is given by a custom chain.
+        let chain_id = match (N::CHAIN_ID, list[7].is_empty() && list[8].is_empty()) {
+            (0, true) => from_chain_id::<N>(from_bytes(&list[6])?)?,
+            _ => None,
+        };
+
+        let parameters = EthereumTransactionParameters {
+            receiver: decode_receiver(&list[3])?,
+            amount: match list[4].is_empty() {
+                true => EthereumAmount::from_u256(U256::zero()),
+                false => EthereumAmount::from_u256(U256::from(list[4].as_slice())),
+            },
+            gas: match list[2].is_empty() {
+                true => U256::zero(),
+                false => U256::from(list[2].as_slice()),
+            },
+            gas_price: match list[1].is_empty() {
+                true => EthereumAmount::from_u256(U256::zero()),
+                false => EthereumAmount::from_u256(U256::from(list[1].as_slice())),
+            },
+            nonce: match list[0].is_empty() {
+                true => U256::zero(),
+                false => U256::from(list[0].as_slice()),
+            },
+            data: list[5].clone(),
+            transaction_type: EthereumTransactionType::Legacy,
+            max_fee_per_gas: EthereumAmount::from_u256(U256::zero()),
+            max_priority_fee_per_gas: EthereumAmount::from_u256(U256::zero()),
+            access_list: vec![],
+            chain_id,
+        };
+
+        let raw_transaction = Self {
+            sender: None,
+            parameters,
+            signature: None,
+            _network: PhantomData,
+        };
+
+        match list[7].is_empty() && list[8].is_empty() {
+            // Raw transaction
+            true => Ok(raw_transaction),
+            // Signed transaction
+            false => {
+                // Unprotected transactions (pre EIP-155) sign the first six fields, with V as 27 or 28.
+                let (raw_transaction, recovery_id, message) = match from_bytes(&list[6])? {
+                    v @ 27..=28 => {
+                        let mut transaction_rlp = RlpStream::new();
+                        transaction_rlp.begin_list(6);
+                        list[..6].iter().for_each(|item| {
+                            transaction_rlp.append(item);
+                        });
+                        (raw_transaction, v - 27, keccak256(&transaction_rlp.out()))
+                    }
+                    v if v >= 35 => {
+                        let mut raw_transaction = raw_transaction;
+                        raw_transaction.parameters.chain_id = from_chain_id::<N>((v - 35) / 2)?;
+                        let message = keccak256(&raw_transaction.to_transaction_bytes()?);
+                        (raw_transaction, (v - 35) % 2, message)
+                    }
+                    v => return Err(TransactionError::Message(format!("invalid signature v {}", v))),
+                };
+                let sender = recover_sender(&message, recovery_id as u8, &list[7], &list[8])?;
+
+                Ok(Self {
+                    sender: Some(sender),
+                    signature: Some(EthereumTransactionSignature {
+                        v: list[6].clone(),
+                        r: list[7].clone(),
+                        s: list[8].clone(),
+                    }),
+                    ..raw_transaction
+                })
+            }
+        }
+    }
+
+    /// Returns a typed transaction given the transaction type and the RLP payload following the type byte.
+    fn from_typed_transaction_bytes(
+        transaction_type: EthereumTransactionType,
+        payload: &[u8],
+    ) -> Result<Self, TransactionError> {
+        let field_count = transaction_type.unsigned_field_count();
+        let rlp = Rlp::new(payload);
+        let item_count = rlp.item_count()?;
+        if transaction_type == EthereumTransactionType::Legacy
+            || (item_count != field_count && item_count != field_count + 3)
+        {
+            return Err(TransactionError::InvalidRlpLength(item_count));
+        }
+
+        let chain_id = from_chain_id::<N>(rlp.val_at(0)?)?;
+
+        // EIP-1559 transactions replace the gas price with the priority fee and the max fee.
+        let (gas_price, max_priority_fee_per_gas, max_fee_per_gas, offset) = match transaction_type {
+            EthereumTransactionType::FeeMarket => (U256::zero(), rlp.val_at(2)?, rlp.val_at(3)?, 1),
+            _ => (rlp.val_at(2)?, U256::zero(), U256::zero(), 0),
+        };
+
+        let receiver: Vec<u8> = rlp.val_at(4 + offset)?;
+        let mut access_list = vec![];
+        for item in rlp.at(7 + offset)?.iter() {
+            let address: Vec<u8> = item.val_at(0)?;
+            let mut storage_keys = vec![];
+            for storage_key in item.at(1)?.iter() {
+                let storage_key: Vec<u8> = storage_key.as_val()?;
+                if storage_key.len() != 32 {
+                    return Err(TransactionError::Message(format!(
+                        "invalid storage key length {}",
+                        storage_key.len()
+                    )));
+                }
+                let mut key = [0u8; 32];
+                key.copy_from_slice(&storage_key);
+                storage_keys.push(key);
+            }
+            access_list.push(EthereumAccessListItem {
+                address: EthereumAddress::from_str(&hex::encode(&address))?,
+                storage_keys,
+            });
+        }
+
+        let parameters = EthereumTransactionParameters {
+            receiver: decode_receiver(&receiver)?,
+            amount: EthereumAmount::from_u256(rlp.val_at(5 + offset)?),
+            gas: rlp.val_at(3 + offset)?,
+            gas_price: EthereumAmount::from_u256(gas_price),
+            nonce: rlp.val_at(1)?,
+            data: rlp.val_at(6 + offset)?,
+            transaction_type,
+            max_fee_per_gas: EthereumAmount::from_u256(max_fee_per_gas),
+            max_priority_fee_per_gas: EthereumAmount::from_u256(max_priority_fee_per_gas),
+            access_list,
+            chain_id,
+        };
+
+        let raw_transaction = Self {
+            sender: None,
+            parameters,
+            signature: None,
+            _network: PhantomData,
+        };
+
+        match item_count == field_count {
+            // Raw transaction
+            true => Ok(raw_transaction),
+            // Signed transaction
+            false => {
+                let v: Vec<u8> = rlp.val_at(field_count)?;
+                let r: Vec<u8> = rlp.val_at(field_count + 1)?;
+                let s: Vec<u8> = rlp.val_at(field_count + 2)?;
+                let y_parity = match v.as_slice() {
+                    [] => 0,
+                    [1] => 1,
+                    _ => {
+                        return Err(TransactionError::Message(format!(
+                            "invalid y-parity {}",
+                            hex::encode(&v)
+                        )))
+                    }
+                };
+                let sender = recover_sender(&raw_transaction.to_transaction_id()?.txid, y_parity, &r, &s)?;
+
+                Ok(Self {
+                    sender: Some(sender),
+                    signature: Some(EthereumTransactionSignature { v, r, s }),
+                    ..raw_transaction
+                })
+            }
+        }
+    }
+}
+
+/// Returns the address that signed the given message hash with the given recovery id and signature.
+fn recover_sender(message: &[u8], recovery_id: u8, r: &[u8], s: &[u8]) -> Result<EthereumAddress, TransactionError> {
+    let recovery_id = secp256k1::RecoveryId::parse(recovery_id)?;
+    let signature = [pad_to_32_bytes(r)?, pad_to_32_bytes(s)?].concat();
+
+    let message = secp256k1::Message::parse_slice(message)?;
+    let public_key = EthereumPublicKey::from_secp256k1_public_key(secp256k1::recover(
+        &message,
+        &secp256k1::Signature::parse_slice(signature.as_slice())?,
+        &recovery_id,
+    )?);
+    Ok(public_key.to_address(&EthereumFormat::Standard)?)
+}
+
 impl<N: EthereumNetwork> FromStr for EthereumTransaction<N> {
     type Err = TransactionError;
 
@@ -311,12 +663,17 @@ mod tests {
         let expected_signed_transaction_hash = transaction.signed_transaction_hash;
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
             nonce: U256::from_dec_str(transaction.nonce).unwrap(),
             data: transaction.data.as_bytes().to_vec(),
+            transaction_type: EthereumTransactionType::Legacy,
+            max_fee_per_gas: EthereumAmount::from_wei("0").unwrap(),
+            max_priority_fee_per_gas: EthereumAmount::from_wei("0").unwrap(),
+            access_list: vec![],
+            chain_id: None,
         };
 
         let transaction = EthereumTransaction::<N>::new(&parameters).unwrap();
@@ -332,12 +689,17 @@ mod tests {
         let expected_signed_transaction = transaction.signed_transaction;
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
             nonce: U256::from_dec_str(transaction.nonce).unwrap(),
             data: transaction.data.as_bytes().to_vec(),
+            transaction_type: EthereumTransactionType::Legacy,
+            max_fee_per_gas: EthereumAmount::from_wei("0").unwrap(),
+            max_priority_fee_per_gas: EthereumAmount::from_wei("0").unwrap(),
+            access_list: vec![],
+            chain_id: None,
         };
 
         let transaction = EthereumTransaction::<N>::new(&parameters).unwrap();
@@ -357,12 +719,17 @@ mod tests {
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let expected_sender = Some(private_key.to_address(&EthereumFormat::Standard).unwrap());
         let expected_parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
             nonce: U256::from_dec_str(transaction.nonce).unwrap(),
             data: transaction.data.as_bytes().to_vec(),
+            transaction_type: EthereumTransactionType::Legacy,
+            max_fee_per_gas: EthereumAmount::from_wei("0").unwrap(),
+            max_priority_fee_per_gas: EthereumAmount::from_wei("0").unwrap(),
+            access_list: vec![],
+            chain_id: None,
         };
         let signed_transaction_bytes = hex::decode(&transaction.signed_transaction[2..]).unwrap();
 
@@ -376,12 +743,17 @@ mod tests {
         let expected_signed_transaction_bytes = hex::decode(&transaction.signed_transaction[2..]).unwrap();
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
             nonce: U256::from_dec_str(transaction.nonce).unwrap(),
             data: transaction.data.as_bytes().to_vec(),
+            transaction_type: EthereumTransactionType::Legacy,
+            max_fee_per_gas: EthereumAmount::from_wei("0").unwrap(),
+            max_priority_fee_per_gas: EthereumAmount::from_wei("0").unwrap(),
+            access_list: vec![],
+            chain_id: None,
         };
 
         let transaction = EthereumTransaction::<N>::new(&parameters).unwrap();
@@ -396,12 +768,17 @@ mod tests {
         let expected_signed_transaction_hash = transaction.signed_transaction_hash;
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
             nonce: U256::from_dec_str(transaction.nonce).unwrap(),
             data: transaction.data.as_bytes().to_vec(),
+            transaction_type: EthereumTransactionType::Legacy,
+            max_fee_per_gas: EthereumAmount::from_wei("0").unwrap(),
+            max_priority_fee_per_gas: EthereumAmount::from_wei("0").unwrap(),
+            access_list: vec![],
+            chain_id: None,
         };
 
         let transaction = EthereumTransaction::<N>::new(&parameters).unwrap();
@@ -416,12 +793,17 @@ mod tests {
         let expected_signed_transaction = transaction.signed_transaction;
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
             nonce: U256::from_dec_str(transaction.nonce).unwrap(),
             data: transaction.data.as_bytes().to_vec(),
+            transaction_type: EthereumTransactionType::Legacy,
+            max_fee_per_gas: EthereumAmount::from_wei("0").unwrap(),
+            max_priority_fee_per_gas: EthereumAmount::from_wei("0").unwrap(),
+            access_list: vec![],
+            chain_id: None,
         };
 
         let transaction = EthereumTransaction::<N>::new(&parameters).unwrap();
@@ -823,4 +1205,753 @@ mod tests {
                 .for_each(test_to_string::<N>);
         }
     }
+
+    mod typed_transactions {
+        use super::*;
+
+        pub struct TypedTransactionTestCase {
+            pub transaction_type: EthereumTransactionType,
+            pub nonce: &'static str,
+            pub gas_price: &'static str,
+            pub max_priority_fee_per_gas: &'static str,
+            pub max_fee_per_gas: &'static str,
+            pub gas: &'static str,
+            pub to: &'static str,
+            pub value: &'static str,
+            pub data: &'static str,
+            pub access_list: &'static [(&'static str, &'static [&'static str])],
+            pub private_key: &'static str,
+            pub signed_transaction: &'static str,
+            pub signed_transaction_hash: &'static str,
+        }
+
+        fn parameters(transaction: &TypedTransactionTestCase) -> EthereumTransactionParameters {
+            EthereumTransactionParameters {
+                receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
+                amount: EthereumAmount::from_wei(transaction.value).unwrap(),
+                gas: U256::from_dec_str(transaction.gas).unwrap(),
+                gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
+                nonce: U256::from_dec_str(transaction.nonce).unwrap(),
+                data: transaction.data.as_bytes().to_vec(),
+                transaction_type: transaction.transaction_type,
+                max_fee_per_gas: EthereumAmount::from_wei(transaction.max_fee_per_gas).unwrap(),
+                max_priority_fee_per_gas: EthereumAmount::from_wei(transaction.max_priority_fee_per_gas).unwrap(),
+                access_list: transaction
+                    .access_list
+                    .iter()
+                    .map(|(address, storage_keys)| EthereumAccessListItem {
+                        address: EthereumAddress::from_str(address).unwrap(),
+                        storage_keys: storage_keys
+                            .iter()
+                            .map(|key| {
+                                let mut storage_key = [0u8; 32];
+                                storage_key.copy_from_slice(&hex::decode(key).unwrap());
+                                storage_key
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+                chain_id: None,
+            }
+        }
+
+        fn test_sign<N: EthereumNetwork>(transaction: &TypedTransactionTestCase) {
+            let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+            let parameters = parameters(transaction);
+
+            let unsigned_transaction = EthereumTransaction::<N>::new(&parameters).unwrap();
+            let signed_transaction = unsigned_transaction.sign(&private_key).unwrap();
+
+            assert_eq!(
+                private_key.to_address(&EthereumFormat::Standard).unwrap(),
+                signed_transaction.sender().unwrap()
+            );
+            assert_eq!(parameters, signed_transaction.parameters());
+            assert_eq!(transaction.signed_transaction, signed_transaction.to_string());
+            assert_eq!(
+                transaction.signed_transaction_hash,
+                signed_transaction.to_transaction_id().unwrap().to_string()
+            );
+        }
+
+        fn test_from_transaction_bytes<N: EthereumNetwork>(transaction: &TypedTransactionTestCase) {
+            let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+            let expected_sender = Some(private_key.to_address(&EthereumFormat::Standard).unwrap());
+            let expected_parameters = parameters(transaction);
+            let signed_transaction_bytes = hex::decode(&transaction.signed_transaction[2..]).unwrap();
+
+            let signed_transaction =
+                EthereumTransaction::<N>::from_transaction_bytes(&signed_transaction_bytes).unwrap();
+            assert_eq!(expected_sender, signed_transaction.sender());
+            assert_eq!(expected_parameters, signed_transaction.parameters());
+            assert_eq!(
+                signed_transaction_bytes,
+                signed_transaction.to_transaction_bytes().unwrap()
+            );
+
+            let unsigned_transaction = EthereumTransaction::<N>::new(&expected_parameters).unwrap();
+            let unsigned_transaction_bytes = unsigned_transaction.to_transaction_bytes().unwrap();
+            let parsed_transaction =
+                EthereumTransaction::<N>::from_transaction_bytes(&unsigned_transaction_bytes).unwrap();
+            assert_eq!(unsigned_transaction, parsed_transaction);
+        }
+
+        mod mainnet {
+            use super::*;
+
+            type N = Mainnet;
+
+            const TRANSACTIONS: [TypedTransactionTestCase; 4] = [
+                TypedTransactionTestCase {
+                    transaction_type: EthereumTransactionType::AccessList,
+                    nonce: "0",
+                    gas_price: "1000000000",
+                    max_priority_fee_per_gas: "0",
+                    max_fee_per_gas: "0",
+                    gas: "30000",
+                    to: "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65",
+                    value: "1000000000000000000",
+                    data: "",
+                    access_list: &[(
+                        "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65",
+                        &[
+                            "0000000000000000000000000000000000000000000000000000000000000003",
+                            "0000000000000000000000000000000000000000000000000000000000000007",
+                        ],
+                    )],
+                    private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+                    signed_transaction: "0x01f8c90180843b9aca0082753094b5d590a6abf5e349c1b6c511bc87ceabfb3d7e65880de0b6b3a764000080f85bf85994b5d590a6abf5e349c1b6c511bc87ceabfb3d7e65f842a00000000000000000000000000000000000000000000000000000000000000003a0000000000000000000000000000000000000000000000000000000000000000701a0725f36629cb3389727d9bce2d93dea5692204ed9f5136b4f87cfcdb87426dd5ca0406413ea9a3c902e20110c1c7e1e73f3443b1480059b1930cebfe23c2406f21f",
+                    signed_transaction_hash: "0x61d85d92b2db03195ce9e52b9e708adf682312de1d1bf4007af1ee22120e1550",
+                },
+                TypedTransactionTestCase {
+                    transaction_type: EthereumTransactionType::AccessList,
+                    nonce: "12345",
+                    gas_price: "2000000000",
+                    max_priority_fee_per_gas: "0",
+                    max_fee_per_gas: "0",
+                    gas: "54000",
+                    to: "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6",
+                    value: "1000000000000000000000",
+                    data: "Send 1000 ETH",
+                    access_list: &[],
+                    private_key: "6cff516706e4eef887c3906f279efa86ac2eeb669b1a2a9f009e85c362fb640c",
+                    signed_transaction: "0x01f87d01823039847735940082d2f09452c3a8a79a521d10b25569847cb1a3ffb66550d6893635c9adc5dea000008d53656e64203130303020455448c080a08fb32e283ddaa6079e9c6b37d16c5e49c4ff693ba8b351fff8029c6a245e63eca00cb31f13bf48b7f54b73afed74ed44ee9dea38fd78ed2c3860b8d0130c1998ed",
+                    signed_transaction_hash: "0x72624d95fbf21cf06055f8abe664d09ff0f0ae4821c782f5032a675870490095",
+                },
+                TypedTransactionTestCase {
+                    transaction_type: EthereumTransactionType::FeeMarket,
+                    nonce: "1",
+                    gas_price: "0",
+                    max_priority_fee_per_gas: "1500000000",
+                    max_fee_per_gas: "30000000000",
+                    gas: "21000",
+                    to: "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65",
+                    value: "1000000000000000000",
+                    data: "",
+                    access_list: &[],
+                    private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+                    signed_transaction: "0x02f87301018459682f008506fc23ac0082520894b5d590a6abf5e349c1b6c511bc87ceabfb3d7e65880de0b6b3a764000080c001a0209e0166b9d090ecd60c0117cb935235edb8b52486944709e9a4d80038023134a02c9927fa923bf4ca2d0918b51c7425940359f4b175220ed1ff1ae1e0972cdc43",
+                    signed_transaction_hash: "0xdb6b7c1acb91261d7b7ebf5e040d166a006bc69c7d26f58e4fdcd993aa4847f2",
+                },
+                TypedTransactionTestCase {
+                    transaction_type: EthereumTransactionType::FeeMarket,
+                    nonce: "12346",
+                    gas_price: "0",
+                    max_priority_fee_per_gas: "2000000000",
+                    max_fee_per_gas: "100000000000",
+                    gas: "60000",
+                    to: "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6",
+                    value: "500000000000000000",
+                    data: "EIP-1559",
+                    access_list: &[
+                        (
+                            "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65",
+                            &[
+                                "0000000000000000000000000000000000000000000000000000000000000003",
+                                "0000000000000000000000000000000000000000000000000000000000000007",
+                            ],
+                        ),
+                        ("0x52C3a8a79a521D10b25569847CB1a3FfB66550D6", &[]),
+                    ],
+                    private_key: "6cff516706e4eef887c3906f279efa86ac2eeb669b1a2a9f009e85c362fb640c",
+                    signed_transaction: "0x02f8f00182303a847735940085174876e80082ea609452c3a8a79a521d10b25569847cb1a3ffb66550d68806f05b59d3b20000884549502d31353539f872f85994b5d590a6abf5e349c1b6c511bc87ceabfb3d7e65f842a00000000000000000000000000000000000000000000000000000000000000003a00000000000000000000000000000000000000000000000000000000000000007d69452c3a8a79a521d10b25569847cb1a3ffb66550d6c001a02d999375f8e16ff8561c44110dabd867f8a5d1f92756e8bf0c39285487c4a27ca0196be9b9316495c2b53eaedcd9b5b62e0bf0b8c6e68d3c91e3fe44071a856ed0",
+                    signed_transaction_hash: "0x895f281f9b486a13a3cc3c8612d12963df7ca09f75cffa770b649571df49028d",
+                },
+            ];
+
+            #[test]
+            fn sign() {
+                TRANSACTIONS.iter().for_each(test_sign::<N>);
+            }
+
+            #[test]
+            fn from_transaction_bytes() {
+                TRANSACTIONS.iter().for_each(test_from_transaction_bytes::<N>);
+            }
+
+            #[test]
+            fn invalid_chain_id() {
+                TRANSACTIONS.iter().for_each(|transaction| {
+                    let transaction_bytes = hex::decode(&transaction.signed_transaction[2..]).unwrap();
+                    assert!(EthereumTransaction::<Goerli>::from_transaction_bytes(&transaction_bytes).is_err());
+                });
+            }
+
+            #[test]
+            fn invalid_transaction_type() {
+                let mut transaction_bytes = hex::decode(&TRANSACTIONS[0].signed_transaction[2..]).unwrap();
+                transaction_bytes[0] = 0x03;
+                assert!(EthereumTransaction::<N>::from_transaction_bytes(&transaction_bytes).is_err());
+            }
+        }
+
+        mod goerli {
+            use super::*;
+
+            type N = Goerli;
+
+            // The signature R value has a leading zero byte, which is omitted in the RLP encoding.
+            const TRANSACTIONS: [TypedTransactionTestCase; 1] = [TypedTransactionTestCase {
+                transaction_type: EthereumTransactionType::FeeMarket,
+                nonce: "246",
+                gas_price: "0",
+                max_priority_fee_per_gas: "1000000000",
+                max_fee_per_gas: "20000000000",
+                gas: "21000",
+                to: "0x9Fd6441Ce8CC4524FaCd033921B6A2e910EC00FC",
+                value: "49580000000000000",
+                data: "",
+                access_list: &[],
+                private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+                signed_transaction: "0x02f8720581f6843b9aca008504a817c800825208949fd6441ce8cc4524facd033921b6a2e910ec00fc87b024bf4ff6c00080c0019fec2e190959a22cb2104f8ccbe0e1d2f5d3aa20821f70cd9692272b543e6e33a06ef1ac35e8de6acbc34eb957f6dbe7b17d7db3f7d17d0fd1646de59954229b13",
+                signed_transaction_hash: "0xc983c4828f9f9d0d0f6b3c25f1cfcb5b567e108557a19108512ed98264fc2fc3",
+            }];
+
+            #[test]
+            fn sign() {
+                TRANSACTIONS.iter().for_each(test_sign::<N>);
+            }
+
+            #[test]
+            fn from_transaction_bytes() {
+                TRANSACTIONS.iter().for_each(test_from_transaction_bytes::<N>);
+            }
+        }
+    }
+
+    mod contract_creation {
+        use super::*;
+
+        type N = Mainnet;
+
+        pub struct ContractCreationTestCase {
+            pub transaction_type: EthereumTransactionType,
+            pub nonce: &'static str,
+            pub gas_price: &'static str,
+            pub max_priority_fee_per_gas: &'static str,
+            pub max_fee_per_gas: &'static str,
+            pub gas: &'static str,
+            pub value: &'static str,
+            pub init_code: &'static str,
+            pub private_key: &'static str,
+            pub signed_transaction: &'static str,
+            pub signed_transaction_hash: &'static str,
+            pub contract_address: &'static str,
+        }
+
+        const TRANSACTIONS: [ContractCreationTestCase; 4] = [
+            ContractCreationTestCase {
+                transaction_type: EthereumTransactionType::Legacy,
+                nonce: "0",
+                gas_price: "1000000000",
+                max_priority_fee_per_gas: "0",
+                max_fee_per_gas: "0",
+                gas: "100000",
+                value: "0",
+                init_code: "600a600c600039600a6000f3602a60005260206000f3",
+                private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+                signed_transaction: "0xf86680843b9aca00830186a0808096600a600c600039600a6000f3602a60005260206000f325a05162d702983f9173b01546bf63fb3716631e23210ed764dd72e8d0399b094301a0112eb4a72c908e52ead0619e3ac84df37206cb8e14f707c67021c6c53a91a36b",
+                signed_transaction_hash: "0xbef2a00214c080fd0d20544842c765ef0b0826674c42aa8a92d2711c6ab98dcd",
+                contract_address: "0x22d3CDD4ca705616B11fa73ec6C1eC43d929a130",
+            },
+            ContractCreationTestCase {
+                transaction_type: EthereumTransactionType::Legacy,
+                nonce: "12345",
+                gas_price: "2000000000",
+                max_priority_fee_per_gas: "0",
+                max_fee_per_gas: "0",
+                gas: "120000",
+                value: "10000000000000000",
+                init_code: "600a600c600039600a6000f3602a60005260206000f3",
+                private_key: "6cff516706e4eef887c3906f279efa86ac2eeb669b1a2a9f009e85c362fb640c",
+                signed_transaction: "0xf86f82303984773594008301d4c080872386f26fc1000096600a600c600039600a6000f3602a60005260206000f326a0dd4147bff6d3c3af68a6caa63aae7585cfb7691d4826cde7f0860ea8fbc31fa6a0256aa2a4c4b996bb56f62b55a47d14d3bc705dc071359a4f9ad06d0bc59821e6",
+                signed_transaction_hash: "0xeeee19464e9a9566dfa7896c3b3d84820459eb305c5e3a59303a967150582fe3",
+                contract_address: "0xA6BC4Fb895F0F15F358620E6d2c70007E5d2d863",
+            },
+            ContractCreationTestCase {
+                transaction_type: EthereumTransactionType::AccessList,
+                nonce: "1",
+                gas_price: "1000000000",
+                max_priority_fee_per_gas: "0",
+                max_fee_per_gas: "0",
+                gas: "100000",
+                value: "0",
+                init_code: "600a600c600039600a6000f3602a60005260206000f3",
+                private_key: "6cff516706e4eef887c3906f279efa86ac2eeb669b1a2a9f009e85c362fb640c",
+                signed_transaction: "0x01f8680101843b9aca00830186a0808096600a600c600039600a6000f3602a60005260206000f3c001a0d8374537320f52def2dc8582ea12a607493981b0e96377d1d715a4a697f7a9a7a07b6dc68370b5c68bc3c51f43425eec34fad7ee7c13fac22c985158d516c33166",
+                signed_transaction_hash: "0x9df741fe60f42800ef1fc62adaf6081972ee27b4097d7a36c1e2fcffe19d18d1",
+                contract_address: "0xa4c99615AF4bA54270b288d704D61573d2DaFf4c",
+            },
+            ContractCreationTestCase {
+                transaction_type: EthereumTransactionType::FeeMarket,
+                nonce: "7",
+                gas_price: "0",
+                max_priority_fee_per_gas: "1500000000",
+                max_fee_per_gas: "30000000000",
+                gas: "100000",
+                value: "0",
+                init_code: "600a600c600039600a6000f3602a60005260206000f3",
+                private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+                signed_transaction: "0x02f86e01078459682f008506fc23ac00830186a0808096600a600c600039600a6000f3602a60005260206000f3c080a09084aba9d87b609bd7a50f9c65748197bebeb4d3f8c18cb98cda041cc54a5162a07778c5aa47863c189596f8578cbec93d5372db5584a7b8183eefe1fd58d78fa8",
+                signed_transaction_hash: "0x442b3d503ce04cddf75357ad9b8a8a78fd4b947a3a17701871b5ca3d94dfcae5",
+                contract_address: "0x2896c5491223d551bB854e384aAe5953fF99560d",
+            },
+        ];
+
+        fn parameters(transaction: &ContractCreationTestCase) -> EthereumTransactionParameters {
+            EthereumTransactionParameters {
+                receiver: None,
+                amount: EthereumAmount::from_wei(transaction.value).unwrap(),
+                gas: U256::from_dec_str(transaction.gas).unwrap(),
+                gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
+                nonce: U256::from_dec_str(transaction.nonce).unwrap(),
+                data: hex::decode(transaction.init_code).unwrap(),
+                transaction_type: transaction.transaction_type,
+                max_fee_per_gas: EthereumAmount::from_wei(transaction.max_fee_per_gas).unwrap(),
+                max_priority_fee_per_gas: EthereumAmount::from_wei(transaction.max_priority_fee_per_gas).unwrap(),
+                access_list: vec![],
+                chain_id: None,
+            }
+        }
+
+        #[test]
+        fn sign() {
+            TRANSACTIONS.iter().for_each(|transaction| {
+                let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+                let unsigned_transaction = EthereumTransaction::<N>::new(&parameters(transaction)).unwrap();
+                assert_eq!(None, unsigned_transaction.contract_address().unwrap());
+
+                let signed_transaction = unsigned_transaction.sign(&private_key).unwrap();
+                assert_eq!(transaction.signed_transaction, signed_transaction.to_string());
+                assert_eq!(
+                    transaction.signed_transaction_hash,
+                    signed_transaction.to_transaction_id().unwrap().to_string()
+                );
+                assert_eq!(
+                    transaction.contract_address,
+                    signed_transaction.contract_address().unwrap().unwrap().to_string()
+                );
+            });
+        }
+
+        #[test]
+        fn from_transaction_bytes() {
+            TRANSACTIONS.iter().for_each(|transaction| {
+                let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+                let signed_transaction_bytes = hex::decode(&transaction.signed_transaction[2..]).unwrap();
+
+                let signed_transaction =
+                    EthereumTransaction::<N>::from_transaction_bytes(&signed_transaction_bytes).unwrap();
+                assert_eq!(
+                    private_key.to_address(&EthereumFormat::Standard).unwrap(),
+                    signed_transaction.sender().unwrap()
+                );
+                assert_eq!(parameters(transaction), signed_transaction.parameters());
+                assert_eq!(
+                    signed_transaction_bytes,
+                    signed_transaction.to_transaction_bytes().unwrap()
+                );
+                assert_eq!(
+                    transaction.contract_address,
+                    signed_transaction.contract_address().unwrap().unwrap().to_string()
+                );
+
+                let unsigned_transaction = EthereumTransaction::<N>::new(&parameters(transaction)).unwrap();
+                let unsigned_transaction_bytes = unsigned_transaction.to_transaction_bytes().unwrap();
+                let parsed_transaction =
+                    EthereumTransaction::<N>::from_transaction_bytes(&unsigned_transaction_bytes).unwrap();
+                assert_eq!(unsigned_transaction, parsed_transaction);
+            });
+        }
+
+        #[test]
+        fn invalid_receiver_length() {
+            // Replaces the empty receiver with a 19-byte receiver
+            let receiver = format!("93{}", "11".repeat(19));
+            let transaction =
+                TRANSACTIONS[0].signed_transaction[2..].replacen("830186a080", &format!("830186a0{}", receiver), 1);
+            let mut transaction_bytes = hex::decode(&transaction).unwrap();
+            transaction_bytes[1] += 19;
+            assert!(EthereumTransaction::<N>::from_transaction_bytes(&transaction_bytes).is_err());
+        }
+    }
+
+    mod chain_ids {
+        use super::*;
+        use crate::{CustomChain, Holesky, Sepolia};
+
+        pub struct ChainIdTestCase {
+            pub transaction_type: EthereumTransactionType,
+            pub chain_id: u64,
+            pub nonce: &'static str,
+            pub gas_price: &'static str,
+            pub max_priority_fee_per_gas: &'static str,
+            pub max_fee_per_gas: &'static str,
+            pub gas: &'static str,
+            pub to: &'static str,
+            pub value: &'static str,
+            pub data: &'static str,
+            pub private_key: &'static str,
+            pub signed_transaction: &'static str,
+            pub signed_transaction_hash: &'static str,
+        }
+
+        fn parameters(transaction: &ChainIdTestCase, chain_id: Option<u64>) -> EthereumTransactionParameters {
+            EthereumTransactionParameters {
+                receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
+                amount: EthereumAmount::from_wei(transaction.value).unwrap(),
+                gas: U256::from_dec_str(transaction.gas).unwrap(),
+                gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
+                nonce: U256::from_dec_str(transaction.nonce).unwrap(),
+                data: transaction.data.as_bytes().to_vec(),
+                transaction_type: transaction.transaction_type,
+                max_fee_per_gas: EthereumAmount::from_wei(transaction.max_fee_per_gas).unwrap(),
+                max_priority_fee_per_gas: EthereumAmount::from_wei(transaction.max_priority_fee_per_gas).unwrap(),
+                access_list: vec![],
+                chain_id,
+            }
+        }
+
+        fn test_sign<N: EthereumNetwork>(transaction: &ChainIdTestCase, chain_id: Option<u64>) {
+            let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+            let unsigned_transaction = EthereumTransaction::<N>::new(&parameters(transaction, chain_id)).unwrap();
+            let signed_transaction = unsigned_transaction.sign(&private_key).unwrap();
+
+            assert_eq!(Some(transaction.chain_id), signed_transaction.chain_id());
+            assert_eq!(transaction.signed_transaction, signed_transaction.to_string());
+            assert_eq!(
+                transaction.signed_transaction_hash,
+                signed_transaction.to_transaction_id().unwrap().to_string()
+            );
+        }
+
+        fn test_from_transaction_bytes<N: EthereumNetwork>(transaction: &ChainIdTestCase, chain_id: Option<u64>) {
+            let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+            let expected_parameters = parameters(transaction, chain_id);
+            let signed_transaction_bytes = hex::decode(&transaction.signed_transaction[2..]).unwrap();
+
+            let signed_transaction =
+                EthereumTransaction::<N>::from_transaction_bytes(&signed_transaction_bytes).unwrap();
+            assert_eq!(
+                private_key.to_address(&EthereumFormat::Standard).unwrap(),
+                signed_transaction.sender().unwrap()
+            );
+            assert_eq!(expected_parameters, signed_transaction.parameters());
+            assert_eq!(Some(transaction.chain_id), signed_transaction.chain_id());
+            assert_eq!(
+                signed_transaction_bytes,
+                signed_transaction.to_transaction_bytes().unwrap()
+            );
+
+            let unsigned_transaction = EthereumTransaction::<N>::new(&expected_parameters).unwrap();
+            let unsigned_transaction_bytes = unsigned_transaction.to_transaction_bytes().unwrap();
+            let parsed_transaction =
+                EthereumTransaction::<N>::from_transaction_bytes(&unsigned_transaction_bytes).unwrap();
+            assert_eq!(unsigned_transaction, parsed_transaction);
+        }
+
+        mod custom_chain {
+            use super::*;
+
+            type N = CustomChain;
+
+            const TRANSACTIONS: [ChainIdTestCase; 3] = [
+                ChainIdTestCase {
+                    transaction_type: EthereumTransactionType::Legacy,
+                    chain_id: 42161,
+                    nonce: "3",
+                    gas_price: "100000000",
+                    max_priority_fee_per_gas: "0",
+                    max_fee_per_gas: "0",
+                    gas: "21000",
+                    to: "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65",
+                    value: "100000000000000000",
+                    data: "",
+                    private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+                    signed_transaction: "0xf86e038405f5e10082520894b5d590a6abf5e349c1b6c511bc87ceabfb3d7e6588016345785d8a00008083014985a071bdc65922bce31e30942ce4ffcd0692b718ac7791c9cec8d4c2688511b2b166a0378ebe507197993bef68b06874edd0b09f1ab39ee97adc908c129e824015965e",
+                    signed_transaction_hash: "0xd0952960ca6d092e227e5fb0c7a858f26be173e3cb33808e7c705a5de6827a9c",
+                },
+                ChainIdTestCase {
+                    transaction_type: EthereumTransactionType::Legacy,
+                    chain_id: 137,
+                    nonce: "9",
+                    gas_price: "30000000000",
+                    max_priority_fee_per_gas: "0",
+                    max_fee_per_gas: "0",
+                    gas: "21000",
+                    to: "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6",
+                    value: "1000000000000000000",
+                    data: "",
+                    private_key: "6cff516706e4eef887c3906f279efa86ac2eeb669b1a2a9f009e85c362fb640c",
+                    signed_transaction: "0xf86e098506fc23ac008252089452c3a8a79a521d10b25569847cb1a3ffb66550d6880de0b6b3a764000080820135a07b0199b98424fc60160d9909416db5b34206444a09b458f7018f45bf300f9bc6a03a93b86ebb90276b45a45c2a3e27f02114304d719a9f35883699281ec9f3ea89",
+                    signed_transaction_hash: "0xba38e1878b7b0a0e41ba6af821a4c6870dbe7786249600bca5d6a595e6583dfa",
+                },
+                ChainIdTestCase {
+                    transaction_type: EthereumTransactionType::FeeMarket,
+                    chain_id: 42161,
+                    nonce: "4",
+                    gas_price: "0",
+                    max_priority_fee_per_gas: "0",
+                    max_fee_per_gas: "100000000",
+                    gas: "30000",
+                    to: "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6",
+                    value: "10000000000000000",
+                    data: "Arbitrum",
+                    private_key: "6cff516706e4eef887c3906f279efa86ac2eeb669b1a2a9f009e85c362fb640c",
+                    signed_transaction: "0x02f87782a4b104808405f5e1008275309452c3a8a79a521d10b25569847cb1a3ffb66550d6872386f26fc1000088417262697472756dc001a0d8599904fcf150f5c687ee83eb4d2e15d83f514a5f28b9cab041012006bfe108a0774a9568564467bec8f01d9c6c9a1c91c0a735bbe5d72c11fee72a4b51e387fd",
+                    signed_transaction_hash: "0xac7a3789ac0318abc9cd745c2db21c331d2bd2689a53dc2cf911f861ac8ab3d9",
+                },
+            ];
+
+            #[test]
+            fn sign() {
+                TRANSACTIONS
+                    .iter()
+                    .for_each(|transaction| test_sign::<N>(transaction, Some(transaction.chain_id)));
+            }
+
+            #[test]
+            fn from_transaction_bytes() {
+                TRANSACTIONS
+                    .iter()
+                    .for_each(|transaction| test_from_transaction_bytes::<N>(transaction, Some(transaction.chain_id)));
+            }
+
+            #[test]
+            fn missing_chain_id() {
+                TRANSACTIONS.iter().for_each(|transaction| {
+                    assert!(EthereumTransaction::<N>::new(&parameters(transaction, None)).is_err());
+                    assert!(EthereumTransaction::<N>::new(&parameters(transaction, Some(0))).is_err());
+                });
+            }
+
+            #[test]
+            fn invalid_chain_id() {
+                TRANSACTIONS.iter().for_each(|transaction| {
+                    let parameters = parameters(transaction, Some(transaction.chain_id));
+                    assert!(EthereumTransaction::<Mainnet>::new(&parameters).is_err());
+
+                    let transaction_bytes = hex::decode(&transaction.signed_transaction[2..]).unwrap();
+                    assert!(EthereumTransaction::<Mainnet>::from_transaction_bytes(&transaction_bytes).is_err());
+                });
+            }
+
+            #[test]
+            fn overflowing_chain_id() {
+                let transaction = &TRANSACTIONS[0];
+                let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+                let transaction =
+                    EthereumTransaction::<N>::new(&parameters(transaction, Some(u64::max_value() / 2))).unwrap();
+                assert!(transaction.sign(&private_key).is_err());
+            }
+        }
+
+        mod sepolia {
+            use super::*;
+
+            type N = Sepolia;
+
+            const TRANSACTIONS: [ChainIdTestCase; 2] = [
+                ChainIdTestCase {
+                    transaction_type: EthereumTransactionType::Legacy,
+                    chain_id: 11155111,
+                    nonce: "0",
+                    gas_price: "1000000000",
+                    max_priority_fee_per_gas: "0",
+                    max_fee_per_gas: "0",
+                    gas: "21000",
+                    to: "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65",
+                    value: "1000000000000000000",
+                    data: "",
+                    private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+                    signed_transaction: "0xf86f80843b9aca0082520894b5d590a6abf5e349c1b6c511bc87ceabfb3d7e65880de0b6b3a7640000808401546d71a0c66182914850edb9a5770b59b2d0ad6097f178ee15be99fb164b49fd392feccea00531ff4d74bba7ec0ee0a06cb10b84d553790482410682f63409c605442b49b5",
+                    signed_transaction_hash: "0x4c05507ffd05a49201826f82207c14bd104f484cc684b4fcd8ceba09f0522ffe",
+                },
+                ChainIdTestCase {
+                    transaction_type: EthereumTransactionType::FeeMarket,
+                    chain_id: 11155111,
+                    nonce: "1",
+                    gas_price: "0",
+                    max_priority_fee_per_gas: "1000000000",
+                    max_fee_per_gas: "20000000000",
+                    gas: "21000",
+                    to: "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6",
+                    value: "1000000000000000",
+                    data: "",
+                    private_key: "6cff516706e4eef887c3906f279efa86ac2eeb669b1a2a9f009e85c362fb640c",
+                    signed_transaction: "0x02f87583aa36a701843b9aca008504a817c8008252089452c3a8a79a521d10b25569847cb1a3ffb66550d687038d7ea4c6800080c001a0464dcfb098080689db9debd13f47fe22ea9b81c631dfbb0bcf9945f40eacabffa07de9917a7420d03e2ecab81b346322cd469c97126ec79488d71054bf98e8550d",
+                    signed_transaction_hash: "0x0bbef4cd8893acef720d315ebbe0b907448c20cbeda7a005189e812a25847189",
+                },
+            ];
+
+            #[test]
+            fn sign() {
+                TRANSACTIONS
+                    .iter()
+                    .for_each(|transaction| test_sign::<N>(transaction, None));
+            }
+
+            #[test]
+            fn from_transaction_bytes() {
+                TRANSACTIONS
+                    .iter()
+                    .for_each(|transaction| test_from_transaction_bytes::<N>(transaction, None));
+            }
+
+            #[test]
+            fn invalid_chain_id() {
+                TRANSACTIONS.iter().for_each(|transaction| {
+                    let transaction_bytes = hex::decode(&transaction.signed_transaction[2..]).unwrap();
+                    assert!(EthereumTransaction::<Holesky>::from_transaction_bytes(&transaction_bytes).is_err());
+                });
+            }
+        }
+
+        mod holesky {
+            use super::*;
+
+            type N = Holesky;
+
+            const TRANSACTIONS: [ChainIdTestCase; 2] = [
+                ChainIdTestCase {
+                    transaction_type: EthereumTransactionType::Legacy,
+                    chain_id: 17000,
+                    nonce: "5",
+                    gas_price: "2000000000",
+                    max_priority_fee_per_gas: "0",
+                    max_fee_per_gas: "0",
+                    gas: "21000",
+                    to: "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6",
+                    value: "1000000000000000",
+                    data: "Holesky",
+                    private_key: "6cff516706e4eef887c3906f279efa86ac2eeb669b1a2a9f009e85c362fb640c",
+                    signed_transaction: "0xf8730584773594008252089452c3a8a79a521d10b25569847cb1a3ffb66550d687038d7ea4c6800087486f6c65736b798284f3a06f4ba56bbac3e8100d7903d794617b6f3640b29e80ba323d459781f8c997e129a07241b6429cd7efb457ac2deb5c7cf754b01df0dcbacc67e9e55c973f6d2468d8",
+                    signed_transaction_hash: "0x03bf6200b82cac5e8ccf7a908210ddb4a3b6020b3259ed2d7eef46bbf62e4453",
+                },
+                ChainIdTestCase {
+                    transaction_type: EthereumTransactionType::FeeMarket,
+                    chain_id: 17000,
+                    nonce: "2",
+                    gas_price: "0",
+                    max_priority_fee_per_gas: "1000000000",
+                    max_fee_per_gas: "20000000000",
+                    gas: "21000",
+                    to: "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65",
+                    value: "1000000000000000",
+                    data: "",
+                    private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+                    signed_transaction: "0x02f87482426802843b9aca008504a817c80082520894b5d590a6abf5e349c1b6c511bc87ceabfb3d7e6587038d7ea4c6800080c080a0d44d71983cf1d634b6da77281dc7e3244b94736dbf2e84440c9d06f8a67a809ca0453e530cf8e48d47697aee623dea5bf282970b9bdc7436ec7c2e5965bcd24e9b",
+                    signed_transaction_hash: "0xcb5a4a8c4395a46207b209136a2d332139f95c3f186451ac34e6809a5307f5b5",
+                },
+            ];
+
+            #[test]
+            fn sign() {
+                TRANSACTIONS
+                    .iter()
+                    .for_each(|transaction| test_sign::<N>(transaction, None));
+            }
+
+            #[test]
+            fn from_transaction_bytes() {
+                TRANSACTIONS
+                    .iter()
+                    .for_each(|transaction| test_from_transaction_bytes::<N>(transaction, None));
+            }
+        }
+
+        mod unprotected {
+            use super::*;
+
+            // Legacy transactions signed without a chain id, with V as 27 or 28 (pre EIP-155)
+            const TRANSACTIONS: [ChainIdTestCase; 2] = [
+                ChainIdTestCase {
+                    transaction_type: EthereumTransactionType::Legacy,
+                    chain_id: 0,
+                    nonce: "0",
+                    gas_price: "20000000000",
+                    max_priority_fee_per_gas: "0",
+                    max_fee_per_gas: "0",
+                    gas: "21000",
+                    to: "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65",
+                    value: "1000000000000000000",
+                    data: "",
+                    private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+                    signed_transaction: "0xf86c808504a817c80082520894b5d590a6abf5e349c1b6c511bc87ceabfb3d7e65880de0b6b3a7640000801ca0067a4d12414ad4f30937baf3150bc2cd2c4e25018f68352e4696b261c119c272a072aba23aa98812457943f3d9c589b9bbde73b3e5a8e1370b6ca19e1c97aa238d",
+                    signed_transaction_hash: "0x3fdbf6750965d9e2b9c9164f4e10cfa4d8784500e1d2fba22446f672f4541015",
+                },
+                ChainIdTestCase {
+                    transaction_type: EthereumTransactionType::Legacy,
+                    chain_id: 0,
+                    nonce: "12345",
+                    gas_price: "2000000000",
+                    max_priority_fee_per_gas: "0",
+                    max_fee_per_gas: "0",
+                    gas: "54000",
+                    to: "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6",
+                    value: "1000000000000000000000",
+                    data: "Send 1000 ETH",
+                    private_key: "6cff516706e4eef887c3906f279efa86ac2eeb669b1a2a9f009e85c362fb640c",
+                    signed_transaction: "0xf87b823039847735940082d2f09452c3a8a79a521d10b25569847cb1a3ffb66550d6893635c9adc5dea000008d53656e642031303030204554481ba05bc5c55a5887c782349275be0e76080e0c4093fcadf50b5f954253d59b6ce11ba0159ce2102810917d03a727e541eb15669503895a40dbaada263045f17523e01b",
+                    signed_transaction_hash: "0xd73dad755a1603ae42284e46d54d7d15f1d0eefe844a21a0204506f807dd4ab6",
+                },
+            ];
+
+            fn test_from_transaction_bytes<N: EthereumNetwork>(transaction: &ChainIdTestCase) {
+                let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+                let signed_transaction_bytes = hex::decode(&transaction.signed_transaction[2..]).unwrap();
+
+                let signed_transaction =
+                    EthereumTransaction::<N>::from_transaction_bytes(&signed_transaction_bytes).unwrap();
+                assert_eq!(
+                    private_key.to_address(&EthereumFormat::Standard).unwrap(),
+                    signed_transaction.sender().unwrap()
+                );
+                assert_eq!(parameters(transaction, None), signed_transaction.parameters());
+                assert_eq!(None, signed_transaction.chain_id());
+                assert_eq!(
+                    signed_transaction_bytes,
+                    signed_transaction.to_transaction_bytes().unwrap()
+                );
+                assert_eq!(
+                    transaction.signed_transaction_hash,
+                    signed_transaction.to_transaction_id().unwrap().to_string()
+                );
+            }
+
+            #[test]
+            fn from_transaction_bytes() {
+                TRANSACTIONS.iter().for_each(test_from_transaction_bytes::<Mainnet>);
+                TRANSACTIONS.iter().for_each(test_from_transaction_bytes::<Goerli>);
+            }
+
+            #[test]
+            fn invalid_v() {
+                // Replaces V with 29, which is neither unprotected nor EIP-155
+                let transaction = TRANSACTIONS[0].signed_transaction[2..].replacen("801ca0", "801da0", 1);
+                let transaction_bytes = hex::decode(&transaction).unwrap();
+                assert!(EthereumTransaction::<Mainnet>::from_transaction_bytes(&transaction_bytes).is_err());
+            }
+        }
+    }
 }
@@ -0,0 +1,597 @@
+//!
+//! EIP-712
+//!
+//! This module contains the hashing and signing of typed structured data.
+//! https://github.com/ethereum/EIPs/blob/master/EIPS/eip-712.md
+//!
+//! Typed data is given in the JSON format of `eth_signTypedData_v4`, which consists of the struct
+//! `types`, the `primaryType` of the message, the `domain` and the `message` itself. The signed hash
+//! is `keccak256(0x19 || 0x01 || domainSeparator || hashStruct(message))`.
+//!
+
+use crate::abi::token::{negate, parse_integer};
+use crate::format::EthereumFormat;
+use crate::private_key::EthereumPrivateKey;
+use crate::public_key::EthereumPublicKey;
+use wagyu_model::{AddressError, PublicKey};
+
+use ethereum_types::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::BTreeMap, collections::BTreeSet, fmt, str::FromStr};
+use tiny_keccak::keccak256;
+
+/// The name of the domain struct type.
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// The fields of the domain struct type, in their canonical order, if the type is not given.
+const DOMAIN_FIELDS: [(&str, &str); 5] = [
+    ("name", "string"),
+    ("version", "string"),
+    ("chainId", "uint256"),
+    ("verifyingContract", "address"),
+    ("salt", "bytes32"),
+];
+
+#[derive(Debug, Fail)]
+pub enum TypedDataError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "invalid JSON: {}", _0)]
+    InvalidJson(String),
+
+    #[fail(display = "invalid signature: {}", _0)]
+    InvalidSignature(String),
+
+    #[fail(display = "invalid type {}", _0)]
+    InvalidType(String),
+
+    #[fail(display = "invalid value for type {}: {}", _0, _1)]
+    InvalidValue(String, String),
+
+    #[fail(display = "missing field {} in {}", _0, _1)]
+    MissingField(String, String),
+
+    #[fail(display = "undefined type {}", _0)]
+    UndefinedType(String),
+}
+
+impl From<AddressError> for TypedDataError {
+    fn from(error: AddressError) -> Self {
+        TypedDataError::AddressError(error)
+    }
+}
+
+impl From<hex::FromHexError> for TypedDataError {
+    fn from(error: hex::FromHexError) -> Self {
+        TypedDataError::InvalidJson(format!("{:?}", error))
+    }
+}
+
+impl From<secp256k1::Error> for TypedDataError {
+    fn from(error: secp256k1::Error) -> Self {
+        TypedDataError::InvalidSignature(format!("{:?}", error))
+    }
+}
+
+impl From<serde_json::error::Error> for TypedDataError {
+    fn from(error: serde_json::error::Error) -> Self {
+        TypedDataError::InvalidJson(format!("{:?}", error))
+    }
+}
+
+/// Represents a member of an EIP-712 struct type
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct TypedDataField {
+    /// The name of the member
+    pub name: String,
+    /// The type of the member
+    #[serde(rename = "type")]
+    pub field_type: String,
+}
+
+/// Represents EIP-712 typed structured data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EthereumTypedData {
+    /// The struct types, by name
+    pub types: BTreeMap<String, Vec<TypedDataField>>,
+    /// The struct type of the message
+    #[serde(rename = "primaryType")]
+    pub primary_type: String,
+    /// The domain of the message
+    pub domain: Value,
+    /// The message
+    pub message: Value,
+}
+
+impl EthereumTypedData {
+    /// Returns the encoding of the given struct type, followed by its referenced struct types in alphabetical order.
+    pub fn encode_type(&self, struct_type: &str) -> Result<String, TypedDataError> {
+        let mut dependencies = BTreeSet::new();
+        self.find_dependencies(struct_type, &mut dependencies)?;
+        dependencies.remove(struct_type);
+
+        let encode_struct_type = |name: &str| -> Result<String, TypedDataError> {
+            let members: Vec<String> = self
+                .fields(name)?
+                .iter()
+                .map(|field| format!("{} {}", field.field_type, field.name))
+                .collect();
+            Ok(format!("{}({})", name, members.join(",")))
+        };
+
+        let mut encoding = encode_struct_type(struct_type)?;
+        for dependency in dependencies {
+            encoding += &encode_struct_type(&dependency)?;
+        }
+        Ok(encoding)
+    }
+
+    /// Returns the hash of the encoding of the given struct type.
+    pub fn type_hash(&self, struct_type: &str) -> Result<[u8; 32], TypedDataError> {
+        Ok(keccak256(self.encode_type(struct_type)?.as_bytes()))
+    }
+
+    /// Returns the encoding of the given struct value, as the type hash followed by the encoding of each member.
+    pub fn encode_data(&self, struct_type: &str, value: &Value) -> Result<Vec<u8>, TypedDataError> {
+        let object = match value {
+            Value::Object(object) => object,
+            _ => return Err(TypedDataError::InvalidValue(struct_type.into(), value.to_string())),
+        };
+
+        let mut encoding = self.type_hash(struct_type)?.to_vec();
+        for field in self.fields(struct_type)?.iter() {
+            match object.get(&field.name) {
+                Some(member) => encoding.extend(&self.encode_value(&field.field_type, member)?),
+                None => return Err(TypedDataError::MissingField(field.name.clone(), struct_type.into())),
+            }
+        }
+        Ok(encoding)
+    }
+
+    /// Returns the hash of the encoding of the given struct value.
+    pub fn hash_struct(&self, struct_type: &str, value: &Value) -> Result<[u8; 32], TypedDataError> {
+        Ok(keccak256(&self.encode_data(struct_type, value)?))
+    }
+
+    /// Returns the domain separator, the hash of the domain struct.
+    pub fn domain_separator(&self) -> Result<[u8; 32], TypedDataError> {
+        self.hash_struct(DOMAIN_TYPE, &self.domain)
+    }
+
+    /// Returns the hash of the typed data to be signed.
+    pub fn to_signing_hash(&self) -> Result<[u8; 32], TypedDataError> {
+        let mut encoding = vec![0x19, 0x01];
+        encoding.extend(&self.domain_separator()?);
+        // A message of the domain type only commits to the domain separator.
+        if self.primary_type != DOMAIN_TYPE {
+            encoding.extend(&self.hash_struct(&self.primary_type, &self.message)?);
+        }
+        Ok(keccak256(&encoding))
+    }
+
+    /// Returns the 65-byte signature (r || s || v) of the typed data, where v is 27 or 28.
+    pub fn sign(&self, private_key: &EthereumPrivateKey) -> Result<Vec<u8>, TypedDataError> {
+        let message = secp256k1::Message::parse(&self.to_signing_hash()?);
+        let (signature, recovery_id) = secp256k1::sign(&message, &private_key.to_secp256k1_secret_key());
+
+        let mut signature = signature.serialize().to_vec();
+        signature.push(Into::<i32>::into(recovery_id) as u8 + 27);
+        Ok(signature)
+    }
+
+    /// Returns the public key that produced the given 65-byte signature (r || s || v) of the typed data.
+    pub fn recover_public_key(&self, signature: &[u8]) -> Result<EthereumPublicKey, TypedDataError> {
+        if signature.len() != 65 {
+            return Err(TypedDataError::InvalidSignature(format!(
+                "invalid signature length {}",
+                signature.len()
+            )));
+        }
+
+        let recovery_id = match signature[64] {
+            0 | 1 => signature[64],
+            27 | 28 => signature[64] - 27,
+            v => return Err(TypedDataError::InvalidSignature(format!("invalid recovery id {}", v))),
+        };
+        let message = secp256k1::Message::parse(&self.to_signing_hash()?);
+        let public_key = secp256k1::recover(
+            &message,
+            &secp256k1::Signature::parse_slice(&signature[0..64])?,
+            &secp256k1::RecoveryId::parse(recovery_id)?,
+        )?;
+        Ok(EthereumPublicKey::from_secp256k1_public_key(public_key))
+    }
+
+    /// Returns `true` if the given signature of the typed data was produced by the private key of the given address.
+    pub fn verify(&self, signature: &[u8], address: &str) -> Result<bool, TypedDataError> {
+        let signer = self
+            .recover_public_key(signature)?
+            .to_address(&EthereumFormat::Standard)?;
+        Ok(signer.to_string().to_lowercase() == address.to_lowercase())
+    }
+
+    /// Returns the members of the given struct type.
+    /// If the domain type is not defined, it is inferred from the members present in the domain.
+    fn fields(&self, struct_type: &str) -> Result<Vec<TypedDataField>, TypedDataError> {
+        match (self.types.get(struct_type), &self.domain) {
+            (Some(fields), _) => Ok(fields.clone()),
+            (None, Value::Object(domain)) if struct_type == DOMAIN_TYPE => Ok(DOMAIN_FIELDS
+                .iter()
+                .filter(|(name, _)| domain.contains_key(*name))
+                .map(|(name, field_type)| TypedDataField {
+                    name: name.to_string(),
+                    field_type: field_type.to_string(),
+                })
+                .collect()),
+            _ => Err(TypedDataError::UndefinedType(struct_type.into())),
+        }
+    }
+
+    /// Adds the given struct type and the struct types it references, recursively, to the given set.
+    fn find_dependencies(&self, struct_type: &str, dependencies: &mut BTreeSet<String>) -> Result<(), TypedDataError> {
+        if dependencies.contains(struct_type) {
+            return Ok(());
+        }
+        dependencies.insert(struct_type.into());
+
+        for field in self.fields(struct_type)? {
+            let base_type = field.field_type.split('[').next().unwrap_or_default();
+            if self.types.contains_key(base_type) {
+                self.find_dependencies(base_type, dependencies)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the 32-byte encoding of the given value of the given type.
+    fn encode_value(&self, value_type: &str, value: &Value) -> Result<Vec<u8>, TypedDataError> {
+        let invalid_value = || TypedDataError::InvalidValue(value_type.into(), value.to_string());
+
+        // Arrays are encoded as the hash of the concatenated encodings of their elements.
+        if value_type.ends_with(']') {
+            let (element_type, length) = match value_type.rfind('[') {
+                Some(index) => (&value_type[..index], &value_type[index + 1..value_type.len() - 1]),
+                None => return Err(TypedDataError::InvalidType(value_type.into())),
+            };
+            let elements = value.as_array().ok_or_else(invalid_value)?;
+            if !length.is_empty() && length.parse::<usize>().ok() != Some(elements.len()) {
+                return Err(invalid_value());
+            }
+
+            let mut encoding = vec![];
+            for element in elements {
+                encoding.extend(self.encode_value(element_type, element)?);
+            }
+            return Ok(keccak256(&encoding).to_vec());
+        }
+
+        // Structs are encoded as their struct hash.
+        if self.types.contains_key(value_type) {
+            return Ok(self.hash_struct(value_type, value)?.to_vec());
+        }
+
+        match value_type {
+            "string" => Ok(keccak256(value.as_str().ok_or_else(invalid_value)?.as_bytes()).to_vec()),
+            "bytes" => Ok(keccak256(&decode_hex(value).ok_or_else(invalid_value)?).to_vec()),
+            "bool" => match value {
+                Value::Bool(boolean) => Ok(U256::from(*boolean as u8).to_big_endian_vec()),
+                _ => Err(invalid_value()),
+            },
+            "address" => {
+                let address = decode_hex(value).ok_or_else(invalid_value)?;
+                match address.len() {
+                    20 => Ok([vec![0u8; 12], address].concat()),
+                    _ => Err(invalid_value()),
+                }
+            }
+            _ if value_type.starts_with("bytes") => {
+                let size =
+                    parse_size(&value_type[5..], 1, 32, 1).ok_or(TypedDataError::InvalidType(value_type.into()))?;
+                let bytes = decode_hex(value).ok_or_else(invalid_value)?;
+                match bytes.len() == size {
+                    true => Ok([bytes, vec![0u8; 32 - size]].concat()),
+                    false => Err(invalid_value()),
+                }
+            }
+            _ if value_type.starts_with("uint") => {
+                let bits =
+                    parse_size(&value_type[4..], 8, 256, 8).ok_or(TypedDataError::InvalidType(value_type.into()))?;
+                let (negative, magnitude) = parse_integer(value).ok_or_else(invalid_value)?;
+                match !negative && magnitude.bits() <= bits {
+                    true => Ok(magnitude.to_big_endian_vec()),
+                    false => Err(invalid_value()),
+                }
+            }
+            _ if value_type.starts_with("int") => {
+                let bits =
+                    parse_size(&value_type[3..], 8, 256, 8).ok_or(TypedDataError::InvalidType(value_type.into()))?;
+                let (negative, magnitude) = parse_integer(value).ok_or_else(invalid_value)?;
+                // The magnitude of a signed integer is bounded by 2^(bits - 1), inclusive only if negative.
+                let bound = U256::one() << (bits - 1);
+                match (negative, magnitude) {
+                    (false, magnitude) if magnitude < bound => Ok(magnitude.to_big_endian_vec()),
+                    (true, magnitude) if magnitude <= bound => {
+                        // Two's complement
+                        Ok(negate(magnitude).to_big_endian_vec())
+                    }
+                    _ => Err(invalid_value()),
+                }
+            }
+            _ => Err(TypedDataError::UndefinedType(value_type.into())),
+        }
+    }
+}
+
+impl FromStr for EthereumTypedData {
+    type Err = TypedDataError;
+
+    fn from_str(typed_data: &str) -> Result<Self, Self::Err> {
+        let typed_data: Self = serde_json::from_str(typed_data)?;
+        if !typed_data.domain.is_object() {
+            return Err(TypedDataError::InvalidValue(
+                DOMAIN_TYPE.into(),
+                typed_data.domain.to_string(),
+            ));
+        }
+        Ok(typed_data)
+    }
+}
+
+impl fmt::Display for EthereumTypedData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match serde_json::to_string(self) {
+            Ok(typed_data) => write!(f, "{}", typed_data),
+            _ => Err(fmt::Error),
+        }
+    }
+}
+
+/// A helper trait for the big-endian encoding of a 256-bit value.
+trait ToBigEndianVec {
+    fn to_big_endian_vec(&self) -> Vec<u8>;
+}
+
+impl ToBigEndianVec for U256 {
+    fn to_big_endian_vec(&self) -> Vec<u8> {
+        let mut bytes = [0u8; 32];
+        self.to_big_endian(&mut bytes);
+        bytes.to_vec()
+    }
+}
+
+/// Returns the bytes of the given 0x-prefixed hex string value.
+fn decode_hex(value: &Value) -> Option<Vec<u8>> {
+    match value.as_str() {
+        Some(hex) if hex.starts_with("0x") => hex::decode(&hex[2..]).ok(),
+        _ => None,
+    }
+}
+
+/// Returns the size suffix of a type, if it is within the given bounds and a multiple of the given step.
+/// An empty suffix denotes the maximum size.
+fn parse_size(suffix: &str, min: usize, max: usize, step: usize) -> Option<usize> {
+    match suffix {
+        "" => Some(max),
+        _ => suffix
+            .parse::<usize>()
+            .ok()
+            .filter(|size| *size >= min && *size <= max && size % step == 0 && !suffix.starts_with('0')),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::EthereumAddress;
+    use wagyu_model::PrivateKey;
+
+    pub struct TypedDataTestCase {
+        typed_data: &'static str,
+        encoded_type: &'static str,
+        domain_separator: &'static str,
+        signing_hash: &'static str,
+        private_key: &'static str,
+        signature: &'static str,
+    }
+
+    fn test_encode_type(expected_encoded_type: &str, typed_data: &EthereumTypedData) {
+        let encoded_type = typed_data.encode_type(&typed_data.primary_type).unwrap();
+        assert_eq!(expected_encoded_type, encoded_type);
+    }
+
+    fn test_domain_separator(expected_domain_separator: &str, typed_data: &EthereumTypedData) {
+        let domain_separator = typed_data.domain_separator().unwrap();
+        assert_eq!(expected_domain_separator, hex::encode(domain_separator));
+    }
+
+    fn test_to_signing_hash(expected_signing_hash: &str, typed_data: &EthereumTypedData) {
+        let signing_hash = typed_data.to_signing_hash().unwrap();
+        assert_eq!(expected_signing_hash, hex::encode(signing_hash));
+    }
+
+    fn test_sign(expected_signature: &str, private_key: &EthereumPrivateKey, typed_data: &EthereumTypedData) {
+        let signature = typed_data.sign(private_key).unwrap();
+        assert_eq!(expected_signature, hex::encode(signature));
+    }
+
+    fn test_recover_public_key(signature: &str, private_key: &EthereumPrivateKey, typed_data: &EthereumTypedData) {
+        let signature = hex::decode(signature).unwrap();
+        let public_key = typed_data.recover_public_key(&signature).unwrap();
+        assert_eq!(private_key.to_public_key(), public_key);
+
+        let address: EthereumAddress = private_key.to_address(&EthereumFormat::Standard).unwrap();
+        assert!(typed_data.verify(&signature, &address.to_string()).unwrap());
+    }
+
+    const TYPED_DATA: [TypedDataTestCase; 4] = [
+        TypedDataTestCase {
+            typed_data: r#"{"types":{"EIP712Domain":[{"name":"name","type":"string"},{"name":"version","type":"string"},{"name":"chainId","type":"uint256"},{"name":"verifyingContract","type":"address"}],"Person":[{"name":"name","type":"string"},{"name":"wallet","type":"address"}],"Mail":[{"name":"from","type":"Person"},{"name":"to","type":"Person"},{"name":"contents","type":"string"}]},"primaryType":"Mail","domain":{"name":"Ether Mail","version":"1","chainId":1,"verifyingContract":"0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"},"message":{"from":{"name":"Cow","wallet":"0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},"to":{"name":"Bob","wallet":"0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},"contents":"Hello, Bob!"}}"#,
+            encoded_type: "Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+            domain_separator: "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f",
+            signing_hash: "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2",
+            private_key: "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4",
+            signature: "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c",
+        },
+        TypedDataTestCase {
+            typed_data: r#"{"types":{"EIP712Domain":[{"name":"name","type":"string"},{"name":"version","type":"string"},{"name":"chainId","type":"uint256"},{"name":"verifyingContract","type":"address"}],"Person":[{"name":"name","type":"string"},{"name":"wallets","type":"address[]"}],"Mail":[{"name":"from","type":"Person"},{"name":"to","type":"Person[]"},{"name":"contents","type":"string"}],"Group":[{"name":"name","type":"string"},{"name":"members","type":"Person[]"}]},"primaryType":"Mail","domain":{"name":"Ether Mail","version":"1","chainId":1,"verifyingContract":"0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"},"message":{"from":{"name":"Cow","wallets":["0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826","0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF"]},"to":[{"name":"Bob","wallets":["0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB","0xB0BdaBea57B0BDABeA57b0bdABEA57b0BDabEa57","0xB0B0b0b0b0b0B000000000000000000000000000"]}],"contents":"Hello, Bob!"}}"#,
+            encoded_type: "Mail(Person from,Person[] to,string contents)Person(string name,address[] wallets)",
+            domain_separator: "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f",
+            signing_hash: "a85c2e2b118698e88db68a8105b794a8cc7cec074e89ef991cb4f5f533819cc2",
+            private_key: "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4",
+            signature: "65cbd956f2fae28a601bebc9b906cea0191744bd4c4247bcd27cd08f8eb6b71c78efdf7a31dc9abee78f492292721f362d296cf86b4538e07b51303b67f749061b",
+        },
+        TypedDataTestCase {
+            typed_data: r#"{"types":{"Permit":[{"name":"owner","type":"address"},{"name":"spender","type":"address"},{"name":"value","type":"uint256"},{"name":"nonce","type":"uint256"},{"name":"deadline","type":"uint256"}]},"primaryType":"Permit","domain":{"name":"USD Coin","version":"2","chainId":1,"verifyingContract":"0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"},"message":{"owner":"0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65","spender":"0x52C3a8a79a521D10b25569847CB1a3FfB66550D6","value":"1000000000000000000000","nonce":0,"deadline":"0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"}}"#,
+            encoded_type: "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)",
+            domain_separator: "06c37168a7db5138defc7866392bb87a741f9b3d104deb5094588ce041cae335",
+            signing_hash: "edb58ce047b7bf347fe26849012d7828e33253fe83492c83af4c1a68995df32e",
+            private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+            signature: "6f8e216d4890c08558b70f863f2dcffdaea55592013c4bd9ad78005d4b4e7b015dc69fc463250b7336028595997992fb3d77be696608991ece408ff3b41ea1281b",
+        },
+        TypedDataTestCase {
+            typed_data: r#"{"types":{"EIP712Domain":[{"name":"name","type":"string"},{"name":"chainId","type":"uint256"},{"name":"salt","type":"bytes32"}],"Vote":[{"name":"proposal","type":"bytes4"},{"name":"support","type":"bool"},{"name":"weight","type":"int64"},{"name":"reason","type":"bytes"},{"name":"scores","type":"uint8[2]"},{"name":"offsets","type":"int8[]"}]},"primaryType":"Vote","domain":{"name":"Governor","chainId":"5","salt":"0xf2d857f4a3edcb9b78b4d503bfe733db1e3f6cdc2b7971ee739626c97e86a558"},"message":{"proposal":"0x12345678","support":true,"weight":-1000000,"reason":"0x","scores":[255,"0x0a"],"offsets":[-128,127,"-1"]}}"#,
+            encoded_type: "Vote(bytes4 proposal,bool support,int64 weight,bytes reason,uint8[2] scores,int8[] offsets)",
+            domain_separator: "c5a07d8dfcded8f4480aa923fc3515e9b69b62d5fd6866d98d0f8d9948a019d4",
+            signing_hash: "2612e29571ce799cb3adf3d0ac8e825933613975236de3fa46e6f15b6524d60b",
+            private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+            signature: "74687850bb9fdddb135b9b0f96e65de80157a5f41cd1b5adcd9a2283f3d78ea90a9a33b658a0256f5ef988ff7c94abcabac66508290be8d0a948dd70a3a0a5d21c",
+        },
+    ];
+
+    #[test]
+    fn encode_type() {
+        TYPED_DATA.iter().for_each(|test_case| {
+            let typed_data = EthereumTypedData::from_str(test_case.typed_data).unwrap();
+            test_encode_type(test_case.encoded_type, &typed_data);
+        });
+    }
+
+    #[test]
+    fn domain_separator() {
+        TYPED_DATA.iter().for_each(|test_case| {
+            let typed_data = EthereumTypedData::from_str(test_case.typed_data).unwrap();
+            test_domain_separator(test_case.domain_separator, &typed_data);
+        });
+    }
+
+    #[test]
+    fn to_signing_hash() {
+        TYPED_DATA.iter().for_each(|test_case| {
+            let typed_data = EthereumTypedData::from_str(test_case.typed_data).unwrap();
+            test_to_signing_hash(test_case.signing_hash, &typed_data);
+        });
+    }
+
+    #[test]
+    fn sign() {
+        TYPED_DATA.iter().for_each(|test_case| {
+            let typed_data = EthereumTypedData::from_str(test_case.typed_data).unwrap();
+            let private_key = EthereumPrivateKey::from_str(test_case.private_key).unwrap();
+            test_sign(test_case.signature, &private_key, &typed_data);
+        });
+    }
+
+    #[test]
+    fn recover_public_key() {
+        TYPED_DATA.iter().for_each(|test_case| {
+            let typed_data = EthereumTypedData::from_str(test_case.typed_data).unwrap();
+            let private_key = EthereumPrivateKey::from_str(test_case.private_key).unwrap();
+            test_recover_public_key(test_case.signature, &private_key, &typed_data);
+        });
+    }
+
+    mod invalid {
+        use super::*;
+
+        const TYPES: &str = r#"{"Value":[{"name":"value","type":"%s"}]}"#;
+
+        fn typed_data(value_type: &str, value: &str) -> EthereumTypedData {
+            let json = format!(
+                r#"{{"types":{},"primaryType":"Value","domain":{{"name":"Test"}},"message":{{"value":{}}}}}"#,
+                TYPES.replace("%s", value_type),
+                value
+            );
+            EthereumTypedData::from_str(&json).unwrap()
+        }
+
+        #[test]
+        fn to_signing_hash_invalid_value() {
+            let invalid_values = [
+                ("uint8", "256"),
+                ("uint8", "-1"),
+                ("int8", "128"),
+                ("int8", "-129"),
+                (
+                    "uint256",
+                    r#""0x10000000000000000000000000000000000000000000000000000000000000000""#,
+                ),
+                ("bool", "1"),
+                ("address", r#""0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e""#),
+                ("bytes4", r#""0x1234""#),
+                ("bytes", r#""1234""#),
+                ("string", "1"),
+                ("uint8[2]", "[1]"),
+                ("uint8[]", "1"),
+            ];
+            invalid_values.iter().for_each(|(value_type, value)| {
+                assert!(typed_data(value_type, value).to_signing_hash().is_err());
+            });
+        }
+
+        #[test]
+        fn to_signing_hash_invalid_type() {
+            let invalid_types = [
+                ("uint7", "1"),
+                ("uint264", "1"),
+                ("int0", "1"),
+                ("bytes33", r#""0x00""#),
+                ("Undefined", "1"),
+            ];
+            invalid_types.iter().for_each(|(value_type, value)| {
+                assert!(typed_data(value_type, value).to_signing_hash().is_err());
+            });
+        }
+
+        #[test]
+        fn to_signing_hash_missing_field() {
+            let typed_data = EthereumTypedData::from_str(
+                TYPED_DATA[0]
+                    .typed_data
+                    .replace(r#""contents":"Hello, Bob!""#, r#""content":"Hello, Bob!""#)
+                    .as_str(),
+            )
+            .unwrap();
+            assert!(typed_data.to_signing_hash().is_err());
+        }
+
+        #[test]
+        fn recover_public_key_invalid_signature() {
+            let typed_data = EthereumTypedData::from_str(TYPED_DATA[0].typed_data).unwrap();
+            let signature = hex::decode(TYPED_DATA[0].signature).unwrap();
+            assert!(typed_data.recover_public_key(&signature[..64]).is_err());
+
+            let mut invalid_signature = signature.clone();
+            invalid_signature[64] = 29;
+            assert!(typed_data.recover_public_key(&invalid_signature).is_err());
+
+            let other = EthereumTypedData::from_str(TYPED_DATA[2].typed_data).unwrap();
+            let address = other
+                .recover_public_key(&hex::decode(TYPED_DATA[2].signature).unwrap())
+                .unwrap()
+                .to_address(&EthereumFormat::Standard)
+                .unwrap();
+            assert!(!typed_data.verify(&signature, &address.to_string()).unwrap());
+        }
+
+        #[test]
+        fn from_str_invalid_json() {
+            assert!(EthereumTypedData::from_str("").is_err());
+            assert!(
+                EthereumTypedData::from_str(r#"{"types":{},"primaryType":"Mail","domain":1,"message":{}}"#).is_err()
+            );
+            assert!(EthereumTypedData::from_str(r#"{"types":{},"domain":{},"message":{}}"#).is_err());
+        }
+    }
+}
@@ -0,0 +1,641 @@
+//!
+//! ERC-4337 User Operations
+//!
+//! This module contains the hashing and signing of user operations of account abstraction.
+//! https://eips.ethereum.org/EIPS/eip-4337
+//!
+//! A user operation is hashed as `keccak256(abi.encode(keccak256(pack(userOp)), entryPoint, chainId))`, where
+//! the packing of the fields depends on the version of the entry point. The operation is signed as the EIP-191
+//! personal message of its hash, as verified by the `SimpleAccount` of the reference implementation.
+//!
+
+use crate::abi::{encode, Token};
+use crate::address::EthereumAddress;
+use crate::format::EthereumFormat;
+use crate::private_key::EthereumPrivateKey;
+use crate::public_key::EthereumPublicKey;
+use crate::siwe::personal_message_hash;
+use wagyu_model::{AddressError, PublicKey};
+
+use ethereum_types::U256;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use tiny_keccak::keccak256;
+
+/// The address of the v0.6 entry point contract
+pub const ENTRY_POINT_V06: &str = "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789";
+
+/// The address of the v0.7 entry point contract
+pub const ENTRY_POINT_V07: &str = "0x0000000071727De22E5E9d8BAf0edAc6f37da032";
+
+#[derive(Debug, Fail)]
+pub enum UserOperationError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "invalid {}: {}", _0, _1)]
+    InvalidField(&'static str, String),
+
+    #[fail(display = "invalid JSON: {}", _0)]
+    InvalidJson(String),
+
+    #[fail(display = "invalid signature: {}", _0)]
+    InvalidSignature(String),
+
+    #[fail(display = "missing field {}", _0)]
+    MissingField(&'static str),
+}
+
+impl From<AddressError> for UserOperationError {
+    fn from(error: AddressError) -> Self {
+        UserOperationError::AddressError(error)
+    }
+}
+
+impl From<secp256k1::Error> for UserOperationError {
+    fn from(error: secp256k1::Error) -> Self {
+        UserOperationError::InvalidSignature(format!("{:?}", error))
+    }
+}
+
+impl From<serde_json::error::Error> for UserOperationError {
+    fn from(error: serde_json::error::Error) -> Self {
+        UserOperationError::InvalidJson(format!("{:?}", error))
+    }
+}
+
+/// Represents the version of the entry point contract of a user operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryPointVersion {
+    V06,
+    V07,
+}
+
+impl EntryPointVersion {
+    /// Returns the canonical address of the entry point contract of the version.
+    pub fn entry_point(&self) -> EthereumAddress {
+        match self {
+            EntryPointVersion::V06 => EthereumAddress::from_str(ENTRY_POINT_V06).unwrap(),
+            EntryPointVersion::V07 => EthereumAddress::from_str(ENTRY_POINT_V07).unwrap(),
+        }
+    }
+}
+
+/// Represents an ERC-4337 user operation, with the fields of the bundler RPC.
+/// In v0.7, the init code is the factory address followed by its data, and the paymaster and data is
+/// the paymaster address followed by its 16-byte verification and post-operation gas limits and its data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserOperation {
+    /// The version of the entry point of the operation
+    pub version: EntryPointVersion,
+    /// The account making the operation
+    pub sender: EthereumAddress,
+    /// The anti-replay nonce, a 192-bit key followed by a 64-bit sequence number
+    pub nonce: U256,
+    /// The factory call deploying the account, if it does not yet exist
+    pub init_code: Vec<u8>,
+    /// The data of the call to the account
+    pub call_data: Vec<u8>,
+    /// The gas limit of the execution of the call
+    pub call_gas_limit: U256,
+    /// The gas limit of the verification of the operation
+    pub verification_gas_limit: U256,
+    /// The gas paid to the bundler for the pre-verification execution and calldata
+    pub pre_verification_gas: U256,
+    /// The maximum fee per gas, as in EIP-1559
+    pub max_fee_per_gas: U256,
+    /// The maximum priority fee per gas, as in EIP-1559
+    pub max_priority_fee_per_gas: U256,
+    /// The paymaster sponsoring the operation and its data, or empty if the account pays for itself
+    pub paymaster_and_data: Vec<u8>,
+    /// The signature of the operation, as verified by the account
+    pub signature: Vec<u8>,
+}
+
+/// Represents an ERC-4337 v0.7 user operation in the packed layout of the entry point
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedUserOperation {
+    pub sender: EthereumAddress,
+    pub nonce: U256,
+    pub init_code: Vec<u8>,
+    pub call_data: Vec<u8>,
+    /// The verification gas limit and the call gas limit, as two 16-byte values
+    pub account_gas_limits: [u8; 32],
+    pub pre_verification_gas: U256,
+    /// The maximum priority fee per gas and the maximum fee per gas, as two 16-byte values
+    pub gas_fees: [u8; 32],
+    pub paymaster_and_data: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+impl PackedUserOperation {
+    /// Returns the ABI encoding of the operation, with its dynamic fields hashed and without its signature.
+    pub fn pack(&self) -> Vec<u8> {
+        encode(&[
+            Token::Address(self.sender.clone()),
+            Token::Uint(self.nonce),
+            Token::FixedBytes(keccak256(&self.init_code).to_vec()),
+            Token::FixedBytes(keccak256(&self.call_data).to_vec()),
+            Token::FixedBytes(self.account_gas_limits.to_vec()),
+            Token::Uint(self.pre_verification_gas),
+            Token::FixedBytes(self.gas_fees.to_vec()),
+            Token::FixedBytes(keccak256(&self.paymaster_and_data).to_vec()),
+        ])
+    }
+}
+
+impl UserOperation {
+    /// Returns the v0.7 packed layout of the operation.
+    pub fn to_packed(&self) -> Result<PackedUserOperation, UserOperationError> {
+        Ok(PackedUserOperation {
+            sender: self.sender.clone(),
+            nonce: self.nonce,
+            init_code: self.init_code.clone(),
+            call_data: self.call_data.clone(),
+            account_gas_limits: pack_u128_pair(
+                ("verificationGasLimit", self.verification_gas_limit),
+                ("callGasLimit", self.call_gas_limit),
+            )?,
+            pre_verification_gas: self.pre_verification_gas,
+            gas_fees: pack_u128_pair(
+                ("maxPriorityFeePerGas", self.max_priority_fee_per_gas),
+                ("maxFeePerGas", self.max_fee_per_gas),
+            )?,
+            paymaster_and_data: self.paymaster_and_data.clone(),
+            signature: self.signature.clone(),
+        })
+    }
+
+    /// Returns the v0.7 operation of the given packed layout.
+    pub fn from_packed(packed: &PackedUserOperation) -> Self {
+        Self {
+            version: EntryPointVersion::V07,
+            sender: packed.sender.clone(),
+            nonce: packed.nonce,
+            init_code: packed.init_code.clone(),
+            call_data: packed.call_data.clone(),
+            call_gas_limit: U256::from(&packed.account_gas_limits[16..]),
+            verification_gas_limit: U256::from(&packed.account_gas_limits[..16]),
+            pre_verification_gas: packed.pre_verification_gas,
+            max_fee_per_gas: U256::from(&packed.gas_fees[16..]),
+            max_priority_fee_per_gas: U256::from(&packed.gas_fees[..16]),
+            paymaster_and_data: packed.paymaster_and_data.clone(),
+            signature: packed.signature.clone(),
+        }
+    }
+
+    /// Returns the ABI encoding of the operation for its entry point version, without its signature.
+    pub fn pack(&self) -> Result<Vec<u8>, UserOperationError> {
+        match self.version {
+            EntryPointVersion::V06 => Ok(encode(&[
+                Token::Address(self.sender.clone()),
+                Token::Uint(self.nonce),
+                Token::FixedBytes(keccak256(&self.init_code).to_vec()),
+                Token::FixedBytes(keccak256(&self.call_data).to_vec()),
+                Token::Uint(self.call_gas_limit),
+                Token::Uint(self.verification_gas_limit),
+                Token::Uint(self.pre_verification_gas),
+                Token::Uint(self.max_fee_per_gas),
+                Token::Uint(self.max_priority_fee_per_gas),
+                Token::FixedBytes(keccak256(&self.paymaster_and_data).to_vec()),
+            ])),
+            EntryPointVersion::V07 => Ok(self.to_packed()?.pack()),
+        }
+    }
+
+    /// Returns the hash of the operation, as computed by `getUserOpHash` of the given entry point on the given chain.
+    pub fn to_user_op_hash(
+        &self,
+        entry_point: &EthereumAddress,
+        chain_id: u64,
+    ) -> Result<[u8; 32], UserOperationError> {
+        Ok(keccak256(&encode(&[
+            Token::FixedBytes(keccak256(&self.pack()?).to_vec()),
+            Token::Address(entry_point.clone()),
+            Token::Uint(U256::from(chain_id)),
+        ])))
+    }
+
+    /// Returns the 65-byte signature (r || s || v) of the EIP-191 message of the operation hash.
+    pub fn sign(
+        &self,
+        private_key: &EthereumPrivateKey,
+        entry_point: &EthereumAddress,
+        chain_id: u64,
+    ) -> Result<Vec<u8>, UserOperationError> {
+        let hash = personal_message_hash(&self.to_user_op_hash(entry_point, chain_id)?);
+        let (signature, recovery_id) = secp256k1::sign(
+            &secp256k1::Message::parse(&hash),
+            &private_key.to_secp256k1_secret_key(),
+        );
+
+        let mut signature = signature.serialize().to_vec();
+        signature.push(Into::<i32>::into(recovery_id) as u8 + 27);
+        Ok(signature)
+    }
+
+    /// Returns the address that produced the signature of the operation.
+    pub fn recover_address(
+        &self,
+        entry_point: &EthereumAddress,
+        chain_id: u64,
+    ) -> Result<EthereumAddress, UserOperationError> {
+        if self.signature.len() != 65 {
+            return Err(UserOperationError::InvalidSignature(format!(
+                "invalid signature length {}",
+                self.signature.len()
+            )));
+        }
+
+        let recovery_id = match self.signature[64] {
+            0 | 1 => self.signature[64],
+            27 | 28 => self.signature[64] - 27,
+            v => {
+                return Err(UserOperationError::InvalidSignature(format!(
+                    "invalid recovery id {}",
+                    v
+                )))
+            }
+        };
+        let hash = personal_message_hash(&self.to_user_op_hash(entry_point, chain_id)?);
+        let public_key = secp256k1::recover(
+            &secp256k1::Message::parse(&hash),
+            &secp256k1::Signature::parse_slice(&self.signature[0..64])?,
+            &secp256k1::RecoveryId::parse(recovery_id)?,
+        )?;
+        Ok(EthereumPublicKey::from_secp256k1_public_key(public_key).to_address(&EthereumFormat::Standard)?)
+    }
+
+    /// Returns the operation in the JSON format of the bundler RPC of its entry point version.
+    pub fn to_json(&self) -> Result<String, UserOperationError> {
+        let mut json = UserOperationJson {
+            sender: self.sender.to_string(),
+            nonce: format!("{:#x}", self.nonce),
+            call_data: encode_hex(&self.call_data),
+            call_gas_limit: format!("{:#x}", self.call_gas_limit),
+            verification_gas_limit: format!("{:#x}", self.verification_gas_limit),
+            pre_verification_gas: format!("{:#x}", self.pre_verification_gas),
+            max_fee_per_gas: format!("{:#x}", self.max_fee_per_gas),
+            max_priority_fee_per_gas: format!("{:#x}", self.max_priority_fee_per_gas),
+            signature: encode_hex(&self.signature),
+            ..Default::default()
+        };
+
+        match self.version {
+            EntryPointVersion::V06 => {
+                json.init_code = Some(encode_hex(&self.init_code));
+                json.paymaster_and_data = Some(encode_hex(&self.paymaster_and_data));
+            }
+            EntryPointVersion::V07 => {
+                if !self.init_code.is_empty() {
+                    if self.init_code.len() < 20 {
+                        return Err(UserOperationError::InvalidField(
+                            "initCode",
+                            encode_hex(&self.init_code),
+                        ));
+                    }
+                    json.factory = Some(EthereumAddress::from_str(&hex::encode(&self.init_code[..20]))?.to_string());
+                    json.factory_data = Some(encode_hex(&self.init_code[20..]));
+                }
+                if !self.paymaster_and_data.is_empty() {
+                    let paymaster_and_data = &self.paymaster_and_data;
+                    if paymaster_and_data.len() < 52 {
+                        return Err(UserOperationError::InvalidField(
+                            "paymasterAndData",
+                            encode_hex(paymaster_and_data),
+                        ));
+                    }
+                    json.paymaster =
+                        Some(EthereumAddress::from_str(&hex::encode(&paymaster_and_data[..20]))?.to_string());
+                    json.paymaster_verification_gas_limit =
+                        Some(format!("{:#x}", U256::from(&paymaster_and_data[20..36])));
+                    json.paymaster_post_op_gas_limit = Some(format!("{:#x}", U256::from(&paymaster_and_data[36..52])));
+                    json.paymaster_data = Some(encode_hex(&paymaster_and_data[52..]));
+                }
+            }
+        }
+
+        Ok(serde_json::to_string(&json)?)
+    }
+}
+
+impl FromStr for UserOperation {
+    type Err = UserOperationError;
+
+    /// Returns the operation of the given JSON of the bundler RPC, whose version is inferred from its fields.
+    /// Operations with `initCode` or `paymasterAndData` are v0.6 and all other operations are v0.7.
+    fn from_str(user_operation: &str) -> Result<Self, Self::Err> {
+        let json: UserOperationJson = serde_json::from_str(user_operation)?;
+
+        let version = match (&json.init_code, &json.paymaster_and_data) {
+            (None, None) => EntryPointVersion::V07,
+            _ => EntryPointVersion::V06,
+        };
+
+        let (init_code, paymaster_and_data) = match version {
+            EntryPointVersion::V06 => (
+                decode_hex("initCode", json.init_code.as_ref())?,
+                decode_hex("paymasterAndData", json.paymaster_and_data.as_ref())?,
+            ),
+            EntryPointVersion::V07 => {
+                let mut init_code = vec![];
+                if let Some(factory) = &json.factory {
+                    init_code.extend(EthereumAddress::from_str(factory)?.to_bytes()?);
+                    init_code.extend(decode_hex("factoryData", json.factory_data.as_ref())?);
+                }
+
+                let mut paymaster_and_data = vec![];
+                if let Some(paymaster) = &json.paymaster {
+                    let gas_limits = pack_u128_pair(
+                        (
+                            "paymasterVerificationGasLimit",
+                            decode_quantity(
+                                "paymasterVerificationGasLimit",
+                                json.paymaster_verification_gas_limit.as_ref(),
+                            )?,
+                        ),
+                        (
+                            "paymasterPostOpGasLimit",
+                            decode_quantity("paymasterPostOpGasLimit", json.paymaster_post_op_gas_limit.as_ref())?,
+                        ),
+                    )?;
+                    paymaster_and_data.extend(EthereumAddress::from_str(paymaster)?.to_bytes()?);
+                    paymaster_and_data.extend_from_slice(&gas_limits);
+                    paymaster_and_data.extend(decode_hex("paymasterData", json.paymaster_data.as_ref())?);
+                }
+                (init_code, paymaster_and_data)
+            }
+        };
+
+        Ok(Self {
+            version,
+            sender: EthereumAddress::from_str(&json.sender)?,
+            nonce: decode_quantity("nonce", Some(&json.nonce))?,
+            init_code,
+            call_data: decode_hex("callData", Some(&json.call_data))?,
+            call_gas_limit: decode_quantity("callGasLimit", Some(&json.call_gas_limit))?,
+            verification_gas_limit: decode_quantity("verificationGasLimit", Some(&json.verification_gas_limit))?,
+            pre_verification_gas: decode_quantity("preVerificationGas", Some(&json.pre_verification_gas))?,
+            max_fee_per_gas: decode_quantity("maxFeePerGas", Some(&json.max_fee_per_gas))?,
+            max_priority_fee_per_gas: decode_quantity("maxPriorityFeePerGas", Some(&json.max_priority_fee_per_gas))?,
+            paymaster_and_data,
+            signature: decode_hex("signature", Some(&json.signature))?,
+        })
+    }
+}
+
+impl fmt::Display for UserOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_json() {
+            Ok(user_operation) => write!(f, "{}", user_operation),
+            _ => Err(fmt::Error),
+        }
+    }
+}
+
+/// Represents a user operation in the JSON format of the bundler RPC of either entry point version
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UserOperationJson {
+    sender: String,
+    nonce: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    init_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    factory: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    factory_data: Option<String>,
+    call_data: String,
+    call_gas_limit: String,
+    verification_gas_limit: String,
+    pre_verification_gas: String,
+    max_fee_per_gas: String,
+    max_priority_fee_per_gas: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    paymaster_and_data: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    paymaster: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    paymaster_verification_gas_limit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    paymaster_post_op_gas_limit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    paymaster_data: Option<String>,
+    #[serde(default = "empty_hex")]
+    signature: String,
+}
+
+/// Returns the empty 0x-prefixed hex string.
+fn empty_hex() -> String {
+    "0x".into()
+}
+
+/// Returns the 0x-prefixed hex string of the given bytes.
+fn encode_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Returns the bytes of the given 0x-prefixed hex string of a field, which are empty if the field is absent.
+fn decode_hex(field: &'static str, value: Option<&String>) -> Result<Vec<u8>, UserOperationError> {
+    match value {
+        Some(hex) if hex.starts_with("0x") => {
+            hex::decode(&hex[2..]).map_err(|_| UserOperationError::InvalidField(field, hex.clone()))
+        }
+        Some(hex) => Err(UserOperationError::InvalidField(field, hex.clone())),
+        None => Ok(vec![]),
+    }
+}
+
+/// Returns the value of the given 0x-prefixed hex quantity of a field.
+fn decode_quantity(field: &'static str, value: Option<&String>) -> Result<U256, UserOperationError> {
+    match value {
+        Some(quantity) if quantity.len() > 2 && quantity.starts_with("0x") => {
+            let digits = match quantity.len() % 2 {
+                0 => quantity[2..].to_string(),
+                _ => format!("0{}", &quantity[2..]),
+            };
+            match hex::decode(&digits) {
+                Ok(bytes) if bytes.len() <= 32 => Ok(U256::from(&bytes[..])),
+                _ => Err(UserOperationError::InvalidField(field, quantity.clone())),
+            }
+        }
+        Some(quantity) => Err(UserOperationError::InvalidField(field, quantity.clone())),
+        None => Err(UserOperationError::MissingField(field)),
+    }
+}
+
+/// Returns the concatenation of the given two 128-bit values of the named fields.
+fn pack_u128_pair(high: (&'static str, U256), low: (&'static str, U256)) -> Result<[u8; 32], UserOperationError> {
+    let mut packed = [0u8; 32];
+    for (index, (field, value)) in [high, low].iter().enumerate() {
+        if value.bits() > 128 {
+            return Err(UserOperationError::InvalidField(field, value.to_string()));
+        }
+        let mut bytes = [0u8; 32];
+        value.to_big_endian(&mut bytes);
+        packed[index * 16..(index + 1) * 16].copy_from_slice(&bytes[16..]);
+    }
+    Ok(packed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    pub struct UserOperationTestCase {
+        user_operation: &'static str,
+        entry_point: &'static str,
+        chain_id: u64,
+        user_op_hash: &'static str,
+        signature: &'static str,
+    }
+
+    const USER_OPERATIONS: [UserOperationTestCase; 2] = [
+        UserOperationTestCase {
+            user_operation: r#"{"sender":"0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266","nonce":"0x0","initCode":"0x9406cc6185a346906296840746125a0e449764545fbfb9cf000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb922660000000000000000000000000000000000000000000000000000000000000000","callData":"0xb61d27f600000000000000000000000070997970c51812dc3a010c7d01b50e0d17dc79c8000000000000000000000000000000000000000000000000016345785d8a000000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000","callGasLimit":"0x11170","verificationGasLimit":"0x186a0","preVerificationGas":"0xc350","maxFeePerGas":"0x59682f00","maxPriorityFeePerGas":"0x3b9aca00","paymasterAndData":"0x","signature":"0x"}"#,
+            entry_point: ENTRY_POINT_V06,
+            chain_id: 1,
+            user_op_hash: "9062e7a912b2f2f521399d3ae7107973117db33d7cd38a5afcb099480cd4c898",
+            signature: "522ee1c801e67704da4478ed70fdf1b410e22e0d5428b78c4fb19e22ee138b6a70cb085399bfb7176a95f09578c36bdfa15726f145ad8131a44e7a72fcf6bf761c",
+        },
+        UserOperationTestCase {
+            user_operation: r#"{"sender":"0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266","nonce":"0x50000000000000003","factory":"0x91E60e0613810449d098b0b5Ec8b51A0FE8c8985","factoryData":"0x5fbfb9cf000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb922660000000000000000000000000000000000000000000000000000000000000001","callData":"0xb61d27f600000000000000000000000070997970c51812dc3a010c7d01b50e0d17dc79c8000000000000000000000000000000000000000000000000016345785d8a000000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000","callGasLimit":"0x186a0","verificationGasLimit":"0x30d40","preVerificationGas":"0xc350","maxFeePerGas":"0x77359400","maxPriorityFeePerGas":"0x3b9aca00","paymaster":"0x0000000000325602a77416A16136FDafd04b299f","paymasterVerificationGasLimit":"0x7530","paymasterPostOpGasLimit":"0x2710","paymasterData":"0xdeadbeef","signature":"0x"}"#,
+            entry_point: ENTRY_POINT_V07,
+            chain_id: 11155111,
+            user_op_hash: "90d04dd552fdc1bca8321660a0f555db59f0692532b7e83e71f9dc8f6f897bee",
+            signature: "6d04247e2080a8eabb880f3ba8aeea34d21719a2fcc2929346a5b5585a9693927ab6fee99815d032ab97f7738f74ebb93ff1f5fdb4cab81d9a4091745aff016e1c",
+        },
+    ];
+
+    #[test]
+    fn from_str() {
+        USER_OPERATIONS.iter().for_each(|test_case| {
+            let user_operation = UserOperation::from_str(test_case.user_operation).unwrap();
+            assert_eq!(test_case.user_operation, user_operation.to_string());
+        });
+
+        let user_operation = UserOperation::from_str(USER_OPERATIONS[0].user_operation).unwrap();
+        assert_eq!(EntryPointVersion::V06, user_operation.version);
+
+        let user_operation = UserOperation::from_str(USER_OPERATIONS[1].user_operation).unwrap();
+        assert_eq!(EntryPointVersion::V07, user_operation.version);
+        assert_eq!(
+            "0000000000325602a77416a16136fdafd04b299f0000000000000000000000000000753000000000000000000000000000002710deadbeef",
+            hex::encode(&user_operation.paymaster_and_data)
+        );
+    }
+
+    #[test]
+    fn to_packed() {
+        let user_operation = UserOperation::from_str(USER_OPERATIONS[1].user_operation).unwrap();
+        let packed = user_operation.to_packed().unwrap();
+        assert_eq!(
+            "00000000000000000000000000030d40000000000000000000000000000186a0",
+            hex::encode(packed.account_gas_limits)
+        );
+        assert_eq!(
+            "0000000000000000000000003b9aca0000000000000000000000000077359400",
+            hex::encode(packed.gas_fees)
+        );
+        assert_eq!(user_operation, UserOperation::from_packed(&packed));
+        assert_eq!(user_operation.pack().unwrap(), packed.pack());
+    }
+
+    #[test]
+    fn to_user_op_hash() {
+        USER_OPERATIONS.iter().for_each(|test_case| {
+            let user_operation = UserOperation::from_str(test_case.user_operation).unwrap();
+            let entry_point = EthereumAddress::from_str(test_case.entry_point).unwrap();
+            let user_op_hash = user_operation
+                .to_user_op_hash(&entry_point, test_case.chain_id)
+                .unwrap();
+            assert_eq!(test_case.user_op_hash, hex::encode(user_op_hash));
+            assert_eq!(entry_point, user_operation.version.entry_point());
+        });
+    }
+
+    #[test]
+    fn sign() {
+        let private_key = EthereumPrivateKey::from_str(PRIVATE_KEY).unwrap();
+        USER_OPERATIONS.iter().for_each(|test_case| {
+            let mut user_operation = UserOperation::from_str(test_case.user_operation).unwrap();
+            let entry_point = EthereumAddress::from_str(test_case.entry_point).unwrap();
+            let signature = user_operation
+                .sign(&private_key, &entry_point, test_case.chain_id)
+                .unwrap();
+            assert_eq!(test_case.signature, hex::encode(&signature));
+
+            // The signature is excluded from the hash of the operation.
+            user_operation.signature = signature;
+            let user_op_hash = user_operation
+                .to_user_op_hash(&entry_point, test_case.chain_id)
+                .unwrap();
+            assert_eq!(test_case.user_op_hash, hex::encode(user_op_hash));
+            assert_eq!(
+                user_operation.sender,
+                user_operation
+                    .recover_address(&entry_point, test_case.chain_id)
+                    .unwrap()
+            );
+        });
+    }
+
+    mod invalid {
+        use super::*;
+
+        #[test]
+        fn from_str_invalid() {
+            let replacements = [
+                (r#""nonce":"0x0""#, r#""nonce":"0""#),
+                (r#""nonce":"0x0""#, r#""nonce":"0x""#),
+                (r#""nonce":"0x0","#, ""),
+                (r#""callData":"0xb6"#, r#""callData":"0x6"#),
+                (r#""paymasterAndData":"0x""#, r#""paymasterAndData":"0xzz""#),
+                (
+                    r#""callGasLimit":"0x11170""#,
+                    r#""callGasLimit":"0x10000000000000000000000000000000000000000000000000000000000000000""#,
+                ),
+                (
+                    r#""sender":"0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266""#,
+                    r#""sender":"0xf39F""#,
+                ),
+            ];
+
+            replacements.iter().for_each(|(from, to)| {
+                let user_operation = USER_OPERATIONS[0].user_operation.replacen(from, to, 1);
+                assert_ne!(USER_OPERATIONS[0].user_operation, user_operation);
+                assert!(UserOperation::from_str(&user_operation).is_err(), "{}", user_operation);
+            });
+        }
+
+        #[test]
+        fn to_packed_invalid() {
+            let mut user_operation = UserOperation::from_str(USER_OPERATIONS[1].user_operation).unwrap();
+            user_operation.max_fee_per_gas = U256::from(1) << 128;
+            assert!(user_operation.to_packed().is_err());
+            assert!(user_operation.pack().is_err());
+
+            user_operation.max_fee_per_gas = U256::from(1);
+            user_operation.paymaster_and_data.truncate(40);
+            assert!(user_operation.to_json().is_err());
+        }
+
+        #[test]
+        fn recover_address_invalid() {
+            let entry_point = EthereumAddress::from_str(ENTRY_POINT_V06).unwrap();
+            let mut user_operation = UserOperation::from_str(USER_OPERATIONS[0].user_operation).unwrap();
+            assert!(user_operation.recover_address(&entry_point, 1).is_err());
+
+            user_operation.signature = hex::decode(USER_OPERATIONS[0].signature).unwrap();
+            assert_ne!(
+                user_operation.sender,
+                user_operation.recover_address(&entry_point, 5).unwrap()
+            );
+
+            user_operation.signature[64] = 29;
+            assert!(user_operation.recover_address(&entry_point, 1).is_err());
+        }
+    }
+}
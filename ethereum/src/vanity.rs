@@ -0,0 +1,197 @@
+use crate::address::EthereumAddress;
+use crate::private_key::EthereumPrivateKey;
+use crate::public_key::EthereumPublicKey;
+use wagyu_model::{vanity_search, PrivateKey, VanityError, VanityProgress};
+
+use rand::Rng;
+use std::str::FromStr;
+use tiny_keccak::keccak256;
+
+/// Represents a search for Ethereum addresses that begin with a given prefix
+#[derive(Debug, Clone, PartialEq)]
+pub struct EthereumVanity {
+    /// The prefix of the addresses, without "0x"
+    prefix: String,
+    /// If `true`, the prefix matches the EIP-55 checksum case of the addresses
+    case_sensitive: bool,
+}
+
+impl EthereumVanity {
+    /// Returns a search for addresses that begin with the given hex prefix, with or without "0x".
+    /// If the search is case sensitive, the letters of the prefix must match the EIP-55 checksum of the address.
+    pub fn new(prefix: &str, case_sensitive: bool) -> Result<Self, VanityError> {
+        let invalid_prefix = |message: &str| VanityError::InvalidPrefix(prefix.into(), message.into());
+
+        let hex = prefix.trim_start_matches("0x");
+        if hex.len() > 40 {
+            return Err(invalid_prefix("the prefix is longer than the address"));
+        }
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid_prefix("invalid hex character"));
+        }
+
+        Ok(Self {
+            prefix: match case_sensitive {
+                true => hex.to_string(),
+                false => hex.to_lowercase(),
+            },
+            case_sensitive,
+        })
+    }
+
+    /// Returns the expected number of keys to try for a match.
+    /// Each hex digit matches one in 16 addresses, and each letter of a case-sensitive prefix
+    /// matches the case of one in 2 checksums.
+    pub fn difficulty(&self) -> f64 {
+        let letters = match self.case_sensitive {
+            true => self.prefix.chars().filter(|c| c.is_ascii_alphabetic()).count(),
+            false => 0,
+        };
+        16f64.powi(self.prefix.len() as i32) * 2f64.powi(letters as i32)
+    }
+
+    /// Returns `true` if the given address begins with the prefix.
+    pub fn is_match(&self, address: &EthereumAddress) -> bool {
+        let address = &address.to_string()[2..];
+        match self.case_sensitive {
+            true => address.starts_with(&self.prefix),
+            false => address.to_lowercase().starts_with(&self.prefix),
+        }
+    }
+
+    /// Returns a private key whose address begins with the prefix, and the number of keys tried.
+    pub fn search<R: Rng, P: FnMut(&VanityProgress)>(
+        &self,
+        rng: &mut R,
+        threads: usize,
+        progress: P,
+    ) -> Result<(EthereumPrivateKey, u64), VanityError> {
+        let (secret_key, attempts) = vanity_search(rng, None, threads, self.matcher(), progress)?;
+        Ok((EthereumPrivateKey::from_secp256k1_secret_key(&secret_key), attempts))
+    }
+
+    /// Returns a partial private key whose public key, added to the given public key,
+    /// has an address that begins with the prefix, and the number of keys tried.
+    /// The owner of the given public key adds the partial private key to their private key
+    /// with `combine` to obtain the private key of the address.
+    pub fn search_with_public_key<R: Rng, P: FnMut(&VanityProgress)>(
+        &self,
+        public_key: &EthereumPublicKey,
+        rng: &mut R,
+        threads: usize,
+        progress: P,
+    ) -> Result<(EthereumPrivateKey, u64), VanityError> {
+        let public_key = public_key.to_secp256k1_public_key();
+        let (secret_key, attempts) = vanity_search(rng, Some(&public_key), threads, self.matcher(), progress)?;
+        Ok((EthereumPrivateKey::from_secp256k1_secret_key(&secret_key), attempts))
+    }
+
+    /// Returns the sum of the given private key and a partial private key found by a split-key search.
+    pub fn combine(
+        private_key: &EthereumPrivateKey,
+        partial_private_key: &EthereumPrivateKey,
+    ) -> Result<EthereumPrivateKey, VanityError> {
+        let mut secret_key = private_key.to_secp256k1_secret_key();
+        secret_key.tweak_add_assign(&partial_private_key.to_secp256k1_secret_key())?;
+        Ok(EthereumPrivateKey::from_secp256k1_secret_key(&secret_key))
+    }
+
+    /// Returns the sum of the given public key and the public key of a partial private key
+    /// found by a split-key search, which is the public key of the address that was found.
+    pub fn combine_public_key(
+        public_key: &EthereumPublicKey,
+        partial_private_key: &EthereumPrivateKey,
+    ) -> Result<EthereumPublicKey, VanityError> {
+        let public_key = secp256k1::PublicKey::combine(&[
+            public_key.to_secp256k1_public_key(),
+            partial_private_key.to_public_key().to_secp256k1_public_key(),
+        ])?;
+        Ok(EthereumPublicKey::from_secp256k1_public_key(public_key))
+    }
+
+    /// Returns the matcher of public keys whose address begins with the prefix.
+    /// The checksum is only computed for addresses whose lowercase hex begins with the prefix.
+    fn matcher(&self) -> impl Fn(&secp256k1::PublicKey) -> bool + Send + Sync + 'static {
+        let vanity = self.clone();
+        let lowercase_prefix = self.prefix.to_lowercase();
+        move |public_key: &secp256k1::PublicKey| {
+            let address = hex::encode(&keccak256(&public_key.serialize()[1..])[12..]);
+            match address.starts_with(&lowercase_prefix) {
+                true if vanity.case_sensitive => match EthereumAddress::from_str(&address) {
+                    Ok(address) => vanity.is_match(&address),
+                    Err(_) => false,
+                },
+                matched => matched,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use wagyu_model::{PrivateKey, PublicKey};
+
+    fn test_search(prefix: &str, case_sensitive: bool) {
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        let vanity = EthereumVanity::new(prefix, case_sensitive).unwrap();
+        let (private_key, attempts) = vanity.search(&mut rng, 2, |_| ()).unwrap();
+
+        let address = private_key.to_address(&crate::EthereumFormat::Standard).unwrap();
+        assert!(vanity.is_match(&address));
+        assert!(attempts > 0);
+    }
+
+    #[test]
+    fn search() {
+        test_search("0xdea", false);
+        test_search("0x0a1", true);
+        test_search("Ab", true);
+    }
+
+    #[test]
+    fn search_with_public_key() {
+        let mut rng = XorShiftRng::seed_from_u64(1);
+        let private_key = EthereumPrivateKey::new(&mut rng).unwrap();
+        let public_key = EthereumPublicKey::from_private_key(&private_key);
+
+        let vanity = EthereumVanity::new("0xbee", false).unwrap();
+        let (partial_private_key, _) = vanity.search_with_public_key(&public_key, &mut rng, 2, |_| ()).unwrap();
+
+        let combined_public_key = EthereumVanity::combine_public_key(&public_key, &partial_private_key).unwrap();
+        let private_key = EthereumVanity::combine(&private_key, &partial_private_key).unwrap();
+        let address = private_key.to_address(&crate::EthereumFormat::Standard).unwrap();
+        assert!(address.to_string().to_lowercase().starts_with("0xbee"));
+        assert_eq!(
+            address,
+            combined_public_key
+                .to_address(&crate::EthereumFormat::Standard)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn is_match() {
+        let address = EthereumAddress::from_str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").unwrap();
+        assert!(EthereumVanity::new("0x5aaeb", false).unwrap().is_match(&address));
+        assert!(EthereumVanity::new("0x5AAEB", false).unwrap().is_match(&address));
+        assert!(EthereumVanity::new("0x5aAeb", true).unwrap().is_match(&address));
+        assert!(!EthereumVanity::new("0x5aaeb", true).unwrap().is_match(&address));
+    }
+
+    #[test]
+    fn difficulty() {
+        assert_eq!(1.0, EthereumVanity::new("0x", false).unwrap().difficulty());
+        assert_eq!(65_536.0, EthereumVanity::new("0xdead", false).unwrap().difficulty());
+        assert_eq!(65_536.0, EthereumVanity::new("0x1234", true).unwrap().difficulty());
+        assert_eq!(1_048_576.0, EthereumVanity::new("0xdead", true).unwrap().difficulty());
+    }
+
+    #[test]
+    fn invalid_prefix() {
+        assert!(EthereumVanity::new("0xdeadbeefg", false).is_err());
+        assert!(EthereumVanity::new("0x0123456789abcdef0123456789abcdef012345678", false).is_err());
+    }
+}
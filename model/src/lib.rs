@@ -53,5 +53,10 @@ pub use self::transaction::*;
 pub mod utilities;
 pub use self::utilities::*;
 
+#[cfg(feature = "std")]
+pub mod vanity;
+#[cfg(feature = "std")]
+pub use self::vanity::*;
+
 pub mod wordlist;
 pub use self::wordlist::*;
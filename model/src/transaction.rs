@@ -64,7 +64,7 @@ pub enum TransactionError {
     InvalidBindingSig(),
 
     #[fail(display = "invalid chain id {:?}", _0)]
-    InvalidChainId(u8),
+    InvalidChainId(u64),
 
     #[fail(display = "invalid ephemeral key {}", _0)]
     InvalidEphemeralKey(String),
@@ -87,6 +87,9 @@ pub enum TransactionError {
     #[fail(display = "invalid segwit flag: {:?}", _0)]
     InvalidSegwitFlag(usize),
 
+    #[fail(display = "invalid signature hash type: {:?}", _0)]
+    InvalidSignatureHash(u8),
+
     #[fail(display = "invalid spend description for address")]
     InvalidSpendDescription,
 
@@ -0,0 +1,300 @@
+//!
+//! Vanity Search
+//!
+//! This module contains a multi-threaded search for secp256k1 keys whose public keys match a pattern.
+//! Each thread starts from a random key and steps through consecutive keys by adding the generator
+//! to its public key, converting batches of points to affine coordinates with a single field inversion.
+//!
+
+use secp256k1::curve::{Affine, Field, Jacobian, Scalar, AFFINE_G};
+use secp256k1::{PublicKey, SecretKey};
+
+use rand::Rng;
+use std::{
+    convert::TryFrom,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// The number of consecutive points converted to affine coordinates with a single field inversion
+const BATCH_SIZE: usize = 256;
+
+/// The interval between progress reports of a search
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Fail)]
+pub enum VanityError {
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "invalid vanity prefix {}: {}", _0, _1)]
+    InvalidPrefix(String, String),
+
+    #[fail(display = "invalid thread count: {}", _0)]
+    InvalidThreadCount(usize),
+
+    #[fail(display = "{}", _0)]
+    Message(String),
+}
+
+impl From<secp256k1::Error> for VanityError {
+    fn from(error: secp256k1::Error) -> Self {
+        VanityError::Crate("libsecp256k1", format!("{:?}", error))
+    }
+}
+
+/// Represents the progress of a vanity search
+#[derive(Debug, Clone, PartialEq)]
+pub struct VanityProgress {
+    /// The number of keys tried by all threads
+    pub attempts: u64,
+    /// The time since the search started
+    pub elapsed: Duration,
+}
+
+impl VanityProgress {
+    /// Returns the number of keys tried per second.
+    pub fn rate(&self) -> f64 {
+        match self.elapsed.as_secs_f64() {
+            seconds if seconds > 0.0 => self.attempts as f64 / seconds,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the probability of a match having been found by now, for a pattern of the given difficulty.
+    pub fn probability(&self, difficulty: f64) -> f64 {
+        1.0 - (1.0 - 1.0 / difficulty).powf(self.attempts as f64)
+    }
+
+    /// Returns the expected time to reach a 50% probability of a match, for a pattern of the given difficulty.
+    pub fn expected_time(&self, difficulty: f64) -> Option<Duration> {
+        match self.rate() > 0.0 {
+            true => Some(Duration::from_secs_f64(
+                difficulty * std::f64::consts::LN_2 / self.rate(),
+            )),
+            false => None,
+        }
+    }
+}
+
+/// Returns a secret key whose public key satisfies the given matcher, and the number of keys tried.
+///
+/// If a public key is given, the search is split-key: the public key that is matched is the sum of
+/// the given public key and the public key of the returned secret key. The returned secret key is
+/// then a partial key, to be added to the secret key of the given public key by its owner,
+/// who is the only party that learns the full key.
+///
+/// The search runs on the given number of threads, reporting its progress every second.
+pub fn vanity_search<R, M, P>(
+    rng: &mut R,
+    public_key: Option<&PublicKey>,
+    threads: usize,
+    matcher: M,
+    mut progress: P,
+) -> Result<(SecretKey, u64), VanityError>
+where
+    R: Rng,
+    M: Fn(&PublicKey) -> bool + Send + Sync + 'static,
+    P: FnMut(&VanityProgress),
+{
+    if threads == 0 {
+        return Err(VanityError::InvalidThreadCount(threads));
+    }
+
+    let matcher = Arc::new(matcher);
+    let found = Arc::new(AtomicBool::new(false));
+    let attempts = Arc::new(AtomicU64::new(0));
+    let (sender, receiver) = mpsc::channel();
+
+    let mut workers = Vec::with_capacity(threads);
+    for _ in 0..threads {
+        let start = SecretKey::random(rng);
+        let public_key = public_key.cloned();
+        let matcher = matcher.clone();
+        let found = found.clone();
+        let attempts = attempts.clone();
+        let sender = sender.clone();
+        workers.push(thread::spawn(move || {
+            let result = search_worker(start, public_key, &*matcher, &found, &attempts);
+            if let Ok(None) = result {
+                return;
+            }
+            found.store(true, Ordering::Relaxed);
+            let _ = sender.send(result);
+        }));
+    }
+    drop(sender);
+
+    let start = Instant::now();
+    let result = loop {
+        match receiver.recv_timeout(PROGRESS_INTERVAL) {
+            Ok(result) => break result,
+            Err(mpsc::RecvTimeoutError::Timeout) => progress(&VanityProgress {
+                attempts: attempts.load(Ordering::Relaxed),
+                elapsed: start.elapsed(),
+            }),
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                break Err(VanityError::Message(
+                    "vanity search threads exited without a match".into(),
+                ))
+            }
+        }
+    };
+
+    found.store(true, Ordering::Relaxed);
+    for worker in workers {
+        let _ = worker.join();
+    }
+
+    let attempts = attempts.load(Ordering::Relaxed);
+    match result? {
+        Some((secret_key, index)) => Ok((secret_key, attempts + index)),
+        None => Err(VanityError::Message("vanity search stopped without a match".into())),
+    }
+}
+
+/// Searches the keys following the given start key until a match is found or the search is stopped.
+/// Returns the matching secret key and the number of keys tried in its batch, or `None` if stopped.
+fn search_worker<M: Fn(&PublicKey) -> bool>(
+    start: SecretKey,
+    public_key: Option<PublicKey>,
+    matcher: &M,
+    found: &AtomicBool,
+    attempts: &AtomicU64,
+) -> Result<Option<(SecretKey, u64)>, VanityError> {
+    let mut point = PublicKey::from_secret_key(&start);
+    if let Some(public_key) = public_key {
+        point = PublicKey::combine(&[point, public_key])?;
+    }
+
+    let affine: Affine = point.into();
+    let mut next = Jacobian::from_ge(&affine);
+    let mut key: Scalar = start.into();
+
+    let mut points = vec![next.clone(); BATCH_SIZE];
+    let mut products = vec![Field::default(); BATCH_SIZE];
+    let mut serialized = [0u8; 65];
+    serialized[0] = 0x04;
+
+    while !found.load(Ordering::Relaxed) {
+        // The points of consecutive keys, in jacobian coordinates.
+        // A point at infinity requires a key sum of zero, and has negligible probability.
+        for point in points.iter_mut() {
+            *point = next.clone();
+            next = next.add_ge_var(&AFFINE_G, None);
+        }
+
+        // Inverts the z coordinates of the batch with one inversion of their product (Montgomery's trick).
+        products[0] = points[0].z.clone();
+        for i in 1..BATCH_SIZE {
+            products[i] = &products[i - 1] * &points[i].z;
+        }
+        let mut inverse = products[BATCH_SIZE - 1].inv_var();
+
+        for i in (0..BATCH_SIZE).rev() {
+            let z_inverse = match i {
+                0 => inverse.clone(),
+                _ => &inverse * &products[i - 1],
+            };
+            inverse = &inverse * &points[i].z;
+
+            let mut affine = Affine::default();
+            affine.set_gej_zinv(&points[i], &z_inverse);
+            affine.x.normalize_var();
+            affine.y.normalize_var();
+            serialized[1..33].copy_from_slice(&affine.x.b32());
+            serialized[33..65].copy_from_slice(&affine.y.b32());
+
+            if matcher(&PublicKey::parse(&serialized)?) {
+                let secret_key = SecretKey::try_from(key + Scalar::from_int(i as u32))?;
+                return Ok(Some((secret_key, (BATCH_SIZE - i) as u64)));
+            }
+        }
+
+        attempts.fetch_add(BATCH_SIZE as u64, Ordering::Relaxed);
+        key = key + Scalar::from_int(BATCH_SIZE as u32);
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn search() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // Matches one in 256 public keys
+        let (secret_key, attempts) = vanity_search(
+            &mut rng,
+            None,
+            2,
+            |public_key: &PublicKey| public_key.serialize()[1] == 0xab,
+            |_| (),
+        )
+        .unwrap();
+
+        assert_eq!(0xab, PublicKey::from_secret_key(&secret_key).serialize()[1]);
+        assert!(attempts > 0);
+    }
+
+    #[test]
+    fn search_batches() {
+        let mut rng = StdRng::seed_from_u64(1);
+        // Matches one in 65536 public keys, spanning many batches
+        let (secret_key, _) = vanity_search(
+            &mut rng,
+            None,
+            4,
+            |public_key: &PublicKey| public_key.serialize()[1..3] == [0x12, 0x34],
+            |_| (),
+        )
+        .unwrap();
+
+        assert_eq!([0x12, 0x34], PublicKey::from_secret_key(&secret_key).serialize()[1..3]);
+    }
+
+    #[test]
+    fn split_key_search() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let owner_secret_key = SecretKey::random(&mut rng);
+        let owner_public_key = PublicKey::from_secret_key(&owner_secret_key);
+
+        let (partial_secret_key, _) = vanity_search(
+            &mut rng,
+            Some(&owner_public_key),
+            2,
+            |public_key: &PublicKey| public_key.serialize()[1] == 0xcd,
+            |_| (),
+        )
+        .unwrap();
+
+        // The partial key alone does not match, but its sum with the owner key does.
+        let mut secret_key = owner_secret_key.clone();
+        secret_key.tweak_add_assign(&partial_secret_key).unwrap();
+        assert_eq!(0xcd, PublicKey::from_secret_key(&secret_key).serialize()[1]);
+    }
+
+    #[test]
+    fn invalid_thread_count() {
+        let mut rng = StdRng::seed_from_u64(3);
+        assert!(vanity_search(&mut rng, None, 0, |_: &PublicKey| true, |_| ()).is_err());
+    }
+
+    #[test]
+    fn progress() {
+        let progress = VanityProgress {
+            attempts: 1_000_000,
+            elapsed: Duration::from_secs(2),
+        };
+        assert_eq!(500_000.0, progress.rate());
+        assert!((progress.probability(1_000_000.0) - 0.632).abs() < 0.001);
+        assert_eq!(1, progress.expected_time(1_000_000.0).unwrap().as_secs());
+    }
+}
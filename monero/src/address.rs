@@ -26,8 +26,11 @@ impl<N: MoneroNetwork> Address for MoneroAddress<N> {
     fn from_private_key(private_key: &Self::PrivateKey, format: &Self::Format) -> Result<Self, AddressError> {
         match (private_key.format(), format) {
             (MoneroFormat::Standard, _) | (MoneroFormat::Subaddress(_, _), MoneroFormat::Subaddress(_, _)) => {
-                let private_key =
-                    Self::PrivateKey::from_private_spend_key(&hex::encode(private_key.to_private_spend_key()), format)?;
+                let private_key = Self::PrivateKey::from_private_keys(
+                    &hex::encode(private_key.to_private_spend_key()),
+                    &hex::encode(private_key.to_private_view_key()),
+                    format,
+                )?;
                 Self::from_public_key(&private_key.to_public_key(), format)
             }
             (MoneroFormat::Integrated(_), MoneroFormat::Standard)
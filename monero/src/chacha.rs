@@ -0,0 +1,218 @@
+use crate::cryptonight::cn_slow_hash;
+use crate::ringct::{RingCtError, Signature};
+
+use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, scalar::Scalar};
+use rand::Rng;
+use tiny_keccak::keccak256;
+
+/// The number of CryptoNight hashes that derive a ChaCha20 key, unless a wallet sets `--kdf-rounds`
+pub const DEFAULT_KDF_ROUNDS: u64 = 1;
+
+/// The ChaCha20 constant "expand 32-byte k".
+const SIGMA: [u32; 4] = [0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574];
+
+/// Returns the 64-byte keystream block of the ChaCha state after the given number of double rounds.
+fn chacha_block(input: &[u32; 16], double_rounds: usize) -> [u8; 64] {
+    let mut x = *input;
+    let quarter_round = |x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize| {
+        x[a] = x[a].wrapping_add(x[b]);
+        x[d] = (x[d] ^ x[a]).rotate_left(16);
+        x[c] = x[c].wrapping_add(x[d]);
+        x[b] = (x[b] ^ x[c]).rotate_left(12);
+        x[a] = x[a].wrapping_add(x[b]);
+        x[d] = (x[d] ^ x[a]).rotate_left(8);
+        x[c] = x[c].wrapping_add(x[d]);
+        x[b] = (x[b] ^ x[c]).rotate_left(7);
+    };
+    for _ in 0..double_rounds {
+        quarter_round(&mut x, 0, 4, 8, 12);
+        quarter_round(&mut x, 1, 5, 9, 13);
+        quarter_round(&mut x, 2, 6, 10, 14);
+        quarter_round(&mut x, 3, 7, 11, 15);
+        quarter_round(&mut x, 0, 5, 10, 15);
+        quarter_round(&mut x, 1, 6, 11, 12);
+        quarter_round(&mut x, 2, 7, 8, 13);
+        quarter_round(&mut x, 3, 4, 9, 14);
+    }
+
+    let mut block = [0u8; 64];
+    for (i, bytes) in block.chunks_mut(4).enumerate() {
+        bytes.copy_from_slice(&x[i].wrapping_add(input[i]).to_le_bytes());
+    }
+    block
+}
+
+/// Returns the data encrypted (or decrypted) with the original ChaCha20 of an 8-byte IV
+/// and a 64-bit block counter (Monero `chacha20`).
+pub fn chacha20(data: &[u8], key: &[u8; 32], iv: &[u8; 8]) -> Vec<u8> {
+    chacha(data, key, iv, 10)
+}
+
+/// Returns the data encrypted (or decrypted) with ChaCha8, as used by the wallet files
+/// of Monero versions before v0.12 (Monero `chacha8`).
+pub fn chacha8(data: &[u8], key: &[u8; 32], iv: &[u8; 8]) -> Vec<u8> {
+    chacha(data, key, iv, 4)
+}
+
+fn chacha(data: &[u8], key: &[u8; 32], iv: &[u8; 8], double_rounds: usize) -> Vec<u8> {
+    let word = |bytes: &[u8]| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+    let mut input = [0u32; 16];
+    input[..4].copy_from_slice(&SIGMA);
+    for i in 0..8 {
+        input[4 + i] = word(&key[4 * i..]);
+    }
+    input[14] = word(&iv[0..4]);
+    input[15] = word(&iv[4..8]);
+
+    let mut output = Vec::with_capacity(data.len());
+    for (counter, chunk) in data.chunks(64).enumerate() {
+        input[12] = counter as u32;
+        input[13] = (counter as u64 >> 32) as u32;
+        let keystream = chacha_block(&input, double_rounds);
+        output.extend(chunk.iter().zip(keystream.iter()).map(|(byte, key)| byte ^ key));
+    }
+    output
+}
+
+/// Returns the ChaCha20 key of a secret, which is its CryptoNight hash repeated `kdf_rounds` times
+/// (Monero `generate_chacha_key`).
+pub fn generate_chacha_key(secret: &[u8], kdf_rounds: u64) -> [u8; 32] {
+    let mut key = cn_slow_hash(secret);
+    for _ in 1..kdf_rounds {
+        key = cn_slow_hash(&key);
+    }
+    key
+}
+
+/// Returns the plaintext encrypted with the ChaCha20 key of a secret key, as the random IV and the
+/// ciphertext followed by a signature of their hash by the secret key (Monero `wallet2::encrypt`).
+pub fn encrypt_with_secret_key<R: Rng>(
+    plaintext: &[u8],
+    secret_key: &[u8; 32],
+    kdf_rounds: u64,
+    rng: &mut R,
+) -> Vec<u8> {
+    let key = generate_chacha_key(secret_key, kdf_rounds);
+    let iv: [u8; 8] = rng.gen();
+
+    let mut ciphertext = iv.to_vec();
+    ciphertext.extend(chacha20(plaintext, &key, &iv));
+
+    let signature = Signature::sign(&keccak256(&ciphertext), &Scalar::from_bytes_mod_order(*secret_key), rng);
+    ciphertext.extend(signature.to_bytes().iter());
+    ciphertext
+}
+
+/// Returns the plaintext of a ciphertext encrypted with the ChaCha20 key of a secret key,
+/// after authenticating its signature (Monero `wallet2::decrypt`).
+pub fn decrypt_with_secret_key(
+    ciphertext: &[u8],
+    secret_key: &[u8; 32],
+    kdf_rounds: u64,
+) -> Result<Vec<u8>, RingCtError> {
+    if ciphertext.len() < 8 + 64 {
+        return Err(RingCtError::InvalidByteLength(ciphertext.len()));
+    }
+    let (data, signature) = ciphertext.split_at(ciphertext.len() - 64);
+
+    let public_key = &Scalar::from_bytes_mod_order(*secret_key) * &ED25519_BASEPOINT_TABLE;
+    Signature::from_bytes(signature)?.verify(&keccak256(data), &public_key)?;
+
+    let mut iv = [0u8; 8];
+    iv.copy_from_slice(&data[0..8]);
+    let key = generate_chacha_key(secret_key, kdf_rounds);
+    Ok(chacha20(&data[8..], &key, &iv))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    // (key, iv, plaintext, ciphertext)
+    const CHACHA20: [(&str, &str, &str, &str); 2] = [
+        (
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000",
+            "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            "0001020304050607",
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f60616263",
+            "f799a38af590e06e8a1955f06806b97a6f468fb00217ea84f418b64de447ddde14857658571e6066186be36f2c64394635e28c6ade2965c6612f2f6717d51a257841c9d962f973d3566d0e5c30c79029d98fc7751dd38f0fd1a23ab31a7497e2fa3ba97f",
+        ),
+    ];
+
+    // (key, iv, keystream)
+    const CHACHA8: [(&str, &str, &str); 1] = [(
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000",
+        "3e00ef2f895f40d67f5bb8e81f09a5a12c840ec3ce9a7f3b181be188ef711a1e984ce172b9216f419f445367456d5619314a42a3da86b001387bfdb80e0cfe42",
+    )];
+
+    fn to_array<T: Default + AsMut<[u8]>>(hex: &str) -> T {
+        let mut array = T::default();
+        array.as_mut().copy_from_slice(&hex::decode(hex).unwrap());
+        array
+    }
+
+    #[test]
+    fn chacha20() {
+        CHACHA20.iter().for_each(|(key, iv, plaintext, ciphertext)| {
+            let key: [u8; 32] = to_array(key);
+            let iv: [u8; 8] = to_array(iv);
+            let plaintext = hex::decode(plaintext).unwrap();
+
+            let encrypted = super::chacha20(&plaintext, &key, &iv);
+            assert_eq!(*ciphertext, hex::encode(&encrypted));
+            assert_eq!(plaintext, super::chacha20(&encrypted, &key, &iv));
+        });
+    }
+
+    #[test]
+    fn chacha8() {
+        CHACHA8.iter().for_each(|(key, iv, keystream)| {
+            let key: [u8; 32] = to_array(key);
+            let iv: [u8; 8] = to_array(iv);
+            assert_eq!(*keystream, hex::encode(super::chacha8(&[0u8; 64], &key, &iv)));
+        });
+    }
+
+    #[test]
+    fn encrypt_and_decrypt() {
+        let rng = &mut StdRng::from_seed([0u8; 32]);
+        let secret_key: [u8; 32] = to_array("55c5b2fa94b2c5ee387eb9dd71b197a6358a5a90ed5eb5acdfa088583125a40a");
+        let plaintext = b"key images".to_vec();
+
+        let ciphertext = encrypt_with_secret_key(&plaintext, &secret_key, DEFAULT_KDF_ROUNDS, rng);
+        assert_eq!(8 + plaintext.len() + 64, ciphertext.len());
+        assert_eq!(
+            plaintext,
+            decrypt_with_secret_key(&ciphertext, &secret_key, DEFAULT_KDF_ROUNDS).unwrap()
+        );
+    }
+
+    mod invalid {
+        use super::*;
+
+        #[test]
+        fn decrypt_invalid_ciphertext() {
+            let rng = &mut StdRng::from_seed([0u8; 32]);
+            let secret_key = [1u8; 32];
+            let other_key = [2u8; 32];
+
+            let mut ciphertext = encrypt_with_secret_key(b"key images", &secret_key, DEFAULT_KDF_ROUNDS, rng);
+            assert!(decrypt_with_secret_key(&ciphertext, &other_key, DEFAULT_KDF_ROUNDS).is_err());
+            assert!(decrypt_with_secret_key(&ciphertext[..71], &secret_key, DEFAULT_KDF_ROUNDS).is_err());
+
+            ciphertext[10] ^= 1;
+            assert_eq!(
+                Err(RingCtError::InvalidSignature),
+                decrypt_with_secret_key(&ciphertext, &secret_key, DEFAULT_KDF_ROUNDS)
+            );
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! The AES rounds of the CryptoNight scratchpad, which are the encryption rounds of AES
+//! (`SubBytes`, `ShiftRows`, `MixColumns` and `AddRoundKey`) without the key whitening.
+
+/// Multiplies two elements of GF(2^8) modulo the AES polynomial `x^8 + x^4 + x^3 + x + 1`.
+pub(crate) fn gf_mul(a: u8, b: u8) -> u8 {
+    let (mut a, mut b, mut product) = (a, b, 0u8);
+    while b != 0 {
+        if b & 1 == 1 {
+            product ^= a;
+        }
+        a = (a << 1) ^ if a & 0x80 != 0 { 0x1b } else { 0 };
+        b >>= 1;
+    }
+    product
+}
+
+/// Returns the AES S-box, the affine transformation of the multiplicative inverse.
+fn sbox() -> [u8; 256] {
+    let mut sbox = [0u8; 256];
+    for (x, entry) in sbox.iter_mut().enumerate() {
+        // x^254 is the inverse of x, and zero for zero.
+        let mut inverse = 1u8;
+        for _ in 0..254 {
+            inverse = gf_mul(inverse, x as u8);
+        }
+        *entry = inverse
+            ^ inverse.rotate_left(1)
+            ^ inverse.rotate_left(2)
+            ^ inverse.rotate_left(3)
+            ^ inverse.rotate_left(4)
+            ^ 0x63;
+    }
+    sbox
+}
+
+lazy_static! {
+    /// The AES S-box.
+    pub(crate) static ref SBOX: [u8; 256] = sbox();
+
+    /// The combined `SubBytes` and `MixColumns` table of the first row, as little-endian columns.
+    static ref TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        for (x, entry) in table.iter_mut().enumerate() {
+            let s = SBOX[x];
+            *entry = u32::from_le_bytes([gf_mul(s, 2), s, s, gf_mul(s, 3)]);
+        }
+        table
+    };
+}
+
+/// Represents the round keys of an AES-256 key, of which CryptoNight uses the first ten
+pub struct AesKey {
+    round_keys: [[u32; 4]; 10],
+}
+
+impl AesKey {
+    /// Returns the round keys of the AES-256 key schedule.
+    pub fn new(key: &[u8]) -> Self {
+        let mut words = [0u32; 60];
+        for (i, word) in key.chunks(4).take(8).enumerate() {
+            words[i] = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+        }
+
+        let sub_word = |word: u32| {
+            let bytes = word.to_le_bytes();
+            u32::from_le_bytes([
+                SBOX[bytes[0] as usize],
+                SBOX[bytes[1] as usize],
+                SBOX[bytes[2] as usize],
+                SBOX[bytes[3] as usize],
+            ])
+        };
+
+        let mut rcon = 1u8;
+        for i in 8..60 {
+            let mut word = words[i - 1];
+            if i % 8 == 0 {
+                word = sub_word(word.rotate_right(8)) ^ rcon as u32;
+                rcon = gf_mul(rcon, 2);
+            } else if i % 8 == 4 {
+                word = sub_word(word);
+            }
+            words[i] = words[i - 8] ^ word;
+        }
+
+        let mut round_keys = [[0u32; 4]; 10];
+        for (i, round_key) in round_keys.iter_mut().enumerate() {
+            round_key.copy_from_slice(&words[4 * i..4 * i + 4]);
+        }
+        Self { round_keys }
+    }
+
+    /// Encrypts a block in place with the ten rounds of the key.
+    pub fn encrypt(&self, block: &mut [u8]) {
+        let mut state = to_columns(block);
+        self.round_keys
+            .iter()
+            .for_each(|round_key| state = round(&state, round_key));
+        from_columns(&state, block);
+    }
+}
+
+/// Returns the columns of a 16-byte block as little-endian words.
+fn to_columns(block: &[u8]) -> [u32; 4] {
+    let mut columns = [0u32; 4];
+    for (column, bytes) in columns.iter_mut().zip(block.chunks(4)) {
+        *column = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    columns
+}
+
+/// Writes the little-endian columns into a 16-byte block.
+fn from_columns(columns: &[u32; 4], block: &mut [u8]) {
+    for (column, bytes) in columns.iter().zip(block.chunks_mut(4)) {
+        bytes.copy_from_slice(&column.to_le_bytes());
+    }
+}
+
+/// Returns a single AES encryption round of the state with the round key (`aesenc`).
+pub fn round(state: &[u32; 4], round_key: &[u32; 4]) -> [u32; 4] {
+    let mut output = [0u32; 4];
+    for (c, column) in output.iter_mut().enumerate() {
+        // ShiftRows moves row r of column c + r into column c.
+        *column = TABLE[(state[c] & 0xff) as usize]
+            ^ TABLE[((state[(c + 1) % 4] >> 8) & 0xff) as usize].rotate_left(8)
+            ^ TABLE[((state[(c + 2) % 4] >> 16) & 0xff) as usize].rotate_left(16)
+            ^ TABLE[(state[(c + 3) % 4] >> 24) as usize].rotate_left(24)
+            ^ round_key[c];
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sbox() {
+        assert_eq!(0x63, SBOX[0x00]);
+        assert_eq!(0x7c, SBOX[0x01]);
+        assert_eq!(0xed, SBOX[0x53]);
+        assert_eq!(0x16, SBOX[0xff]);
+    }
+
+    #[test]
+    fn aes256_round_keys() {
+        // FIPS-197 appendix A.3
+        let key = hex::decode("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4").unwrap();
+        let round_keys = AesKey::new(&key).round_keys;
+
+        let mut block = [0u8; 16];
+        from_columns(&round_keys[2], &mut block);
+        assert_eq!("9ba354118e6925afa51a8b5f2067fcde", hex::encode(block));
+    }
+}
@@ -0,0 +1,120 @@
+//! BLAKE-256, the 14-round version of the final SHA-3 submission.
+
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const CONSTANTS: [u32; 16] = [
+    0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344, 0xa4093822, 0x299f31d0, 0x082efa98, 0xec4e6c89, 0x452821e6,
+    0x38d01377, 0xbe5466cf, 0x34e90c6c, 0xc0ac29b7, 0xc97c50dd, 0x3f84d5b5, 0xb5470917,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+const ROUNDS: usize = 14;
+
+/// Compresses a block into the chain value given the number of message bits hashed so far.
+fn compress(h: &mut [u32; 8], block: &[u8], counter: u64) {
+    let mut m = [0u32; 16];
+    for (word, bytes) in m.iter_mut().zip(block.chunks(4)) {
+        *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+
+    let mut v = [0u32; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&CONSTANTS[..8]);
+    v[12] ^= counter as u32;
+    v[13] ^= counter as u32;
+    v[14] ^= (counter >> 32) as u32;
+    v[15] ^= (counter >> 32) as u32;
+
+    for round in 0..ROUNDS {
+        let sigma = &SIGMA[round % 10];
+        let mut g = |a: usize, b: usize, c: usize, d: usize, i: usize| {
+            let (x, y) = (sigma[2 * i], sigma[2 * i + 1]);
+            v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[x] ^ CONSTANTS[y]);
+            v[d] = (v[d] ^ v[a]).rotate_right(16);
+            v[c] = v[c].wrapping_add(v[d]);
+            v[b] = (v[b] ^ v[c]).rotate_right(12);
+            v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[y] ^ CONSTANTS[x]);
+            v[d] = (v[d] ^ v[a]).rotate_right(8);
+            v[c] = v[c].wrapping_add(v[d]);
+            v[b] = (v[b] ^ v[c]).rotate_right(7);
+        };
+        g(0, 4, 8, 12, 0);
+        g(1, 5, 9, 13, 1);
+        g(2, 6, 10, 14, 2);
+        g(3, 7, 11, 15, 3);
+        g(0, 5, 10, 15, 4);
+        g(1, 6, 11, 12, 5);
+        g(2, 7, 8, 13, 6);
+        g(3, 4, 9, 14, 7);
+    }
+
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// Returns the BLAKE-256 hash of the data.
+pub fn blake256(data: &[u8]) -> [u8; 32] {
+    // The padding is a one bit, zeros and a one bit, followed by the 64-bit message length.
+    let mut padded = data.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    let last = padded.len() - 1;
+    padded[last] |= 0x01;
+    padded.extend(&(data.len() as u64 * 8).to_be_bytes());
+
+    let mut h = IV;
+    for (i, block) in padded.chunks(64).enumerate() {
+        // The counter is the number of message bits up to the end of the block,
+        // or zero for a block of padding only.
+        let counter = match i * 64 < data.len() {
+            true => data.len().min((i + 1) * 64) as u64 * 8,
+            false => 0,
+        };
+        compress(&mut h, block, counter);
+    }
+
+    let mut hash = [0u8; 32];
+    for (bytes, word) in hash.chunks_mut(4).zip(h.iter()) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (data, hash)
+    const HASHES: [(&str, &str); 3] = [
+        ("", "716f6e863f744b9ac22c97ec7b76ea5f5908bc5b2f67c61510bfc4751384ea7a"),
+        ("00", "0ce8d4ef4dd7cd8d62dfded9d4edb0a774ae6a41929a74da23109e8f11139c87"),
+        (
+            "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "d419bad32d504fb7d44d460c42c5593fe544fa4c135dec31e21bd9abdcc22d41",
+        ),
+    ];
+
+    #[test]
+    fn hash() {
+        HASHES.iter().for_each(|(data, expected)| {
+            assert_eq!(*expected, hex::encode(blake256(&hex::decode(data).unwrap())));
+        });
+    }
+}
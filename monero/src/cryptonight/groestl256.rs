@@ -0,0 +1,109 @@
+//! Grøstl-256, the final (tweaked) version of the SHA-3 submission.
+
+use super::aes::{gf_mul, SBOX};
+
+const ROUNDS: u8 = 10;
+
+/// The circulant MixBytes matrix row.
+const MIX_BYTES: [u8; 8] = [2, 2, 3, 4, 5, 3, 5, 7];
+
+/// The row shifts of the permutations P and Q.
+const SHIFTS_P: [usize; 8] = [0, 1, 2, 3, 4, 5, 6, 7];
+const SHIFTS_Q: [usize; 8] = [1, 3, 5, 7, 0, 2, 4, 6];
+
+/// Applies the permutation P (or Q) to a state of eight columns, stored column by column.
+fn permute(state: &mut [u8; 64], q: bool) {
+    for round in 0..ROUNDS {
+        // AddRoundConstant
+        for column in 0..8 {
+            let constant = ((column as u8) << 4) ^ round;
+            match q {
+                false => state[8 * column] ^= constant,
+                true => {
+                    state[8 * column..8 * column + 8]
+                        .iter_mut()
+                        .for_each(|byte| *byte ^= 0xff);
+                    state[8 * column + 7] ^= constant;
+                }
+            }
+        }
+
+        // SubBytes and ShiftBytes
+        let shifts = if q { &SHIFTS_Q } else { &SHIFTS_P };
+        let mut shifted = [0u8; 64];
+        for column in 0..8 {
+            for row in 0..8 {
+                shifted[8 * column + row] = SBOX[state[8 * ((column + shifts[row]) % 8) + row] as usize];
+            }
+        }
+
+        // MixBytes
+        for column in 0..8 {
+            for row in 0..8 {
+                state[8 * column + row] = (0..8).fold(0, |sum, j| {
+                    sum ^ gf_mul(MIX_BYTES[(8 + j - row) % 8], shifted[8 * column + j])
+                });
+            }
+        }
+    }
+}
+
+/// Returns the Grøstl-256 hash of the data.
+pub fn groestl256(data: &[u8]) -> [u8; 32] {
+    // The padding is a one bit, zeros and the 64-bit number of blocks.
+    let mut padded = data.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    padded.extend(&((padded.len() / 64 + 1) as u64).to_be_bytes());
+
+    // The initial chain value encodes the output length of 256 bits.
+    let mut h = [0u8; 64];
+    h[62] = 0x01;
+
+    for block in padded.chunks(64) {
+        // h = P(h ^ m) ^ Q(m) ^ h
+        let mut p = [0u8; 64];
+        let mut q = [0u8; 64];
+        for i in 0..64 {
+            p[i] = h[i] ^ block[i];
+            q[i] = block[i];
+        }
+        permute(&mut p, false);
+        permute(&mut q, true);
+        for i in 0..64 {
+            h[i] ^= p[i] ^ q[i];
+        }
+    }
+
+    // The output transformation truncates P(h) ^ h to its last 256 bits.
+    let mut p = h;
+    permute(&mut p, false);
+    let mut hash = [0u8; 32];
+    for i in 0..32 {
+        hash[i] = p[32 + i] ^ h[32 + i];
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (data, hash)
+    const HASHES: [(&str, &str); 2] = [
+        ("", "1a52d11d550039be16107f9c58db9ebcc417f16f736adb2502567119f0083467"),
+        (
+            "54686520717569636b2062726f776e20666f78206a756d7073206f76657220746865206c617a7920646f67",
+            "8c7ad62eb26a21297bc39c2d7293b4bd4d3399fa8afab29e970471739e28b301",
+        ),
+    ];
+
+    #[test]
+    fn hash() {
+        HASHES.iter().for_each(|(data, expected)| {
+            assert_eq!(*expected, hex::encode(groestl256(&hex::decode(data).unwrap())));
+        });
+    }
+}
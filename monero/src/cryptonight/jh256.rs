@@ -0,0 +1,164 @@
+//! JH-256, the 42-round version of the final SHA-3 submission, following its reference implementation
+//! on 4-bit elements.
+
+const ROUNDS: usize = 42;
+
+/// The two S-boxes, selected by the bits of the round constant.
+const SBOXES: [[u8; 16]; 2] = [
+    [9, 0, 4, 11, 13, 12, 3, 15, 1, 10, 2, 6, 7, 5, 8, 14],
+    [3, 12, 6, 13, 5, 7, 1, 9, 15, 2, 0, 4, 11, 10, 14, 8],
+];
+
+/// The round constant of the first round, the fractional part of the square root of two.
+const ROUND_CONSTANT_ZERO: [u8; 32] = [
+    0x6a, 0x09, 0xe6, 0x67, 0xf3, 0xbc, 0xc9, 0x08, 0xb2, 0xfb, 0x13, 0x66, 0xea, 0x95, 0x7d, 0x3e, 0x3a, 0xde, 0xc1,
+    0x75, 0x12, 0x77, 0x50, 0x99, 0xda, 0x2f, 0x59, 0x0b, 0x06, 0x67, 0x32, 0x2a,
+];
+
+/// Multiplies an element of GF(2^4) by two modulo `x^4 + x + 1`.
+fn double(a: u8) -> u8 {
+    ((a << 1) ^ (a >> 3) ^ ((a >> 2) & 2)) & 0xf
+}
+
+/// Applies the S-boxes, the linear transformation and the permutation to the 4-bit elements.
+fn round(elements: &mut [u8], constant_bits: &[u8]) {
+    let length = elements.len();
+
+    // S-box layer, where each constant bit selects the S-box of an element
+    let mut t: Vec<u8> = elements
+        .iter()
+        .zip(constant_bits.iter())
+        .map(|(element, bit)| SBOXES[*bit as usize][*element as usize])
+        .collect();
+
+    // Linear transformation L, the MDS code over pairs of elements
+    for pair in t.chunks_mut(2) {
+        pair[1] ^= double(pair[0]);
+        pair[0] ^= double(pair[1]);
+    }
+
+    // Initial swap Pi
+    for quad in t.chunks_mut(4) {
+        quad.swap(2, 3);
+    }
+
+    // Permutation P'
+    for i in 0..length / 2 {
+        elements[i] = t[2 * i];
+        elements[i + length / 2] = t[2 * i + 1];
+    }
+
+    // Final swap Phi
+    for i in (length / 2..length).step_by(2) {
+        elements.swap(i, i + 1);
+    }
+}
+
+/// Returns the bits of a 4-bit element array, most significant bit first.
+fn to_bits(elements: &[u8]) -> Vec<u8> {
+    elements
+        .iter()
+        .flat_map(|element| (0..4).rev().map(move |bit| (element >> bit) & 1))
+        .collect()
+}
+
+/// Applies the bijective function E8 to the 1024-bit state.
+fn e8(h: &mut [u8; 128]) {
+    let bit = |i: usize| (h[i >> 3] >> (7 - (i & 7))) & 1;
+
+    // Group bits i, i + 256, i + 512 and i + 768 into the i-th element,
+    // and interleave the first and second halves of the elements.
+    let mut grouped = [0u8; 256];
+    for (i, element) in grouped.iter_mut().enumerate() {
+        *element = (bit(i) << 3) | (bit(i + 256) << 2) | (bit(i + 512) << 1) | bit(i + 768);
+    }
+    let mut elements = [0u8; 256];
+    for i in 0..128 {
+        elements[2 * i] = grouped[i];
+        elements[2 * i + 1] = grouped[i + 128];
+    }
+
+    // The round constants are generated by the 6-dimensional round function with constant zero.
+    let mut constant: Vec<u8> = ROUND_CONSTANT_ZERO
+        .iter()
+        .flat_map(|byte| vec![byte >> 4, byte & 0xf])
+        .collect();
+    for _ in 0..ROUNDS {
+        round(&mut elements, &to_bits(&constant));
+        round(&mut constant, &[0u8; 64]);
+    }
+
+    // Degroup the elements into the state.
+    for i in 0..128 {
+        grouped[i] = elements[2 * i];
+        grouped[i + 128] = elements[2 * i + 1];
+    }
+    *h = [0u8; 128];
+    for (i, element) in grouped.iter().enumerate() {
+        for (j, offset) in [0, 256, 512, 768].iter().enumerate() {
+            let position = i + offset;
+            h[position >> 3] |= ((element >> (3 - j)) & 1) << (7 - (position & 7));
+        }
+    }
+}
+
+/// Compresses a message block into the state.
+fn f8(h: &mut [u8; 128], block: &[u8]) {
+    for i in 0..64 {
+        h[i] ^= block[i];
+    }
+    e8(h);
+    for i in 0..64 {
+        h[64 + i] ^= block[i];
+    }
+}
+
+/// Returns the JH-256 hash of the data.
+pub fn jh256(data: &[u8]) -> [u8; 32] {
+    // The padding is a one bit and zeros, followed by a final block that ends with the
+    // 128-bit message length (in the same block if the message is a multiple of 64 bytes).
+    let mut padded = data.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 0 {
+        padded.push(0);
+    }
+    if data.len() % 64 != 0 {
+        padded.extend(&[0u8; 64]);
+    }
+    let length = padded.len();
+    padded[length - 16..].copy_from_slice(&(data.len() as u128 * 8).to_be_bytes());
+
+    // The initial state is the compression of a zero block into the output length of 256 bits.
+    let mut h = [0u8; 128];
+    h[0] = 0x01;
+    f8(&mut h, &[0u8; 64]);
+
+    for block in padded.chunks(64) {
+        f8(&mut h, block);
+    }
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&h[96..128]);
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (data, hash)
+    const HASHES: [(&str, &str); 2] = [
+        ("", "46e64619c18bb0a92a5e87185a47eef83ca747b8fcc8e1412921357e326df434"),
+        (
+            "54686520717569636b2062726f776e20666f78206a756d7073206f76657220746865206c617a7920646f67",
+            "6a049fed5fc6874acfdc4a08b568a4f8cbac27de933496f031015b38961608a0",
+        ),
+    ];
+
+    #[test]
+    fn hash() {
+        HASHES.iter().for_each(|(data, expected)| {
+            assert_eq!(*expected, hex::encode(jh256(&hex::decode(data).unwrap())));
+        });
+    }
+}
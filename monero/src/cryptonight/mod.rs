@@ -0,0 +1,198 @@
+//!
+//! CryptoNight
+//!
+//! This module contains the original CryptoNight slow hash (variant 0), which Monero wallets
+//! use to derive the ChaCha20 keys of their encrypted files.
+//!
+
+use tiny_keccak::keccakf;
+
+mod aes;
+use self::aes::*;
+
+mod blake256;
+use self::blake256::*;
+
+mod groestl256;
+use self::groestl256::*;
+
+mod jh256;
+use self::jh256::*;
+
+mod skein512;
+use self::skein512::*;
+
+/// The size of the scratchpad in bytes.
+const MEMORY: usize = 1 << 21;
+
+/// The number of iterations of the memory-hard loop.
+const ITERATIONS: usize = 1 << 20;
+
+/// The rate of the Keccak sponge in bytes.
+const KECCAK_RATE: usize = 136;
+
+/// Returns the 200-byte Keccak-1600 state after absorbing the data.
+fn keccak1600(data: &[u8]) -> [u64; 25] {
+    let mut padded = data.to_vec();
+    padded.push(0x01);
+    while padded.len() % KECCAK_RATE != 0 {
+        padded.push(0);
+    }
+    let last = padded.len() - 1;
+    padded[last] |= 0x80;
+
+    let mut state = [0u64; 25];
+    for block in padded.chunks(KECCAK_RATE) {
+        for (word, bytes) in state.iter_mut().zip(block.chunks(8)) {
+            *word ^= read_u64(bytes);
+        }
+        keccakf(&mut state);
+    }
+    state
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    let mut word = [0u8; 8];
+    word.copy_from_slice(&bytes[0..8]);
+    u64::from_le_bytes(word)
+}
+
+fn to_bytes(state: &[u64; 25]) -> [u8; 200] {
+    let mut bytes = [0u8; 200];
+    for (chunk, word) in bytes.chunks_mut(8).zip(state.iter()) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    bytes
+}
+
+fn from_bytes(bytes: &[u8; 200]) -> [u64; 25] {
+    let mut state = [0u64; 25];
+    for (word, chunk) in state.iter_mut().zip(bytes.chunks(8)) {
+        *word = read_u64(chunk);
+    }
+    state
+}
+
+/// Returns the 16-byte aligned scratchpad offset addressed by the low 64 bits of a block.
+fn address(block: &[u64; 2]) -> usize {
+    (block[0] as usize) & (MEMORY - 16)
+}
+
+fn read_block(scratchpad: &[u8], offset: usize) -> [u64; 2] {
+    [read_u64(&scratchpad[offset..]), read_u64(&scratchpad[offset + 8..])]
+}
+
+fn write_block(scratchpad: &mut [u8], offset: usize, block: &[u64; 2]) {
+    scratchpad[offset..offset + 8].copy_from_slice(&block[0].to_le_bytes());
+    scratchpad[offset + 8..offset + 16].copy_from_slice(&block[1].to_le_bytes());
+}
+
+/// Returns a single AES round of a block given as two little-endian words.
+fn aes_round(block: &[u64; 2], round_key: &[u64; 2]) -> [u64; 2] {
+    let columns = |words: &[u64; 2]| {
+        [
+            words[0] as u32,
+            (words[0] >> 32) as u32,
+            words[1] as u32,
+            (words[1] >> 32) as u32,
+        ]
+    };
+    let output = round(&columns(block), &columns(round_key));
+    [
+        output[0] as u64 | (output[1] as u64) << 32,
+        output[2] as u64 | (output[3] as u64) << 32,
+    ]
+}
+
+/// Returns the CryptoNight hash (variant 0) of the data, as computed by Monero `cn_slow_hash`.
+pub fn cn_slow_hash(data: &[u8]) -> [u8; 32] {
+    let state = keccak1600(data);
+    let mut bytes = to_bytes(&state);
+
+    // Fill the scratchpad with the repeated AES encryption of bytes 64 to 192 of the state.
+    let mut scratchpad = vec![0u8; MEMORY];
+    let key = AesKey::new(&bytes[0..32]);
+    let mut text = [0u8; 128];
+    text.copy_from_slice(&bytes[64..192]);
+    for chunk in scratchpad.chunks_mut(128) {
+        text.chunks_mut(16).for_each(|block| key.encrypt(block));
+        chunk.copy_from_slice(&text);
+    }
+
+    // The memory-hard loop alternates an AES round and a 64-bit multiplication at addresses
+    // derived from the previous results.
+    let mut a = [state[0] ^ state[4], state[1] ^ state[5]];
+    let mut b = [state[2] ^ state[6], state[3] ^ state[7]];
+    for _ in 0..ITERATIONS / 2 {
+        let j = address(&a);
+        let c = aes_round(&read_block(&scratchpad, j), &a);
+        write_block(&mut scratchpad, j, &[c[0] ^ b[0], c[1] ^ b[1]]);
+
+        let j = address(&c);
+        let d = read_block(&scratchpad, j);
+        let product = (c[0] as u128) * (d[0] as u128);
+        a[0] = a[0].wrapping_add((product >> 64) as u64);
+        a[1] = a[1].wrapping_add(product as u64);
+        write_block(&mut scratchpad, j, &a);
+        a = [a[0] ^ d[0], a[1] ^ d[1]];
+        b = c;
+    }
+
+    // Fold the scratchpad back into bytes 64 to 192 of the state with the second AES key.
+    let key = AesKey::new(&bytes[32..64]);
+    text.copy_from_slice(&bytes[64..192]);
+    for chunk in scratchpad.chunks(128) {
+        for (block, scratch) in text.chunks_mut(16).zip(chunk.chunks(16)) {
+            block.iter_mut().zip(scratch.iter()).for_each(|(byte, x)| *byte ^= x);
+            key.encrypt(block);
+        }
+    }
+    bytes[64..192].copy_from_slice(&text);
+
+    let mut state = from_bytes(&bytes);
+    keccakf(&mut state);
+    let bytes = to_bytes(&state);
+
+    match bytes[0] & 3 {
+        0 => blake256(&bytes),
+        1 => groestl256(&bytes),
+        2 => jh256(&bytes),
+        _ => skein512_256(&bytes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (data, hash)
+    const HASHES: [(&str, &str); 5] = [
+        (
+            "6465206f6d6e69627573206475626974616e64756d",
+            "2f8e3df40bd11f9ac90c743ca8e32bb391da4fb98612aa3b6cdc639ee00b31f5",
+        ),
+        (
+            "6162756e64616e732063617574656c61206e6f6e206e6f636574",
+            "722fa8ccd594d40e4a41f3822734304c8d5eff7e1b528408e2229da38ba553c4",
+        ),
+        (
+            "63617665617420656d70746f72",
+            "bbec2cacf69866a8e740380fe7b818fc78f8571221742d729d9d02d7f8989b87",
+        ),
+        (
+            "6578206e6968696c6f206e6968696c20666974",
+            "b1257de4efc5ce28c6b40ceb1c6c8f812a64634eb3e81c5220bee9b2b76a6f05",
+        ),
+        (
+            "5468697320697320612074657374",
+            "a084f01d1437a09c6985401b60d43554ae105802c5f5d8a9b3253649c0be6605",
+        ),
+    ];
+
+    #[test]
+    fn hash() {
+        HASHES.iter().for_each(|(data, expected)| {
+            assert_eq!(*expected, hex::encode(cn_slow_hash(&hex::decode(data).unwrap())));
+        });
+    }
+}
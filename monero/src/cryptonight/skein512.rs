@@ -0,0 +1,143 @@
+//! Skein-512-256, Skein with the 512-bit Threefish block cipher and a 256-bit output.
+
+const ROUNDS: usize = 72;
+
+/// The key schedule parity constant.
+const C240: u64 = 0x1bd1_1bda_a9fc_1a22;
+
+const ROTATIONS: [[u32; 4]; 8] = [
+    [46, 36, 19, 37],
+    [33, 27, 14, 42],
+    [17, 49, 36, 39],
+    [44, 9, 54, 56],
+    [39, 30, 34, 24],
+    [13, 50, 10, 17],
+    [25, 29, 39, 43],
+    [8, 35, 56, 22],
+];
+
+const PERMUTATION: [usize; 8] = [2, 1, 4, 7, 6, 5, 0, 3];
+
+/// The UBI block types.
+const TYPE_CONFIG: u64 = 4;
+const TYPE_MESSAGE: u64 = 48;
+const TYPE_OUTPUT: u64 = 63;
+
+/// Returns the Threefish-512 encryption of a block with the key and tweak.
+fn threefish(key: &[u64; 8], tweak: &[u64; 2], block: &[u64; 8]) -> [u64; 8] {
+    let mut k = [0u64; 9];
+    k[..8].copy_from_slice(key);
+    k[8] = key.iter().fold(C240, |parity, word| parity ^ word);
+    let t = [tweak[0], tweak[1], tweak[0] ^ tweak[1]];
+
+    let subkey = |s: usize, i: usize| -> u64 {
+        let word = k[(s + i) % 9];
+        match i {
+            5 => word.wrapping_add(t[s % 3]),
+            6 => word.wrapping_add(t[(s + 1) % 3]),
+            7 => word.wrapping_add(s as u64),
+            _ => word,
+        }
+    };
+
+    let mut v = *block;
+    for d in 0..ROUNDS {
+        if d % 4 == 0 {
+            for (i, word) in v.iter_mut().enumerate() {
+                *word = word.wrapping_add(subkey(d / 4, i));
+            }
+        }
+        for j in 0..4 {
+            let (x0, x1) = (v[2 * j], v[2 * j + 1]);
+            v[2 * j] = x0.wrapping_add(x1);
+            v[2 * j + 1] = x1.rotate_left(ROTATIONS[d % 8][j]) ^ v[2 * j];
+        }
+        let mixed = v;
+        for (i, word) in v.iter_mut().enumerate() {
+            *word = mixed[PERMUTATION[i]];
+        }
+    }
+    for (i, word) in v.iter_mut().enumerate() {
+        *word = word.wrapping_add(subkey(ROUNDS / 4, i));
+    }
+    v
+}
+
+/// Returns the chain value after the unique block iteration of a message of the given type.
+fn ubi(chain: &[u64; 8], message: &[u8], block_type: u64) -> [u64; 8] {
+    let mut chain = *chain;
+    let blocks = match message.len() {
+        0 => 1,
+        length => (length + 63) / 64,
+    };
+
+    for i in 0..blocks {
+        let start = 64 * i;
+        let end = message.len().min(start + 64);
+        let mut bytes = [0u8; 64];
+        bytes[..end - start].copy_from_slice(&message[start..end]);
+
+        let mut block = [0u64; 8];
+        for (word, chunk) in block.iter_mut().zip(bytes.chunks(8)) {
+            let mut word_bytes = [0u8; 8];
+            word_bytes.copy_from_slice(chunk);
+            *word = u64::from_le_bytes(word_bytes);
+        }
+
+        // The tweak holds the bytes processed so far, the type and the first and final flags.
+        let mut flags = block_type << 56;
+        if i == 0 {
+            flags |= 1 << 62;
+        }
+        if i == blocks - 1 {
+            flags |= 1 << 63;
+        }
+
+        let encrypted = threefish(&chain, &[end as u64, flags], &block);
+        for j in 0..8 {
+            chain[j] = encrypted[j] ^ block[j];
+        }
+    }
+    chain
+}
+
+/// Returns the Skein-512-256 hash of the data.
+pub fn skein512_256(data: &[u8]) -> [u8; 32] {
+    // The configuration is the schema "SHA3", version 1 and the output length in bits.
+    let mut config = b"SHA3".to_vec();
+    config.extend(&1u16.to_le_bytes());
+    config.extend(&[0u8; 2]);
+    config.extend(&256u64.to_le_bytes());
+    config.extend(&[0u8; 16]);
+
+    let chain = ubi(&[0u64; 8], &config, TYPE_CONFIG);
+    let chain = ubi(&chain, data, TYPE_MESSAGE);
+    let output = ubi(&chain, &0u64.to_le_bytes(), TYPE_OUTPUT);
+
+    let mut hash = [0u8; 32];
+    for (bytes, word) in hash.chunks_mut(8).zip(output.iter()) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (data, hash)
+    const HASHES: [(&str, &str); 2] = [
+        ("", "39ccc4554a8b31853b9de7a1fe638a24cce6b35a55f2431009e18780335d2621"),
+        (
+            "54686520717569636b2062726f776e20666f78206a756d7073206f76657220746865206c617a7920646f67",
+            "b3250457e05d3060b1a4bbc1428bc75a3f525ca389aeab96cfa34638d96e492a",
+        ),
+    ];
+
+    #[test]
+    fn hash() {
+        HASHES.iter().for_each(|(data, expected)| {
+            assert_eq!(*expected, hex::encode(skein512_256(&hex::decode(data).unwrap())));
+        });
+    }
+}
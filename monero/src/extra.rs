@@ -0,0 +1,451 @@
+use crate::ringct::*;
+use wagyu_model::TransactionError;
+
+use curve25519_dalek::scalar::Scalar;
+use tiny_keccak::keccak256;
+
+/// The tag of the zero padding at the end of the extra field
+const TX_EXTRA_TAG_PADDING: u8 = 0x00;
+
+/// The tag of the transaction public key in the extra field
+const TX_EXTRA_TAG_PUBKEY: u8 = 0x01;
+
+/// The tag of the nonce in the extra field, which carries the payment id
+const TX_EXTRA_NONCE: u8 = 0x02;
+
+/// The tag of the merge mining data in the extra field
+const TX_EXTRA_MERGE_MINING_TAG: u8 = 0x03;
+
+/// The tag of the additional transaction public keys in the extra field
+const TX_EXTRA_TAG_ADDITIONAL_PUBKEYS: u8 = 0x04;
+
+/// The tag of the data appended to the extra field by the MinerGate pool
+const TX_EXTRA_MYSTERIOUS_MINERGATE_TAG: u8 = 0xde;
+
+/// The maximum length of the padding, including its tag, and of the nonce
+const TX_EXTRA_PADDING_MAX_COUNT: usize = 255;
+const TX_EXTRA_NONCE_MAX_COUNT: usize = 255;
+
+/// The first byte of a nonce with an unencrypted 32-byte payment id
+const TX_EXTRA_NONCE_PAYMENT_ID: u8 = 0x00;
+
+/// The first byte of a nonce with an encrypted 8-byte payment id
+const TX_EXTRA_NONCE_ENCRYPTED_PAYMENT_ID: u8 = 0x01;
+
+/// The byte appended to the key derivation to hash the payment id key stream
+const ENCRYPTED_PAYMENT_ID_TAIL: u8 = 0x8d;
+
+/// Represents the nonce of the extra field of a Monero transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoneroExtraNonce {
+    /// An unencrypted 32-byte payment id, which wallets no longer send since v0.15
+    PaymentId([u8; 32]),
+    /// The 8-byte payment id of an integrated address, encrypted for its recipient
+    EncryptedPaymentId([u8; 8]),
+    /// Arbitrary data, such as the extra nonce of a mining pool
+    Data(Vec<u8>),
+}
+
+impl MoneroExtraNonce {
+    fn from_bytes(nonce: &[u8]) -> Self {
+        match (nonce.first(), nonce.len()) {
+            (Some(&TX_EXTRA_NONCE_PAYMENT_ID), 33) => {
+                let mut payment_id = [0u8; 32];
+                payment_id.copy_from_slice(&nonce[1..]);
+                MoneroExtraNonce::PaymentId(payment_id)
+            }
+            (Some(&TX_EXTRA_NONCE_ENCRYPTED_PAYMENT_ID), 9) => {
+                let mut payment_id = [0u8; 8];
+                payment_id.copy_from_slice(&nonce[1..]);
+                MoneroExtraNonce::EncryptedPaymentId(payment_id)
+            }
+            _ => MoneroExtraNonce::Data(nonce.to_vec()),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            MoneroExtraNonce::PaymentId(payment_id) => [&[TX_EXTRA_NONCE_PAYMENT_ID], &payment_id[..]].concat(),
+            MoneroExtraNonce::EncryptedPaymentId(payment_id) => {
+                [&[TX_EXTRA_NONCE_ENCRYPTED_PAYMENT_ID], &payment_id[..]].concat()
+            }
+            MoneroExtraNonce::Data(data) => data.clone(),
+        }
+    }
+}
+
+/// Represents a field of the extra field of a Monero transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoneroExtraField {
+    /// Zero padding of the given length including its tag, which ends the extra field
+    Padding(usize),
+    /// The transaction public key
+    PublicKey([u8; 32]),
+    /// The nonce, which carries the payment id
+    Nonce(MoneroExtraNonce),
+    /// The depth and Merkle root of the merge mined chains of a block
+    MergeMiningTag(u64, [u8; 32]),
+    /// The additional transaction public keys of each output, used by transactions to subaddresses
+    AdditionalPublicKeys(Vec<[u8; 32]>),
+    /// The data appended by the MinerGate pool
+    MysteriousMinergate(Vec<u8>),
+}
+
+/// Represents the extra field of a Monero transaction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MoneroTransactionExtra {
+    /// The fields in order
+    pub fields: Vec<MoneroExtraField>,
+}
+
+impl MoneroTransactionExtra {
+    /// Returns the fields of an extra field, which must all be known and well formed.
+    pub fn from_bytes(extra: &[u8]) -> Result<Self, TransactionError> {
+        let mut fields = vec![];
+        let mut offset = 0;
+        while offset < extra.len() {
+            fields.push(Self::read_field(extra, &mut offset)?);
+        }
+        Ok(Self { fields })
+    }
+
+    /// Returns the fields of an extra field up to the first one that is unknown or malformed,
+    /// as Monero reads them (Monero `parse_tx_extra`).
+    pub fn from_bytes_partial(extra: &[u8]) -> Self {
+        let mut fields = vec![];
+        let mut offset = 0;
+        while offset < extra.len() {
+            match Self::read_field(extra, &mut offset) {
+                Ok(field) => fields.push(field),
+                Err(_) => break,
+            };
+        }
+        Self { fields }
+    }
+
+    /// Returns the serialized extra field.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, TransactionError> {
+        let mut extra = vec![];
+        for (i, field) in self.fields.iter().enumerate() {
+            match field {
+                MoneroExtraField::Padding(length) => {
+                    if *length == 0 || *length > TX_EXTRA_PADDING_MAX_COUNT || i != self.fields.len() - 1 {
+                        return Err(TransactionError::Message(format!(
+                            "invalid padding of {} bytes",
+                            length
+                        )));
+                    }
+                    extra.extend(vec![TX_EXTRA_TAG_PADDING; *length]);
+                }
+                MoneroExtraField::PublicKey(key) => {
+                    extra.push(TX_EXTRA_TAG_PUBKEY);
+                    extra.extend(key);
+                }
+                MoneroExtraField::Nonce(nonce) => {
+                    let nonce = nonce.to_bytes();
+                    if nonce.len() > TX_EXTRA_NONCE_MAX_COUNT {
+                        return Err(TransactionError::Message(format!(
+                            "invalid nonce of {} bytes",
+                            nonce.len()
+                        )));
+                    }
+                    extra.push(TX_EXTRA_NONCE);
+                    write_varint(&mut extra, nonce.len() as u64);
+                    extra.extend(nonce);
+                }
+                MoneroExtraField::MergeMiningTag(depth, merkle_root) => {
+                    let mut tag = vec![];
+                    write_varint(&mut tag, *depth);
+                    tag.extend(merkle_root);
+                    extra.push(TX_EXTRA_MERGE_MINING_TAG);
+                    write_varint(&mut extra, tag.len() as u64);
+                    extra.extend(tag);
+                }
+                MoneroExtraField::AdditionalPublicKeys(keys) => {
+                    extra.push(TX_EXTRA_TAG_ADDITIONAL_PUBKEYS);
+                    write_varint(&mut extra, keys.len() as u64);
+                    keys.iter().for_each(|key| extra.extend(key));
+                }
+                MoneroExtraField::MysteriousMinergate(data) => {
+                    extra.push(TX_EXTRA_MYSTERIOUS_MINERGATE_TAG);
+                    write_varint(&mut extra, data.len() as u64);
+                    extra.extend(data);
+                }
+            };
+        }
+        Ok(extra)
+    }
+
+    /// Returns the transaction public keys.
+    pub fn public_keys(&self) -> Vec<[u8; 32]> {
+        self.fields
+            .iter()
+            .filter_map(|field| match field {
+                MoneroExtraField::PublicKey(key) => Some(*key),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the additional transaction public keys of each output, if any.
+    pub fn additional_public_keys(&self) -> Vec<[u8; 32]> {
+        self.fields
+            .iter()
+            .rev()
+            .find_map(|field| match field {
+                MoneroExtraField::AdditionalPublicKeys(keys) => Some(keys.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the nonce, if any.
+    pub fn nonce(&self) -> Option<&MoneroExtraNonce> {
+        self.fields.iter().find_map(|field| match field {
+            MoneroExtraField::Nonce(nonce) => Some(nonce),
+            _ => None,
+        })
+    }
+
+    /// Returns the payment id given the private view key of the recipient, decrypting the payment id
+    /// of an integrated address with the first transaction public key, or returns `None`.
+    pub fn to_payment_id(&self, private_view_key: &[u8; 32]) -> Result<Option<Vec<u8>>, TransactionError> {
+        match self.nonce() {
+            Some(MoneroExtraNonce::PaymentId(payment_id)) => Ok(Some(payment_id.to_vec())),
+            Some(MoneroExtraNonce::EncryptedPaymentId(payment_id)) => match self.public_keys().first() {
+                Some(tx_public_key) => Ok(Some(
+                    decrypt_payment_id(payment_id, tx_public_key, private_view_key)?.to_vec(),
+                )),
+                None => Err(TransactionError::Message("missing transaction public key".into())),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    fn read_field(extra: &[u8], offset: &mut usize) -> Result<MoneroExtraField, TransactionError> {
+        let read_key = |offset: &mut usize| -> Result<[u8; 32], TransactionError> {
+            let mut key = [0u8; 32];
+            key.copy_from_slice(take(extra, offset, 32)?);
+            Ok(key)
+        };
+        let read_count = |offset: &mut usize| -> Result<u64, TransactionError> {
+            let (value, size) = read_varint(extra.get(*offset..).unwrap_or(&[]))?;
+            *offset += size;
+            Ok(value)
+        };
+        let read_data = |offset: &mut usize| -> Result<&[u8], TransactionError> {
+            let length = read_count(offset)?;
+            Ok(take(extra, offset, length as usize)?)
+        };
+
+        let tag = take(extra, offset, 1)?[0];
+        Ok(match tag {
+            TX_EXTRA_TAG_PADDING => {
+                let padding = &extra[*offset - 1..];
+                if padding.len() > TX_EXTRA_PADDING_MAX_COUNT || padding.iter().any(|byte| *byte != 0) {
+                    return Err(TransactionError::Message("invalid padding".into()));
+                }
+                *offset = extra.len();
+                MoneroExtraField::Padding(padding.len())
+            }
+            TX_EXTRA_TAG_PUBKEY => MoneroExtraField::PublicKey(read_key(offset)?),
+            TX_EXTRA_NONCE => {
+                let nonce = read_data(offset)?;
+                if nonce.len() > TX_EXTRA_NONCE_MAX_COUNT {
+                    return Err(TransactionError::Message(format!(
+                        "invalid nonce of {} bytes",
+                        nonce.len()
+                    )));
+                }
+                MoneroExtraField::Nonce(MoneroExtraNonce::from_bytes(nonce))
+            }
+            TX_EXTRA_MERGE_MINING_TAG => {
+                let tag = read_data(offset)?;
+                let (depth, size) = read_varint(tag)?;
+                if tag.len() != size + 32 {
+                    return Err(TransactionError::Message("invalid merge mining tag".into()));
+                }
+                let mut merkle_root = [0u8; 32];
+                merkle_root.copy_from_slice(&tag[size..]);
+                MoneroExtraField::MergeMiningTag(depth, merkle_root)
+            }
+            TX_EXTRA_TAG_ADDITIONAL_PUBKEYS => {
+                let count = read_count(offset)?;
+                let keys: Result<Vec<[u8; 32]>, TransactionError> = (0..count).map(|_| read_key(offset)).collect();
+                MoneroExtraField::AdditionalPublicKeys(keys?)
+            }
+            TX_EXTRA_MYSTERIOUS_MINERGATE_TAG => MoneroExtraField::MysteriousMinergate(read_data(offset)?.to_vec()),
+            _ => return Err(TransactionError::Message(format!("unknown extra field tag {}", tag))),
+        })
+    }
+}
+
+/// Returns the payment id of an integrated address encrypted for its recipient, given the public view key
+/// of the recipient and the transaction private key (Monero `encrypt_payment_id`).
+pub fn encrypt_payment_id(
+    payment_id: &[u8; 8],
+    public_key: &[u8; 32],
+    secret_key: &[u8; 32],
+) -> Result<[u8; 8], TransactionError> {
+    let derivation = (Scalar::from_bits(*secret_key) * read_point(public_key)?).mul_by_cofactor();
+    let mut data = derivation.compress().to_bytes().to_vec();
+    data.push(ENCRYPTED_PAYMENT_ID_TAIL);
+    let key_stream = keccak256(&data);
+
+    let mut encrypted = *payment_id;
+    encrypted
+        .iter_mut()
+        .zip(key_stream.iter())
+        .for_each(|(byte, key)| *byte ^= key);
+    Ok(encrypted)
+}
+
+/// Returns the payment id of an integrated address decrypted by its recipient, given the transaction
+/// public key and the private view key of the recipient (Monero `decrypt_payment_id`).
+pub fn decrypt_payment_id(
+    encrypted_payment_id: &[u8; 8],
+    public_key: &[u8; 32],
+    secret_key: &[u8; 32],
+) -> Result<[u8; 8], TransactionError> {
+    encrypt_payment_id(encrypted_payment_id, public_key, secret_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::constants::ED25519_BASEPOINT_TABLE;
+
+    /// Returns serialized extra fields and their fields.
+    fn extras() -> Vec<(String, Vec<MoneroExtraField>)> {
+        vec![
+            (
+                format!("01{}", "11".repeat(32)),
+                vec![MoneroExtraField::PublicKey([0x11; 32])],
+            ),
+            (
+                format!("01{}0401{}0209010102030405060708", "11".repeat(32), "22".repeat(32)),
+                vec![
+                    MoneroExtraField::PublicKey([0x11; 32]),
+                    MoneroExtraField::AdditionalPublicKeys(vec![[0x22; 32]]),
+                    MoneroExtraField::Nonce(MoneroExtraNonce::EncryptedPaymentId([1, 2, 3, 4, 5, 6, 7, 8])),
+                ],
+            ),
+            (
+                format!(
+                    "022100{}01{}032103{}de02aabb000000",
+                    "44".repeat(32),
+                    "11".repeat(32),
+                    "33".repeat(32)
+                ),
+                vec![
+                    MoneroExtraField::Nonce(MoneroExtraNonce::PaymentId([0x44; 32])),
+                    MoneroExtraField::PublicKey([0x11; 32]),
+                    MoneroExtraField::MergeMiningTag(3, [0x33; 32]),
+                    MoneroExtraField::MysteriousMinergate(vec![0xaa, 0xbb]),
+                    MoneroExtraField::Padding(3),
+                ],
+            ),
+            (
+                "0203abcdef".into(),
+                vec![MoneroExtraField::Nonce(MoneroExtraNonce::Data(vec![0xab, 0xcd, 0xef]))],
+            ),
+        ]
+    }
+
+    #[test]
+    fn from_bytes_and_to_bytes() {
+        extras().into_iter().for_each(|(extra, fields)| {
+            let extra = hex::decode(extra).unwrap();
+            let parsed = MoneroTransactionExtra::from_bytes(&extra).unwrap();
+            assert_eq!(fields, parsed.fields);
+            assert_eq!(parsed, MoneroTransactionExtra::from_bytes_partial(&extra));
+            assert_eq!(extra, parsed.to_bytes().unwrap());
+        });
+    }
+
+    #[test]
+    fn from_bytes_partial() {
+        // Monero keeps the fields before an unknown tag, such as the transaction public key of this extra field.
+        let extra = hex::decode(format!("01{}050102", "11".repeat(32))).unwrap();
+        assert!(MoneroTransactionExtra::from_bytes(&extra).is_err());
+
+        let parsed = MoneroTransactionExtra::from_bytes_partial(&extra);
+        assert_eq!(vec![[0x11; 32]], parsed.public_keys());
+        assert!(parsed.additional_public_keys().is_empty());
+        assert_eq!(None, parsed.nonce());
+    }
+
+    #[test]
+    fn encrypt_and_decrypt_payment_id() {
+        let private_view_key = hash_to_scalar(b"view key");
+        let public_view_key = (&private_view_key * &ED25519_BASEPOINT_TABLE).compress().to_bytes();
+        let tx_key = hash_to_scalar(b"tx key");
+        let tx_public_key = (&tx_key * &ED25519_BASEPOINT_TABLE).compress().to_bytes();
+        let payment_id = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0];
+
+        let encrypted = encrypt_payment_id(&payment_id, &public_view_key, &tx_key.to_bytes()).unwrap();
+        assert_ne!(payment_id, encrypted);
+        assert_eq!(
+            payment_id,
+            decrypt_payment_id(&encrypted, &tx_public_key, &private_view_key.to_bytes()).unwrap()
+        );
+
+        let extra = MoneroTransactionExtra {
+            fields: vec![
+                MoneroExtraField::PublicKey(tx_public_key),
+                MoneroExtraField::Nonce(MoneroExtraNonce::EncryptedPaymentId(encrypted)),
+            ],
+        };
+        assert_eq!(
+            Some(payment_id.to_vec()),
+            extra.to_payment_id(&private_view_key.to_bytes()).unwrap()
+        );
+
+        // Unencrypted payment ids are read as they are.
+        let extra = MoneroTransactionExtra {
+            fields: vec![MoneroExtraField::Nonce(MoneroExtraNonce::PaymentId([0x44; 32]))],
+        };
+        assert_eq!(Some(vec![0x44; 32]), extra.to_payment_id(&[0u8; 32]).unwrap());
+    }
+
+    mod invalid {
+        use super::*;
+
+        const INVALID_EXTRAS: [&str; 6] = ["0111", "000001", "0205", "0401", "03020102", "05"];
+
+        #[test]
+        fn from_invalid_bytes() {
+            INVALID_EXTRAS.iter().for_each(|extra| {
+                assert!(MoneroTransactionExtra::from_bytes(&hex::decode(extra).unwrap()).is_err());
+            });
+
+            let long_padding = vec![0u8; TX_EXTRA_PADDING_MAX_COUNT + 1];
+            assert!(MoneroTransactionExtra::from_bytes(&long_padding).is_err());
+        }
+
+        #[test]
+        fn to_invalid_bytes() {
+            let invalid_fields = vec![
+                vec![MoneroExtraField::Padding(0)],
+                vec![MoneroExtraField::Padding(TX_EXTRA_PADDING_MAX_COUNT + 1)],
+                vec![MoneroExtraField::Padding(1), MoneroExtraField::PublicKey([0x11; 32])],
+                vec![MoneroExtraField::Nonce(MoneroExtraNonce::Data(vec![
+                    0;
+                    TX_EXTRA_NONCE_MAX_COUNT
+                        + 1
+                ]))],
+            ];
+            invalid_fields.into_iter().for_each(|fields| {
+                assert!(MoneroTransactionExtra { fields }.to_bytes().is_err());
+            });
+        }
+
+        #[test]
+        fn missing_transaction_public_key() {
+            let extra = MoneroTransactionExtra {
+                fields: vec![MoneroExtraField::Nonce(MoneroExtraNonce::EncryptedPaymentId([0u8; 8]))],
+            };
+            assert!(extra.to_payment_id(&[0u8; 32]).is_err());
+        }
+    }
+}
@@ -24,6 +24,8 @@ impl MoneroFormat {
     }
 
     /// Returns the format of the given address.
+    /// The indices of a subaddress are unknown without the private view key of its wallet,
+    /// and are resolved with a `MoneroSubaddressTable`.
     pub fn from_address(address: &[u8]) -> Result<Self, AddressError> {
         match address[0] {
             18 | 24 | 53 => Ok(MoneroFormat::Standard),
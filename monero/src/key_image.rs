@@ -0,0 +1,466 @@
+use crate::chacha::{decrypt_with_secret_key, encrypt_with_secret_key, DEFAULT_KDF_ROUNDS};
+use crate::format::MoneroFormat;
+use crate::network::MoneroNetwork;
+use crate::one_time_key::{OneTimeKey, OneTimeKeyError};
+use crate::private_key::MoneroPrivateKey;
+use crate::ringct::{read_point, RingCtError, RingSignature};
+use crate::scanner::MoneroOwnedOutput;
+use wagyu_model::PrivateKeyError;
+
+use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, scalar::Scalar};
+use rand::Rng;
+use std::marker::PhantomData;
+
+/// The plaintext prefix of the key image files of `monero-wallet-cli export_key_images`
+pub const KEY_IMAGE_EXPORT_FILE_MAGIC: &[u8] = b"Monero key image export\x03";
+
+#[derive(Debug, Fail)]
+pub enum KeyImageError {
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "invalid byte length: {}", _0)]
+    InvalidByteLength(usize),
+
+    #[fail(display = "invalid key image file magic")]
+    InvalidMagic,
+
+    #[fail(display = "invalid signature of key image {}", _0)]
+    InvalidSignature(String),
+
+    #[fail(display = "key images are for a different account with public spend key {}", _0)]
+    MismatchedAccount(String),
+
+    #[fail(display = "missing output {} of the key images", _0)]
+    MissingOutput(usize),
+
+    #[fail(display = "{}", _0)]
+    OneTimeKeyError(OneTimeKeyError),
+
+    #[fail(display = "{}", _0)]
+    PrivateKeyError(PrivateKeyError),
+
+    #[fail(display = "{}", _0)]
+    RingCtError(RingCtError),
+}
+
+impl From<OneTimeKeyError> for KeyImageError {
+    fn from(error: OneTimeKeyError) -> Self {
+        KeyImageError::OneTimeKeyError(error)
+    }
+}
+
+impl From<PrivateKeyError> for KeyImageError {
+    fn from(error: PrivateKeyError) -> Self {
+        KeyImageError::PrivateKeyError(error)
+    }
+}
+
+impl From<RingCtError> for KeyImageError {
+    fn from(error: RingCtError) -> Self {
+        KeyImageError::RingCtError(error)
+    }
+}
+
+/// Represents the key image of an output with a ring signature by its one time private key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroSignedKeyImage {
+    /// The key image
+    pub key_image: [u8; 32],
+    /// The ring signature of the key image by the output, a ring of size one
+    pub signature: [u8; 64],
+}
+
+impl MoneroSignedKeyImage {
+    /// Returns the signed key image of an output received by the private key.
+    pub fn new<N: MoneroNetwork, R: Rng>(
+        private_key: &MoneroPrivateKey<N>,
+        output: &MoneroOwnedOutput,
+        rng: &mut R,
+    ) -> Result<Self, KeyImageError> {
+        let (secret_key, key_image) = one_time_private_key(private_key, output)?;
+
+        // The signed message is the key image itself (Monero `wallet2::export_key_images`).
+        let public_key = read_point(&output.public_key)?;
+        let (signature, _) = RingSignature::sign(&key_image, &[public_key], 0, &secret_key, rng)?;
+
+        let mut signed_key_image = Self {
+            key_image,
+            signature: [0u8; 64],
+        };
+        signed_key_image
+            .signature
+            .copy_from_slice(&signature.signatures[0].to_bytes());
+        Ok(signed_key_image)
+    }
+
+    /// Verifies that the key image is signed by the one time private key of the output public key.
+    pub fn verify(&self, public_key: &[u8; 32]) -> Result<(), KeyImageError> {
+        let signature = RingSignature::read(1, &self.signature)?;
+        let key_image = read_point(&self.key_image)?;
+        match signature.verify(&self.key_image, &[read_point(public_key)?], &key_image) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(KeyImageError::InvalidSignature(hex::encode(self.key_image))),
+        }
+    }
+}
+
+/// Returns the one time private key and the key image of an output received by the private key.
+pub(crate) fn one_time_private_key<N: MoneroNetwork>(
+    private_key: &MoneroPrivateKey<N>,
+    output: &MoneroOwnedOutput,
+) -> Result<(Scalar, [u8; 32]), KeyImageError> {
+    // The one time private key of an output received on a subaddress is offset by its secret.
+    let (major, minor) = output.subaddress;
+    let private_key = MoneroPrivateKey::<N>::from_private_keys(
+        &hex::encode(private_key.to_private_spend_key()),
+        &hex::encode(private_key.to_private_view_key()),
+        &MoneroFormat::Subaddress(major, minor),
+    )?;
+
+    let one_time_key = OneTimeKey::<N>::from(&output.public_key, &output.tx_pub_key);
+    let secret_key = Scalar::from_bytes_mod_order(one_time_key.to_private(&private_key, output.index)?);
+    let key_image = one_time_key.to_key_image(&private_key, output.index)?;
+    Ok((secret_key, key_image))
+}
+
+/// Represents the signed key images of a wallet's outputs, as exported by a wallet holding
+/// the private spend key for import into a view-only wallet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroKeyImages<N: MoneroNetwork> {
+    /// The index of the wallet output of the first key image
+    offset: u32,
+    /// The public spend key of the wallet
+    public_spend_key: [u8; 32],
+    /// The public view key of the wallet
+    public_view_key: [u8; 32],
+    /// The signed key images of the outputs from the offset onward
+    key_images: Vec<MoneroSignedKeyImage>,
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: MoneroNetwork> MoneroKeyImages<N> {
+    /// Returns the signed key images of the wallet outputs from the offset onward,
+    /// given the outputs in the order they were received.
+    pub fn new<R: Rng>(
+        private_key: &MoneroPrivateKey<N>,
+        outputs: &[MoneroOwnedOutput],
+        offset: u32,
+        rng: &mut R,
+    ) -> Result<Self, KeyImageError> {
+        let exported = outputs
+            .get(offset as usize..)
+            .ok_or(KeyImageError::MissingOutput(offset as usize))?;
+        let key_images = exported
+            .iter()
+            .map(|output| MoneroSignedKeyImage::new(private_key, output, rng))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let to_public_key = |secret_key: [u8; 32]| {
+            (&Scalar::from_bytes_mod_order(secret_key) * &ED25519_BASEPOINT_TABLE)
+                .compress()
+                .to_bytes()
+        };
+
+        Ok(Self {
+            offset,
+            public_spend_key: to_public_key(private_key.to_private_spend_key()),
+            public_view_key: to_public_key(private_key.to_private_view_key()),
+            key_images,
+            _network: PhantomData,
+        })
+    }
+
+    /// Verifies the signature of each key image by its output, given the wallet outputs
+    /// in the order they were received.
+    pub fn verify(&self, outputs: &[MoneroOwnedOutput]) -> Result<(), KeyImageError> {
+        self.key_images.iter().enumerate().try_for_each(|(i, key_image)| {
+            let index = self.offset as usize + i;
+            match outputs.get(index) {
+                Some(output) => key_image.verify(&output.public_key),
+                None => Err(KeyImageError::MissingOutput(index)),
+            }
+        })
+    }
+
+    /// Returns the contents of a key image file, encrypted and signed with the private view key
+    /// (Monero `wallet2::export_key_images`).
+    pub fn to_bytes<R: Rng>(&self, private_view_key: &[u8; 32], rng: &mut R) -> Vec<u8> {
+        let mut data = self.offset.to_le_bytes().to_vec();
+        data.extend(&self.public_spend_key);
+        data.extend(&self.public_view_key);
+        for key_image in &self.key_images {
+            data.extend(&key_image.key_image);
+            data.extend(key_image.signature.iter());
+        }
+
+        let mut bytes = KEY_IMAGE_EXPORT_FILE_MAGIC.to_vec();
+        bytes.extend(encrypt_with_secret_key(
+            &data,
+            private_view_key,
+            DEFAULT_KDF_ROUNDS,
+            rng,
+        ));
+        bytes
+    }
+
+    /// Returns the key images of a key image file given the private view key and the public
+    /// spend key of the wallet (Monero `wallet2::import_key_images`).
+    pub fn from_bytes(
+        bytes: &[u8],
+        private_view_key: &[u8; 32],
+        public_spend_key: &[u8; 32],
+    ) -> Result<Self, KeyImageError> {
+        if !bytes.starts_with(KEY_IMAGE_EXPORT_FILE_MAGIC) {
+            return Err(KeyImageError::InvalidMagic);
+        }
+        let data = decrypt_with_secret_key(
+            &bytes[KEY_IMAGE_EXPORT_FILE_MAGIC.len()..],
+            private_view_key,
+            DEFAULT_KDF_ROUNDS,
+        )?;
+
+        // The data is the offset and the public keys, followed by each key image and its signature.
+        const HEADER_LENGTH: usize = 4 + 2 * 32;
+        const RECORD_LENGTH: usize = 32 + 64;
+        if data.len() < HEADER_LENGTH || (data.len() - HEADER_LENGTH) % RECORD_LENGTH != 0 {
+            return Err(KeyImageError::InvalidByteLength(data.len()));
+        }
+
+        let mut offset = [0u8; 4];
+        offset.copy_from_slice(&data[0..4]);
+        let mut key_images = Self {
+            offset: u32::from_le_bytes(offset),
+            public_spend_key: [0u8; 32],
+            public_view_key: [0u8; 32],
+            key_images: vec![],
+            _network: PhantomData,
+        };
+        key_images.public_spend_key.copy_from_slice(&data[4..36]);
+        key_images.public_view_key.copy_from_slice(&data[36..68]);
+
+        if &key_images.public_spend_key != public_spend_key {
+            return Err(KeyImageError::MismatchedAccount(hex::encode(
+                key_images.public_spend_key,
+            )));
+        }
+
+        for record in data[HEADER_LENGTH..].chunks(RECORD_LENGTH) {
+            let mut key_image = MoneroSignedKeyImage {
+                key_image: [0u8; 32],
+                signature: [0u8; 64],
+            };
+            key_image.key_image.copy_from_slice(&record[..32]);
+            key_image.signature.copy_from_slice(&record[32..]);
+            key_images.key_images.push(key_image);
+        }
+        Ok(key_images)
+    }
+
+    /// Returns the index of the wallet output of the first key image.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Returns the public spend key of the wallet.
+    pub fn public_spend_key(&self) -> [u8; 32] {
+        self.public_spend_key
+    }
+
+    /// Returns the public view key of the wallet.
+    pub fn public_view_key(&self) -> [u8; 32] {
+        self.public_view_key
+    }
+
+    /// Returns the signed key images of the outputs from the offset onward.
+    pub fn key_images(&self) -> &[MoneroSignedKeyImage] {
+        &self.key_images
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ringct::hash_to_scalar;
+    use crate::Stagenet;
+
+    use curve25519_dalek::edwards::EdwardsPoint;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    type N = Stagenet;
+
+    const PRIVATE_SPEND_KEY: &str = "0cf0c38429e00fa4abecb98296cb15dec209c0a7e6ea34ed86d32429498e4700";
+    const PRIVATE_VIEW_KEY: &str = "55c5b2fa94b2c5ee387eb9dd71b197a6358a5a90ed5eb5acdfa088583125a40a";
+    const PUBLIC_SPEND_KEY: &str = "be8553705de481503db0c2c87e072d446552744f9c87d3bdd682dd9f40e9c708";
+    const PUBLIC_VIEW_KEY: &str = "d827ac85d37f76be92c9250d6c401afac93ddfb982ac13ba1aecba17d8ae12b9";
+
+    // (output index, amount, one time public key, transaction public key, key image)
+    const OUTPUTS: [(u64, u64, &str, &str, &str); 1] = [(
+        0,
+        9996522470000,
+        "dd397dea109a2b94056fd5e236eada6f57c60ab4de5563469a4245e7394f8f65",
+        "0ee98e87f68a2526393ed487495f2d3dba6e90e860537d0aae1d5fad5d492f21",
+        "29aa580f31686ac8e50fee827d7849fd0dcb3e54db970d03ea7665108140a0dd",
+    )];
+
+    fn private_key() -> MoneroPrivateKey<N> {
+        MoneroPrivateKey::from_private_keys(PRIVATE_SPEND_KEY, PRIVATE_VIEW_KEY, &MoneroFormat::Standard).unwrap()
+    }
+
+    fn to_bytes(hex: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hex::decode(hex).unwrap());
+        bytes
+    }
+
+    fn recorded_output(output: &(u64, u64, &str, &str, &str)) -> MoneroOwnedOutput {
+        let (index, amount, public_key, tx_pub_key, _) = output;
+        MoneroOwnedOutput {
+            index: *index,
+            subaddress: (0, 0),
+            amount: *amount,
+            public_key: to_bytes(public_key),
+            tx_pub_key: to_bytes(tx_pub_key),
+            mask: Scalar::one().to_bytes(),
+        }
+    }
+
+    /// Returns an output paid to a subaddress of the private key at the given index.
+    fn subaddress_output(subaddress: (u32, u32), index: u64, seed: &[u8]) -> MoneroOwnedOutput {
+        let private_key = private_key();
+        let subaddress_secret =
+            Scalar::from_bytes_mod_order(private_key.to_subaddress_private_view_key(subaddress.0, subaddress.1));
+        let spend_key = Scalar::from_bytes_mod_order(private_key.to_private_spend_key()) + subaddress_secret;
+        let public_spend_key: EdwardsPoint = &spend_key * &ED25519_BASEPOINT_TABLE;
+
+        let tx_pub_key = (&hash_to_scalar(seed) * &ED25519_BASEPOINT_TABLE).compress().to_bytes();
+        let mut derivation = vec![];
+        OneTimeKey::<N>::generate_key_derivation(&tx_pub_key, &private_key.to_private_view_key(), &mut derivation)
+            .unwrap();
+        let shared_secret = OneTimeKey::<N>::derivation_to_scalar(&derivation, index);
+        let public_key = &shared_secret * &ED25519_BASEPOINT_TABLE + public_spend_key;
+
+        MoneroOwnedOutput {
+            index,
+            subaddress,
+            amount: 1_000_000_000_000,
+            public_key: public_key.compress().to_bytes(),
+            tx_pub_key,
+            mask: Scalar::one().to_bytes(),
+        }
+    }
+
+    fn outputs() -> Vec<MoneroOwnedOutput> {
+        let mut outputs: Vec<MoneroOwnedOutput> = OUTPUTS.iter().map(recorded_output).collect();
+        outputs.push(subaddress_output((1, 2), 1, b"first"));
+        outputs.push(subaddress_output((0, 5), 0, b"second"));
+        outputs
+    }
+
+    #[test]
+    fn key_image() {
+        let rng = &mut StdRng::from_seed([0u8; 32]);
+        OUTPUTS.iter().for_each(|output| {
+            let key_image = MoneroSignedKeyImage::new(&private_key(), &recorded_output(output), rng).unwrap();
+            assert_eq!(output.4, hex::encode(key_image.key_image));
+            assert!(key_image.verify(&to_bytes(output.2)).is_ok());
+        });
+    }
+
+    #[test]
+    fn export_and_import() {
+        let rng = &mut StdRng::from_seed([0u8; 32]);
+        let outputs = outputs();
+
+        for offset in [0u32, 2].iter() {
+            let offset = *offset;
+            let key_images = MoneroKeyImages::<N>::new(&private_key(), &outputs, offset, rng).unwrap();
+            assert_eq!(outputs.len() - offset as usize, key_images.key_images().len());
+            assert_eq!(PUBLIC_SPEND_KEY, hex::encode(key_images.public_spend_key()));
+            assert_eq!(PUBLIC_VIEW_KEY, hex::encode(key_images.public_view_key()));
+            assert!(key_images.verify(&outputs).is_ok());
+
+            let bytes = key_images.to_bytes(&to_bytes(PRIVATE_VIEW_KEY), rng);
+            assert!(bytes.starts_with(KEY_IMAGE_EXPORT_FILE_MAGIC));
+            assert_eq!(
+                KEY_IMAGE_EXPORT_FILE_MAGIC.len() + 8 + 68 + 96 * key_images.key_images().len() + 64,
+                bytes.len()
+            );
+
+            let imported =
+                MoneroKeyImages::<N>::from_bytes(&bytes, &to_bytes(PRIVATE_VIEW_KEY), &to_bytes(PUBLIC_SPEND_KEY))
+                    .unwrap();
+            assert_eq!(key_images, imported);
+            assert_eq!(offset, imported.offset());
+            assert!(imported.verify(&outputs).is_ok());
+        }
+    }
+
+    #[test]
+    fn subaddress_key_images() {
+        let rng = &mut StdRng::from_seed([0u8; 32]);
+        let outputs = outputs();
+        let key_images = MoneroKeyImages::<N>::new(&private_key(), &outputs, 1, rng).unwrap();
+
+        // The key images of distinct outputs are distinct, and each one is x * Hp(P).
+        let images: Vec<[u8; 32]> = key_images.key_images().iter().map(|image| image.key_image).collect();
+        assert_ne!(images[0], images[1]);
+        for (output, image) in outputs[1..].iter().zip(images.iter()) {
+            let private_key = MoneroPrivateKey::<N>::from_private_keys(
+                PRIVATE_SPEND_KEY,
+                PRIVATE_VIEW_KEY,
+                &MoneroFormat::Subaddress(output.subaddress.0, output.subaddress.1),
+            )
+            .unwrap();
+            let one_time_key = OneTimeKey::<N>::from(&output.public_key, &output.tx_pub_key);
+            assert!(one_time_key.verify(&private_key, output.index).unwrap());
+            assert_eq!(*image, one_time_key.to_key_image(&private_key, output.index).unwrap());
+        }
+    }
+
+    mod invalid {
+        use super::*;
+
+        #[test]
+        fn verify_invalid_key_images() {
+            let rng = &mut StdRng::from_seed([0u8; 32]);
+            let outputs = outputs();
+            let key_images = MoneroKeyImages::<N>::new(&private_key(), &outputs, 0, rng).unwrap();
+
+            // The signatures do not verify for other outputs or an offset past the known outputs.
+            let mut reordered = outputs.clone();
+            reordered.swap(0, 1);
+            assert!(key_images.verify(&reordered).is_err());
+            assert!(key_images.verify(&outputs[..2]).is_err());
+
+            let mut tampered = key_images.key_images()[0].clone();
+            tampered.signature[0] ^= 1;
+            assert!(tampered.verify(&outputs[0].public_key).is_err());
+
+            assert!(MoneroKeyImages::<N>::new(&private_key(), &outputs, 4, rng).is_err());
+        }
+
+        #[test]
+        fn import_invalid_file() {
+            let rng = &mut StdRng::from_seed([0u8; 32]);
+            let outputs = outputs();
+            let key_images = MoneroKeyImages::<N>::new(&private_key(), &outputs, 0, rng).unwrap();
+            let bytes = key_images.to_bytes(&to_bytes(PRIVATE_VIEW_KEY), rng);
+            let view_key = to_bytes(PRIVATE_VIEW_KEY);
+            let spend_key = to_bytes(PUBLIC_SPEND_KEY);
+
+            let mut invalid_magic = bytes.clone();
+            invalid_magic[0] ^= 1;
+            assert!(MoneroKeyImages::<N>::from_bytes(&invalid_magic, &view_key, &spend_key).is_err());
+
+            let mut tampered = bytes.clone();
+            tampered[KEY_IMAGE_EXPORT_FILE_MAGIC.len() + 20] ^= 1;
+            assert!(MoneroKeyImages::<N>::from_bytes(&tampered, &view_key, &spend_key).is_err());
+
+            assert!(MoneroKeyImages::<N>::from_bytes(&bytes, &[1u8; 32], &spend_key).is_err());
+            assert!(MoneroKeyImages::<N>::from_bytes(&bytes, &view_key, &[1u8; 32]).is_err());
+            assert!(MoneroKeyImages::<N>::from_bytes(&bytes[..40], &view_key, &spend_key).is_err());
+        }
+    }
+}
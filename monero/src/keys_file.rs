@@ -0,0 +1,922 @@
+use crate::address::MoneroAddress;
+use crate::chacha::{chacha20, chacha8, generate_chacha_key};
+use crate::format::MoneroFormat;
+use crate::network::MoneroNetwork;
+use crate::private_key::MoneroPrivateKey;
+use crate::public_key::MoneroPublicKey;
+use crate::ringct::*;
+use wagyu_model::{Address, AddressError, PrivateKeyError, PublicKeyError};
+
+use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, scalar::Scalar};
+use rand::Rng;
+use std::{collections::BTreeMap, marker::PhantomData};
+
+/// The signatures and version that begin an epee portable storage
+/// (Monero `PORTABLE_STORAGE_SIGNATUREA`, `PORTABLE_STORAGE_SIGNATUREB` and `PORTABLE_STORAGE_FORMAT_VER`)
+const PORTABLE_STORAGE_HEADER: [u8; 9] = [0x01, 0x11, 0x01, 0x01, 0x01, 0x01, 0x02, 0x01, 0x01];
+
+/// The type codes of the entries of an epee portable storage
+const SERIALIZE_TYPE_INT64: u8 = 1;
+const SERIALIZE_TYPE_INT32: u8 = 2;
+const SERIALIZE_TYPE_INT16: u8 = 3;
+const SERIALIZE_TYPE_INT8: u8 = 4;
+const SERIALIZE_TYPE_UINT64: u8 = 5;
+const SERIALIZE_TYPE_UINT32: u8 = 6;
+const SERIALIZE_TYPE_UINT16: u8 = 7;
+const SERIALIZE_TYPE_UINT8: u8 = 8;
+const SERIALIZE_TYPE_DOUBLE: u8 = 9;
+const SERIALIZE_TYPE_STRING: u8 = 10;
+const SERIALIZE_TYPE_BOOL: u8 = 11;
+const SERIALIZE_TYPE_OBJECT: u8 = 12;
+const SERIALIZE_FLAG_ARRAY: u8 = 0x80;
+
+/// The nesting depth beyond which JSON values and portable storage sections are rejected
+const MAX_NESTING_DEPTH: usize = 16;
+
+/// The byte appended to the password key to derive the key stream of the secret keys (Monero `HASH_KEY_MEMORY`)
+const HASH_KEY_MEMORY: u8 = b'k';
+
+#[derive(Debug, Fail)]
+pub enum KeysFileError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "invalid byte length: {}", _0)]
+    InvalidByteLength(usize),
+
+    #[fail(display = "invalid field {}", _0)]
+    InvalidField(&'static str),
+
+    #[fail(display = "invalid JSON at byte {}", _0)]
+    InvalidJson(usize),
+
+    #[fail(display = "invalid key data: {}", _0)]
+    InvalidKeyData(String),
+
+    #[fail(display = "invalid network type {{ expected: {:?}, found: {:?} }}", _0, _1)]
+    InvalidNetworkType(u64, u64),
+
+    #[fail(display = "invalid password")]
+    InvalidPassword,
+
+    #[fail(display = "missing field {}", _0)]
+    MissingField(&'static str),
+
+    #[fail(display = "multisig wallets are not supported")]
+    MultisigWallet,
+
+    #[fail(display = "{}", _0)]
+    PrivateKeyError(PrivateKeyError),
+
+    #[fail(display = "{}", _0)]
+    PublicKeyError(PublicKeyError),
+
+    #[fail(display = "{}", _0)]
+    RingCtError(RingCtError),
+
+    #[fail(display = "watch-only wallets have no private spend key")]
+    WatchOnlyWallet,
+}
+
+impl From<AddressError> for KeysFileError {
+    fn from(error: AddressError) -> Self {
+        KeysFileError::AddressError(error)
+    }
+}
+
+impl From<PrivateKeyError> for KeysFileError {
+    fn from(error: PrivateKeyError) -> Self {
+        KeysFileError::PrivateKeyError(error)
+    }
+}
+
+impl From<PublicKeyError> for KeysFileError {
+    fn from(error: PublicKeyError) -> Self {
+        KeysFileError::PublicKeyError(error)
+    }
+}
+
+impl From<RingCtError> for KeysFileError {
+    fn from(error: RingCtError) -> Self {
+        KeysFileError::RingCtError(error)
+    }
+}
+
+/// Represents the keys of a Monero wallet stored in its `.keys` file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroKeysFile<N: MoneroNetwork> {
+    /// The private spend key, which is zero in watch-only wallets
+    spend_key: [u8; 32],
+    /// The private view key
+    view_key: [u8; 32],
+    /// The public spend key
+    public_spend_key: [u8; 32],
+    /// The public view key
+    public_view_key: [u8; 32],
+    /// The creation time of the wallet in seconds since the Unix epoch
+    creation_timestamp: u64,
+    /// The language of the mnemonic seed
+    seed_language: String,
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: MoneroNetwork> MoneroKeysFile<N> {
+    /// Returns the keys of a wallet with the given private key and creation time.
+    pub fn from_private_key(private_key: &MoneroPrivateKey<N>, creation_timestamp: u64) -> Self {
+        let spend_key = private_key.to_private_spend_key();
+        let view_key = private_key.to_private_view_key();
+        Self {
+            spend_key,
+            view_key,
+            public_spend_key: public_key(&spend_key),
+            public_view_key: public_key(&view_key),
+            creation_timestamp,
+            seed_language: "English".into(),
+            _network: PhantomData,
+        }
+    }
+
+    /// Returns the keys of a wallet given the contents of its `.keys` file, the password and the number of
+    /// CryptoNight hashes the wallet derives its ChaCha20 key with (Monero `wallet2::load_keys_buf`).
+    pub fn from_keys_file(keys_file: &[u8], password: &str, kdf_rounds: u64) -> Result<Self, KeysFileError> {
+        if keys_file.len() < 9 {
+            return Err(KeysFileError::InvalidByteLength(keys_file.len()));
+        }
+        let mut iv = [0u8; 8];
+        iv.copy_from_slice(&keys_file[0..8]);
+        let (length, size) = read_varint(&keys_file[8..])?;
+        let ciphertext = &keys_file[8 + size..];
+        if ciphertext.len() as u64 != length {
+            return Err(KeysFileError::InvalidByteLength(keys_file.len()));
+        }
+
+        // Wallets before v0.12 encrypted with ChaCha8, and before v0.10 stored the key data without JSON.
+        let key = generate_chacha_key(password.as_bytes(), kdf_rounds);
+        let mut account_data = chacha20(ciphertext, &key, &iv);
+        let mut json = parse_json(&account_data).ok();
+        if json.is_none() {
+            account_data = chacha8(ciphertext, &key, &iv);
+            json = parse_json(&account_data).ok();
+        }
+
+        let (key_data, encrypted_secret_keys, watch_only, seed_language) = match json {
+            Some(json) => {
+                if let Some(network_type) = json_integer(&json, "nettype")? {
+                    if network_type != to_network_type::<N>() {
+                        return Err(KeysFileError::InvalidNetworkType(to_network_type::<N>(), network_type));
+                    }
+                }
+                if json_integer(&json, "multisig")?.unwrap_or(0) != 0 {
+                    return Err(KeysFileError::MultisigWallet);
+                }
+
+                let key_data = match json.get("key_data") {
+                    Some(JsonValue::String(key_data)) => key_data.clone(),
+                    Some(_) => return Err(KeysFileError::InvalidField("key_data")),
+                    None => return Err(KeysFileError::MissingField("key_data")),
+                };
+                let seed_language = match json.get("seed_language") {
+                    Some(JsonValue::String(language)) => String::from_utf8_lossy(language).into_owned(),
+                    _ => String::new(),
+                };
+                (
+                    key_data,
+                    json_integer(&json, "encrypted_secret_keys")?.unwrap_or(0) != 0,
+                    json_integer(&json, "watch_only")?.unwrap_or(0) != 0,
+                    seed_language,
+                )
+            }
+            None if account_data.starts_with(&PORTABLE_STORAGE_HEADER) => (account_data, false, false, String::new()),
+            None => return Err(KeysFileError::InvalidPassword),
+        };
+
+        let storage = read_portable_storage(&key_data)?;
+        let keys = storage_section(&storage, "m_keys")?;
+        let address = storage_section(keys, "m_account_address")?;
+        let public_spend_key = storage_key(address, "m_spend_public_key")?;
+        let public_view_key = storage_key(address, "m_view_public_key")?;
+        let mut spend_key = storage_key(keys, "m_spend_secret_key")?;
+        let mut view_key = storage_key(keys, "m_view_secret_key")?;
+        let creation_timestamp = match storage.get("m_creation_timestamp") {
+            Some(StorageValue::Integer(timestamp)) => *timestamp,
+            _ => 0,
+        };
+
+        if encrypted_secret_keys {
+            let mut encryption_iv = [0u8; 8];
+            match keys.get("m_encryption_iv") {
+                Some(StorageValue::Blob(blob)) if blob.len() == 8 => encryption_iv.copy_from_slice(blob),
+                None => {}
+                _ => return Err(KeysFileError::InvalidField("m_encryption_iv")),
+            };
+            let key_stream = to_key_stream(&key, &encryption_iv);
+            xor(&mut spend_key, &key_stream[0..32]);
+            xor(&mut view_key, &key_stream[32..64]);
+        }
+
+        // A wrong password leaves secret keys that do not match the public keys (Monero `verify_keys`).
+        let watch_only = watch_only || spend_key == [0u8; 32];
+        if public_key(&view_key) != public_view_key || (!watch_only && public_key(&spend_key) != public_spend_key) {
+            return Err(KeysFileError::InvalidPassword);
+        }
+
+        Ok(Self {
+            spend_key: if watch_only { [0u8; 32] } else { spend_key },
+            view_key,
+            public_spend_key,
+            public_view_key,
+            creation_timestamp,
+            seed_language,
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns the contents of a `.keys` file of the wallet, encrypted with the password and the number of
+    /// CryptoNight hashes the wallet derives its ChaCha20 key with (Monero `wallet2::get_keys_file_data`).
+    pub fn to_keys_file<R: Rng>(&self, password: &str, kdf_rounds: u64, rng: &mut R) -> Vec<u8> {
+        let key = generate_chacha_key(password.as_bytes(), kdf_rounds);
+        let encryption_iv: [u8; 8] = rng.gen();
+        let key_data = self.to_key_data(&to_key_stream(&key, &encryption_iv), &encryption_iv);
+
+        let mut json = b"{\"key_data\":".to_vec();
+        write_json_string(&mut json, &key_data);
+        json.extend(b",\"seed_language\":");
+        write_json_string(&mut json, self.seed_language.as_bytes());
+        json.extend(
+            format!(
+                ",\"key_on_device\":0,\"watch_only\":{},\"multisig\":0,\"multisig_threshold\":0,\"nettype\":{},\
+                 \"encrypted_secret_keys\":1}}",
+                self.is_watch_only() as u8,
+                to_network_type::<N>(),
+            )
+            .as_bytes(),
+        );
+
+        let iv: [u8; 8] = rng.gen();
+        let mut keys_file = iv.to_vec();
+        write_varint(&mut keys_file, json.len() as u64);
+        keys_file.extend(chacha20(&json, &key, &iv));
+        keys_file
+    }
+
+    /// Returns the keys of the wallet without its private spend key.
+    pub fn to_watch_only(&self) -> Self {
+        Self {
+            spend_key: [0u8; 32],
+            ..self.clone()
+        }
+    }
+
+    /// Returns the private key of the wallet.
+    pub fn to_private_key(&self) -> Result<MoneroPrivateKey<N>, KeysFileError> {
+        if self.is_watch_only() {
+            return Err(KeysFileError::WatchOnlyWallet);
+        }
+        Ok(MoneroPrivateKey::from_private_keys(
+            &hex::encode(self.spend_key),
+            &hex::encode(self.view_key),
+            &MoneroFormat::Standard,
+        )?)
+    }
+
+    /// Returns the private view key of the wallet.
+    pub fn to_private_view_key(&self) -> [u8; 32] {
+        self.view_key
+    }
+
+    /// Returns the primary address of the wallet.
+    pub fn to_address(&self) -> Result<MoneroAddress<N>, KeysFileError> {
+        let public_key = MoneroPublicKey::<N>::from(
+            &hex::encode(self.public_spend_key),
+            &hex::encode(self.public_view_key),
+            &MoneroFormat::Standard,
+        )?;
+        Ok(MoneroAddress::from_public_key(&public_key, &MoneroFormat::Standard)?)
+    }
+
+    /// Returns the creation time of the wallet in seconds since the Unix epoch.
+    pub fn creation_timestamp(&self) -> u64 {
+        self.creation_timestamp
+    }
+
+    /// Returns the language of the mnemonic seed of the wallet.
+    pub fn seed_language(&self) -> &str {
+        &self.seed_language
+    }
+
+    /// Returns `true` if the wallet has no private spend key.
+    pub fn is_watch_only(&self) -> bool {
+        self.spend_key == [0u8; 32]
+    }
+
+    /// Returns the portable storage of the `cryptonote::account_base` of the wallet, with the secret keys
+    /// xored with the key stream.
+    fn to_key_data(&self, key_stream: &[u8; 64], encryption_iv: &[u8; 8]) -> Vec<u8> {
+        let mut spend_key = self.spend_key;
+        let mut view_key = self.view_key;
+        xor(&mut spend_key, &key_stream[0..32]);
+        xor(&mut view_key, &key_stream[32..64]);
+
+        let mut key_data = PORTABLE_STORAGE_HEADER.to_vec();
+        write_storage_varint(&mut key_data, 2);
+        write_storage_section(&mut key_data, "m_keys", 5);
+        write_storage_section(&mut key_data, "m_account_address", 2);
+        write_storage_blob(&mut key_data, "m_spend_public_key", &self.public_spend_key);
+        write_storage_blob(&mut key_data, "m_view_public_key", &self.public_view_key);
+        write_storage_blob(&mut key_data, "m_spend_secret_key", &spend_key);
+        write_storage_blob(&mut key_data, "m_view_secret_key", &view_key);
+        write_storage_blob(&mut key_data, "m_multisig_keys", &[]);
+        write_storage_blob(&mut key_data, "m_encryption_iv", encryption_iv);
+        write_storage_name(&mut key_data, "m_creation_timestamp");
+        key_data.push(SERIALIZE_TYPE_UINT64);
+        key_data.extend(&self.creation_timestamp.to_le_bytes());
+        key_data
+    }
+}
+
+/// Returns the `nettype` of the network in the wallet keys JSON.
+fn to_network_type<N: MoneroNetwork>() -> u64 {
+    match N::NAME {
+        "testnet" => 1,
+        "stagenet" => 2,
+        _ => 0,
+    }
+}
+
+/// Returns the public key of a secret key.
+fn public_key(secret_key: &[u8; 32]) -> [u8; 32] {
+    (&Scalar::from_bytes_mod_order(*secret_key) * &ED25519_BASEPOINT_TABLE)
+        .compress()
+        .to_bytes()
+}
+
+/// Returns the ChaCha20 key stream that the secret keys are xored with, under a key derived from the password
+/// key (Monero `account_keys::xor_with_key_stream`).
+fn to_key_stream(key: &[u8; 32], encryption_iv: &[u8; 8]) -> [u8; 64] {
+    let mut data = key.to_vec();
+    data.push(HASH_KEY_MEMORY);
+    let mut key_stream = [0u8; 64];
+    key_stream.copy_from_slice(&chacha20(&[0u8; 64], &generate_chacha_key(&data, 1), encryption_iv));
+    key_stream
+}
+
+fn xor(bytes: &mut [u8], key_stream: &[u8]) {
+    bytes.iter_mut().zip(key_stream).for_each(|(byte, key)| *byte ^= key);
+}
+
+/// A value of the wallet keys JSON, with strings as bytes since rapidjson writes the binary key data unescaped
+enum JsonValue {
+    String(Vec<u8>),
+    Number(String),
+    Bool(bool),
+    Other,
+}
+
+/// Returns the integer field of the wallet keys JSON, if present.
+fn json_integer(json: &BTreeMap<String, JsonValue>, name: &'static str) -> Result<Option<u64>, KeysFileError> {
+    match json.get(name) {
+        Some(JsonValue::Number(number)) => match number.parse() {
+            Ok(number) => Ok(Some(number)),
+            Err(_) => Err(KeysFileError::InvalidField(name)),
+        },
+        Some(JsonValue::Bool(value)) => Ok(Some(*value as u64)),
+        Some(_) => Err(KeysFileError::InvalidField(name)),
+        None => Ok(None),
+    }
+}
+
+/// Returns the fields of a JSON object.
+fn parse_json(bytes: &[u8]) -> Result<BTreeMap<String, JsonValue>, KeysFileError> {
+    let mut reader = JsonReader { bytes, offset: 0 };
+    let object = reader.read_object(0)?;
+    match reader.peek() {
+        None => Ok(object),
+        Some(_) => Err(reader.error()),
+    }
+}
+
+struct JsonReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> JsonReader<'a> {
+    fn error(&self) -> KeysFileError {
+        KeysFileError::InvalidJson(self.offset)
+    }
+
+    /// Returns the next byte that is not whitespace, without consuming it.
+    fn peek(&mut self) -> Option<u8> {
+        while let Some(byte) = self.bytes.get(self.offset) {
+            match byte {
+                b' ' | b'\t' | b'\n' | b'\r' => self.offset += 1,
+                _ => return Some(*byte),
+            }
+        }
+        None
+    }
+
+    fn next(&mut self) -> Result<u8, KeysFileError> {
+        match self.bytes.get(self.offset) {
+            Some(byte) => {
+                self.offset += 1;
+                Ok(*byte)
+            }
+            None => Err(self.error()),
+        }
+    }
+
+    fn expect(&mut self, expected: &[u8]) -> Result<(), KeysFileError> {
+        self.peek();
+        match self.bytes.get(self.offset..self.offset + expected.len()) {
+            Some(bytes) if bytes == expected => {
+                self.offset += expected.len();
+                Ok(())
+            }
+            _ => Err(self.error()),
+        }
+    }
+
+    fn read_object(&mut self, depth: usize) -> Result<BTreeMap<String, JsonValue>, KeysFileError> {
+        if depth > MAX_NESTING_DEPTH {
+            return Err(self.error());
+        }
+        self.expect(b"{")?;
+        let mut object = BTreeMap::new();
+        if self.peek() == Some(b'}') {
+            self.offset += 1;
+            return Ok(object);
+        }
+        loop {
+            self.expect(b"\"")?;
+            let name = String::from_utf8_lossy(&self.read_string()?).into_owned();
+            self.expect(b":")?;
+            object.insert(name, self.read_value(depth)?);
+            match self.peek() {
+                Some(b',') => self.offset += 1,
+                Some(b'}') => {
+                    self.offset += 1;
+                    return Ok(object);
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    fn read_array(&mut self, depth: usize) -> Result<(), KeysFileError> {
+        if depth > MAX_NESTING_DEPTH {
+            return Err(self.error());
+        }
+        self.expect(b"[")?;
+        if self.peek() == Some(b']') {
+            self.offset += 1;
+            return Ok(());
+        }
+        loop {
+            self.read_value(depth)?;
+            match self.peek() {
+                Some(b',') => self.offset += 1,
+                Some(b']') => {
+                    self.offset += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    fn read_value(&mut self, depth: usize) -> Result<JsonValue, KeysFileError> {
+        match self.peek() {
+            Some(b'"') => {
+                self.offset += 1;
+                Ok(JsonValue::String(self.read_string()?))
+            }
+            Some(b'{') => self.read_object(depth + 1).map(|_| JsonValue::Other),
+            Some(b'[') => self.read_array(depth + 1).map(|_| JsonValue::Other),
+            Some(b't') => self.expect(b"true").map(|_| JsonValue::Bool(true)),
+            Some(b'f') => self.expect(b"false").map(|_| JsonValue::Bool(false)),
+            Some(b'n') => self.expect(b"null").map(|_| JsonValue::Other),
+            Some(_) => {
+                let start = self.offset;
+                while let Some(b'0'..=b'9') | Some(b'-') | Some(b'+') | Some(b'.') | Some(b'e') | Some(b'E') =
+                    self.bytes.get(self.offset)
+                {
+                    self.offset += 1;
+                }
+                match start == self.offset {
+                    true => Err(self.error()),
+                    false => Ok(JsonValue::Number(
+                        String::from_utf8_lossy(&self.bytes[start..self.offset]).into_owned(),
+                    )),
+                }
+            }
+            None => Err(self.error()),
+        }
+    }
+
+    /// Returns the bytes of a string after its opening quote.
+    fn read_string(&mut self) -> Result<Vec<u8>, KeysFileError> {
+        let mut string = vec![];
+        loop {
+            match self.next()? {
+                b'"' => return Ok(string),
+                b'\\' => match self.next()? {
+                    b'"' => string.push(b'"'),
+                    b'\\' => string.push(b'\\'),
+                    b'/' => string.push(b'/'),
+                    b'b' => string.push(0x08),
+                    b'f' => string.push(0x0c),
+                    b'n' => string.push(b'\n'),
+                    b'r' => string.push(b'\r'),
+                    b't' => string.push(b'\t'),
+                    b'u' => {
+                        let mut code_point = self.read_code_unit()?;
+                        if (0xd800..0xdc00).contains(&code_point) {
+                            self.expect(b"\\u")?;
+                            let low = self.read_code_unit()?;
+                            if !(0xdc00..0xe000).contains(&low) {
+                                return Err(self.error());
+                            }
+                            code_point = 0x10000 + ((code_point - 0xd800) << 10) + (low - 0xdc00);
+                        }
+                        match std::char::from_u32(code_point) {
+                            Some(character) => {
+                                let mut buffer = [0u8; 4];
+                                string.extend(character.encode_utf8(&mut buffer).as_bytes());
+                            }
+                            None => return Err(self.error()),
+                        }
+                    }
+                    _ => return Err(self.error()),
+                },
+                byte => string.push(byte),
+            }
+        }
+    }
+
+    fn read_code_unit(&mut self) -> Result<u32, KeysFileError> {
+        let mut code_unit = 0;
+        for _ in 0..4 {
+            let digit = (self.next()? as char).to_digit(16).ok_or_else(|| self.error())?;
+            code_unit = code_unit << 4 | digit;
+        }
+        Ok(code_unit)
+    }
+}
+
+/// Appends the bytes as a JSON string escaped the way rapidjson does, leaving bytes above 0x7f unescaped.
+fn write_json_string(buffer: &mut Vec<u8>, bytes: &[u8]) {
+    buffer.push(b'"');
+    for byte in bytes {
+        match byte {
+            b'"' => buffer.extend(b"\\\""),
+            b'\\' => buffer.extend(b"\\\\"),
+            0x08 => buffer.extend(b"\\b"),
+            0x0c => buffer.extend(b"\\f"),
+            b'\n' => buffer.extend(b"\\n"),
+            b'\r' => buffer.extend(b"\\r"),
+            b'\t' => buffer.extend(b"\\t"),
+            0x00..=0x1f => buffer.extend(format!("\\u{:04X}", byte).as_bytes()),
+            _ => buffer.push(*byte),
+        }
+    }
+    buffer.push(b'"');
+}
+
+/// A value of an epee portable storage, keeping the integers, strings and sections that key data uses
+enum StorageValue {
+    Integer(u64),
+    Blob(Vec<u8>),
+    Section(BTreeMap<String, StorageValue>),
+    Other,
+}
+
+/// Returns the subsection of a portable storage section.
+fn storage_section<'a>(
+    section: &'a BTreeMap<String, StorageValue>,
+    name: &'static str,
+) -> Result<&'a BTreeMap<String, StorageValue>, KeysFileError> {
+    match section.get(name) {
+        Some(StorageValue::Section(subsection)) => Ok(subsection),
+        Some(_) => Err(KeysFileError::InvalidField(name)),
+        None => Err(KeysFileError::MissingField(name)),
+    }
+}
+
+/// Returns the 32-byte key of a portable storage section.
+fn storage_key(section: &BTreeMap<String, StorageValue>, name: &'static str) -> Result<[u8; 32], KeysFileError> {
+    match section.get(name) {
+        Some(StorageValue::Blob(blob)) if blob.len() == 32 => {
+            let mut key = [0u8; 32];
+            key.copy_from_slice(blob);
+            Ok(key)
+        }
+        Some(_) => Err(KeysFileError::InvalidField(name)),
+        None => Err(KeysFileError::MissingField(name)),
+    }
+}
+
+/// Returns the root section of an epee portable storage.
+fn read_portable_storage(bytes: &[u8]) -> Result<BTreeMap<String, StorageValue>, KeysFileError> {
+    if !bytes.starts_with(&PORTABLE_STORAGE_HEADER) {
+        return Err(KeysFileError::InvalidKeyData("invalid portable storage header".into()));
+    }
+    let mut reader = StorageReader {
+        bytes,
+        offset: PORTABLE_STORAGE_HEADER.len(),
+    };
+    let root = reader.read_section(0)?;
+    match reader.offset == bytes.len() {
+        true => Ok(root),
+        false => Err(KeysFileError::InvalidKeyData("trailing bytes".into())),
+    }
+}
+
+struct StorageReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> StorageReader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], KeysFileError> {
+        Ok(take(self.bytes, &mut self.offset, length)?)
+    }
+
+    fn read_integer(&mut self, size: usize) -> Result<u64, KeysFileError> {
+        let mut bytes = [0u8; 8];
+        bytes[..size].copy_from_slice(self.take(size)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    /// Returns a variable-length integer whose two low bits give its size of 1, 2, 4 or 8 bytes.
+    fn read_varint(&mut self) -> Result<u64, KeysFileError> {
+        let size = 1 << (self.take(1)?[0] & 0x03);
+        self.offset -= 1;
+        Ok(self.read_integer(size)? >> 2)
+    }
+
+    fn read_section(&mut self, depth: usize) -> Result<BTreeMap<String, StorageValue>, KeysFileError> {
+        if depth > MAX_NESTING_DEPTH {
+            return Err(KeysFileError::InvalidKeyData("sections are nested too deeply".into()));
+        }
+        let mut section = BTreeMap::new();
+        for _ in 0..self.read_varint()? {
+            let length = self.take(1)?[0] as usize;
+            let name = String::from_utf8_lossy(self.take(length)?).into_owned();
+            let serialize_type = self.take(1)?[0];
+            let value = match serialize_type & SERIALIZE_FLAG_ARRAY {
+                0 => self.read_value(serialize_type, depth)?,
+                _ => {
+                    for _ in 0..self.read_varint()? {
+                        self.read_value(serialize_type & !SERIALIZE_FLAG_ARRAY, depth)?;
+                    }
+                    StorageValue::Other
+                }
+            };
+            section.insert(name, value);
+        }
+        Ok(section)
+    }
+
+    fn read_value(&mut self, serialize_type: u8, depth: usize) -> Result<StorageValue, KeysFileError> {
+        Ok(match serialize_type {
+            SERIALIZE_TYPE_INT64 | SERIALIZE_TYPE_UINT64 => StorageValue::Integer(self.read_integer(8)?),
+            SERIALIZE_TYPE_INT32 | SERIALIZE_TYPE_UINT32 => StorageValue::Integer(self.read_integer(4)?),
+            SERIALIZE_TYPE_INT16 | SERIALIZE_TYPE_UINT16 => StorageValue::Integer(self.read_integer(2)?),
+            SERIALIZE_TYPE_INT8 | SERIALIZE_TYPE_UINT8 => StorageValue::Integer(self.read_integer(1)?),
+            SERIALIZE_TYPE_DOUBLE => self.take(8).map(|_| StorageValue::Other)?,
+            SERIALIZE_TYPE_BOOL => self.take(1).map(|_| StorageValue::Other)?,
+            SERIALIZE_TYPE_STRING => {
+                let length = self.read_varint()? as usize;
+                StorageValue::Blob(self.take(length)?.to_vec())
+            }
+            SERIALIZE_TYPE_OBJECT => StorageValue::Section(self.read_section(depth + 1)?),
+            _ => {
+                return Err(KeysFileError::InvalidKeyData(format!(
+                    "invalid type {}",
+                    serialize_type
+                )))
+            }
+        })
+    }
+}
+
+/// Appends the value as a portable storage variable-length integer.
+fn write_storage_varint(buffer: &mut Vec<u8>, value: u64) {
+    let (size, marker) = match value {
+        0..=0x3f => (1, 0),
+        0x40..=0x3fff => (2, 1),
+        0x4000..=0x3fff_ffff => (4, 2),
+        _ => (8, 3),
+    };
+    buffer.extend(&((value << 2) | marker).to_le_bytes()[..size]);
+}
+
+fn write_storage_name(buffer: &mut Vec<u8>, name: &str) {
+    buffer.push(name.len() as u8);
+    buffer.extend(name.as_bytes());
+}
+
+/// Appends the name and type of a section with the given number of entries, which follow it.
+fn write_storage_section(buffer: &mut Vec<u8>, name: &str, entries: u64) {
+    write_storage_name(buffer, name);
+    buffer.push(SERIALIZE_TYPE_OBJECT);
+    write_storage_varint(buffer, entries);
+}
+
+fn write_storage_blob(buffer: &mut Vec<u8>, name: &str, blob: &[u8]) {
+    write_storage_name(buffer, name);
+    buffer.push(SERIALIZE_TYPE_STRING);
+    write_storage_varint(buffer, blob.len() as u64);
+    buffer.extend(blob);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Mainnet, Stagenet};
+    use rand::{rngs::StdRng, SeedableRng};
+    use wagyu_model::PrivateKey;
+
+    type N = Stagenet;
+
+    const PRIVATE_SPEND_KEY: &str = "0cf0c38429e00fa4abecb98296cb15dec209c0a7e6ea34ed86d32429498e4700";
+    const PRIVATE_VIEW_KEY: &str = "55c5b2fa94b2c5ee387eb9dd71b197a6358a5a90ed5eb5acdfa088583125a40a";
+    const CREATION_TIMESTAMP: u64 = 1_571_000_000;
+    const PASSWORD: &str = "correct horse battery staple";
+
+    fn keys() -> MoneroKeysFile<N> {
+        let private_key =
+            MoneroPrivateKey::from_private_keys(PRIVATE_SPEND_KEY, PRIVATE_VIEW_KEY, &MoneroFormat::Standard).unwrap();
+        MoneroKeysFile::from_private_key(&private_key, CREATION_TIMESTAMP)
+    }
+
+    /// Returns a keys file of the given account data in the layout of older wallets.
+    fn legacy_keys_file(account_data: &[u8], cipher: fn(&[u8], &[u8; 32], &[u8; 8]) -> Vec<u8>) -> Vec<u8> {
+        let iv = [7u8; 8];
+        let mut keys_file = iv.to_vec();
+        write_varint(&mut keys_file, account_data.len() as u64);
+        keys_file.extend(cipher(account_data, &generate_chacha_key(PASSWORD.as_bytes(), 1), &iv));
+        keys_file
+    }
+
+    #[test]
+    fn to_keys_file_and_from_keys_file() {
+        let rng = &mut StdRng::from_seed([0u8; 32]);
+        let keys = keys();
+        let keys_file = keys.to_keys_file(PASSWORD, 1, rng);
+        let recovered = MoneroKeysFile::<N>::from_keys_file(&keys_file, PASSWORD, 1).unwrap();
+
+        assert_eq!(keys, recovered);
+        assert!(!recovered.is_watch_only());
+        assert_eq!(CREATION_TIMESTAMP, recovered.creation_timestamp());
+        assert_eq!("English", recovered.seed_language());
+
+        let private_key = recovered.to_private_key().unwrap();
+        assert_eq!(PRIVATE_SPEND_KEY, hex::encode(private_key.to_private_spend_key()));
+        assert_eq!(PRIVATE_VIEW_KEY, hex::encode(private_key.to_private_view_key()));
+        assert_eq!(
+            private_key.to_address(&MoneroFormat::Standard).unwrap(),
+            recovered.to_address().unwrap()
+        );
+    }
+
+    #[test]
+    fn watch_only() {
+        let rng = &mut StdRng::from_seed([0u8; 32]);
+        let keys = keys().to_watch_only();
+        let keys_file = keys.to_keys_file(PASSWORD, 1, rng);
+        let recovered = MoneroKeysFile::<N>::from_keys_file(&keys_file, PASSWORD, 1).unwrap();
+
+        assert_eq!(keys, recovered);
+        assert!(recovered.is_watch_only());
+        assert_eq!(PRIVATE_VIEW_KEY, hex::encode(recovered.to_private_view_key()));
+        assert_eq!(self::keys().to_address().unwrap(), recovered.to_address().unwrap());
+        match recovered.to_private_key() {
+            Err(KeysFileError::WatchOnlyWallet) => {}
+            result => panic!("expected a watch-only wallet, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn legacy_keys_files() {
+        let keys = keys();
+        let key_data = keys.to_key_data(&[0u8; 64], &[0u8; 8]);
+
+        // Wallets before v0.10 stored the key data encrypted with ChaCha8 and without JSON.
+        let keys_file = legacy_keys_file(&key_data, chacha8);
+        let recovered = MoneroKeysFile::<N>::from_keys_file(&keys_file, PASSWORD, 1).unwrap();
+        assert_eq!(keys.to_private_key().unwrap(), recovered.to_private_key().unwrap());
+        assert_eq!("", recovered.seed_language());
+
+        // Wallets before v0.13 stored the secret keys unencrypted inside the JSON.
+        let mut json = b"{\"key_data\":".to_vec();
+        write_json_string(&mut json, &key_data);
+        json.extend(b",\"seed_language\":\"English\",\"watch_only\":0,\"testnet\":false}");
+        let keys_file = legacy_keys_file(&json, chacha20);
+        assert_eq!(
+            keys,
+            MoneroKeysFile::<N>::from_keys_file(&keys_file, PASSWORD, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn json_string() {
+        let bytes = b"\"\\/\x00\x08\x1f\x7f\x80\xff key";
+        let mut json = vec![];
+        write_json_string(&mut json, bytes);
+        assert_eq!(&b"\"\\\"\\\\/\\u0000\\b\\u001F\x7f\x80\xff key\""[..], &json[..]);
+
+        let mut reader = JsonReader {
+            bytes: &json,
+            offset: 1,
+        };
+        assert_eq!(&bytes[..], &reader.read_string().unwrap()[..]);
+
+        let mut reader = JsonReader {
+            bytes: b"\\u00e9\\ud83d\\ude00\"",
+            offset: 0,
+        };
+        assert_eq!("é😀".as_bytes(), &reader.read_string().unwrap()[..]);
+    }
+
+    #[test]
+    fn storage_varint() {
+        [0, 0x3f, 0x40, 0x3fff, 0x4000, 0x3fff_ffff, 0x4000_0000]
+            .iter()
+            .for_each(|value| {
+                let mut buffer = vec![];
+                write_storage_varint(&mut buffer, *value);
+                let mut reader = StorageReader {
+                    bytes: &buffer,
+                    offset: 0,
+                };
+                assert_eq!(*value, reader.read_varint().unwrap());
+                assert_eq!(buffer.len(), reader.offset);
+            });
+    }
+
+    mod invalid {
+        use super::*;
+
+        #[test]
+        fn invalid_password() {
+            let rng = &mut StdRng::from_seed([0u8; 32]);
+            let keys_file = keys().to_keys_file(PASSWORD, 1, rng);
+            match MoneroKeysFile::<N>::from_keys_file(&keys_file, "Tr0ub4dor&3", 1) {
+                Err(KeysFileError::InvalidPassword) => {}
+                result => panic!("expected an invalid password, found {:?}", result),
+            }
+            match MoneroKeysFile::<Mainnet>::from_keys_file(&keys_file, PASSWORD, 1) {
+                Err(KeysFileError::InvalidNetworkType(0, 2)) => {}
+                result => panic!("expected an invalid network type, found {:?}", result),
+            }
+        }
+
+        #[test]
+        fn invalid_keys_file() {
+            assert!(MoneroKeysFile::<N>::from_keys_file(&[0u8; 8], PASSWORD, 1).is_err());
+            assert!(MoneroKeysFile::<N>::from_keys_file(&[0u8; 10], PASSWORD, 1).is_err());
+        }
+
+        #[test]
+        fn invalid_key_data() {
+            let key_data = keys().to_key_data(&[0u8; 64], &[0u8; 8]);
+            assert!(read_portable_storage(&key_data).is_ok());
+            assert!(read_portable_storage(&key_data[1..]).is_err());
+            assert!(read_portable_storage(&key_data[..key_data.len() - 1]).is_err());
+
+            let mut trailing = key_data.clone();
+            trailing.push(0);
+            assert!(read_portable_storage(&trailing).is_err());
+
+            let mut nested = PORTABLE_STORAGE_HEADER.to_vec();
+            for _ in 0..=MAX_NESTING_DEPTH + 1 {
+                write_storage_varint(&mut nested, 1);
+                write_storage_name(&mut nested, "a");
+                nested.push(SERIALIZE_TYPE_OBJECT);
+            }
+            write_storage_varint(&mut nested, 0);
+            assert!(read_portable_storage(&nested).is_err());
+        }
+
+        #[test]
+        fn invalid_json() {
+            assert!(parse_json(b"{\"key_data\":\"abc}").is_err());
+            assert!(parse_json(b"{\"key_data\":1,}").is_err());
+            assert!(parse_json(b"{} {}").is_err());
+
+            let mut nested = b"{\"a\":".to_vec();
+            nested.extend(&[b'['; MAX_NESTING_DEPTH + 2]);
+            nested.extend(&[b']'; MAX_NESTING_DEPTH + 2]);
+            nested.push(b'}');
+            assert!(parse_json(&nested).is_err());
+            assert!(parse_json(&nested[1..]).is_err());
+            assert!(parse_json(b"{\"a\":\"\\ud800\"}").is_err());
+        }
+    }
+}
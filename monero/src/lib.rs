@@ -5,6 +5,8 @@
 
 #[macro_use]
 extern crate failure;
+#[macro_use]
+extern crate lazy_static;
 
 pub mod address;
 pub use self::address::*;
@@ -12,27 +14,63 @@ pub use self::address::*;
 pub mod amount;
 pub use self::amount::*;
 
+pub mod chacha;
+pub use self::chacha::*;
+
+pub mod cryptonight;
+pub use self::cryptonight::*;
+
 pub mod mnemonic;
 pub use self::mnemonic::*;
 
+pub mod extra;
+pub use self::extra::*;
+
 pub mod format;
 pub use self::format::*;
 
+pub mod key_image;
+pub use self::key_image::*;
+
+pub mod keys_file;
+pub use self::keys_file::*;
+
+pub mod message;
+pub use self::message::*;
+
+pub mod multisig;
+pub use self::multisig::*;
+
+pub mod mymonero;
+pub use self::mymonero::*;
+
 pub mod network;
 pub use self::network::*;
 
 pub mod one_time_key;
 pub use self::one_time_key::*;
 
+pub mod polyseed;
+pub use self::polyseed::*;
+
 pub mod private_key;
 pub use self::private_key::*;
 
+pub mod proof;
+pub use self::proof::*;
+
 pub mod public_key;
 pub use self::public_key::*;
 
-#[cfg(transaction)]
+pub mod ringct;
+
+pub mod scanner;
+pub use self::scanner::*;
+
+pub mod subaddress;
+pub use self::subaddress::*;
+
 pub mod transaction;
-#[cfg(transaction)]
 pub use self::transaction::*;
 
 pub mod wordlist;
@@ -0,0 +1,324 @@
+use crate::address::MoneroAddress;
+use crate::format::MoneroFormat;
+use crate::network::MoneroNetwork;
+use crate::private_key::MoneroPrivateKey;
+use crate::ringct::{read_point, write_varint, RingCtError, Signature};
+use wagyu_model::{AddressError, PrivateKey};
+
+use base58_monero as base58;
+use curve25519_dalek::scalar::Scalar;
+use rand::Rng;
+use std::{fmt, marker::PhantomData, str::FromStr};
+use tiny_keccak::keccak256;
+
+/// The domain separator of version 2 message signatures, including its null terminator
+const HASH_KEY_MESSAGE_SIGNING: &[u8] = b"MoneroMessageSignature\x00";
+
+/// The prefix of a message signature, followed by its version
+const SIGNATURE_HEADER: &str = "SigV";
+
+/// The length of the base58 encoding of a signature
+const ENCODED_SIGNATURE_LENGTH: usize = 88;
+
+#[derive(Debug, Fail)]
+pub enum MessageError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "invalid message signature header: {}", _0)]
+    InvalidHeader(String),
+
+    #[fail(display = "invalid message signature length: {}", _0)]
+    InvalidLength(usize),
+
+    #[fail(display = "invalid message signature")]
+    InvalidSignature,
+
+    #[fail(display = "{}", _0)]
+    RingCtError(RingCtError),
+}
+
+impl From<AddressError> for MessageError {
+    fn from(error: AddressError) -> Self {
+        MessageError::AddressError(error)
+    }
+}
+
+impl From<RingCtError> for MessageError {
+    fn from(error: RingCtError) -> Self {
+        MessageError::RingCtError(error)
+    }
+}
+
+/// Represents the key that signs a message
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MoneroMessageSigner {
+    /// The private spend key of the address
+    SpendKey,
+    /// The private view key of the address
+    ViewKey,
+}
+
+impl MoneroMessageSigner {
+    /// Returns the signature mode hashed with the message.
+    fn mode(&self) -> u8 {
+        match self {
+            MoneroMessageSigner::SpendKey => 0,
+            MoneroMessageSigner::ViewKey => 1,
+        }
+    }
+}
+
+/// Represents a signature of a message by an address, as made by `monero-wallet-cli sign`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroMessageSignature<N: MoneroNetwork> {
+    /// The version of the signature, 1 or 2
+    version: u8,
+    /// The signature
+    signature: Signature,
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: MoneroNetwork> MoneroMessageSignature<N> {
+    /// Returns the version 2 signature of the message by the spend or view key of the address
+    /// with the given subaddress index, where (0, 0) is the primary address.
+    pub fn sign<R: Rng>(
+        private_key: &MoneroPrivateKey<N>,
+        message: &[u8],
+        signer: MoneroMessageSigner,
+        subaddress: (u32, u32),
+        rng: &mut R,
+    ) -> Result<Self, MessageError> {
+        let (major, minor) = subaddress;
+        let format = match subaddress {
+            (0, 0) => MoneroFormat::Standard,
+            _ => MoneroFormat::Subaddress(major, minor),
+        };
+        let address = private_key.to_address(&format)?;
+
+        // The keys of a subaddress are D = (b + m)*G and C = a*D.
+        let private_spend_key = Scalar::from_bits(private_key.to_private_spend_key())
+            + Scalar::from_bits(private_key.to_subaddress_private_view_key(major, minor));
+        let secret_key = match signer {
+            MoneroMessageSigner::SpendKey => private_spend_key,
+            MoneroMessageSigner::ViewKey => match subaddress {
+                (0, 0) => Scalar::from_bits(private_key.to_private_view_key()),
+                _ => Scalar::from_bits(private_key.to_private_view_key()) * private_spend_key,
+            },
+        };
+
+        Ok(Self {
+            version: 2,
+            signature: Signature::sign(&message_hash(&address, message, signer)?, &secret_key, rng),
+            _network: PhantomData,
+        })
+    }
+
+    /// Verifies the signature of the message by the address and returns the key that signed it
+    /// (Monero `wallet2::verify`).
+    pub fn verify(&self, address: &MoneroAddress<N>, message: &[u8]) -> Result<MoneroMessageSigner, MessageError> {
+        let public_key = address.to_public_key()?;
+        let public_spend_key = read_point(&public_key.to_public_spend_key().unwrap_or_default())?;
+        let public_view_key = read_point(&public_key.to_public_view_key().unwrap_or_default())?;
+
+        // Version 1 signs the plain hash of the message with the spend key.
+        if self.version == 1 {
+            return match self.signature.verify(&keccak256(message), &public_spend_key) {
+                Ok(()) => Ok(MoneroMessageSigner::SpendKey),
+                Err(_) => Err(MessageError::InvalidSignature),
+            };
+        }
+
+        for (signer, public_key) in [
+            (MoneroMessageSigner::SpendKey, public_spend_key),
+            (MoneroMessageSigner::ViewKey, public_view_key),
+        ]
+        .iter()
+        {
+            let hash = message_hash(address, message, *signer)?;
+            if self.signature.verify(&hash, public_key).is_ok() {
+                return Ok(*signer);
+            }
+        }
+        Err(MessageError::InvalidSignature)
+    }
+
+    /// Returns the version of the signature.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl<N: MoneroNetwork> FromStr for MoneroMessageSignature<N> {
+    type Err = MessageError;
+
+    fn from_str(signature: &str) -> Result<Self, Self::Err> {
+        let header_length = SIGNATURE_HEADER.len() + 1;
+        let version = match signature.get(..header_length) {
+            Some("SigV1") => 1,
+            Some("SigV2") => 2,
+            _ => {
+                return Err(MessageError::InvalidHeader(
+                    signature.chars().take(header_length).collect(),
+                ))
+            }
+        };
+        if signature.len() != header_length + ENCODED_SIGNATURE_LENGTH {
+            return Err(MessageError::InvalidLength(signature.len()));
+        }
+
+        let bytes = base58::decode(&signature[header_length..])
+            .map_err(|error| MessageError::Crate("base58", format!("{:?}", error)))?;
+        Ok(Self {
+            version,
+            signature: Signature::from_bytes(&bytes)?,
+            _network: PhantomData,
+        })
+    }
+}
+
+impl<N: MoneroNetwork> fmt::Display for MoneroMessageSignature<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match base58::encode(&self.signature.to_bytes()) {
+            Ok(signature) => write!(f, "{}{}{}", SIGNATURE_HEADER, self.version, signature),
+            _ => Err(fmt::Error),
+        }
+    }
+}
+
+/// Returns the hash `H(domain || B || A || mode || varint(length) || message)` of a version 2 signature.
+fn message_hash<N: MoneroNetwork>(
+    address: &MoneroAddress<N>,
+    message: &[u8],
+    signer: MoneroMessageSigner,
+) -> Result<[u8; 32], MessageError> {
+    let public_key = address.to_public_key()?;
+    let mut data = HASH_KEY_MESSAGE_SIGNING.to_vec();
+    data.extend(&public_key.to_public_spend_key().unwrap_or_default());
+    data.extend(&public_key.to_public_view_key().unwrap_or_default());
+    data.push(signer.mode());
+    write_varint(&mut data, message.len() as u64);
+    data.extend(message);
+    Ok(keccak256(&data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Stagenet;
+    use curve25519_dalek::constants::ED25519_BASEPOINT_TABLE;
+
+    type N = Stagenet;
+
+    const PRIVATE_SPEND_KEY: &str = "0cf0c38429e00fa4abecb98296cb15dec209c0a7e6ea34ed86d32429498e4700";
+    const PRIVATE_VIEW_KEY: &str = "55c5b2fa94b2c5ee387eb9dd71b197a6358a5a90ed5eb5acdfa088583125a40a";
+    const OTHER_ADDRESS: &str =
+        "59McWTPGc745SRWrSMoh8oTjoXoQq6sPUgKZ66dQWXuKFQ2q19h9gvhJNZcFTizcnT12r63NFgHiGd6gBCjabzmzHAMoyD6";
+
+    const MESSAGES: [&str; 3] = ["", "wagyu", "The quick brown fox jumps over the lazy dog"];
+    const SUBADDRESSES: [(u32, u32); 3] = [(0, 0), (0, 1), (3, 7)];
+
+    fn private_key() -> MoneroPrivateKey<N> {
+        MoneroPrivateKey::from_private_keys(PRIVATE_SPEND_KEY, PRIVATE_VIEW_KEY, &MoneroFormat::Standard).unwrap()
+    }
+
+    fn address(subaddress: (u32, u32)) -> MoneroAddress<N> {
+        let format = match subaddress {
+            (0, 0) => MoneroFormat::Standard,
+            (major, minor) => MoneroFormat::Subaddress(major, minor),
+        };
+        private_key().to_address(&format).unwrap()
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let rng = &mut rand::thread_rng();
+        for subaddress in SUBADDRESSES.iter() {
+            for signer in [MoneroMessageSigner::SpendKey, MoneroMessageSigner::ViewKey].iter() {
+                MESSAGES.iter().for_each(|message| {
+                    let signature =
+                        MoneroMessageSignature::sign(&private_key(), message.as_bytes(), *signer, *subaddress, rng)
+                            .unwrap();
+                    assert_eq!(
+                        *signer,
+                        signature.verify(&address(*subaddress), message.as_bytes()).unwrap()
+                    );
+
+                    let encoded = signature.to_string();
+                    assert!(encoded.starts_with("SigV2"));
+                    assert_eq!(93, encoded.len());
+                    assert_eq!(signature, MoneroMessageSignature::from_str(&encoded).unwrap());
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn verify_v1() {
+        // Version 1 signs the plain hash of the message with the private spend key.
+        let secret_key = Scalar::from_bits(private_key().to_private_spend_key());
+        let signature = Signature::sign(&keccak256(b"wagyu"), &secret_key, &mut rand::thread_rng());
+        let encoded = format!("SigV1{}", base58::encode(&signature.to_bytes()).unwrap());
+
+        let signature = MoneroMessageSignature::<N>::from_str(&encoded).unwrap();
+        assert_eq!(1, signature.version());
+        assert_eq!(
+            MoneroMessageSigner::SpendKey,
+            signature.verify(&address((0, 0)), b"wagyu").unwrap()
+        );
+        assert_eq!(encoded, signature.to_string());
+    }
+
+    #[test]
+    fn message_hash_domain() {
+        // The view key signature is not a valid spend key signature of the same message.
+        let address = address((0, 0));
+        let spend_hash = message_hash(&address, b"wagyu", MoneroMessageSigner::SpendKey).unwrap();
+        let view_hash = message_hash(&address, b"wagyu", MoneroMessageSigner::ViewKey).unwrap();
+        assert_ne!(spend_hash, view_hash);
+        assert_ne!(keccak256(b"wagyu"), spend_hash);
+
+        let private_view_key = Scalar::from_bits(private_key().to_private_view_key());
+        let public_view_key = &private_view_key * &ED25519_BASEPOINT_TABLE;
+        let signature = Signature::sign(&view_hash, &private_view_key, &mut rand::thread_rng());
+        assert!(signature.verify(&view_hash, &public_view_key).is_ok());
+        assert!(signature.verify(&spend_hash, &public_view_key).is_err());
+    }
+
+    mod invalid {
+        use super::*;
+
+        const INVALID_SIGNATURES: [&str; 4] = [
+            "",
+            "SigV3",
+            "SigV2",
+            "SigV25ceCv6f3pZS3wHj4AGdm8R6wdbwrFzNhJaaoqQ2QCRZ8iC9dDrGNUN5ga9Nk1ZB9XSdB4hk1HFcp91AGHhkDxixwh4a",
+        ];
+
+        #[test]
+        fn verify_invalid_signature() {
+            let rng = &mut rand::thread_rng();
+            let signature =
+                MoneroMessageSignature::sign(&private_key(), b"wagyu", MoneroMessageSigner::SpendKey, (0, 1), rng)
+                    .unwrap();
+
+            // Another message, the primary address of the subaddress, or another wallet
+            assert!(signature.verify(&address((0, 1)), b"wagy").is_err());
+            assert!(signature.verify(&address((0, 0)), b"wagyu").is_err());
+            assert!(signature
+                .verify(&MoneroAddress::from_str(OTHER_ADDRESS).unwrap(), b"wagyu")
+                .is_err());
+        }
+
+        #[test]
+        fn from_invalid_signature() {
+            INVALID_SIGNATURES.iter().for_each(|signature| {
+                assert!(MoneroMessageSignature::<N>::from_str(signature).is_err());
+            });
+        }
+    }
+}
@@ -39,45 +39,9 @@ impl<N: MoneroNetwork, W: MoneroWordlist> Mnemonic for MoneroMnemonic<N, W> {
 
     /// Returns the mnemonic for the given phrase.
     fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
-        let length = 1626;
-        let words = phrase.split(" ").collect::<Vec<&str>>();
-        let mut phrase = words.iter().map(|word| word.to_string()).collect::<Vec<String>>();
-
-        if phrase.len() % 3 == 2 {
-            return Err(MnemonicError::MissingWord);
-        } else if phrase.len() % 3 == 0 {
-            return Err(MnemonicError::MissingChecksumWord);
-        }
-
-        let checksum = match phrase.pop() {
-            Some(word) => word,
-            _ => return Err(MnemonicError::MissingWord),
-        };
-
-        // Decode the phrase
-        let mut buffer = vec![];
-        let chunks = phrase.chunks(3);
-        for chunk in chunks {
-            let w1 = W::get_index_trimmed(&W::to_trimmed(&chunk[0]))?;
-            let w2 = W::get_index_trimmed(&W::to_trimmed(&chunk[1]))?;
-            let w3 = W::get_index_trimmed(&W::to_trimmed(&chunk[2]))?;
-
-            let n = length;
-            let x = w1 + n * (((n - w1) + w2) % n) + n * n * (((n - w2) + w3) % n);
-
-            if x % n != w1 {
-                return Err(MnemonicError::InvalidDecoding);
-            }
-
-            buffer.extend_from_slice(&u32::to_le_bytes(x as u32));
-        }
-
-        // Verify the checksum
-        let expected_checksum = Self::checksum_word(&phrase.into());
-        if W::to_trimmed(&expected_checksum) != W::to_trimmed(&checksum) {
-            let expected = W::to_trimmed(&expected_checksum);
-            let found = W::to_trimmed(&checksum);
-            return Err(MnemonicError::InvalidChecksumWord(expected, found));
+        let buffer = decode_phrase::<W>(phrase)?;
+        if buffer.len() != 32 {
+            return Err(MnemonicError::InvalidWordCount(phrase.split(" ").count() as u8));
         }
 
         let mut seed = [0u8; 32];
@@ -91,36 +55,7 @@ impl<N: MoneroNetwork, W: MoneroWordlist> Mnemonic for MoneroMnemonic<N, W> {
     }
 
     fn to_phrase(&self) -> Result<String, MnemonicError> {
-        let seed = &Scalar::from_bytes_mod_order(self.seed).to_bytes();
-
-        // Reverse the endian in 4 byte intervals
-        let length = 1626;
-        let inputs = seed
-            .chunks(4)
-            .map(|chunk| {
-                let mut input: [u8; 4] = [0u8; 4];
-                input.copy_from_slice(chunk);
-
-                u32::from_le_bytes(input)
-            })
-            .collect::<Vec<u32>>();
-
-        // Generate three words from every 4 byte interval
-        let mut phrase = vec![];
-        for index in inputs {
-            let w1 = index % length;
-            let w2 = ((index / length) + w1) % length;
-            let w3 = (((index / length) / length) + w2) % length;
-
-            phrase.push(W::get(w1 as usize)?);
-            phrase.push(W::get(w2 as usize)?);
-            phrase.push(W::get(w3 as usize)?);
-        }
-
-        // Compute the checksum word
-        phrase.push(Self::checksum_word(&phrase));
-
-        Ok(phrase.join(" "))
+        encode_phrase::<W>(&Scalar::from_bytes_mod_order(self.seed).to_bytes())
     }
 
     /// Returns the private key of the corresponding mnemonic.
@@ -156,15 +91,93 @@ impl<N: MoneroNetwork, W: MoneroWordlist> MoneroMnemonic<N, W> {
     pub fn verify_phrase(phrase: &str) -> bool {
         Self::from_phrase(phrase).is_ok()
     }
+}
+
+/// Returns the phrase of the given bytes, three words for every 4 bytes followed by a checksum word.
+pub(crate) fn encode_phrase<W: MoneroWordlist>(bytes: &[u8]) -> Result<String, MnemonicError> {
+    // Reverse the endian in 4 byte intervals
+    let length = 1626;
+    let inputs = bytes
+        .chunks(4)
+        .map(|chunk| {
+            let mut input: [u8; 4] = [0u8; 4];
+            input.copy_from_slice(chunk);
+
+            u32::from_le_bytes(input)
+        })
+        .collect::<Vec<u32>>();
+
+    // Generate three words from every 4 byte interval
+    let mut phrase = vec![];
+    for index in inputs {
+        let w1 = index % length;
+        let w2 = ((index / length) + w1) % length;
+        let w3 = (((index / length) / length) + w2) % length;
+
+        phrase.push(W::get(w1 as usize)?);
+        phrase.push(W::get(w2 as usize)?);
+        phrase.push(W::get(w3 as usize)?);
+    }
+
+    // Compute the checksum word
+    phrase.push(checksum_word::<W>(&phrase));
+
+    Ok(phrase.join(" "))
+}
+
+/// Returns the bytes of the given phrase after verifying its checksum word.
+pub(crate) fn decode_phrase<W: MoneroWordlist>(phrase: &str) -> Result<Vec<u8>, MnemonicError> {
+    let length = 1626;
+    let words = phrase.split(" ").collect::<Vec<&str>>();
+    let mut phrase = words.iter().map(|word| word.to_string()).collect::<Vec<String>>();
+
+    if phrase.len() % 3 == 2 {
+        return Err(MnemonicError::MissingWord);
+    } else if phrase.len() % 3 == 0 {
+        return Err(MnemonicError::MissingChecksumWord);
+    }
+
+    let checksum = match phrase.pop() {
+        Some(word) => word,
+        _ => return Err(MnemonicError::MissingWord),
+    };
 
-    /// Returns the checksum word for a given phrase.
-    fn checksum_word(phrase: &Vec<String>) -> String {
-        let phrase_trimmed = phrase.iter().map(|word| W::to_trimmed(word)).collect::<Vec<String>>();
+    // Decode the phrase
+    let mut buffer = vec![];
+    let chunks = phrase.chunks(3);
+    for chunk in chunks {
+        let w1 = W::get_index_trimmed(&W::to_trimmed(&chunk[0]))?;
+        let w2 = W::get_index_trimmed(&W::to_trimmed(&chunk[1]))?;
+        let w3 = W::get_index_trimmed(&W::to_trimmed(&chunk[2]))?;
 
-        let mut digest = crc32::Digest::new(crc32::IEEE);
-        digest.write(phrase_trimmed.concat().as_bytes());
-        phrase[(digest.sum32() % phrase.len() as u32) as usize].clone()
+        let n = length;
+        let x = w1 + n * (((n - w1) + w2) % n) + n * n * (((n - w2) + w3) % n);
+
+        if x % n != w1 {
+            return Err(MnemonicError::InvalidDecoding);
+        }
+
+        buffer.extend_from_slice(&u32::to_le_bytes(x as u32));
+    }
+
+    // Verify the checksum
+    let expected_checksum = checksum_word::<W>(&phrase);
+    if W::to_trimmed(&expected_checksum) != W::to_trimmed(&checksum) {
+        let expected = W::to_trimmed(&expected_checksum);
+        let found = W::to_trimmed(&checksum);
+        return Err(MnemonicError::InvalidChecksumWord(expected, found));
     }
+
+    Ok(buffer)
+}
+
+/// Returns the checksum word for a given phrase.
+fn checksum_word<W: MoneroWordlist>(phrase: &Vec<String>) -> String {
+    let phrase_trimmed = phrase.iter().map(|word| W::to_trimmed(word)).collect::<Vec<String>>();
+
+    let mut digest = crc32::Digest::new(crc32::IEEE);
+    digest.write(phrase_trimmed.concat().as_bytes());
+    phrase[(digest.sum32() % phrase.len() as u32) as usize].clone()
 }
 
 impl<N: MoneroNetwork, W: MoneroWordlist> FromStr for MoneroMnemonic<N, W> {
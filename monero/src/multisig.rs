@@ -0,0 +1,982 @@
+use crate::address::MoneroAddress;
+use crate::format::MoneroFormat;
+use crate::key_image::{one_time_private_key, KeyImageError};
+use crate::network::MoneroNetwork;
+use crate::private_key::MoneroPrivateKey;
+use crate::public_key::MoneroPublicKey;
+use crate::ringct::*;
+use crate::scanner::MoneroOwnedOutput;
+use wagyu_model::{Address, AddressError, PrivateKeyError, PublicKeyError};
+
+use base58_monero as base58;
+use curve25519_dalek::{
+    constants::ED25519_BASEPOINT_TABLE,
+    edwards::EdwardsPoint,
+    scalar::Scalar,
+    traits::{Identity, IsIdentity},
+};
+use rand::Rng;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    marker::PhantomData,
+    str::FromStr,
+};
+use tiny_keccak::keccak256;
+
+/// The prefixes of the key exchange messages of the first round and of the later rounds
+const KEX_MESSAGE_ROUND_1_MAGIC: &str = "MultisigxV2R1";
+const KEX_MESSAGE_ROUND_N_MAGIC: &str = "MultisigxV2Rn";
+
+/// The domain separators of multisig private keys and of key aggregation coefficients,
+/// which are zero padded to 32 bytes
+const HASH_KEY_MULTISIG: &[u8] = b"Multisig";
+const HASH_KEY_MULTISIG_KEY_AGGREGATION: &[u8] = b"Multisig_key_agg";
+
+/// The maximum number of signers of a multisig wallet
+pub const MULTISIG_MAX_SIGNERS: usize = 16;
+
+#[derive(Debug, Fail)]
+pub enum MultisigError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "the key exchange is already complete")]
+    CompleteKeyExchange,
+
+    #[fail(display = "the key exchange is incomplete")]
+    IncompleteKeyExchange,
+
+    #[fail(display = "invalid key exchange message: {}", _0)]
+    InvalidKexMessage(String),
+
+    #[fail(display = "invalid key exchange round: expected {}, found {}", _0, _1)]
+    InvalidKexRound(u32, u32),
+
+    #[fail(display = "invalid key exchange message signature")]
+    InvalidSignature,
+
+    #[fail(display = "invalid number of signers: {}", _0)]
+    InvalidSignerCount(usize),
+
+    #[fail(display = "invalid threshold {} of {} signers", _0, _1)]
+    InvalidThreshold(u32, usize),
+
+    #[fail(display = "{}", _0)]
+    KeyImageError(KeyImageError),
+
+    #[fail(display = "{}", _0)]
+    PrivateKeyError(PrivateKeyError),
+
+    #[fail(display = "{}", _0)]
+    PublicKeyError(PublicKeyError),
+
+    #[fail(display = "{}", _0)]
+    RingCtError(RingCtError),
+
+    #[fail(display = "unknown signer {}", _0)]
+    UnknownSigner(String),
+}
+
+impl From<AddressError> for MultisigError {
+    fn from(error: AddressError) -> Self {
+        MultisigError::AddressError(error)
+    }
+}
+
+impl From<KeyImageError> for MultisigError {
+    fn from(error: KeyImageError) -> Self {
+        MultisigError::KeyImageError(error)
+    }
+}
+
+impl From<PrivateKeyError> for MultisigError {
+    fn from(error: PrivateKeyError) -> Self {
+        MultisigError::PrivateKeyError(error)
+    }
+}
+
+impl From<PublicKeyError> for MultisigError {
+    fn from(error: PublicKeyError) -> Self {
+        MultisigError::PublicKeyError(error)
+    }
+}
+
+impl From<RingCtError> for MultisigError {
+    fn from(error: RingCtError) -> Self {
+        MultisigError::RingCtError(error)
+    }
+}
+
+/// Represents a message of a round of the multisig key exchange, as passed between the signers
+/// of `monero-wallet-cli prepare_multisig`, `make_multisig` and `exchange_multisig_keys`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroMultisigKexMessage {
+    /// The round of the key exchange
+    round: u32,
+    /// The base public key of the signer, which signs the message
+    signing_public_key: [u8; 32],
+    /// The base common private key of the signer, sent in the first round
+    private_key: Option<[u8; 32]>,
+    /// The public keys of the signer, sent in the later rounds
+    public_keys: Vec<[u8; 32]>,
+    /// The signature of the message
+    signature: Signature,
+}
+
+impl MoneroMultisigKexMessage {
+    /// Returns the first round message with the base common private key of the signer.
+    fn new_first_round<R: Rng>(signing_private_key: &Scalar, private_key: &Scalar, rng: &mut R) -> Self {
+        Self::signed(1, signing_private_key, Some(private_key.to_bytes()), vec![], rng)
+    }
+
+    /// Returns the message of a later round with the public keys of the signer.
+    fn new<R: Rng>(round: u32, signing_private_key: &Scalar, public_keys: Vec<[u8; 32]>, rng: &mut R) -> Self {
+        Self::signed(round, signing_private_key, None, public_keys, rng)
+    }
+
+    fn signed<R: Rng>(
+        round: u32,
+        signing_private_key: &Scalar,
+        private_key: Option<[u8; 32]>,
+        public_keys: Vec<[u8; 32]>,
+        rng: &mut R,
+    ) -> Self {
+        let mut message = Self {
+            round,
+            signing_public_key: (signing_private_key * &ED25519_BASEPOINT_TABLE).compress().to_bytes(),
+            private_key,
+            public_keys,
+            signature: Signature {
+                c: Scalar::zero(),
+                r: Scalar::zero(),
+            },
+        };
+        message.signature = Signature::sign(&message.to_signature_message(), signing_private_key, rng);
+        message
+    }
+
+    /// Returns the round of the key exchange of the message.
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    /// Returns the base public key of the signer of the message.
+    pub fn signing_public_key(&self) -> [u8; 32] {
+        self.signing_public_key
+    }
+
+    /// Returns the public keys of the message, which are empty in the first round.
+    pub fn public_keys(&self) -> &[[u8; 32]] {
+        &self.public_keys
+    }
+
+    /// Returns the serialized message, a Monero binary archive.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = vec![];
+        match self.private_key {
+            Some(private_key) => buffer.extend(&private_key),
+            None => {
+                write_varint(&mut buffer, self.round as u64);
+                write_varint(&mut buffer, self.public_keys.len() as u64);
+                self.public_keys.iter().for_each(|key| buffer.extend(key));
+            }
+        };
+        buffer.extend(&self.signing_public_key);
+        buffer.extend(self.signature.to_bytes().iter());
+        buffer
+    }
+
+    /// Returns the hash `H(magic || round || K_signing || k_common)` of the first round or
+    /// `H(magic || round || K_signing || public keys)` of the later rounds.
+    fn to_signature_message(&self) -> [u8; 32] {
+        let mut data = match self.round {
+            1 => KEX_MESSAGE_ROUND_1_MAGIC,
+            _ => KEX_MESSAGE_ROUND_N_MAGIC,
+        }
+        .as_bytes()
+        .to_vec();
+        data.extend(&self.round.to_le_bytes());
+        data.extend(&self.signing_public_key);
+        match self.private_key {
+            Some(private_key) => data.extend(&private_key),
+            None => self.public_keys.iter().for_each(|key| data.extend(key)),
+        };
+        keccak256(&data)
+    }
+}
+
+impl FromStr for MoneroMultisigKexMessage {
+    type Err = MultisigError;
+
+    fn from_str(message: &str) -> Result<Self, Self::Err> {
+        let first_round = message.starts_with(KEX_MESSAGE_ROUND_1_MAGIC);
+        if !first_round && !message.starts_with(KEX_MESSAGE_ROUND_N_MAGIC) {
+            return Err(MultisigError::InvalidKexMessage(format!(
+                "invalid magic {}",
+                message
+                    .chars()
+                    .take(KEX_MESSAGE_ROUND_1_MAGIC.len())
+                    .collect::<String>()
+            )));
+        }
+        let bytes = base58::decode(&message[KEX_MESSAGE_ROUND_1_MAGIC.len()..])
+            .map_err(|error| MultisigError::Crate("base58", format!("{:?}", error)))?;
+
+        let mut offset = 0;
+        let read_key = |offset: &mut usize| -> Result<[u8; 32], MultisigError> {
+            let mut key = [0u8; 32];
+            key.copy_from_slice(take(&bytes, offset, 32)?);
+            Ok(key)
+        };
+
+        let (round, private_key, public_keys) = match first_round {
+            true => {
+                // The base common private key is a nonzero canonical scalar.
+                let private_key = read_key(&mut offset)?;
+                if read_scalar(&private_key)? == Scalar::zero() {
+                    return Err(MultisigError::InvalidKexMessage("zero private key".into()));
+                }
+                (1, Some(private_key), vec![])
+            }
+            false => {
+                let (round, length) = read_varint(&bytes)?;
+                offset += length;
+                if round < 2 || round > u32::max_value() as u64 {
+                    return Err(MultisigError::InvalidKexMessage(format!("invalid round {}", round)));
+                }
+
+                let (count, length) = read_varint(&bytes[offset..])?;
+                offset += length;
+                let mut public_keys = vec![];
+                for _ in 0..count {
+                    // The public keys are in the prime order subgroup.
+                    let public_key = read_key(&mut offset)?;
+                    let point = read_point(&public_key)?;
+                    if point.is_identity() || !point.is_torsion_free() {
+                        return Err(MultisigError::InvalidKexMessage("invalid public key".into()));
+                    }
+                    public_keys.push(public_key);
+                }
+                (round as u32, None, public_keys)
+            }
+        };
+
+        let signing_public_key = read_key(&mut offset)?;
+        let signature = Signature::from_bytes(take(&bytes, &mut offset, 64)?)?;
+        if offset != bytes.len() {
+            return Err(MultisigError::InvalidKexMessage(format!(
+                "{} trailing bytes",
+                bytes.len() - offset
+            )));
+        }
+
+        let message = Self {
+            round,
+            signing_public_key,
+            private_key,
+            public_keys,
+            signature,
+        };
+        match message
+            .signature
+            .verify(&message.to_signature_message(), &read_point(&signing_public_key)?)
+        {
+            Ok(()) => Ok(message),
+            Err(_) => Err(MultisigError::InvalidSignature),
+        }
+    }
+}
+
+impl fmt::Display for MoneroMultisigKexMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let magic = match self.round {
+            1 => KEX_MESSAGE_ROUND_1_MAGIC,
+            _ => KEX_MESSAGE_ROUND_N_MAGIC,
+        };
+        match base58::encode(&self.to_bytes()) {
+            Ok(message) => write!(f, "{}{}", magic, message),
+            _ => Err(fmt::Error),
+        }
+    }
+}
+
+/// Represents the account of a signer of an M-of-N multisig wallet
+///
+/// The signers exchange `N - M + 1` rounds of messages to derive the shares of the private spend
+/// key, followed by a round to verify that all signers derived the same public spend key.
+#[derive(Debug, Clone)]
+pub struct MoneroMultisigAccount<N: MoneroNetwork> {
+    /// The base private key of the signer, derived from its private spend key
+    base_private_key: Scalar,
+    /// The base common private key of the signer, derived from its private view key
+    base_common_private_key: Scalar,
+    /// The base public key of the signer
+    base_public_key: [u8; 32],
+    /// The number of signers needed to sign a transaction
+    threshold: u32,
+    /// The sorted base public keys of all signers
+    signers: Vec<[u8; 32]>,
+    /// The private view key of the wallet, shared by all signers
+    common_private_key: Scalar,
+    /// The shares of the private spend key held by the signer
+    multisig_private_keys: Vec<Scalar>,
+    /// The public spend key of the wallet
+    multisig_public_key: Option<[u8; 32]>,
+    /// The keys the signer sent in the current round, which are ignored in the messages of other signers
+    kex_keys: Vec<[u8; 32]>,
+    /// The number of completed rounds of the key exchange
+    rounds_complete: u32,
+    /// The message of the signer for the next round
+    next_message: MoneroMultisigKexMessage,
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: MoneroNetwork> MoneroMultisigAccount<N> {
+    /// Returns a multisig account for the private key of a signer, ready to send its first round
+    /// message (Monero `wallet2::prepare_multisig`).
+    pub fn new<R: Rng>(private_key: &MoneroPrivateKey<N>, rng: &mut R) -> Result<Self, MultisigError> {
+        let base_private_key = blinded_secret_key(&private_key.to_private_spend_key());
+        let base_common_private_key = blinded_secret_key(&private_key.to_private_view_key());
+        Ok(Self {
+            base_private_key,
+            base_common_private_key,
+            base_public_key: (&base_private_key * &ED25519_BASEPOINT_TABLE).compress().to_bytes(),
+            threshold: 0,
+            signers: vec![],
+            common_private_key: Scalar::zero(),
+            multisig_private_keys: vec![],
+            multisig_public_key: None,
+            kex_keys: vec![],
+            rounds_complete: 0,
+            next_message: MoneroMultisigKexMessage::new_first_round(&base_private_key, &base_common_private_key, rng),
+            _network: PhantomData,
+        })
+    }
+
+    /// Starts the key exchange of an M-of-N wallet with the first round messages of the other
+    /// signers (Monero `wallet2::make_multisig`).
+    pub fn initialize<R: Rng>(
+        &mut self,
+        threshold: u32,
+        messages: &[MoneroMultisigKexMessage],
+        rng: &mut R,
+    ) -> Result<(), MultisigError> {
+        if !self.signers.is_empty() {
+            return Err(MultisigError::InvalidKexMessage(
+                "the key exchange is already initialized".into(),
+            ));
+        }
+
+        // The base common private key of each signer
+        let mut common_private_keys = BTreeMap::new();
+        common_private_keys.insert(self.base_public_key, self.base_common_private_key.to_bytes());
+        for message in messages {
+            match message.private_key {
+                Some(private_key) if message.round == 1 => {
+                    common_private_keys.insert(message.signing_public_key, private_key);
+                }
+                _ => return Err(MultisigError::InvalidKexRound(1, message.round)),
+            };
+        }
+
+        let signers: Vec<[u8; 32]> = common_private_keys.keys().cloned().collect();
+        if signers.len() < 2 || signers.len() > MULTISIG_MAX_SIGNERS {
+            return Err(MultisigError::InvalidSignerCount(signers.len()));
+        }
+        if threshold == 0 || threshold as usize > signers.len() {
+            return Err(MultisigError::InvalidThreshold(threshold, signers.len()));
+        }
+
+        // The private view key is the hash of the sorted base common private keys.
+        let mut private_keys: Vec<[u8; 32]> = common_private_keys.values().cloned().collect();
+        private_keys.sort();
+        self.common_private_key = hash_to_scalar(&private_keys.concat());
+        self.threshold = threshold;
+        self.signers = signers;
+
+        // The base private key of an N-of-N wallet is the share of the private spend key.
+        if self.kex_rounds_required() == 1 {
+            self.multisig_private_keys = vec![self.base_private_key];
+            self.kex_keys = vec![self.base_public_key];
+        }
+        self.update(messages, rng)
+    }
+
+    /// Processes the messages of the other signers for the current round and prepares the message
+    /// for the next round (Monero `wallet2::exchange_multisig_keys`).
+    pub fn update<R: Rng>(&mut self, messages: &[MoneroMultisigKexMessage], rng: &mut R) -> Result<(), MultisigError> {
+        let rounds_required = self.kex_rounds_required();
+        if self.signers.is_empty() {
+            return Err(MultisigError::IncompleteKeyExchange);
+        }
+        if self.rounds_complete > rounds_required {
+            return Err(MultisigError::CompleteKeyExchange);
+        }
+
+        // The keys of the messages, each with the signers that sent it
+        let round = self.rounds_complete + 1;
+        let mut origins: BTreeMap<[u8; 32], BTreeSet<[u8; 32]>> = BTreeMap::new();
+        let mut senders = BTreeSet::new();
+        for message in messages {
+            if message.round != round {
+                return Err(MultisigError::InvalidKexRound(round, message.round));
+            }
+            if !self.signers.contains(&message.signing_public_key) {
+                return Err(MultisigError::UnknownSigner(hex::encode(message.signing_public_key)));
+            }
+            if message.signing_public_key == self.base_public_key {
+                continue;
+            }
+
+            senders.insert(message.signing_public_key);
+            match round {
+                1 => vec![message.signing_public_key],
+                _ => message.public_keys.clone(),
+            }
+            .into_iter()
+            .filter(|key| !self.kex_keys.contains(key))
+            .for_each(|key| {
+                origins.entry(key).or_default().insert(message.signing_public_key);
+            });
+        }
+        origins.remove(&self.base_public_key);
+
+        let signers = self.signers.len();
+        if senders.len() != signers - 1 {
+            return Err(MultisigError::InvalidKexMessage(format!(
+                "expected messages from {} signers, found {}",
+                signers - 1,
+                senders.len()
+            )));
+        }
+
+        // Each key of a round is shared by a group of signers of the size of the round, and the keys
+        // of the other signers are those of the groups without this signer.
+        if round <= rounds_required {
+            let expected = binomial(signers as u64 - 1, round as u64) as usize;
+            if origins.len() != expected || origins.values().any(|origins| origins.len() != round as usize) {
+                return Err(MultisigError::InvalidKexMessage(format!(
+                    "expected {} keys, each from {} signers",
+                    expected, round
+                )));
+            }
+        } else {
+            let verified = match (origins.len(), self.multisig_public_key) {
+                (1, Some(public_key)) => origins.get(&public_key).map(|origins| origins.len()) == Some(signers - 1),
+                _ => false,
+            };
+            if !verified {
+                return Err(MultisigError::InvalidKexMessage(
+                    "the signers derived different public spend keys".into(),
+                ));
+            }
+        }
+
+        let keys: Vec<[u8; 32]> = origins.keys().cloned().collect();
+        let next_keys = if self.rounds_complete == rounds_required {
+            // After the verification round, the message recommends the public spend key again.
+            vec![self.multisig_public_key.unwrap_or_default()]
+        } else if self.rounds_complete + 1 == rounds_required {
+            // The final round aggregates the shares of the other signers with those of this signer.
+            let (public_key, private_keys) = aggregate_keys(keys, &self.multisig_private_keys)?;
+            self.multisig_private_keys = private_keys;
+            self.multisig_public_key = Some(public_key);
+            self.kex_keys.clear();
+            vec![public_key]
+        } else {
+            // D = 8 * k_base * K is shared with the signers of K.
+            let derivations = keys
+                .iter()
+                .map(|key| {
+                    let derivation = self.base_private_key * read_point(key)?;
+                    Ok(derivation.mul_by_cofactor().compress().to_bytes())
+                })
+                .collect::<Result<Vec<[u8; 32]>, MultisigError>>()?;
+
+            // Before the final round, the derivations become the shares k = H(D) of the private spend key.
+            if self.rounds_complete + 2 == rounds_required {
+                self.multisig_private_keys = derivations.iter().map(blinded_secret_key).collect();
+                self.kex_keys = self
+                    .multisig_private_keys
+                    .iter()
+                    .map(|key| (key * &ED25519_BASEPOINT_TABLE).compress().to_bytes())
+                    .collect();
+            } else {
+                self.kex_keys = derivations;
+            }
+            self.kex_keys.clone()
+        };
+
+        self.rounds_complete += 1;
+        let next_round = self.rounds_complete.min(rounds_required) + 1;
+        self.next_message = MoneroMultisigKexMessage::new(next_round, &self.base_private_key, next_keys, rng);
+        Ok(())
+    }
+
+    /// Returns the message of the signer for the next round.
+    pub fn to_kex_message(&self) -> &MoneroMultisigKexMessage {
+        &self.next_message
+    }
+
+    /// Returns the number of rounds `N - M + 1` that derive the public spend key, which are
+    /// followed by a verification round.
+    pub fn kex_rounds_required(&self) -> u32 {
+        match self.signers.len() {
+            0 => 0,
+            signers => signers as u32 - self.threshold + 1,
+        }
+    }
+
+    /// Returns the number of completed rounds of the key exchange.
+    pub fn rounds_complete(&self) -> u32 {
+        self.rounds_complete
+    }
+
+    /// Returns true if the key exchange and its verification round are complete.
+    pub fn is_ready(&self) -> bool {
+        !self.signers.is_empty() && self.rounds_complete > self.kex_rounds_required()
+    }
+
+    /// Returns the threshold of the wallet.
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// Returns the sorted base public keys of the signers.
+    pub fn signers(&self) -> &[[u8; 32]] {
+        &self.signers
+    }
+
+    /// Returns the public spend key of the wallet.
+    pub fn to_public_spend_key(&self) -> Result<[u8; 32], MultisigError> {
+        self.multisig_public_key.ok_or(MultisigError::IncompleteKeyExchange)
+    }
+
+    /// Returns the private view key of the wallet, which is shared by all signers.
+    pub fn to_private_view_key(&self) -> Result<[u8; 32], MultisigError> {
+        match self.signers.is_empty() {
+            true => Err(MultisigError::IncompleteKeyExchange),
+            false => Ok(self.common_private_key.to_bytes()),
+        }
+    }
+
+    /// Returns the shares of the private spend key held by the signer.
+    pub fn to_multisig_private_keys(&self) -> Result<Vec<[u8; 32]>, MultisigError> {
+        self.to_public_spend_key()?;
+        Ok(self.multisig_private_keys.iter().map(Scalar::to_bytes).collect())
+    }
+
+    /// Returns the address of the wallet.
+    pub fn to_address(&self) -> Result<MoneroAddress<N>, MultisigError> {
+        let public_view_key = &self.common_private_key * &ED25519_BASEPOINT_TABLE;
+        let public_key = MoneroPublicKey::<N>::from(
+            &hex::encode(self.to_public_spend_key()?),
+            &hex::encode(public_view_key.compress().as_bytes()),
+            &MoneroFormat::Standard,
+        )?;
+        Ok(MoneroAddress::from_public_key(&public_key, &MoneroFormat::Standard)?)
+    }
+
+    /// Returns the partial key images `k * Hp(P)` of an output for each share of the private spend key
+    /// held by the signer, which are sent to the other signers (Monero `generate_multisig_key_image`).
+    pub fn to_partial_key_images(&self, output_public_key: &[u8; 32]) -> Result<Vec<[u8; 32]>, MultisigError> {
+        self.to_public_spend_key()?;
+        let point = hash_to_point(output_public_key);
+        Ok(self
+            .multisig_private_keys
+            .iter()
+            .map(|key| (key * point).compress().to_bytes())
+            .collect())
+    }
+
+    /// Returns the key image of an owned output given the partial key images of the other signers,
+    /// which must cover the shares of the private spend key missing from this signer
+    /// (Monero `generate_multisig_composite_key_image`).
+    pub fn to_key_image(
+        &self,
+        output: &MoneroOwnedOutput,
+        partial_key_images: &[[u8; 32]],
+    ) -> Result<[u8; 32], MultisigError> {
+        // The key image of the shares held by the signer, (H_s(8aR || i) + sum(k)) * Hp(P)
+        let private_spend_key = self.multisig_private_keys.iter().sum::<Scalar>();
+        let private_key = MoneroPrivateKey::<N>::from_private_keys(
+            &hex::encode(private_spend_key.to_bytes()),
+            &hex::encode(self.to_private_view_key()?),
+            &MoneroFormat::Standard,
+        )?;
+        let (_, key_image) = one_time_private_key(&private_key, output)?;
+
+        // Each other share is added once.
+        let mut used: BTreeSet<[u8; 32]> = self.to_partial_key_images(&output.public_key)?.into_iter().collect();
+        let mut key_image = read_point(&key_image)?;
+        for partial_key_image in partial_key_images {
+            if used.insert(*partial_key_image) {
+                key_image += read_point(partial_key_image)?;
+            }
+        }
+        Ok(key_image.compress().to_bytes())
+    }
+}
+
+/// Returns the multisig private key `H_s(key || "Multisig")` of a secret key or a derivation
+/// (Monero `get_multisig_blinded_secret_key`).
+fn blinded_secret_key(key: &[u8; 32]) -> Scalar {
+    let mut data = key.to_vec();
+    data.extend(&salt(HASH_KEY_MULTISIG));
+    hash_to_scalar(&data)
+}
+
+/// Returns the public spend key `sum(H_s(K_i || sorted keys || "Multisig_key_agg") * K_i)` of the
+/// shares of the other signers and of this signer, and the shares of this signer multiplied by their
+/// aggregation coefficients (Monero `generate_multisig_aggregate_key`).
+fn aggregate_keys(
+    other_keys: Vec<[u8; 32]>,
+    private_keys: &[Scalar],
+) -> Result<([u8; 32], Vec<Scalar>), MultisigError> {
+    let own_keys: Vec<[u8; 32]> = private_keys
+        .iter()
+        .map(|key| (key * &ED25519_BASEPOINT_TABLE).compress().to_bytes())
+        .collect();
+
+    let mut keys = other_keys;
+    keys.extend(&own_keys);
+    keys.sort();
+    let count = keys.len();
+    keys.dedup();
+    if keys.len() != count {
+        return Err(MultisigError::InvalidKexMessage("duplicate key shares".into()));
+    }
+
+    let mut aggregate = EdwardsPoint::identity();
+    let mut private_keys = private_keys.to_vec();
+    for key in &keys {
+        let mut data = key.to_vec();
+        keys.iter().for_each(|key| data.extend(key));
+        data.extend(&salt(HASH_KEY_MULTISIG_KEY_AGGREGATION));
+        let coefficient = hash_to_scalar(&data);
+
+        if let Some(i) = own_keys.iter().position(|own_key| own_key == key) {
+            private_keys[i] *= coefficient;
+        }
+        aggregate += coefficient * read_point(key)?;
+    }
+    Ok((aggregate.compress().to_bytes(), private_keys))
+}
+
+/// Returns the domain separator zero padded to 32 bytes.
+fn salt(domain: &[u8]) -> [u8; 32] {
+    let mut salt = [0u8; 32];
+    salt[..domain.len()].copy_from_slice(domain);
+    salt
+}
+
+/// Returns the number of combinations of k of n elements.
+fn binomial(n: u64, k: u64) -> u64 {
+    match k > n {
+        true => 0,
+        false => (0..k).fold(1, |result, i| result * (n - i) / (i + 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::one_time_key::OneTimeKey;
+    use crate::Stagenet;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    type N = Stagenet;
+
+    // (threshold, signers, address) of the signers with private keys from `private_key`
+    const ADDRESSES: [(u32, u8, &str); 3] = [
+        (
+            2,
+            2,
+            "53oXHXaL9guUBbbqLEuNv2JoVRD7hSr73axeMg83TBV834vnSGW5Mg4Kfq4dZSh4vUJrsS9vKCKMte3AgX5KTJ135DRJKjF",
+        ),
+        (
+            2,
+            3,
+            "54h5gDqkcr2eAoP7G2tQKNYWopjY2ngG3ZJh8Zh1khnMQUYiMM7BB5XE9KbZuqiXpkRneVLGAoDHfLhHhpabFccHApigmP6",
+        ),
+        (
+            2,
+            4,
+            "55s8mwyChY8WLThW2y2w2kN29KRwLoNqaahmoEDQHcADKZPFnnESiQNVzs6x2vcAxVZgpkfbeCs3YNBkb56wLAU14KBVX26",
+        ),
+    ];
+
+    /// Returns the private key of the signer with the given index.
+    fn private_key(index: u8) -> MoneroPrivateKey<N> {
+        let private_spend_key = hash_to_scalar(&[index]).to_bytes();
+        MoneroPrivateKey::from_private_spend_key(&hex::encode(private_spend_key), &MoneroFormat::Standard).unwrap()
+    }
+
+    /// Returns the messages of the signers passed as strings.
+    fn messages(accounts: &[MoneroMultisigAccount<N>]) -> Vec<MoneroMultisigKexMessage> {
+        accounts
+            .iter()
+            .map(|account| MoneroMultisigKexMessage::from_str(&account.to_kex_message().to_string()).unwrap())
+            .collect()
+    }
+
+    /// Returns the accounts of the signers of an M-of-N wallet after the key exchange.
+    fn key_exchange(threshold: u32, signers: u8) -> Vec<MoneroMultisigAccount<N>> {
+        let rng = &mut StdRng::from_seed([0u8; 32]);
+        let mut accounts: Vec<MoneroMultisigAccount<N>> = (0..signers)
+            .map(|i| MoneroMultisigAccount::new(&private_key(i), rng).unwrap())
+            .collect();
+
+        let first_round = messages(&accounts);
+        accounts
+            .iter_mut()
+            .for_each(|account| account.initialize(threshold, &first_round, rng).unwrap());
+
+        let rounds = signers as u32 - threshold + 1;
+        for round in 2..=rounds + 1 {
+            assert!(!accounts[0].is_ready());
+            let messages = messages(&accounts);
+            assert!(messages.iter().all(|message| message.round() == round));
+            accounts
+                .iter_mut()
+                .for_each(|account| account.update(&messages, rng).unwrap());
+        }
+        accounts
+    }
+
+    /// Returns the distinct shares of the private spend key held by the signers.
+    fn shares(accounts: &[MoneroMultisigAccount<N>]) -> BTreeSet<[u8; 32]> {
+        accounts
+            .iter()
+            .flat_map(|account| account.to_multisig_private_keys().unwrap())
+            .collect()
+    }
+
+    /// Returns an output received by the address with a synthetic transaction public key.
+    fn owned_output(account: &MoneroMultisigAccount<N>, index: u64) -> MoneroOwnedOutput {
+        let tx_pub_key = hash_to_point(b"tx_pub_key").compress().to_bytes();
+        let mut derivation = vec![];
+        OneTimeKey::<N>::generate_key_derivation(&tx_pub_key, &account.to_private_view_key().unwrap(), &mut derivation)
+            .unwrap();
+        let public_key = &OneTimeKey::<N>::derivation_to_scalar(&derivation, index) * &ED25519_BASEPOINT_TABLE
+            + read_point(&account.to_public_spend_key().unwrap()).unwrap();
+        MoneroOwnedOutput {
+            index,
+            subaddress: (0, 0),
+            amount: 1_000,
+            public_key: public_key.compress().to_bytes(),
+            tx_pub_key,
+            mask: Scalar::one().to_bytes(),
+        }
+    }
+
+    #[test]
+    fn kex_messages() {
+        let rng = &mut StdRng::from_seed([0u8; 32]);
+        let account = MoneroMultisigAccount::<N>::new(&private_key(0), rng).unwrap();
+        let message = account.to_kex_message();
+        assert_eq!(1, message.round());
+        assert!(message.public_keys().is_empty());
+
+        let encoded = message.to_string();
+        assert!(encoded.starts_with("MultisigxV2R1"));
+        assert_eq!(*message, MoneroMultisigKexMessage::from_str(&encoded).unwrap());
+
+        let private_key = hash_to_scalar(b"signer");
+        let public_keys = (0..3u8)
+            .map(|i| hash_to_point(&[i]).compress().to_bytes())
+            .collect::<Vec<_>>();
+        let message = MoneroMultisigKexMessage::new(2, &private_key, public_keys.clone(), rng);
+        let encoded = message.to_string();
+        assert!(encoded.starts_with("MultisigxV2Rn"));
+
+        let decoded = MoneroMultisigKexMessage::from_str(&encoded).unwrap();
+        assert_eq!(message, decoded);
+        assert_eq!(public_keys, decoded.public_keys());
+        assert_eq!(
+            (&private_key * &ED25519_BASEPOINT_TABLE).compress().to_bytes(),
+            decoded.signing_public_key()
+        );
+    }
+
+    #[test]
+    fn key_exchanges() {
+        for (threshold, signers) in [(2, 2), (3, 3), (2, 3), (2, 4), (3, 4), (3, 5), (1, 3)].iter() {
+            let accounts = key_exchange(*threshold, *signers);
+            let address = accounts[0].to_address().unwrap();
+            let rounds = *signers as u32 - threshold + 1;
+            accounts.iter().for_each(|account| {
+                assert!(account.is_ready());
+                assert_eq!(rounds + 1, account.rounds_complete());
+                assert_eq!(address, account.to_address().unwrap());
+                assert_eq!(*signers as usize, account.signers().len());
+            });
+
+            // The public spend key is the sum of the distinct shares, each held by N - M + 1 signers.
+            let shares = shares(&accounts);
+            assert_eq!(binomial(*signers as u64, rounds as u64) as usize, shares.len());
+            let private_spend_key = shares.iter().map(|share| Scalar::from_bits(*share)).sum::<Scalar>();
+            assert_eq!(
+                accounts[0].to_public_spend_key().unwrap(),
+                (&private_spend_key * &ED25519_BASEPOINT_TABLE).compress().to_bytes()
+            );
+
+            // Any M signers hold all shares.
+            (0..*signers as usize).for_each(|first| {
+                let signers: Vec<MoneroMultisigAccount<N>> = (0..*threshold as usize)
+                    .map(|i| accounts[(first + i) % accounts.len()].clone())
+                    .collect();
+                assert_eq!(shares, self::shares(&signers));
+            });
+        }
+    }
+
+    #[test]
+    fn key_exchange_is_deterministic() {
+        // The wallet depends on the keys of the signers, not on the signatures of their messages.
+        ADDRESSES.iter().for_each(|(threshold, signers, address)| {
+            let first = key_exchange(*threshold, *signers);
+            let second = key_exchange(*threshold, *signers);
+            assert_eq!(*address, first[0].to_address().unwrap().to_string());
+            assert_eq!(shares(&first), shares(&second));
+        });
+    }
+
+    #[test]
+    fn key_images() {
+        for (threshold, signers) in [(2, 2), (2, 3), (3, 4)].iter() {
+            let accounts = key_exchange(*threshold, *signers);
+            let output = owned_output(&accounts[0], 1);
+
+            // The key image is (H_s(8aR || i) + sum(k)) * Hp(P).
+            let mut derivation = vec![];
+            OneTimeKey::<N>::generate_key_derivation(
+                &output.tx_pub_key,
+                &accounts[0].to_private_view_key().unwrap(),
+                &mut derivation,
+            )
+            .unwrap();
+            let private_key = OneTimeKey::<N>::derivation_to_scalar(&derivation, output.index)
+                + shares(&accounts)
+                    .iter()
+                    .map(|share| Scalar::from_bits(*share))
+                    .sum::<Scalar>();
+            assert_eq!(
+                output.public_key,
+                (&private_key * &ED25519_BASEPOINT_TABLE).compress().to_bytes()
+            );
+            let expected = (private_key * hash_to_point(&output.public_key)).compress().to_bytes();
+
+            // Each signer of M signers combines the partial key images of the others.
+            let signers = &accounts[..*threshold as usize];
+            signers.iter().enumerate().for_each(|(i, account)| {
+                let partial_key_images: Vec<[u8; 32]> = signers
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .flat_map(|(_, other)| other.to_partial_key_images(&output.public_key).unwrap())
+                    .collect();
+                assert_eq!(expected, account.to_key_image(&output, &partial_key_images).unwrap());
+            });
+        }
+    }
+
+    mod invalid {
+        use super::*;
+
+        const INVALID_KEX_MESSAGES: [&str; 4] = [
+            "",
+            "MultisigV1",
+            "MultisigxV2R1",
+            "MultisigxV2Rn1111111111111111111111111111111111111111111111111111111111111111111111111111111111",
+        ];
+
+        #[test]
+        fn from_invalid_kex_message() {
+            INVALID_KEX_MESSAGES.iter().for_each(|message| {
+                assert!(MoneroMultisigKexMessage::from_str(message).is_err());
+            });
+        }
+
+        #[test]
+        fn from_tampered_kex_message() {
+            let rng = &mut StdRng::from_seed([0u8; 32]);
+            let account = MoneroMultisigAccount::<N>::new(&private_key(0), rng).unwrap();
+            let mut message = account.to_kex_message().clone();
+            message.private_key = Some(hash_to_scalar(b"other").to_bytes());
+            assert!(MoneroMultisigKexMessage::from_str(&message.to_string()).is_err());
+        }
+
+        #[test]
+        fn invalid_thresholds() {
+            let rng = &mut StdRng::from_seed([0u8; 32]);
+            let mut accounts: Vec<MoneroMultisigAccount<N>> = (0..3)
+                .map(|i| MoneroMultisigAccount::new(&private_key(i), rng).unwrap())
+                .collect();
+            let first_round = messages(&accounts);
+            for threshold in [0, 4].iter() {
+                match accounts[0].initialize(*threshold, &first_round, rng) {
+                    Err(MultisigError::InvalidThreshold(_, 3)) => (),
+                    result => panic!("unexpected result {:?}", result),
+                }
+            }
+
+            // A wallet of a single signer
+            match accounts[1].initialize(1, &first_round[1..2], rng) {
+                Err(MultisigError::InvalidSignerCount(1)) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+        }
+
+        #[test]
+        fn invalid_rounds() {
+            let rng = &mut StdRng::from_seed([0u8; 32]);
+            let mut accounts: Vec<MoneroMultisigAccount<N>> = (0..3)
+                .map(|i| MoneroMultisigAccount::new(&private_key(i), rng).unwrap())
+                .collect();
+            let first_round = messages(&accounts);
+            assert!(accounts[0].update(&first_round, rng).is_err());
+            accounts
+                .iter_mut()
+                .for_each(|account| account.initialize(2, &first_round, rng).unwrap());
+            assert!(accounts[0].to_address().is_err());
+
+            // Messages of the wrong round, of a missing signer or of an unknown signer
+            assert!(accounts[0].update(&first_round, rng).is_err());
+            let second_round = messages(&accounts);
+            assert!(accounts[0].update(&second_round[..2], rng).is_err());
+            let mut unknown = second_round.clone();
+            unknown[2] = MoneroMultisigKexMessage::new(2, &hash_to_scalar(b"other"), vec![], rng);
+            assert!(accounts[0].update(&unknown, rng).is_err());
+
+            // A signer that sends a different key
+            let mut tampered = second_round.clone();
+            tampered[1] = MoneroMultisigKexMessage::new(
+                2,
+                &accounts[1].base_private_key,
+                vec![hash_to_point(b"other").compress().to_bytes()],
+                rng,
+            );
+            assert!(accounts[0].update(&tampered, rng).is_err());
+
+            accounts
+                .iter_mut()
+                .for_each(|account| account.update(&second_round, rng).unwrap());
+            let third_round = messages(&accounts);
+            accounts
+                .iter_mut()
+                .for_each(|account| account.update(&third_round, rng).unwrap());
+            let fourth_round = messages(&accounts);
+            match accounts[0].update(&fourth_round, rng) {
+                Err(MultisigError::CompleteKeyExchange) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+        }
+    }
+}
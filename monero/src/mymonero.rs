@@ -0,0 +1,201 @@
+use crate::address::MoneroAddress;
+use crate::format::MoneroFormat;
+use crate::mnemonic::{decode_phrase, encode_phrase};
+use crate::network::MoneroNetwork;
+use crate::private_key::MoneroPrivateKey;
+use crate::public_key::MoneroPublicKey;
+use crate::wordlist::MoneroWordlist;
+use wagyu_model::{Mnemonic, MnemonicError, PrivateKey};
+
+use rand::Rng;
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Represents a legacy MyMonero mnemonic of 13 words
+pub struct MyMoneroMnemonic<N: MoneroNetwork, W: MoneroWordlist> {
+    /// The initial 128-bit seed
+    seed: [u8; 16],
+    /// PhantomData
+    _network: PhantomData<N>,
+    /// PhantomData
+    _wordlist: PhantomData<W>,
+}
+
+impl<N: MoneroNetwork, W: MoneroWordlist> Mnemonic for MyMoneroMnemonic<N, W> {
+    type Address = MoneroAddress<N>;
+    type Format = MoneroFormat;
+    type PrivateKey = MoneroPrivateKey<N>;
+    type PublicKey = MoneroPublicKey<N>;
+
+    /// Returns a new mnemonic.
+    fn new<R: Rng>(rng: &mut R) -> Result<Self, MnemonicError> {
+        Ok(Self {
+            seed: rng.gen(),
+            _network: PhantomData,
+            _wordlist: PhantomData,
+        })
+    }
+
+    /// Returns the mnemonic for the given phrase.
+    fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
+        let buffer = decode_phrase::<W>(phrase)?;
+        if buffer.len() != 16 {
+            return Err(MnemonicError::InvalidWordCount(phrase.split(" ").count() as u8));
+        }
+
+        let mut seed = [0u8; 16];
+        seed.copy_from_slice(&buffer);
+
+        Ok(Self {
+            seed,
+            _network: PhantomData,
+            _wordlist: PhantomData,
+        })
+    }
+
+    fn to_phrase(&self) -> Result<String, MnemonicError> {
+        encode_phrase::<W>(&self.seed)
+    }
+
+    /// Returns the private key of the corresponding mnemonic.
+    fn to_private_key(&self, _: Option<&str>) -> Result<Self::PrivateKey, MnemonicError> {
+        Ok(MoneroPrivateKey::from_mymonero_seed(
+            hex::encode(&self.seed).as_str(),
+            &MoneroFormat::Standard,
+        )?)
+    }
+
+    /// Returns the public key of the corresponding mnemonic.
+    fn to_public_key(&self, _: Option<&str>) -> Result<Self::PublicKey, MnemonicError> {
+        Ok(self.to_private_key(None)?.to_public_key())
+    }
+
+    /// Returns the address of the corresponding mnemonic.
+    fn to_address(&self, _: Option<&str>, format: &Self::Format) -> Result<Self::Address, MnemonicError> {
+        Ok(self.to_private_key(None)?.to_address(format)?)
+    }
+}
+
+impl<N: MoneroNetwork, W: MoneroWordlist> MyMoneroMnemonic<N, W> {
+    /// Compares the given phrase against the phrase extracted from its entropy.
+    pub fn verify_phrase(phrase: &str) -> bool {
+        Self::from_phrase(phrase).is_ok()
+    }
+}
+
+impl<N: MoneroNetwork, W: MoneroWordlist> FromStr for MyMoneroMnemonic<N, W> {
+    type Err = MnemonicError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_phrase(s)
+    }
+}
+
+impl<N: MoneroNetwork, W: MoneroWordlist> fmt::Display for MyMoneroMnemonic<N, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self.to_phrase() {
+                Ok(phrase) => phrase,
+                _ => return Err(fmt::Error),
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use crate::wordlist::*;
+    use hex;
+
+    type N = Mainnet;
+    type W = English;
+
+    // (seed, phrase, (private_spend_key, private_view_key), address)
+    const KEYPAIRS: [(&str, &str, (&str, &str), &str); 1] = [(
+        "9c973aa296b79bbf452781dd3d32ad7f",
+        "foxes selfish humid nexus juvenile dodge pepper ember biscuit elapse jazz vibrate biscuit",
+        (
+            "4e6d43cd03812b803c6f3206689f5fcc910005fc7e91d50d79b0776dbefcd803",
+            "7bea1907940afdd480eff7c4bcadb478a0fbb626df9e3ed74ae801e18f53e104",
+        ),
+        "43zxvpcj5Xv9SEkNXbMCG7LPQStHMpFCQCmkmR4u5nzjWwq5Xkv5VmGgYEsHXg4ja2FGRD5wMWbBVMijDTqmmVqm93wHGkg",
+    )];
+
+    #[test]
+    fn new() {
+        let rng = &mut rand::thread_rng();
+        (0..10).for_each(|_| {
+            let mnemonic = MyMoneroMnemonic::<N, W>::new(rng).unwrap();
+            let phrase = mnemonic.to_phrase().unwrap();
+            assert_eq!(13, phrase.split(" ").count());
+            assert_eq!(mnemonic, MyMoneroMnemonic::<N, W>::from_phrase(&phrase).unwrap());
+        })
+    }
+
+    #[test]
+    fn from_phrase() {
+        KEYPAIRS.iter().for_each(|(seed, phrase, _, _)| {
+            let mnemonic = MyMoneroMnemonic::<N, W>::from_phrase(phrase).unwrap();
+            assert_eq!(*seed, hex::encode(mnemonic.seed));
+            assert_eq!(*phrase, mnemonic.to_phrase().unwrap());
+        })
+    }
+
+    #[test]
+    fn verify_phrase() {
+        KEYPAIRS.iter().for_each(|(_, phrase, _, _)| {
+            assert!(MyMoneroMnemonic::<N, W>::verify_phrase(phrase));
+        });
+    }
+
+    #[test]
+    fn to_private_key() {
+        KEYPAIRS
+            .iter()
+            .for_each(|(_, phrase, (private_spend_key, private_view_key), address)| {
+                let mnemonic = MyMoneroMnemonic::<N, W>::from_phrase(phrase).unwrap();
+                let private_key = mnemonic.to_private_key(None).unwrap();
+                assert_eq!(*private_spend_key, hex::encode(private_key.to_private_spend_key()));
+                assert_eq!(*private_view_key, hex::encode(private_key.to_private_view_key()));
+                assert_eq!(
+                    *address,
+                    mnemonic.to_address(None, &MoneroFormat::Standard).unwrap().to_string()
+                );
+            });
+    }
+
+    mod invalid {
+        use super::*;
+
+        const INVALID_PHRASES: [&str; 4] = [
+            // Invalid checksum word
+            "foxes selfish humid nexus juvenile dodge pepper ember biscuit elapse jazz vibrate foxes",
+            // Missing checksum word
+            "foxes selfish humid nexus juvenile dodge pepper ember biscuit elapse jazz vibrate",
+            // Invalid word
+            "foxes selfish humid nexus juvenile dodge pepper ember biscuit elapse jazz vibrate wagyu",
+            // A 25-word phrase
+            "reruns today hookup itself thorn nirvana symptoms jukebox patio unquoted sushi long diode digit rewind hacksaw obvious soothe nightly return agile hobby algebra awesome nirvana",
+        ];
+
+        #[test]
+        fn from_invalid_phrase() {
+            INVALID_PHRASES.iter().for_each(|phrase| {
+                assert!(MyMoneroMnemonic::<N, W>::from_phrase(phrase).is_err());
+            });
+        }
+
+        #[test]
+        fn from_mymonero_phrase_as_standard_mnemonic() {
+            let phrase = KEYPAIRS[0].1;
+            match crate::mnemonic::MoneroMnemonic::<N, W>::from_phrase(phrase) {
+                Err(MnemonicError::InvalidWordCount(13)) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+        }
+    }
+}
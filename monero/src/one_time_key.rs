@@ -1,8 +1,10 @@
 #![allow(non_snake_case)]
 
+use crate::format::MoneroFormat;
 use crate::network::MoneroNetwork;
 use crate::private_key::MoneroPrivateKey;
 use crate::public_key::MoneroPublicKey;
+use crate::ringct::hash_to_point;
 use wagyu_model::{PublicKeyError, TransactionError};
 
 use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
@@ -83,6 +85,15 @@ impl<N: MoneroNetwork> OneTimeKey<N> {
         })
     }
 
+    /// Returns a one time key given a destination key and its transaction public key
+    pub fn from(destination_key: &[u8; 32], transaction_public_key: &[u8; 32]) -> OneTimeKey<N> {
+        Self {
+            destination_key: *destination_key,
+            transaction_public_key: *transaction_public_key,
+            _network: PhantomData,
+        }
+    }
+
     /// Returns the one time private key given recipient private keys
     pub fn to_private(&self, private: &MoneroPrivateKey<N>, index: u64) -> Result<[u8; 32], OneTimeKeyError> {
         //one_time_private_key = hash((private_view_key * transaction_public_key) || index) + private_spend_key
@@ -96,11 +107,25 @@ impl<N: MoneroNetwork> OneTimeKey<N> {
 
         let hash = Self::derivation_to_scalar(&mut concat, index);
         let private_spend_scalar = Scalar::from_bits(private.to_private_spend_key());
-        let x: Scalar = hash + private_spend_scalar;
+        let mut x: Scalar = hash + private_spend_scalar;
+
+        // Outputs received on a subaddress are also offset by the subaddress secret
+        if let MoneroFormat::Subaddress(major, minor) = private.format() {
+            x += Scalar::from_bits(private.to_subaddress_private_view_key(major, minor));
+        }
 
         Ok(x.to_bytes())
     }
 
+    /// Returns the key image `x * Hp(P)` of the output given recipient private keys,
+    /// where `x` is the one time private key and `P` the destination key
+    pub fn to_key_image(&self, private: &MoneroPrivateKey<N>, index: u64) -> Result<[u8; 32], OneTimeKeyError> {
+        let x = Scalar::from_bytes_mod_order(self.to_private(private, index)?);
+        let key_image = x * hash_to_point(&self.destination_key);
+
+        Ok(key_image.compress().to_bytes())
+    }
+
     /// Returns one time public destination key given recipient private keys for verification
     fn to_public(&self, private: &MoneroPrivateKey<N>, index: u64) -> Result<[u8; 32], OneTimeKeyError> {
         //destination_key = one_time_private_key * G
@@ -145,7 +170,7 @@ impl<N: MoneroNetwork> OneTimeKey<N> {
     }
 
     /// Returns scalar base multiplication of public and secret key then multiplies result by cofactor
-    fn generate_key_derivation(
+    pub fn generate_key_derivation(
         public: &[u8; 32],
         secret_key: &[u8; 32],
         dest: &mut Vec<u8>,
@@ -167,7 +192,7 @@ impl<N: MoneroNetwork> OneTimeKey<N> {
     }
 
     /// Returns keccak256 hash of key derivation extended by output index as a scalar
-    fn derivation_to_scalar(derivation: &Vec<u8>, output_index: u64) -> Scalar {
+    pub fn derivation_to_scalar(derivation: &Vec<u8>, output_index: u64) -> Scalar {
         // H_s(derivation || output_index)
         let mut derivation = derivation.clone();
         derivation.extend(&Self::encode_varint(output_index));
@@ -175,6 +200,15 @@ impl<N: MoneroNetwork> OneTimeKey<N> {
         Scalar::from_bytes_mod_order(keccak256(&derivation))
     }
 
+    /// Returns the view tag, the first byte of keccak256 hash of "view_tag" || key derivation || output index
+    pub fn derive_view_tag(derivation: &Vec<u8>, output_index: u64) -> u8 {
+        let mut data = b"view_tag".to_vec();
+        data.extend(derivation);
+        data.extend(&Self::encode_varint(output_index));
+
+        keccak256(&data)[0]
+    }
+
     pub fn to_destination_key(&self) -> [u8; 32] {
         self.destination_key
     }
@@ -205,11 +239,12 @@ mod tests {
     //      output_index,
     //      (
     //          one_time_public_key,
-    //          one_time_private_key
+    //          one_time_private_key,
+    //          key_image
     //      ),
     //      transaction_public_key
     // )
-    const KEYPAIRS: [(&str, (&str, &str), &str, &str, (&str, &str), &str); 1] = [
+    const KEYPAIRS: [(&str, (&str, &str), &str, &str, (&str, &str, &str), &str); 1] = [
         // generated from https://xmr.llcoins.net/
         (
             "c595161ea20ccd8c692947c2d3ced471e9b13a18b150c881232794e8042bf107",
@@ -222,6 +257,7 @@ mod tests {
             (
                 "6cabaac48d3b9043525a703e9e5feb72132f69ea6deca9b4acf9228beb74cd8f",
                 "97df43cb906896405a8b54ecd4610c92b99de5090b404e5e64b17af17da01601",
+                "2ba7ee37314d4a1edbeef727f49099c79d55797570cb1206ee2685c94b6550b1",
             ),
             "396fc23bc389046b214087a9522c0fbd673d2f3f00ab9768f35fa52f953fef22",
         ),
@@ -234,6 +270,7 @@ mod tests {
         output_index: u64,
         one_time_public_key: &'static str,
         one_time_private_key: &'static str,
+        key_image: &'static str,
         transaction_public_key: &'static str,
     ) {
         let one_time_key = OneTimeKey::new(receiver_public_key, random_bytes, output_index).unwrap();
@@ -247,6 +284,10 @@ mod tests {
             hex::encode(one_time_key.to_private(receiver_private_key, output_index).unwrap()),
             one_time_private_key
         );
+        assert_eq!(
+            hex::encode(one_time_key.to_key_image(receiver_private_key, output_index).unwrap()),
+            key_image
+        );
 
         assert!(one_time_key.verify(receiver_private_key, output_index).unwrap());
     }
@@ -259,7 +300,7 @@ mod tests {
                 (receiver_public_spend_key, receiver_public_view_key),
                 random_str,
                 output_index,
-                (one_time_public_key, one_time_private_key),
+                (one_time_public_key, one_time_private_key, key_image),
                 transaction_public_key,
             )| {
                 let public_key =
@@ -279,6 +320,7 @@ mod tests {
                     index,
                     one_time_public_key,
                     one_time_private_key,
+                    key_image,
                     transaction_public_key,
                 );
             },
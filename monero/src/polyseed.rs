@@ -0,0 +1,531 @@
+use crate::address::MoneroAddress;
+use crate::format::MoneroFormat;
+use crate::network::MoneroNetwork;
+use crate::private_key::MoneroPrivateKey;
+use crate::public_key::MoneroPublicKey;
+use crate::wordlist::{
+    ChineseSimplified, ChineseTraditional, English, French, Italian, Japanese, Korean, PolyseedWordlist, Spanish,
+};
+use wagyu_model::{Mnemonic, MnemonicError, PrivateKey};
+
+use hmac::Hmac;
+use pbkdf2::pbkdf2;
+use rand::Rng;
+use sha2::Sha256;
+use std::{
+    fmt,
+    marker::PhantomData,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use unicode_normalization::UnicodeNormalization;
+
+/// The number of words of a Polyseed phrase
+pub const POLYSEED_NUM_WORDS: usize = 16;
+
+/// The coin of Monero seeds, which is mixed into the first data word
+const COIN_MONERO: u16 = 0;
+
+/// The size of the 150-bit secret, whose last byte holds 6 bits
+const SECRET_SIZE: usize = 19;
+const SECRET_CLEAR_MASK: u8 = 0x3f;
+/// The size of the zero-padded secret that derives the key
+const SECRET_BUFFER_SIZE: usize = 32;
+
+/// The number of secret bits of each data word, which also holds one bit of the birthday or features
+const SHARE_BITS: usize = 10;
+const DATE_BITS: usize = 10;
+const DATE_MASK: u16 = (1 << DATE_BITS) - 1;
+const FEATURE_BITS: usize = 5;
+const ENCRYPTED_MASK: u8 = 16;
+
+/// The Polyseed epoch, 1 November 2021 12:00 UTC, and the birthday step of 1/12 of a Gregorian year
+const EPOCH: u64 = 1_635_768_000;
+const TIME_STEP: u64 = 2_629_746;
+
+const KDF_ROUNDS: usize = 10_000;
+
+/// Represents a Polyseed mnemonic, the 16-word Monero seed with an embedded wallet birthday
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MoneroPolyseed<N: MoneroNetwork, W: PolyseedWordlist> {
+    /// The 150-bit secret seed
+    secret: [u8; SECRET_SIZE],
+    /// The wallet birthday in steps since the Polyseed epoch
+    birthday: u16,
+    /// The feature bits
+    features: u8,
+    /// PhantomData
+    _network: PhantomData<N>,
+    /// PhantomData
+    _wordlist: PhantomData<W>,
+}
+
+impl<N: MoneroNetwork, W: PolyseedWordlist> Mnemonic for MoneroPolyseed<N, W> {
+    type Address = MoneroAddress<N>;
+    type Format = MoneroFormat;
+    type PrivateKey = MoneroPrivateKey<N>;
+    type PublicKey = MoneroPublicKey<N>;
+
+    /// Returns a new mnemonic with the current time as its birthday.
+    fn new<R: Rng>(rng: &mut R) -> Result<Self, MnemonicError> {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        Ok(Self::new_with_birthday(rng, time))
+    }
+
+    /// Returns the mnemonic for the given phrase.
+    fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
+        let words = phrase.split_whitespace().collect::<Vec<&str>>();
+        if words.len() != POLYSEED_NUM_WORDS {
+            return Err(MnemonicError::InvalidWordCount(words.len() as u8));
+        }
+
+        let mut coefficients = [0u16; POLYSEED_NUM_WORDS];
+        for (coefficient, word) in coefficients.iter_mut().zip(words.iter()) {
+            *coefficient = W::get_polyseed_index(word)? as u16;
+        }
+        coefficients[1] ^= COIN_MONERO;
+
+        if evaluate(&coefficients) != 0 {
+            let mut expected = coefficients;
+            expected[0] = 0;
+            let checksum = evaluate(&expected);
+            return Err(MnemonicError::InvalidChecksumWord(
+                W::get_polyseed(checksum as usize)?,
+                words[0].into(),
+            ));
+        }
+
+        let mnemonic = Self::from_coefficients(&coefficients);
+        if mnemonic.features & !ENCRYPTED_MASK != 0 {
+            return Err(MnemonicError::InvalidPhrase(format!(
+                "unsupported polyseed features {}",
+                mnemonic.features
+            )));
+        }
+        Ok(mnemonic)
+    }
+
+    /// Returns the phrase of the corresponding mnemonic.
+    fn to_phrase(&self) -> Result<String, MnemonicError> {
+        let mut coefficients = self.to_coefficients();
+        coefficients[1] ^= COIN_MONERO;
+
+        let words = coefficients
+            .iter()
+            .map(|coefficient| W::get_polyseed(*coefficient as usize))
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(words.join(W::POLYSEED_SEPARATOR))
+    }
+
+    /// Returns the private key of the corresponding mnemonic, given the passphrase of an encrypted seed.
+    fn to_private_key(&self, password: Option<&str>) -> Result<Self::PrivateKey, MnemonicError> {
+        let seed = match (self.is_encrypted(), password) {
+            (true, Some(passphrase)) => self.decrypt(passphrase)?,
+            (true, None) => {
+                return Err(MnemonicError::InvalidPhrase(
+                    "missing passphrase of encrypted seed".into(),
+                ))
+            }
+            (false, _) => self.clone(),
+        };
+
+        // The key is reduced to the private spend key (Monero `sc_reduce32`).
+        let key = seed.to_key();
+        Ok(MoneroPrivateKey::from_seed(&hex::encode(key), &MoneroFormat::Standard)?)
+    }
+
+    /// Returns the public key of the corresponding mnemonic.
+    fn to_public_key(&self, password: Option<&str>) -> Result<Self::PublicKey, MnemonicError> {
+        Ok(self.to_private_key(password)?.to_public_key())
+    }
+
+    /// Returns the address of the corresponding mnemonic.
+    fn to_address(&self, password: Option<&str>, format: &Self::Format) -> Result<Self::Address, MnemonicError> {
+        Ok(self.to_private_key(password)?.to_address(format)?)
+    }
+}
+
+impl<N: MoneroNetwork, W: PolyseedWordlist> MoneroPolyseed<N, W> {
+    /// Returns a new mnemonic with the birthday of the given Unix time.
+    pub fn new_with_birthday<R: Rng>(rng: &mut R, time: u64) -> Self {
+        let mut secret: [u8; SECRET_SIZE] = rng.gen();
+        secret[SECRET_SIZE - 1] &= SECRET_CLEAR_MASK;
+
+        let birthday = match time < EPOCH {
+            true => 0,
+            false => (((time - EPOCH) / TIME_STEP) as u16) & DATE_MASK,
+        };
+
+        Self {
+            secret,
+            birthday,
+            features: 0,
+            _network: PhantomData,
+            _wordlist: PhantomData,
+        }
+    }
+
+    /// Compares the given phrase against the phrase extracted from its entropy.
+    pub fn verify_phrase(phrase: &str) -> bool {
+        Self::from_phrase(phrase).is_ok()
+    }
+
+    /// Returns the Unix time of the wallet birthday, which precedes the creation of the seed by up to a month.
+    pub fn birthday(&self) -> u64 {
+        EPOCH + self.birthday as u64 * TIME_STEP
+    }
+
+    /// Returns `true` if the seed is encrypted with a passphrase.
+    pub fn is_encrypted(&self) -> bool {
+        self.features & ENCRYPTED_MASK != 0
+    }
+
+    /// Returns the seed encrypted with a passphrase.
+    pub fn encrypt(&self, passphrase: &str) -> Result<Self, MnemonicError> {
+        match self.is_encrypted() {
+            true => Err(MnemonicError::InvalidPhrase("seed is already encrypted".into())),
+            false => Ok(self.crypt(passphrase)),
+        }
+    }
+
+    /// Returns the seed decrypted with a passphrase. A wrong passphrase decrypts to a different seed.
+    pub fn decrypt(&self, passphrase: &str) -> Result<Self, MnemonicError> {
+        match self.is_encrypted() {
+            true => Ok(self.crypt(passphrase)),
+            false => Err(MnemonicError::InvalidPhrase("seed is not encrypted".into())),
+        }
+    }
+
+    /// Returns the seed with its secret masked by the key of a passphrase and its encryption flag toggled
+    /// (Polyseed `polyseed_crypt`).
+    fn crypt(&self, passphrase: &str) -> Self {
+        let mut salt = [0u8; 16];
+        salt[..13].copy_from_slice(b"POLYSEED mask");
+        salt[14] = 0xff;
+        salt[15] = 0xff;
+
+        let passphrase = passphrase.nfkd().collect::<String>();
+        let mut mask = [0u8; 32];
+        pbkdf2::<Hmac<Sha256>>(passphrase.as_bytes(), &salt, KDF_ROUNDS, &mut mask);
+
+        let mut seed = self.clone();
+        seed.secret
+            .iter_mut()
+            .zip(mask.iter())
+            .for_each(|(byte, mask)| *byte ^= mask);
+        seed.secret[SECRET_SIZE - 1] &= SECRET_CLEAR_MASK;
+        seed.features ^= ENCRYPTED_MASK;
+        seed
+    }
+
+    /// Returns the 32-byte key of the seed (Polyseed `polyseed_keygen`).
+    fn to_key(&self) -> [u8; 32] {
+        let mut salt = [0u8; 32];
+        salt[..12].copy_from_slice(b"POLYSEED key");
+        salt[13..16].copy_from_slice(&[0xff; 3]);
+        salt[16..20].copy_from_slice(&(COIN_MONERO as u32).to_le_bytes());
+        salt[20..24].copy_from_slice(&(self.birthday as u32).to_le_bytes());
+        salt[24..28].copy_from_slice(&(self.features as u32).to_le_bytes());
+
+        let mut secret = [0u8; SECRET_BUFFER_SIZE];
+        secret[..SECRET_SIZE].copy_from_slice(&self.secret);
+
+        let mut key = [0u8; 32];
+        pbkdf2::<Hmac<Sha256>>(&secret, &salt, KDF_ROUNDS, &mut key);
+        key
+    }
+
+    /// Returns the polynomial coefficients of the seed, which are the word indices. The first coefficient
+    /// is the checksum, and each other one holds 10 bits of the secret followed by a bit of the features
+    /// and birthday.
+    fn to_coefficients(&self) -> [u16; POLYSEED_NUM_WORDS] {
+        let secret_bits = self.secret_bits();
+        let extra = ((self.features as u16) << DATE_BITS) | self.birthday;
+        let extra_bits = FEATURE_BITS + DATE_BITS;
+
+        let mut coefficients = [0u16; POLYSEED_NUM_WORDS];
+        for (i, coefficient) in coefficients.iter_mut().skip(1).enumerate() {
+            let share = secret_bits[SHARE_BITS * i..SHARE_BITS * (i + 1)]
+                .iter()
+                .fold(0u16, |share, bit| (share << 1) | *bit as u16);
+            *coefficient = (share << 1) | ((extra >> (extra_bits - 1 - i)) & 1);
+        }
+        coefficients[0] = evaluate(&coefficients);
+        coefficients
+    }
+
+    /// Returns the seed of the given polynomial coefficients.
+    fn from_coefficients(coefficients: &[u16; POLYSEED_NUM_WORDS]) -> Self {
+        let mut secret_bits = vec![];
+        let mut extra = 0u16;
+        for coefficient in coefficients.iter().skip(1) {
+            extra = (extra << 1) | (coefficient & 1);
+            secret_bits.extend((1..=SHARE_BITS).rev().map(|bit| ((coefficient >> bit) & 1) as u8));
+        }
+
+        // The last byte of the secret holds its remaining 6 bits.
+        let mut secret = [0u8; SECRET_SIZE];
+        for (byte, bits) in secret.iter_mut().zip(secret_bits.chunks(8)) {
+            *byte = bits.iter().fold(0u8, |byte, bit| (byte << 1) | bit);
+        }
+
+        Self {
+            secret,
+            birthday: extra & DATE_MASK,
+            features: (extra >> DATE_BITS) as u8,
+            _network: PhantomData,
+            _wordlist: PhantomData,
+        }
+    }
+
+    /// Returns the 150 bits of the secret, most significant bit first.
+    fn secret_bits(&self) -> Vec<u8> {
+        let mut bits = vec![];
+        for (i, byte) in self.secret.iter().enumerate() {
+            let length = if i == SECRET_SIZE - 1 { 6 } else { 8 };
+            bits.extend((0..length).rev().map(|bit| (byte >> bit) & 1));
+        }
+        bits
+    }
+}
+
+/// Multiplies an element of GF(2^11) by two.
+fn double(x: u16) -> u16 {
+    const TABLE: [u16; 8] = [5, 7, 1, 3, 13, 15, 9, 11];
+    match x < 1024 {
+        true => 2 * x,
+        false => TABLE[(x % 8) as usize] + 16 * ((x - 1024) / 8),
+    }
+}
+
+/// Returns the evaluation at two of the polynomial with the given coefficients, which is zero
+/// for a valid checksum.
+fn evaluate(coefficients: &[u16; POLYSEED_NUM_WORDS]) -> u16 {
+    coefficients
+        .iter()
+        .rev()
+        .skip(1)
+        .fold(coefficients[POLYSEED_NUM_WORDS - 1], |result, coefficient| {
+            double(result) ^ coefficient
+        })
+}
+
+/// Returns the language of the given Polyseed phrase, which is the first supported wordlist that decodes it.
+/// The Czech and Portuguese wordlists of Polyseed are not supported, and phrases in them are rejected.
+pub fn polyseed_language<N: MoneroNetwork>(phrase: &str) -> Result<&'static str, MnemonicError> {
+    // Returns the decoding of the phrase, if all of its words are in the wordlist.
+    fn decode<N: MoneroNetwork, W: PolyseedWordlist>(phrase: &str) -> Option<Result<(), MnemonicError>> {
+        match phrase
+            .split_whitespace()
+            .all(|word| W::get_polyseed_index(word).is_ok())
+        {
+            true => Some(MoneroPolyseed::<N, W>::from_phrase(phrase).map(|_| ())),
+            false => None,
+        }
+    }
+
+    let languages: [(&'static str, fn(&str) -> Option<Result<(), MnemonicError>>); 8] = [
+        ("chinese_simplified", decode::<N, ChineseSimplified>),
+        ("chinese_traditional", decode::<N, ChineseTraditional>),
+        ("english", decode::<N, English>),
+        ("french", decode::<N, French>),
+        ("italian", decode::<N, Italian>),
+        ("japanese", decode::<N, Japanese>),
+        ("korean", decode::<N, Korean>),
+        ("spanish", decode::<N, Spanish>),
+    ];
+
+    let mut error = None;
+    for (language, decode) in languages.iter() {
+        match decode(phrase) {
+            Some(Ok(())) => return Ok(language),
+            Some(Err(decode_error)) => error = error.or(Some(decode_error)),
+            None => continue,
+        }
+    }
+
+    Err(error.unwrap_or(MnemonicError::InvalidPhrase(
+        "unsupported language, czech and portuguese polyseeds are not supported".into(),
+    )))
+}
+
+impl<N: MoneroNetwork, W: PolyseedWordlist> FromStr for MoneroPolyseed<N, W> {
+    type Err = MnemonicError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_phrase(s)
+    }
+}
+
+impl<N: MoneroNetwork, W: PolyseedWordlist> fmt::Display for MoneroPolyseed<N, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self.to_phrase() {
+                Ok(phrase) => phrase,
+                _ => return Err(fmt::Error),
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use crate::wordlist::*;
+    use hex;
+
+    type N = Mainnet;
+    type W = English;
+
+    // (phrase, secret, birthday, (private_spend_key, private_view_key))
+    const KEYPAIRS: [(&str, &str, u64, (&str, &str)); 1] = [(
+        "raven tail swear infant grief assist regular lamp duck valid someone little harsh puppy airport language",
+        "dd76e7359a0ded37cd0ff0f3c829a5ae016733",
+        1638397746,
+        (
+            "6dd6b2029bfdf1c44a36ce8b229f35dcaa5800b8d858da9facf4b0a778dc2800",
+            "3c56a3cc3e7f94dc428ffe3b856adb6054552dfa14360d4cdec3f7730b999107",
+        ),
+    )];
+
+    const PASSPHRASE: &str = "wagyu";
+
+    #[test]
+    fn new() {
+        let rng = &mut rand::thread_rng();
+        (0..10).for_each(|_| {
+            let mnemonic = MoneroPolyseed::<N, W>::new(rng).unwrap();
+            let phrase = mnemonic.to_phrase().unwrap();
+            assert_eq!(POLYSEED_NUM_WORDS, phrase.split(" ").count());
+            assert_eq!(mnemonic, MoneroPolyseed::<N, W>::from_phrase(&phrase).unwrap());
+        })
+    }
+
+    #[test]
+    fn new_with_birthday() {
+        let rng = &mut rand::thread_rng();
+        let mnemonic = MoneroPolyseed::<N, W>::new_with_birthday(rng, EPOCH + 5 * TIME_STEP + 1);
+        assert_eq!(EPOCH + 5 * TIME_STEP, mnemonic.birthday());
+        let mnemonic = MoneroPolyseed::<N, W>::new_with_birthday(rng, EPOCH - 1);
+        assert_eq!(EPOCH, mnemonic.birthday());
+    }
+
+    #[test]
+    fn from_phrase() {
+        KEYPAIRS.iter().for_each(|(phrase, secret, birthday, _)| {
+            let mnemonic = MoneroPolyseed::<N, W>::from_phrase(phrase).unwrap();
+            assert_eq!(*secret, hex::encode(mnemonic.secret));
+            assert_eq!(*birthday, mnemonic.birthday());
+            assert!(!mnemonic.is_encrypted());
+            assert_eq!(*phrase, mnemonic.to_phrase().unwrap());
+        })
+    }
+
+    #[test]
+    fn from_prefixes() {
+        KEYPAIRS.iter().for_each(|(phrase, _, _, _)| {
+            let prefixes = phrase
+                .split(" ")
+                .map(|word| word.chars().take(4).collect::<String>())
+                .collect::<Vec<String>>();
+            let mnemonic = MoneroPolyseed::<N, W>::from_phrase(&prefixes.join(" ")).unwrap();
+            assert_eq!(*phrase, mnemonic.to_phrase().unwrap());
+        })
+    }
+
+    #[test]
+    fn to_private_key() {
+        KEYPAIRS
+            .iter()
+            .for_each(|(phrase, _, _, (private_spend_key, private_view_key))| {
+                let mnemonic = MoneroPolyseed::<N, W>::from_phrase(phrase).unwrap();
+                let private_key = mnemonic.to_private_key(None).unwrap();
+                assert_eq!(*private_spend_key, hex::encode(private_key.to_private_spend_key()));
+                assert_eq!(*private_view_key, hex::encode(private_key.to_private_view_key()));
+            });
+    }
+
+    #[test]
+    fn encrypt() {
+        KEYPAIRS
+            .iter()
+            .for_each(|(phrase, _, birthday, (private_spend_key, _))| {
+                let mnemonic = MoneroPolyseed::<N, W>::from_phrase(phrase).unwrap();
+                let encrypted = mnemonic.encrypt(PASSPHRASE).unwrap();
+                assert!(encrypted.is_encrypted());
+                assert_eq!(*birthday, encrypted.birthday());
+
+                let encrypted_phrase = encrypted.to_phrase().unwrap();
+                assert_ne!(*phrase, encrypted_phrase);
+                let encrypted = MoneroPolyseed::<N, W>::from_phrase(&encrypted_phrase).unwrap();
+                assert_eq!(mnemonic, encrypted.decrypt(PASSPHRASE).unwrap());
+
+                let private_key = encrypted.to_private_key(Some(PASSPHRASE)).unwrap();
+                assert_eq!(*private_spend_key, hex::encode(private_key.to_private_spend_key()));
+                assert!(encrypted.to_private_key(None).is_err());
+            });
+    }
+
+    #[test]
+    fn korean() {
+        let rng = &mut rand::thread_rng();
+        let mnemonic = MoneroPolyseed::<N, Korean>::new(rng).unwrap();
+        let phrase = mnemonic.to_phrase().unwrap();
+        assert_eq!(mnemonic, MoneroPolyseed::<N, Korean>::from_phrase(&phrase).unwrap());
+    }
+
+    #[test]
+    fn language() {
+        assert_eq!("english", polyseed_language::<N>(KEYPAIRS[0].0).unwrap());
+
+        let rng = &mut rand::thread_rng();
+        let phrase = MoneroPolyseed::<N, Korean>::new(rng).unwrap().to_phrase().unwrap();
+        assert_eq!("korean", polyseed_language::<N>(&phrase).unwrap());
+    }
+
+    mod invalid {
+        use super::*;
+
+        const INVALID_PHRASES: [&str; 3] = [
+            // Invalid checksum word
+            "tail tail swear infant grief assist regular lamp duck valid someone little harsh puppy airport language",
+            // Missing word
+            "raven tail swear infant grief assist regular lamp duck valid someone little harsh puppy airport",
+            // Invalid word
+            "raven tail swear infant grief assist regular lamp duck valid someone little harsh puppy airport wagyu",
+        ];
+
+        #[test]
+        fn from_invalid_phrase() {
+            INVALID_PHRASES.iter().for_each(|phrase| {
+                assert!(MoneroPolyseed::<N, W>::from_phrase(phrase).is_err());
+            });
+        }
+
+        #[test]
+        fn unsupported_language() {
+            // Words of the Portuguese wordlist
+            let phrase = "abacate abaixo abalar abater abduzir abelha aberto abismo abotoar abranger abreviar abrigar abrupto absinto absoluto absurdo";
+            assert!(polyseed_language::<N>(phrase).is_err());
+
+            // Phrases of a supported language keep their decoding error
+            match polyseed_language::<N>(INVALID_PHRASES[0]) {
+                Err(MnemonicError::InvalidChecksumWord(_, _)) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+        }
+
+        #[test]
+        fn encrypt_twice() {
+            let mnemonic = MoneroPolyseed::<N, W>::from_phrase(KEYPAIRS[0].0).unwrap();
+            assert!(mnemonic.decrypt(PASSPHRASE).is_err());
+            assert!(mnemonic.encrypt(PASSPHRASE).unwrap().encrypt(PASSPHRASE).is_err());
+        }
+    }
+}
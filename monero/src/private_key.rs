@@ -70,6 +70,30 @@ impl<N: MoneroNetwork> MoneroPrivateKey<N> {
         })
     }
 
+    /// Returns a private key given the 128-bit seed of a legacy MyMonero wallet, which hashes the seed
+    /// into the spend key and hashes it twice into the view key.
+    pub fn from_mymonero_seed(seed: &str, format: &MoneroFormat) -> Result<Self, PrivateKeyError> {
+        let seed = hex::decode(seed)?;
+        if seed.len() != 16 {
+            return Err(PrivateKeyError::InvalidByteLength(seed.len()));
+        }
+
+        let hash = keccak256(&seed);
+        let spend_key = Scalar::from_bytes_mod_order(hash).to_bytes();
+
+        let format = match format {
+            MoneroFormat::Subaddress(major, minor) if *major == 0 && *minor == 0 => MoneroFormat::Standard,
+            _ => *format,
+        };
+
+        Ok(Self {
+            spend_key,
+            view_key: Scalar::from_bytes_mod_order(keccak256(&hash)).to_bytes(),
+            format,
+            _network: PhantomData,
+        })
+    }
+
     /// Returns a private key given a private spend key.
     pub fn from_private_spend_key(private_spend_key: &str, format: &MoneroFormat) -> Result<Self, PrivateKeyError> {
         let key = hex::decode(private_spend_key)?;
@@ -93,6 +117,22 @@ impl<N: MoneroNetwork> MoneroPrivateKey<N> {
         })
     }
 
+    /// Returns a private key given a private spend key and a private view key that need not be derived from it.
+    pub fn from_private_keys(
+        private_spend_key: &str,
+        private_view_key: &str,
+        format: &MoneroFormat,
+    ) -> Result<Self, PrivateKeyError> {
+        let view_key = hex::decode(private_view_key)?;
+        if view_key.len() != 32 {
+            return Err(PrivateKeyError::InvalidByteLength(view_key.len()));
+        }
+
+        let mut private_key = Self::from_private_spend_key(private_spend_key, format)?;
+        private_key.view_key.copy_from_slice(view_key.as_slice());
+        Ok(private_key)
+    }
+
     /// Update the private key format and returns a subaddress private view key.
     pub fn to_subaddress_private_view_key(&self, major: u32, minor: u32) -> [u8; 32] {
         if major == 0 && minor == 0 {
@@ -216,6 +256,13 @@ mod tests {
         const FORMAT: &MoneroFormat = &MoneroFormat::Standard;
 
         // (seed, (private_spend_key, private_view_key), (public_spend_key, public_view_key), address)
+        // The 128-bit seed of a legacy MyMonero wallet
+        const MYMONERO_SEED: &str = "9c973aa296b79bbf452781dd3d32ad7f";
+        const MYMONERO_PRIVATE_SPEND_KEY: &str = "4e6d43cd03812b803c6f3206689f5fcc910005fc7e91d50d79b0776dbefcd803";
+        const MYMONERO_PRIVATE_VIEW_KEY: &str = "7bea1907940afdd480eff7c4bcadb478a0fbb626df9e3ed74ae801e18f53e104";
+        const MYMONERO_ADDRESS: &str =
+            "43zxvpcj5Xv9SEkNXbMCG7LPQStHMpFCQCmkmR4u5nzjWwq5Xkv5VmGgYEsHXg4ja2FGRD5wMWbBVMijDTqmmVqm93wHGkg";
+
         const KEYPAIRS: [(&str, (&str, &str), (&str, &str), &str); 5] = [
             (
                 "3eb8e283b45559d4d2fb6b3a4f52443b420e6da2b38832ea0eb642100c92d600",
@@ -336,6 +383,16 @@ mod tests {
                     test_to_str(private_spend_key, private_view_key, &private_key);
                 });
         }
+
+        #[test]
+        fn from_mymonero_seed() {
+            let private_key = MoneroPrivateKey::<N>::from_mymonero_seed(MYMONERO_SEED, FORMAT).unwrap();
+            assert_eq!(MYMONERO_PRIVATE_SPEND_KEY, hex::encode(private_key.spend_key));
+            assert_eq!(MYMONERO_PRIVATE_VIEW_KEY, hex::encode(private_key.view_key));
+            assert_eq!(MYMONERO_ADDRESS, private_key.to_address(FORMAT).unwrap().to_string());
+
+            assert!(MoneroPrivateKey::<N>::from_mymonero_seed(KEYPAIRS[0].0, FORMAT).is_err());
+        }
     }
 
     mod integrated_mainnet {
@@ -0,0 +1,1059 @@
+use crate::address::MoneroAddress;
+use crate::format::MoneroFormat;
+use crate::key_image::{one_time_private_key, KeyImageError};
+use crate::network::MoneroNetwork;
+use crate::one_time_key::OneTimeKey;
+use crate::private_key::MoneroPrivateKey;
+use crate::ringct::*;
+use crate::scanner::MoneroOwnedOutput;
+use crate::transaction::{MoneroTransaction, RCT_TYPE_NULL};
+use wagyu_model::{AddressError, Transaction, TransactionError};
+
+use base58_monero as base58;
+use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, edwards::EdwardsPoint, scalar::Scalar};
+use rand::Rng;
+use std::{collections::BTreeSet, fmt, iter::once, marker::PhantomData, str::FromStr};
+use tiny_keccak::keccak256;
+
+/// The prefixes of the encodings of `monero-wallet-cli get_tx_proof` and `get_reserve_proof`
+const OUT_PROOF_HEADER: &str = "OutProof";
+const IN_PROOF_HEADER: &str = "InProof";
+const RESERVE_PROOF_HEADER: &str = "ReserveProofV2";
+
+/// The length of the base58 encoding of a shared secret and its signature
+const ENCODED_KEY_LENGTH: usize = 44;
+const ENCODED_SIGNATURE_LENGTH: usize = 88;
+
+#[derive(Debug, Fail)]
+pub enum ProofError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "the address is not found in the proof")]
+    AddressNotInProof,
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "invalid byte length: {}", _0)]
+    InvalidByteLength(usize),
+
+    #[fail(display = "invalid proof header: {}", _0)]
+    InvalidHeader(String),
+
+    #[fail(display = "invalid output index {} of a transaction with {} outputs", _0, _1)]
+    InvalidOutputIndex(u64, usize),
+
+    #[fail(display = "invalid number of proofs {} for {} transaction public keys", _0, _1)]
+    InvalidProofCount(usize, usize),
+
+    #[fail(display = "invalid proof signature")]
+    InvalidSignature,
+
+    #[fail(display = "{}", _0)]
+    KeyImageError(KeyImageError),
+
+    #[fail(display = "missing transaction {}", _0)]
+    MissingTransaction(String),
+
+    #[fail(display = "missing transaction public key")]
+    MissingTransactionPublicKey,
+
+    #[fail(display = "no funds are received by the address in the transaction")]
+    NoFundsReceived,
+
+    #[fail(display = "output {} of transaction {} is not received by the address", _1, _0)]
+    OutputNotReceived(String, u64),
+
+    #[fail(display = "{}", _0)]
+    RingCtError(RingCtError),
+
+    #[fail(display = "reserve proofs are for primary addresses, found a subaddress")]
+    SubaddressReserveProof,
+
+    #[fail(display = "{}", _0)]
+    TransactionError(TransactionError),
+}
+
+impl From<AddressError> for ProofError {
+    fn from(error: AddressError) -> Self {
+        ProofError::AddressError(error)
+    }
+}
+
+impl From<KeyImageError> for ProofError {
+    fn from(error: KeyImageError) -> Self {
+        ProofError::KeyImageError(error)
+    }
+}
+
+impl From<RingCtError> for ProofError {
+    fn from(error: RingCtError) -> Self {
+        ProofError::RingCtError(error)
+    }
+}
+
+impl From<TransactionError> for ProofError {
+    fn from(error: TransactionError) -> Self {
+        ProofError::TransactionError(error)
+    }
+}
+
+/// Represents the party that proves a payment
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MoneroTxProofDirection {
+    /// The sender, who proves the payment with the transaction private keys
+    Out,
+    /// The recipient, who proves the receipt with the private view key
+    In,
+}
+
+/// Represents a proof that a transaction pays an address, as made by `monero-wallet-cli get_tx_proof`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroTxProof<N: MoneroNetwork> {
+    /// The party that made the proof
+    direction: MoneroTxProofDirection,
+    /// The version of the proof, 1 or 2
+    version: u8,
+    /// The shared secret and its proof for the transaction public key and each additional public key
+    proofs: Vec<([u8; 32], Signature)>,
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: MoneroNetwork> MoneroTxProof<N> {
+    /// Returns the proof by the sender that the transaction pays the address, given the transaction
+    /// private key and the additional private keys of a transaction to subaddresses.
+    pub fn new_outbound<R: Rng>(
+        transaction: &MoneroTransaction<N>,
+        tx_key: &[u8; 32],
+        additional_tx_keys: &[[u8; 32]],
+        address: &MoneroAddress<N>,
+        message: &str,
+        rng: &mut R,
+    ) -> Result<Self, ProofError> {
+        let prefix_hash = tx_proof_prefix_hash(&transaction.to_transaction_id()?.to_bytes(), message);
+        let (public_spend_key, public_view_key, subaddress_spend_key) = address_keys(address)?;
+
+        // The transaction public key of a payment to a subaddress is R = r*D rather than r*G.
+        let mut proofs = vec![];
+        for tx_key in once(tx_key).chain(additional_tx_keys) {
+            let secret_key = read_scalar(tx_key)?;
+            let tx_pub_key = match subaddress_spend_key {
+                Some(_) => secret_key * public_spend_key,
+                None => &secret_key * &ED25519_BASEPOINT_TABLE,
+            };
+            let shared_secret = secret_key * public_view_key;
+            let keys = (&tx_pub_key, &public_view_key, subaddress_spend_key.as_ref());
+            let signature = Signature::sign_tx_proof(&prefix_hash, keys, &shared_secret, &secret_key, rng)?;
+            proofs.push((shared_secret.compress().to_bytes(), signature));
+        }
+
+        Self::checked(MoneroTxProofDirection::Out, proofs, transaction, address, message)
+    }
+
+    /// Returns the proof by the recipient that the transaction pays the address, given the private key
+    /// of the wallet of the address.
+    pub fn new_inbound<R: Rng>(
+        transaction: &MoneroTransaction<N>,
+        private_key: &MoneroPrivateKey<N>,
+        address: &MoneroAddress<N>,
+        message: &str,
+        rng: &mut R,
+    ) -> Result<Self, ProofError> {
+        let prefix_hash = tx_proof_prefix_hash(&transaction.to_transaction_id()?.to_bytes(), message);
+        let (_, public_view_key, subaddress_spend_key) = address_keys(address)?;
+        let (public_keys, additional_public_keys) = transaction.prefix().to_transaction_public_keys();
+        let tx_pub_key = public_keys.first().ok_or(ProofError::MissingTransactionPublicKey)?;
+
+        let private_view_key = Scalar::from_bits(private_key.to_private_view_key());
+        let mut proofs = vec![];
+        for tx_pub_key in once(tx_pub_key).chain(additional_public_keys.iter()) {
+            let tx_pub_key = read_point(tx_pub_key)?;
+            let shared_secret = private_view_key * tx_pub_key;
+            let keys = (&public_view_key, &tx_pub_key, subaddress_spend_key.as_ref());
+            let signature = Signature::sign_tx_proof(&prefix_hash, keys, &shared_secret, &private_view_key, rng)?;
+            proofs.push((shared_secret.compress().to_bytes(), signature));
+        }
+
+        Self::checked(MoneroTxProofDirection::In, proofs, transaction, address, message)
+    }
+
+    /// Returns the proof if it shows a payment to the address, as Monero refuses to prove
+    /// transactions that pay nothing to the address.
+    fn checked(
+        direction: MoneroTxProofDirection,
+        proofs: Vec<([u8; 32], Signature)>,
+        transaction: &MoneroTransaction<N>,
+        address: &MoneroAddress<N>,
+        message: &str,
+    ) -> Result<Self, ProofError> {
+        let proof = Self {
+            direction,
+            version: 2,
+            proofs,
+            _network: PhantomData,
+        };
+        match proof.verify(transaction, address, message)? {
+            0 => Err(ProofError::NoFundsReceived),
+            _ => Ok(proof),
+        }
+    }
+
+    /// Verifies the proof of a payment to the address by the transaction and returns the amount
+    /// received by the address, which is zero if the transaction pays nothing to the address
+    /// (Monero `wallet2::check_tx_proof`).
+    pub fn verify(
+        &self,
+        transaction: &MoneroTransaction<N>,
+        address: &MoneroAddress<N>,
+        message: &str,
+    ) -> Result<u64, ProofError> {
+        let prefix_hash = tx_proof_prefix_hash(&transaction.to_transaction_id()?.to_bytes(), message);
+        let (public_spend_key, public_view_key, subaddress_spend_key) = address_keys(address)?;
+        let (public_keys, additional_public_keys) = transaction.prefix().to_transaction_public_keys();
+        let tx_pub_key = public_keys.first().ok_or(ProofError::MissingTransactionPublicKey)?;
+        if self.proofs.len() != 1 + additional_public_keys.len() {
+            return Err(ProofError::InvalidProofCount(
+                self.proofs.len(),
+                1 + additional_public_keys.len(),
+            ));
+        }
+
+        // The key derivation 8*D of each shared secret with a valid proof
+        let derivations: Vec<Option<Vec<u8>>> = self
+            .proofs
+            .iter()
+            .zip(once(tx_pub_key).chain(additional_public_keys.iter()))
+            .map(|((shared_secret, signature), tx_pub_key)| {
+                let tx_pub_key = read_point(tx_pub_key).ok()?;
+                let shared_secret = read_point(shared_secret).ok()?;
+                let keys = match self.direction {
+                    MoneroTxProofDirection::Out => (&tx_pub_key, &public_view_key, subaddress_spend_key.as_ref()),
+                    MoneroTxProofDirection::In => (&public_view_key, &tx_pub_key, subaddress_spend_key.as_ref()),
+                };
+                signature
+                    .verify_tx_proof(&prefix_hash, keys, &shared_secret, self.version)
+                    .ok()
+                    .map(|_| shared_secret.mul_by_cofactor().compress().to_bytes().to_vec())
+            })
+            .collect();
+        if derivations.iter().all(Option::is_none) {
+            return Err(ProofError::InvalidSignature);
+        }
+
+        // Outputs are derived from the transaction public key, or else from their additional public key.
+        let mut received = 0;
+        for (i, output) in transaction.prefix().outputs.iter().enumerate() {
+            let index = i as u64;
+            let derivation = once(&derivations[0])
+                .chain(derivations.get(i + 1))
+                .flatten()
+                .find(|derivation| {
+                    let shared_secret = OneTimeKey::<N>::derivation_to_scalar(derivation, index);
+                    (&shared_secret * &ED25519_BASEPOINT_TABLE + public_spend_key)
+                        .compress()
+                        .as_bytes()
+                        == &output.key
+                });
+            if let Some(derivation) = derivation {
+                received += output_amount(transaction, index, derivation);
+            }
+        }
+        Ok(received)
+    }
+
+    /// Returns the party that made the proof.
+    pub fn direction(&self) -> MoneroTxProofDirection {
+        self.direction
+    }
+
+    /// Returns the version of the proof.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl<N: MoneroNetwork> FromStr for MoneroTxProof<N> {
+    type Err = ProofError;
+
+    fn from_str(proof: &str) -> Result<Self, Self::Err> {
+        let (direction, header) = match proof {
+            _ if proof.starts_with(OUT_PROOF_HEADER) => (MoneroTxProofDirection::Out, OUT_PROOF_HEADER),
+            _ if proof.starts_with(IN_PROOF_HEADER) => (MoneroTxProofDirection::In, IN_PROOF_HEADER),
+            _ => return Err(ProofError::InvalidHeader(proof.chars().take(10).collect())),
+        };
+        let version = match proof.get(header.len()..header.len() + 2) {
+            Some("V1") => 1,
+            Some("V2") => 2,
+            _ => {
+                return Err(ProofError::InvalidHeader(
+                    proof.chars().take(header.len() + 2).collect(),
+                ))
+            }
+        };
+
+        let encoded = &proof[header.len() + 2..];
+        let length = ENCODED_KEY_LENGTH + ENCODED_SIGNATURE_LENGTH;
+        if encoded.is_empty() || encoded.len() % length != 0 || !encoded.is_ascii() {
+            return Err(ProofError::InvalidByteLength(encoded.len()));
+        }
+
+        let mut proofs = vec![];
+        for i in (0..encoded.len()).step_by(length) {
+            let shared_secret = base58_decode(&encoded[i..i + ENCODED_KEY_LENGTH])?;
+            let signature = base58_decode(&encoded[i + ENCODED_KEY_LENGTH..i + length])?;
+            if shared_secret.len() != 32 {
+                return Err(ProofError::InvalidByteLength(shared_secret.len()));
+            }
+
+            let mut key = [0u8; 32];
+            key.copy_from_slice(&shared_secret);
+            proofs.push((key, Signature::from_bytes(&signature)?));
+        }
+
+        Ok(Self {
+            direction,
+            version,
+            proofs,
+            _network: PhantomData,
+        })
+    }
+}
+
+impl<N: MoneroNetwork> fmt::Display for MoneroTxProof<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let header = match self.direction {
+            MoneroTxProofDirection::Out => OUT_PROOF_HEADER,
+            MoneroTxProofDirection::In => IN_PROOF_HEADER,
+        };
+        write!(f, "{}V{}", header, self.version)?;
+        for (shared_secret, signature) in &self.proofs {
+            match (base58::encode(shared_secret), base58::encode(&signature.to_bytes())) {
+                (Ok(shared_secret), Ok(signature)) => write!(f, "{}{}", shared_secret, signature)?,
+                _ => return Err(fmt::Error),
+            };
+        }
+        Ok(())
+    }
+}
+
+/// Represents the proof of an output in a reserve proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroReserveProofEntry {
+    /// The id of the transaction of the output
+    pub txid: [u8; 32],
+    /// The index of the output in its transaction
+    pub index: u64,
+    /// The shared secret of the private view key and the transaction public key
+    pub shared_secret: [u8; 32],
+    /// The key image of the output
+    pub key_image: [u8; 32],
+    /// The proof of the shared secret by the private view key
+    pub shared_secret_signature: Signature,
+    /// The ring signature of the key image by the output, a ring of size one
+    pub key_image_signature: Signature,
+}
+
+/// Represents a proof that a wallet owns a set of outputs with their key images,
+/// as made by `monero-wallet-cli get_reserve_proof`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroReserveProof<N: MoneroNetwork> {
+    /// The proofs of the outputs
+    entries: Vec<MoneroReserveProofEntry>,
+    /// The public spend keys of the primary address and of the subaddresses receiving the outputs,
+    /// each with a signature by its private spend key
+    spend_keys: Vec<([u8; 32], Signature)>,
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: MoneroNetwork> MoneroReserveProof<N> {
+    /// Returns the proof that the private key owns the given outputs, each with the id of its transaction.
+    pub fn new<R: Rng>(
+        private_key: &MoneroPrivateKey<N>,
+        outputs: &[([u8; 32], MoneroOwnedOutput)],
+        message: &str,
+        rng: &mut R,
+    ) -> Result<Self, ProofError> {
+        let private_spend_key = Scalar::from_bits(private_key.to_private_spend_key());
+        let private_view_key = Scalar::from_bits(private_key.to_private_view_key());
+        let public_spend_key = &private_spend_key * &ED25519_BASEPOINT_TABLE;
+        let public_view_key = &private_view_key * &ED25519_BASEPOINT_TABLE;
+
+        let secret_keys = outputs
+            .iter()
+            .map(|(_, output)| one_time_private_key(private_key, output))
+            .collect::<Result<Vec<(Scalar, [u8; 32])>, _>>()?;
+        let key_images: Vec<[u8; 32]> = secret_keys.iter().map(|(_, key_image)| *key_image).collect();
+        let prefix_hash = reserve_proof_prefix_hash(message, &public_spend_key, &public_view_key, &key_images);
+
+        let mut entries = vec![];
+        for ((txid, output), (secret_key, key_image)) in outputs.iter().zip(secret_keys) {
+            let tx_pub_key = read_point(&output.tx_pub_key)?;
+            let shared_secret = private_view_key * tx_pub_key;
+            let keys = (&public_view_key, &tx_pub_key, None);
+            let shared_secret_signature =
+                Signature::sign_tx_proof(&prefix_hash, keys, &shared_secret, &private_view_key, rng)?;
+
+            let public_key = read_point(&output.public_key)?;
+            let (key_image_signature, _) = RingSignature::sign(&prefix_hash, &[public_key], 0, &secret_key, rng)?;
+
+            entries.push(MoneroReserveProofEntry {
+                txid: *txid,
+                index: output.index,
+                shared_secret: shared_secret.compress().to_bytes(),
+                key_image,
+                shared_secret_signature,
+                key_image_signature: key_image_signature.signatures[0],
+            });
+        }
+
+        // The private spend key of a subaddress is b + H_s("SubAddr" || a || major || minor).
+        let subaddresses: BTreeSet<(u32, u32)> = once((0, 0))
+            .chain(outputs.iter().map(|(_, output)| output.subaddress))
+            .collect();
+        let mut spend_keys: Vec<([u8; 32], Signature)> = subaddresses
+            .iter()
+            .map(|(major, minor)| {
+                let secret_key =
+                    private_spend_key + Scalar::from_bits(private_key.to_subaddress_private_view_key(*major, *minor));
+                let public_key = (&secret_key * &ED25519_BASEPOINT_TABLE).compress().to_bytes();
+                (public_key, Signature::sign(&prefix_hash, &secret_key, rng))
+            })
+            .collect();
+        spend_keys.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Ok(Self {
+            entries,
+            spend_keys,
+            _network: PhantomData,
+        })
+    }
+
+    /// Verifies the proof of the outputs owned by the primary address, given their transactions,
+    /// and returns their total amount (Monero `wallet2::check_reserve_proof`).
+    ///
+    /// The proof does not show whether the outputs are unspent, which is found by looking up
+    /// their key images with the daemon.
+    pub fn verify(
+        &self,
+        address: &MoneroAddress<N>,
+        message: &str,
+        transactions: &[MoneroTransaction<N>],
+    ) -> Result<u64, ProofError> {
+        let (public_spend_key, public_view_key, subaddress_spend_key) = address_keys(address)?;
+        if subaddress_spend_key.is_some() {
+            return Err(ProofError::SubaddressReserveProof);
+        }
+
+        // The outputs are owned by the address only if its spend key signs the proof, otherwise a holder
+        // of the private view key could prove the outputs of any spend key it knows.
+        let address_spend_key = public_spend_key.compress().to_bytes();
+        if !self
+            .spend_keys
+            .iter()
+            .any(|(public_key, _)| public_key == &address_spend_key)
+        {
+            return Err(ProofError::AddressNotInProof);
+        }
+
+        let key_images: Vec<[u8; 32]> = self.entries.iter().map(|entry| entry.key_image).collect();
+        let prefix_hash = reserve_proof_prefix_hash(message, &public_spend_key, &public_view_key, &key_images);
+        let transaction_ids = transactions
+            .iter()
+            .map(|transaction| Ok(transaction.to_transaction_id()?.to_bytes()))
+            .collect::<Result<Vec<[u8; 32]>, ProofError>>()?;
+
+        let mut total = 0;
+        for entry in &self.entries {
+            let transaction = match transaction_ids.iter().position(|txid| txid == &entry.txid) {
+                Some(i) => &transactions[i],
+                None => return Err(ProofError::MissingTransaction(hex::encode(entry.txid))),
+            };
+            let outputs = &transaction.prefix().outputs;
+            let output = outputs
+                .get(entry.index as usize)
+                .ok_or(ProofError::InvalidOutputIndex(entry.index, outputs.len()))?;
+
+            // The shared secret is proven for the transaction public key or the additional public key.
+            let (public_keys, additional_public_keys) = transaction.prefix().to_transaction_public_keys();
+            let tx_pub_key = public_keys.first().ok_or(ProofError::MissingTransactionPublicKey)?;
+            let shared_secret = read_point(&entry.shared_secret)?;
+            let verify_shared_secret = |tx_pub_key: &[u8; 32]| match read_point(tx_pub_key) {
+                Ok(tx_pub_key) => entry
+                    .shared_secret_signature
+                    .verify_tx_proof(&prefix_hash, (&public_view_key, &tx_pub_key, None), &shared_secret, 2)
+                    .is_ok(),
+                Err(_) => false,
+            };
+            let additional_tx_pub_key = match additional_public_keys.len() == outputs.len() {
+                true => additional_public_keys.get(entry.index as usize),
+                false => None,
+            };
+            if !once(tx_pub_key).chain(additional_tx_pub_key).any(verify_shared_secret) {
+                return Err(ProofError::InvalidSignature);
+            }
+
+            let public_key = read_point(&output.key)?;
+            let key_image_signature = RingSignature {
+                signatures: vec![entry.key_image_signature],
+            };
+            if key_image_signature
+                .verify(&prefix_hash, &[public_key], &read_point(&entry.key_image)?)
+                .is_err()
+            {
+                return Err(ProofError::InvalidSignature);
+            }
+
+            // D = P - H_s(8aR || i) * G is a public spend key of the proof.
+            let derivation = shared_secret.mul_by_cofactor().compress().to_bytes().to_vec();
+            let secret = OneTimeKey::<N>::derivation_to_scalar(&derivation, entry.index);
+            let spend_key = (public_key - &secret * &ED25519_BASEPOINT_TABLE).compress().to_bytes();
+            if !self.spend_keys.iter().any(|(public_key, _)| public_key == &spend_key) {
+                return Err(ProofError::OutputNotReceived(hex::encode(entry.txid), entry.index));
+            }
+
+            total += output_amount(transaction, entry.index, &derivation);
+        }
+
+        for (public_key, signature) in &self.spend_keys {
+            if signature.verify(&prefix_hash, &read_point(public_key)?).is_err() {
+                return Err(ProofError::InvalidSignature);
+            }
+        }
+        Ok(total)
+    }
+
+    /// Returns the proofs of the outputs.
+    pub fn entries(&self) -> &[MoneroReserveProofEntry] {
+        &self.entries
+    }
+
+    /// Returns the key images of the outputs.
+    pub fn key_images(&self) -> Vec<[u8; 32]> {
+        self.entries.iter().map(|entry| entry.key_image).collect()
+    }
+
+    /// Returns the serialized proof, a Monero binary archive of the entries followed by the map
+    /// of public spend keys to their signatures.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = vec![];
+        write_varint(&mut buffer, self.entries.len() as u64);
+        for entry in &self.entries {
+            // The version of the entry
+            write_varint(&mut buffer, 0);
+            buffer.extend(&entry.txid);
+            write_varint(&mut buffer, entry.index);
+            buffer.extend(&entry.shared_secret);
+            buffer.extend(&entry.key_image);
+            buffer.extend(entry.shared_secret_signature.to_bytes().iter());
+            buffer.extend(entry.key_image_signature.to_bytes().iter());
+        }
+
+        // Each element of the map is a pair, which is an array of two elements.
+        write_varint(&mut buffer, self.spend_keys.len() as u64);
+        for (public_key, signature) in &self.spend_keys {
+            write_varint(&mut buffer, 2);
+            buffer.extend(public_key);
+            buffer.extend(signature.to_bytes().iter());
+        }
+        buffer
+    }
+
+    /// Returns the proof given its serialization.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofError> {
+        let mut offset = 0;
+        let read_count = |offset: &mut usize| -> Result<u64, ProofError> {
+            let (count, length) = read_varint(bytes.get(*offset..).unwrap_or(&[]))?;
+            *offset += length;
+            Ok(count)
+        };
+        let read_key = |offset: &mut usize| -> Result<[u8; 32], ProofError> {
+            let mut key = [0u8; 32];
+            key.copy_from_slice(take(bytes, offset, 32)?);
+            Ok(key)
+        };
+
+        let mut entries = vec![];
+        for _ in 0..read_count(&mut offset)? {
+            let version = read_count(&mut offset)?;
+            if version != 0 {
+                return Err(ProofError::Crate(
+                    "proof",
+                    format!("unsupported entry version {}", version),
+                ));
+            }
+            entries.push(MoneroReserveProofEntry {
+                txid: read_key(&mut offset)?,
+                index: read_count(&mut offset)?,
+                shared_secret: read_key(&mut offset)?,
+                key_image: read_key(&mut offset)?,
+                shared_secret_signature: Signature::from_bytes(take(bytes, &mut offset, 64)?)?,
+                key_image_signature: Signature::from_bytes(take(bytes, &mut offset, 64)?)?,
+            });
+        }
+
+        let mut spend_keys = vec![];
+        for _ in 0..read_count(&mut offset)? {
+            if read_count(&mut offset)? != 2 {
+                return Err(ProofError::Crate("proof", "invalid map element".into()));
+            }
+            let public_key = read_key(&mut offset)?;
+            spend_keys.push((public_key, Signature::from_bytes(take(bytes, &mut offset, 64)?)?));
+        }
+
+        if offset != bytes.len() {
+            return Err(ProofError::InvalidByteLength(bytes.len()));
+        }
+        Ok(Self {
+            entries,
+            spend_keys,
+            _network: PhantomData,
+        })
+    }
+}
+
+impl<N: MoneroNetwork> FromStr for MoneroReserveProof<N> {
+    type Err = ProofError;
+
+    fn from_str(proof: &str) -> Result<Self, Self::Err> {
+        if !proof.starts_with(RESERVE_PROOF_HEADER) {
+            return Err(ProofError::InvalidHeader(
+                proof.chars().take(RESERVE_PROOF_HEADER.len()).collect(),
+            ));
+        }
+        Self::from_bytes(&base58_decode(&proof[RESERVE_PROOF_HEADER.len()..])?)
+    }
+}
+
+impl<N: MoneroNetwork> fmt::Display for MoneroReserveProof<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match base58::encode(&self.to_bytes()) {
+            Ok(proof) => write!(f, "{}{}", RESERVE_PROOF_HEADER, proof),
+            _ => Err(fmt::Error),
+        }
+    }
+}
+
+/// Returns the public spend key and public view key of the address, and the public spend key
+/// again if the address is a subaddress.
+fn address_keys<N: MoneroNetwork>(
+    address: &MoneroAddress<N>,
+) -> Result<(EdwardsPoint, EdwardsPoint, Option<EdwardsPoint>), ProofError> {
+    let public_key = address.to_public_key()?;
+    let public_spend_key = read_point(&public_key.to_public_spend_key().unwrap_or_default())?;
+    let public_view_key = read_point(&public_key.to_public_view_key().unwrap_or_default())?;
+    let subaddress_spend_key = match address.format()? {
+        MoneroFormat::Subaddress(_, _) => Some(public_spend_key),
+        _ => None,
+    };
+    Ok((public_spend_key, public_view_key, subaddress_spend_key))
+}
+
+/// Returns the hash `H(txid || message)` signed by a transaction proof.
+fn tx_proof_prefix_hash(txid: &[u8; 32], message: &str) -> [u8; 32] {
+    let mut data = txid.to_vec();
+    data.extend(message.as_bytes());
+    keccak256(&data)
+}
+
+/// Returns the hash `H(message || B || A || key images)` signed by a reserve proof.
+fn reserve_proof_prefix_hash(
+    message: &str,
+    public_spend_key: &EdwardsPoint,
+    public_view_key: &EdwardsPoint,
+    key_images: &[[u8; 32]],
+) -> [u8; 32] {
+    let mut data = message.as_bytes().to_vec();
+    data.extend(public_spend_key.compress().as_bytes());
+    data.extend(public_view_key.compress().as_bytes());
+    key_images.iter().for_each(|key_image| data.extend(key_image));
+    keccak256(&data)
+}
+
+/// Returns the amount of the output given its key derivation, or zero if its commitment
+/// does not open to the decoded amount.
+fn output_amount<N: MoneroNetwork>(transaction: &MoneroTransaction<N>, index: u64, derivation: &Vec<u8>) -> u64 {
+    let output = &transaction.prefix().outputs[index as usize];
+    let signatures = transaction.rct_signatures();
+    if signatures.rct_type == RCT_TYPE_NULL {
+        return output.amount;
+    }
+
+    let shared_secret = OneTimeKey::<N>::derivation_to_scalar(derivation, index);
+    match (
+        signatures.ecdh_info.get(index as usize),
+        signatures.out_pk.get(index as usize),
+    ) {
+        (Some(encrypted_amount), Some(commitment)) => {
+            let amount = decode_amount(encrypted_amount, &shared_secret);
+            match commit(&commitment_mask(&shared_secret), amount) == *commitment {
+                true => amount,
+                false => 0,
+            }
+        }
+        _ => 0,
+    }
+}
+
+/// Returns the bytes of a Monero base58 string.
+fn base58_decode(encoded: &str) -> Result<Vec<u8>, ProofError> {
+    base58::decode(encoded).map_err(|error| ProofError::Crate("base58", format!("{:?}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::MoneroScanner;
+    use crate::transaction::*;
+    use crate::Stagenet;
+    use wagyu_model::PrivateKey;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    type N = Stagenet;
+
+    const PRIVATE_SPEND_KEY: &str = "0cf0c38429e00fa4abecb98296cb15dec209c0a7e6ea34ed86d32429498e4700";
+    const PRIVATE_VIEW_KEY: &str = "55c5b2fa94b2c5ee387eb9dd71b197a6358a5a90ed5eb5acdfa088583125a40a";
+    const OTHER_ADDRESS: &str =
+        "59McWTPGc745SRWrSMoh8oTjoXoQq6sPUgKZ66dQWXuKFQ2q19h9gvhJNZcFTizcnT12r63NFgHiGd6gBCjabzmzHAMoyD6";
+    const MESSAGE: &str = "wagyu";
+
+    const INPUT_AMOUNT: u64 = 1_000_000_000_000;
+    const FEE: u64 = 10_000_000;
+
+    fn private_key() -> MoneroPrivateKey<N> {
+        MoneroPrivateKey::from_private_keys(PRIVATE_SPEND_KEY, PRIVATE_VIEW_KEY, &MoneroFormat::Standard).unwrap()
+    }
+
+    fn other_address() -> MoneroAddress<N> {
+        MoneroAddress::from_str(OTHER_ADDRESS).unwrap()
+    }
+
+    /// Returns a signed transaction paying the destinations from a synthetic output of the private key.
+    fn signed_transaction(
+        destinations: Vec<(MoneroAddress<N>, u64)>,
+        change_address: &MoneroAddress<N>,
+    ) -> MoneroTransaction<N> {
+        let private_key = private_key();
+        let tx_pub_key = hash_to_point(b"tx_pub_key").compress().to_bytes();
+        let mut derivation = vec![];
+        OneTimeKey::<N>::generate_key_derivation(&tx_pub_key, &private_key.to_private_view_key(), &mut derivation)
+            .unwrap();
+        let public_key = &OneTimeKey::<N>::derivation_to_scalar(&derivation, 0) * &ED25519_BASEPOINT_TABLE
+            + read_point(&private_key.to_public_key().to_public_spend_key().unwrap()).unwrap();
+
+        let mix_outs = (0..RING_SIZE as u64 - 1)
+            .map(|i| MixOut {
+                global_index: 100 + i,
+                public_key: hex::encode(hash_to_point(&i.to_le_bytes()).compress().as_bytes()),
+                rct: Some(hex::encode(commit(&Scalar::one(), i).compress().as_bytes())),
+            })
+            .collect();
+        let parameters = MoneroTransactionParameters {
+            using_outs: vec![UnspentOutput {
+                amount: INPUT_AMOUNT,
+                index: 0,
+                global_index: 1,
+                public_key: hex::encode(public_key.compress().as_bytes()),
+                rct: None,
+                tx_pub_key: hex::encode(tx_pub_key),
+            }],
+            mix_outs: vec![MixAmountAndOuts {
+                amount: 0,
+                outputs: mix_outs,
+            }],
+            destinations,
+            change_address: change_address.clone(),
+            fee: FEE,
+            unlock_time: 0,
+        };
+        MoneroTransaction::new(&parameters).unwrap().sign(&private_key).unwrap()
+    }
+
+    /// Returns the outbound proof of the transaction for the address.
+    fn outbound_proof(transaction: &MoneroTransaction<N>, address: &MoneroAddress<N>) -> MoneroTxProof<N> {
+        MoneroTxProof::new_outbound(
+            transaction,
+            &transaction.to_tx_key().unwrap(),
+            &transaction.to_additional_tx_keys(),
+            address,
+            MESSAGE,
+            &mut rand::thread_rng(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn tx_proofs() {
+        let private_key = private_key();
+        let address = private_key.to_address(&MoneroFormat::Standard).unwrap();
+        let subaddress = private_key.to_address(&MoneroFormat::Subaddress(1, 2)).unwrap();
+        let transaction = signed_transaction(
+            vec![(address.clone(), 1_000), (subaddress.clone(), 2_000)],
+            &other_address(),
+        );
+        let additional_keys = transaction.prefix().to_transaction_public_keys().1.len();
+        assert_eq!(3, additional_keys);
+
+        for (address, amount) in [(address, 1_000), (subaddress, 2_000)].iter() {
+            let outbound = outbound_proof(&transaction, address);
+            let inbound =
+                MoneroTxProof::new_inbound(&transaction, &private_key, address, MESSAGE, &mut rand::thread_rng())
+                    .unwrap();
+
+            for (proof, header) in [(outbound, "OutProofV2"), (inbound, "InProofV2")].iter() {
+                assert_eq!(*amount, proof.verify(&transaction, address, MESSAGE).unwrap());
+                assert_eq!(2, proof.version());
+
+                let encoded = proof.to_string();
+                assert!(encoded.starts_with(header));
+                assert_eq!(header.len() + (1 + additional_keys) * 132, encoded.len());
+                assert_eq!(*proof, MoneroTxProof::from_str(&encoded).unwrap());
+            }
+        }
+
+        // The change is proven to the address of the other wallet by the sender.
+        let other = other_address();
+        assert_eq!(
+            INPUT_AMOUNT - FEE - 3_000,
+            outbound_proof(&transaction, &other)
+                .verify(&transaction, &other, MESSAGE)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn tx_proof_single_subaddress() {
+        // A single subaddress recipient derives the transaction public key from its spend key.
+        let private_key = private_key();
+        let subaddress = private_key.to_address(&MoneroFormat::Subaddress(0, 1)).unwrap();
+        let transaction = signed_transaction(vec![(subaddress.clone(), 3_000)], &subaddress);
+        assert!(transaction.prefix().to_transaction_public_keys().1.is_empty());
+
+        let outbound = outbound_proof(&transaction, &subaddress);
+        let inbound = MoneroTxProof::new_inbound(
+            &transaction,
+            &private_key,
+            &subaddress,
+            MESSAGE,
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+        assert_eq!(MoneroTxProofDirection::Out, outbound.direction());
+        assert_eq!(MoneroTxProofDirection::In, inbound.direction());
+        assert_eq!(
+            INPUT_AMOUNT - FEE,
+            outbound.verify(&transaction, &subaddress, MESSAGE).unwrap()
+        );
+        assert_eq!(
+            INPUT_AMOUNT - FEE,
+            inbound.verify(&transaction, &subaddress, MESSAGE).unwrap()
+        );
+    }
+
+    #[test]
+    fn reserve_proof() {
+        let private_key = private_key();
+        let address = private_key.to_address(&MoneroFormat::Standard).unwrap();
+        let subaddress = private_key.to_address(&MoneroFormat::Subaddress(1, 2)).unwrap();
+        let transactions = vec![
+            signed_transaction(
+                vec![(address.clone(), 1_000), (subaddress.clone(), 2_000)],
+                &other_address(),
+            ),
+            signed_transaction(vec![(subaddress.clone(), 3_000)], &subaddress),
+        ];
+
+        let scanner = MoneroScanner::new(&private_key, &[(1, 2)]).unwrap();
+        let mut outputs = vec![];
+        for transaction in &transactions {
+            let txid = transaction.to_transaction_id().unwrap().to_bytes();
+            for output in scanner.scan(transaction).unwrap() {
+                outputs.push((txid, output));
+            }
+        }
+        assert_eq!(4, outputs.len());
+
+        let proof = MoneroReserveProof::new(&private_key, &outputs, MESSAGE, &mut rand::thread_rng()).unwrap();
+        assert_eq!(
+            1_000 + 2_000 + INPUT_AMOUNT - FEE,
+            proof.verify(&address, MESSAGE, &transactions).unwrap()
+        );
+
+        let key_images: Vec<[u8; 32]> = outputs
+            .iter()
+            .map(|(_, output)| one_time_private_key(&private_key, output).unwrap().1)
+            .collect();
+        assert_eq!(key_images, proof.key_images());
+        assert_eq!(4, proof.entries().len());
+
+        let encoded = proof.to_string();
+        assert!(encoded.starts_with("ReserveProofV2"));
+        assert_eq!(proof, MoneroReserveProof::from_str(&encoded).unwrap());
+        assert_eq!(proof, MoneroReserveProof::from_bytes(&proof.to_bytes()).unwrap());
+    }
+
+    mod invalid {
+        use super::*;
+
+        const INVALID_TX_PROOFS: [&str; 4] = ["", "OutProofV3", "SpendProofV1", "InProofV2abc"];
+
+        #[test]
+        fn verify_invalid_tx_proof() {
+            let private_key = private_key();
+            let address = private_key.to_address(&MoneroFormat::Standard).unwrap();
+            let transaction = signed_transaction(vec![(address.clone(), 1_000)], &other_address());
+            let proof = outbound_proof(&transaction, &address);
+            let inbound =
+                MoneroTxProof::new_inbound(&transaction, &private_key, &address, MESSAGE, &mut rand::thread_rng())
+                    .unwrap();
+
+            let other_transaction = signed_transaction(vec![(address.clone(), 1_000)], &other_address());
+            for proof in [&proof, &inbound].iter() {
+                // A proof of another message, address or transaction
+                assert!(proof.verify(&transaction, &address, "other").is_err());
+                assert!(proof.verify(&transaction, &other_address(), MESSAGE).is_err());
+                assert!(proof.verify(&other_transaction, &address, MESSAGE).is_err());
+            }
+
+            // A proof with a tampered shared secret or signature
+            let mut tampered_secret = proof.clone();
+            tampered_secret.proofs[0].0 = hash_to_point(b"tampered").compress().to_bytes();
+            let mut tampered_signature = proof.clone();
+            tampered_signature.proofs[0].1 = outbound_proof(&other_transaction, &address).proofs[0].1;
+            for tampered in [tampered_secret, tampered_signature].iter() {
+                let decoded = MoneroTxProof::<N>::from_str(&tampered.to_string()).unwrap();
+                assert_eq!(*tampered, decoded);
+                assert!(decoded.verify(&transaction, &address, MESSAGE).is_err());
+            }
+        }
+
+        #[test]
+        fn forged_reserve_proof() {
+            // A holder of the private view key pays outputs to its own spend key and the view key of the victim.
+            let victim = private_key();
+            let victim_address = victim.to_address(&MoneroFormat::Standard).unwrap();
+            let attacker_spend_key = hash_to_scalar(b"attacker");
+            let attacker = MoneroPrivateKey::<N>::from_private_keys(
+                &hex::encode(attacker_spend_key.to_bytes()),
+                PRIVATE_VIEW_KEY,
+                &MoneroFormat::Standard,
+            )
+            .unwrap();
+            let attacker_address = attacker.to_address(&MoneroFormat::Standard).unwrap();
+            let transaction = signed_transaction(vec![(attacker_address, 5_000)], &other_address());
+            let txid = transaction.to_transaction_id().unwrap().to_bytes();
+            let outputs: Vec<MoneroOwnedOutput> =
+                MoneroScanner::new(&attacker, &[]).unwrap().scan(&transaction).unwrap();
+            assert_eq!(1, outputs.len());
+
+            // The proof signs the message, the keys of the victim and the key images with the keys the attacker knows.
+            let rng = &mut StdRng::from_seed([0u8; 32]);
+            let private_view_key = Scalar::from_bits(victim.to_private_view_key());
+            let public_view_key = &private_view_key * &ED25519_BASEPOINT_TABLE;
+            let public_spend_key = read_point(&victim.to_public_key().to_public_spend_key().unwrap()).unwrap();
+            let (secret_key, key_image) = one_time_private_key(&attacker, &outputs[0]).unwrap();
+            let prefix_hash = reserve_proof_prefix_hash(MESSAGE, &public_spend_key, &public_view_key, &[key_image]);
+
+            let tx_pub_key = read_point(&outputs[0].tx_pub_key).unwrap();
+            let shared_secret = private_view_key * tx_pub_key;
+            let keys = (&public_view_key, &tx_pub_key, None);
+            let shared_secret_signature =
+                Signature::sign_tx_proof(&prefix_hash, keys, &shared_secret, &private_view_key, rng).unwrap();
+            let public_key = read_point(&outputs[0].public_key).unwrap();
+            let (key_image_signature, _) =
+                RingSignature::sign(&prefix_hash, &[public_key], 0, &secret_key, rng).unwrap();
+            let attacker_public_spend_key = &attacker_spend_key * &ED25519_BASEPOINT_TABLE;
+
+            let forged = MoneroReserveProof::<N> {
+                entries: vec![MoneroReserveProofEntry {
+                    txid,
+                    index: outputs[0].index,
+                    shared_secret: shared_secret.compress().to_bytes(),
+                    key_image,
+                    shared_secret_signature,
+                    key_image_signature: key_image_signature.signatures[0],
+                }],
+                spend_keys: vec![(
+                    attacker_public_spend_key.compress().to_bytes(),
+                    Signature::sign(&prefix_hash, &attacker_spend_key, rng),
+                )],
+                _network: PhantomData,
+            };
+            match forged.verify(&victim_address, MESSAGE, &[transaction]) {
+                Err(ProofError::AddressNotInProof) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+        }
+
+        #[test]
+        fn new_tx_proof_without_funds() {
+            let private_key = private_key();
+            let address = private_key.to_address(&MoneroFormat::Standard).unwrap();
+            let subaddress = private_key.to_address(&MoneroFormat::Subaddress(1, 3)).unwrap();
+            let transaction = signed_transaction(vec![(address.clone(), 1_000)], &other_address());
+
+            let rng = &mut rand::thread_rng();
+            match MoneroTxProof::new_inbound(&transaction, &private_key, &subaddress, MESSAGE, rng) {
+                Err(ProofError::NoFundsReceived) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+
+            // The private view key of another wallet does not match the address.
+            let other = other_address();
+            assert!(MoneroTxProof::new_inbound(&transaction, &private_key, &other, MESSAGE, rng).is_err());
+        }
+
+        #[test]
+        fn from_invalid_tx_proof() {
+            INVALID_TX_PROOFS.iter().for_each(|proof| {
+                assert!(MoneroTxProof::<N>::from_str(proof).is_err());
+            });
+        }
+
+        #[test]
+        fn verify_invalid_reserve_proof() {
+            let private_key = private_key();
+            let address = private_key.to_address(&MoneroFormat::Standard).unwrap();
+            let transaction = signed_transaction(vec![(address.clone(), 1_000)], &other_address());
+            let txid = transaction.to_transaction_id().unwrap().to_bytes();
+            let outputs: Vec<([u8; 32], MoneroOwnedOutput)> = MoneroScanner::new(&private_key, &[])
+                .unwrap()
+                .scan(&transaction)
+                .unwrap()
+                .into_iter()
+                .map(|output| (txid, output))
+                .collect();
+            let proof = MoneroReserveProof::new(&private_key, &outputs, MESSAGE, &mut rand::thread_rng()).unwrap();
+            let transactions = vec![transaction];
+
+            assert!(proof.verify(&address, "other", &transactions).is_err());
+            assert!(proof.verify(&other_address(), MESSAGE, &transactions).is_err());
+            match proof.verify(&address, MESSAGE, &[]) {
+                Err(ProofError::MissingTransaction(_)) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+
+            let subaddress = private_key.to_address(&MoneroFormat::Subaddress(0, 1)).unwrap();
+            match proof.verify(&subaddress, MESSAGE, &transactions) {
+                Err(ProofError::SubaddressReserveProof) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+
+            // A proof whose spend keys omit the primary spend key of the address
+            let mut without_address = proof.clone();
+            without_address.spend_keys.clear();
+            match without_address.verify(&address, MESSAGE, &transactions) {
+                Err(ProofError::AddressNotInProof) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+
+            let mut bytes = proof.to_bytes();
+            bytes.push(0);
+            assert!(MoneroReserveProof::<N>::from_bytes(&bytes).is_err());
+            assert!(MoneroReserveProof::<N>::from_str("ReserveProofV1").is_err());
+        }
+    }
+}
@@ -0,0 +1,163 @@
+#![allow(non_snake_case)]
+
+use crate::ringct::{
+    bulletproofs_plus::MAX_ROUNDS, commitment::INV_EIGHT, read_point, read_scalar, read_varint, take, write_varint,
+    RingCtError,
+};
+
+use curve25519_dalek::{edwards::EdwardsPoint, scalar::Scalar};
+
+/// Represents an aggregated Bulletproof range proof of the RingCT types 4 and 5, which preceded Bulletproofs+.
+/// These proofs are parsed and serialized to read historical transactions, but are neither created nor verified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bulletproof {
+    /// The amount commitments multiplied by the inverse of 8, which are not serialized
+    pub V: Vec<EdwardsPoint>,
+    /// The commitment to the bits of the amounts
+    pub A: EdwardsPoint,
+    /// The commitment to the blinding vectors
+    pub S: EdwardsPoint,
+    /// The commitments to the coefficients of the polynomial t(x)
+    pub T1: EdwardsPoint,
+    pub T2: EdwardsPoint,
+    /// The blinding factors of t(x) and of the inner product vectors
+    pub taux: Scalar,
+    pub mu: Scalar,
+    /// The left and right commitments of the inner product rounds
+    pub L: Vec<EdwardsPoint>,
+    pub R: Vec<EdwardsPoint>,
+    /// The final inner product arguments
+    pub a: Scalar,
+    pub b: Scalar,
+    /// The evaluation of t(x) at the challenge
+    pub t: Scalar,
+}
+
+impl Bulletproof {
+    /// Appends the serialized proof, which omits the commitments.
+    pub fn write(&self, buffer: &mut Vec<u8>) {
+        [self.A, self.S, self.T1, self.T2]
+            .iter()
+            .for_each(|point| buffer.extend(point.compress().as_bytes()));
+        buffer.extend(self.taux.as_bytes());
+        buffer.extend(self.mu.as_bytes());
+        for points in [&self.L, &self.R].iter() {
+            write_varint(buffer, points.len() as u64);
+            points
+                .iter()
+                .for_each(|point| buffer.extend(point.compress().as_bytes()));
+        }
+        [self.a, self.b, self.t]
+            .iter()
+            .for_each(|scalar| buffer.extend(scalar.as_bytes()));
+    }
+
+    /// Returns a proof and the number of bytes read given the output commitments and a serialized proof.
+    pub fn read(commitments: &[EdwardsPoint], bytes: &[u8]) -> Result<(Self, usize), RingCtError> {
+        let mut offset = 0;
+        let A = read_point(take(bytes, &mut offset, 32)?)?;
+        let S = read_point(take(bytes, &mut offset, 32)?)?;
+        let T1 = read_point(take(bytes, &mut offset, 32)?)?;
+        let T2 = read_point(take(bytes, &mut offset, 32)?)?;
+        let taux = read_scalar(take(bytes, &mut offset, 32)?)?;
+        let mu = read_scalar(take(bytes, &mut offset, 32)?)?;
+
+        let read_points = |offset: &mut usize| -> Result<Vec<EdwardsPoint>, RingCtError> {
+            let (length, varint_length) = read_varint(&bytes[*offset..])?;
+            *offset += varint_length;
+            if length > MAX_ROUNDS {
+                return Err(RingCtError::InvalidRangeProof);
+            }
+            (0..length).map(|_| read_point(take(bytes, offset, 32)?)).collect()
+        };
+        let L = read_points(&mut offset)?;
+        let R = read_points(&mut offset)?;
+
+        let a = read_scalar(take(bytes, &mut offset, 32)?)?;
+        let b = read_scalar(take(bytes, &mut offset, 32)?)?;
+        let t = read_scalar(take(bytes, &mut offset, 32)?)?;
+
+        let proof = Self {
+            V: commitments.iter().map(|commitment| *INV_EIGHT * commitment).collect(),
+            A,
+            S,
+            T1,
+            T2,
+            taux,
+            mu,
+            L,
+            R,
+            a,
+            b,
+            t,
+        };
+        Ok((proof, offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ringct::random_scalar;
+    use curve25519_dalek::constants::ED25519_BASEPOINT_TABLE;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn proof(rng: &mut StdRng, rounds: usize) -> Bulletproof {
+        let mut point = || &random_scalar(rng) * &ED25519_BASEPOINT_TABLE;
+        let (A, S, T1, T2) = (point(), point(), point(), point());
+        let L = (0..rounds).map(|_| point()).collect();
+        let R = (0..rounds).map(|_| point()).collect();
+        Bulletproof {
+            V: vec![],
+            A,
+            S,
+            T1,
+            T2,
+            taux: random_scalar(rng),
+            mu: random_scalar(rng),
+            L,
+            R,
+            a: random_scalar(rng),
+            b: random_scalar(rng),
+            t: random_scalar(rng),
+        }
+    }
+
+    #[test]
+    fn write_and_read() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let proof = proof(&mut rng, 7);
+
+        let mut buffer = vec![];
+        proof.write(&mut buffer);
+        assert_eq!(32 * (9 + 2 * 7) + 2, buffer.len());
+
+        let (read, length) = Bulletproof::read(&[], &buffer).unwrap();
+        assert_eq!(proof, read);
+        assert_eq!(buffer.len(), length);
+    }
+
+    mod invalid {
+        use super::*;
+
+        #[test]
+        fn read_truncated_proof() {
+            let mut rng = StdRng::seed_from_u64(1);
+            let mut buffer = vec![];
+            proof(&mut rng, 6).write(&mut buffer);
+            buffer.pop();
+            assert!(Bulletproof::read(&[], &buffer).is_err());
+        }
+
+        #[test]
+        fn read_too_many_rounds() {
+            let mut rng = StdRng::seed_from_u64(2);
+            let mut buffer = vec![];
+            proof(&mut rng, MAX_ROUNDS as usize + 1).write(&mut buffer);
+            assert_eq!(
+                RingCtError::InvalidRangeProof,
+                Bulletproof::read(&[], &buffer).unwrap_err()
+            );
+        }
+    }
+}
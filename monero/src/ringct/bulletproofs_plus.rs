@@ -0,0 +1,532 @@
+#![allow(non_snake_case)]
+
+use crate::ringct::{
+    commitment::{commit, H, H_BYTES, INV_EIGHT},
+    hash_to_point::*,
+    random_scalar, read_point, read_scalar, read_varint, take, write_varint, RingCtError,
+};
+
+use curve25519_dalek::{
+    constants::{ED25519_BASEPOINT_POINT, ED25519_BASEPOINT_TABLE},
+    edwards::EdwardsPoint,
+    scalar::Scalar,
+    traits::{IsIdentity, MultiscalarMul, VartimeMultiscalarMul},
+};
+use rand::Rng;
+use tiny_keccak::keccak256;
+
+/// The number of bits of each proven amount.
+const BITS: usize = 64;
+
+/// The maximum number of amounts in an aggregated range proof.
+pub const MAX_OUTPUTS: usize = 16;
+
+/// The number of inner product rounds of the largest aggregated range proof.
+pub(crate) const MAX_ROUNDS: u64 = 10;
+
+lazy_static! {
+    /// The vector generators (Gi, Hi) for the largest aggregated range proof.
+    static ref GENERATORS: (Vec<EdwardsPoint>, Vec<EdwardsPoint>) = {
+        let generator = |index: u64| {
+            let mut data = H_BYTES.to_vec();
+            data.extend(b"bulletproof_plus");
+            write_varint(&mut data, index);
+            hash_to_point(&keccak256(&data))
+        };
+
+        let count = (BITS * MAX_OUTPUTS) as u64;
+        let Gi = (0..count).map(|i| generator(2 * i + 1)).collect();
+        let Hi = (0..count).map(|i| generator(2 * i)).collect();
+        (Gi, Hi)
+    };
+
+    /// The initial value of the Fiat-Shamir transcript.
+    static ref INITIAL_TRANSCRIPT: [u8; 32] =
+        hash_to_point(&keccak256(b"bulletproof_plus_transcript")).compress().to_bytes();
+}
+
+/// Represents an aggregated Bulletproofs+ range proof that amounts lie in [0, 2^64)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulletproofPlus {
+    /// The amount commitments multiplied by the inverse of 8, which are not serialized
+    pub V: Vec<EdwardsPoint>,
+    /// The commitment to the bits of the amounts
+    pub A: EdwardsPoint,
+    /// The commitment of the final inner product round
+    pub A1: EdwardsPoint,
+    /// The blinding commitment of the final inner product round
+    pub B: EdwardsPoint,
+    /// The final responses
+    pub r1: Scalar,
+    pub s1: Scalar,
+    pub d1: Scalar,
+    /// The left and right commitments of the inner product rounds
+    pub L: Vec<EdwardsPoint>,
+    pub R: Vec<EdwardsPoint>,
+}
+
+/// Represents the Fiat-Shamir transcript of a proof
+struct Transcript([u8; 32]);
+
+impl Transcript {
+    fn new(V: &[EdwardsPoint]) -> Self {
+        let commitments: Vec<u8> = V
+            .iter()
+            .flat_map(|point| point.compress().to_bytes().to_vec())
+            .collect();
+        let mut transcript = Self(*INITIAL_TRANSCRIPT);
+        transcript.update(&[hash_to_scalar(&commitments).to_bytes()]);
+        transcript
+    }
+
+    /// Updates the transcript with the hash of itself and the given elements and returns it as a challenge.
+    fn update(&mut self, elements: &[[u8; 32]]) -> Scalar {
+        let mut data = self.0.to_vec();
+        elements.iter().for_each(|element| data.extend(element));
+        let challenge = hash_to_scalar(&data);
+        self.0 = challenge.to_bytes();
+        challenge
+    }
+}
+
+/// Returns the powers `[1, x, x^2, ..., x^(length - 1)]`.
+fn powers(x: &Scalar, length: usize) -> Vec<Scalar> {
+    let mut powers = Vec::with_capacity(length);
+    let mut power = Scalar::one();
+    for _ in 0..length {
+        powers.push(power);
+        power *= x;
+    }
+    powers
+}
+
+/// Returns the inner product of `a` and `b` weighted by `[y, y^2, ..., y^n]`.
+fn weighted_inner_product(a: &[Scalar], b: &[Scalar], y: &Scalar) -> Scalar {
+    let mut result = Scalar::zero();
+    let mut power = Scalar::one();
+    for (a, b) in a.iter().zip(b.iter()) {
+        power *= y;
+        result += a * b * power;
+    }
+    result
+}
+
+/// Returns the windowed vector `d[j * 64 + i] = z^(2 * (j + 1)) * 2^i`.
+fn windowed_vector(z: &Scalar, M: usize) -> Vec<Scalar> {
+    let z_squared = z * z;
+    let mut d = vec![z_squared; M * BITS];
+    for i in 1..BITS {
+        d[i] = d[i - 1] + d[i - 1];
+    }
+    for j in 1..M {
+        for i in 0..BITS {
+            d[j * BITS + i] = d[(j - 1) * BITS + i] * z_squared;
+        }
+    }
+    d
+}
+
+impl BulletproofPlus {
+    /// Returns a range proof for the amounts committed with the given masks.
+    pub fn prove<R: Rng>(amounts: &[u64], masks: &[Scalar], rng: &mut R) -> Result<Self, RingCtError> {
+        if amounts.is_empty() || amounts.len() > MAX_OUTPUTS || amounts.len() != masks.len() {
+            return Err(RingCtError::InvalidOutputCount(amounts.len()));
+        }
+
+        let M = amounts.len().next_power_of_two();
+        let MN = M * BITS;
+        let (Gi, Hi) = &*GENERATORS;
+
+        let V: Vec<EdwardsPoint> = amounts
+            .iter()
+            .zip(masks.iter())
+            .map(|(amount, mask)| *INV_EIGHT * commit(mask, *amount))
+            .collect();
+        let mut transcript = Transcript::new(&V);
+
+        // aL holds the bits of the amounts, and aR = aL - 1.
+        let aL: Vec<Scalar> = (0..MN)
+            .map(|i| match amounts.get(i / BITS) {
+                Some(amount) => Scalar::from((amount >> (i % BITS)) & 1),
+                None => Scalar::zero(),
+            })
+            .collect();
+        let aR: Vec<Scalar> = aL.iter().map(|bit| bit - Scalar::one()).collect();
+
+        let alpha = random_scalar(rng);
+        let A = EdwardsPoint::multiscalar_mul(
+            aL.iter()
+                .chain(aR.iter())
+                .chain(Some(&alpha))
+                .map(|scalar| scalar * *INV_EIGHT),
+            Gi[..MN]
+                .iter()
+                .chain(Hi[..MN].iter())
+                .chain(Some(&ED25519_BASEPOINT_POINT)),
+        );
+
+        let y = transcript.update(&[A.compress().to_bytes()]);
+        let z = hash_to_scalar(y.as_bytes());
+        transcript.0 = z.to_bytes();
+        if y == Scalar::zero() || z == Scalar::zero() {
+            return Err(RingCtError::InvalidRangeProof);
+        }
+
+        let d = windowed_vector(&z, M);
+        let y_powers = powers(&y, MN + 2);
+
+        let mut a: Vec<Scalar> = aL.iter().map(|bit| bit - z).collect();
+        let mut b: Vec<Scalar> = (0..MN).map(|i| aR[i] + z + d[i] * y_powers[MN - i]).collect();
+
+        let mut alpha1 = alpha;
+        let mut z_power = Scalar::one();
+        for mask in masks {
+            z_power *= z * z;
+            alpha1 += z_power * y_powers[MN + 1] * mask;
+        }
+
+        let y_inverse_powers = powers(&y.invert(), MN);
+        let mut G = Gi[..MN].to_vec();
+        let mut H_ = Hi[..MN].to_vec();
+        let mut L = vec![];
+        let mut R = vec![];
+
+        let mut n = MN;
+        while n > 1 {
+            n /= 2;
+            let (a_lo, a_hi) = a.split_at(n);
+            let (b_lo, b_hi) = b.split_at(n);
+            let (G_lo, G_hi) = G.split_at(n);
+            let (H_lo, H_hi) = H_.split_at(n);
+
+            let a_hi_y: Vec<Scalar> = a_hi.iter().map(|a| a * y_powers[n]).collect();
+            let cL = weighted_inner_product(a_lo, b_hi, &y);
+            let cR = weighted_inner_product(&a_hi_y, b_lo, &y);
+            let dL = random_scalar(rng);
+            let dR = random_scalar(rng);
+
+            let L_round = EdwardsPoint::multiscalar_mul(
+                a_lo.iter()
+                    .map(|a| a * y_inverse_powers[n])
+                    .chain(b_hi.iter().cloned())
+                    .chain(vec![cL, dL])
+                    .map(|scalar| scalar * *INV_EIGHT),
+                G_hi.iter()
+                    .chain(H_lo.iter())
+                    .chain(vec![&*H, &ED25519_BASEPOINT_POINT]),
+            );
+            let R_round = EdwardsPoint::multiscalar_mul(
+                a_hi_y
+                    .iter()
+                    .chain(b_lo.iter())
+                    .chain(vec![&cR, &dR])
+                    .map(|scalar| scalar * *INV_EIGHT),
+                G_lo.iter()
+                    .chain(H_hi.iter())
+                    .chain(vec![&*H, &ED25519_BASEPOINT_POINT]),
+            );
+
+            let e = transcript.update(&[L_round.compress().to_bytes(), R_round.compress().to_bytes()]);
+            let e_inverse = e.invert();
+
+            G = (0..n)
+                .map(|i| {
+                    EdwardsPoint::vartime_multiscalar_mul(&[e_inverse, y_inverse_powers[n] * e], &[G_lo[i], G_hi[i]])
+                })
+                .collect();
+            H_ = (0..n)
+                .map(|i| EdwardsPoint::vartime_multiscalar_mul(&[e, e_inverse], &[H_lo[i], H_hi[i]]))
+                .collect();
+            a = (0..n)
+                .map(|i| e * a_lo[i] + e_inverse * y_powers[n] * a_hi[i])
+                .collect();
+            b = (0..n).map(|i| e_inverse * b_lo[i] + e * b_hi[i]).collect();
+
+            alpha1 += dL * e * e + dR * e_inverse * e_inverse;
+            L.push(L_round);
+            R.push(R_round);
+        }
+
+        let r = random_scalar(rng);
+        let s = random_scalar(rng);
+        let delta = random_scalar(rng);
+        let eta = random_scalar(rng);
+
+        let A1 = EdwardsPoint::multiscalar_mul(
+            vec![r, s, delta, r * y * b[0] + s * y * a[0]]
+                .into_iter()
+                .map(|scalar| scalar * *INV_EIGHT),
+            vec![G[0], H_[0], ED25519_BASEPOINT_POINT, *H],
+        );
+        let B = EdwardsPoint::multiscalar_mul(
+            vec![r * y * s * *INV_EIGHT, eta * *INV_EIGHT],
+            vec![*H, ED25519_BASEPOINT_POINT],
+        );
+
+        let e = transcript.update(&[A1.compress().to_bytes(), B.compress().to_bytes()]);
+
+        Ok(Self {
+            V,
+            A,
+            A1,
+            B,
+            r1: r + a[0] * e,
+            s1: s + b[0] * e,
+            d1: eta + delta * e + alpha1 * e * e,
+            L,
+            R,
+        })
+    }
+
+    /// Verifies the range proof against its commitments.
+    pub fn verify(&self) -> Result<(), RingCtError> {
+        let m = self.V.len();
+        if m == 0 || m > MAX_OUTPUTS {
+            return Err(RingCtError::InvalidOutputCount(m));
+        }
+
+        let M = m.next_power_of_two();
+        let MN = M * BITS;
+        let rounds = MN.trailing_zeros() as usize;
+        if self.L.len() != rounds || self.R.len() != rounds {
+            return Err(RingCtError::InvalidRangeProof);
+        }
+        let (Gi, Hi) = &*GENERATORS;
+
+        // Replay the transcript to recover the challenges.
+        let mut transcript = Transcript::new(&self.V);
+        let y = transcript.update(&[self.A.compress().to_bytes()]);
+        let z = hash_to_scalar(y.as_bytes());
+        transcript.0 = z.to_bytes();
+        if y == Scalar::zero() || z == Scalar::zero() {
+            return Err(RingCtError::InvalidRangeProof);
+        }
+        let challenges: Vec<Scalar> = self
+            .L
+            .iter()
+            .zip(self.R.iter())
+            .map(|(L, R)| transcript.update(&[L.compress().to_bytes(), R.compress().to_bytes()]))
+            .collect();
+        let e = transcript.update(&[self.A1.compress().to_bytes(), self.B.compress().to_bytes()]);
+
+        let d = windowed_vector(&z, M);
+        let y_powers = powers(&y, MN + 2);
+        let z_squared = z * z;
+
+        // The commitment to the weighted inner product of (aL - z, aR + z + d * y^(MN - i)).
+        let mut V_scalars = Vec::with_capacity(m);
+        let mut z_power = Scalar::one();
+        for _ in 0..m {
+            z_power *= z_squared;
+            V_scalars.push(z_power * y_powers[MN + 1]);
+        }
+        let y_sum: Scalar = y_powers[1..=MN].iter().sum();
+        let d_sum: Scalar = d.iter().sum();
+        let H_scalar = (z - z_squared) * y_sum - z * y_powers[MN + 1] * d_sum;
+
+        let mut P = self.A.mul_by_cofactor()
+            + EdwardsPoint::vartime_multiscalar_mul(
+                (0..MN)
+                    .map(|_| -z)
+                    .chain((0..MN).map(|i| z + d[i] * y_powers[MN - i]))
+                    .chain(V_scalars)
+                    .chain(Some(H_scalar)),
+                Gi[..MN]
+                    .iter()
+                    .chain(Hi[..MN].iter())
+                    .cloned()
+                    .chain(self.V.iter().map(|V| V.mul_by_cofactor()))
+                    .chain(Some(*H)),
+            );
+
+        // Fold the generators and the commitment through the inner product rounds.
+        let y_inverse_powers = powers(&y.invert(), MN);
+        let mut G = Gi[..MN].to_vec();
+        let mut H_ = Hi[..MN].to_vec();
+        let mut n = MN;
+        for ((L, R), e) in self.L.iter().zip(self.R.iter()).zip(challenges.iter()) {
+            n /= 2;
+            let e_inverse = e.invert();
+            let y_inverse_n = y_inverse_powers[n];
+
+            G = (0..n)
+                .map(|i| EdwardsPoint::vartime_multiscalar_mul(&[e_inverse, y_inverse_n * e], &[G[i], G[n + i]]))
+                .collect();
+            H_ = (0..n)
+                .map(|i| EdwardsPoint::vartime_multiscalar_mul(&[*e, e_inverse], &[H_[i], H_[n + i]]))
+                .collect();
+            P += EdwardsPoint::vartime_multiscalar_mul(
+                &[e * e, e_inverse * e_inverse],
+                &[L.mul_by_cofactor(), R.mul_by_cofactor()],
+            );
+        }
+
+        let lhs = EdwardsPoint::vartime_multiscalar_mul(
+            &[e * e, e, Scalar::one()],
+            &[P, self.A1.mul_by_cofactor(), self.B.mul_by_cofactor()],
+        );
+        let rhs = EdwardsPoint::vartime_multiscalar_mul(
+            &[self.r1 * e, self.s1 * e, self.r1 * y * self.s1],
+            &[G[0], H_[0], *H],
+        ) + &self.d1 * &ED25519_BASEPOINT_TABLE;
+
+        match (lhs - rhs).is_identity() {
+            true => Ok(()),
+            false => Err(RingCtError::InvalidRangeProof),
+        }
+    }
+
+    /// Returns the amount commitments of the proof, which are 8 times the stored commitments.
+    pub fn commitments(&self) -> Vec<EdwardsPoint> {
+        self.V.iter().map(|V| V.mul_by_cofactor()).collect()
+    }
+
+    /// Appends the serialized proof, which omits the commitments.
+    pub fn write(&self, buffer: &mut Vec<u8>) {
+        buffer.extend(self.A.compress().as_bytes());
+        buffer.extend(self.A1.compress().as_bytes());
+        buffer.extend(self.B.compress().as_bytes());
+        buffer.extend(self.r1.as_bytes());
+        buffer.extend(self.s1.as_bytes());
+        buffer.extend(self.d1.as_bytes());
+        for points in [&self.L, &self.R].iter() {
+            write_varint(buffer, points.len() as u64);
+            points
+                .iter()
+                .for_each(|point| buffer.extend(point.compress().as_bytes()));
+        }
+    }
+
+    /// Returns a proof and the number of bytes read given the output commitments and a serialized proof.
+    pub fn read(commitments: &[EdwardsPoint], bytes: &[u8]) -> Result<(Self, usize), RingCtError> {
+        let mut offset = 0;
+        let A = read_point(take(bytes, &mut offset, 32)?)?;
+        let A1 = read_point(take(bytes, &mut offset, 32)?)?;
+        let B = read_point(take(bytes, &mut offset, 32)?)?;
+        let r1 = read_scalar(take(bytes, &mut offset, 32)?)?;
+        let s1 = read_scalar(take(bytes, &mut offset, 32)?)?;
+        let d1 = read_scalar(take(bytes, &mut offset, 32)?)?;
+
+        let read_points = |offset: &mut usize| -> Result<Vec<EdwardsPoint>, RingCtError> {
+            let (length, varint_length) = read_varint(&bytes[*offset..])?;
+            *offset += varint_length;
+            if length > MAX_ROUNDS {
+                return Err(RingCtError::InvalidRangeProof);
+            }
+            (0..length).map(|_| read_point(take(bytes, offset, 32)?)).collect()
+        };
+        let L = read_points(&mut offset)?;
+        let R = read_points(&mut offset)?;
+
+        let proof = Self {
+            V: commitments.iter().map(|commitment| *INV_EIGHT * commitment).collect(),
+            A,
+            A1,
+            B,
+            r1,
+            s1,
+            d1,
+            L,
+            R,
+        };
+        Ok((proof, offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn prove(rng: &mut StdRng, amounts: &[u64]) -> (BulletproofPlus, Vec<Scalar>) {
+        let masks: Vec<Scalar> = amounts.iter().map(|_| random_scalar(rng)).collect();
+        (BulletproofPlus::prove(amounts, &masks, rng).unwrap(), masks)
+    }
+
+    #[test]
+    fn prove_and_verify() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let cases: [&[u64]; 4] = [
+            &[0],
+            &[1_000_000_000_000, u64::max_value()],
+            &[1, 2, 3],
+            &[5; MAX_OUTPUTS],
+        ];
+
+        for amounts in cases.iter() {
+            let (proof, masks) = prove(&mut rng, amounts);
+            assert!(proof.verify().is_ok());
+
+            let commitments: Vec<EdwardsPoint> = amounts
+                .iter()
+                .zip(masks.iter())
+                .map(|(amount, mask)| commit(mask, *amount))
+                .collect();
+            assert_eq!(commitments, proof.commitments());
+
+            let mut buffer = vec![];
+            proof.write(&mut buffer);
+            let (read, length) = BulletproofPlus::read(&commitments, &buffer).unwrap();
+            assert_eq!(buffer.len(), length);
+            assert_eq!(proof, read);
+        }
+    }
+
+    #[test]
+    fn generators() {
+        let (Gi, Hi) = &*GENERATORS;
+        assert_eq!(BITS * MAX_OUTPUTS, Gi.len());
+        assert_eq!(BITS * MAX_OUTPUTS, Hi.len());
+        assert!(Gi.iter().chain(Hi.iter()).all(|point| point.is_torsion_free()));
+        assert_ne!(Gi[0], Hi[0]);
+        assert_eq!(
+            "4a677c90eb73051e790da45591107f6ee105904d9187c5d35471096c445a2275",
+            hex::encode(&*INITIAL_TRANSCRIPT)
+        );
+    }
+
+    mod invalid {
+        use super::*;
+
+        #[test]
+        fn verify_tampered() {
+            let mut rng = StdRng::seed_from_u64(1);
+            let (proof, _) = prove(&mut rng, &[10, 20]);
+
+            let mut tampered = proof.clone();
+            tampered.V[0] += *INV_EIGHT * *H;
+            assert!(tampered.verify().is_err());
+
+            let mut tampered = proof.clone();
+            tampered.r1 += Scalar::one();
+            assert!(tampered.verify().is_err());
+
+            let mut tampered = proof.clone();
+            tampered.L.swap(0, 1);
+            assert!(tampered.verify().is_err());
+
+            let mut tampered = proof.clone();
+            tampered.V.pop();
+            assert!(tampered.verify().is_err());
+        }
+
+        #[test]
+        fn prove_invalid_counts() {
+            let mut rng = StdRng::seed_from_u64(2);
+            assert!(BulletproofPlus::prove(&[], &[], &mut rng).is_err());
+            assert!(BulletproofPlus::prove(&[1, 2], &[Scalar::one()], &mut rng).is_err());
+            assert!(
+                BulletproofPlus::prove(&[1; MAX_OUTPUTS + 1], &[Scalar::one(); MAX_OUTPUTS + 1], &mut rng).is_err()
+            );
+        }
+
+        #[test]
+        fn verify_wrapped_amount() {
+            // A commitment to a negative amount cannot be proven in range.
+            let mut rng = StdRng::seed_from_u64(3);
+            let (mut proof, masks) = prove(&mut rng, &[7]);
+            proof.V[0] = *INV_EIGHT * (commit(&masks[0], 0) - *H);
+            assert!(proof.verify().is_err());
+        }
+    }
+}
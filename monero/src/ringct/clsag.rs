@@ -0,0 +1,350 @@
+#![allow(non_snake_case)]
+
+use crate::ringct::{commitment::INV_EIGHT, hash_to_point::*, random_scalar, read_point, read_scalar, RingCtError};
+
+use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, edwards::EdwardsPoint, scalar::Scalar, traits::IsIdentity};
+use rand::Rng;
+
+/// The domain separators of the CLSAG hashes, zero-padded to 32 bytes.
+const CLSAG_ROUND: &[u8] = b"CLSAG_round";
+const CLSAG_AGG_0: &[u8] = b"CLSAG_agg_0";
+const CLSAG_AGG_1: &[u8] = b"CLSAG_agg_1";
+
+/// Returns the key image `x * Hp(x * G)` of a one-time private key.
+pub fn key_image(secret_key: &Scalar) -> EdwardsPoint {
+    let public_key = (secret_key * &ED25519_BASEPOINT_TABLE).compress();
+    secret_key * hash_to_point(public_key.as_bytes())
+}
+
+/// Represents a member of a ring, a one-time output key with its amount commitment
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RingMember {
+    /// The one-time output key
+    pub key: EdwardsPoint,
+    /// The amount commitment
+    pub commitment: EdwardsPoint,
+}
+
+/// Represents a CLSAG ring signature for one transaction input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clsag {
+    /// The responses of the ring members
+    pub s: Vec<Scalar>,
+    /// The challenge of the first ring member
+    pub c1: Scalar,
+    /// The commitment key image, multiplied by the inverse of 8
+    pub D: EdwardsPoint,
+}
+
+/// Represents the hashes shared by signing and verification
+struct ClsagTranscript {
+    /// The preimage of the round hashes, ending in the two round points
+    round: Vec<u8>,
+    /// The aggregation coefficient of the ring keys
+    mu_P: Scalar,
+    /// The aggregation coefficient of the ring commitments
+    mu_C: Scalar,
+}
+
+impl ClsagTranscript {
+    fn new(
+        message: &[u8; 32],
+        ring: &[RingMember],
+        key_image: &EdwardsPoint,
+        D: &EdwardsPoint,
+        pseudo_out: &EdwardsPoint,
+    ) -> Self {
+        let mut keys = Vec::with_capacity(64 * ring.len() + 32);
+        ring.iter()
+            .for_each(|member| keys.extend(member.key.compress().as_bytes()));
+        ring.iter()
+            .for_each(|member| keys.extend(member.commitment.compress().as_bytes()));
+
+        let aggregate = |domain: &[u8]| {
+            let mut data = domain_separator(domain);
+            data.extend(&keys);
+            data.extend(key_image.compress().as_bytes());
+            data.extend(D.compress().as_bytes());
+            data.extend(pseudo_out.compress().as_bytes());
+            hash_to_scalar(&data)
+        };
+
+        let mut round = domain_separator(CLSAG_ROUND);
+        round.extend(&keys);
+        round.extend(pseudo_out.compress().as_bytes());
+        round.extend(message);
+
+        Self {
+            mu_P: aggregate(CLSAG_AGG_0),
+            mu_C: aggregate(CLSAG_AGG_1),
+            round,
+        }
+    }
+
+    /// Returns the challenge of the next ring member given the points of the current one.
+    fn challenge(&self, L: &EdwardsPoint, R: &EdwardsPoint) -> Scalar {
+        let mut data = self.round.clone();
+        data.extend(L.compress().as_bytes());
+        data.extend(R.compress().as_bytes());
+        hash_to_scalar(&data)
+    }
+}
+
+/// Returns the domain separator zero-padded to 32 bytes.
+fn domain_separator(domain: &[u8]) -> Vec<u8> {
+    let mut separator = domain.to_vec();
+    separator.resize(32, 0);
+    separator
+}
+
+impl Clsag {
+    /// Returns a CLSAG signature and the key image of the signer at `index` of the ring.
+    ///
+    /// The commitment of the pseudo output must be the real commitment minus `mask_difference * G`.
+    pub fn sign<R: Rng>(
+        message: &[u8; 32],
+        ring: &[RingMember],
+        index: usize,
+        secret_key: &Scalar,
+        mask_difference: &Scalar,
+        pseudo_out: &EdwardsPoint,
+        rng: &mut R,
+    ) -> Result<(Self, EdwardsPoint), RingCtError> {
+        if index >= ring.len() {
+            return Err(RingCtError::InvalidRingIndex(index, ring.len()));
+        }
+        if secret_key * &ED25519_BASEPOINT_TABLE != ring[index].key {
+            return Err(RingCtError::InvalidSecretKey);
+        }
+        if mask_difference * &ED25519_BASEPOINT_TABLE != ring[index].commitment - pseudo_out {
+            return Err(RingCtError::InvalidCommitment);
+        }
+
+        let hash_point = hash_to_point(ring[index].key.compress().as_bytes());
+        let key_image = secret_key * hash_point;
+        let D = mask_difference * hash_point;
+        let D_inv_eight = *INV_EIGHT * D;
+
+        let transcript = ClsagTranscript::new(message, ring, &key_image, &D_inv_eight, pseudo_out);
+
+        let alpha = random_scalar(rng);
+        let mut c = transcript.challenge(&(&alpha * &ED25519_BASEPOINT_TABLE), &(alpha * hash_point));
+
+        // The challenge of the first member is the initial one if the signer is the last member.
+        let mut s = vec![Scalar::zero(); ring.len()];
+        let mut c1 = c;
+
+        let mut i = (index + 1) % ring.len();
+        while i != index {
+            s[i] = random_scalar(rng);
+            let (L, R) = Self::round(&transcript, &ring[i], &s[i], &c, &key_image, &D, pseudo_out);
+            c = transcript.challenge(&L, &R);
+
+            i = (i + 1) % ring.len();
+            if i == 0 {
+                c1 = c;
+            }
+        }
+
+        s[index] = alpha - c * (transcript.mu_P * secret_key + transcript.mu_C * mask_difference);
+
+        Ok((Self { s, c1, D: D_inv_eight }, key_image))
+    }
+
+    /// Verifies the CLSAG signature of the message for the ring, key image and pseudo output.
+    pub fn verify(
+        &self,
+        message: &[u8; 32],
+        ring: &[RingMember],
+        key_image: &EdwardsPoint,
+        pseudo_out: &EdwardsPoint,
+    ) -> Result<(), RingCtError> {
+        if ring.is_empty() || self.s.len() != ring.len() {
+            return Err(RingCtError::InvalidRingSize(self.s.len(), ring.len()));
+        }
+        if key_image.is_identity() || !key_image.is_torsion_free() {
+            return Err(RingCtError::InvalidKeyImage(key_image.compress().to_bytes()));
+        }
+
+        let transcript = ClsagTranscript::new(message, ring, key_image, &self.D, pseudo_out);
+        let D = self.D.mul_by_cofactor();
+
+        let mut c = self.c1;
+        for (member, s) in ring.iter().zip(self.s.iter()) {
+            let (L, R) = Self::round(&transcript, member, s, &c, key_image, &D, pseudo_out);
+            c = transcript.challenge(&L, &R);
+        }
+
+        match c == self.c1 {
+            true => Ok(()),
+            false => Err(RingCtError::InvalidSignature),
+        }
+    }
+
+    /// Returns the round points `L = s*G + c_P*P + c_C*(C - C')` and `R = s*Hp(P) + c_P*I + c_C*D`.
+    fn round(
+        transcript: &ClsagTranscript,
+        member: &RingMember,
+        s: &Scalar,
+        c: &Scalar,
+        key_image: &EdwardsPoint,
+        D: &EdwardsPoint,
+        pseudo_out: &EdwardsPoint,
+    ) -> (EdwardsPoint, EdwardsPoint) {
+        let c_P = transcript.mu_P * c;
+        let c_C = transcript.mu_C * c;
+
+        let L = s * &ED25519_BASEPOINT_TABLE + c_P * member.key + c_C * (member.commitment - pseudo_out);
+        let R = s * hash_to_point(member.key.compress().as_bytes()) + c_P * key_image + c_C * D;
+        (L, R)
+    }
+
+    /// Appends the serialized signature, which is the responses followed by `c1` and `D`.
+    pub fn write(&self, buffer: &mut Vec<u8>) {
+        self.s.iter().for_each(|s| buffer.extend(s.as_bytes()));
+        buffer.extend(self.c1.as_bytes());
+        buffer.extend(self.D.compress().as_bytes());
+    }
+
+    /// Returns a signature given the ring size and a serialized signature.
+    pub fn read(ring_size: usize, bytes: &[u8]) -> Result<Self, RingCtError> {
+        if bytes.len() != 32 * (ring_size + 2) {
+            return Err(RingCtError::InvalidByteLength(bytes.len()));
+        }
+
+        let mut s = bytes[..32 * (ring_size + 1)]
+            .chunks(32)
+            .map(read_scalar)
+            .collect::<Result<Vec<Scalar>, RingCtError>>()?;
+        let c1 = s.pop().unwrap_or_default();
+        let D = read_point(&bytes[32 * (ring_size + 1)..])?;
+
+        Ok(Self { s, c1, D })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ringct::commitment::commit;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const RING_SIZE: usize = 16;
+
+    fn ring_with_signer(rng: &mut StdRng, index: usize, secret_key: &Scalar, mask: &Scalar) -> Vec<RingMember> {
+        (0..RING_SIZE)
+            .map(|i| match i == index {
+                true => RingMember {
+                    key: secret_key * &ED25519_BASEPOINT_TABLE,
+                    commitment: commit(mask, 1000),
+                },
+                false => RingMember {
+                    key: &random_scalar(rng) * &ED25519_BASEPOINT_TABLE,
+                    commitment: commit(&random_scalar(rng), rng.gen()),
+                },
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let message = [7u8; 32];
+
+        for index in [0, 1, 7, RING_SIZE - 1].iter() {
+            let secret_key = random_scalar(&mut rng);
+            let mask = random_scalar(&mut rng);
+            let pseudo_mask = random_scalar(&mut rng);
+            let pseudo_out = commit(&pseudo_mask, 1000);
+            let ring = ring_with_signer(&mut rng, *index, &secret_key, &mask);
+
+            let (signature, image) = Clsag::sign(
+                &message,
+                &ring,
+                *index,
+                &secret_key,
+                &(mask - pseudo_mask),
+                &pseudo_out,
+                &mut rng,
+            )
+            .unwrap();
+
+            assert_eq!(key_image(&secret_key), image);
+            assert!(signature.verify(&message, &ring, &image, &pseudo_out).is_ok());
+
+            let mut buffer = vec![];
+            signature.write(&mut buffer);
+            assert_eq!(32 * (RING_SIZE + 2), buffer.len());
+            assert_eq!(signature, Clsag::read(RING_SIZE, &buffer).unwrap());
+        }
+    }
+
+    mod invalid {
+        use super::*;
+
+        #[test]
+        fn verify_tampered() {
+            let mut rng = StdRng::seed_from_u64(1);
+            let message = [7u8; 32];
+            let secret_key = random_scalar(&mut rng);
+            let mask = random_scalar(&mut rng);
+            let pseudo_mask = random_scalar(&mut rng);
+            let pseudo_out = commit(&pseudo_mask, 1000);
+            let ring = ring_with_signer(&mut rng, 3, &secret_key, &mask);
+
+            let (signature, image) = Clsag::sign(
+                &message,
+                &ring,
+                3,
+                &secret_key,
+                &(mask - pseudo_mask),
+                &pseudo_out,
+                &mut rng,
+            )
+            .unwrap();
+
+            assert!(signature.verify(&[8u8; 32], &ring, &image, &pseudo_out).is_err());
+            assert!(signature
+                .verify(&message, &ring, &image, &commit(&pseudo_mask, 1001))
+                .is_err());
+            assert!(signature
+                .verify(&message, &ring, &key_image(&random_scalar(&mut rng)), &pseudo_out)
+                .is_err());
+            assert!(signature.verify(&message, &ring[1..], &image, &pseudo_out).is_err());
+        }
+
+        #[test]
+        fn sign_unbalanced() {
+            let mut rng = StdRng::seed_from_u64(2);
+            let secret_key = random_scalar(&mut rng);
+            let mask = random_scalar(&mut rng);
+            let pseudo_mask = random_scalar(&mut rng);
+            let ring = ring_with_signer(&mut rng, 0, &secret_key, &mask);
+
+            // The pseudo output commits to a different amount than the real input.
+            let pseudo_out = commit(&pseudo_mask, 999);
+            let result = Clsag::sign(
+                &[0u8; 32],
+                &ring,
+                0,
+                &secret_key,
+                &(mask - pseudo_mask),
+                &pseudo_out,
+                &mut rng,
+            );
+            assert!(result.is_err());
+
+            let pseudo_out = commit(&pseudo_mask, 1000);
+            let result = Clsag::sign(
+                &[0u8; 32],
+                &ring,
+                1,
+                &secret_key,
+                &(mask - pseudo_mask),
+                &pseudo_out,
+                &mut rng,
+            );
+            assert!(result.is_err());
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::ringct::hash_to_point::hash_to_scalar;
+
+use curve25519_dalek::{
+    constants::ED25519_BASEPOINT_TABLE,
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar,
+};
+use tiny_keccak::keccak256;
+
+/// The compressed amount generator H, which is 8 times the point decoded from Keccak-256(G).
+pub const H_BYTES: [u8; 32] = [
+    0x8b, 0x65, 0x59, 0x70, 0x15, 0x37, 0x99, 0xaf, 0x2a, 0xea, 0xdc, 0x9f, 0xf1, 0xad, 0xd0, 0xea, 0x6c, 0x72, 0x51,
+    0xd5, 0x41, 0x54, 0xcf, 0xa9, 0x2c, 0x17, 0x3a, 0x0d, 0xd3, 0x9c, 0x1f, 0x94,
+];
+
+lazy_static! {
+    /// The amount generator H of Pedersen commitments.
+    pub static ref H: EdwardsPoint = CompressedEdwardsY(H_BYTES).decompress().unwrap();
+
+    /// The inverse of 8, used to store points that are multiplied by the cofactor on verification.
+    pub static ref INV_EIGHT: Scalar = Scalar::from(8u64).invert();
+}
+
+/// Returns the Pedersen commitment `mask * G + amount * H`.
+pub fn commit(mask: &Scalar, amount: u64) -> EdwardsPoint {
+    mask * &ED25519_BASEPOINT_TABLE + Scalar::from(amount) * *H
+}
+
+/// Returns the commitment of a pre-RingCT output, which uses a mask of one.
+pub fn zero_commit(amount: u64) -> EdwardsPoint {
+    commit(&Scalar::one(), amount)
+}
+
+/// Returns the commitment mask of an output given its shared secret `H_s(8rA || i)`.
+pub fn commitment_mask(shared_secret: &Scalar) -> Scalar {
+    let mut data = b"commitment_mask".to_vec();
+    data.extend(shared_secret.as_bytes());
+    hash_to_scalar(&data)
+}
+
+/// Returns the 8-byte keystream that masks the amount of an output given its shared secret.
+fn amount_keystream(shared_secret: &Scalar) -> [u8; 8] {
+    let mut data = b"amount".to_vec();
+    data.extend(shared_secret.as_bytes());
+
+    let mut keystream = [0u8; 8];
+    keystream.copy_from_slice(&keccak256(&data)[0..8]);
+    keystream
+}
+
+/// Returns the encrypted amount of an output as stored in `ecdhInfo` (compact version 2).
+pub fn encode_amount(amount: u64, shared_secret: &Scalar) -> [u8; 8] {
+    let keystream = amount_keystream(shared_secret);
+    let mut encrypted = amount.to_le_bytes();
+    encrypted
+        .iter_mut()
+        .zip(keystream.iter())
+        .for_each(|(byte, key)| *byte ^= key);
+    encrypted
+}
+
+/// Returns the amount of an output given its encrypted amount (compact version 2).
+pub fn decode_amount(encrypted: &[u8; 8], shared_secret: &Scalar) -> u64 {
+    let keystream = amount_keystream(shared_secret);
+    let mut amount = *encrypted;
+    amount
+        .iter_mut()
+        .zip(keystream.iter())
+        .for_each(|(byte, key)| *byte ^= key);
+    u64::from_le_bytes(amount)
+}
+
+/// Returns the mask and amount of an output encrypted with the original 64-byte `ecdhInfo` format.
+pub fn decode_amount_v1(
+    encrypted_mask: &[u8; 32],
+    encrypted_amount: &[u8; 32],
+    shared_secret: &Scalar,
+) -> (Scalar, u64) {
+    let mask_secret = hash_to_scalar(shared_secret.as_bytes());
+    let amount_secret = hash_to_scalar(mask_secret.as_bytes());
+
+    let mask = Scalar::from_bytes_mod_order(*encrypted_mask) - mask_secret;
+    let amount = Scalar::from_bytes_mod_order(*encrypted_amount) - amount_secret;
+
+    let mut amount_bytes = [0u8; 8];
+    amount_bytes.copy_from_slice(&amount.as_bytes()[0..8]);
+    (mask, u64::from_le_bytes(amount_bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ringct::hash_to_point::hash_to_point;
+    use curve25519_dalek::constants::ED25519_BASEPOINT_COMPRESSED;
+
+    #[test]
+    fn generator() {
+        let basepoint = ED25519_BASEPOINT_COMPRESSED;
+        let expected = CompressedEdwardsY(keccak256(basepoint.as_bytes()))
+            .decompress()
+            .unwrap()
+            .mul_by_cofactor();
+        assert_eq!(expected, *H);
+        assert_ne!(hash_to_point(basepoint.as_bytes()), *H);
+        assert_eq!(Scalar::one(), *INV_EIGHT * Scalar::from(8u64));
+    }
+
+    #[test]
+    fn encode_decode_amount() {
+        let shared_secret = hash_to_scalar(b"shared secret");
+        let amount = 1_234_567_890_123u64;
+
+        let encrypted = encode_amount(amount, &shared_secret);
+        assert_ne!(amount.to_le_bytes(), encrypted);
+        assert_eq!(amount, decode_amount(&encrypted, &shared_secret));
+    }
+
+    #[test]
+    fn decode_v1() {
+        let shared_secret = hash_to_scalar(b"shared secret");
+        let mask = hash_to_scalar(b"mask");
+        let amount = 5_000_000_000u64;
+
+        let mask_secret = hash_to_scalar(shared_secret.as_bytes());
+        let amount_secret = hash_to_scalar(mask_secret.as_bytes());
+        let encrypted_mask = (mask + mask_secret).to_bytes();
+        let encrypted_amount = (Scalar::from(amount) + amount_secret).to_bytes();
+
+        assert_eq!(
+            (mask, amount),
+            decode_amount_v1(&encrypted_mask, &encrypted_amount, &shared_secret)
+        );
+    }
+
+    #[test]
+    fn commitments_are_homomorphic() {
+        let a = hash_to_scalar(b"a");
+        let b = hash_to_scalar(b"b");
+        assert_eq!(commit(&(a + b), 30), commit(&a, 10) + commit(&b, 20));
+        assert_eq!(zero_commit(7), commit(&Scalar::one(), 7));
+    }
+}
@@ -0,0 +1,290 @@
+use curve25519_dalek::{
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar,
+};
+use tiny_keccak::keccak256;
+
+/// The Montgomery curve constant A of Curve25519.
+const MONTGOMERY_A: u64 = 486662;
+
+/// The field modulus p = 2^255 - 19 in little-endian limbs.
+const MODULUS: [u64; 4] = [
+    0xffff_ffff_ffff_ffed,
+    0xffff_ffff_ffff_ffff,
+    0xffff_ffff_ffff_ffff,
+    0x7fff_ffff_ffff_ffff,
+];
+
+/// The exponent (p - 5) / 8 = 2^252 - 3 in little-endian limbs.
+const EXPONENT_P_MINUS_5_DIV_8: [u64; 4] = [
+    0xffff_ffff_ffff_fffd,
+    0xffff_ffff_ffff_ffff,
+    0xffff_ffff_ffff_ffff,
+    0x0fff_ffff_ffff_ffff,
+];
+
+/// The exponent p - 2 = 2^255 - 21 in little-endian limbs.
+const EXPONENT_P_MINUS_2: [u64; 4] = [
+    0xffff_ffff_ffff_ffeb,
+    0xffff_ffff_ffff_ffff,
+    0xffff_ffff_ffff_ffff,
+    0x7fff_ffff_ffff_ffff,
+];
+
+/// Represents an element of the field of integers modulo 2^255 - 19
+///
+/// The limbs hold any value below 2^256 and are only fully reduced when serialized,
+/// which matches the way `ge_fromfe_frombytes_vartime` loads all 256 bits of its input.
+#[derive(Debug, Copy, Clone)]
+struct FieldElement([u64; 4]);
+
+impl FieldElement {
+    fn zero() -> Self {
+        FieldElement([0u64; 4])
+    }
+
+    fn one() -> Self {
+        FieldElement([1, 0, 0, 0])
+    }
+
+    fn from_u64(value: u64) -> Self {
+        FieldElement([value, 0, 0, 0])
+    }
+
+    /// Returns the field element of the full 256-bit little-endian integer.
+    fn from_bytes(bytes: &[u8; 32]) -> Self {
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[i * 8..(i + 1) * 8]);
+            *limb = u64::from_le_bytes(word);
+        }
+        FieldElement(limbs)
+    }
+
+    /// Returns the canonical little-endian encoding of the field element.
+    fn to_bytes(&self) -> [u8; 32] {
+        let reduced = self.reduce();
+        let mut bytes = [0u8; 32];
+        for (i, limb) in reduced.0.iter().enumerate() {
+            bytes[i * 8..(i + 1) * 8].copy_from_slice(&limb.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Returns the representative of the field element below the modulus.
+    fn reduce(&self) -> Self {
+        let mut limbs = self.0;
+        // A value below 2^256 exceeds the modulus by at most two multiples.
+        for _ in 0..2 {
+            let mut difference = [0u64; 4];
+            let mut borrow = 0u64;
+            for i in 0..4 {
+                let (value, borrow_a) = limbs[i].overflowing_sub(MODULUS[i]);
+                let (value, borrow_b) = value.overflowing_sub(borrow);
+                difference[i] = value;
+                borrow = (borrow_a || borrow_b) as u64;
+            }
+            if borrow == 0 {
+                limbs = difference;
+            }
+        }
+        FieldElement(limbs)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.reduce().0 == [0u64; 4]
+    }
+
+    /// Folds a carry out of 2^256 back into the limbs, using 2^256 = 38 (mod p).
+    fn fold_carry(mut limbs: [u64; 4], mut carry: u64) -> Self {
+        while carry != 0 {
+            let mut accumulator = (carry as u128) * 38;
+            for limb in limbs.iter_mut() {
+                accumulator += *limb as u128;
+                *limb = accumulator as u64;
+                accumulator >>= 64;
+            }
+            carry = accumulator as u64;
+        }
+        FieldElement(limbs)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        let mut limbs = [0u64; 4];
+        let mut accumulator = 0u128;
+        for i in 0..4 {
+            accumulator += self.0[i] as u128 + other.0[i] as u128;
+            limbs[i] = accumulator as u64;
+            accumulator >>= 64;
+        }
+        Self::fold_carry(limbs, accumulator as u64)
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        let mut limbs = [0u64; 4];
+        let mut borrow = false;
+        for i in 0..4 {
+            let (value, borrow_a) = self.0[i].overflowing_sub(other.0[i]);
+            let (value, borrow_b) = value.overflowing_sub(borrow as u64);
+            limbs[i] = value;
+            borrow = borrow_a || borrow_b;
+        }
+        // A wrapped difference is 2^256 too large, which is 38 too large modulo p.
+        while borrow {
+            let mut carry = 38u64;
+            borrow = false;
+            for limb in limbs.iter_mut() {
+                let (value, underflow) = limb.overflowing_sub(carry);
+                *limb = value;
+                carry = underflow as u64;
+            }
+            if carry != 0 {
+                borrow = true;
+            }
+        }
+        FieldElement(limbs)
+    }
+
+    fn neg(&self) -> Self {
+        Self::zero().sub(self)
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        let mut product = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let accumulator = (self.0[i] as u128) * (other.0[j] as u128) + product[i + j] as u128 + carry;
+                product[i + j] = accumulator as u64;
+                carry = accumulator >> 64;
+            }
+            product[i + 4] = carry as u64;
+        }
+
+        let mut limbs = [0u64; 4];
+        let mut accumulator = 0u128;
+        for i in 0..4 {
+            accumulator += product[i] as u128 + (product[i + 4] as u128) * 38;
+            limbs[i] = accumulator as u64;
+            accumulator >>= 64;
+        }
+        Self::fold_carry(limbs, accumulator as u64)
+    }
+
+    fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    /// Returns the field element raised to a little-endian 256-bit exponent.
+    fn pow(&self, exponent: &[u64; 4]) -> Self {
+        let mut result = Self::one();
+        for limb in exponent.iter().rev() {
+            for bit in (0..64).rev() {
+                result = result.square();
+                if (limb >> bit) & 1 == 1 {
+                    result = result.mul(self);
+                }
+            }
+        }
+        result
+    }
+
+    fn invert(&self) -> Self {
+        self.pow(&EXPONENT_P_MINUS_2)
+    }
+}
+
+/// Returns the Keccak-256 hash of the data reduced to a scalar (Monero `hash_to_scalar`).
+pub fn hash_to_scalar(data: &[u8]) -> Scalar {
+    Scalar::from_bytes_mod_order(keccak256(data))
+}
+
+/// Returns the point of the Keccak-256 hash of the data (Monero `hash_to_ec`).
+///
+/// The hash is mapped onto the curve with `ge_fromfe_frombytes_vartime` and multiplied
+/// by the cofactor, so the result always lies in the prime-order subgroup.
+pub fn hash_to_point(data: &[u8]) -> EdwardsPoint {
+    let a = FieldElement::from_u64(MONTGOMERY_A);
+
+    // u = H(data), v = 2u^2, w = 2u^2 + 1, x = w^2 - 2A^2u^2
+    let u = FieldElement::from_bytes(&keccak256(data));
+    let v = u.square().add(&u.square());
+    let w = v.add(&FieldElement::one());
+    let x = w.square().sub(&a.square().mul(&v));
+
+    // r = (w / x)^((p + 3) / 8), computed as w * x^3 * (w * x^7)^((p - 5) / 8)
+    let x3 = x.square().mul(&x);
+    let x7 = x3.square().mul(&x);
+    let r = w.mul(&x3).mul(&w.mul(&x7).pow(&EXPONENT_P_MINUS_5_DIV_8));
+    let x = r.square().mul(&x);
+
+    // The sign of the x-coordinate is set whenever w / x is not a square.
+    let sign = !w.sub(&x).is_zero() && !w.add(&x).is_zero();
+
+    let z = match sign {
+        true => a.neg(),
+        false => a.neg().mul(&v),
+    };
+    let y = z.sub(&w).mul(&z.add(&w).invert());
+
+    let mut bytes = y.to_bytes();
+    bytes[31] |= (sign as u8) << 7;
+
+    // The mapped y-coordinate always lies on the curve, so decompression cannot fail.
+    match CompressedEdwardsY(bytes).decompress() {
+        Some(point) => point.mul_by_cofactor(),
+        None => EdwardsPoint::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex;
+
+    // (data, point)
+    const HASH_TO_POINT: [(&str, &str); 5] = [
+        ("", "d6d7d783ab18e1be65586adb7902a4175b737ef0b902875e1d1d5c5cf0478c0b"),
+        (
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "2d2c4d74df05ba930eaab01825af274eaabcd217bf99dfd54fdf2efe574033f3",
+        ),
+        (
+            "5866666666666666666666666666666666666666666666666666666666666666",
+            "d6329b5b1f7c0805b5c345f4957554002a2f557845f64d7645dae0e051a6498a",
+        ),
+        (
+            "7761677975",
+            "ebfa9cccafd89bbab596352e83705e4d09f4a0f579c616517aa819454de45704",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            "6c0ac4e62d277e1d7371cf19fc0cd5228ecc593520c16eea45a0c51218c05559",
+        ),
+    ];
+
+    #[test]
+    fn hash_to_point() {
+        HASH_TO_POINT.iter().for_each(|(data, expected)| {
+            let point = super::hash_to_point(&hex::decode(data).unwrap());
+            assert_eq!(*expected, hex::encode(point.compress().as_bytes()));
+            assert!(point.is_torsion_free());
+        });
+    }
+
+    #[test]
+    fn field_arithmetic() {
+        let two = FieldElement::from_u64(2);
+        let p_minus_one = FieldElement::zero().sub(&FieldElement::one());
+        assert!(p_minus_one.add(&FieldElement::one()).is_zero());
+        assert!(two.mul(&two.invert()).sub(&FieldElement::one()).is_zero());
+        assert!(p_minus_one.square().sub(&FieldElement::one()).is_zero());
+
+        let mut bytes = [0xffu8; 32];
+        bytes[0] = 0xec;
+        bytes[31] = 0x7f;
+        assert_eq!(bytes, p_minus_one.to_bytes());
+        assert_eq!(FieldElement::from_bytes(&[0xffu8; 32]).to_bytes()[0], 37);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::ringct::{read_scalar, RingCtError};
+
+use curve25519_dalek::scalar::Scalar;
+
+/// Represents an MLSAG ring signature for one input of a transaction of RingCT type 4, which preceded CLSAG.
+/// These signatures are parsed and serialized to read historical transactions, but are neither created nor verified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mlsag {
+    /// The responses of the ring members, for their one-time key and their amount commitment
+    pub ss: Vec<[Scalar; 2]>,
+    /// The challenge of the first ring member
+    pub cc: Scalar,
+}
+
+impl Mlsag {
+    /// Appends the serialized signature, which is the responses followed by `cc`.
+    pub fn write(&self, buffer: &mut Vec<u8>) {
+        self.ss.iter().flatten().for_each(|s| buffer.extend(s.as_bytes()));
+        buffer.extend(self.cc.as_bytes());
+    }
+
+    /// Returns a signature given the ring size and a serialized signature.
+    pub fn read(ring_size: usize, bytes: &[u8]) -> Result<Self, RingCtError> {
+        if bytes.len() != 32 * (2 * ring_size + 1) {
+            return Err(RingCtError::InvalidByteLength(bytes.len()));
+        }
+
+        let ss = bytes[..64 * ring_size]
+            .chunks(64)
+            .map(|row| Ok([read_scalar(&row[..32])?, read_scalar(&row[32..])?]))
+            .collect::<Result<Vec<[Scalar; 2]>, RingCtError>>()?;
+        let cc = read_scalar(&bytes[64 * ring_size..])?;
+
+        Ok(Self { ss, cc })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ringct::random_scalar;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const RING_SIZE: usize = 11;
+
+    #[test]
+    fn write_and_read() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let signature = Mlsag {
+            ss: (0..RING_SIZE)
+                .map(|_| [random_scalar(rng), random_scalar(rng)])
+                .collect(),
+            cc: random_scalar(rng),
+        };
+
+        let mut buffer = vec![];
+        signature.write(&mut buffer);
+        assert_eq!(32 * (2 * RING_SIZE + 1), buffer.len());
+        assert_eq!(signature, Mlsag::read(RING_SIZE, &buffer).unwrap());
+    }
+
+    mod invalid {
+        use super::*;
+
+        #[test]
+        fn read_invalid_length() {
+            let buffer = [0u8; 32 * (2 * RING_SIZE + 1)];
+            assert!(Mlsag::read(RING_SIZE, &buffer[1..]).is_err());
+            assert!(Mlsag::read(RING_SIZE + 1, &buffer).is_err());
+        }
+
+        #[test]
+        fn read_noncanonical_scalar() {
+            let buffer = [0xffu8; 32 * (2 * RING_SIZE + 1)];
+            assert!(Mlsag::read(RING_SIZE, &buffer).is_err());
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//!
+//! Ring Confidential Transactions
+//!
+//! This module contains the RingCT primitives of Monero transactions: hashing to the curve,
+//! Pedersen commitments with encrypted amounts, CLSAG ring signatures and Bulletproofs+ range proofs,
+//! along with the original CryptoNote signatures that wallets use to prove key images.
+//! The MLSAG signatures and Bulletproofs of older transactions are parsed, but not verified.
+//!
+
+use wagyu_model::{AddressError, TransactionError};
+
+use curve25519_dalek::{edwards::CompressedEdwardsY, edwards::EdwardsPoint, scalar::Scalar};
+use rand::Rng;
+
+pub mod bulletproof;
+pub use self::bulletproof::*;
+
+pub mod bulletproofs_plus;
+pub use self::bulletproofs_plus::*;
+
+pub mod clsag;
+pub use self::clsag::*;
+
+pub mod commitment;
+pub use self::commitment::*;
+
+pub mod hash_to_point;
+pub use self::hash_to_point::*;
+
+pub mod mlsag;
+pub use self::mlsag::*;
+
+pub mod signature;
+pub use self::signature::*;
+
+#[derive(Debug, Fail, PartialEq, Eq)]
+pub enum RingCtError {
+    #[fail(display = "invalid byte length: {}", _0)]
+    InvalidByteLength(usize),
+
+    #[fail(display = "commitment does not open to the pseudo output")]
+    InvalidCommitment,
+
+    #[fail(display = "invalid key image {:?}", _0)]
+    InvalidKeyImage([u8; 32]),
+
+    #[fail(display = "invalid number of outputs for a range proof: {}", _0)]
+    InvalidOutputCount(usize),
+
+    #[fail(display = "invalid point {:?}", _0)]
+    InvalidPoint([u8; 32]),
+
+    #[fail(display = "invalid range proof")]
+    InvalidRangeProof,
+
+    #[fail(display = "invalid ring index {} for a ring of size {}", _0, _1)]
+    InvalidRingIndex(usize, usize),
+
+    #[fail(display = "invalid ring size: {{ expected: {:?}, found: {:?} }}", _1, _0)]
+    InvalidRingSize(usize, usize),
+
+    #[fail(display = "invalid scalar {:?}", _0)]
+    InvalidScalar([u8; 32]),
+
+    #[fail(display = "secret key does not match the ring member")]
+    InvalidSecretKey,
+
+    #[fail(display = "invalid ring signature")]
+    InvalidSignature,
+
+    #[fail(display = "invalid variable size integer")]
+    InvalidVariableSizeInteger,
+}
+
+impl From<RingCtError> for TransactionError {
+    fn from(error: RingCtError) -> Self {
+        TransactionError::Crate("RingCt", format!("{:?}", error))
+    }
+}
+
+impl From<RingCtError> for AddressError {
+    fn from(error: RingCtError) -> Self {
+        AddressError::Crate("RingCt", format!("{:?}", error))
+    }
+}
+
+/// Returns a uniformly random scalar.
+pub fn random_scalar<R: Rng>(rng: &mut R) -> Scalar {
+    let mut bytes = [0u8; 64];
+    rng.fill(&mut bytes[..]);
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+/// Returns the canonical scalar encoded in the first 32 bytes.
+pub fn read_scalar(bytes: &[u8]) -> Result<Scalar, RingCtError> {
+    if bytes.len() < 32 {
+        return Err(RingCtError::InvalidByteLength(bytes.len()));
+    }
+    let mut scalar = [0u8; 32];
+    scalar.copy_from_slice(&bytes[0..32]);
+    match Scalar::from_canonical_bytes(scalar) {
+        Some(scalar) => Ok(scalar),
+        None => Err(RingCtError::InvalidScalar(scalar)),
+    }
+}
+
+/// Returns the point encoded in the first 32 bytes.
+pub fn read_point(bytes: &[u8]) -> Result<EdwardsPoint, RingCtError> {
+    if bytes.len() < 32 {
+        return Err(RingCtError::InvalidByteLength(bytes.len()));
+    }
+    let mut point = [0u8; 32];
+    point.copy_from_slice(&bytes[0..32]);
+    match CompressedEdwardsY(point).decompress() {
+        Some(decompressed) => Ok(decompressed),
+        None => Err(RingCtError::InvalidPoint(point)),
+    }
+}
+
+/// Returns the next `length` bytes at the offset and advances the offset past them.
+pub fn take<'a>(bytes: &'a [u8], offset: &mut usize, length: usize) -> Result<&'a [u8], RingCtError> {
+    match bytes.get(*offset..*offset + length) {
+        Some(slice) => {
+            *offset += length;
+            Ok(slice)
+        }
+        None => Err(RingCtError::InvalidByteLength(bytes.len())),
+    }
+}
+
+/// Appends the value as a variable-length integer of 7-bit groups, least significant first.
+pub fn write_varint(buffer: &mut Vec<u8>, value: u64) {
+    let mut value = value;
+    while value >= 0x80 {
+        buffer.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+/// Returns a variable-length integer and the number of bytes it occupies.
+pub fn read_varint(bytes: &[u8]) -> Result<(u64, usize), RingCtError> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(10) {
+        let bits = (byte & 0x7f) as u64;
+        // Reject encodings that overflow 64 bits or are not minimal.
+        if (i == 9 && bits > 1) || (i > 0 && *byte == 0) {
+            return Err(RingCtError::InvalidVariableSizeInteger);
+        }
+        value |= bits << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(RingCtError::InvalidVariableSizeInteger)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (value, encoding)
+    const VARINTS: [(u64, &str); 6] = [
+        (0, "00"),
+        (127, "7f"),
+        (128, "8001"),
+        (300, "ac02"),
+        (7611174, "a6c6d003"),
+        (u64::max_value(), "ffffffffffffffffff01"),
+    ];
+
+    #[test]
+    fn varint() {
+        VARINTS.iter().for_each(|(value, encoding)| {
+            let mut buffer = vec![];
+            write_varint(&mut buffer, *value);
+            assert_eq!(*encoding, hex::encode(&buffer));
+            assert_eq!((*value, buffer.len()), read_varint(&buffer).unwrap());
+        });
+    }
+
+    mod invalid {
+        use super::*;
+
+        const INVALID_VARINTS: [&str; 4] = ["", "80", "8000", "ffffffffffffffffff02"];
+
+        #[test]
+        fn read_invalid_varint() {
+            INVALID_VARINTS.iter().for_each(|encoding| {
+                assert!(read_varint(&hex::decode(encoding).unwrap()).is_err());
+            });
+        }
+    }
+}
@@ -0,0 +1,413 @@
+use crate::ringct::{hash_to_point::*, random_scalar, read_scalar, RingCtError};
+
+use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, edwards::EdwardsPoint, scalar::Scalar, traits::IsIdentity};
+use rand::Rng;
+use tiny_keccak::keccak256;
+
+/// Represents a CryptoNote signature, which is a challenge and a response
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Signature {
+    /// The challenge
+    pub c: Scalar,
+    /// The response
+    pub r: Scalar,
+}
+
+impl Signature {
+    /// Returns a signature of the prefix hash by the secret key (Monero `generate_signature`).
+    pub fn sign<R: Rng>(prefix_hash: &[u8; 32], secret_key: &Scalar, rng: &mut R) -> Self {
+        let public_key = secret_key * &ED25519_BASEPOINT_TABLE;
+        let k = random_scalar(rng);
+        let c = Self::challenge(prefix_hash, &public_key, &(&k * &ED25519_BASEPOINT_TABLE));
+        Self {
+            c,
+            r: k - c * secret_key,
+        }
+    }
+
+    /// Verifies the signature of the prefix hash by the public key (Monero `check_signature`).
+    pub fn verify(&self, prefix_hash: &[u8; 32], public_key: &EdwardsPoint) -> Result<(), RingCtError> {
+        // comm = c*P + r*G
+        let commitment = self.c * public_key + &self.r * &ED25519_BASEPOINT_TABLE;
+        if self.c == Scalar::zero() || commitment.is_identity() {
+            return Err(RingCtError::InvalidSignature);
+        }
+        match Self::challenge(prefix_hash, public_key, &commitment) == self.c {
+            true => Ok(()),
+            false => Err(RingCtError::InvalidSignature),
+        }
+    }
+
+    /// Returns the challenge `H_s(prefix_hash || P || comm)`.
+    fn challenge(prefix_hash: &[u8; 32], public_key: &EdwardsPoint, commitment: &EdwardsPoint) -> Scalar {
+        let mut data = prefix_hash.to_vec();
+        data.extend(public_key.compress().as_bytes());
+        data.extend(commitment.compress().as_bytes());
+        hash_to_scalar(&data)
+    }
+
+    /// Returns a proof that the secret key of `R = r*G`, or `R = r*B` given a subaddress spend key `B`,
+    /// is the secret of the shared secret `D = r*A`, signing the prefix hash (Monero `generate_tx_proof`).
+    pub fn sign_tx_proof<R: Rng>(
+        prefix_hash: &[u8; 32],
+        keys: (&EdwardsPoint, &EdwardsPoint, Option<&EdwardsPoint>),
+        shared_secret: &EdwardsPoint,
+        secret_key: &Scalar,
+        rng: &mut R,
+    ) -> Result<Self, RingCtError> {
+        let (public_key, other_key, spend_key) = keys;
+        let base = |scalar: &Scalar| match spend_key {
+            Some(spend_key) => scalar * spend_key,
+            None => scalar * &ED25519_BASEPOINT_TABLE,
+        };
+        if base(secret_key) != *public_key || secret_key * other_key != *shared_secret {
+            return Err(RingCtError::InvalidSecretKey);
+        }
+
+        // X = k*G (or k*B), Y = k*A
+        let k = random_scalar(rng);
+        let commitments = (base(&k), k * other_key);
+        let c = Self::tx_proof_challenge(prefix_hash, keys, shared_secret, commitments, 2);
+        Ok(Self {
+            c,
+            r: k - c * secret_key,
+        })
+    }
+
+    /// Verifies the proof of a shared secret of the given version, 1 or 2 (Monero `check_tx_proof`).
+    pub fn verify_tx_proof(
+        &self,
+        prefix_hash: &[u8; 32],
+        keys: (&EdwardsPoint, &EdwardsPoint, Option<&EdwardsPoint>),
+        shared_secret: &EdwardsPoint,
+        version: u8,
+    ) -> Result<(), RingCtError> {
+        let (public_key, other_key, spend_key) = keys;
+
+        // X = c*R + r*G (or r*B), Y = c*D + r*A
+        let x = self.c * public_key
+            + match spend_key {
+                Some(spend_key) => self.r * spend_key,
+                None => &self.r * &ED25519_BASEPOINT_TABLE,
+            };
+        let y = self.c * shared_secret + self.r * other_key;
+
+        match version {
+            1 | 2 if Self::tx_proof_challenge(prefix_hash, keys, shared_secret, (x, y), version) == self.c => Ok(()),
+            _ => Err(RingCtError::InvalidSignature),
+        }
+    }
+
+    /// Returns the challenge `H_s(prefix_hash || D || X || Y)` of a version 1 proof, followed by
+    /// `H("TXPROOF_V2") || R || A || B` in version 2, where `B` is zero for a primary address.
+    fn tx_proof_challenge(
+        prefix_hash: &[u8; 32],
+        keys: (&EdwardsPoint, &EdwardsPoint, Option<&EdwardsPoint>),
+        shared_secret: &EdwardsPoint,
+        commitments: (EdwardsPoint, EdwardsPoint),
+        version: u8,
+    ) -> Scalar {
+        let (public_key, other_key, spend_key) = keys;
+        let mut data = prefix_hash.to_vec();
+        data.extend(shared_secret.compress().as_bytes());
+        data.extend(commitments.0.compress().as_bytes());
+        data.extend(commitments.1.compress().as_bytes());
+        if version > 1 {
+            data.extend(&keccak256(b"TXPROOF_V2"));
+            data.extend(public_key.compress().as_bytes());
+            data.extend(other_key.compress().as_bytes());
+            match spend_key {
+                Some(spend_key) => data.extend(spend_key.compress().as_bytes()),
+                None => data.extend(&[0u8; 32]),
+            };
+        }
+        hash_to_scalar(&data)
+    }
+
+    /// Returns the serialized signature, which is the challenge followed by the response.
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(self.c.as_bytes());
+        bytes[32..].copy_from_slice(self.r.as_bytes());
+        bytes
+    }
+
+    /// Returns a signature given its serialization.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RingCtError> {
+        if bytes.len() != 64 {
+            return Err(RingCtError::InvalidByteLength(bytes.len()));
+        }
+        Ok(Self {
+            c: read_scalar(&bytes[..32])?,
+            r: read_scalar(&bytes[32..])?,
+        })
+    }
+}
+
+/// Represents a CryptoNote ring signature, which proves knowledge of the secret key of one
+/// member of a ring of public keys and links it to a key image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingSignature {
+    /// The challenge and response of each ring member
+    pub signatures: Vec<Signature>,
+}
+
+impl RingSignature {
+    /// Returns a ring signature of the prefix hash and the key image of the signer at `index` of the ring
+    /// (Monero `generate_ring_signature`).
+    pub fn sign<R: Rng>(
+        prefix_hash: &[u8; 32],
+        ring: &[EdwardsPoint],
+        index: usize,
+        secret_key: &Scalar,
+        rng: &mut R,
+    ) -> Result<(Self, EdwardsPoint), RingCtError> {
+        if index >= ring.len() {
+            return Err(RingCtError::InvalidRingIndex(index, ring.len()));
+        }
+        if secret_key * &ED25519_BASEPOINT_TABLE != ring[index] {
+            return Err(RingCtError::InvalidSecretKey);
+        }
+
+        let key_image = secret_key * hash_to_point(ring[index].compress().as_bytes());
+        let k = random_scalar(rng);
+
+        let mut sum = Scalar::zero();
+        let mut data = prefix_hash.to_vec();
+        let mut signatures = vec![];
+        for (i, key) in ring.iter().enumerate() {
+            let (a, b, signature) = match i == index {
+                // a = k*G, b = k*Hp(P)
+                true => (
+                    &k * &ED25519_BASEPOINT_TABLE,
+                    k * hash_to_point(key.compress().as_bytes()),
+                    Signature {
+                        c: Scalar::zero(),
+                        r: Scalar::zero(),
+                    },
+                ),
+                false => {
+                    let signature = Signature {
+                        c: random_scalar(rng),
+                        r: random_scalar(rng),
+                    };
+                    sum += signature.c;
+                    let (a, b) = Self::round(key, &key_image, &signature);
+                    (a, b, signature)
+                }
+            };
+            data.extend(a.compress().as_bytes());
+            data.extend(b.compress().as_bytes());
+            signatures.push(signature);
+        }
+
+        // The challenges sum to H_s(prefix_hash || a_0 || b_0 || ... ), which closes the ring at the signer.
+        let c = hash_to_scalar(&data) - sum;
+        signatures[index] = Signature {
+            c,
+            r: k - c * secret_key,
+        };
+
+        Ok((Self { signatures }, key_image))
+    }
+
+    /// Verifies the ring signature of the prefix hash for the ring and key image
+    /// (Monero `check_ring_signature`).
+    pub fn verify(
+        &self,
+        prefix_hash: &[u8; 32],
+        ring: &[EdwardsPoint],
+        key_image: &EdwardsPoint,
+    ) -> Result<(), RingCtError> {
+        if ring.is_empty() || self.signatures.len() != ring.len() {
+            return Err(RingCtError::InvalidRingSize(self.signatures.len(), ring.len()));
+        }
+        if key_image.is_identity() || !key_image.is_torsion_free() {
+            return Err(RingCtError::InvalidKeyImage(key_image.compress().to_bytes()));
+        }
+
+        let mut sum = Scalar::zero();
+        let mut data = prefix_hash.to_vec();
+        for (key, signature) in ring.iter().zip(self.signatures.iter()) {
+            let (a, b) = Self::round(key, key_image, signature);
+            data.extend(a.compress().as_bytes());
+            data.extend(b.compress().as_bytes());
+            sum += signature.c;
+        }
+
+        match hash_to_scalar(&data) == sum {
+            true => Ok(()),
+            false => Err(RingCtError::InvalidSignature),
+        }
+    }
+
+    /// Returns the round points `a = c*P + r*G` and `b = r*Hp(P) + c*I`.
+    fn round(key: &EdwardsPoint, key_image: &EdwardsPoint, signature: &Signature) -> (EdwardsPoint, EdwardsPoint) {
+        let a = signature.c * key + &signature.r * &ED25519_BASEPOINT_TABLE;
+        let b = signature.r * hash_to_point(key.compress().as_bytes()) + signature.c * key_image;
+        (a, b)
+    }
+
+    /// Appends the serialized signature, which is the challenge and response of each ring member.
+    pub fn write(&self, buffer: &mut Vec<u8>) {
+        self.signatures
+            .iter()
+            .for_each(|signature| buffer.extend(signature.to_bytes().iter()));
+    }
+
+    /// Returns a signature given the ring size and a serialized signature.
+    pub fn read(ring_size: usize, bytes: &[u8]) -> Result<Self, RingCtError> {
+        if bytes.len() != 64 * ring_size {
+            return Err(RingCtError::InvalidByteLength(bytes.len()));
+        }
+        let signatures = bytes
+            .chunks(64)
+            .map(Signature::from_bytes)
+            .collect::<Result<Vec<Signature>, RingCtError>>()?;
+        Ok(Self { signatures })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const RING_SIZE: usize = 11;
+
+    #[test]
+    fn sign_and_verify() {
+        let rng = &mut StdRng::from_seed([7u8; 32]);
+        let prefix_hash = [42u8; 32];
+        let secret_key = random_scalar(rng);
+        let public_key = &secret_key * &ED25519_BASEPOINT_TABLE;
+
+        let signature = Signature::sign(&prefix_hash, &secret_key, rng);
+        assert!(signature.verify(&prefix_hash, &public_key).is_ok());
+        assert_eq!(signature, Signature::from_bytes(&signature.to_bytes()).unwrap());
+    }
+
+    #[test]
+    fn ring_sign_and_verify() {
+        let rng = &mut StdRng::from_seed([7u8; 32]);
+        let prefix_hash = [42u8; 32];
+        let secret_key = random_scalar(rng);
+
+        for index in [0, 5, RING_SIZE - 1].iter() {
+            let ring: Vec<EdwardsPoint> = (0..RING_SIZE)
+                .map(|i| match i == *index {
+                    true => &secret_key * &ED25519_BASEPOINT_TABLE,
+                    false => &random_scalar(rng) * &ED25519_BASEPOINT_TABLE,
+                })
+                .collect();
+
+            let (signature, key_image) = RingSignature::sign(&prefix_hash, &ring, *index, &secret_key, rng).unwrap();
+            assert_eq!(crate::ringct::key_image(&secret_key), key_image);
+            assert!(signature.verify(&prefix_hash, &ring, &key_image).is_ok());
+
+            let mut buffer = vec![];
+            signature.write(&mut buffer);
+            assert_eq!(64 * RING_SIZE, buffer.len());
+            assert_eq!(signature, RingSignature::read(RING_SIZE, &buffer).unwrap());
+        }
+    }
+
+    #[test]
+    fn tx_proof_sign_and_verify() {
+        let rng = &mut StdRng::from_seed([7u8; 32]);
+        let prefix_hash = [42u8; 32];
+        let secret_key = random_scalar(rng);
+        let other_key = &random_scalar(rng) * &ED25519_BASEPOINT_TABLE;
+        let spend_key = &random_scalar(rng) * &ED25519_BASEPOINT_TABLE;
+        let shared_secret = secret_key * other_key;
+
+        // A primary address, and a subaddress with its spend key as the base
+        let public_key = &secret_key * &ED25519_BASEPOINT_TABLE;
+        let keys = (&public_key, &other_key, None);
+        let signature = Signature::sign_tx_proof(&prefix_hash, keys, &shared_secret, &secret_key, rng).unwrap();
+        assert!(signature.verify_tx_proof(&prefix_hash, keys, &shared_secret, 2).is_ok());
+
+        let public_key = secret_key * spend_key;
+        let keys = (&public_key, &other_key, Some(&spend_key));
+        let signature = Signature::sign_tx_proof(&prefix_hash, keys, &shared_secret, &secret_key, rng).unwrap();
+        assert!(signature.verify_tx_proof(&prefix_hash, keys, &shared_secret, 2).is_ok());
+    }
+
+    mod invalid {
+        use super::*;
+
+        #[test]
+        fn verify_invalid_signature() {
+            let rng = &mut StdRng::from_seed([7u8; 32]);
+            let prefix_hash = [42u8; 32];
+            let secret_key = random_scalar(rng);
+            let public_key = &secret_key * &ED25519_BASEPOINT_TABLE;
+            let other_key = &random_scalar(rng) * &ED25519_BASEPOINT_TABLE;
+
+            let signature = Signature::sign(&prefix_hash, &secret_key, rng);
+            assert!(signature.verify(&[43u8; 32], &public_key).is_err());
+            assert!(signature.verify(&prefix_hash, &other_key).is_err());
+            assert!(Signature::from_bytes(&[0xffu8; 64]).is_err());
+        }
+
+        #[test]
+        fn verify_invalid_tx_proof() {
+            let rng = &mut StdRng::from_seed([7u8; 32]);
+            let prefix_hash = [42u8; 32];
+            let secret_key = random_scalar(rng);
+            let public_key = &secret_key * &ED25519_BASEPOINT_TABLE;
+            let other_key = &random_scalar(rng) * &ED25519_BASEPOINT_TABLE;
+            let shared_secret = secret_key * other_key;
+            let keys = (&public_key, &other_key, None);
+
+            let signature = Signature::sign_tx_proof(&prefix_hash, keys, &shared_secret, &secret_key, rng).unwrap();
+            assert!(signature.verify_tx_proof(&[43u8; 32], keys, &shared_secret, 2).is_err());
+            assert!(signature.verify_tx_proof(&prefix_hash, keys, &other_key, 2).is_err());
+            assert!(signature
+                .verify_tx_proof(&prefix_hash, keys, &shared_secret, 1)
+                .is_err());
+            assert!(signature
+                .verify_tx_proof(&prefix_hash, keys, &shared_secret, 3)
+                .is_err());
+            assert!(signature
+                .verify_tx_proof(
+                    &prefix_hash,
+                    (&public_key, &other_key, Some(&other_key)),
+                    &shared_secret,
+                    2
+                )
+                .is_err());
+
+            assert_eq!(
+                Err(RingCtError::InvalidSecretKey),
+                Signature::sign_tx_proof(&prefix_hash, keys, &other_key, &secret_key, rng)
+            );
+        }
+
+        #[test]
+        fn verify_invalid_ring_signature() {
+            let rng = &mut StdRng::from_seed([7u8; 32]);
+            let prefix_hash = [42u8; 32];
+            let secret_key = random_scalar(rng);
+            let ring = vec![
+                &random_scalar(rng) * &ED25519_BASEPOINT_TABLE,
+                &secret_key * &ED25519_BASEPOINT_TABLE,
+            ];
+
+            let (signature, key_image) = RingSignature::sign(&prefix_hash, &ring, 1, &secret_key, rng).unwrap();
+            let other_image = crate::ringct::key_image(&random_scalar(rng));
+            assert!(signature.verify(&[43u8; 32], &ring, &key_image).is_err());
+            assert!(signature.verify(&prefix_hash, &ring, &other_image).is_err());
+            assert!(signature.verify(&prefix_hash, &ring[..1], &key_image).is_err());
+
+            assert_eq!(
+                Err(RingCtError::InvalidSecretKey),
+                RingSignature::sign(&prefix_hash, &ring, 0, &secret_key, rng)
+            );
+            assert_eq!(
+                Err(RingCtError::InvalidRingIndex(2, 2)),
+                RingSignature::sign(&prefix_hash, &ring, 2, &secret_key, rng)
+            );
+        }
+    }
+}
@@ -0,0 +1,440 @@
+use crate::network::MoneroNetwork;
+use crate::one_time_key::OneTimeKey;
+use crate::private_key::MoneroPrivateKey;
+use crate::ringct::*;
+use crate::subaddress::MoneroSubaddressTable;
+use crate::transaction::{MoneroTransaction, MoneroTransactionPrefix, RctSignatures, RCT_TYPE_NULL};
+use wagyu_model::TransactionError;
+
+use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, scalar::Scalar};
+
+/// Represents a transaction output received by a scanned wallet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroOwnedOutput {
+    /// The index of the output in its transaction
+    pub index: u64,
+    /// The account and subaddress indices of the receiving address
+    pub subaddress: (u32, u32),
+    /// The amount in piconero
+    pub amount: u64,
+    /// The one time public key of the output
+    pub public_key: [u8; 32],
+    /// The transaction public key (or the additional public key) of the output
+    pub tx_pub_key: [u8; 32],
+    /// The mask of the amount commitment, which is one for outputs with a clear amount
+    pub mask: [u8; 32],
+}
+
+/// Represents a scanner of the outputs received by a wallet and its subaddresses
+#[derive(Debug, Clone)]
+pub struct MoneroScanner<N: MoneroNetwork> {
+    /// The subaddresses of the wallet
+    subaddresses: MoneroSubaddressTable<N>,
+}
+
+impl<N: MoneroNetwork> MoneroScanner<N> {
+    /// Returns a scanner of the primary address and the given subaddresses of a private key.
+    pub fn new(private_key: &MoneroPrivateKey<N>, subaddresses: &[(u32, u32)]) -> Result<Self, TransactionError> {
+        let private_spend_key = Scalar::from_bits(private_key.to_private_spend_key());
+        let public_spend_key = (&private_spend_key * &ED25519_BASEPOINT_TABLE).compress().to_bytes();
+        Self::from_view_key(&private_key.to_private_view_key(), &public_spend_key, subaddresses)
+    }
+
+    /// Returns a scanner of the primary address and the given subaddresses
+    /// given the private view key and the public spend key of a wallet.
+    pub fn from_view_key(
+        private_view_key: &[u8; 32],
+        public_spend_key: &[u8; 32],
+        subaddresses: &[(u32, u32)],
+    ) -> Result<Self, TransactionError> {
+        let mut table = MoneroSubaddressTable::new(private_view_key, public_spend_key, 0, 0)?;
+        for (major, minor) in subaddresses {
+            table.insert(*major, *minor)?;
+        }
+        Ok(Self::from_subaddress_table(table))
+    }
+
+    /// Returns a scanner of the subaddresses in a subaddress table.
+    pub fn from_subaddress_table(subaddresses: MoneroSubaddressTable<N>) -> Self {
+        Self { subaddresses }
+    }
+
+    /// Returns the subaddress table of the scanner.
+    pub fn subaddress_table(&self) -> &MoneroSubaddressTable<N> {
+        &self.subaddresses
+    }
+
+    /// Adds the first `indices` subaddresses of the first `accounts` accounts to the scanned subaddresses,
+    /// such as to look ahead of the subaddresses that received outputs.
+    pub fn extend(&mut self, accounts: u32, indices: u32) -> Result<(), TransactionError> {
+        Ok(self.subaddresses.extend(accounts, indices)?)
+    }
+
+    /// Returns the outputs of the transaction received by the wallet.
+    pub fn scan(&self, transaction: &MoneroTransaction<N>) -> Result<Vec<MoneroOwnedOutput>, TransactionError> {
+        self.scan_outputs(transaction.prefix(), transaction.rct_signatures())
+    }
+
+    /// Returns the payment id of a transaction in hex, decrypting the payment id of an integrated address
+    /// of the wallet, or returns `None`. It matches `MoneroAddress::to_payment_id` of the integrated address
+    /// that was paid, and is meaningless for transactions that pay no outputs to the wallet.
+    pub fn to_payment_id(&self, prefix: &MoneroTransactionPrefix) -> Result<Option<String>, TransactionError> {
+        let payment_id = prefix.to_extra().to_payment_id(&self.subaddresses.private_view_key())?;
+        Ok(payment_id.map(hex::encode))
+    }
+
+    /// Returns the outputs received by the wallet given a transaction prefix and its RingCT signatures.
+    pub fn scan_outputs(
+        &self,
+        prefix: &MoneroTransactionPrefix,
+        signatures: &RctSignatures,
+    ) -> Result<Vec<MoneroOwnedOutput>, TransactionError> {
+        // Keys that are not valid points cannot derive an output and are skipped, as in Monero.
+        let derive = |tx_pub_key: &[u8; 32]| {
+            let mut derivation = vec![];
+            OneTimeKey::<N>::generate_key_derivation(tx_pub_key, &self.subaddresses.private_view_key(), &mut derivation)
+                .ok()
+                .map(|_| (*tx_pub_key, derivation))
+        };
+        let (public_keys, additional_public_keys) = prefix.to_transaction_public_keys();
+        let derivations: Vec<([u8; 32], Vec<u8>)> = public_keys.iter().filter_map(derive).collect();
+        let additional_derivations: Vec<Option<([u8; 32], Vec<u8>)>> =
+            additional_public_keys.iter().map(derive).collect();
+
+        let mut outputs = vec![];
+        for (i, output) in prefix.outputs.iter().enumerate() {
+            let index = i as u64;
+            let output_key = match read_point(&output.key) {
+                Ok(point) => point,
+                Err(_) => continue,
+            };
+
+            let additional_derivation = additional_derivations.get(i).and_then(Option::as_ref);
+            for (tx_pub_key, derivation) in derivations.iter().chain(additional_derivation) {
+                // The view tag rejects most outputs of other wallets with a single hash.
+                if let Some(view_tag) = output.view_tag {
+                    if OneTimeKey::<N>::derive_view_tag(derivation, index) != view_tag {
+                        continue;
+                    }
+                }
+
+                // D = P - H_s(8aR || i) * G is the public spend key of the receiving (sub)address.
+                let shared_secret = OneTimeKey::<N>::derivation_to_scalar(derivation, index);
+                let public_spend_key = output_key - &shared_secret * &ED25519_BASEPOINT_TABLE;
+                let subaddress = match self.subaddresses.get(public_spend_key.compress().as_bytes()) {
+                    Some(subaddress) => subaddress,
+                    None => continue,
+                };
+
+                let (amount, mask) = match signatures.rct_type {
+                    RCT_TYPE_NULL => (output.amount, Scalar::one()),
+                    _ => {
+                        let encrypted_amount = signatures.ecdh_info.get(i).ok_or(TransactionError::Message(
+                            format!("missing encrypted amount of output {}", i),
+                        ))?;
+                        let amount = decode_amount(encrypted_amount, &shared_secret);
+                        let mask = commitment_mask(&shared_secret);
+                        if signatures.out_pk.get(i) != Some(&commit(&mask, amount)) {
+                            return Err(TransactionError::Message(format!(
+                                "the commitment of output {} does not open to its decoded amount",
+                                i
+                            )));
+                        }
+                        (amount, mask)
+                    }
+                };
+
+                outputs.push(MoneroOwnedOutput {
+                    index,
+                    subaddress,
+                    amount,
+                    public_key: output.key,
+                    tx_pub_key: *tx_pub_key,
+                    mask: mask.to_bytes(),
+                });
+                break;
+            }
+        }
+        Ok(outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::MoneroAddress;
+    use crate::extra::MoneroExtraNonce;
+    use crate::format::MoneroFormat;
+    use crate::transaction::*;
+    use crate::Stagenet;
+    use wagyu_model::{PrivateKey, Transaction};
+
+    use std::str::FromStr;
+
+    type N = Stagenet;
+
+    const PRIVATE_SPEND_KEY: &str = "0cf0c38429e00fa4abecb98296cb15dec209c0a7e6ea34ed86d32429498e4700";
+    const PRIVATE_VIEW_KEY: &str = "55c5b2fa94b2c5ee387eb9dd71b197a6358a5a90ed5eb5acdfa088583125a40a";
+    const OTHER_ADDRESS: &str =
+        "59McWTPGc745SRWrSMoh8oTjoXoQq6sPUgKZ66dQWXuKFQ2q19h9gvhJNZcFTizcnT12r63NFgHiGd6gBCjabzmzHAMoyD6";
+
+    // (output index, amount, one time public key, commitment, encrypted amount, transaction public key)
+    const OUTPUTS: [(u64, u64, &str, &str, &str, &str); 1] = [(
+        0,
+        9996522470000,
+        "dd397dea109a2b94056fd5e236eada6f57c60ab4de5563469a4245e7394f8f65",
+        "c32e27e0f773d08efc3a651cbabbbd563a60fb230ccd3ebf5320ae825ab05e86",
+        "da36753b8b5957c4",
+        "0ee98e87f68a2526393ed487495f2d3dba6e90e860537d0aae1d5fad5d492f21",
+    )];
+
+    fn private_key() -> MoneroPrivateKey<N> {
+        MoneroPrivateKey::from_private_keys(PRIVATE_SPEND_KEY, PRIVATE_VIEW_KEY, &MoneroFormat::Standard).unwrap()
+    }
+
+    fn to_bytes(hex: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hex::decode(hex).unwrap());
+        bytes
+    }
+
+    /// Returns the prefix and signatures of a transaction that pays a recorded output at its index.
+    fn recorded_transaction(output: &(u64, u64, &str, &str, &str, &str)) -> (MoneroTransactionPrefix, RctSignatures) {
+        let (index, _, public_key, commitment, encrypted_amount, tx_pub_key) = output;
+
+        let mut outputs = vec![];
+        let mut ecdh_info = vec![];
+        let mut out_pk = vec![];
+        for i in 0..=*index {
+            let decoy = hash_to_point(&i.to_le_bytes());
+            outputs.push(MoneroTransactionOutput {
+                amount: 0,
+                key: decoy.compress().to_bytes(),
+                view_tag: None,
+            });
+            ecdh_info.push([0u8; 8]);
+            out_pk.push(decoy);
+        }
+        outputs[*index as usize].key = to_bytes(public_key);
+        ecdh_info[*index as usize].copy_from_slice(&hex::decode(encrypted_amount).unwrap());
+        out_pk[*index as usize] = read_point(&to_bytes(commitment)).unwrap();
+
+        let mut extra = vec![0x01];
+        extra.extend(&to_bytes(tx_pub_key));
+        let prefix = MoneroTransactionPrefix {
+            version: 2,
+            unlock_time: 0,
+            inputs: vec![],
+            outputs,
+            extra,
+        };
+        let signatures = RctSignatures {
+            rct_type: 6,
+            ecdh_info,
+            out_pk,
+            ..Default::default()
+        };
+        (prefix, signatures)
+    }
+
+    /// Returns the parameters of a transaction paying the destinations from a synthetic output.
+    fn parameters(
+        destinations: Vec<(MoneroAddress<N>, u64)>,
+        change_address: &MoneroAddress<N>,
+    ) -> MoneroTransactionParameters<N> {
+        let input_amount = 1_000_000_000_000;
+        let mix_outs = (0..RING_SIZE as u64 - 1)
+            .map(|i| MixOut {
+                global_index: 100 + i,
+                public_key: hex::encode(hash_to_point(&i.to_le_bytes()).compress().as_bytes()),
+                rct: Some(hex::encode(commit(&Scalar::one(), i).compress().as_bytes())),
+            })
+            .collect();
+        MoneroTransactionParameters {
+            using_outs: vec![UnspentOutput {
+                amount: input_amount,
+                index: 0,
+                global_index: 1,
+                public_key: hex::encode(hash_to_point(b"input").compress().as_bytes()),
+                rct: Some(hex::encode(commit(&Scalar::one(), input_amount).compress().as_bytes())),
+                tx_pub_key: hex::encode(hash_to_point(b"tx_pub_key").compress().as_bytes()),
+            }],
+            mix_outs: vec![MixAmountAndOuts {
+                amount: 0,
+                outputs: mix_outs,
+            }],
+            destinations,
+            change_address: change_address.clone(),
+            fee: 10_000_000,
+            unlock_time: 0,
+        }
+    }
+
+    /// Returns an unsigned transaction paying the destinations from a synthetic output.
+    fn unsigned_transaction(
+        destinations: Vec<(MoneroAddress<N>, u64)>,
+        change_address: &MoneroAddress<N>,
+    ) -> MoneroTransaction<N> {
+        MoneroTransaction::new(&parameters(destinations, change_address)).unwrap()
+    }
+
+    #[test]
+    fn scan_recorded_outputs() {
+        let scanner = MoneroScanner::new(&private_key(), &[]).unwrap();
+        OUTPUTS.iter().for_each(|output| {
+            let (prefix, signatures) = recorded_transaction(output);
+            let owned = scanner.scan_outputs(&prefix, &signatures).unwrap();
+            assert_eq!(1, owned.len());
+            assert_eq!(output.0, owned[0].index);
+            assert_eq!((0, 0), owned[0].subaddress);
+            assert_eq!(output.1, owned[0].amount);
+            assert_eq!(to_bytes(output.2), owned[0].public_key);
+            assert_eq!(to_bytes(output.5), owned[0].tx_pub_key);
+        });
+    }
+
+    #[test]
+    fn scan_subaddresses() {
+        let private_key = private_key();
+        let address = private_key.to_address(&MoneroFormat::Standard).unwrap();
+        let subaddress = private_key.to_address(&MoneroFormat::Subaddress(1, 2)).unwrap();
+        let other = MoneroAddress::<N>::from_str(OTHER_ADDRESS).unwrap();
+
+        // Outputs to several recipients including a subaddress carry additional public keys.
+        let transaction = unsigned_transaction(vec![(address, 1_000), (subaddress.clone(), 2_000)], &other);
+        assert_eq!(3, transaction.prefix().to_transaction_public_keys().1.len());
+
+        let scanner = MoneroScanner::new(&private_key, &[(0, 1), (1, 2)]).unwrap();
+        let mut owned = scanner.scan(&transaction).unwrap();
+        owned.sort_by_key(|output| output.amount);
+        assert_eq!(
+            vec![((0, 0), 1_000), ((1, 2), 2_000)],
+            owned
+                .iter()
+                .map(|output| (output.subaddress, output.amount))
+                .collect::<Vec<_>>()
+        );
+        owned.iter().for_each(|output| {
+            let transaction_output = &transaction.prefix().outputs[output.index as usize];
+            assert_eq!(transaction_output.key, output.public_key);
+        });
+
+        // A scanner with only the private view key and public spend key finds the same outputs.
+        let public_spend_key = private_key.to_public_key().to_public_spend_key().unwrap();
+        let view_only =
+            MoneroScanner::<N>::from_view_key(&private_key.to_private_view_key(), &public_spend_key, &[(0, 1), (1, 2)])
+                .unwrap();
+        let mut view_only_owned = view_only.scan(&transaction).unwrap();
+        view_only_owned.sort_by_key(|output| output.amount);
+        assert_eq!(owned, view_only_owned);
+
+        // Subaddresses outside of the scanned indices are not detected.
+        let mut primary_only = MoneroScanner::new(&private_key, &[]).unwrap();
+        let primary_owned = primary_only.scan(&transaction).unwrap();
+        assert_eq!(1, primary_owned.len());
+        assert_eq!(1_000, primary_owned[0].amount);
+
+        // Extending the subaddress table of the scanner detects them.
+        primary_only.extend(2, 3).unwrap();
+        assert_eq!(2, primary_only.scan(&transaction).unwrap().len());
+        let table = MoneroSubaddressTable::from_private_key(&private_key, 2, 3).unwrap();
+        let mut table_owned = MoneroScanner::from_subaddress_table(table).scan(&transaction).unwrap();
+        table_owned.sort_by_key(|output| output.amount);
+        assert_eq!(owned, table_owned);
+
+        // A single subaddress recipient derives the transaction public key from its spend key.
+        let transaction = unsigned_transaction(vec![(subaddress.clone(), 3_000)], &subaddress);
+        assert!(transaction.prefix().to_transaction_public_keys().1.is_empty());
+        let owned = scanner.scan(&transaction).unwrap();
+        assert_eq!(2, owned.len());
+        assert!(owned.iter().all(|output| output.subaddress == (1, 2)));
+        assert_eq!(
+            1_000_000_000_000 - 10_000_000,
+            owned.iter().map(|output| output.amount).sum::<u64>()
+        );
+    }
+
+    #[test]
+    fn scan_integrated_address() {
+        let private_key = private_key();
+        let integrated = private_key
+            .to_address(&MoneroFormat::Integrated([1, 2, 3, 4, 5, 6, 7, 8]))
+            .unwrap();
+        let other = MoneroAddress::<N>::from_str(OTHER_ADDRESS).unwrap();
+
+        // The payment id is encrypted for the recipient, who matches it to the integrated address.
+        let transaction = unsigned_transaction(vec![(integrated.clone(), 1_000)], &other);
+        match transaction.prefix().to_extra().nonce() {
+            Some(MoneroExtraNonce::EncryptedPaymentId(encrypted)) => assert_ne!(&[1, 2, 3, 4, 5, 6, 7, 8], encrypted),
+            nonce => panic!("expected an encrypted payment id, found {:?}", nonce),
+        };
+
+        let scanner = MoneroScanner::new(&private_key, &[]).unwrap();
+        let owned = scanner.scan(&transaction).unwrap();
+        assert_eq!(1, owned.len());
+        assert_eq!(((0, 0), 1_000), (owned[0].subaddress, owned[0].amount));
+        assert_eq!(
+            integrated.to_payment_id(),
+            scanner.to_payment_id(transaction.prefix()).unwrap()
+        );
+
+        // Transactions without a payment id have none.
+        let standard = private_key.to_address(&MoneroFormat::Standard).unwrap();
+        let transaction = unsigned_transaction(vec![(standard, 1_000)], &other);
+        assert_eq!(None, scanner.to_payment_id(transaction.prefix()).unwrap());
+    }
+
+    #[test]
+    fn scan_coinbase() {
+        // The genesis transaction pays a clear amount to a key of an unknown wallet.
+        let genesis = hex::decode("013c01ff0001ffffffffffff03029b2e4c0281c0b02e7c53291a94d1d0cbff8883f8024f5142ee494ffbbd08807121017767aafcde9be00dcfd098715ebcf7f410daebc582fda69d24a28e9d0bc890d1").unwrap();
+        let transaction = MoneroTransaction::<N>::from_transaction_bytes(&genesis).unwrap();
+        let scanner = MoneroScanner::new(&private_key(), &[(0, 1)]).unwrap();
+        assert!(scanner.scan(&transaction).unwrap().is_empty());
+    }
+
+    mod invalid {
+        use super::*;
+
+        #[test]
+        fn scan_other_wallet() {
+            let private_key = private_key();
+            let other = MoneroAddress::<N>::from_str(OTHER_ADDRESS).unwrap();
+            let transaction = unsigned_transaction(vec![(other.clone(), 1_000)], &other);
+
+            let scanner = MoneroScanner::new(&private_key, &[(0, 1), (1, 2)]).unwrap();
+            assert!(scanner.scan(&transaction).unwrap().is_empty());
+        }
+
+        #[test]
+        fn multiple_payment_ids() {
+            let private_key = private_key();
+            let first = private_key.to_address(&MoneroFormat::Integrated([1; 8])).unwrap();
+            let second = private_key.to_address(&MoneroFormat::Integrated([2; 8])).unwrap();
+            let other = MoneroAddress::<N>::from_str(OTHER_ADDRESS).unwrap();
+
+            let different = parameters(vec![(first.clone(), 1_000), (second, 2_000)], &other);
+            assert!(MoneroTransaction::new(&different).is_err());
+            let same = parameters(vec![(first.clone(), 1_000), (first, 2_000)], &other);
+            assert!(MoneroTransaction::new(&same).is_ok());
+        }
+
+        #[test]
+        fn scan_tampered_amount() {
+            let scanner = MoneroScanner::new(&private_key(), &[]).unwrap();
+            OUTPUTS.iter().for_each(|output| {
+                let (prefix, mut signatures) = recorded_transaction(output);
+                signatures.ecdh_info[output.0 as usize][0] ^= 1;
+                assert!(scanner.scan_outputs(&prefix, &signatures).is_err());
+            });
+        }
+
+        #[test]
+        fn invalid_public_spend_key() {
+            let private_view_key = private_key().to_private_view_key();
+            let mut public_spend_key = [0u8; 32];
+            public_spend_key[0] = 2;
+            assert!(MoneroScanner::<N>::from_view_key(&private_view_key, &public_spend_key, &[]).is_err());
+        }
+    }
+}
@@ -0,0 +1,373 @@
+use crate::address::MoneroAddress;
+use crate::format::MoneroFormat;
+use crate::network::MoneroNetwork;
+use crate::private_key::MoneroPrivateKey;
+use crate::public_key::MoneroPublicKey;
+use crate::ringct::read_point;
+use wagyu_model::AddressError;
+
+use curve25519_dalek::{
+    constants::{ED25519_BASEPOINT_POINT, ED25519_BASEPOINT_TABLE},
+    edwards::EdwardsPoint,
+    scalar::Scalar,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    marker::PhantomData,
+};
+use tiny_keccak::keccak256;
+
+/// Represents the subaddresses of a wallet, as a lookup table from the public spend key
+/// of each subaddress to its account and subaddress indices
+#[derive(Debug, Clone)]
+pub struct MoneroSubaddressTable<N: MoneroNetwork> {
+    /// The private view key
+    private_view_key: [u8; 32],
+    /// The public spend key of the primary address
+    public_spend_key: [u8; 32],
+    /// The account and subaddress indices of each public spend key
+    keys: HashMap<[u8; 32], (u32, u32)>,
+    /// The public spend key of each account and subaddress indices
+    indices: BTreeMap<(u32, u32), [u8; 32]>,
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: MoneroNetwork> MoneroSubaddressTable<N> {
+    /// Returns the table of the primary address and the first `indices` subaddresses of the first
+    /// `accounts` accounts given the private view key and the public spend key of a wallet.
+    pub fn new(
+        private_view_key: &[u8; 32],
+        public_spend_key: &[u8; 32],
+        accounts: u32,
+        indices: u32,
+    ) -> Result<Self, AddressError> {
+        read_point(public_spend_key)?;
+
+        let mut table = Self {
+            private_view_key: *private_view_key,
+            public_spend_key: *public_spend_key,
+            keys: HashMap::new(),
+            indices: BTreeMap::new(),
+            _network: PhantomData,
+        };
+        table.insert(0, 0)?;
+        table.extend(accounts, indices)?;
+        Ok(table)
+    }
+
+    /// Returns the table of the primary address and the first `indices` subaddresses of the first
+    /// `accounts` accounts of a private key.
+    pub fn from_private_key(
+        private_key: &MoneroPrivateKey<N>,
+        accounts: u32,
+        indices: u32,
+    ) -> Result<Self, AddressError> {
+        let private_spend_key = Scalar::from_bits(private_key.to_private_spend_key());
+        let public_spend_key = (&private_spend_key * &ED25519_BASEPOINT_TABLE).compress().to_bytes();
+        Self::new(&private_key.to_private_view_key(), &public_spend_key, accounts, indices)
+    }
+
+    /// Adds the subaddress at the account and subaddress indices, and returns its public spend key.
+    pub fn insert(&mut self, major: u32, minor: u32) -> Result<[u8; 32], AddressError> {
+        if let Some(key) = self.indices.get(&(major, minor)) {
+            return Ok(*key);
+        }
+
+        let key = self.subaddress_spend_key(major, minor)?.compress().to_bytes();
+        self.keys.insert(key, (major, minor));
+        self.indices.insert((major, minor), key);
+        Ok(key)
+    }
+
+    /// Adds the first `indices` subaddresses of the first `accounts` accounts,
+    /// deriving only the subaddresses that are not in the table yet.
+    pub fn extend(&mut self, accounts: u32, indices: u32) -> Result<(), AddressError> {
+        for major in 0..accounts {
+            for minor in 0..indices {
+                self.insert(major, minor)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the account and subaddress indices of a public spend key, or returns `None`.
+    pub fn get(&self, public_spend_key: &[u8; 32]) -> Option<(u32, u32)> {
+        self.keys.get(public_spend_key).cloned()
+    }
+
+    /// Returns the format of an address of the wallet with the indices of a subaddress,
+    /// or returns `None` if the address is not in the table.
+    pub fn resolve(&self, address: &MoneroAddress<N>) -> Result<Option<MoneroFormat>, AddressError> {
+        let public_key = address.to_public_key()?;
+        let public_spend_key = public_key.to_public_spend_key().ok_or(AddressError::MissingPublicKey)?;
+        let public_view_key = public_key.to_public_view_key().ok_or(AddressError::MissingPublicKey)?;
+
+        let (major, minor) = match self.get(&public_spend_key) {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+
+        // The view key is a*G for the primary address and C = a*D for a subaddress.
+        let format = address.format()?;
+        let view_point = match format {
+            MoneroFormat::Subaddress(_, _) => read_point(&public_spend_key)?,
+            _ if (major, minor) == (0, 0) => ED25519_BASEPOINT_POINT,
+            _ => return Ok(None),
+        };
+        if (Scalar::from_bits(self.private_view_key) * view_point)
+            .compress()
+            .to_bytes()
+            != public_view_key
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(match format {
+            MoneroFormat::Subaddress(_, _) if (major, minor) == (0, 0) => MoneroFormat::Standard,
+            MoneroFormat::Subaddress(_, _) => MoneroFormat::Subaddress(major, minor),
+            format => format,
+        }))
+    }
+
+    /// Returns the public key of the subaddress at the account and subaddress indices.
+    pub fn to_public_key(&self, major: u32, minor: u32) -> Result<MoneroPublicKey<N>, AddressError> {
+        let public_spend_key = self.subaddress_spend_key(major, minor)?;
+        let (view_point, format) = match (major, minor) {
+            (0, 0) => (ED25519_BASEPOINT_POINT, MoneroFormat::Standard),
+            _ => (public_spend_key, MoneroFormat::Subaddress(major, minor)),
+        };
+        let public_view_key = Scalar::from_bits(self.private_view_key) * view_point;
+
+        Ok(MoneroPublicKey::from(
+            &hex::encode(public_spend_key.compress().as_bytes()),
+            &hex::encode(public_view_key.compress().as_bytes()),
+            &format,
+        )?)
+    }
+
+    /// Returns the address of the subaddress at the account and subaddress indices,
+    /// which is the primary address at indices (0, 0).
+    pub fn to_address(&self, major: u32, minor: u32) -> Result<MoneroAddress<N>, AddressError> {
+        let public_key = self.to_public_key(major, minor)?;
+        MoneroAddress::generate_address(&public_key, &public_key.format())
+    }
+
+    /// Returns the account and subaddress indices and the public spend key of each subaddress in the table,
+    /// ordered by account and subaddress index.
+    pub fn subaddresses(&self) -> Vec<((u32, u32), [u8; 32])> {
+        self.indices.iter().map(|(index, key)| (*index, *key)).collect()
+    }
+
+    /// Returns the number of subaddresses in the table, including the primary address.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns `true` if the table has no subaddresses.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Returns the private view key of the table.
+    pub fn private_view_key(&self) -> [u8; 32] {
+        self.private_view_key
+    }
+
+    /// Returns the public spend key of the primary address of the table.
+    pub fn public_spend_key(&self) -> [u8; 32] {
+        self.public_spend_key
+    }
+
+    /// Returns the public spend key `D = B + H_s("SubAddr" || a || major || minor) * G`
+    /// of the subaddress at the account and subaddress indices, which is `B` at indices (0, 0).
+    fn subaddress_spend_key(&self, major: u32, minor: u32) -> Result<EdwardsPoint, AddressError> {
+        let public_spend_point = read_point(&self.public_spend_key)?;
+        if (major, minor) == (0, 0) {
+            return Ok(public_spend_point);
+        }
+
+        let mut data = b"SubAddr\x00".to_vec();
+        data.extend(&self.private_view_key);
+        data.extend(&major.to_le_bytes());
+        data.extend(&minor.to_le_bytes());
+        let subaddress_secret = Scalar::from_bytes_mod_order(keccak256(&data));
+
+        Ok(public_spend_point + &subaddress_secret * &ED25519_BASEPOINT_TABLE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Mainnet, Stagenet};
+    use wagyu_model::PrivateKey;
+
+    use std::str::FromStr;
+
+    type N = Stagenet;
+
+    const PRIVATE_SPEND_KEY: &str = "0cf0c38429e00fa4abecb98296cb15dec209c0a7e6ea34ed86d32429498e4700";
+    const PRIVATE_VIEW_KEY: &str = "55c5b2fa94b2c5ee387eb9dd71b197a6358a5a90ed5eb5acdfa088583125a40a";
+    const OTHER_ADDRESS: &str =
+        "59McWTPGc745SRWrSMoh8oTjoXoQq6sPUgKZ66dQWXuKFQ2q19h9gvhJNZcFTizcnT12r63NFgHiGd6gBCjabzmzHAMoyD6";
+
+    // (seed, major, minor, address)
+    const MAINNET_SUBADDRESSES: [(&str, u32, u32, &str); 3] = [
+        (
+            "7130e7a7657a75590fc00c2926bbcbd252044ca2210fde0dc74a6dfdd2512501",
+            0,
+            1,
+            "83NfRiAk5AiBZDyTjGDejpap8VJDgspsyQhHfGZAVATpfPwaJ9SXRcHEkTC8chu8gEcrudMymLT8dFKkidrqTEVRKApoPSx",
+        ),
+        (
+            "a22b4a3418db16214f1a278e1f0b115ede224f043bc1d0596a74f9748f41b00b",
+            1,
+            100,
+            "87DrKYY4cXidB8g9kB9pS6dH3xvySXRg71Abpy2nGHemGX7sKA2kdFT8MgReY8jbdMaESqJU8XeAzbFqYdUREQPtKC3yTYS",
+        ),
+        (
+            "c25c2b372c49fe3056b211432da1c5f76173230215df1ab0554ecf51417e7709",
+            25000,
+            0,
+            "8AypGY3tMu49YQqZ49cFUjBoywJJ72r6R9xmjq77jLHTD8GxyV3AKogahHNhNWZDWKZPxbdaDASwT5axCkmwhCaYH8DYADx",
+        ),
+    ];
+
+    fn private_key() -> MoneroPrivateKey<N> {
+        MoneroPrivateKey::from_private_keys(PRIVATE_SPEND_KEY, PRIVATE_VIEW_KEY, &MoneroFormat::Standard).unwrap()
+    }
+
+    #[test]
+    fn new() {
+        let private_key = private_key();
+        let table = MoneroSubaddressTable::from_private_key(&private_key, 3, 4).unwrap();
+        assert_eq!(12, table.len());
+
+        for major in 0..3 {
+            for minor in 0..4 {
+                let format = MoneroFormat::Subaddress(major, minor);
+                let address = private_key.to_address(&format).unwrap();
+                let public_spend_key = address.to_public_key().unwrap().to_public_spend_key().unwrap();
+                assert_eq!(Some((major, minor)), table.get(&public_spend_key));
+                assert_eq!(address, table.to_address(major, minor).unwrap());
+            }
+        }
+        assert_eq!(None, table.get(&[0u8; 32]));
+    }
+
+    #[test]
+    fn from_view_key() {
+        let private_key = private_key();
+        let public_spend_key = private_key.to_public_key().to_public_spend_key().unwrap();
+        let table =
+            MoneroSubaddressTable::<N>::new(&private_key.to_private_view_key(), &public_spend_key, 2, 2).unwrap();
+        let expected = MoneroSubaddressTable::from_private_key(&private_key, 2, 2).unwrap();
+        assert_eq!(expected.subaddresses(), table.subaddresses());
+        assert_eq!(public_spend_key, table.public_spend_key());
+    }
+
+    #[test]
+    fn extend() {
+        let private_key = private_key();
+        let mut table = MoneroSubaddressTable::from_private_key(&private_key, 0, 0).unwrap();
+        assert_eq!(
+            vec![(0, 0)],
+            table.subaddresses().iter().map(|(index, _)| *index).collect::<Vec<_>>()
+        );
+
+        table.extend(1, 3).unwrap();
+        table.extend(2, 2).unwrap();
+        table.insert(7, 42).unwrap();
+        assert_eq!(
+            vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (7, 42)],
+            table.subaddresses().iter().map(|(index, _)| *index).collect::<Vec<_>>()
+        );
+
+        let address = private_key.to_address(&MoneroFormat::Subaddress(7, 42)).unwrap();
+        let public_spend_key = address.to_public_key().unwrap().to_public_spend_key().unwrap();
+        assert_eq!(Some((7, 42)), table.get(&public_spend_key));
+    }
+
+    #[test]
+    fn resolve() {
+        let private_key = private_key();
+        let table = MoneroSubaddressTable::from_private_key(&private_key, 2, 3).unwrap();
+
+        let subaddress = MoneroAddress::<N>::from_str(&table.to_address(1, 2).unwrap().to_string()).unwrap();
+        assert_eq!(
+            MoneroFormat::Subaddress(u32::max_value(), u32::max_value()),
+            subaddress.format().unwrap()
+        );
+        assert_eq!(
+            Some(MoneroFormat::Subaddress(1, 2)),
+            table.resolve(&subaddress).unwrap()
+        );
+
+        let address = private_key.to_address(&MoneroFormat::Standard).unwrap();
+        assert_eq!(Some(MoneroFormat::Standard), table.resolve(&address).unwrap());
+
+        let payment_id = [1u8; 8];
+        let integrated = private_key.to_address(&MoneroFormat::Integrated(payment_id)).unwrap();
+        assert_eq!(
+            Some(MoneroFormat::Integrated(payment_id)),
+            table.resolve(&integrated).unwrap()
+        );
+    }
+
+    #[test]
+    fn resolve_mainnet_subaddresses() {
+        MAINNET_SUBADDRESSES.iter().for_each(|(seed, major, minor, address)| {
+            let private_key = MoneroPrivateKey::<Mainnet>::from_seed(seed, &MoneroFormat::Standard).unwrap();
+            let mut table = MoneroSubaddressTable::from_private_key(&private_key, 0, 0).unwrap();
+            let address = MoneroAddress::<Mainnet>::from_str(address).unwrap();
+            assert_eq!(None, table.resolve(&address).unwrap());
+
+            table.insert(*major, *minor).unwrap();
+            assert_eq!(
+                Some(MoneroFormat::Subaddress(*major, *minor)),
+                table.resolve(&address).unwrap()
+            );
+            assert_eq!(address, table.to_address(*major, *minor).unwrap());
+        });
+    }
+
+    mod invalid {
+        use super::*;
+
+        #[test]
+        fn resolve_other_wallet() {
+            let table = MoneroSubaddressTable::from_private_key(&private_key(), 2, 3).unwrap();
+            let other = MoneroAddress::<N>::from_str(OTHER_ADDRESS).unwrap();
+            assert_eq!(None, table.resolve(&other).unwrap());
+        }
+
+        #[test]
+        fn resolve_other_view_key() {
+            // A subaddress with the spend key of the wallet and the view key of another wallet is not resolved.
+            let private_key = private_key();
+            let table = MoneroSubaddressTable::from_private_key(&private_key, 2, 3).unwrap();
+            let subaddress = table.to_public_key(1, 2).unwrap();
+            let other = MoneroAddress::<N>::from_str(OTHER_ADDRESS)
+                .unwrap()
+                .to_public_key()
+                .unwrap();
+            let public_key = MoneroPublicKey::<N>::from(
+                &hex::encode(subaddress.to_public_spend_key().unwrap()),
+                &hex::encode(other.to_public_view_key().unwrap()),
+                &MoneroFormat::Subaddress(1, 2),
+            )
+            .unwrap();
+            let address = MoneroAddress::generate_address(&public_key, &MoneroFormat::Subaddress(1, 2)).unwrap();
+            assert_eq!(None, table.resolve(&address).unwrap());
+        }
+
+        #[test]
+        fn invalid_public_spend_key() {
+            let private_view_key = private_key().to_private_view_key();
+            let mut public_spend_key = [0u8; 32];
+            public_spend_key[0] = 2;
+            assert!(MoneroSubaddressTable::<N>::new(&private_view_key, &public_spend_key, 1, 1).is_err());
+        }
+    }
+}
@@ -1,43 +1,480 @@
-#![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 
 use crate::address::MoneroAddress;
+use crate::extra::{encrypt_payment_id, MoneroExtraField, MoneroExtraNonce, MoneroTransactionExtra};
 use crate::format::MoneroFormat;
 use crate::network::MoneroNetwork;
+use crate::one_time_key::{OneTimeKey, OneTimeKeyError};
 use crate::private_key::MoneroPrivateKey;
 use crate::public_key::MoneroPublicKey;
+use crate::ringct::*;
 use wagyu_model::{Transaction, TransactionError, TransactionId};
 
-use libc::c_char;
-use serde::{export::PhantomData, Deserialize, Serialize};
-use serde_json;
-use std::{
-    ffi::{CStr, CString},
-    fmt, str,
-};
+use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, edwards::EdwardsPoint, scalar::Scalar};
+use rand::{seq::SliceRandom, thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{fmt, marker::PhantomData};
+use tiny_keccak::keccak256;
+
+/// The transaction version of RingCT transactions
+const TRANSACTION_VERSION: u64 = 2;
+
+/// The RingCT type of coinbase transactions, which carry no signatures
+pub(crate) const RCT_TYPE_NULL: u8 = 0;
+
+/// The RingCT type of transactions with MLSAG signatures and Bulletproof range proofs
+const RCT_TYPE_BULLETPROOF_2: u8 = 4;
+
+/// The RingCT type of transactions with CLSAG signatures and Bulletproof range proofs
+const RCT_TYPE_CLSAG: u8 = 5;
+
+/// The RingCT type of transactions with CLSAG signatures and Bulletproofs+ range proofs
+const RCT_TYPE_BULLETPROOF_PLUS: u8 = 6;
+
+/// The number of members of each ring, fixed by consensus since the v15 network upgrade
+pub const RING_SIZE: usize = 16;
+
+/// The minimum number of outputs of a transaction, fixed by consensus since the v12 network upgrade
+const MIN_OUTPUTS: usize = 2;
+
+/// The variant tag of a `txin_gen` input
+const TXIN_GEN: u8 = 0xff;
+
+/// The variant tag of a `txin_to_key` input
+const TXIN_TO_KEY: u8 = 0x02;
+
+/// The variant tag of a `txout_to_key` output
+const TXOUT_TO_KEY: u8 = 0x02;
+
+/// The variant tag of a `txout_to_tagged_key` output
+const TXOUT_TO_TAGGED_KEY: u8 = 0x03;
+
+impl From<OneTimeKeyError> for TransactionError {
+    fn from(error: OneTimeKeyError) -> Self {
+        TransactionError::Crate("OneTimeKey", format!("{:?}", error))
+    }
+}
+
+/// Represents an output owned by the sender to be spent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnspentOutput {
+    /// The amount in piconero
+    pub amount: u64,
+    /// The index of the output in its transaction
+    pub index: u64,
+    /// The global index of the output among the outputs of its amount
+    pub global_index: u64,
+    /// The one time public key of the output
+    pub public_key: String,
+    /// The commitment followed by the encrypted amount, or `None` for a pre-RingCT output
+    pub rct: Option<String>,
+    /// The public key of the transaction (or the additional public key) of the output
+    pub tx_pub_key: String,
+}
+
+/// Represents the decoys of the ring of one spent output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MixAmountAndOuts {
+    /// The amount of the decoys, which is zero for RingCT outputs
+    pub amount: u64,
+    /// The decoy outputs, which may include the spent output itself
+    pub outputs: Vec<MixOut>,
+}
+
+/// Represents a decoy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MixOut {
+    /// The global index of the output among the outputs of its amount
+    pub global_index: u64,
+    /// The one time public key of the output
+    pub public_key: String,
+    /// The commitment of the output, or `None` for a pre-RingCT output
+    pub rct: Option<String>,
+}
+
+/// Represents the parameters for a Monero transaction
+#[derive(Debug, Clone)]
+pub struct MoneroTransactionParameters<N: MoneroNetwork> {
+    /// The outputs to spend
+    pub using_outs: Vec<UnspentOutput>,
+    /// The decoys of each spent output, in the same order, already selected by the caller
+    pub mix_outs: Vec<MixAmountAndOuts>,
+    /// The recipients and their amounts in piconero
+    pub destinations: Vec<(MoneroAddress<N>, u64)>,
+    /// The address receiving the change
+    pub change_address: MoneroAddress<N>,
+    /// The fee in piconero
+    pub fee: u64,
+    /// The block height or timestamp until which the outputs are locked
+    pub unlock_time: u64,
+}
+
+/// Represents a Monero transaction input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoneroTransactionInput {
+    /// A coinbase input minting the block reward at the given height (`txin_gen`)
+    Generation { height: u64 },
+    /// An input spending one member of a ring (`txin_to_key`)
+    ToKey {
+        /// The amount of a pre-RingCT output, or zero
+        amount: u64,
+        /// The global indices of the ring members, each relative to the previous one
+        key_offsets: Vec<u64>,
+        /// The key image of the spent output
+        key_image: [u8; 32],
+    },
+}
+
+impl MoneroTransactionInput {
+    /// Returns the number of ring members of the input, which is zero for a coinbase input.
+    pub fn ring_size(&self) -> usize {
+        match self {
+            MoneroTransactionInput::Generation { .. } => 0,
+            MoneroTransactionInput::ToKey { key_offsets, .. } => key_offsets.len(),
+        }
+    }
+}
+
+/// Represents a Monero transaction output (`txout_to_key`, or `txout_to_tagged_key` with a view tag)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroTransactionOutput {
+    /// The amount of a pre-RingCT output, or zero
+    pub amount: u64,
+    /// The one time public key of the output
+    pub key: [u8; 32],
+    /// The view tag of the output
+    pub view_tag: Option<u8>,
+}
+
+/// Represents the prefix of a Monero transaction, which is signed by the ring signatures
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroTransactionPrefix {
+    /// The transaction version
+    pub version: u64,
+    /// The block height or timestamp until which the outputs are locked
+    pub unlock_time: u64,
+    /// The inputs
+    pub inputs: Vec<MoneroTransactionInput>,
+    /// The outputs
+    pub outputs: Vec<MoneroTransactionOutput>,
+    /// The extra field
+    pub extra: Vec<u8>,
+}
+
+impl MoneroTransactionPrefix {
+    /// Appends the serialized transaction prefix.
+    pub fn write(&self, buffer: &mut Vec<u8>) {
+        write_varint(buffer, self.version);
+        write_varint(buffer, self.unlock_time);
+
+        write_varint(buffer, self.inputs.len() as u64);
+        for input in &self.inputs {
+            match input {
+                MoneroTransactionInput::Generation { height } => {
+                    buffer.push(TXIN_GEN);
+                    write_varint(buffer, *height);
+                }
+                MoneroTransactionInput::ToKey {
+                    amount,
+                    key_offsets,
+                    key_image,
+                } => {
+                    buffer.push(TXIN_TO_KEY);
+                    write_varint(buffer, *amount);
+                    write_varint(buffer, key_offsets.len() as u64);
+                    key_offsets.iter().for_each(|offset| write_varint(buffer, *offset));
+                    buffer.extend(key_image);
+                }
+            }
+        }
+
+        write_varint(buffer, self.outputs.len() as u64);
+        for output in &self.outputs {
+            write_varint(buffer, output.amount);
+            match output.view_tag {
+                Some(view_tag) => {
+                    buffer.push(TXOUT_TO_TAGGED_KEY);
+                    buffer.extend(&output.key);
+                    buffer.push(view_tag);
+                }
+                None => {
+                    buffer.push(TXOUT_TO_KEY);
+                    buffer.extend(&output.key);
+                }
+            }
+        }
+
+        write_varint(buffer, self.extra.len() as u64);
+        buffer.extend(&self.extra);
+    }
+
+    /// Returns the transaction prefix serialized at the offset and advances the offset past it.
+    pub fn read(bytes: &[u8], offset: &mut usize) -> Result<Self, TransactionError> {
+        let version = take_varint(bytes, offset)?;
+        let unlock_time = take_varint(bytes, offset)?;
+
+        let inputs = (0..take_varint(bytes, offset)?)
+            .map(|_| match take(bytes, offset, 1)?[0] {
+                TXIN_GEN => Ok(MoneroTransactionInput::Generation {
+                    height: take_varint(bytes, offset)?,
+                }),
+                TXIN_TO_KEY => {
+                    let amount = take_varint(bytes, offset)?;
+                    let key_offsets = (0..take_varint(bytes, offset)?)
+                        .map(|_| take_varint(bytes, offset))
+                        .collect::<Result<Vec<u64>, TransactionError>>()?;
+                    let mut key_image = [0u8; 32];
+                    key_image.copy_from_slice(take(bytes, offset, 32)?);
+                    Ok(MoneroTransactionInput::ToKey {
+                        amount,
+                        key_offsets,
+                        key_image,
+                    })
+                }
+                tag => Err(TransactionError::Message(format!("unsupported input type {}", tag))),
+            })
+            .collect::<Result<Vec<MoneroTransactionInput>, TransactionError>>()?;
+
+        let outputs = (0..take_varint(bytes, offset)?)
+            .map(|_| {
+                let amount = take_varint(bytes, offset)?;
+                let tag = take(bytes, offset, 1)?[0];
+                let mut key = [0u8; 32];
+                key.copy_from_slice(take(bytes, offset, 32)?);
+                let view_tag = match tag {
+                    TXOUT_TO_KEY => None,
+                    TXOUT_TO_TAGGED_KEY => Some(take(bytes, offset, 1)?[0]),
+                    tag => return Err(TransactionError::Message(format!("unsupported output type {}", tag))),
+                };
+                Ok(MoneroTransactionOutput { amount, key, view_tag })
+            })
+            .collect::<Result<Vec<MoneroTransactionOutput>, TransactionError>>()?;
+
+        let extra_length = take_varint(bytes, offset)? as usize;
+        let extra = take(bytes, offset, extra_length)?.to_vec();
+
+        Ok(Self {
+            version,
+            unlock_time,
+            inputs,
+            outputs,
+            extra,
+        })
+    }
+
+    /// Returns the hash of the transaction prefix.
+    pub fn to_hash(&self) -> [u8; 32] {
+        let mut buffer = vec![];
+        self.write(&mut buffer);
+        keccak256(&buffer)
+    }
+
+    /// Returns the fields of the extra field up to the first one that is unknown or malformed.
+    pub fn to_extra(&self) -> MoneroTransactionExtra {
+        MoneroTransactionExtra::from_bytes_partial(&self.extra)
+    }
+
+    /// Returns the transaction public keys and the additional transaction public keys in the extra field.
+    ///
+    /// As in Monero, the fields are read until the first one that is unknown or malformed.
+    pub fn to_transaction_public_keys(&self) -> (Vec<[u8; 32]>, Vec<[u8; 32]>) {
+        let extra = self.to_extra();
+        (extra.public_keys(), extra.additional_public_keys())
+    }
+}
+
+/// Represents the RingCT signatures of a Monero transaction
+///
+/// Transactions of RingCT types 4 and 5 are parsed and serialized, but only type 6 is created and verified.
+/// The types 1 to 3 of transactions before the v10 network upgrade, which encrypt amounts in 64 bytes, are not supported.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RctSignatures {
+    /// The RingCT type
+    pub rct_type: u8,
+    /// The fee in piconero
+    pub fee: u64,
+    /// The encrypted amounts of the outputs
+    pub ecdh_info: Vec<[u8; 8]>,
+    /// The amount commitments of the outputs
+    pub out_pk: Vec<EdwardsPoint>,
+    /// The aggregated range proofs of the output amounts of RingCT types 4 and 5
+    pub bulletproofs: Vec<Bulletproof>,
+    /// The aggregated range proofs of the output amounts of RingCT type 6
+    pub bulletproofs_plus: Vec<BulletproofPlus>,
+    /// The ring signatures of the inputs of RingCT type 4
+    pub mlsags: Vec<Mlsag>,
+    /// The ring signatures of the inputs of RingCT types 5 and 6
+    pub clsags: Vec<Clsag>,
+    /// The commitments to the input amounts under fresh masks
+    pub pseudo_outs: Vec<EdwardsPoint>,
+}
+
+impl RctSignatures {
+    /// Appends the serialized base of the signatures, which is hashed into the ring signature message.
+    pub fn write_base(&self, buffer: &mut Vec<u8>) {
+        buffer.push(self.rct_type);
+        if self.rct_type == RCT_TYPE_NULL {
+            return;
+        }
+        write_varint(buffer, self.fee);
+        self.ecdh_info.iter().for_each(|amount| buffer.extend(amount));
+        self.out_pk
+            .iter()
+            .for_each(|commitment| buffer.extend(commitment.compress().as_bytes()));
+    }
+
+    /// Appends the serialized prunable part of the signatures.
+    pub fn write_prunable(&self, buffer: &mut Vec<u8>) {
+        if self.rct_type == RCT_TYPE_NULL {
+            return;
+        }
+        match self.rct_type {
+            RCT_TYPE_BULLETPROOF_PLUS => {
+                write_varint(buffer, self.bulletproofs_plus.len() as u64);
+                self.bulletproofs_plus.iter().for_each(|proof| proof.write(buffer));
+            }
+            _ => {
+                write_varint(buffer, self.bulletproofs.len() as u64);
+                self.bulletproofs.iter().for_each(|proof| proof.write(buffer));
+            }
+        };
+        self.mlsags.iter().for_each(|mlsag| mlsag.write(buffer));
+        self.clsags.iter().for_each(|clsag| clsag.write(buffer));
+        self.pseudo_outs
+            .iter()
+            .for_each(|pseudo_out| buffer.extend(pseudo_out.compress().as_bytes()));
+    }
+
+    /// Returns the signatures of the transaction prefix serialized at the offset and advances the offset past them.
+    /// Only the RingCT types 0 and 4 to 6 are supported.
+    pub fn read(prefix: &MoneroTransactionPrefix, bytes: &[u8], offset: &mut usize) -> Result<Self, TransactionError> {
+        let rct_type = take(bytes, offset, 1)?[0];
+        match rct_type {
+            RCT_TYPE_NULL => return Ok(Self::default()),
+            RCT_TYPE_BULLETPROOF_2 | RCT_TYPE_CLSAG | RCT_TYPE_BULLETPROOF_PLUS => {}
+            rct_type => {
+                return Err(TransactionError::Message(format!(
+                    "unsupported RingCT type {}",
+                    rct_type
+                )))
+            }
+        };
+
+        let fee = take_varint(bytes, offset)?;
+        let mut ecdh_info = vec![];
+        for _ in &prefix.outputs {
+            let mut amount = [0u8; 8];
+            amount.copy_from_slice(take(bytes, offset, 8)?);
+            ecdh_info.push(amount);
+        }
+        let out_pk = prefix
+            .outputs
+            .iter()
+            .map(|_| read_point(take(bytes, offset, 32)?))
+            .collect::<Result<Vec<EdwardsPoint>, RingCtError>>()?;
+
+        // The outputs share one aggregated range proof.
+        if take_varint(bytes, offset)? != 1 {
+            return Err(RingCtError::InvalidRangeProof.into());
+        }
+        let (bulletproofs, bulletproofs_plus) = match rct_type {
+            RCT_TYPE_BULLETPROOF_PLUS => {
+                let (bulletproof_plus, length) = BulletproofPlus::read(&out_pk, &bytes[*offset..])?;
+                *offset += length;
+                (vec![], vec![bulletproof_plus])
+            }
+            _ => {
+                let (bulletproof, length) = Bulletproof::read(&out_pk, &bytes[*offset..])?;
+                *offset += length;
+                (vec![bulletproof], vec![])
+            }
+        };
+
+        let mut mlsags = vec![];
+        let mut clsags = vec![];
+        for input in &prefix.inputs {
+            let ring_size = input.ring_size();
+            if ring_size == 0 {
+                return Err(RingCtError::InvalidRingSize(ring_size, RING_SIZE).into());
+            }
+            match rct_type {
+                RCT_TYPE_BULLETPROOF_2 => {
+                    mlsags.push(Mlsag::read(ring_size, take(bytes, offset, 32 * (2 * ring_size + 1))?)?)
+                }
+                _ => clsags.push(Clsag::read(ring_size, take(bytes, offset, 32 * (ring_size + 2))?)?),
+            };
+        }
+        let pseudo_outs = prefix
+            .inputs
+            .iter()
+            .map(|_| read_point(take(bytes, offset, 32)?))
+            .collect::<Result<Vec<EdwardsPoint>, RingCtError>>()?;
+
+        Ok(Self {
+            rct_type,
+            fee,
+            ecdh_info,
+            out_pk,
+            bulletproofs,
+            bulletproofs_plus,
+            mlsags,
+            clsags,
+            pseudo_outs,
+        })
+    }
+}
+
+/// Represents an output being spent, together with its ring, until the transaction is signed
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MoneroSpend {
+    /// The output being spent
+    output: UnspentOutput,
+    /// The ring members, ordered by global index
+    ring: Vec<RingMember>,
+    /// The position of the spent output in the ring
+    real_index: usize,
+    /// The mask of the pseudo output commitment
+    pseudo_mask: Scalar,
+}
 
 /// Represents a Monero transaction
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MoneroTransaction<N: MoneroNetwork> {
-    tx_must_be_reconstructed: bool,
-    serialized_signed_tx: String,
-    tx_hash: MoneroTransactionId,
-    tx_key: String,
-    tx_pub_key: String,
+    /// The transaction prefix
+    prefix: MoneroTransactionPrefix,
+    /// The ring signatures of a version 1 transaction, as (c, r) pairs for each ring member of each input
+    ring_signatures: Vec<Vec<[u8; 64]>>,
+    /// The RingCT signatures
+    rct_signatures: RctSignatures,
+    /// The transaction private key
+    tx_key: Option<Scalar>,
+    /// The additional transaction private keys of transactions with subaddress outputs
+    additional_tx_keys: Vec<Scalar>,
+    /// The outputs being spent, until the transaction is signed
+    spends: Vec<MoneroSpend>,
+    /// PhantomData
     _network: PhantomData<N>,
 }
 
-/// Represents an Monero transaction id
+/// Represents a Monero transaction id
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct MoneroTransactionId {
-    tx_hash: String,
+    tx_hash: [u8; 32],
 }
 
 impl TransactionId for MoneroTransactionId {}
 
+impl MoneroTransactionId {
+    /// Returns the transaction hash.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.tx_hash
+    }
+}
+
 impl fmt::Display for MoneroTransactionId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", &self.tx_hash)
+        write!(f, "{}", hex::encode(&self.tx_hash))
     }
 }
 
@@ -47,910 +484,1628 @@ impl<N: MoneroNetwork> Transaction for MoneroTransaction<N> {
     type PrivateKey = MoneroPrivateKey<N>;
     type PublicKey = MoneroPublicKey<N>;
     type TransactionId = MoneroTransactionId;
-    type TransactionParameters = TransactionParameters;
+    type TransactionParameters = MoneroTransactionParameters<N>;
 
-    fn new(_parameters: &Self::TransactionParameters) -> Result<Self, TransactionError> {
-        unimplemented!();
+    /// Returns an unsigned transaction given the transaction parameters.
+    fn new(parameters: &Self::TransactionParameters) -> Result<Self, TransactionError> {
+        Self::new_with_rng(parameters, &mut thread_rng())
     }
 
-    fn sign(&self, _private_key: &Self::PrivateKey) -> Result<Self, TransactionError> {
-        unimplemented!();
+    /// Returns a signed transaction given the private key of the sender.
+    fn sign(&self, private_key: &Self::PrivateKey) -> Result<Self, TransactionError> {
+        self.sign_with_rng(private_key, &mut thread_rng())
     }
 
-    fn from_transaction_bytes(_transaction: &Vec<u8>) -> Result<Self, TransactionError> {
-        unimplemented!();
+    /// Returns a transaction given the transaction bytes.
+    fn from_transaction_bytes(transaction: &Vec<u8>) -> Result<Self, TransactionError> {
+        let mut offset = 0;
+        let prefix = MoneroTransactionPrefix::read(transaction, &mut offset)?;
+
+        let mut ring_signatures = vec![];
+        let mut rct_signatures = RctSignatures::default();
+        match prefix.version {
+            1 => {
+                for input in &prefix.inputs {
+                    let signatures = (0..input.ring_size())
+                        .map(|_| {
+                            let mut signature = [0u8; 64];
+                            signature.copy_from_slice(take(transaction, &mut offset, 64)?);
+                            Ok(signature)
+                        })
+                        .collect::<Result<Vec<[u8; 64]>, RingCtError>>()?;
+                    ring_signatures.push(signatures);
+                }
+            }
+            TRANSACTION_VERSION => rct_signatures = RctSignatures::read(&prefix, transaction, &mut offset)?,
+            version => return Err(TransactionError::Message(format!("unsupported version {}", version))),
+        };
+
+        if offset != transaction.len() {
+            return Err(TransactionError::Message(format!(
+                "{} trailing bytes",
+                transaction.len() - offset
+            )));
+        }
+
+        Ok(Self {
+            prefix,
+            ring_signatures,
+            rct_signatures,
+            tx_key: None,
+            additional_tx_keys: vec![],
+            spends: vec![],
+            _network: PhantomData,
+        })
     }
 
+    /// Returns the transaction in bytes.
     fn to_transaction_bytes(&self) -> Result<Vec<u8>, TransactionError> {
-        unimplemented!();
+        if !self.is_signed() {
+            return Err(TransactionError::Message("the transaction is not signed".into()));
+        }
+
+        let mut buffer = vec![];
+        self.prefix.write(&mut buffer);
+        match self.prefix.version {
+            1 => self
+                .ring_signatures
+                .iter()
+                .flatten()
+                .for_each(|signature| buffer.extend(&signature[..])),
+            _ => {
+                self.rct_signatures.write_base(&mut buffer);
+                self.rct_signatures.write_prunable(&mut buffer);
+            }
+        };
+        Ok(buffer)
     }
 
+    /// Returns the transaction id, which is the hash of the prefix, base and prunable hashes
+    /// of a version 2 transaction, or the hash of a version 1 transaction.
     fn to_transaction_id(&self) -> Result<Self::TransactionId, TransactionError> {
-        unimplemented!();
+        if self.prefix.version == 1 {
+            return Ok(MoneroTransactionId {
+                tx_hash: keccak256(&self.to_transaction_bytes()?),
+            });
+        }
+        if !self.is_signed() {
+            return Err(TransactionError::Message("the transaction is not signed".into()));
+        }
+
+        let mut base = vec![];
+        self.rct_signatures.write_base(&mut base);
+
+        // Coinbase transactions have no prunable part, which is hashed as zero.
+        let prunable_hash = match self.rct_signatures.rct_type {
+            RCT_TYPE_NULL => [0u8; 32],
+            _ => {
+                let mut prunable = vec![];
+                self.rct_signatures.write_prunable(&mut prunable);
+                keccak256(&prunable)
+            }
+        };
+
+        let mut hashes = self.prefix.to_hash().to_vec();
+        hashes.extend(&keccak256(&base));
+        hashes.extend(&prunable_hash);
+
+        Ok(MoneroTransactionId {
+            tx_hash: keccak256(&hashes),
+        })
     }
 }
 
-/// External C methods from mymonero-core-cpp library
-#[cfg(not(target_os = "linux"))]
-extern "C" {
-    fn extern_send_step1(arg_arr: *const c_char) -> *const c_char;
+impl<N: MoneroNetwork> MoneroTransaction<N> {
+    /// Returns an unsigned transaction given the transaction parameters and a source of randomness.
+    ///
+    /// The rings consist of the spent outputs and exactly `RING_SIZE - 1` decoys each, which are not sampled here.
+    /// Callers select the decoys with the gamma distribution over the output ages of the Monero wallet,
+    /// as decoys drawn uniformly would be told apart from the real spends. The outputs are shuffled.
+    pub fn new_with_rng<R: Rng>(
+        parameters: &MoneroTransactionParameters<N>,
+        rng: &mut R,
+    ) -> Result<Self, TransactionError> {
+        if parameters.using_outs.is_empty() {
+            return Err(TransactionError::InvalidInputs("unspent outputs".into()));
+        }
+        if parameters.mix_outs.len() != parameters.using_outs.len() {
+            return Err(TransactionError::InvalidInputs("decoys for each unspent output".into()));
+        }
+        if parameters.destinations.is_empty() {
+            return Err(TransactionError::MissingOutputParameters);
+        }
 
-    fn extern_send_step2(arg_arr: *const c_char) -> *const c_char;
-}
+        let input_amount = parameters
+            .using_outs
+            .iter()
+            .try_fold(0u64, |sum, output| sum.checked_add(output.amount));
+        let output_amount = parameters
+            .destinations
+            .iter()
+            .try_fold(parameters.fee, |sum, (_, amount)| sum.checked_add(*amount));
+        let change = match (input_amount, output_amount) {
+            (Some(input), Some(output)) if input >= output => input - output,
+            _ => {
+                return Err(TransactionError::Message(
+                    "insufficient funds for the outputs and fee".into(),
+                ))
+            }
+        };
 
-#[derive(Serialize, Deserialize)]
-pub struct UnspentOutput {
-    amount: u64,
-    index: u64,
-    global_index: u64,
-    public_key: String,
-    rct: Option<String>,
-    tx_pub_key: String,
-}
+        // Pay the change (possibly zero) to keep the consensus minimum of two outputs.
+        let mut destinations = parameters.destinations.clone();
+        if change > 0 || destinations.len() < MIN_OUTPUTS {
+            destinations.push((parameters.change_address.clone(), change));
+        }
+        if destinations.len() > MAX_OUTPUTS {
+            return Err(TransactionError::Message(format!(
+                "too many outputs: {}",
+                destinations.len()
+            )));
+        }
+        destinations.shuffle(rng);
 
-#[derive(Serialize, Deserialize)]
-pub struct MixAmountAndOuts {
-    amount: u64,
-    outputs: Vec<MixOut>,
-}
+        let (outputs, ecdh_info, masks, tx_key, additional_tx_keys, extra) =
+            Self::construct_outputs(&destinations, rng)?;
 
-#[derive(Serialize, Deserialize)]
-pub struct MixOut {
-    global_index: u64,
-    public_key: String,
-    rct: Option<String>,
-}
+        let amounts: Vec<u64> = destinations.iter().map(|(_, amount)| *amount).collect();
+        let bulletproof_plus = BulletproofPlus::prove(&amounts, &masks, rng)?;
 
-#[derive(Serialize, Deserialize)]
-pub struct TransactionParameters {
-    change_amount: u64,
-    final_total_wo_fee: u64,
-    mixin: u32,
-    using_fee: u64,
-    using_outs: Vec<UnspentOutput>,
-}
+        // The pseudo output masks sum to the output masks so that the commitments balance.
+        let mut spends = vec![];
+        let mut inputs = vec![];
+        let mut pseudo_outs = vec![];
+        let mut mask_sum = masks.iter().sum::<Scalar>();
+        for (i, (output, decoys)) in parameters.using_outs.iter().zip(parameters.mix_outs.iter()).enumerate() {
+            let (ring, global_indices, real_index) = Self::select_ring(output, decoys)?;
 
-#[derive(Serialize, Deserialize)]
-struct PrepareTransaction {
-    is_sweeping: String,
-    fee_mask: String,
-    fee_per_b: String,
-    fork_version: String,
-    sending_amount: String,
-    priority: String,
-    unspent_outs: Vec<UnspentOutput>,
-
-    #[serde(skip_serializing_if = "String::is_empty")]
-    payment_id_string: String,
-
-    #[serde(skip_serializing_if = "String::is_empty")]
-    passedIn_attemptAt_fee: String,
-}
+            let pseudo_mask = match i == parameters.using_outs.len() - 1 {
+                true => mask_sum,
+                false => random_scalar(rng),
+            };
+            mask_sum -= pseudo_mask;
+
+            inputs.push(MoneroTransactionInput::ToKey {
+                amount: match output.rct {
+                    Some(_) => 0,
+                    None => output.amount,
+                },
+                key_offsets: Self::to_key_offsets(&global_indices),
+                key_image: [0u8; 32],
+            });
+            pseudo_outs.push(commit(&pseudo_mask, output.amount));
+            spends.push(MoneroSpend {
+                output: output.clone(),
+                ring,
+                real_index,
+                pseudo_mask,
+            });
+        }
+
+        Ok(Self {
+            prefix: MoneroTransactionPrefix {
+                version: TRANSACTION_VERSION,
+                unlock_time: parameters.unlock_time,
+                inputs,
+                outputs,
+                extra,
+            },
+            ring_signatures: vec![],
+            rct_signatures: RctSignatures {
+                rct_type: RCT_TYPE_BULLETPROOF_PLUS,
+                fee: parameters.fee,
+                ecdh_info,
+                out_pk: bulletproof_plus.commitments(),
+                bulletproofs: vec![],
+                bulletproofs_plus: vec![bulletproof_plus],
+                mlsags: vec![],
+                clsags: vec![],
+                pseudo_outs,
+            },
+            tx_key: Some(tx_key),
+            additional_tx_keys,
+            spends,
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns a signed transaction given the private key of the sender and a source of randomness.
+    pub fn sign_with_rng<R: Rng>(
+        &self,
+        private_key: &MoneroPrivateKey<N>,
+        rng: &mut R,
+    ) -> Result<Self, TransactionError> {
+        if self.is_signed() {
+            return Err(TransactionError::Message("the transaction is already signed".into()));
+        }
+
+        let private_view_key = private_key.to_private_view_key();
+        let mut signers = vec![];
+        for spend in &self.spends {
+            let public_key = decode_key(&spend.output.public_key)?;
+            let tx_pub_key = decode_key(&spend.output.tx_pub_key)?;
+            let one_time_key = OneTimeKey::<N>::from(&public_key, &tx_pub_key);
+            let secret_key = Scalar::from_bytes_mod_order(one_time_key.to_private(private_key, spend.output.index)?);
+
+            let member = &spend.ring[spend.real_index];
+            if &secret_key * &ED25519_BASEPOINT_TABLE != member.key {
+                return Err(TransactionError::Message(format!(
+                    "the private key does not own output {}",
+                    spend.output.public_key
+                )));
+            }
+
+            let mask = Self::output_mask(&spend.output, &tx_pub_key, &private_view_key, &member.commitment)?;
+            signers.push((key_image(&secret_key), secret_key, mask - spend.pseudo_mask));
+        }
 
-impl Default for PrepareTransaction {
-    fn default() -> Self {
-        Self {
-            is_sweeping: String::new(),
-            fee_mask: String::new(),
-            fee_per_b: String::new(),
-            fork_version: String::new(),
-            sending_amount: String::new(),
-            priority: String::new(),
-            unspent_outs: Vec::<UnspentOutput>::new(),
-            payment_id_string: String::new(),
-            passedIn_attemptAt_fee: String::new(),
+        // Inputs are ordered by their key images in descending byte order.
+        let mut order: Vec<usize> = (0..signers.len()).collect();
+        order.sort_by(|a, b| {
+            let (a, b) = (signers[*a].0.compress(), signers[*b].0.compress());
+            b.as_bytes().cmp(a.as_bytes())
+        });
+
+        let mut transaction = self.clone();
+        transaction.spends = order.iter().map(|i| self.spends[*i].clone()).collect();
+        transaction.rct_signatures.pseudo_outs = order.iter().map(|i| self.rct_signatures.pseudo_outs[*i]).collect();
+        transaction.prefix.inputs = order
+            .iter()
+            .map(|i| match &self.prefix.inputs[*i] {
+                MoneroTransactionInput::ToKey {
+                    amount, key_offsets, ..
+                } => MoneroTransactionInput::ToKey {
+                    amount: *amount,
+                    key_offsets: key_offsets.clone(),
+                    key_image: signers[*i].0.compress().to_bytes(),
+                },
+                input => input.clone(),
+            })
+            .collect();
+
+        let message = transaction.to_signature_message();
+        let mut clsags = vec![];
+        for (i, spend) in order.iter().zip(transaction.spends.iter()) {
+            let (_, secret_key, mask_difference) = &signers[*i];
+            let pseudo_out = &self.rct_signatures.pseudo_outs[*i];
+            let (clsag, _) = Clsag::sign(
+                &message,
+                &spend.ring,
+                spend.real_index,
+                secret_key,
+                mask_difference,
+                pseudo_out,
+                rng,
+            )?;
+            clsags.push(clsag);
         }
+        transaction.rct_signatures.clsags = clsags;
+
+        Ok(transaction)
     }
-}
 
-#[derive(Serialize, Deserialize)]
-struct CreateTransaction {
-    change_amount: String,
-    fee_amount: String,
-    fee_mask: String,
-    fee_per_b: String,
-    final_total_wo_fee: String,
-    fork_version: String,
-    from_address_string: String,
-    mix_outs: Vec<MixAmountAndOuts>,
-    nettype_string: String,
-    priority: String,
-    sec_spendKey_string: String,
-    sec_viewKey_string: String,
-    to_address_string: String,
-    unlock_time: String,
-    using_outs: Vec<UnspentOutput>,
-
-    #[serde(skip_serializing_if = "String::is_empty")]
-    payment_id_string: String,
-}
+    /// Verifies the balance, range proofs and ring signatures of a signed transaction given the ring of each input.
+    pub fn verify(&self, rings: &[Vec<RingMember>]) -> Result<(), TransactionError> {
+        let signatures = &self.rct_signatures;
+        if signatures.rct_type != RCT_TYPE_BULLETPROOF_PLUS {
+            return Err(TransactionError::Message(format!(
+                "unsupported RingCT type {}",
+                signatures.rct_type
+            )));
+        }
+        if !self.is_signed() || rings.len() != self.prefix.inputs.len() {
+            return Err(TransactionError::Message(
+                "the transaction is not signed for its rings".into(),
+            ));
+        }
 
-impl Default for CreateTransaction {
-    fn default() -> Self {
-        Self {
-            change_amount: String::new(),
-            fee_amount: String::new(),
-            fee_mask: String::new(),
-            fee_per_b: String::new(),
-            final_total_wo_fee: String::new(),
-            fork_version: String::new(),
-            from_address_string: String::new(),
-            mix_outs: Vec::<MixAmountAndOuts>::new(),
-            nettype_string: String::new(),
-            priority: String::new(),
-            sec_spendKey_string: String::new(),
-            sec_viewKey_string: String::new(),
-            to_address_string: String::new(),
-            unlock_time: String::new(),
-            using_outs: Vec::<UnspentOutput>::new(),
-            payment_id_string: String::new(),
+        let inputs: EdwardsPoint = signatures.pseudo_outs.iter().sum();
+        let outputs: EdwardsPoint = signatures.out_pk.iter().sum();
+        if inputs != outputs + Scalar::from(signatures.fee) * *H {
+            return Err(TransactionError::Message("the commitments do not balance".into()));
         }
+
+        signatures
+            .bulletproofs_plus
+            .iter()
+            .try_for_each(|proof| proof.verify())?;
+
+        let message = self.to_signature_message();
+        for (((input, clsag), ring), pseudo_out) in self
+            .prefix
+            .inputs
+            .iter()
+            .zip(signatures.clsags.iter())
+            .zip(rings.iter())
+            .zip(signatures.pseudo_outs.iter())
+        {
+            let key_image = match input {
+                MoneroTransactionInput::ToKey { key_image, .. } => read_point(key_image)?,
+                MoneroTransactionInput::Generation { .. } => return Err(TransactionError::InvalidSpendDescription),
+            };
+            clsag.verify(&message, ring, &key_image, pseudo_out)?;
+        }
+        Ok(())
     }
-}
 
-#[cfg(not(target_os = "linux"))]
-impl<N: MoneroNetwork> MoneroTransaction<N> {
-    /// Returns Monero transaction cost details, required mixin, and unspent outputs that will be used
-    /// calls https://github.com/mymonero/mymonero-core-cpp/blob/20b6cbabf230ae4ebe01d05c859aad397741cf8f/src/serial_bridge_index.cpp#L445
-    pub fn prepare_transaction(
-        is_sweeping: bool,
-        fee_mask: u64,
-        fee_per_b: u64,
-        fork_version: u8,
-        sending_amount: u64,
-        passed_in_attempt_at_fee: String,
-        payment_id_string: String,
-        priority: u32,
-        unspent_outs: Vec<UnspentOutput>,
-    ) -> Result<TransactionParameters, TransactionError> {
-        let args_value = PrepareTransaction {
-            is_sweeping: is_sweeping.to_string(),
-            fee_mask: fee_mask.to_string(),
-            fee_per_b: fee_per_b.to_string(),
-            fork_version: fork_version.to_string(),
-            sending_amount: sending_amount.to_string(),
-            priority: priority.to_string(),
-            unspent_outs,
-            passedIn_attemptAt_fee: passed_in_attempt_at_fee.to_string(),
-            payment_id_string: payment_id_string.to_string(),
-        };
+    /// Returns the transaction private key, or `None` for a parsed transaction.
+    pub fn to_tx_key(&self) -> Option<[u8; 32]> {
+        self.tx_key.map(|key| key.to_bytes())
+    }
+
+    /// Returns the additional transaction private keys of outputs sent to subaddresses.
+    pub fn to_additional_tx_keys(&self) -> Vec<[u8; 32]> {
+        self.additional_tx_keys.iter().map(|key| key.to_bytes()).collect()
+    }
 
-        let response = call_extern_function(&serde_json::to_string(&args_value)?, extern_send_step1);
+    /// Returns the transaction prefix.
+    pub fn prefix(&self) -> &MoneroTransactionPrefix {
+        &self.prefix
+    }
 
-        #[derive(Serialize, Deserialize)]
-        struct Step1ResultString {
-            change_amount: String,
-            final_total_wo_fee: String,
-            mixin: String,
-            using_fee: String,
-            using_outs: Vec<UnspentOutputString>,
+    /// Returns the RingCT signatures.
+    pub fn rct_signatures(&self) -> &RctSignatures {
+        &self.rct_signatures
+    }
+
+    /// Returns the transaction in the JSON representation of the Monero daemon.
+    pub fn to_json(&self) -> Value {
+        let encode = |point: &EdwardsPoint| hex::encode(point.compress().as_bytes());
+
+        let vin: Vec<Value> = self
+            .prefix
+            .inputs
+            .iter()
+            .map(|input| match input {
+                MoneroTransactionInput::Generation { height } => json!({ "gen": { "height": height } }),
+                MoneroTransactionInput::ToKey {
+                    amount,
+                    key_offsets,
+                    key_image,
+                } => json!({
+                    "key": {
+                        "amount": amount,
+                        "key_offsets": key_offsets,
+                        "k_image": hex::encode(key_image),
+                    }
+                }),
+            })
+            .collect();
+        let vout: Vec<Value> = self
+            .prefix
+            .outputs
+            .iter()
+            .map(|output| match output.view_tag {
+                Some(view_tag) => json!({
+                    "amount": output.amount,
+                    "target": { "tagged_key": { "key": hex::encode(output.key), "view_tag": hex::encode([view_tag]) } },
+                }),
+                None => json!({ "amount": output.amount, "target": { "key": hex::encode(output.key) } }),
+            })
+            .collect();
+
+        let mut transaction = json!({
+            "version": self.prefix.version,
+            "unlock_time": self.prefix.unlock_time,
+            "vin": vin,
+            "vout": vout,
+            "extra": self.prefix.extra,
+        });
+
+        if self.prefix.version == 1 {
+            let signatures: Vec<String> = self
+                .ring_signatures
+                .iter()
+                .filter(|signatures| !signatures.is_empty())
+                .map(|signatures| signatures.iter().map(|signature| hex::encode(&signature[..])).collect())
+                .collect();
+            transaction["signatures"] = json!(signatures);
+            return transaction;
         }
 
-        #[derive(Serialize, Deserialize)]
-        struct UnspentOutputString {
-            amount: String,
-            index: String,
-            global_index: String,
-            public_key: String,
-            rct: Option<String>,
-            tx_pub_key: String,
+        let signatures = &self.rct_signatures;
+        if signatures.rct_type == RCT_TYPE_NULL {
+            transaction["rct_signatures"] = json!({ "type": signatures.rct_type });
+            return transaction;
         }
 
-        let result: Step1ResultString = match serde_json::from_str(&response) {
-            Ok(res) => res,
-            Err(_) => return Err(TransactionError::Message(response)),
+        transaction["rct_signatures"] = json!({
+            "type": signatures.rct_type,
+            "txnFee": signatures.fee,
+            "ecdhInfo": signatures.ecdh_info.iter().map(|amount| json!({ "amount": hex::encode(amount) })).collect::<Vec<Value>>(),
+            "outPk": signatures.out_pk.iter().map(encode).collect::<Vec<String>>(),
+        });
+        let mut prunable = json!({
+            "nbp": signatures.bulletproofs.len() + signatures.bulletproofs_plus.len(),
+            "pseudoOuts": signatures.pseudo_outs.iter().map(encode).collect::<Vec<String>>(),
+        });
+        match signatures.rct_type {
+            RCT_TYPE_BULLETPROOF_PLUS => {
+                prunable["bpp"] = json!(signatures
+                    .bulletproofs_plus
+                    .iter()
+                    .map(|proof| json!({
+                        "A": encode(&proof.A),
+                        "A1": encode(&proof.A1),
+                        "B": encode(&proof.B),
+                        "r1": hex::encode(proof.r1.as_bytes()),
+                        "s1": hex::encode(proof.s1.as_bytes()),
+                        "d1": hex::encode(proof.d1.as_bytes()),
+                        "L": proof.L.iter().map(encode).collect::<Vec<String>>(),
+                        "R": proof.R.iter().map(encode).collect::<Vec<String>>(),
+                    }))
+                    .collect::<Vec<Value>>())
+            }
+            _ => {
+                prunable["bp"] = json!(signatures
+                    .bulletproofs
+                    .iter()
+                    .map(|proof| json!({
+                        "A": encode(&proof.A),
+                        "S": encode(&proof.S),
+                        "T1": encode(&proof.T1),
+                        "T2": encode(&proof.T2),
+                        "taux": hex::encode(proof.taux.as_bytes()),
+                        "mu": hex::encode(proof.mu.as_bytes()),
+                        "L": proof.L.iter().map(encode).collect::<Vec<String>>(),
+                        "R": proof.R.iter().map(encode).collect::<Vec<String>>(),
+                        "a": hex::encode(proof.a.as_bytes()),
+                        "b": hex::encode(proof.b.as_bytes()),
+                        "t": hex::encode(proof.t.as_bytes()),
+                    }))
+                    .collect::<Vec<Value>>())
+            }
+        };
+        match signatures.rct_type {
+            RCT_TYPE_BULLETPROOF_2 => prunable["MGs"] = json!(signatures.mlsags.iter().map(|mlsag| json!({
+                "ss": mlsag.ss.iter().map(|ss| ss.iter().map(|s| hex::encode(s.as_bytes())).collect::<Vec<String>>()).collect::<Vec<Vec<String>>>(),
+                "cc": hex::encode(mlsag.cc.as_bytes()),
+            })).collect::<Vec<Value>>()),
+            _ => prunable["CLSAGs"] = json!(signatures.clsags.iter().map(|clsag| json!({
+                "s": clsag.s.iter().map(|s| hex::encode(s.as_bytes())).collect::<Vec<String>>(),
+                "c1": hex::encode(clsag.c1.as_bytes()),
+                "D": encode(&clsag.D),
+            })).collect::<Vec<Value>>()),
         };
+        transaction["rctsig_prunable"] = prunable;
+        transaction
+    }
 
-        let mut using_outs = Vec::<UnspentOutput>::new();
-        for unspent_output_string in result.using_outs {
-            using_outs.push(UnspentOutput {
-                amount: unspent_output_string.amount.parse::<u64>()?,
-                index: unspent_output_string.index.parse::<u64>()?,
-                global_index: unspent_output_string.global_index.parse::<u64>()?,
-                public_key: unspent_output_string.public_key.into(),
-                rct: unspent_output_string.rct.into(),
-                tx_pub_key: unspent_output_string.tx_pub_key.into(),
+    /// Returns a transaction given its JSON representation of the Monero daemon.
+    pub fn from_json(transaction: &Value) -> Result<Self, TransactionError> {
+        let mut buffer = vec![];
+        let version = json_u64(transaction, "version")?;
+        write_varint(&mut buffer, version);
+        write_varint(&mut buffer, json_u64(transaction, "unlock_time")?);
+
+        let vin = json_array(transaction, "vin")?;
+        let mut ring_sizes = vec![];
+        write_varint(&mut buffer, vin.len() as u64);
+        for input in vin {
+            match (input.get("gen"), input.get("key")) {
+                (Some(generation), _) => {
+                    buffer.push(TXIN_GEN);
+                    write_varint(&mut buffer, json_u64(generation, "height")?);
+                }
+                (None, Some(key)) => {
+                    let key_offsets = json_array(key, "key_offsets")?;
+                    buffer.push(TXIN_TO_KEY);
+                    write_varint(&mut buffer, json_u64(key, "amount")?);
+                    write_varint(&mut buffer, key_offsets.len() as u64);
+                    for key_offset in key_offsets {
+                        write_varint(&mut buffer, json_number(key_offset)?);
+                    }
+                    buffer.extend(json_bytes(json_field(key, "k_image")?, 32)?);
+                    ring_sizes.push(key_offsets.len());
+                }
+                _ => return Err(TransactionError::Message(format!("unsupported input {}", input))),
+            };
+        }
+
+        let vout = json_array(transaction, "vout")?;
+        write_varint(&mut buffer, vout.len() as u64);
+        for output in vout {
+            write_varint(&mut buffer, json_u64(output, "amount")?);
+            let target = json_field(output, "target")?;
+            match target.get("tagged_key") {
+                Some(tagged_key) => {
+                    buffer.push(TXOUT_TO_TAGGED_KEY);
+                    buffer.extend(json_bytes(json_field(tagged_key, "key")?, 32)?);
+                    buffer.extend(json_bytes(json_field(tagged_key, "view_tag")?, 1)?);
+                }
+                None => {
+                    buffer.push(TXOUT_TO_KEY);
+                    buffer.extend(json_bytes(json_field(target, "key")?, 32)?);
+                }
+            };
+        }
+
+        let extra = json_array(transaction, "extra")?
+            .iter()
+            .map(|byte| match byte.as_u64() {
+                Some(byte) if byte <= 0xff => Ok(byte as u8),
+                _ => Err(TransactionError::Message(format!("invalid extra byte {}", byte))),
             })
+            .collect::<Result<Vec<u8>, TransactionError>>()?;
+        write_varint(&mut buffer, extra.len() as u64);
+        buffer.extend(extra);
+
+        if version == 1 {
+            let signatures = json_array(transaction, "signatures")?;
+            if signatures.len() != ring_sizes.len() {
+                return Err(TransactionError::Message(format!(
+                    "expected ring signatures for {} inputs, found {}",
+                    ring_sizes.len(),
+                    signatures.len()
+                )));
+            }
+            for (signature, ring_size) in signatures.iter().zip(ring_sizes) {
+                buffer.extend(json_bytes(signature, 64 * ring_size)?);
+            }
+            return Self::from_transaction_bytes(&buffer);
         }
 
-        Ok(TransactionParameters {
-            change_amount: result.change_amount.parse::<u64>()?,
-            final_total_wo_fee: result.final_total_wo_fee.parse::<u64>()?,
-            mixin: result.mixin.parse::<u32>()?,
-            using_fee: result.using_fee.parse::<u64>()?,
-            using_outs,
-        })
+        let signatures = json_field(transaction, "rct_signatures")?;
+        let rct_type = json_u64(signatures, "type")?;
+        buffer.push(rct_type as u8);
+        if let RCT_TYPE_BULLETPROOF_2 | RCT_TYPE_CLSAG | RCT_TYPE_BULLETPROOF_PLUS = rct_type as u8 {
+            write_varint(&mut buffer, json_u64(signatures, "txnFee")?);
+            for amount in json_array(signatures, "ecdhInfo")? {
+                buffer.extend(json_bytes(json_field(amount, "amount")?, 8)?);
+            }
+            for commitment in json_array(signatures, "outPk")? {
+                buffer.extend(json_bytes(commitment, 32)?);
+            }
+
+            // Bulletproofs+ end with the inner product rounds, and Bulletproofs with their final arguments.
+            let prunable = json_field(transaction, "rctsig_prunable")?;
+            let (proofs, head, tail) = match rct_type as u8 {
+                RCT_TYPE_BULLETPROOF_PLUS => (
+                    json_array(prunable, "bpp")?,
+                    &["A", "A1", "B", "r1", "s1", "d1"][..],
+                    &[][..],
+                ),
+                _ => (
+                    json_array(prunable, "bp")?,
+                    &["A", "S", "T1", "T2", "taux", "mu"][..],
+                    &["a", "b", "t"][..],
+                ),
+            };
+            write_varint(&mut buffer, proofs.len() as u64);
+            for proof in proofs {
+                for name in head.iter() {
+                    buffer.extend(json_bytes(json_field(proof, name)?, 32)?);
+                }
+                for name in ["L", "R"].iter() {
+                    let points = json_array(proof, name)?;
+                    write_varint(&mut buffer, points.len() as u64);
+                    for point in points {
+                        buffer.extend(json_bytes(point, 32)?);
+                    }
+                }
+                for name in tail.iter() {
+                    buffer.extend(json_bytes(json_field(proof, name)?, 32)?);
+                }
+            }
+            match rct_type as u8 {
+                RCT_TYPE_BULLETPROOF_2 => {
+                    for mlsag in json_array(prunable, "MGs")? {
+                        for ss in json_array(mlsag, "ss")? {
+                            for s in ss
+                                .as_array()
+                                .ok_or_else(|| TransactionError::Message(format!("invalid ss {}", ss)))?
+                            {
+                                buffer.extend(json_bytes(s, 32)?);
+                            }
+                        }
+                        buffer.extend(json_bytes(json_field(mlsag, "cc")?, 32)?);
+                    }
+                }
+                _ => {
+                    for clsag in json_array(prunable, "CLSAGs")? {
+                        for s in json_array(clsag, "s")? {
+                            buffer.extend(json_bytes(s, 32)?);
+                        }
+                        buffer.extend(json_bytes(json_field(clsag, "c1")?, 32)?);
+                        buffer.extend(json_bytes(json_field(clsag, "D")?, 32)?);
+                    }
+                }
+            };
+            for pseudo_out in json_array(prunable, "pseudoOuts")? {
+                buffer.extend(json_bytes(pseudo_out, 32)?);
+            }
+        }
+        Self::from_transaction_bytes(&buffer)
     }
 
-    /// Returns Monero raw transaction, transaction hash (transaction id), transaction public key
-    /// calls https://github.com/mymonero/mymonero-core-cpp/blob/20b6cbabf230ae4ebe01d05c859aad397741cf8f/src/serial_bridge_index.cpp#L529
-    pub fn create_transaction(
-        change_amount: u64,
-        fee_amount: u64,
-        fee_mask: u64,
-        fee_per_b: u64,
-        final_total_wo_fee: u64,
-        fork_version: u8,
-        from_address_string: String,
-        mix_outs: Vec<MixAmountAndOuts>,
-        nettype_string: String,
-        payment_id_string: String,
-        priority: u32,
-        sec_spend_key_string: String,
-        sec_view_key_string: String,
-        to_address_string: String,
-        unlock_time: u64,
-        using_outs: Vec<UnspentOutput>,
-    ) -> Result<Self, TransactionError> {
-        let args_value = CreateTransaction {
-            change_amount: change_amount.to_string(),
-            fee_amount: fee_amount.to_string(),
-            fee_mask: fee_mask.to_string(),
-            fee_per_b: fee_per_b.to_string(),
-            fork_version: fork_version.to_string(),
-            final_total_wo_fee: final_total_wo_fee.to_string(),
-            from_address_string,
-            mix_outs,
-            nettype_string,
-            payment_id_string: payment_id_string.to_string(),
-            priority: priority.to_string(),
-            sec_spendKey_string: sec_spend_key_string.to_string(),
-            sec_viewKey_string: sec_view_key_string.to_string(),
-            to_address_string,
-            unlock_time: unlock_time.to_string(),
-            using_outs,
-        };
+    /// Returns true if every input has a ring signature.
+    fn is_signed(&self) -> bool {
+        match self.prefix.version {
+            1 => self.ring_signatures.len() == self.prefix.inputs.len(),
+            _ => {
+                let spends = self.prefix.inputs.iter().filter(|input| input.ring_size() > 0);
+                self.rct_signatures.mlsags.len() + self.rct_signatures.clsags.len() == spends.count()
+            }
+        }
+    }
 
-        let response = call_extern_function(&serde_json::to_string(&args_value)?, extern_send_step2);
+    /// Returns the message signed by the ring signatures, which commits to the prefix, the base and the range proofs.
+    fn to_signature_message(&self) -> [u8; 32] {
+        let mut base = vec![];
+        self.rct_signatures.write_base(&mut base);
+
+        let mut range_proofs = vec![];
+        for proof in &self.rct_signatures.bulletproofs_plus {
+            [proof.A, proof.A1, proof.B]
+                .iter()
+                .for_each(|point| range_proofs.extend(point.compress().as_bytes()));
+            [proof.r1, proof.s1, proof.d1]
+                .iter()
+                .for_each(|scalar| range_proofs.extend(scalar.as_bytes()));
+            proof
+                .L
+                .iter()
+                .chain(proof.R.iter())
+                .for_each(|point| range_proofs.extend(point.compress().as_bytes()));
+        }
+
+        let mut message = self.prefix.to_hash().to_vec();
+        message.extend(&keccak256(&base));
+        message.extend(&keccak256(&range_proofs));
+        keccak256(&message)
+    }
 
-        #[derive(Serialize, Deserialize)]
-        struct Step2Result {
-            tx_must_be_reconstructed: String,
-            serialized_signed_tx: String,
-            tx_hash: String,
-            tx_key: String,
-            tx_pub_key: String,
+    /// Returns the one time keys, encrypted amounts, masks, transaction keys and extra field of the outputs.
+    fn construct_outputs<R: Rng>(
+        destinations: &[(MoneroAddress<N>, u64)],
+        rng: &mut R,
+    ) -> Result<
+        (
+            Vec<MoneroTransactionOutput>,
+            Vec<[u8; 8]>,
+            Vec<Scalar>,
+            Scalar,
+            Vec<Scalar>,
+            Vec<u8>,
+        ),
+        TransactionError,
+    > {
+        // A transaction carries at most one payment id, encrypted for the integrated address that requests it.
+        let mut recipients = vec![];
+        let mut payment_id = None;
+        for (address, _) in destinations {
+            let public_key = address.to_public_key()?;
+            let (spend_key, view_key) = match (public_key.to_public_spend_key(), public_key.to_public_view_key()) {
+                (Some(spend_key), Some(view_key)) => (spend_key, view_key),
+                _ => return Err(TransactionError::InvalidOutputAddress(address.to_string())),
+            };
+            let is_subaddress = match address.format()? {
+                MoneroFormat::Standard => false,
+                MoneroFormat::Subaddress(_, _) => true,
+                MoneroFormat::Integrated(id) => {
+                    match payment_id {
+                        Some((other_id, _)) if other_id != id => {
+                            return Err(TransactionError::Message(
+                                "destinations with more than one payment id".into(),
+                            ))
+                        }
+                        _ => payment_id = Some((id, view_key)),
+                    };
+                    false
+                }
+            };
+            recipients.push((spend_key, view_key, is_subaddress));
         }
 
-        let result: Step2Result = match serde_json::from_str(&response) {
-            Ok(res) => res,
-            Err(_) => return Err(TransactionError::Message(response)),
+        // Subaddress outputs need their own transaction keys unless the only recipient is one subaddress.
+        let mut unique = recipients.clone();
+        unique.sort();
+        unique.dedup();
+        let subaddresses = unique.iter().filter(|(_, _, is_subaddress)| *is_subaddress).count();
+        let use_additional_keys = subaddresses > 0 && unique.len() > 1;
+
+        let tx_key = random_scalar(rng);
+        let tx_public_key = match (subaddresses, unique.len()) {
+            (1, 1) => tx_key * read_point(&unique[0].0)?,
+            _ => &tx_key * &ED25519_BASEPOINT_TABLE,
         };
 
-        Ok(Self {
-            tx_must_be_reconstructed: result.tx_must_be_reconstructed.parse::<bool>()?,
-            serialized_signed_tx: result.serialized_signed_tx.into(),
-            tx_hash: MoneroTransactionId {
-                tx_hash: result.tx_hash.into(),
+        let mut outputs = vec![];
+        let mut ecdh_info = vec![];
+        let mut masks = vec![];
+        let mut additional_tx_keys = vec![];
+        let mut additional_public_keys = vec![];
+        for (i, ((spend_key, view_key, is_subaddress), (_, amount))) in
+            recipients.iter().zip(destinations.iter()).enumerate()
+        {
+            let key = match use_additional_keys {
+                true => {
+                    let additional_key = random_scalar(rng);
+                    additional_public_keys.push(match is_subaddress {
+                        true => additional_key * read_point(spend_key)?,
+                        false => &additional_key * &ED25519_BASEPOINT_TABLE,
+                    });
+                    additional_tx_keys.push(additional_key);
+                    additional_key
+                }
+                false => tx_key,
+            };
+
+            let mut derivation = vec![];
+            OneTimeKey::<N>::generate_key_derivation(view_key, &key.to_bytes(), &mut derivation)?;
+            let shared_secret = OneTimeKey::<N>::derivation_to_scalar(&derivation, i as u64);
+
+            let one_time_key = &shared_secret * &ED25519_BASEPOINT_TABLE + read_point(spend_key)?;
+            outputs.push(MoneroTransactionOutput {
+                amount: 0,
+                key: one_time_key.compress().to_bytes(),
+                view_tag: Some(OneTimeKey::<N>::derive_view_tag(&derivation, i as u64)),
+            });
+            ecdh_info.push(encode_amount(*amount, &shared_secret));
+            masks.push(commitment_mask(&shared_secret));
+        }
+
+        let mut extra = MoneroTransactionExtra::default();
+        extra
+            .fields
+            .push(MoneroExtraField::PublicKey(tx_public_key.compress().to_bytes()));
+        if use_additional_keys {
+            extra.fields.push(MoneroExtraField::AdditionalPublicKeys(
+                additional_public_keys
+                    .iter()
+                    .map(|key| key.compress().to_bytes())
+                    .collect(),
+            ));
+        }
+        if let Some((payment_id, view_key)) = payment_id {
+            let encrypted_payment_id = encrypt_payment_id(&payment_id, &view_key, &tx_key.to_bytes())?;
+            extra
+                .fields
+                .push(MoneroExtraField::Nonce(MoneroExtraNonce::EncryptedPaymentId(
+                    encrypted_payment_id,
+                )));
+        }
+
+        Ok((outputs, ecdh_info, masks, tx_key, additional_tx_keys, extra.to_bytes()?))
+    }
+
+    /// Returns the ring of the output with the given decoys, its global indices and the real index.
+    fn select_ring(
+        output: &UnspentOutput,
+        mix_outs: &MixAmountAndOuts,
+    ) -> Result<(Vec<RingMember>, Vec<u64>, usize), TransactionError> {
+        let mut decoys = mix_outs.outputs.clone();
+        decoys.retain(|decoy| decoy.global_index != output.global_index);
+        decoys.sort_by_key(|decoy| decoy.global_index);
+        decoys.dedup_by_key(|decoy| decoy.global_index);
+        if decoys.len() != RING_SIZE - 1 {
+            return Err(TransactionError::InvalidInputs(format!(
+                "{} decoys for output {}, found {}",
+                RING_SIZE - 1,
+                output.public_key,
+                decoys.len()
+            )));
+        }
+
+        let mut members = vec![(
+            output.global_index,
+            RingMember {
+                key: read_point(&decode_key(&output.public_key)?)?,
+                commitment: decode_commitment(&output.rct, output.amount)?,
             },
-            tx_key: result.tx_key.into(),
-            tx_pub_key: result.tx_pub_key.into(),
-            _network: PhantomData,
-        })
+        )];
+        for decoy in &decoys {
+            members.push((
+                decoy.global_index,
+                RingMember {
+                    key: read_point(&decode_key(&decoy.public_key)?)?,
+                    commitment: decode_commitment(&decoy.rct, mix_outs.amount)?,
+                },
+            ));
+        }
+        members.sort_by_key(|(global_index, _)| *global_index);
+
+        let real_index = members
+            .iter()
+            .position(|(global_index, _)| *global_index == output.global_index)
+            .unwrap_or_default();
+        let global_indices = members.iter().map(|(global_index, _)| *global_index).collect();
+        let ring = members.into_iter().map(|(_, member)| member).collect();
+        Ok((ring, global_indices, real_index))
+    }
+
+    /// Returns the mask of an owned output, checking that it opens the commitment to the amount.
+    fn output_mask(
+        output: &UnspentOutput,
+        tx_pub_key: &[u8; 32],
+        private_view_key: &[u8; 32],
+        commitment: &EdwardsPoint,
+    ) -> Result<Scalar, TransactionError> {
+        let rct = match &output.rct {
+            Some(rct) => hex::decode(rct)?,
+            None => return Ok(Scalar::one()),
+        };
+
+        let mut derivation = vec![];
+        OneTimeKey::<N>::generate_key_derivation(tx_pub_key, private_view_key, &mut derivation)?;
+        let shared_secret = OneTimeKey::<N>::derivation_to_scalar(&derivation, output.index);
+
+        // The commitment is followed by either nothing, an 8-byte amount or a 32-byte mask and amount.
+        let mask = match rct.len() {
+            32 | 40 => commitment_mask(&shared_secret),
+            96 => {
+                let mut encrypted_mask = [0u8; 32];
+                let mut encrypted_amount = [0u8; 32];
+                encrypted_mask.copy_from_slice(&rct[32..64]);
+                encrypted_amount.copy_from_slice(&rct[64..96]);
+                decode_amount_v1(&encrypted_mask, &encrypted_amount, &shared_secret).0
+            }
+            length => return Err(TransactionError::Message(format!("invalid rct length {}", length))),
+        };
+
+        // Coinbase outputs are committed with a mask of one.
+        match [mask, Scalar::one()]
+            .iter()
+            .find(|mask| commit(mask, output.amount) == *commitment)
+        {
+            Some(mask) => Ok(*mask),
+            None => Err(TransactionError::Message(format!(
+                "the commitment of output {} does not open to {}",
+                output.public_key, output.amount
+            ))),
+        }
+    }
+
+    /// Returns the global indices of a ring, each relative to the previous one.
+    fn to_key_offsets(global_indices: &[u64]) -> Vec<u64> {
+        let mut previous = 0;
+        global_indices
+            .iter()
+            .map(|global_index| {
+                let offset = global_index - previous;
+                previous = *global_index;
+                offset
+            })
+            .collect()
+    }
+}
+
+/// Returns the Merkle root of the transaction hashes of a block, as computed by `tree_hash` in Monero.
+pub fn tree_hash(hashes: &[[u8; 32]]) -> Option<[u8; 32]> {
+    let hash_pair = |left: &[u8; 32], right: &[u8; 32]| {
+        let mut pair = left.to_vec();
+        pair.extend(right);
+        keccak256(&pair)
+    };
+
+    match hashes.len() {
+        0 => None,
+        1 => Some(hashes[0]),
+        2 => Some(hash_pair(&hashes[0], &hashes[1])),
+        count => {
+            // Pair the trailing hashes so that a power of two remain, then halve until the root.
+            let mut width = count.next_power_of_two() >> 1;
+            let unpaired = 2 * width - count;
+            let mut layer = hashes[..unpaired].to_vec();
+            layer.extend(hashes[unpaired..].chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])));
+
+            while width > 1 {
+                width >>= 1;
+                layer = layer.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+            }
+            Some(layer[0])
+        }
     }
 }
 
-/// Make an unsafe external call to a C function
-/// the C function should take a character array argument and return a character array
-#[cfg(not(target_os = "linux"))]
-pub fn call_extern_function(arg_str: &str, function: unsafe extern "C" fn(*const c_char) -> *const c_char) -> String {
-    // 1. create C string (ends with the zero byte and can't contain one inside)
-    let str_arr: CString = CString::new(arg_str).unwrap();
+/// Returns the variable-length integer at the offset and advances the offset past it.
+fn take_varint(bytes: &[u8], offset: &mut usize) -> Result<u64, TransactionError> {
+    let (value, length) = read_varint(bytes.get(*offset..).unwrap_or_default())?;
+    *offset += length;
+    Ok(value)
+}
+
+/// Returns the field of a JSON object.
+fn json_field<'a>(value: &'a Value, name: &str) -> Result<&'a Value, TransactionError> {
+    value
+        .get(name)
+        .ok_or_else(|| TransactionError::Message(format!("missing field {}", name)))
+}
 
-    // 2. make unsafe call to C method (extern "C" method in C++)
-    let c_buf: *const c_char = unsafe { function(str_arr.as_ptr()) };
+/// Returns the array field of a JSON object.
+fn json_array<'a>(value: &'a Value, name: &str) -> Result<&'a Vec<Value>, TransactionError> {
+    json_field(value, name)?
+        .as_array()
+        .ok_or_else(|| TransactionError::Message(format!("invalid field {}", name)))
+}
 
-    // 3. make unsafe conversion from pointer to c character array into pointer to rust CStr (i8) type
-    // this method is unsafe because from_ptr returns a CStr with an arbitrary lifetime parameter
-    let c_str: &CStr = unsafe { CStr::from_ptr(c_buf) };
+/// Returns the unsigned integer field of a JSON object.
+fn json_u64(value: &Value, name: &str) -> Result<u64, TransactionError> {
+    json_number(json_field(value, name)?)
+}
+
+/// Returns the unsigned integer of a JSON value.
+fn json_number(value: &Value) -> Result<u64, TransactionError> {
+    value
+        .as_u64()
+        .ok_or_else(|| TransactionError::Message(format!("invalid number {}", value)))
+}
+
+/// Returns the bytes of a JSON hex string of the given length.
+fn json_bytes(value: &Value, length: usize) -> Result<Vec<u8>, TransactionError> {
+    let bytes = hex::decode(value.as_str().unwrap_or_default())?;
+    if bytes.len() != length {
+        return Err(TransactionError::Message(format!(
+            "expected {} bytes, found {}",
+            length,
+            bytes.len()
+        )));
+    }
+    Ok(bytes)
+}
 
-    // 4. convert to rust string slice
-    let str_slice: &str = c_str.to_str().unwrap();
+/// Returns the 32-byte key encoded in hex.
+fn decode_key(key: &str) -> Result<[u8; 32], TransactionError> {
+    let bytes = hex::decode(key)?;
+    if bytes.len() != 32 {
+        return Err(TransactionError::Message(format!("invalid key length {}", bytes.len())));
+    }
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&bytes);
+    Ok(key)
+}
 
-    str_slice.into()
+/// Returns the commitment at the start of the rct field, or the commitment of a pre-RingCT output.
+fn decode_commitment(rct: &Option<String>, amount: u64) -> Result<EdwardsPoint, TransactionError> {
+    match rct {
+        Some(rct) => {
+            let bytes = hex::decode(rct)?;
+            Ok(read_point(&bytes)?)
+        }
+        None => Ok(zero_commit(amount)),
+    }
 }
 
 #[cfg(test)]
-#[cfg(not(target_os = "linux"))]
 mod tests {
     use super::*;
+    use crate::{Mainnet, Stagenet};
+    use std::str::FromStr;
+    use wagyu_model::PrivateKey;
 
     pub struct TransactionTestCase {
-        pub is_sweeping: bool,
-        pub fee_mask: u64,
-        pub fee_per_b: u64,
-        pub fork_version: u8,
-        pub passed_in_attempt_at_fee: &'static str,
-        pub payment_id_string: &'static str,
-        pub sending_amount: u64,
-        pub priority: u32,
-        pub unspent_outs: [Output; 1],
-
-        pub mixin: u32,
-
-        pub change_amount: u64,
-        pub fee_amount: u64,
-        pub final_total_wo_fee: u64,
-        pub sec_spend_key_string: &'static str,
-        pub sec_view_key_string: &'static str,
-        pub from_address_string: &'static str,
-        pub to_address_string: &'static str,
-        pub nettype_string: &'static str,
-        pub unlock_time: u64,
-        pub using_outs: [Output; 1],
-        pub mix_outs: [MixAmountAndOutputs; 1],
-    }
-
-    #[derive(Clone)]
+        pub private_spend_key: &'static str,
+        pub private_view_key: &'static str,
+        pub from_address: &'static str,
+        pub to_address: &'static str,
+        pub amount: u64,
+        pub fee: u64,
+        pub using_out: Output,
+        pub mix_outs: [MixOutput; 10],
+    }
+
     pub struct Output {
         amount: u64,
         index: u64,
         global_index: u64,
         public_key: &'static str,
-        rct: Option<&'static str>,
+        rct: &'static str,
         tx_pub_key: &'static str,
     }
 
-    #[derive(Clone)]
-    pub struct MixAmountAndOutputs {
-        amount: u64,
-        outputs: [MixOutput; 10],
-    }
-
-    #[derive(Clone)]
     pub struct MixOutput {
         global_index: u64,
         public_key: &'static str,
-        rct: Option<&'static str>,
+        rct: &'static str,
     }
 
-    pub fn test_prepare_transaction<N: MoneroNetwork>(transaction: &TransactionTestCase) {
-        let mut unspent_outs: Vec<UnspentOutput> = Vec::new();
-
-        for output in transaction.unspent_outs.to_vec() {
-            let rct: Option<String> = match output.rct {
-                Some(rct) => Some(rct.into()),
-                None => None,
-            };
+    /// Returns the parameters of the test case, padding the recorded decoys with synthetic ones to fill a ring.
+    fn parameters<N: MoneroNetwork>(transaction: &TransactionTestCase) -> MoneroTransactionParameters<N> {
+        let output = &transaction.using_out;
+        let mut outputs: Vec<MixOut> = transaction
+            .mix_outs
+            .iter()
+            .map(|output| MixOut {
+                global_index: output.global_index,
+                public_key: output.public_key.into(),
+                rct: Some(output.rct.into()),
+            })
+            .collect();
+        for i in 0..(RING_SIZE - 1 - outputs.len()) as u64 {
+            let key = hash_to_point(&i.to_le_bytes());
+            let commitment = commit(&hash_to_scalar(&i.to_le_bytes()), 1_000_000);
+            outputs.push(MixOut {
+                global_index: output.global_index + 1 + i,
+                public_key: hex::encode(key.compress().as_bytes()),
+                rct: Some(hex::encode(commitment.compress().as_bytes())),
+            });
+        }
 
-            unspent_outs.push(UnspentOutput {
+        MoneroTransactionParameters {
+            using_outs: vec![UnspentOutput {
                 amount: output.amount,
                 index: output.index,
                 global_index: output.global_index,
                 public_key: output.public_key.into(),
-                rct,
+                rct: Some(output.rct.into()),
                 tx_pub_key: output.tx_pub_key.into(),
-            });
+            }],
+            mix_outs: vec![MixAmountAndOuts { amount: 0, outputs }],
+            destinations: vec![(
+                MoneroAddress::from_str(transaction.to_address).unwrap(),
+                transaction.amount,
+            )],
+            change_address: MoneroAddress::from_str(transaction.from_address).unwrap(),
+            fee: transaction.fee,
+            unlock_time: 0,
         }
+    }
+
+    fn sender_key<N: MoneroNetwork>(transaction: &TransactionTestCase, format: &MoneroFormat) -> MoneroPrivateKey<N> {
+        MoneroPrivateKey::from_private_keys(transaction.private_spend_key, transaction.private_view_key, format)
+            .unwrap()
+    }
+
+    fn rings<N: MoneroNetwork>(transaction: &MoneroTransaction<N>) -> Vec<Vec<RingMember>> {
+        transaction.spends.iter().map(|spend| spend.ring.clone()).collect()
+    }
 
-        let transaction_parameters = MoneroTransaction::<N>::prepare_transaction(
-            transaction.is_sweeping,
-            transaction.fee_mask,
-            transaction.fee_per_b,
-            transaction.fork_version,
-            transaction.sending_amount,
-            transaction.passed_in_attempt_at_fee.into(),
-            transaction.payment_id_string.into(),
-            transaction.priority,
-            unspent_outs,
-        )
-        .unwrap();
-
-        assert_eq!(transaction_parameters.mixin, transaction.mixin);
-        assert_eq!(transaction_parameters.using_fee, transaction.fee_amount);
+    fn test_sign_and_verify<N: MoneroNetwork>(transaction: &TransactionTestCase) {
+        let private_key = sender_key::<N>(transaction, &MoneroFormat::Standard);
+        let unsigned = MoneroTransaction::<N>::new(&parameters(transaction)).unwrap();
+        assert!(unsigned.to_transaction_bytes().is_err());
+
+        let signed = unsigned.sign(&private_key).unwrap();
+        signed.verify(&rings(&signed)).unwrap();
+
+        let prefix = signed.prefix();
+        assert_eq!(1, prefix.inputs.len());
+        assert_eq!(RING_SIZE, prefix.inputs[0].ring_size());
+        assert_eq!(2, prefix.outputs.len());
+        assert_eq!(transaction.fee, signed.rct_signatures().fee);
+
+        let transaction_id = signed.to_transaction_id().unwrap();
+        assert_eq!(64, transaction_id.to_string().len());
+
+        // The serialized transaction parses back to the same transaction and id.
+        let transaction_bytes = signed.to_transaction_bytes().unwrap();
+        let parsed = MoneroTransaction::<N>::from_transaction_bytes(&transaction_bytes).unwrap();
+        assert_eq!(transaction_bytes, parsed.to_transaction_bytes().unwrap());
+        assert_eq!(transaction_id, parsed.to_transaction_id().unwrap());
+        assert_eq!(signed.prefix(), parsed.prefix());
+        assert_eq!(signed.rct_signatures(), parsed.rct_signatures());
+        parsed.verify(&rings(&signed)).unwrap();
+
+        let json = parsed.to_json();
+        assert_eq!(2, json["version"]);
+        assert_eq!(transaction.fee, json["rct_signatures"]["txnFee"]);
+        assert_eq!(
+            RING_SIZE,
+            json["vin"][0]["key"]["key_offsets"].as_array().unwrap().len()
+        );
         assert_eq!(
-            transaction_parameters.final_total_wo_fee,
-            transaction.final_total_wo_fee
+            RING_SIZE,
+            json["rctsig_prunable"]["CLSAGs"][0]["s"].as_array().unwrap().len()
         );
-        assert_eq!(transaction_parameters.change_amount, transaction.change_amount);
+
+        // The JSON representation parses back to the same transaction.
+        let from_json = MoneroTransaction::<N>::from_json(&json).unwrap();
+        assert_eq!(transaction_bytes, from_json.to_transaction_bytes().unwrap());
+
+        let (public_keys, additional_public_keys) = prefix.to_transaction_public_keys();
+        assert_eq!(1, public_keys.len());
+        assert!(additional_public_keys.is_empty());
     }
 
-    pub fn test_create_transaction<N: MoneroNetwork>(transaction: &TransactionTestCase) {
-        let mut using_outs: Vec<UnspentOutput> = Vec::new();
-        for output in transaction.using_outs.to_vec() {
-            let rct: Option<String> = match output.rct {
-                Some(rct) => Some(rct.into()),
-                None => None,
+    // (transaction, transaction id)
+    const COINBASE_TRANSACTIONS: [(&str, &str); 2] = [
+        // The genesis transaction of the mainnet
+        (
+            "013c01ff0001ffffffffffff03029b2e4c0281c0b02e7c53291a94d1d0cbff8883f8024f5142ee494ffbbd08807121017767aafcde9be00dcfd098715ebcf7f410daebc582fda69d24a28e9d0bc890d1",
+            "c88ce9783b4f11190d7b9c17a69c1c52200f9faaee8e98dd07e6811175177139",
+        ),
+        (
+            "02fc8db70101ffc08db7010180e0a596bb11031ff173e7e5db9d68136822460ff1ef7a3bcee8eb91a24b5d9283946cf1cce6145a2101c5e599ac67cd0572181a1ceefcf455836fbbf260785aea7c95c368b49fa5c78100",
+            "6454ea9a35e6edf96f5f675ea7fd2471e629e6154356fded825538accab2cb2f",
+        ),
+    ];
+
+    // A transaction of RingCT type 4 with two inputs of ring size 11, from the tests of monero-rs
+    // (transaction, prefix hash, transaction id)
+    const BULLETPROOF_2_TRANSACTION: (&str, &str, &str) = (
+        "02000202000bc6aa98049bf603fcec06bd3ccbad04e807e328b5128f22a63bfb27b6e287e8d594664d5cddd6c89bc413d1bc607b242203a6eb3180041ff5ae679702000b90e4eb028298a101879110f5bc0383ad03cbfc03a750e52ace37d112c6064faf7d16e2d07c4cc979dccb858aa9b24e12479e4a2db8350a906ba7a1aec409020002ab6d783607d8e712bbd5aad54a412aec890fcdcc1b35bf0ca4a705c2159bfc32000262f4016d5d81ade9e555807a24d23d452f08b6400683da599abd7134fb75324a2c0209016631a2dee1d0f51f015fd9bf938cf132790bdc5c528037e347828c539e82da6e5921e3d1e6052cb25804d0b7ba81018a4cd5385ca23ff4f6d76dc41b5254abf579b1856d3fbd04e81ff97c113e318bf7e158fbb0db7adc6ece9c8d4ab94e91f68e9607667a858ddf3e6890b2835403db6dcc5a1c179a768bcf74d74ace86430176b0056de37f310884e8eed56ee86840f23f842f1db52945b2feef98f4b56b3d4407734e4e8d3b117b5fd78f0d94f6059b495f53cf855b3716bbe8614d51727556c8b2e5c303cffc694257a1e91372de2047c4e12381c1de8df46102cdd84a24692f68ada05d1ffc5122b655582c6307141e130a6963198085ddb67d304b0ddde87e62402a3cadcd07a315604607ddf1530bd85685e910aa879733549bde0d019edc36326d33edd6ccecc800395b7075e4959779bde803dd787c24bc25d40205071b180152dded8b0be1f48a6d6f8e97c3f934f866b1b697f73f73fc9f38c5d2082c610732c79b2f69f403f7e2d312399739dd8d4225a2914a3020bd88c362271df633e8387b5345b50f11c4f148f76c0c24ca5843580a02fe72d18f47dcf8d601e28bbef2cd6659e620179adad4dfa5a0c7b712d716c4e630fe40bbfc6184f76c401db4b801a7001f65a9c11053db919099f9a1a4fe575c6d783e041ee08222a46adb8a1f13b863d95da277b71ccdefbb32f713a13b5ac8d041bbbeed9df4ace5a6b730b871ad2fe14141dbb9c816a21fd7fc48cfba4d2cc3e5e5fba29f581c1507a6a36285a30344790b74d2212dd26178395cd96a18518ead5c59a410baf6ca0b9217865fff207d757bb465fdb053e8c80b2ec1a966ccc01f49096fb991b65cc160a5070532c47318720fb9a90f187ce53661b6ce1e29d6ccd2b131324101170bb87ef273f0e73d762e159264f0839c6b3b31f5264499bdeb029c66a7035108a84256aec2760e74c2e8e788b7747084da8953aa48696b7a46e6320a9534d6c06ddee1b26671f03ae70a30c76b8fbf268da16fbb685f1d3f602668afce2e3eaf089b8758069f398eaeccd01876cef623201dc46dd75f76dc2141a9a2071b2761eefbf735ca599ed15266acbd0f54f49de38b5c7b3b378c386767383396645778021b30910e6e06937d65dac82312968d4f63a0bb28c96b9a09ee1d95c4d3afbb021998a9e290647b51083a80d66e0a8900a89c1236214f2d7c0080e2c18cdc8c0b4bd66765c3c52a26b90ec549bc8358ff8aaac9ffaecb0f6c915113cc97147b06c007a1a6bcd536bddec7fba330877d80cb878c7c9f3da81f8eb3bfb07ac7a804d4d58faadfabc3421350b14af6500b931209b75813759509e8642574982d680632106041b2687bdaef1c2be67c63a0ccc427bf02dc1ba58b153f00f8fabc8c00d99934e3e835291b8fc5a0bd62a3059c22cbd6fc4ae403df254e17cb15f32b0b65a4ed1f0f5fff37e49417c5fee339c21bd4f1cdcb1c803df8cb4baa11fa210089b61e28fe42e112824ee705f13167bfd3e6c8d660f2307216f5eea91a6db505b70f38e4b1b72a8f1fa1dd90bf0a47ed9a71e2e11e4f20240c1dac370f2b18075c6537bb475897fdd90fae360afb0b6b02210c123a3ca8fae31e320639b5e00c91c821abe873c1aaee2c4ebb87b5ecc670bff65de191e1d8463ecf1367685c0f0d39efc20269e516f29b775060a0c7a1595e158e94f64407d8e22b16ff25ed00cd50c46fb95ab5a3ca60a04e222c83d26b11c08a678348c8cdad407a0d841c0a50e91e896cb4ff873e9fc81c35f4a146f25a64b294c07c6adf4e418a3f590f061a89267deea53d985b4576b70b95170970321e07c1397b6f7e1ed3d4629e8e0a81019a7c15ae6d252e856c761664862d7fd0620fbbc7020fcd675fc97dc7310420b0d428093b4a80012f7a46612161ee2eeec8996128876d093f71f954244004aded4185afeec305d104d3905e54ddfda59ce1f5d56cf078ff10a76b138db90789184149cab60dfb1491943a793e85c332b4b36f448b63e5f099e7beb11d07005a31ed1658251e9f880466e44c54357781c9cdaf17d48534b062de482ad94005cbdcf52d6fea2e70c20ecc62a339afbb971e455e38292b78b21393bce982ec06d3e6f3e27897877007283f5a9d44ae134efc0ff14a5ce2fbe711403b535413073c9769ddc0474d64643bd2d60f58e001717f0538e1cc1e6b211c5f06f6ddfa029ec10d7e949673c08cd71713728ff042948c5b75ea2b610f4b4db838696fa40e24d750aa75f910948af39de2eba2ff6864daf92004453e4fa5cdae2f553a460f9bc86073dd7d6d2ea0f31092d28110892d6077dcee3b6293e66867a7ae67c5048df756d253c768debf3989d7643ff8892b7f6f74bf2b36d01bd0b88760c9b30eb54d02aa5498e7b87e2d027ac2a449318deda9cabf356fcd07f4561e6370db09fbd0d081093d93a569f6968b291fb01995415293b7cd997d9367c352c75b230ee70de92efd35302572e9de2809cd8e25eb9f824cb559107a1b87dd8c365de106cc800350e1284d67555b8db9041dfd44b3d5bec42e2b186b6a72b5bc3df0470476c234277592f742c3bf3babd4759b115196117883434af1d014ba8aeca028054fb4e8d9dde6a0e4cca9388a9f4c6283cbf9ae89dd17e4dc2a2f511604b33f0ee6e42e4f9a20b556ebc66e18b6142bfdcd4bf3792d9659ca5f5ef041f5ba28053e752155263ea79bd65dd94157f9464625e6a2e1b1e6d8ff40b4af4cb3804606239b8370d69d14f5c9eb463f49b0e796efdf01ddc087cc710bae43968856000fdc3a6408861363a190edbf2ae3e13d2bc52f87d9f2e31c044698b79f37b75a03199c7585b880df73a362ff40a94ed1d6173998d247a8f74e28407ad933e8bb0cd91ee82b723b3f22152a53b3226e52b32b93b397b5b2c386d3468651e602360eba66e52a1844aac9bbbe1a7183e97fed0c8b4b649f1551bcb98248dd62ebec087d4020a042085d487c64ae3fcf25e11b443ff03eeb8345b6d45d5304fbba83030bce1bbe499a7f5aefff31fef134b3c2c85b2fd16e6a26133cdbf05069299f007e627036d5ce0536e10546ec3b0719c373e00792f45fa78ff62d543e204d9a0f54a2b1c934a1463620a5c789ed600792ace37bc0f79c84399018acd073e86309f16a4ee382baad3e98425be3dcea1fceb47e56d237a49a125a360f7eb56b0305632f3877c17e62204e5a2c2017a934be9e532c5d7fd14ed71c4a2d3947621d03373796d7ffd6c77a73a06e3cbb61e1d872fb012c9ea0327fb65c4ffa46f02507d4db98bd434a7e921130e8846e697da226cc85568aa83f95cdfc4ccbfbff8ab0653000211ee7438364596b53793f2dfc4705f6a491190b35960f9aec1ffaad8a",
+        "3b50349180b4a60e55187507746eabb7bee0de6b74168eac8720a449da28613b",
+        "5a420317e377d3d95b652fb93e65cfe97ef7d89e04be329a2ca94e73ec57b74e",
+    );
+
+    // (number of hashes, root)
+    const TREE_HASHES: [(usize, &str); 6] = [
+        (2, "57d772147cdf27f5f67d679f0f3a513f8b87622ce598a3cf0b048ab178ddfc6e"),
+        (3, "31ea648480acca9d46c5cfd2fd5ecf576ce7a797bdd582869c38deeacf6d17d4"),
+        (4, "dd5115b5dcca3db0bffa31064a0d21f21362cd02e1263e47d69e38bbeec1d359"),
+        (5, "3b85b9b4e7171846e3dd41d242f99cdc136467ff276a272d5d8f960b2c447d67"),
+        (8, "791521f02a712f28265f5200914f9772b133bc2692260f8c8f426e176b1713ed"),
+        (9, "6a31a9bc64f694b411012bf9293fbf312a418c49565fcee0b0125c5c768c77be"),
+    ];
+
+    #[test]
+    fn coinbase_transactions() {
+        COINBASE_TRANSACTIONS.iter().for_each(|(transaction, transaction_id)| {
+            let transaction_bytes = hex::decode(transaction).unwrap();
+            let parsed = MoneroTransaction::<Mainnet>::from_transaction_bytes(&transaction_bytes).unwrap();
+            assert_eq!(transaction_bytes, parsed.to_transaction_bytes().unwrap());
+            assert_eq!(*transaction_id, parsed.to_transaction_id().unwrap().to_string());
+
+            match parsed.prefix().inputs.as_slice() {
+                [MoneroTransactionInput::Generation { .. }] => {}
+                inputs => panic!("unexpected inputs {:?}", inputs),
             };
+            assert!(parsed.to_json()["vin"][0]["gen"]["height"].is_u64());
 
-            using_outs.push(UnspentOutput {
-                amount: output.amount,
-                index: output.index,
-                global_index: output.global_index,
-                public_key: output.public_key.into(),
-                rct,
-                tx_pub_key: output.tx_pub_key.into(),
-            });
-        }
+            let from_json = MoneroTransaction::<Mainnet>::from_json(&parsed.to_json()).unwrap();
+            assert_eq!(transaction_bytes, from_json.to_transaction_bytes().unwrap());
+            assert_eq!(1, parsed.prefix().to_transaction_public_keys().0.len());
+        });
+    }
 
-        let mut mix_outs: Vec<MixAmountAndOuts> = Vec::new();
-        for amount_and_output in transaction.mix_outs.to_vec() {
-            let mut outputs: Vec<MixOut> = Vec::new();
-            for output in amount_and_output.outputs.to_vec() {
-                let rct: Option<String> = match output.rct {
-                    Some(rct) => Some(rct.into()),
-                    None => None,
-                };
+    #[test]
+    fn bulletproof_2_transaction() {
+        let (transaction, prefix_hash, transaction_id) = BULLETPROOF_2_TRANSACTION;
+        let transaction_bytes = hex::decode(transaction).unwrap();
+        let parsed = MoneroTransaction::<Mainnet>::from_transaction_bytes(&transaction_bytes).unwrap();
+        assert_eq!(transaction_bytes, parsed.to_transaction_bytes().unwrap());
+        assert_eq!(prefix_hash, hex::encode(parsed.prefix().to_hash()));
+        assert_eq!(transaction_id, parsed.to_transaction_id().unwrap().to_string());
+
+        let signatures = parsed.rct_signatures();
+        assert_eq!(RCT_TYPE_BULLETPROOF_2, signatures.rct_type);
+        assert_eq!(1, signatures.bulletproofs.len());
+        assert_eq!(2, signatures.out_pk.len());
+        assert_eq!(2, signatures.pseudo_outs.len());
+        assert!(signatures.clsags.is_empty());
+        assert_eq!(2, signatures.mlsags.len());
+        signatures
+            .mlsags
+            .iter()
+            .for_each(|mlsag| assert_eq!(11, mlsag.ss.len()));
+
+        let json = parsed.to_json();
+        assert_eq!(2, json["rctsig_prunable"]["MGs"].as_array().unwrap().len());
+        let from_json = MoneroTransaction::<Mainnet>::from_json(&json).unwrap();
+        assert_eq!(transaction_bytes, from_json.to_transaction_bytes().unwrap());
+
+        // Only transactions of RingCT type 6 are verified.
+        assert!(parsed.verify(&rings(&parsed)).is_err());
+    }
 
-                outputs.push(MixOut {
-                    global_index: output.global_index,
-                    public_key: output.public_key.into(),
-                    rct,
-                });
-            }
+    #[test]
+    fn genesis_block() {
+        let transaction_bytes = hex::decode(COINBASE_TRANSACTIONS[0].0).unwrap();
+        let transaction = MoneroTransaction::<Mainnet>::from_transaction_bytes(&transaction_bytes).unwrap();
+        let transaction_id = transaction.to_transaction_id().unwrap().tx_hash;
+
+        // The block hash commits to the header, the tree hash of the transaction ids and the number of transactions.
+        let mut block = vec![1, 0, 0];
+        block.extend(&[0u8; 32]);
+        block.extend(&10000u32.to_le_bytes());
+        block.extend(&tree_hash(&[transaction_id]).unwrap());
+        block.push(1);
+
+        let mut hashing_blob = vec![];
+        write_varint(&mut hashing_blob, block.len() as u64);
+        hashing_blob.extend(&block);
+        assert_eq!(
+            "418015bb9ae982a1975da7d79277c2705727a56894ba0fb246adaabb1f4632e3",
+            hex::encode(keccak256(&hashing_blob))
+        );
 
-            mix_outs.push(MixAmountAndOuts {
-                amount: amount_and_output.amount,
-                outputs,
+        let json = transaction.to_json();
+        assert_eq!(0, json["vin"][0]["gen"]["height"]);
+        assert_eq!(17592186044415u64, json["vout"][0]["amount"]);
+        assert!(json["signatures"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn tree_hashes() {
+        let hashes: Vec<[u8; 32]> = (0..9u8).map(|i| keccak256(&[i])).collect();
+        assert_eq!(None, tree_hash(&[]));
+        assert_eq!(Some(hashes[0]), tree_hash(&hashes[..1]));
+        TREE_HASHES.iter().for_each(|(count, root)| {
+            assert_eq!(*root, hex::encode(tree_hash(&hashes[..*count]).unwrap()));
+        });
+    }
+
+    /// Checks that the sender finds the change output with their view key and decodes its amount and mask.
+    fn test_change_output<N: MoneroNetwork>(transaction: &TransactionTestCase) {
+        let private_key = sender_key::<N>(transaction, &MoneroFormat::Standard);
+        let signed = MoneroTransaction::<N>::new(&parameters(transaction))
+            .unwrap()
+            .sign(&private_key)
+            .unwrap();
+
+        let mut tx_public_key = [0u8; 32];
+        tx_public_key.copy_from_slice(&signed.prefix().extra[1..33]);
+        let mut derivation = vec![];
+        OneTimeKey::<N>::generate_key_derivation(&tx_public_key, &private_key.to_private_view_key(), &mut derivation)
+            .unwrap();
+
+        let change = transaction.using_out.amount - transaction.amount - transaction.fee;
+        let found: Vec<u64> = signed
+            .prefix()
+            .outputs
+            .iter()
+            .enumerate()
+            .filter(|(i, output)| {
+                let one_time_key = OneTimeKey::<N>::from(&output.key, &tx_public_key);
+                output.view_tag == Some(OneTimeKey::<N>::derive_view_tag(&derivation, *i as u64))
+                    && one_time_key.verify(&private_key, *i as u64).unwrap()
             })
-        }
+            .map(|(i, _)| {
+                let shared_secret = OneTimeKey::<N>::derivation_to_scalar(&derivation, i as u64);
+                let amount = decode_amount(&signed.rct_signatures().ecdh_info[i], &shared_secret);
+                let mask = commitment_mask(&shared_secret);
+                assert_eq!(commit(&mask, amount), signed.rct_signatures().out_pk[i]);
+                amount
+            })
+            .collect();
+        assert_eq!(vec![change], found);
+    }
 
-        let transaction_result = MoneroTransaction::<N>::create_transaction(
-            transaction.change_amount,
-            transaction.fee_amount,
-            transaction.fee_mask,
-            transaction.fee_per_b,
-            transaction.final_total_wo_fee,
-            transaction.fork_version,
-            transaction.from_address_string.into(),
-            mix_outs,
-            transaction.nettype_string.into(),
-            transaction.payment_id_string.into(),
-            transaction.priority,
-            transaction.sec_spend_key_string.into(),
-            transaction.sec_view_key_string.into(),
-            transaction.to_address_string.into(),
-            transaction.unlock_time,
-            using_outs,
-        )
-        .unwrap();
-
-        assert_eq!(transaction_result.tx_must_be_reconstructed, false);
-        assert!(!transaction_result.serialized_signed_tx.is_empty());
+    fn test_subaddress_destination<N: MoneroNetwork>(transaction: &TransactionTestCase) {
+        let private_key = sender_key::<N>(transaction, &MoneroFormat::Standard);
+        let format = MoneroFormat::Subaddress(0, 1);
+        let subaddress_key = sender_key::<N>(transaction, &format);
+        let subaddress = subaddress_key.to_address(&format).unwrap();
+
+        for change_address in [
+            subaddress.clone(),
+            MoneroAddress::from_str(transaction.from_address).unwrap(),
+        ]
+        .iter()
+        {
+            let mut parameters = parameters::<N>(transaction);
+            parameters.destinations = vec![(subaddress.clone(), transaction.amount)];
+            parameters.change_address = change_address.clone();
+
+            let signed = MoneroTransaction::<N>::new(&parameters)
+                .unwrap()
+                .sign(&private_key)
+                .unwrap();
+            signed.verify(&rings(&signed)).unwrap();
+
+            // A single subaddress recipient uses the transaction key, otherwise each output has its own.
+            let extra = &signed.prefix().extra;
+            let tx_public_keys: Vec<[u8; 32]> = match change_address == &subaddress {
+                true => {
+                    assert_eq!(33, extra.len());
+                    vec![extra[1..33].to_vec(); 2]
+                }
+                false => {
+                    assert_eq!(33 + 2 + 2 * 32, extra.len());
+                    vec![extra[35..67].to_vec(), extra[67..99].to_vec()]
+                }
+            }
+            .iter()
+            .map(|key| {
+                let mut bytes = [0u8; 32];
+                bytes.copy_from_slice(key);
+                bytes
+            })
+            .collect();
+
+            let received = signed
+                .prefix()
+                .outputs
+                .iter()
+                .zip(tx_public_keys.iter())
+                .enumerate()
+                .filter(|(i, (output, tx_public_key))| {
+                    OneTimeKey::<N>::from(&output.key, tx_public_key)
+                        .verify(&subaddress_key, *i as u64)
+                        .unwrap()
+                })
+                .count();
+            assert_eq!(if change_address == &subaddress { 2 } else { 1 }, received);
+        }
     }
 
     mod mainnet {
         use super::*;
-        use crate::Mainnet;
 
         type N = Mainnet;
 
         // https://github.com/mymonero/mymonero-core-cpp/blob/20b6cbabf230ae4ebe01d05c859aad397741cf8f/test/test_all.cpp#L347
-        const TEST_CASES: [TransactionTestCase; 2] = [
-            TransactionTestCase {
-                is_sweeping: false,
-                fee_mask: 10000,
-                fee_per_b: 24658,
-                fork_version: 10,
-                passed_in_attempt_at_fee: "",
-                payment_id_string: "",
-                sending_amount: 200000000,
-                priority: 1,
-                unspent_outs: [
-                    Output {
-                        amount: 3000000000,
-                        public_key: "41be1978f58cabf69a9bed5b6cb3c8d588621ef9b67602328da42a213ee42271",
-                        index: 1,
-                        global_index: 7611174,
-                        rct: Some("86a2c9f1f8e66848cd99bfda7a14d4ac6c3525d06947e21e4e55fe42a368507eb5b234ccdd70beca8b1fc8de4f2ceb1374e0f1fd8810849e7f11316c2cc063060008ffa5ac9827b776993468df21af8c963d12148622354f950cbe1369a92a0c"),
-                        tx_pub_key: "bd703d7f37995cc7071fb4d2929594b5e2a4c27d2b7c68a9064500ca7bc638b8",
-                    }
-                ],
-
-                mixin: 10,
-
-                change_amount: 2733990000,
-                fee_amount: 66010000,
-                final_total_wo_fee: 200000000,
-                sec_spend_key_string: "4e6d43cd03812b803c6f3206689f5fcc910005fc7e91d50d79b0776dbefcd803",
-                sec_view_key_string: "7bea1907940afdd480eff7c4bcadb478a0fbb626df9e3ed74ae801e18f53e104",
-                from_address_string: "43zxvpcj5Xv9SEkNXbMCG7LPQStHMpFCQCmkmR4u5nzjWwq5Xkv5VmGgYEsHXg4ja2FGRD5wMWbBVMijDTqmmVqm93wHGkg",
-                to_address_string: "4APbcAKxZ2KPVPMnqa5cPtJK25tr7maE7LrJe67vzumiCtWwjDBvYnHZr18wFexJpih71Mxsjv8b7EpQftpB9NjPPXmZxHN",
-                nettype_string: "MAINNET",
-                unlock_time: 0,
-                using_outs: [
-                    Output {
-                        amount: 3000000000,
-                        public_key: "41be1978f58cabf69a9bed5b6cb3c8d588621ef9b67602328da42a213ee42271",
-                        index: 1,
-                        global_index: 7611174,
-                        rct: Some("86a2c9f1f8e66848cd99bfda7a14d4ac6c3525d06947e21e4e55fe42a368507eb5b234ccdd70beca8b1fc8de4f2ceb1374e0f1fd8810849e7f11316c2cc063060008ffa5ac9827b776993468df21af8c963d12148622354f950cbe1369a92a0c"),
-                        tx_pub_key: "bd703d7f37995cc7071fb4d2929594b5e2a4c27d2b7c68a9064500ca7bc638b8",
-                    }
-                ],
-                mix_outs: [
-                    MixAmountAndOutputs {
-                        amount: 0,
-                        outputs: [
-                            MixOutput {
-                                global_index: 7453099,
-                                public_key: "31f3a7fec0f6f09067e826b6c2904fd4b1684d7893dcf08c5b5d22e317e148bb",
-                                rct: Some("ea6bcb193a25ce2787dd6abaaeef1ee0c924b323c6a5873db1406261e86145fc"),
-                            },
-                            MixOutput {
-                                global_index: 7500097,
-                                public_key: "f9d923500671da05a1bf44b932b872f0c4a3c88e6b3d4bf774c8be915e25f42b",
-                                rct: Some("dcae4267a6c382bcd71fd1af4d2cbceb3749d576d7a3acc473dd579ea9231a52"),
-                            },
-                            MixOutput {
-                                global_index: 7548483,
-                                public_key: "839cbbb73685654b93e824c4843e745e8d5f7742e83494932307bf300641c480",
-                                rct: Some("aa99d492f1d6f1b20dcd95b8fff8f67a219043d0d94b4551759016b4888573e7"),
-                            },
-                            MixOutput {
-                                global_index: 7554755,
-                                public_key: "b8860f0697988c8cefd7b4285fbb8bec463f136c2b9a9cadb3e57cebee10717f",
-                                rct: Some("327f9b07bee9c4c25b5a990123cd2444228e5704ebe32016cd632866710279b5"),
-                            },
-                            MixOutput {
-                                global_index: 7561477,
-                                public_key: "561d734cb90bc4a64d49d37f85ea85575243e2ed749a3d6dcb4d27aa6bec6e88",
-                                rct: Some("b5393e038df95b94bfda62b44a29141cac9e356127270af97193460d51949841"),
-                            },
-                            MixOutput {
-                                global_index: 7567062,
-                                public_key: "db1024ef67e7e73608ef8afab62f49e2402c8da3dc3197008e3ba720ad3c94a8",
-                                rct: Some("1fedf95621881b77f823a70aa83ece26aef62974976d2b8cd87ed4862a4ec92c"),
-                            },
-                            MixOutput {
-                                global_index: 7567508,
-                                public_key: "6283f3cd2f050bba90276443fe04f6076ad2ad46a515bf07b84d424a3ba43d27",
-                                rct: Some("10e16bb8a8b7b0c8a4b193467b010976b962809c9f3e6c047335dba09daa351f"),
-                            },
-                            MixOutput {
-                                global_index: 7568716,
-                                public_key: "7a7deb4eef81c1f5ce9cbd0552891cb19f1014a03a5863d549630824c7c7c0d3",
-                                rct: Some("735d059dc3526334ac705ddc44c4316bb8805d2426dcea9544cde50cf6c7a850"),
-                            },
-                            MixOutput {
-                                global_index: 7571196,
-                                public_key: "535208e354cae530ed7ce752935e555d630cf2edd7f91525024ed9c332b2a347",
-                                rct: Some("c3cf838faa14e993536c5581ca582fb0d96b70f713cf88f7f15c89336e5853ec"),
-                            },
-                            MixOutput {
-                                global_index: 7571333,
-                                public_key: "e73f27b7eb001aa7eac13df82814cda65b42ceeb6ef36227c25d5cbf82f6a5e4",
-                                rct: Some("5f45f33c6800cdae202b37abe6d87b53d6873e7b30f3527161f44fa8db3104b6"),
-                            },
-                        ],
-                    }
-                ],
+        const TRANSACTION: TransactionTestCase = TransactionTestCase {
+            private_spend_key: "4e6d43cd03812b803c6f3206689f5fcc910005fc7e91d50d79b0776dbefcd803",
+            private_view_key: "7bea1907940afdd480eff7c4bcadb478a0fbb626df9e3ed74ae801e18f53e104",
+            from_address: "43zxvpcj5Xv9SEkNXbMCG7LPQStHMpFCQCmkmR4u5nzjWwq5Xkv5VmGgYEsHXg4ja2FGRD5wMWbBVMijDTqmmVqm93wHGkg",
+            to_address: "4APbcAKxZ2KPVPMnqa5cPtJK25tr7maE7LrJe67vzumiCtWwjDBvYnHZr18wFexJpih71Mxsjv8b7EpQftpB9NjPPXmZxHN",
+            amount: 200000000,
+            fee: 66010000,
+            using_out: Output {
+                amount: 3000000000,
+                index: 1,
+                global_index: 7611174,
+                public_key: "41be1978f58cabf69a9bed5b6cb3c8d588621ef9b67602328da42a213ee42271",
+                rct: "86a2c9f1f8e66848cd99bfda7a14d4ac6c3525d06947e21e4e55fe42a368507eb5b234ccdd70beca8b1fc8de4f2ceb1374e0f1fd8810849e7f11316c2cc063060008ffa5ac9827b776993468df21af8c963d12148622354f950cbe1369a92a0c",
+                tx_pub_key: "bd703d7f37995cc7071fb4d2929594b5e2a4c27d2b7c68a9064500ca7bc638b8",
             },
-            TransactionTestCase {
-                is_sweeping: false,
-                fee_mask: 10000,
-                fee_per_b: 24658,
-                fork_version: 10,
-                passed_in_attempt_at_fee: "66290000",
-                payment_id_string: "d2f602b240fbe624",
-                sending_amount: 200000000,
-                priority: 1,
-                unspent_outs: [
-                    Output {
-                        amount: 3000000000,
-                        public_key: "41be1978f58cabf69a9bed5b6cb3c8d588621ef9b67602328da42a213ee42271",
-                        index: 1,
-                        global_index: 7611174,
-                        rct: Some("86a2c9f1f8e66848cd99bfda7a14d4ac6c3525d06947e21e4e55fe42a368507eb5b234ccdd70beca8b1fc8de4f2ceb1374e0f1fd8810849e7f11316c2cc063060008ffa5ac9827b776993468df21af8c963d12148622354f950cbe1369a92a0c"),
-                        tx_pub_key: "bd703d7f37995cc7071fb4d2929594b5e2a4c27d2b7c68a9064500ca7bc638b8",
-                    }
-                ],
-
-                mixin: 10,
-
-                change_amount: 2733710000,
-                fee_amount: 66290000,
-                final_total_wo_fee: 200000000,
-                sec_spend_key_string: "4e6d43cd03812b803c6f3206689f5fcc910005fc7e91d50d79b0776dbefcd803",
-                sec_view_key_string: "7bea1907940afdd480eff7c4bcadb478a0fbb626df9e3ed74ae801e18f53e104",
-                from_address_string: "43zxvpcj5Xv9SEkNXbMCG7LPQStHMpFCQCmkmR4u5nzjWwq5Xkv5VmGgYEsHXg4ja2FGRD5wMWbBVMijDTqmmVqm93wHGkg",
-                to_address_string: "4APbcAKxZ2KPVPMnqa5cPtJK25tr7maE7LrJe67vzumiCtWwjDBvYnHZr18wFexJpih71Mxsjv8b7EpQftpB9NjPPXmZxHN",
-                nettype_string: "MAINNET",
-                unlock_time: 0,
-                using_outs: [
-                    Output {
-                        amount: 3000000000,
-                        public_key: "41be1978f58cabf69a9bed5b6cb3c8d588621ef9b67602328da42a213ee42271",
-                        index: 1,
-                        global_index: 7611174,
-                        rct: Some("86a2c9f1f8e66848cd99bfda7a14d4ac6c3525d06947e21e4e55fe42a368507eb5b234ccdd70beca8b1fc8de4f2ceb1374e0f1fd8810849e7f11316c2cc063060008ffa5ac9827b776993468df21af8c963d12148622354f950cbe1369a92a0c"),
-                        tx_pub_key: "bd703d7f37995cc7071fb4d2929594b5e2a4c27d2b7c68a9064500ca7bc638b8",
-                    }
-                ],
-                mix_outs: [
-                    MixAmountAndOutputs {
-                        amount: 0,
-                        outputs: [
-                            MixOutput {
-                                global_index: 7453099,
-                                public_key: "31f3a7fec0f6f09067e826b6c2904fd4b1684d7893dcf08c5b5d22e317e148bb",
-                                rct: Some("ea6bcb193a25ce2787dd6abaaeef1ee0c924b323c6a5873db1406261e86145fc"),
-                            },
-                            MixOutput {
-                                global_index: 7500097,
-                                public_key: "f9d923500671da05a1bf44b932b872f0c4a3c88e6b3d4bf774c8be915e25f42b",
-                                rct: Some("dcae4267a6c382bcd71fd1af4d2cbceb3749d576d7a3acc473dd579ea9231a52"),
-                            },
-                            MixOutput {
-                                global_index: 7548483,
-                                public_key: "839cbbb73685654b93e824c4843e745e8d5f7742e83494932307bf300641c480",
-                                rct: Some("aa99d492f1d6f1b20dcd95b8fff8f67a219043d0d94b4551759016b4888573e7"),
-                            },
-                            MixOutput {
-                                global_index: 7554755,
-                                public_key: "b8860f0697988c8cefd7b4285fbb8bec463f136c2b9a9cadb3e57cebee10717f",
-                                rct: Some("327f9b07bee9c4c25b5a990123cd2444228e5704ebe32016cd632866710279b5"),
-                            },
-                            MixOutput {
-                                global_index: 7561477,
-                                public_key: "561d734cb90bc4a64d49d37f85ea85575243e2ed749a3d6dcb4d27aa6bec6e88",
-                                rct: Some("b5393e038df95b94bfda62b44a29141cac9e356127270af97193460d51949841"),
-                            },
-                            MixOutput {
-                                global_index: 7567062,
-                                public_key: "db1024ef67e7e73608ef8afab62f49e2402c8da3dc3197008e3ba720ad3c94a8",
-                                rct: Some("1fedf95621881b77f823a70aa83ece26aef62974976d2b8cd87ed4862a4ec92c"),
-                            },
-                            MixOutput {
-                                global_index: 7567508,
-                                public_key: "6283f3cd2f050bba90276443fe04f6076ad2ad46a515bf07b84d424a3ba43d27",
-                                rct: Some("10e16bb8a8b7b0c8a4b193467b010976b962809c9f3e6c047335dba09daa351f"),
-                            },
-                            MixOutput {
-                                global_index: 7568716,
-                                public_key: "7a7deb4eef81c1f5ce9cbd0552891cb19f1014a03a5863d549630824c7c7c0d3",
-                                rct: Some("735d059dc3526334ac705ddc44c4316bb8805d2426dcea9544cde50cf6c7a850"),
-                            },
-                            MixOutput {
-                                global_index: 7571196,
-                                public_key: "535208e354cae530ed7ce752935e555d630cf2edd7f91525024ed9c332b2a347",
-                                rct: Some("c3cf838faa14e993536c5581ca582fb0d96b70f713cf88f7f15c89336e5853ec"),
-                            },
-                            MixOutput {
-                                global_index: 7571333,
-                                public_key: "e73f27b7eb001aa7eac13df82814cda65b42ceeb6ef36227c25d5cbf82f6a5e4",
-                                rct: Some("5f45f33c6800cdae202b37abe6d87b53d6873e7b30f3527161f44fa8db3104b6"),
-                            },
-                        ],
-                    }
-                ],
-            },
-        ];
+            mix_outs: [
+                MixOutput {
+                    global_index: 7453099,
+                    public_key: "31f3a7fec0f6f09067e826b6c2904fd4b1684d7893dcf08c5b5d22e317e148bb",
+                    rct: "ea6bcb193a25ce2787dd6abaaeef1ee0c924b323c6a5873db1406261e86145fc",
+                },
+                MixOutput {
+                    global_index: 7500097,
+                    public_key: "f9d923500671da05a1bf44b932b872f0c4a3c88e6b3d4bf774c8be915e25f42b",
+                    rct: "dcae4267a6c382bcd71fd1af4d2cbceb3749d576d7a3acc473dd579ea9231a52",
+                },
+                MixOutput {
+                    global_index: 7548483,
+                    public_key: "839cbbb73685654b93e824c4843e745e8d5f7742e83494932307bf300641c480",
+                    rct: "aa99d492f1d6f1b20dcd95b8fff8f67a219043d0d94b4551759016b4888573e7",
+                },
+                MixOutput {
+                    global_index: 7554755,
+                    public_key: "b8860f0697988c8cefd7b4285fbb8bec463f136c2b9a9cadb3e57cebee10717f",
+                    rct: "327f9b07bee9c4c25b5a990123cd2444228e5704ebe32016cd632866710279b5",
+                },
+                MixOutput {
+                    global_index: 7561477,
+                    public_key: "561d734cb90bc4a64d49d37f85ea85575243e2ed749a3d6dcb4d27aa6bec6e88",
+                    rct: "b5393e038df95b94bfda62b44a29141cac9e356127270af97193460d51949841",
+                },
+                MixOutput {
+                    global_index: 7567062,
+                    public_key: "db1024ef67e7e73608ef8afab62f49e2402c8da3dc3197008e3ba720ad3c94a8",
+                    rct: "1fedf95621881b77f823a70aa83ece26aef62974976d2b8cd87ed4862a4ec92c",
+                },
+                MixOutput {
+                    global_index: 7567508,
+                    public_key: "6283f3cd2f050bba90276443fe04f6076ad2ad46a515bf07b84d424a3ba43d27",
+                    rct: "10e16bb8a8b7b0c8a4b193467b010976b962809c9f3e6c047335dba09daa351f",
+                },
+                MixOutput {
+                    global_index: 7568716,
+                    public_key: "7a7deb4eef81c1f5ce9cbd0552891cb19f1014a03a5863d549630824c7c7c0d3",
+                    rct: "735d059dc3526334ac705ddc44c4316bb8805d2426dcea9544cde50cf6c7a850",
+                },
+                MixOutput {
+                    global_index: 7571196,
+                    public_key: "535208e354cae530ed7ce752935e555d630cf2edd7f91525024ed9c332b2a347",
+                    rct: "c3cf838faa14e993536c5581ca582fb0d96b70f713cf88f7f15c89336e5853ec",
+                },
+                MixOutput {
+                    global_index: 7571333,
+                    public_key: "e73f27b7eb001aa7eac13df82814cda65b42ceeb6ef36227c25d5cbf82f6a5e4",
+                    rct: "5f45f33c6800cdae202b37abe6d87b53d6873e7b30f3527161f44fa8db3104b6",
+                },
+            ],
+        };
 
         #[test]
-        fn step1_prepare() {
-            TEST_CASES.iter().for_each(|transaction_test_case| {
-                test_prepare_transaction::<N>(transaction_test_case);
-            });
+        fn sign_and_verify() {
+            test_sign_and_verify::<N>(&TRANSACTION);
         }
 
         #[test]
-        fn step2_create() {
-            TEST_CASES.iter().for_each(|transaction_test_case| {
-                test_create_transaction::<N>(transaction_test_case);
-            });
+        fn change_output() {
+            test_change_output::<N>(&TRANSACTION);
+        }
+
+        #[test]
+        fn subaddress_destination() {
+            test_subaddress_destination::<N>(&TRANSACTION);
         }
     }
 
     mod stagenet {
         use super::*;
-        use crate::Stagenet;
 
         type N = Stagenet;
 
-        const TEST_CASES: [TransactionTestCase; 2] = [
-            TransactionTestCase {
-                is_sweeping: false,
-                fee_mask: 10000,
-                fee_per_b: 24658,
-                fork_version: 10,
-                passed_in_attempt_at_fee: "",
-                payment_id_string: "",
-                sending_amount: 200000000,
-                priority: 1,
-                unspent_outs: [
-                    Output {
-                        amount: 9996522470000,
-                        public_key: "dd397dea109a2b94056fd5e236eada6f57c60ab4de5563469a4245e7394f8f65",
-                        index: 0,
-                        global_index: 1745616,
-                        rct: Some("c32e27e0f773d08efc3a651cbabbbd563a60fb230ccd3ebf5320ae825ab05e86da36753b8b5957c4"),
-                        tx_pub_key: "0ee98e87f68a2526393ed487495f2d3dba6e90e860537d0aae1d5fad5d492f21",
-                    }
-                ],
-
-                mixin: 10,
-
-                change_amount: 9996256460000,
-                fee_amount: 66010000,
-                final_total_wo_fee: 200000000,
-                sec_spend_key_string: "0cf0c38429e00fa4abecb98296cb15dec209c0a7e6ea34ed86d32429498e4700",
-                sec_view_key_string: "55c5b2fa94b2c5ee387eb9dd71b197a6358a5a90ed5eb5acdfa088583125a40a",
-                from_address_string: "593u2VupBMzERSQSvwqzwnCSXSYGV28FcYkfcdXEfFyY2UoUfKtFACMYsoRxy1U7B7iwwUsoievaEY8THxzWitdfMsam7uM",
-                to_address_string: "59McWTPGc745SRWrSMoh8oTjoXoQq6sPUgKZ66dQWXuKFQ2q19h9gvhJNZcFTizcnT12r63NFgHiGd6gBCjabzmzHAMoyD6",
-                nettype_string: "STAGENET",
-                unlock_time: 0,
-                using_outs: [
-                    Output {
-                        amount: 9996522470000,
-                        public_key: "dd397dea109a2b94056fd5e236eada6f57c60ab4de5563469a4245e7394f8f65",
-                        index: 0,
-                        global_index: 1745616,
-                        rct: Some("c32e27e0f773d08efc3a651cbabbbd563a60fb230ccd3ebf5320ae825ab05e86da36753b8b5957c4"),
-                        tx_pub_key: "0ee98e87f68a2526393ed487495f2d3dba6e90e860537d0aae1d5fad5d492f21",
-                    }
-                ],
-                mix_outs: [
-                    MixAmountAndOutputs {
-                        amount: 0,
-                        outputs: [
-                            MixOutput {
-                                global_index: 1522098,
-                                public_key: "4c72fa2bef04dca799f1b8821f3f5e53d14e30dd143e73426c473995c7fac5e7",
-                                rct: Some("b76844a6b8ae138a319198efb3bba88406e2b717cb2bd6a1c5675f5c040f4cb600000000000000000000000000000000000000000000000000000000000000001a9c1b4ec57e1362000000000000000000000000000000000000000000000000"),
-                            },
-                            MixOutput {
-                                global_index: 1314128,
-                                public_key: "99b7f212b56fa75260ee7c83535922561e4a3072447bb96cb4e07beb29152220",
-                                rct: Some("13a42eca66f12becc3538aa3ee3da6cab24100079f4bc280b5ea38ec372f64ab00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
-                            },
-                            MixOutput {
-                                global_index: 1455703,
-                                public_key: "ae29e674c69363a6d3f0ab5e1648375cafb3b7e6088747781959a4248a706549",
-                                rct: Some("8f49acf1046ceb5c0aec067623ac6ab30d20d10d5a4b73d0562150dbab20f4070000000000000000000000000000000000000000000000000000000000000000825b0e78dc68428e000000000000000000000000000000000000000000000000"),
-                            },
-                            MixOutput {
-                                global_index: 640952,
-                                public_key: "8d6038a16684a251b19a7684fc91345a468a87b1f90c87492c57a6939f69dbac",
-                                rct: Some("fc6a95dd6bafdce7ffba40ef6282351f82e4580cc439f4325b2b8df0388eac5400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
-                            },
-                            MixOutput {
-                                global_index: 834344,
-                                public_key: "0ab50a508bbbd3cac1c7203a19c30c4cad5dc8aa0c24b824c8283281eaec961e",
-                                rct: Some("9af4f716993de3afd02062339aecd3197356226ed7baca56329a7a3e330041c8ed3c4ad84a4f5974564a7939de6457d989f6f7fae0a578a0a68084444bc30403081b6b568a4379723fb3817b41aad7881d40c2abd8770e1de698ffee2d2c2206"),
-                            },
-                            MixOutput {
-                                global_index: 1214508,
-                                public_key: "53c94ea948205b1c582b82d63e3f90c11d293baffab8d6d1662b8f45d1053464",
-                                rct: Some("4ddb5c7348878df1c30bb1f5d6f65697043f4aae4d62c76adfc630d34580ef3600000000000000000000000000000000000000000000000000000000000000003c171473b3c5ee70000000000000000000000000000000000000000000000000"),
-                            },
-                            MixOutput {
-                                global_index: 1617396,
-                                public_key: "24ad1a4ff3fc39e5b9473f09506041f7ede9605ddf633410a3353be1a2b8b259",
-                                rct: Some("7720631e160804759223ca4dfbb0c66329f102c855c17c12adc6f560d5597f9300000000000000000000000000000000000000000000000000000000000000007d1fff73a88e2caf000000000000000000000000000000000000000000000000"),
-                            },
-                            MixOutput {
-                                global_index: 321717,
-                                public_key: "77b0ff37990913b62a081fe3e98a25be633cf58593194b97fb13ff59e6e4d405",
-                                rct: Some("9883d971e67f14b39f11d4862fa31e6b0849f320f96c687400327dfca43b9b139b633364db9ce0d949d1675c4136e19c24de4ef3a331893d57f6b033bda1760beec0335ea02e8295886e2f0b7c8066ab0649468c812b080fc53e6ee723df720b"),
-                            },
-                            MixOutput {
-                                global_index: 1501374,
-                                public_key: "3bb106d2f8de03523cc201123f768b5670da942130c0360dee70c017ad2a4159",
-                                rct: Some("a0229ea3c1aef3a7570f3834e6f536749fd9aa52198d614134c7d798b5bd15c80000000000000000000000000000000000000000000000000000000000000000631b748cfde1645e000000000000000000000000000000000000000000000000"),
-                            },
-                            MixOutput {
-                                global_index: 491964,
-                                public_key: "d968a59e48c40ff79b4fb47eb08b630184ddbb1f3bc043b1a4797aa87d5d07d8",
-                                rct: Some("d4ac039c43c99460b818cd8daca5468ee78d53e3017b90e6a6a64fd0de79e072a582ce2c8a2b471f51be15c4ca5218748e520c759f07581ae0c0d297668b5c0562adc3999444d40dc11388ac5f2fca05b2f42ed91e3b7c11537213d09d925003"),
-                            }
-                        ],
-                    }
-                ],
+        const TRANSACTION: TransactionTestCase = TransactionTestCase {
+            private_spend_key: "0cf0c38429e00fa4abecb98296cb15dec209c0a7e6ea34ed86d32429498e4700",
+            private_view_key: "55c5b2fa94b2c5ee387eb9dd71b197a6358a5a90ed5eb5acdfa088583125a40a",
+            from_address: "593u2VupBMzERSQSvwqzwnCSXSYGV28FcYkfcdXEfFyY2UoUfKtFACMYsoRxy1U7B7iwwUsoievaEY8THxzWitdfMsam7uM",
+            to_address: "59McWTPGc745SRWrSMoh8oTjoXoQq6sPUgKZ66dQWXuKFQ2q19h9gvhJNZcFTizcnT12r63NFgHiGd6gBCjabzmzHAMoyD6",
+            amount: 200000000,
+            fee: 66010000,
+            using_out: Output {
+                amount: 9996522470000,
+                index: 0,
+                global_index: 1745616,
+                public_key: "dd397dea109a2b94056fd5e236eada6f57c60ab4de5563469a4245e7394f8f65",
+                rct: "c32e27e0f773d08efc3a651cbabbbd563a60fb230ccd3ebf5320ae825ab05e86da36753b8b5957c4",
+                tx_pub_key: "0ee98e87f68a2526393ed487495f2d3dba6e90e860537d0aae1d5fad5d492f21",
             },
-            TransactionTestCase {
-                is_sweeping: false,
-                fee_mask: 10000,
-                fee_per_b: 24658,
-                fork_version: 10,
-                passed_in_attempt_at_fee: "66010000",
-                payment_id_string: "d2f602b240fbe624",
-                sending_amount: 200000000,
-                priority: 1,
-                unspent_outs: [
-                    Output {
-                        amount: 9996522470000,
-                        public_key: "dd397dea109a2b94056fd5e236eada6f57c60ab4de5563469a4245e7394f8f65",
-                        index: 0,
-                        global_index: 1745616,
-                        rct: Some("c32e27e0f773d08efc3a651cbabbbd563a60fb230ccd3ebf5320ae825ab05e86da36753b8b5957c4"),
-                        tx_pub_key: "0ee98e87f68a2526393ed487495f2d3dba6e90e860537d0aae1d5fad5d492f21",
-                    }
-                ],
-
-                mixin: 10,
-
-                change_amount: 9996256460000,
-                fee_amount: 66010000,
-                final_total_wo_fee: 200000000,
-                sec_spend_key_string: "0cf0c38429e00fa4abecb98296cb15dec209c0a7e6ea34ed86d32429498e4700",
-                sec_view_key_string: "55c5b2fa94b2c5ee387eb9dd71b197a6358a5a90ed5eb5acdfa088583125a40a",
-                from_address_string: "593u2VupBMzERSQSvwqzwnCSXSYGV28FcYkfcdXEfFyY2UoUfKtFACMYsoRxy1U7B7iwwUsoievaEY8THxzWitdfMsam7uM",
-                to_address_string: "59McWTPGc745SRWrSMoh8oTjoXoQq6sPUgKZ66dQWXuKFQ2q19h9gvhJNZcFTizcnT12r63NFgHiGd6gBCjabzmzHAMoyD6",
-                nettype_string: "STAGENET",
-                unlock_time: 0,
-                using_outs: [
-                    Output {
-                        amount: 9996522470000,
-                        public_key: "dd397dea109a2b94056fd5e236eada6f57c60ab4de5563469a4245e7394f8f65",
-                        index: 0,
-                        global_index: 1745616,
-                        rct: Some("c32e27e0f773d08efc3a651cbabbbd563a60fb230ccd3ebf5320ae825ab05e86da36753b8b5957c4"),
-                        tx_pub_key: "0ee98e87f68a2526393ed487495f2d3dba6e90e860537d0aae1d5fad5d492f21",
-                    }
-                ],
-                mix_outs: [
-                    MixAmountAndOutputs {
-                        amount: 0,
-                        outputs: [
-                            MixOutput {
-                                global_index: 1522098,
-                                public_key: "4c72fa2bef04dca799f1b8821f3f5e53d14e30dd143e73426c473995c7fac5e7",
-                                rct: Some("b76844a6b8ae138a319198efb3bba88406e2b717cb2bd6a1c5675f5c040f4cb600000000000000000000000000000000000000000000000000000000000000001a9c1b4ec57e1362000000000000000000000000000000000000000000000000"),
-                            },
-                            MixOutput {
-                                global_index: 1314128,
-                                public_key: "99b7f212b56fa75260ee7c83535922561e4a3072447bb96cb4e07beb29152220",
-                                rct: Some("13a42eca66f12becc3538aa3ee3da6cab24100079f4bc280b5ea38ec372f64ab00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
-                            },
-                            MixOutput {
-                                global_index: 1455703,
-                                public_key: "ae29e674c69363a6d3f0ab5e1648375cafb3b7e6088747781959a4248a706549",
-                                rct: Some("8f49acf1046ceb5c0aec067623ac6ab30d20d10d5a4b73d0562150dbab20f4070000000000000000000000000000000000000000000000000000000000000000825b0e78dc68428e000000000000000000000000000000000000000000000000"),
-                            },
-                            MixOutput {
-                                global_index: 640952,
-                                public_key: "8d6038a16684a251b19a7684fc91345a468a87b1f90c87492c57a6939f69dbac",
-                                rct: Some("fc6a95dd6bafdce7ffba40ef6282351f82e4580cc439f4325b2b8df0388eac5400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
-                            },
-                            MixOutput {
-                                global_index: 834344,
-                                public_key: "0ab50a508bbbd3cac1c7203a19c30c4cad5dc8aa0c24b824c8283281eaec961e",
-                                rct: Some("9af4f716993de3afd02062339aecd3197356226ed7baca56329a7a3e330041c8ed3c4ad84a4f5974564a7939de6457d989f6f7fae0a578a0a68084444bc30403081b6b568a4379723fb3817b41aad7881d40c2abd8770e1de698ffee2d2c2206"),
-                            },
-                            MixOutput {
-                                global_index: 1214508,
-                                public_key: "53c94ea948205b1c582b82d63e3f90c11d293baffab8d6d1662b8f45d1053464",
-                                rct: Some("4ddb5c7348878df1c30bb1f5d6f65697043f4aae4d62c76adfc630d34580ef3600000000000000000000000000000000000000000000000000000000000000003c171473b3c5ee70000000000000000000000000000000000000000000000000"),
-                            },
-                            MixOutput {
-                                global_index: 1617396,
-                                public_key: "24ad1a4ff3fc39e5b9473f09506041f7ede9605ddf633410a3353be1a2b8b259",
-                                rct: Some("7720631e160804759223ca4dfbb0c66329f102c855c17c12adc6f560d5597f9300000000000000000000000000000000000000000000000000000000000000007d1fff73a88e2caf000000000000000000000000000000000000000000000000"),
-                            },
-                            MixOutput {
-                                global_index: 321717,
-                                public_key: "77b0ff37990913b62a081fe3e98a25be633cf58593194b97fb13ff59e6e4d405",
-                                rct: Some("9883d971e67f14b39f11d4862fa31e6b0849f320f96c687400327dfca43b9b139b633364db9ce0d949d1675c4136e19c24de4ef3a331893d57f6b033bda1760beec0335ea02e8295886e2f0b7c8066ab0649468c812b080fc53e6ee723df720b"),
-                            },
-                            MixOutput {
-                                global_index: 1501374,
-                                public_key: "3bb106d2f8de03523cc201123f768b5670da942130c0360dee70c017ad2a4159",
-                                rct: Some("a0229ea3c1aef3a7570f3834e6f536749fd9aa52198d614134c7d798b5bd15c80000000000000000000000000000000000000000000000000000000000000000631b748cfde1645e000000000000000000000000000000000000000000000000"),
-                            },
-                            MixOutput {
-                                global_index: 491964,
-                                public_key: "d968a59e48c40ff79b4fb47eb08b630184ddbb1f3bc043b1a4797aa87d5d07d8",
-                                rct: Some("d4ac039c43c99460b818cd8daca5468ee78d53e3017b90e6a6a64fd0de79e072a582ce2c8a2b471f51be15c4ca5218748e520c759f07581ae0c0d297668b5c0562adc3999444d40dc11388ac5f2fca05b2f42ed91e3b7c11537213d09d925003"),
-                            }
-                        ],
-                    }
-                ],
-            },
-        ];
+            mix_outs: [
+                MixOutput {
+                    global_index: 1522098,
+                    public_key: "4c72fa2bef04dca799f1b8821f3f5e53d14e30dd143e73426c473995c7fac5e7",
+                    rct: "b76844a6b8ae138a319198efb3bba88406e2b717cb2bd6a1c5675f5c040f4cb600000000000000000000000000000000000000000000000000000000000000001a9c1b4ec57e1362000000000000000000000000000000000000000000000000",
+                },
+                MixOutput {
+                    global_index: 1314128,
+                    public_key: "99b7f212b56fa75260ee7c83535922561e4a3072447bb96cb4e07beb29152220",
+                    rct: "13a42eca66f12becc3538aa3ee3da6cab24100079f4bc280b5ea38ec372f64ab00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                },
+                MixOutput {
+                    global_index: 1455703,
+                    public_key: "ae29e674c69363a6d3f0ab5e1648375cafb3b7e6088747781959a4248a706549",
+                    rct: "8f49acf1046ceb5c0aec067623ac6ab30d20d10d5a4b73d0562150dbab20f4070000000000000000000000000000000000000000000000000000000000000000825b0e78dc68428e000000000000000000000000000000000000000000000000",
+                },
+                MixOutput {
+                    global_index: 640952,
+                    public_key: "8d6038a16684a251b19a7684fc91345a468a87b1f90c87492c57a6939f69dbac",
+                    rct: "fc6a95dd6bafdce7ffba40ef6282351f82e4580cc439f4325b2b8df0388eac5400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                },
+                MixOutput {
+                    global_index: 834344,
+                    public_key: "0ab50a508bbbd3cac1c7203a19c30c4cad5dc8aa0c24b824c8283281eaec961e",
+                    rct: "9af4f716993de3afd02062339aecd3197356226ed7baca56329a7a3e330041c8ed3c4ad84a4f5974564a7939de6457d989f6f7fae0a578a0a68084444bc30403081b6b568a4379723fb3817b41aad7881d40c2abd8770e1de698ffee2d2c2206",
+                },
+                MixOutput {
+                    global_index: 1214508,
+                    public_key: "53c94ea948205b1c582b82d63e3f90c11d293baffab8d6d1662b8f45d1053464",
+                    rct: "4ddb5c7348878df1c30bb1f5d6f65697043f4aae4d62c76adfc630d34580ef3600000000000000000000000000000000000000000000000000000000000000003c171473b3c5ee70000000000000000000000000000000000000000000000000",
+                },
+                MixOutput {
+                    global_index: 1617396,
+                    public_key: "24ad1a4ff3fc39e5b9473f09506041f7ede9605ddf633410a3353be1a2b8b259",
+                    rct: "7720631e160804759223ca4dfbb0c66329f102c855c17c12adc6f560d5597f9300000000000000000000000000000000000000000000000000000000000000007d1fff73a88e2caf000000000000000000000000000000000000000000000000",
+                },
+                MixOutput {
+                    global_index: 321717,
+                    public_key: "77b0ff37990913b62a081fe3e98a25be633cf58593194b97fb13ff59e6e4d405",
+                    rct: "9883d971e67f14b39f11d4862fa31e6b0849f320f96c687400327dfca43b9b139b633364db9ce0d949d1675c4136e19c24de4ef3a331893d57f6b033bda1760beec0335ea02e8295886e2f0b7c8066ab0649468c812b080fc53e6ee723df720b",
+                },
+                MixOutput {
+                    global_index: 1501374,
+                    public_key: "3bb106d2f8de03523cc201123f768b5670da942130c0360dee70c017ad2a4159",
+                    rct: "a0229ea3c1aef3a7570f3834e6f536749fd9aa52198d614134c7d798b5bd15c80000000000000000000000000000000000000000000000000000000000000000631b748cfde1645e000000000000000000000000000000000000000000000000",
+                },
+                MixOutput {
+                    global_index: 491964,
+                    public_key: "d968a59e48c40ff79b4fb47eb08b630184ddbb1f3bc043b1a4797aa87d5d07d8",
+                    rct: "d4ac039c43c99460b818cd8daca5468ee78d53e3017b90e6a6a64fd0de79e072a582ce2c8a2b471f51be15c4ca5218748e520c759f07581ae0c0d297668b5c0562adc3999444d40dc11388ac5f2fca05b2f42ed91e3b7c11537213d09d925003",
+                },
+            ],
+        };
 
         #[test]
-        fn step1_prepare() {
-            TEST_CASES.iter().for_each(|transaction_test_case| {
-                test_prepare_transaction::<N>(transaction_test_case);
-            });
+        fn sign_and_verify() {
+            test_sign_and_verify::<N>(&TRANSACTION);
         }
 
         #[test]
-        fn step2_create() {
-            TEST_CASES.iter().for_each(|transaction_test_case| {
-                test_create_transaction::<N>(transaction_test_case);
-            });
+        fn change_output() {
+            test_change_output::<N>(&TRANSACTION);
+        }
+
+        #[test]
+        fn subaddress_destination() {
+            test_subaddress_destination::<N>(&TRANSACTION);
+        }
+
+        mod invalid {
+            use super::*;
+
+            #[test]
+            fn sign_with_wrong_key() {
+                let private_key = MoneroPrivateKey::<N>::from_private_spend_key(
+                    "4e6d43cd03812b803c6f3206689f5fcc910005fc7e91d50d79b0776dbefcd803",
+                    &MoneroFormat::Standard,
+                )
+                .unwrap();
+                let unsigned = MoneroTransaction::<N>::new(&parameters(&TRANSACTION)).unwrap();
+                assert!(unsigned.sign(&private_key).is_err());
+            }
+
+            #[test]
+            fn insufficient_funds() {
+                let mut parameters = parameters::<N>(&TRANSACTION);
+                parameters.fee = TRANSACTION.using_out.amount;
+                assert!(MoneroTransaction::<N>::new(&parameters).is_err());
+            }
+
+            #[test]
+            fn insufficient_decoys() {
+                let mut parameters = parameters::<N>(&TRANSACTION);
+                parameters.mix_outs[0].outputs.pop();
+                assert!(MoneroTransaction::<N>::new(&parameters).is_err());
+            }
+
+            #[test]
+            fn excess_decoys() {
+                let mut excess = parameters::<N>(&TRANSACTION);
+                let mut decoy = excess.mix_outs[0].outputs[0].clone();
+                decoy.global_index += 1_000_000;
+                excess.mix_outs[0].outputs.push(decoy);
+                assert!(MoneroTransaction::<N>::new(&excess).is_err());
+
+                // The spent output itself is not counted as a decoy.
+                let mut with_output = parameters::<N>(&TRANSACTION);
+                let mut output = with_output.mix_outs[0].outputs[0].clone();
+                output.global_index = with_output.using_outs[0].global_index;
+                with_output.mix_outs[0].outputs.push(output);
+                assert!(MoneroTransaction::<N>::new(&with_output).is_ok());
+            }
+
+            #[test]
+            fn verify_tampered() {
+                let private_key = sender_key::<N>(&TRANSACTION, &MoneroFormat::Standard);
+                let signed = MoneroTransaction::<N>::new(&parameters(&TRANSACTION))
+                    .unwrap()
+                    .sign(&private_key)
+                    .unwrap();
+                assert!(signed.sign(&private_key).is_err());
+
+                let mut tampered = signed.clone();
+                tampered.rct_signatures.fee += 1;
+                assert!(tampered.verify(&rings(&signed)).is_err());
+
+                let mut tampered = signed.clone();
+                tampered.prefix.unlock_time += 1;
+                assert!(tampered.verify(&rings(&signed)).is_err());
+            }
+
+            #[test]
+            fn from_invalid_transaction_bytes() {
+                let private_key = sender_key::<N>(&TRANSACTION, &MoneroFormat::Standard);
+                let signed = MoneroTransaction::<N>::new(&parameters(&TRANSACTION))
+                    .unwrap()
+                    .sign(&private_key)
+                    .unwrap();
+                let transaction_bytes = signed.to_transaction_bytes().unwrap();
+
+                let mut trailing = transaction_bytes.clone();
+                trailing.push(0);
+                assert!(MoneroTransaction::<N>::from_transaction_bytes(&trailing).is_err());
+
+                let truncated = transaction_bytes[..transaction_bytes.len() - 1].to_vec();
+                assert!(MoneroTransaction::<N>::from_transaction_bytes(&truncated).is_err());
+
+                // The RingCT type follows the prefix, whose extra field ends the prefix.
+                let mut prefix = vec![];
+                signed.prefix().write(&mut prefix);
+                let mut unsupported = transaction_bytes.clone();
+                unsupported[prefix.len()] = 3;
+                assert_eq!(
+                    "unsupported RingCT type 3",
+                    MoneroTransaction::<N>::from_transaction_bytes(&unsupported)
+                        .unwrap_err()
+                        .to_string()
+                );
+
+                assert!(MoneroTransaction::<N>::from_transaction_bytes(&vec![]).is_err());
+                assert!(MoneroTransaction::<N>::from_transaction_bytes(&vec![3, 0, 0, 0, 0]).is_err());
+            }
+
+            #[test]
+            fn from_invalid_json() {
+                let private_key = sender_key::<N>(&TRANSACTION, &MoneroFormat::Standard);
+                let signed = MoneroTransaction::<N>::new(&parameters(&TRANSACTION))
+                    .unwrap()
+                    .sign(&private_key)
+                    .unwrap();
+                let json = signed.to_json();
+
+                let mut missing = json.clone();
+                missing["rctsig_prunable"].as_object_mut().unwrap().remove("pseudoOuts");
+                assert!(MoneroTransaction::<N>::from_json(&missing).is_err());
+
+                let mut key_image = json.clone();
+                key_image["vin"][0]["key"]["k_image"] = json!("00");
+                assert!(MoneroTransaction::<N>::from_json(&key_image).is_err());
+
+                let mut extra = json.clone();
+                extra["extra"][0] = json!(256);
+                assert!(MoneroTransaction::<N>::from_json(&extra).is_err());
+
+                assert!(MoneroTransaction::<N>::from_json(&json!({})).is_err());
+            }
         }
     }
 }
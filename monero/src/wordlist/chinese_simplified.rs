@@ -1,5 +1,5 @@
-use crate::wordlist::MoneroWordlist;
-use wagyu_model::{monero::CHINESE_SIMPLIFIED, wordlist::Wordlist};
+use crate::wordlist::{MoneroWordlist, PolyseedWordlist};
+use wagyu_model::{bip39, monero::CHINESE_SIMPLIFIED, wordlist::Wordlist};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ChineseSimplified;
@@ -13,6 +13,11 @@ impl MoneroWordlist for ChineseSimplified {
     const PREFIX_LENGTH: usize = 1;
 }
 
+impl PolyseedWordlist for ChineseSimplified {
+    /// The BIP-39 wordlist in original form.
+    const POLYSEED_WORDLIST: &'static str = bip39::CHINESE_SIMPLIFIED;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,49 @@
+use crate::wordlist::PolyseedWordlist;
+use wagyu_model::{bip39::CHINESE_TRADITIONAL, wordlist::Wordlist};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChineseTraditional;
+
+impl Wordlist for ChineseTraditional {}
+
+impl PolyseedWordlist for ChineseTraditional {
+    /// The BIP-39 wordlist in original form.
+    const POLYSEED_WORDLIST: &'static str = CHINESE_TRADITIONAL;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_normalization::UnicodeNormalization;
+
+    const VALID_WORD: &str = "去";
+    const VALID_WORD_INDEX: usize = 100;
+    const INVALID_WORD: &str = "a";
+    const INVALID_WORD_INDEX: usize = 3400;
+
+    #[test]
+    fn get_polyseed() {
+        // Valid case
+        assert_eq!(
+            VALID_WORD.nfc().collect::<String>(),
+            ChineseTraditional::get_polyseed(VALID_WORD_INDEX).unwrap()
+        );
+        // Invalid case
+        assert!(ChineseTraditional::get_polyseed(INVALID_WORD_INDEX).is_err());
+    }
+
+    #[test]
+    fn get_polyseed_index() {
+        // Valid case
+        assert_eq!(
+            VALID_WORD_INDEX,
+            ChineseTraditional::get_polyseed_index(VALID_WORD).unwrap()
+        );
+        assert_eq!(
+            VALID_WORD_INDEX,
+            ChineseTraditional::get_polyseed_index(&VALID_WORD.nfc().collect::<String>()).unwrap()
+        );
+        // Invalid case
+        assert!(ChineseTraditional::get_polyseed_index(INVALID_WORD).is_err());
+    }
+}
@@ -1,5 +1,5 @@
-use crate::wordlist::MoneroWordlist;
-use wagyu_model::{monero::ENGLISH, wordlist::Wordlist};
+use crate::wordlist::{MoneroWordlist, PolyseedWordlist};
+use wagyu_model::{bip39, monero::ENGLISH, wordlist::Wordlist};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct English;
@@ -13,6 +13,13 @@ impl MoneroWordlist for English {
     const PREFIX_LENGTH: usize = 3;
 }
 
+impl PolyseedWordlist for English {
+    /// The BIP-39 wordlist in original form.
+    const POLYSEED_WORDLIST: &'static str = bip39::ENGLISH;
+    /// The number of leading characters that identify a word.
+    const POLYSEED_PREFIX_LENGTH: usize = 4;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -44,4 +51,14 @@ mod tests {
         assert_eq!(1626, list.len());
         assert_eq!(VALID_WORD, list[VALID_WORD_INDEX]);
     }
+
+    #[test]
+    fn get_polyseed_index() {
+        // Words are identified by their first four letters.
+        assert_eq!(472, English::get_polyseed_index("deposit").unwrap());
+        assert_eq!(472, English::get_polyseed_index("depo").unwrap());
+        assert_eq!(472, English::get_polyseed_index("depository").unwrap());
+        assert!(English::get_polyseed_index("dep").is_err());
+        assert_eq!("deposit", English::get_polyseed(472).unwrap());
+    }
 }
@@ -1,5 +1,5 @@
-use crate::wordlist::MoneroWordlist;
-use wagyu_model::{monero::FRENCH, wordlist::Wordlist};
+use crate::wordlist::{MoneroWordlist, PolyseedWordlist};
+use wagyu_model::{bip39, monero::FRENCH, wordlist::Wordlist};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct French;
@@ -13,6 +13,13 @@ impl MoneroWordlist for French {
     const PREFIX_LENGTH: usize = 4;
 }
 
+impl PolyseedWordlist for French {
+    /// The BIP-39 wordlist in original form.
+    const POLYSEED_WORDLIST: &'static str = bip39::FRENCH;
+    /// The number of leading characters that identify a word.
+    const POLYSEED_PREFIX_LENGTH: usize = 4;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
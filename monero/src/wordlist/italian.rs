@@ -1,5 +1,5 @@
-use crate::wordlist::MoneroWordlist;
-use wagyu_model::{monero::ITALIAN, wordlist::Wordlist};
+use crate::wordlist::{MoneroWordlist, PolyseedWordlist};
+use wagyu_model::{bip39, monero::ITALIAN, wordlist::Wordlist};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Italian;
@@ -13,6 +13,13 @@ impl MoneroWordlist for Italian {
     const PREFIX_LENGTH: usize = 4;
 }
 
+impl PolyseedWordlist for Italian {
+    /// The BIP-39 wordlist in original form.
+    const POLYSEED_WORDLIST: &'static str = bip39::ITALIAN;
+    /// The number of leading characters that identify a word.
+    const POLYSEED_PREFIX_LENGTH: usize = 4;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,5 @@
-use crate::wordlist::MoneroWordlist;
-use wagyu_model::{monero::JAPANESE, wordlist::Wordlist};
+use crate::wordlist::{MoneroWordlist, PolyseedWordlist};
+use wagyu_model::{bip39, monero::JAPANESE, wordlist::Wordlist};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Japanese;
@@ -13,6 +13,13 @@ impl MoneroWordlist for Japanese {
     const PREFIX_LENGTH: usize = 3;
 }
 
+impl PolyseedWordlist for Japanese {
+    /// The BIP-39 wordlist in original form.
+    const POLYSEED_WORDLIST: &'static str = bip39::JAPANESE;
+    /// The separator of the words of a phrase.
+    const POLYSEED_SEPARATOR: &'static str = "\u{3000}";
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,46 @@
+use crate::wordlist::PolyseedWordlist;
+use wagyu_model::{bip39::KOREAN, wordlist::Wordlist};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Korean;
+
+impl Wordlist for Korean {}
+
+impl PolyseedWordlist for Korean {
+    /// The BIP-39 wordlist in original form.
+    const POLYSEED_WORDLIST: &'static str = KOREAN;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_normalization::UnicodeNormalization;
+
+    const VALID_WORD: &str = "껍질";
+    const VALID_WORD_INDEX: usize = 283;
+    const INVALID_WORD: &str = "a";
+    const INVALID_WORD_INDEX: usize = 3400;
+
+    #[test]
+    fn get_polyseed() {
+        // Valid case
+        assert_eq!(
+            VALID_WORD.nfc().collect::<String>(),
+            Korean::get_polyseed(VALID_WORD_INDEX).unwrap()
+        );
+        // Invalid case
+        assert!(Korean::get_polyseed(INVALID_WORD_INDEX).is_err());
+    }
+
+    #[test]
+    fn get_polyseed_index() {
+        // Valid case
+        assert_eq!(VALID_WORD_INDEX, Korean::get_polyseed_index(VALID_WORD).unwrap());
+        assert_eq!(
+            VALID_WORD_INDEX,
+            Korean::get_polyseed_index(&VALID_WORD.nfc().collect::<String>()).unwrap()
+        );
+        // Invalid case
+        assert!(Korean::get_polyseed_index(INVALID_WORD).is_err());
+    }
+}
@@ -1,8 +1,13 @@
 use wagyu_model::wordlist::{Wordlist, WordlistError};
 
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
 pub mod chinese_simplified;
 pub use self::chinese_simplified::*;
 
+pub mod chinese_traditional;
+pub use self::chinese_traditional::*;
+
 pub mod dutch;
 pub use self::dutch::*;
 
@@ -27,6 +32,9 @@ pub use self::italian::*;
 pub mod japanese;
 pub use self::japanese::*;
 
+pub mod korean;
+pub use self::korean::*;
+
 pub mod lojban;
 pub use self::lojban::*;
 
@@ -88,3 +96,43 @@ pub trait MoneroWordlist: Wordlist {
         }
     }
 }
+
+/// The interface for a Polyseed wordlist, which is the BIP-39 wordlist of a language.
+pub trait PolyseedWordlist: Wordlist {
+    /// The wordlist in original form.
+    const POLYSEED_WORDLIST: &'static str;
+    /// The number of leading characters that identify a word, or zero if words are matched in full.
+    const POLYSEED_PREFIX_LENGTH: usize = 0;
+    /// The separator of the words of a phrase.
+    const POLYSEED_SEPARATOR: &'static str = " ";
+
+    /// Returns the word of a given index from the word list.
+    fn get_polyseed(index: usize) -> Result<String, WordlistError> {
+        match Self::POLYSEED_WORDLIST.lines().nth(index) {
+            Some(word) => Ok(word.nfc().collect()),
+            None => Err(WordlistError::InvalidIndex(index)),
+        }
+    }
+
+    /// Returns the index of a given word from the word list, ignoring accents and
+    /// the characters after the prefix length.
+    fn get_polyseed_index(word: &str) -> Result<usize, WordlistError> {
+        let normalized = Self::to_polyseed_normalized(word);
+        match Self::POLYSEED_WORDLIST
+            .lines()
+            .position(|element| Self::to_polyseed_normalized(element) == normalized)
+        {
+            Some(index) => Ok(index),
+            None => Err(WordlistError::InvalidWord(word.into())),
+        }
+    }
+
+    /// Returns the decomposed word without accents, trimmed to the prefix length.
+    fn to_polyseed_normalized(word: &str) -> String {
+        let characters = word.nfkd().filter(|character| !is_combining_mark(*character));
+        match Self::POLYSEED_PREFIX_LENGTH {
+            0 => characters.collect(),
+            length => characters.take(length).collect(),
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use crate::wordlist::MoneroWordlist;
-use wagyu_model::{monero::SPANISH, wordlist::Wordlist};
+use crate::wordlist::{MoneroWordlist, PolyseedWordlist};
+use wagyu_model::{bip39, monero::SPANISH, wordlist::Wordlist};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Spanish;
@@ -13,6 +13,13 @@ impl MoneroWordlist for Spanish {
     const PREFIX_LENGTH: usize = 4;
 }
 
+impl PolyseedWordlist for Spanish {
+    /// The BIP-39 wordlist in original form.
+    const POLYSEED_WORDLIST: &'static str = bip39::SPANISH;
+    /// The number of leading characters that identify a word.
+    const POLYSEED_PREFIX_LENGTH: usize = 4;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -44,4 +51,13 @@ mod tests {
         assert_eq!(1626, list.len());
         assert_eq!(VALID_WORD, list[VALID_WORD_INDEX]);
     }
+
+    #[test]
+    fn get_polyseed_index() {
+        // Words are matched without their accents.
+        assert_eq!(147, Spanish::get_polyseed_index("arnés").unwrap());
+        assert_eq!(147, Spanish::get_polyseed_index("arnes").unwrap());
+        assert_eq!("arnés", Spanish::get_polyseed(147).unwrap());
+        assert!(Spanish::get_polyseed_index("a").is_err());
+    }
 }
@@ -2,13 +2,13 @@ use crate::bitcoin::{
     format::BitcoinFormat, wordlist::*, BitcoinAddress, BitcoinAmount, BitcoinDerivationPath,
     BitcoinExtendedPrivateKey, BitcoinExtendedPublicKey, BitcoinMnemonic, BitcoinNetwork, BitcoinPrivateKey,
     BitcoinPublicKey, BitcoinTransaction, BitcoinTransactionInput, BitcoinTransactionOutput,
-    BitcoinTransactionParameters, BitcoinWordlist, Mainnet as BitcoinMainnet, Outpoint, SignatureHash,
+    BitcoinTransactionParameters, BitcoinVanity, BitcoinWordlist, Mainnet as BitcoinMainnet, Outpoint, SignatureHash,
     Testnet as BitcoinTestnet,
 };
-use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
+use crate::cli::{flag, option, print_vanity_progress, subcommand, types::*, CLIError, CLI};
 use crate::model::{
     crypto::hash160, ExtendedPrivateKey, ExtendedPublicKey, Mnemonic, MnemonicCount, MnemonicExtended, PrivateKey,
-    PublicKey, Transaction,
+    PublicKey, Transaction, VanityProgress,
 };
 
 use clap::{ArgMatches, Values};
@@ -35,6 +35,8 @@ struct BitcoinWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub private_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_private_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub public_key: Option<String>,
     pub address: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -65,6 +67,66 @@ impl BitcoinWallet {
         })
     }
 
+    pub fn new_vanity<N: BitcoinNetwork, R: Rng>(
+        rng: &mut R,
+        prefix: &str,
+        format: &BitcoinFormat,
+        public_key: &Option<String>,
+        threads: usize,
+    ) -> Result<Self, CLIError> {
+        let vanity = BitcoinVanity::<N>::new(prefix, format)?;
+        let difficulty = vanity.difficulty();
+        eprintln!(
+            "\n      {}           {:.0} keys",
+            "Difficulty".cyan().bold(),
+            difficulty
+        );
+        let progress = |progress: &VanityProgress| print_vanity_progress(progress, difficulty);
+
+        let (wallet, attempts) = match public_key {
+            // Split-key search, where only the owner of the public key can derive the private key
+            Some(public_key) => {
+                let public_key = BitcoinPublicKey::<N>::from_str(public_key)?;
+                let (partial_private_key, attempts) =
+                    vanity.search_with_public_key(&public_key, rng, threads, progress)?;
+                let public_key = BitcoinVanity::combine_public_key(&public_key, &partial_private_key)?;
+                let address = public_key.to_address(format)?;
+                let wallet = Self {
+                    partial_private_key: Some(partial_private_key.to_string()),
+                    public_key: Some(public_key.to_string()),
+                    address: Some(address.to_string()),
+                    network: Some(N::NAME.to_string()),
+                    format: Some(address.format().to_string()),
+                    compressed: Some(true),
+                    ..Default::default()
+                };
+                (wallet, attempts)
+            }
+            None => {
+                let (private_key, attempts) = vanity.search(rng, threads, progress)?;
+                let public_key = private_key.to_public_key();
+                let address = public_key.to_address(format)?;
+                let wallet = Self {
+                    private_key: Some(private_key.to_string()),
+                    public_key: Some(public_key.to_string()),
+                    address: Some(address.to_string()),
+                    network: Some(N::NAME.to_string()),
+                    format: Some(address.format().to_string()),
+                    compressed: private_key.is_compressed().into(),
+                    ..Default::default()
+                };
+                (wallet, attempts)
+            }
+        };
+
+        eprintln!(
+            "\r\x1b[K      {}             {} keys",
+            "Searched".cyan().bold(),
+            attempts
+        );
+        Ok(wallet)
+    }
+
     pub fn new_hd<N: BitcoinNetwork, W: BitcoinWordlist, R: Rng>(
         rng: &mut R,
         word_count: u8,
@@ -219,6 +281,7 @@ impl BitcoinWallet {
     pub fn to_raw_transaction<N: BitcoinNetwork>(
         inputs: &Vec<BitcoinInput>,
         outputs: &Vec<&str>,
+        data: &Option<Vec<u8>>,
         version: u32,
         lock_time: u32,
     ) -> Result<Self, CLIError> {
@@ -239,14 +302,19 @@ impl BitcoinWallet {
 
         let mut transaction_outputs = vec![];
         for output in outputs {
-            let values: Vec<&str> = output.split(":").collect();
-            let address = BitcoinAddress::<N>::from_str(values[0])?;
+            // Split on the last separator, as CashAddr addresses contain a `:` after their prefix
+            let values: Vec<&str> = output.rsplitn(2, ":").collect();
+            let address = BitcoinAddress::<N>::from_str(values[values.len() - 1])?;
             transaction_outputs.push(BitcoinTransactionOutput::new(
                 &address,
-                BitcoinAmount::from_satoshi(i64::from_str(values[1])?)?,
+                BitcoinAmount::from_satoshi(i64::from_str(values[0])?)?,
             )?);
         }
 
+        if let Some(data) = data {
+            transaction_outputs.push(BitcoinTransactionOutput::new_data(data)?);
+        }
+
         let transaction_parameters = BitcoinTransactionParameters::<N> {
             version,
             inputs: transaction_inputs,
@@ -356,6 +424,14 @@ impl Display for BitcoinWallet {
                 Some(private_key) => format!("      {}          {}\n", "Private Key".cyan().bold(), private_key),
                 _ => "".to_owned(),
             },
+            match &self.partial_private_key {
+                Some(partial_private_key) => format!(
+                    "      {}  {}\n",
+                    "Partial Private Key".cyan().bold(),
+                    partial_private_key
+                ),
+                _ => "".to_owned(),
+            },
             match &self.public_key {
                 Some(public_key) => format!("      {}           {}\n", "Public Key".cyan().bold(), public_key),
                 _ => "".to_owned(),
@@ -422,6 +498,7 @@ pub struct BitcoinOptions {
     // HD and Import HD subcommands
     account: u32,
     chain: u32,
+    coin_type: Option<u32>,
     derivation: String,
     extended_private_key: Option<String>,
     extended_public_key: Option<String>,
@@ -439,8 +516,12 @@ pub struct BitcoinOptions {
     transaction_inputs: Option<String>,
     transaction_hex: Option<String>,
     transaction_outputs: Option<String>,
+    data: Option<String>,
     lock_time: Option<u32>,
     version: Option<u32>,
+    // Vanity subcommand
+    prefix: Option<String>,
+    threads: Option<usize>,
 }
 
 impl Default for BitcoinOptions {
@@ -455,6 +536,7 @@ impl Default for BitcoinOptions {
             // HD and Import HD subcommands
             account: 0,
             chain: 0,
+            coin_type: None,
             derivation: "bip32".into(),
             extended_private_key: None,
             extended_public_key: None,
@@ -472,8 +554,12 @@ impl Default for BitcoinOptions {
             transaction_inputs: None,
             transaction_hex: None,
             transaction_outputs: None,
+            data: None,
             lock_time: None,
             version: None,
+            // Vanity subcommand
+            prefix: None,
+            threads: None,
         }
     }
 }
@@ -486,6 +572,7 @@ impl BitcoinOptions {
             "chain" => self.chain(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "count" => self.count(clap::value_t!(arguments.value_of(*option), usize).ok()),
             "createrawtransaction" => self.create_raw_transaction(arguments.values_of(option)),
+            "data" => self.data(arguments.value_of(option)),
             "derivation" => self.derivation(arguments.value_of(option)),
             "extended private" => self.extended_private(arguments.value_of(option)),
             "extended public" => self.extended_public(arguments.value_of(option)),
@@ -497,9 +584,11 @@ impl BitcoinOptions {
             "mnemonic" => self.mnemonic(arguments.value_of(option)),
             "network" => self.network(arguments.value_of(option)),
             "password" => self.password(arguments.value_of(option)),
+            "prefix" => self.prefix(arguments.value_of(option)),
             "private" => self.private(arguments.value_of(option)),
             "public" => self.public(arguments.value_of(option)),
             "signrawtransaction" => self.sign_raw_transaction(arguments.values_of(option)),
+            "threads" => self.threads(clap::value_t!(arguments.value_of(*option), usize).ok()),
             "word count" => self.word_count(clap::value_t!(arguments.value_of(*option), u8).ok()),
             "version" => self.version(clap::value_t!(arguments.value_of(*option), u32).ok()),
             _ => (),
@@ -547,6 +636,14 @@ impl BitcoinOptions {
         }
     }
 
+    /// Sets `data` to the specified output data, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn data(&mut self, argument: Option<&str>) {
+        if let Some(data) = argument {
+            self.data = Some(data.to_string());
+        }
+    }
+
     /// Sets `derivation` to the specified derivation, overriding its previous state.
     /// If `derivation` is `\"custom\"`, then `path` is set to the specified path.
     /// If the specified argument is `None`, then no change occurs.
@@ -583,7 +680,7 @@ impl BitcoinOptions {
     /// If the specified argument is `None`, then no change occurs.
     fn format(&mut self, argument: Option<&str>) {
         match argument {
-            Some("legacy") => self.format = BitcoinFormat::P2PKH,
+            Some("legacy") | Some("p2pkh") => self.format = BitcoinFormat::P2PKH,
             Some("segwit") => self.format = BitcoinFormat::P2SH_P2WPKH,
             Some("bech32") => self.format = BitcoinFormat::Bech32,
             _ => (),
@@ -653,6 +750,14 @@ impl BitcoinOptions {
         }
     }
 
+    /// Sets `prefix` to the specified vanity address prefix, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn prefix(&mut self, argument: Option<&str>) {
+        if let Some(prefix) = argument {
+            self.prefix = Some(prefix.to_string());
+        }
+    }
+
     /// Imports a wallet for the specified private key, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn private(&mut self, argument: Option<&str>) {
@@ -679,6 +784,14 @@ impl BitcoinOptions {
         }
     }
 
+    /// Sets `threads` to the specified number of vanity search threads, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn threads(&mut self, argument: Option<usize>) {
+        if let Some(threads) = argument {
+            self.threads = Some(threads);
+        }
+    }
+
     /// Sets `word_count` to the specified word count, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn word_count(&mut self, argument: Option<u8>) {
@@ -687,13 +800,36 @@ impl BitcoinOptions {
         }
     }
 
+    /// Returns the OP_RETURN output data, decoded as hex if 0x-prefixed, and otherwise as UTF-8 bytes.
+    fn to_data(&self) -> Result<Option<Vec<u8>>, CLIError> {
+        match &self.data {
+            Some(data) if data.starts_with("0x") => Ok(Some(hex::decode(&data[2..])?)),
+            Some(data) => Ok(Some(data.as_bytes().to_vec())),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the number of vanity search threads, which defaults to the number of CPUs.
+    fn to_threads(&self) -> usize {
+        self.threads.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|threads| threads.get())
+                .unwrap_or(1)
+        })
+    }
+
     /// Returns the derivation path with the specified account, chain, derivation, index, and path.
+    /// The bip44 and bip49 derivations use the fixed coin type, or else the SLIP-44 coin type of the network.
     /// If `default` is enabled, then return the default path if no derivation was provided.
-    fn to_derivation_path(&self, default: bool) -> Option<String> {
+    fn to_derivation_path<N: BitcoinNetwork>(&self, default: bool) -> Option<String> {
+        let coin_type = match self.coin_type {
+            Some(coin_type) => format!("{}'", coin_type),
+            None => N::HD_COIN_TYPE.to_string(),
+        };
         match self.derivation.as_str() {
             "bip32" => Some(format!("m/0'/0'/{}'", self.index)),
-            "bip44" => Some(format!("m/44'/0'/{}'/{}/{}", self.account, self.chain, self.index)),
-            "bip49" => Some(format!("m/49'/0'/{}'/{}/{}", self.account, self.chain, self.index)),
+            "bip44" => Some(format!("m/44'/{}/{}'/{}/{}", coin_type, self.account, self.chain, self.index)),
+            "bip49" => Some(format!("m/49'/{}/{}'/{}/{}", coin_type, self.account, self.chain, self.index)),
             "custom" => self.path.clone(),
             _ => match default {
                 true => Some(format!("m/0'/0'/{}'", self.index)),
@@ -725,6 +861,7 @@ impl CLI for BitcoinCLI {
         subcommand::IMPORT_BITCOIN,
         subcommand::IMPORT_HD_BITCOIN,
         subcommand::TRANSACTION_BITCOIN,
+        subcommand::VANITY_BITCOIN,
     ];
 
     /// Handle all CLI arguments and flags for Bitcoin
@@ -765,9 +902,14 @@ impl CLI for BitcoinCLI {
                 options.subcommand = Some("transaction".into());
                 options.parse(
                     arguments,
-                    &["createrawtransaction", "lock time", "signrawtransaction", "version"],
+                    &["createrawtransaction", "data", "lock time", "signrawtransaction", "version"],
                 );
             }
+            ("vanity", Some(arguments)) => {
+                options.subcommand = Some("vanity".into());
+                options.parse(arguments, &["format", "json", "network"]);
+                options.parse(arguments, &["prefix", "public", "threads"]);
+            }
             _ => {}
         };
 
@@ -777,10 +919,26 @@ impl CLI for BitcoinCLI {
     /// Generate the Bitcoin wallet and print the relevant fields
     #[cfg_attr(tarpaulin, skip)]
     fn print(options: Self::Options) -> Result<(), CLIError> {
-        fn output<N: BitcoinNetwork, W: BitcoinWordlist>(options: BitcoinOptions) -> Result<(), CLIError> {
+        // Bitcoin wallets keep the coin type 0' on both mainnet and testnet.
+        let options = BitcoinOptions {
+            coin_type: Some(0),
+            ..options
+        };
+        Self::print_networks::<BitcoinMainnet, BitcoinTestnet>(options)
+    }
+}
+
+impl BitcoinCLI {
+    /// Generate the wallet for a Bitcoin-derived network and print the relevant fields
+    /// (`M` and `T` are the mainnet and testnet of the network)
+    #[cfg_attr(tarpaulin, skip)]
+    pub(crate) fn print_networks<M: BitcoinNetwork, T: BitcoinNetwork>(options: BitcoinOptions) -> Result<(), CLIError> {
+        fn output<M: BitcoinNetwork, T: BitcoinNetwork, N: BitcoinNetwork, W: BitcoinWordlist>(
+            options: BitcoinOptions,
+        ) -> Result<(), CLIError> {
             let wallets =
                 match options.subcommand.as_ref().map(String::as_str) {
-                    Some("hd") => match options.to_derivation_path(true) {
+                    Some("hd") => match options.to_derivation_path::<N>(true) {
                         Some(path) => (0..options.count)
                             .flat_map(|_| {
                                 match BitcoinWallet::new_hd::<N, W, _>(
@@ -799,15 +957,15 @@ impl CLI for BitcoinCLI {
                     Some("import") => {
                         if let Some(private_key) = options.private {
                             vec![
-                                BitcoinWallet::from_private_key::<BitcoinMainnet>(&private_key, &options.format).or(
-                                    BitcoinWallet::from_private_key::<BitcoinTestnet>(&private_key, &options.format),
+                                BitcoinWallet::from_private_key::<M>(&private_key, &options.format).or(
+                                    BitcoinWallet::from_private_key::<T>(&private_key, &options.format),
                                 )?,
                             ]
                         } else if let Some(public_key) = options.public {
                             vec![BitcoinWallet::from_public_key::<N>(&public_key, &options.format)?]
                         } else if let Some(address) = options.address {
-                            vec![BitcoinWallet::from_address::<BitcoinMainnet>(&address)
-                                .or(BitcoinWallet::from_address::<BitcoinTestnet>(&address))?]
+                            vec![BitcoinWallet::from_address::<M>(&address)
+                                .or(BitcoinWallet::from_address::<T>(&address))?]
                         } else {
                             vec![]
                         }
@@ -816,7 +974,7 @@ impl CLI for BitcoinCLI {
                         if let Some(mnemonic) = options.mnemonic.clone() {
                             let password = &options.password.as_ref().map(String::as_str);
 
-                            match options.to_derivation_path(true) {
+                            match options.to_derivation_path::<N>(true) {
                                 Some(path) => vec![BitcoinWallet::from_mnemonic::<N, ChineseSimplified>(
                                     &mnemonic, password, &path,
                                 )
@@ -833,16 +991,16 @@ impl CLI for BitcoinCLI {
                             }
                         } else if let Some(extended_private_key) = options.extended_private_key.clone() {
                             let key = &extended_private_key;
-                            let path = &options.to_derivation_path(false);
+                            let path = &options.to_derivation_path::<N>(false);
 
-                            vec![BitcoinWallet::from_extended_private_key::<BitcoinMainnet>(key, path)
-                                .or(BitcoinWallet::from_extended_private_key::<BitcoinTestnet>(key, path))?]
+                            vec![BitcoinWallet::from_extended_private_key::<M>(key, path)
+                                .or(BitcoinWallet::from_extended_private_key::<T>(key, path))?]
                         } else if let Some(extended_public_key) = options.extended_public_key.clone() {
                             let key = &extended_public_key;
-                            let path = &options.to_derivation_path(false);
+                            let path = &options.to_derivation_path::<N>(false);
 
-                            vec![BitcoinWallet::from_extended_public_key::<BitcoinMainnet>(key, path)
-                                .or(BitcoinWallet::from_extended_public_key::<BitcoinTestnet>(key, path))?]
+                            vec![BitcoinWallet::from_extended_public_key::<M>(key, path)
+                                .or(BitcoinWallet::from_extended_public_key::<T>(key, path))?]
                         } else {
                             vec![]
                         }
@@ -854,14 +1012,15 @@ impl CLI for BitcoinCLI {
                             let inputs: &Vec<BitcoinInput> = &from_str(&transaction_inputs)?;
                             let outputs = transaction_outputs.replace(&['{', '}', '"', ' '][..], "");
                             let outputs: &Vec<&str> = &outputs.split(",").collect();
+                            let data = &options.to_data()?;
                             let version = options.version.unwrap_or(1);
                             let lock_time = options.lock_time.unwrap_or(0);
 
-                            vec![BitcoinWallet::to_raw_transaction::<BitcoinMainnet>(
-                                inputs, outputs, version, lock_time,
+                            vec![BitcoinWallet::to_raw_transaction::<M>(
+                                inputs, outputs, data, version, lock_time,
                             )
-                            .or(BitcoinWallet::to_raw_transaction::<BitcoinTestnet>(
-                                inputs, outputs, version, lock_time,
+                            .or(BitcoinWallet::to_raw_transaction::<T>(
+                                inputs, outputs, data, version, lock_time,
                             ))?]
                         } else if let (Some(transaction_hex), Some(transaction_inputs)) =
                             (options.transaction_hex.clone(), options.transaction_inputs.clone())
@@ -869,14 +1028,24 @@ impl CLI for BitcoinCLI {
                             let inputs: &Vec<BitcoinInput> = &from_str(&transaction_inputs)?;
 
                             vec![
-                                BitcoinWallet::to_signed_transaction::<BitcoinMainnet>(&transaction_hex, inputs).or(
-                                    BitcoinWallet::to_signed_transaction::<BitcoinTestnet>(&transaction_hex, inputs),
+                                BitcoinWallet::to_signed_transaction::<M>(&transaction_hex, inputs).or(
+                                    BitcoinWallet::to_signed_transaction::<T>(&transaction_hex, inputs),
                                 )?,
                             ]
                         } else {
                             vec![]
                         }
                     }
+                    Some("vanity") => match &options.prefix {
+                        Some(prefix) => vec![BitcoinWallet::new_vanity::<N, _>(
+                            &mut StdRng::from_entropy(),
+                            prefix,
+                            &options.format,
+                            &options.public,
+                            options.to_threads(),
+                        )?],
+                        None => vec![],
+                    },
                     _ => (0..options.count)
                         .flat_map(
                             |_| match BitcoinWallet::new::<N, _>(&mut StdRng::from_entropy(), &options.format) {
@@ -897,40 +1066,40 @@ impl CLI for BitcoinCLI {
 
         match options.language.as_str() {
             "chinese_simplified" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, ChineseSimplified>(options),
-                _ => output::<BitcoinMainnet, ChineseSimplified>(options),
+                "testnet" => output::<M, T, T, ChineseSimplified>(options),
+                _ => output::<M, T, M, ChineseSimplified>(options),
             },
             "chinese_traditional" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, ChineseTraditional>(options),
-                _ => output::<BitcoinMainnet, ChineseTraditional>(options),
+                "testnet" => output::<M, T, T, ChineseTraditional>(options),
+                _ => output::<M, T, M, ChineseTraditional>(options),
             },
             "english" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, English>(options),
-                _ => output::<BitcoinMainnet, English>(options),
+                "testnet" => output::<M, T, T, English>(options),
+                _ => output::<M, T, M, English>(options),
             },
             "french" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, French>(options),
-                _ => output::<BitcoinMainnet, French>(options),
+                "testnet" => output::<M, T, T, French>(options),
+                _ => output::<M, T, M, French>(options),
             },
             "italian" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, Italian>(options),
-                _ => output::<BitcoinMainnet, Italian>(options),
+                "testnet" => output::<M, T, T, Italian>(options),
+                _ => output::<M, T, M, Italian>(options),
             },
             "japanese" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, Japanese>(options),
-                _ => output::<BitcoinMainnet, Japanese>(options),
+                "testnet" => output::<M, T, T, Japanese>(options),
+                _ => output::<M, T, M, Japanese>(options),
             },
             "korean" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, Korean>(options),
-                _ => output::<BitcoinMainnet, Korean>(options),
+                "testnet" => output::<M, T, T, Korean>(options),
+                _ => output::<M, T, M, Korean>(options),
             },
             "spanish" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, Spanish>(options),
-                _ => output::<BitcoinMainnet, Spanish>(options),
+                "testnet" => output::<M, T, T, Spanish>(options),
+                _ => output::<M, T, M, Spanish>(options),
             },
             _ => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, English>(options),
-                _ => output::<BitcoinMainnet, English>(options),
+                "testnet" => output::<M, T, T, English>(options),
+                _ => output::<M, T, M, English>(options),
             },
         }
     }
@@ -0,0 +1,34 @@
+use crate::bitcoin::{BitcoinCashMainnet, BitcoinCashTestnet};
+use crate::cli::bitcoin::{BitcoinCLI, BitcoinOptions};
+use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
+
+use clap::ArgMatches;
+
+pub struct BitcoinCashCLI;
+
+impl CLI for BitcoinCashCLI {
+    type Options = BitcoinOptions;
+
+    const NAME: NameType = "bitcoin-cash";
+    const ABOUT: AboutType = "Generates a Bitcoin Cash wallet (include -h for more options)";
+    const FLAGS: &'static [FlagType] = &[flag::JSON];
+    const OPTIONS: &'static [OptionType] = &[option::COUNT, option::FORMAT_BITCOIN_CASH, option::NETWORK_BITCOIN];
+    const SUBCOMMANDS: &'static [SubCommandType] = &[
+        subcommand::HD_BITCOIN,
+        subcommand::IMPORT_BITCOIN_CASH,
+        subcommand::IMPORT_HD_BITCOIN,
+        subcommand::TRANSACTION_BITCOIN,
+    ];
+
+    /// Handle all CLI arguments and flags for Bitcoin Cash
+    #[cfg_attr(tarpaulin, skip)]
+    fn parse(arguments: &ArgMatches) -> Result<Self::Options, CLIError> {
+        BitcoinCLI::parse(arguments)
+    }
+
+    /// Generate the Bitcoin Cash wallet and print the relevant fields
+    #[cfg_attr(tarpaulin, skip)]
+    fn print(options: Self::Options) -> Result<(), CLIError> {
+        BitcoinCLI::print_networks::<BitcoinCashMainnet, BitcoinCashTestnet>(options)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::bitcoin::{DogecoinMainnet, DogecoinTestnet};
+use crate::cli::bitcoin::{BitcoinCLI, BitcoinOptions};
+use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
+
+use clap::ArgMatches;
+
+pub struct DogecoinCLI;
+
+impl CLI for DogecoinCLI {
+    type Options = BitcoinOptions;
+
+    const NAME: NameType = "dogecoin";
+    const ABOUT: AboutType = "Generates a Dogecoin wallet (include -h for more options)";
+    const FLAGS: &'static [FlagType] = &[flag::JSON];
+    const OPTIONS: &'static [OptionType] = &[option::COUNT, option::FORMAT_DOGECOIN, option::NETWORK_BITCOIN];
+    const SUBCOMMANDS: &'static [SubCommandType] = &[
+        subcommand::HD_DOGECOIN,
+        subcommand::IMPORT_DOGECOIN,
+        subcommand::IMPORT_HD_DOGECOIN,
+        subcommand::TRANSACTION_BITCOIN,
+    ];
+
+    /// Handle all CLI arguments and flags for Dogecoin
+    #[cfg_attr(tarpaulin, skip)]
+    fn parse(arguments: &ArgMatches) -> Result<Self::Options, CLIError> {
+        BitcoinCLI::parse(arguments)
+    }
+
+    /// Generate the Dogecoin wallet and print the relevant fields
+    #[cfg_attr(tarpaulin, skip)]
+    fn print(options: Self::Options) -> Result<(), CLIError> {
+        BitcoinCLI::print_networks::<DogecoinMainnet, DogecoinTestnet>(options)
+    }
+}
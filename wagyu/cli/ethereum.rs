@@ -1,13 +1,14 @@
-use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
+use crate::cli::{flag, option, print_vanity_progress, subcommand, types::*, CLIError, CLI};
 use crate::ethereum::{
-    wordlist::*, EthereumAddress, EthereumAmount, EthereumDerivationPath, EthereumExtendedPrivateKey,
-    EthereumExtendedPublicKey, EthereumFormat, EthereumMnemonic, EthereumNetwork, EthereumPrivateKey,
-    EthereumPublicKey, EthereumTransaction, EthereumTransactionParameters, Goerli, Kovan, Mainnet as EthereumMainnet,
-    Rinkeby, Ropsten,
+    wordlist::*, AbiFunction, CustomChain, EthereumAbi, EthereumAccessListItem, EthereumAddress, EthereumAmount,
+    EthereumDerivationPath, EthereumExtendedPrivateKey, EthereumExtendedPublicKey, EthereumFormat, EthereumMnemonic,
+    EthereumNetwork, EthereumPrivateKey, EthereumPublicKey, EthereumTransaction, EthereumTransactionParameters,
+    EthereumTransactionType, EthereumTypedData, EthereumVanity, Goerli, Holesky, Kovan, Mainnet as EthereumMainnet,
+    Rinkeby, Ropsten, Sepolia, Token, UserOperation, ERC20, ERC721,
 };
 use crate::model::{
     ExtendedPrivateKey, ExtendedPublicKey, Mnemonic, MnemonicCount, MnemonicExtended, Network, PrivateKey, PublicKey,
-    Transaction,
+    Transaction, TransactionError, VanityProgress,
 };
 
 use clap::{ArgMatches, Values};
@@ -15,7 +16,7 @@ use colored::*;
 use rand::{rngs::StdRng, Rng};
 use rand_core::SeedableRng;
 use serde::{Deserialize, Serialize};
-use serde_json::from_str;
+use serde_json::{from_str, Value};
 use std::{fmt, fmt::Display, str::FromStr};
 
 /// Represents a generic wallet to output
@@ -34,6 +35,8 @@ struct EthereumWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub private_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_private_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub public_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub address: Option<String>,
@@ -43,6 +46,26 @@ struct EthereumWallet {
     pub network: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_hex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain_separator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_operation_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_operation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
 }
 
 impl EthereumWallet {
@@ -58,6 +81,60 @@ impl EthereumWallet {
         })
     }
 
+    pub fn new_vanity<R: Rng>(
+        rng: &mut R,
+        prefix: &str,
+        case_sensitive: bool,
+        public_key: &Option<String>,
+        threads: usize,
+    ) -> Result<Self, CLIError> {
+        let vanity = EthereumVanity::new(prefix, case_sensitive)?;
+        let difficulty = vanity.difficulty();
+        eprintln!(
+            "\n      {}           {:.0} keys",
+            "Difficulty".cyan().bold(),
+            difficulty
+        );
+        let progress = |progress: &VanityProgress| print_vanity_progress(progress, difficulty);
+
+        let (wallet, attempts) = match public_key {
+            // Split-key search, where only the owner of the public key can derive the private key
+            Some(public_key) => {
+                let public_key = EthereumPublicKey::from_str(public_key)?;
+                let (partial_private_key, attempts) =
+                    vanity.search_with_public_key(&public_key, rng, threads, progress)?;
+                let public_key = EthereumVanity::combine_public_key(&public_key, &partial_private_key)?;
+                let address = public_key.to_address(&EthereumFormat::Standard)?;
+                let wallet = Self {
+                    partial_private_key: Some(partial_private_key.to_string()),
+                    public_key: Some(public_key.to_string()),
+                    address: Some(address.to_string()),
+                    ..Default::default()
+                };
+                (wallet, attempts)
+            }
+            None => {
+                let (private_key, attempts) = vanity.search(rng, threads, progress)?;
+                let public_key = private_key.to_public_key();
+                let address = public_key.to_address(&EthereumFormat::Standard)?;
+                let wallet = Self {
+                    private_key: Some(private_key.to_string()),
+                    public_key: Some(public_key.to_string()),
+                    address: Some(address.to_string()),
+                    ..Default::default()
+                };
+                (wallet, attempts)
+            }
+        };
+
+        eprintln!(
+            "\r\x1b[K      {}             {} keys",
+            "Searched".cyan().bold(),
+            attempts
+        );
+        Ok(wallet)
+    }
+
     pub fn new_hd<N: EthereumNetwork, W: EthereumWordlist, R: Rng>(
         rng: &mut R,
         word_count: u8,
@@ -186,13 +263,73 @@ impl EthereumWallet {
     }
 
     pub fn to_raw_transaction<N: EthereumNetwork>(parameters: EthereumInput) -> Result<Self, CLIError> {
+        // Infer the transaction type from the given fee fields, unless it is specified.
+        let transaction_type = match (
+            parameters.transaction_type,
+            &parameters.max_fee_per_gas,
+            &parameters.access_list,
+        ) {
+            (Some(transaction_type), _, _) => EthereumTransactionType::from_byte(transaction_type)?,
+            (None, Some(_), _) => EthereumTransactionType::FeeMarket,
+            (None, None, Some(_)) => EthereumTransactionType::AccessList,
+            (None, None, None) => EthereumTransactionType::Legacy,
+        };
+
+        // Typed transactions commit to their chain id, so it must be given rather than assumed to be mainnet.
+        if transaction_type != EthereumTransactionType::Legacy && parameters.chain_id.is_none() {
+            return Err(TransactionError::Message("typed transactions require a chainId field".into()).into());
+        }
+
+        let mut access_list = vec![];
+        for item in parameters.access_list.unwrap_or_default() {
+            let mut storage_keys = vec![];
+            for storage_key in item.storage_keys {
+                let storage_key = hex::decode(storage_key.trim_start_matches("0x"))?;
+                if storage_key.len() != 32 {
+                    return Err(
+                        TransactionError::Message(format!("invalid storage key length {}", storage_key.len())).into(),
+                    );
+                }
+                let mut key = [0u8; 32];
+                key.copy_from_slice(&storage_key);
+                storage_keys.push(key);
+            }
+            access_list.push(EthereumAccessListItem {
+                address: EthereumAddress::from_str(&item.address)?,
+                storage_keys,
+            });
+        }
+
         let transaction_parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(&parameters.to)?,
+            receiver: match parameters.to.as_ref().map(String::as_str) {
+                Some("") | None => None,
+                Some(receiver) => Some(EthereumAddress::from_str(receiver)?),
+            },
             amount: EthereumAmount::from_wei(&parameters.value)?,
             gas: EthereumAmount::u256_from_str(&parameters.gas)?,
-            gas_price: EthereumAmount::from_wei(&parameters.gas_price)?,
+            gas_price: match (transaction_type, parameters.gas_price) {
+                (_, Some(gas_price)) => EthereumAmount::from_wei(&gas_price)?,
+                (EthereumTransactionType::FeeMarket, None) => EthereumAmount::from_wei("0")?,
+                (_, None) => {
+                    return Err(TransactionError::Message(
+                        "legacy and EIP-2930 transactions require a gasPrice field".into(),
+                    )
+                    .into())
+                }
+            },
             nonce: EthereumAmount::u256_from_str(&parameters.nonce.to_string())?,
-            data: parameters.data.unwrap_or("".to_string()).as_bytes().to_vec(),
+            data: match parameters.data {
+                Some(data) if data.starts_with("0x") => hex::decode(&data[2..])?,
+                Some(data) => data.as_bytes().to_vec(),
+                None => vec![],
+            },
+            transaction_type,
+            max_fee_per_gas: EthereumAmount::from_wei(&parameters.max_fee_per_gas.unwrap_or("0".to_string()))?,
+            max_priority_fee_per_gas: EthereumAmount::from_wei(
+                &parameters.max_priority_fee_per_gas.unwrap_or("0".to_string()),
+            )?,
+            access_list,
+            chain_id: parameters.chain_id,
         };
 
         let raw_transaction = EthereumTransaction::<N>::new(&transaction_parameters)?;
@@ -207,6 +344,7 @@ impl EthereumWallet {
     pub fn to_signed_transaction<N: EthereumNetwork>(
         transaction_hex: String,
         private_key: String,
+        chain_id: Option<u64>,
     ) -> Result<Self, CLIError> {
         let transaction_bytes = match &transaction_hex[0..2] {
             "0x" => hex::decode(&transaction_hex[2..])?,
@@ -216,11 +354,120 @@ impl EthereumWallet {
         let private_key = EthereumPrivateKey::from_str(&private_key)?;
 
         let mut transaction = EthereumTransaction::<N>::from_transaction_bytes(&transaction_bytes)?;
+        if let (Some(chain_id), Some(transaction_chain_id)) = (chain_id, transaction.chain_id()) {
+            if chain_id != transaction_chain_id {
+                return Err(TransactionError::InvalidChainId(transaction_chain_id).into());
+            }
+        }
         transaction = transaction.sign(&private_key)?;
 
         Ok(Self {
             transaction_id: Some(transaction.to_transaction_id()?.to_string()),
             transaction_hex: Some(format!("0x{}", hex::encode(&transaction.to_transaction_bytes()?))),
+            contract_address: transaction.contract_address()?.map(|address| address.to_string()),
+            ..Default::default()
+        })
+    }
+
+    pub fn to_typed_data_signature(typed_data: &str, private_key: Option<String>) -> Result<Self, CLIError> {
+        let typed_data = EthereumTypedData::from_str(typed_data)?;
+        let domain_separator = Some(format!("0x{}", hex::encode(typed_data.domain_separator()?)));
+        let signing_hash = Some(format!("0x{}", hex::encode(typed_data.to_signing_hash()?)));
+
+        match private_key {
+            Some(private_key) => {
+                let private_key = EthereumPrivateKey::from_str(&private_key)?;
+                Ok(Self {
+                    address: Some(private_key.to_address(&EthereumFormat::Standard)?.to_string()),
+                    domain_separator,
+                    signing_hash,
+                    signature: Some(format!("0x{}", hex::encode(typed_data.sign(&private_key)?))),
+                    ..Default::default()
+                })
+            }
+            None => Ok(Self {
+                domain_separator,
+                signing_hash,
+                ..Default::default()
+            }),
+        }
+    }
+
+    pub fn to_user_operation_signature(
+        user_operation: &str,
+        entry_point: Option<String>,
+        chain_id: Option<u64>,
+        private_key: Option<String>,
+    ) -> Result<Self, CLIError> {
+        let mut user_operation = UserOperation::from_str(user_operation)?;
+        let entry_point = match entry_point {
+            Some(entry_point) => EthereumAddress::from_str(&entry_point)?,
+            None => user_operation.version.entry_point(),
+        };
+        let chain_id = chain_id.unwrap_or(1);
+        let user_operation_hash = user_operation.to_user_op_hash(&entry_point, chain_id)?;
+
+        match private_key {
+            Some(private_key) => {
+                let private_key = EthereumPrivateKey::from_str(&private_key)?;
+                user_operation.signature = user_operation.sign(&private_key, &entry_point, chain_id)?;
+                Ok(Self {
+                    address: Some(private_key.to_address(&EthereumFormat::Standard)?.to_string()),
+                    user_operation_hash: Some(format!("0x{}", hex::encode(user_operation_hash))),
+                    signature: Some(format!("0x{}", hex::encode(&user_operation.signature))),
+                    user_operation: Some(user_operation.to_json()?),
+                    ..Default::default()
+                })
+            }
+            None => Ok(Self {
+                user_operation_hash: Some(format!("0x{}", hex::encode(user_operation_hash))),
+                ..Default::default()
+            }),
+        }
+    }
+
+    pub fn to_abi_call(call: &str, arguments: &[String]) -> Result<Self, CLIError> {
+        let address = |index: usize| EthereumAddress::from_str(&arguments[index]);
+        let amount = |index: usize| EthereumAmount::u256_from_str(&arguments[index]);
+
+        let (function, data) = match call {
+            "decode" => {
+                let abi = EthereumAbi::from_str(&std::fs::read_to_string(&arguments[0])?)?;
+                let data = hex::decode(arguments[1].trim_start_matches("0x"))?;
+                let (function, _) = abi.decode_input(&data)?;
+                (function.clone(), data)
+            }
+            "encode" => {
+                let function = AbiFunction::from_signature(&arguments[0])?;
+                let values: Vec<Value> = from_str(&arguments[1])?;
+                let mut tokens = vec![];
+                for (input, value) in function.inputs.iter().zip(values.iter()) {
+                    tokens.push(Token::from_json(&input.to_param_type()?, value)?);
+                }
+                let data = function.encode_input(&tokens)?;
+                (function, data)
+            }
+            "erc20-approve" => (
+                AbiFunction::from_signature("approve(address,uint256)")?,
+                ERC20::approve(&address(0)?, amount(1)?),
+            ),
+            "erc20-transfer" => (
+                AbiFunction::from_signature("transfer(address,uint256)")?,
+                ERC20::transfer(&address(0)?, amount(1)?),
+            ),
+            "erc721-transfer" => (
+                AbiFunction::from_signature("safeTransferFrom(address,address,uint256)")?,
+                ERC721::safe_transfer_from(&address(0)?, &address(1)?, amount(2)?),
+            ),
+            _ => return Ok(Self::default()),
+        };
+
+        let arguments: Vec<String> = function.decode_input(&data)?.iter().map(Token::to_string).collect();
+        Ok(Self {
+            function: Some(function.signature()?),
+            selector: Some(format!("0x{}", hex::encode(function.selector()?))),
+            arguments: Some(arguments.join(", ")),
+            data: Some(format!("0x{}", hex::encode(&data))),
             ..Default::default()
         })
     }
@@ -262,6 +509,14 @@ impl Display for EthereumWallet {
                 Some(private_key) => format!("      {}          {}\n", "Private Key".cyan().bold(), private_key),
                 _ => "".to_owned(),
             },
+            match &self.partial_private_key {
+                Some(partial_private_key) => format!(
+                    "      {}  {}\n",
+                    "Partial Private Key".cyan().bold(),
+                    partial_private_key
+                ),
+                _ => "".to_owned(),
+            },
             match &self.public_key {
                 Some(public_key) => format!("      {}           {}\n", "Public Key".cyan().bold(), public_key),
                 _ => "".to_owned(),
@@ -284,6 +539,56 @@ impl Display for EthereumWallet {
                 }
                 _ => "".to_owned(),
             },
+            match &self.contract_address {
+                Some(contract_address) => {
+                    format!("      {}     {}\n", "Contract Address".cyan().bold(), contract_address)
+                }
+                _ => "".to_owned(),
+            },
+            match &self.domain_separator {
+                Some(domain_separator) => {
+                    format!("      {}     {}\n", "Domain Separator".cyan().bold(), domain_separator)
+                }
+                _ => "".to_owned(),
+            },
+            match &self.signing_hash {
+                Some(signing_hash) => format!("      {}         {}\n", "Signing Hash".cyan().bold(), signing_hash),
+                _ => "".to_owned(),
+            },
+            match &self.user_operation_hash {
+                Some(user_operation_hash) => format!(
+                    "      {}  {}\n",
+                    "User Operation Hash".cyan().bold(),
+                    user_operation_hash
+                ),
+                _ => "".to_owned(),
+            },
+            match &self.signature {
+                Some(signature) => format!("      {}            {}\n", "Signature".cyan().bold(), signature),
+                _ => "".to_owned(),
+            },
+            match &self.user_operation {
+                Some(user_operation) => {
+                    format!("      {}       {}\n", "User Operation".cyan().bold(), user_operation)
+                }
+                _ => "".to_owned(),
+            },
+            match &self.function {
+                Some(function) => format!("      {}             {}\n", "Function".cyan().bold(), function),
+                _ => "".to_owned(),
+            },
+            match &self.selector {
+                Some(selector) => format!("      {}             {}\n", "Selector".cyan().bold(), selector),
+                _ => "".to_owned(),
+            },
+            match &self.arguments {
+                Some(arguments) => format!("      {}            {}\n", "Arguments".cyan().bold(), arguments),
+                _ => "".to_owned(),
+            },
+            match &self.data {
+                Some(data) => format!("      {}                 {}\n", "Data".cyan().bold(), data),
+                _ => "".to_owned(),
+            },
         ]
         .concat();
 
@@ -296,13 +601,31 @@ impl Display for EthereumWallet {
 /// Represents parameters for an Ethereum transaction input
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EthereumInput {
-    pub to: String,
+    pub to: Option<String>,
     pub value: String,
     pub gas: String,
     #[serde(rename(deserialize = "gasPrice"))]
-    pub gas_price: String,
+    pub gas_price: Option<String>,
+    #[serde(rename(deserialize = "maxFeePerGas"))]
+    pub max_fee_per_gas: Option<String>,
+    #[serde(rename(deserialize = "maxPriorityFeePerGas"))]
+    pub max_priority_fee_per_gas: Option<String>,
+    #[serde(rename(deserialize = "accessList"))]
+    pub access_list: Option<Vec<EthereumAccessListInput>>,
+    #[serde(rename(deserialize = "type"))]
+    pub transaction_type: Option<u8>,
     pub nonce: u64,
     pub data: Option<String>,
+    #[serde(rename(deserialize = "chainId"))]
+    pub chain_id: Option<u64>,
+}
+
+/// Represents an access list entry for an Ethereum transaction input
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EthereumAccessListInput {
+    pub address: String,
+    #[serde(rename(deserialize = "storageKeys"))]
+    pub storage_keys: Vec<String>,
 }
 
 /// Represents options for an Ethereum wallet
@@ -332,6 +655,19 @@ pub struct EthereumOptions {
     transaction_parameters: Option<String>,
     transaction_private_key: Option<String>,
     network: Option<String>,
+    // Sign Typed Data subcommand
+    typed_data: Option<String>,
+    // Sign User Operation subcommand
+    chain_id: Option<u64>,
+    entry_point: Option<String>,
+    user_operation: Option<String>,
+    // ABI subcommand
+    abi_call: Option<String>,
+    abi_arguments: Vec<String>,
+    // Vanity subcommand
+    case_sensitive: bool,
+    prefix: Option<String>,
+    threads: Option<usize>,
 }
 
 impl Default for EthereumOptions {
@@ -361,6 +697,19 @@ impl Default for EthereumOptions {
             transaction_parameters: None,
             transaction_private_key: None,
             network: None,
+            // Sign Typed Data subcommand
+            typed_data: None,
+            // Sign User Operation subcommand
+            chain_id: None,
+            entry_point: None,
+            user_operation: None,
+            // ABI subcommand
+            abi_call: None,
+            abi_arguments: vec![],
+            // Vanity subcommand
+            case_sensitive: false,
+            prefix: None,
+            threads: None,
         }
     }
 }
@@ -369,9 +718,15 @@ impl EthereumOptions {
     fn parse(&mut self, arguments: &ArgMatches, options: &[&str]) {
         options.iter().for_each(|option| match *option {
             "address" => self.address(arguments.value_of(option)),
+            "case sensitive" => self.case_sensitive(arguments.is_present(option)),
+            "chain id" => self.chain_id(clap::value_t!(arguments.value_of(*option), u64).ok()),
             "count" => self.count(clap::value_t!(arguments.value_of(*option), usize).ok()),
             "createrawtransaction" => self.create_raw_transaction(arguments.value_of(option)),
+            "decode" | "encode" | "erc20-approve" | "erc20-transfer" | "erc721-transfer" => {
+                self.abi_call(option, arguments.values_of(option))
+            }
             "derivation" => self.derivation(arguments.value_of(option)),
+            "entry point" => self.entry_point(arguments.value_of(option)),
             "extended private" => self.extended_private(arguments.value_of(option)),
             "extended public" => self.extended_public(arguments.value_of(option)),
             "json" => self.json(arguments.is_present(option)),
@@ -381,14 +736,27 @@ impl EthereumOptions {
             "mnemonic" => self.mnemonic(arguments.value_of(option)),
             "network" => self.network(arguments.value_of(option)),
             "password" => self.password(arguments.value_of(option)),
+            "prefix" => self.prefix(arguments.value_of(option)),
             "private" => self.private(arguments.value_of(option)),
             "public" => self.public(arguments.value_of(option)),
             "signrawtransaction" => self.sign_raw_transaction(arguments.values_of(option)),
+            "threads" => self.threads(clap::value_t!(arguments.value_of(*option), usize).ok()),
+            "typed data" => self.typed_data(arguments.value_of(option)),
+            "user operation" => self.user_operation(arguments.value_of(option)),
             "word count" => self.word_count(clap::value_t!(arguments.value_of(*option), u8).ok()),
             _ => (),
         });
     }
 
+    /// Sets `abi_call` and `abi_arguments` to the specified contract call and its values, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn abi_call(&mut self, call: &str, argument: Option<Values>) {
+        if let Some(values) = argument {
+            self.abi_call = Some(call.to_string());
+            self.abi_arguments = values.map(String::from).collect();
+        }
+    }
+
     /// Imports a wallet for the specified address, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn address(&mut self, argument: Option<&str>) {
@@ -397,6 +765,19 @@ impl EthereumOptions {
         }
     }
 
+    /// Sets `case_sensitive` to the specified boolean value, overriding its previous state.
+    fn case_sensitive(&mut self, argument: bool) {
+        self.case_sensitive = argument;
+    }
+
+    /// Sets `chain_id` to the specified chain id, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn chain_id(&mut self, argument: Option<u64>) {
+        if let Some(chain_id) = argument {
+            self.chain_id = Some(chain_id);
+        }
+    }
+
     /// Sets `count` to the specified count, overriding its previous state.
     fn count(&mut self, argument: Option<usize>) {
         if let Some(count) = argument {
@@ -430,6 +811,14 @@ impl EthereumOptions {
         };
     }
 
+    /// Sets `entry_point` to the specified entry point address, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn entry_point(&mut self, argument: Option<&str>) {
+        if let Some(entry_point) = argument {
+            self.entry_point = Some(entry_point.to_string());
+        }
+    }
+
     /// Sets `extended_private_key` to the specified extended private key, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn extended_private(&mut self, argument: Option<&str>) {
@@ -507,6 +896,14 @@ impl EthereumOptions {
         }
     }
 
+    /// Sets `prefix` to the specified vanity address prefix, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn prefix(&mut self, argument: Option<&str>) {
+        if let Some(prefix) = argument {
+            self.prefix = Some(prefix.to_string());
+        }
+    }
+
     /// Imports a wallet for the specified private key, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn private(&mut self, argument: Option<&str>) {
@@ -533,6 +930,30 @@ impl EthereumOptions {
         }
     }
 
+    /// Sets `threads` to the specified number of vanity search threads, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn threads(&mut self, argument: Option<usize>) {
+        if let Some(threads) = argument {
+            self.threads = Some(threads);
+        }
+    }
+
+    /// Sets `typed_data` to the specified typed data file path, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn typed_data(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.typed_data = Some(path.to_string());
+        }
+    }
+
+    /// Sets `user_operation` to the specified user operation file path, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn user_operation(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.user_operation = Some(path.to_string());
+        }
+    }
+
     /// Sets `word_count` to the specified word count, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn word_count(&mut self, argument: Option<u8>) {
@@ -541,6 +962,15 @@ impl EthereumOptions {
         }
     }
 
+    /// Returns the number of vanity search threads, which defaults to the number of CPUs.
+    fn to_threads(&self) -> usize {
+        self.threads.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|threads| threads.get())
+                .unwrap_or(1)
+        })
+    }
+
     /// Returns the derivation path with the specified account, chain, derivation, index, and path.
     /// If `default` is enabled, then return the default path if no derivation was provided.
     fn to_derivation_path(&self, default: bool) -> Option<String> {
@@ -583,10 +1013,14 @@ impl CLI for EthereumCLI {
     const NAME: NameType = "ethereum";
     const OPTIONS: &'static [OptionType] = &[option::COUNT];
     const SUBCOMMANDS: &'static [SubCommandType] = &[
+        subcommand::ABI_ETHEREUM,
         subcommand::HD_ETHEREUM,
         subcommand::IMPORT_ETHEREUM,
         subcommand::IMPORT_HD_ETHEREUM,
+        subcommand::SIGN_TYPED_DATA_ETHEREUM,
+        subcommand::SIGN_USER_OPERATION_ETHEREUM,
         subcommand::TRANSACTION_ETHEREUM,
+        subcommand::VANITY_ETHEREUM,
     ];
 
     /// Handle all CLI arguments and flags for Ethereum
@@ -596,6 +1030,14 @@ impl CLI for EthereumCLI {
         options.parse(arguments, &["count", "json"]);
 
         match arguments.subcommand() {
+            ("abi", Some(arguments)) => {
+                options.subcommand = Some("abi".into());
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &["decode", "encode", "erc20-approve", "erc20-transfer", "erc721-transfer"],
+                );
+            }
             ("hd", Some(arguments)) => {
                 options.subcommand = Some("hd".into());
                 options.parse(arguments, &["count", "json"]);
@@ -624,10 +1066,25 @@ impl CLI for EthereumCLI {
                     ],
                 );
             }
+            ("sign-typed-data", Some(arguments)) => {
+                options.subcommand = Some("sign-typed-data".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["private", "typed data"]);
+            }
+            ("sign-user-operation", Some(arguments)) => {
+                options.subcommand = Some("sign-user-operation".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["chain id", "entry point", "private", "user operation"]);
+            }
             ("transaction", Some(arguments)) => {
                 options.subcommand = Some("transaction".into());
                 options.parse(arguments, &["createrawtransaction", "network", "signrawtransaction"]);
             }
+            ("vanity", Some(arguments)) => {
+                options.subcommand = Some("vanity".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["case sensitive", "prefix", "public", "threads"]);
+            }
             _ => {}
         };
 
@@ -639,6 +1096,10 @@ impl CLI for EthereumCLI {
     fn print(options: Self::Options) -> Result<(), CLIError> {
         fn output<N: EthereumNetwork, W: EthereumWordlist>(options: EthereumOptions) -> Result<(), CLIError> {
             let wallets = match options.subcommand.as_ref().map(String::as_str) {
+                Some("abi") => match options.abi_call.clone() {
+                    Some(call) => vec![EthereumWallet::to_abi_call(&call, &options.abi_arguments)?],
+                    None => vec![],
+                },
                 Some("hd") => {
                     let password = options.password.as_ref().map(String::as_str);
                     (0..options.count)
@@ -720,46 +1181,96 @@ impl CLI for EthereumCLI {
                         vec![]
                     }
                 }
+                Some("sign-typed-data") => match options.typed_data.clone() {
+                    Some(path) => vec![EthereumWallet::to_typed_data_signature(
+                        &std::fs::read_to_string(path)?,
+                        options.private.clone(),
+                    )?],
+                    None => vec![],
+                },
+                Some("sign-user-operation") => match options.user_operation.clone() {
+                    Some(path) => vec![EthereumWallet::to_user_operation_signature(
+                        &std::fs::read_to_string(path)?,
+                        options.entry_point.clone(),
+                        options.chain_id,
+                        options.private.clone(),
+                    )?],
+                    None => vec![],
+                },
                 Some("transaction") => {
                     if let Some(transaction_parameters) = options.transaction_parameters.clone() {
                         let parameters: EthereumInput = from_str(&transaction_parameters)?;
 
-                        // Note: Raw Ethereum transactions are network agnostic
-                        vec![EthereumWallet::to_raw_transaction::<EthereumMainnet>(parameters)?]
-                    } else if let (Some(transaction_hex), Some(transaction_private_key)) =
+                        // Note: Raw legacy Ethereum transactions are network agnostic, unless a custom chain id is given
+                        match parameters.chain_id {
+                            Some(_) => vec![EthereumWallet::to_raw_transaction::<CustomChain>(parameters)?],
+                            None => vec![EthereumWallet::to_raw_transaction::<EthereumMainnet>(parameters)?],
+                        }
+                    } else if let (Some(transaction_hex), Some(private_key)) =
                         (options.transaction_hex.clone(), options.transaction_private_key.clone())
                     {
                         match options.network.as_ref().map(String::as_str) {
                             Some(EthereumMainnet::NAME) => vec![EthereumWallet::to_signed_transaction::<
                                 EthereumMainnet,
-                            >(
-                                transaction_hex, transaction_private_key
-                            )?],
+                            >(transaction_hex, private_key, None)?],
                             Some(Goerli::NAME) => vec![EthereumWallet::to_signed_transaction::<Goerli>(
                                 transaction_hex,
-                                transaction_private_key,
+                                private_key,
+                                None,
+                            )?],
+                            Some(Holesky::NAME) => vec![EthereumWallet::to_signed_transaction::<Holesky>(
+                                transaction_hex,
+                                private_key,
+                                None,
                             )?],
                             Some(Kovan::NAME) => vec![EthereumWallet::to_signed_transaction::<Kovan>(
                                 transaction_hex,
-                                transaction_private_key,
+                                private_key,
+                                None,
                             )?],
                             Some(Rinkeby::NAME) => vec![EthereumWallet::to_signed_transaction::<Rinkeby>(
                                 transaction_hex,
-                                transaction_private_key,
+                                private_key,
+                                None,
                             )?],
                             Some(Ropsten::NAME) => vec![EthereumWallet::to_signed_transaction::<Ropsten>(
                                 transaction_hex,
-                                transaction_private_key,
+                                private_key,
+                                None,
+                            )?],
+                            Some(Sepolia::NAME) => vec![EthereumWallet::to_signed_transaction::<Sepolia>(
+                                transaction_hex,
+                                private_key,
+                                None,
                             )?],
+                            // A numeric network is the chain id of a custom chain
+                            Some(network) if network.parse::<u64>().is_ok() => {
+                                vec![EthereumWallet::to_signed_transaction::<CustomChain>(
+                                    transaction_hex,
+                                    private_key,
+                                    Some(network.parse::<u64>()?),
+                                )?]
+                            }
                             _ => vec![EthereumWallet::to_signed_transaction::<EthereumMainnet>(
                                 transaction_hex,
-                                transaction_private_key,
+                                private_key,
+                                None,
                             )?],
                         }
                     } else {
                         vec![]
                     }
                 }
+                Some("vanity") => match &options.prefix {
+                    Some(prefix) => vec![EthereumWallet::new_vanity(
+                        &mut StdRng::from_entropy(),
+                        prefix,
+                        options.case_sensitive,
+                        &options.public,
+                        options.to_threads(),
+                    )?],
+                    None => vec![],
+                },
                 _ => (0..options.count)
                     .flat_map(|_| match EthereumWallet::new::<_>(&mut StdRng::from_entropy()) {
                         Ok(wallet) => vec![wallet],
@@ -0,0 +1,34 @@
+use crate::bitcoin::{LitecoinMainnet, LitecoinTestnet};
+use crate::cli::bitcoin::{BitcoinCLI, BitcoinOptions};
+use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
+
+use clap::ArgMatches;
+
+pub struct LitecoinCLI;
+
+impl CLI for LitecoinCLI {
+    type Options = BitcoinOptions;
+
+    const NAME: NameType = "litecoin";
+    const ABOUT: AboutType = "Generates a Litecoin wallet (include -h for more options)";
+    const FLAGS: &'static [FlagType] = &[flag::JSON];
+    const OPTIONS: &'static [OptionType] = &[option::COUNT, option::FORMAT_BITCOIN, option::NETWORK_BITCOIN];
+    const SUBCOMMANDS: &'static [SubCommandType] = &[
+        subcommand::HD_BITCOIN,
+        subcommand::IMPORT_BITCOIN,
+        subcommand::IMPORT_HD_BITCOIN,
+        subcommand::TRANSACTION_BITCOIN,
+    ];
+
+    /// Handle all CLI arguments and flags for Litecoin
+    #[cfg_attr(tarpaulin, skip)]
+    fn parse(arguments: &ArgMatches) -> Result<Self::Options, CLIError> {
+        BitcoinCLI::parse(arguments)
+    }
+
+    /// Generate the Litecoin wallet and print the relevant fields
+    #[cfg_attr(tarpaulin, skip)]
+    fn print(options: Self::Options) -> Result<(), CLIError> {
+        BitcoinCLI::print_networks::<LitecoinMainnet, LitecoinTestnet>(options)
+    }
+}
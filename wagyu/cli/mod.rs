@@ -1,10 +1,13 @@
 use crate::model::{
     AddressError, AmountError, DerivationPathError, ExtendedPrivateKeyError, ExtendedPublicKeyError, MnemonicError,
-    PrivateKeyError, PublicKeyError, TransactionError,
+    PrivateKeyError, PublicKeyError, TransactionError, VanityError, VanityProgress,
 };
 
 pub mod bitcoin;
+pub mod bitcoin_cash;
+pub mod dogecoin;
 pub mod ethereum;
+pub mod litecoin;
 pub mod monero;
 pub mod zcash;
 
@@ -14,6 +17,8 @@ pub use self::parameters::*;
 use types::*;
 
 use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use colored::*;
+use std::time::Duration;
 
 pub trait CLI {
     type Options;
@@ -79,6 +84,37 @@ pub trait CLI {
     fn print(options: Self::Options) -> Result<(), CLIError>;
 }
 
+/// Prints the progress of a vanity search for a prefix of the given difficulty to stderr,
+/// overwriting the previous progress line.
+#[cfg_attr(tarpaulin, skip)]
+pub fn print_vanity_progress(progress: &VanityProgress, difficulty: f64) {
+    let expected_time = match progress.expected_time(difficulty) {
+        Some(expected_time) => format_duration(expected_time),
+        None => "unknown".into(),
+    };
+    eprint!(
+        "\r      {}             {} keys ({:.0} keys/s), {:.2}% probability, {} expected, {} elapsed    ",
+        "Searched".cyan().bold(),
+        progress.attempts,
+        progress.rate(),
+        100.0 * progress.probability(difficulty),
+        expected_time,
+        format_duration(progress.elapsed),
+    );
+}
+
+/// Returns the given duration in days, hours, minutes, and seconds.
+#[cfg_attr(tarpaulin, skip)]
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m {}s", seconds / 60, seconds % 60),
+        3600..=86399 => format!("{}h {}m", seconds / 3600, seconds % 3600 / 60),
+        _ => format!("{}d {}h", seconds / 86400, seconds % 86400 / 3600),
+    }
+}
+
 #[derive(Debug, Fail)]
 pub enum CLIError {
     #[fail(display = "{}", _0)]
@@ -116,6 +152,9 @@ pub enum CLIError {
 
     #[fail(display = "unsupported mnemonic language")]
     UnsupportedLanguage,
+
+    #[fail(display = "{}", _0)]
+    VanityError(VanityError),
 }
 
 impl From<AddressError> for CLIError {
@@ -160,6 +199,12 @@ impl From<hex::FromHexError> for CLIError {
     }
 }
 
+impl From<std::io::Error> for CLIError {
+    fn from(error: std::io::Error) -> Self {
+        CLIError::Crate("std::io", format!("{:?}", error))
+    }
+}
+
 impl From<MnemonicError> for CLIError {
     fn from(error: MnemonicError) -> Self {
         CLIError::MnemonicError(error)
@@ -189,3 +234,27 @@ impl From<TransactionError> for CLIError {
         CLIError::TransactionError(error)
     }
 }
+
+impl From<VanityError> for CLIError {
+    fn from(error: VanityError) -> Self {
+        CLIError::VanityError(error)
+    }
+}
+
+impl From<crate::ethereum::AbiError> for CLIError {
+    fn from(error: crate::ethereum::AbiError) -> Self {
+        CLIError::Crate("abi", format!("{:?}", error))
+    }
+}
+
+impl From<crate::ethereum::TypedDataError> for CLIError {
+    fn from(error: crate::ethereum::TypedDataError) -> Self {
+        CLIError::Crate("typed_data", format!("{:?}", error))
+    }
+}
+
+impl From<crate::ethereum::UserOperationError> for CLIError {
+    fn from(error: crate::ethereum::UserOperationError) -> Self {
+        CLIError::Crate("user_operation", format!("{:?}", error))
+    }
+}
@@ -1,8 +1,10 @@
 use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
-use crate::model::{Mnemonic, PrivateKey, PublicKey};
+use crate::model::{Mnemonic, PrivateKey, PrivateKeyError, PublicKey, Transaction, TransactionError};
 use crate::monero::{
-    format::MoneroFormat, wordlist::*, Mainnet as MoneroMainnet, MoneroAddress, MoneroMnemonic, MoneroNetwork,
-    MoneroPublicKey, MoneroWordlist, Stagenet as MoneroStagenet, Testnet as MoneroTestnet,
+    format::MoneroFormat, polyseed_language, wordlist::*, Mainnet as MoneroMainnet, MoneroAddress, MoneroMnemonic,
+    MoneroNetwork, MoneroPolyseed, MoneroPublicKey, MoneroScanner, MoneroSubaddressTable, MoneroTransaction,
+    MoneroWordlist, MyMoneroMnemonic, PolyseedWordlist, Stagenet as MoneroStagenet, Testnet as MoneroTestnet,
+    POLYSEED_NUM_WORDS,
 };
 
 use clap::{ArgMatches, Values};
@@ -10,6 +12,7 @@ use colored::*;
 use rand::{rngs::StdRng, Rng};
 use rand_core::SeedableRng;
 use serde::Serialize;
+use serde_json::Value;
 use std::{fmt, fmt::Display, str::FromStr};
 
 /// Represents a generic wallet to output
@@ -18,6 +21,8 @@ struct MoneroWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mnemonic: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub birthday: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub private_spend_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub private_view_key: Option<String>,
@@ -33,6 +38,20 @@ struct MoneroWallet {
     pub payment_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_index: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_public_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_index: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subaddress_index: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u64>,
 }
 
 impl MoneroWallet {
@@ -88,6 +107,107 @@ impl MoneroWallet {
         })
     }
 
+    pub fn from_polyseed<N: MoneroNetwork, W: PolyseedWordlist>(
+        mnemonic: &str,
+        password: Option<&str>,
+        format: &MoneroFormat,
+    ) -> Result<Self, CLIError> {
+        let mnemonic = MoneroPolyseed::<N, W>::from_phrase(&mnemonic)?;
+        let private_key = mnemonic.to_private_key(password)?;
+        let private_spend_key = private_key.to_private_spend_key();
+        let private_view_key = private_key.to_private_view_key();
+        let public_key = private_key.to_public_key();
+        let public_spend_key = public_key.to_public_spend_key().unwrap();
+        let public_view_key = public_key.to_public_view_key().unwrap();
+        let address = public_key.to_address(format)?;
+        Ok(Self {
+            mnemonic: Some(mnemonic.to_string()),
+            birthday: Some(mnemonic.birthday()),
+            private_spend_key: Some(hex::encode(private_spend_key)),
+            private_view_key: Some(hex::encode(private_view_key)),
+            public_spend_key: Some(hex::encode(public_spend_key)),
+            public_view_key: Some(hex::encode(public_view_key)),
+            address: Some(address.to_string()),
+            format: Some(format.to_string()),
+            payment_id: address.to_payment_id(),
+            network: Some(N::NAME.to_string()),
+            ..Default::default()
+        })
+    }
+
+    pub fn from_mymonero_mnemonic<N: MoneroNetwork, W: MoneroWordlist>(
+        mnemonic: &str,
+        format: &MoneroFormat,
+    ) -> Result<Self, CLIError> {
+        let mnemonic = MyMoneroMnemonic::<N, W>::from_phrase(&mnemonic)?;
+        let private_key = mnemonic.to_private_key(None)?;
+        let private_spend_key = private_key.to_private_spend_key();
+        let private_view_key = private_key.to_private_view_key();
+        let public_key = private_key.to_public_key();
+        let public_spend_key = public_key.to_public_spend_key().unwrap();
+        let public_view_key = public_key.to_public_view_key().unwrap();
+        let address = public_key.to_address(format)?;
+        Ok(Self {
+            mnemonic: Some(mnemonic.to_string()),
+            private_spend_key: Some(hex::encode(private_spend_key)),
+            private_view_key: Some(hex::encode(private_view_key)),
+            public_spend_key: Some(hex::encode(public_spend_key)),
+            public_view_key: Some(hex::encode(public_view_key)),
+            address: Some(address.to_string()),
+            format: Some(format.to_string()),
+            payment_id: address.to_payment_id(),
+            network: Some(N::NAME.to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the wallet of a mnemonic in any of the supported languages, as a 16-word Polyseed,
+    /// a 13-word legacy MyMonero mnemonic, or a 25-word Monero mnemonic.
+    pub fn from_mnemonic_phrase<N: MoneroNetwork>(
+        mnemonic: &str,
+        password: Option<&str>,
+        format: &MoneroFormat,
+    ) -> Result<Self, CLIError> {
+        match mnemonic.split_whitespace().count() {
+            POLYSEED_NUM_WORDS => match polyseed_language::<N>(mnemonic)? {
+                "chinese_simplified" => Self::from_polyseed::<N, ChineseSimplified>(mnemonic, password, format),
+                "chinese_traditional" => Self::from_polyseed::<N, ChineseTraditional>(mnemonic, password, format),
+                "english" => Self::from_polyseed::<N, English>(mnemonic, password, format),
+                "french" => Self::from_polyseed::<N, French>(mnemonic, password, format),
+                "italian" => Self::from_polyseed::<N, Italian>(mnemonic, password, format),
+                "japanese" => Self::from_polyseed::<N, Japanese>(mnemonic, password, format),
+                "korean" => Self::from_polyseed::<N, Korean>(mnemonic, password, format),
+                _ => Self::from_polyseed::<N, Spanish>(mnemonic, password, format),
+            },
+            13 => Self::from_mymonero_mnemonic::<N, ChineseSimplified>(mnemonic, format)
+                .or(Self::from_mymonero_mnemonic::<N, Dutch>(mnemonic, format))
+                .or(Self::from_mymonero_mnemonic::<N, English>(mnemonic, format))
+                .or(Self::from_mymonero_mnemonic::<N, EnglishOld>(mnemonic, format))
+                .or(Self::from_mymonero_mnemonic::<N, Esperanto>(mnemonic, format))
+                .or(Self::from_mymonero_mnemonic::<N, French>(mnemonic, format))
+                .or(Self::from_mymonero_mnemonic::<N, German>(mnemonic, format))
+                .or(Self::from_mymonero_mnemonic::<N, Italian>(mnemonic, format))
+                .or(Self::from_mymonero_mnemonic::<N, Japanese>(mnemonic, format))
+                .or(Self::from_mymonero_mnemonic::<N, Lojban>(mnemonic, format))
+                .or(Self::from_mymonero_mnemonic::<N, Portuguese>(mnemonic, format))
+                .or(Self::from_mymonero_mnemonic::<N, Russian>(mnemonic, format))
+                .or(Self::from_mymonero_mnemonic::<N, Spanish>(mnemonic, format)),
+            _ => Self::from_mnemonic::<N, ChineseSimplified>(mnemonic, format)
+                .or(Self::from_mnemonic::<N, Dutch>(mnemonic, format))
+                .or(Self::from_mnemonic::<N, English>(mnemonic, format))
+                .or(Self::from_mnemonic::<N, EnglishOld>(mnemonic, format))
+                .or(Self::from_mnemonic::<N, Esperanto>(mnemonic, format))
+                .or(Self::from_mnemonic::<N, French>(mnemonic, format))
+                .or(Self::from_mnemonic::<N, German>(mnemonic, format))
+                .or(Self::from_mnemonic::<N, Italian>(mnemonic, format))
+                .or(Self::from_mnemonic::<N, Japanese>(mnemonic, format))
+                .or(Self::from_mnemonic::<N, Lojban>(mnemonic, format))
+                .or(Self::from_mnemonic::<N, Portuguese>(mnemonic, format))
+                .or(Self::from_mnemonic::<N, Russian>(mnemonic, format))
+                .or(Self::from_mnemonic::<N, Spanish>(mnemonic, format)),
+        }
+    }
+
     pub fn from_private_spend_key<N: MoneroNetwork, W: MoneroWordlist>(
         private_spend_key: &str,
         format: &MoneroFormat,
@@ -163,6 +283,131 @@ impl MoneroWallet {
             ..Default::default()
         })
     }
+
+    pub fn from_transaction_hex<N: MoneroNetwork>(transaction_hex: &str) -> Result<Self, CLIError> {
+        let transaction = MoneroTransaction::<N>::from_transaction_bytes(&hex::decode(transaction_hex)?)?;
+        Ok(Self {
+            transaction_id: Some(transaction.to_transaction_id()?.to_string()),
+            transaction: Some(transaction.to_json()),
+            ..Default::default()
+        })
+    }
+
+    pub fn from_scan<N: MoneroNetwork>(
+        transactions: &str,
+        private_view_key: &str,
+        public_spend_key: &str,
+        lookahead: (u32, u32),
+    ) -> Result<Vec<Self>, CLIError> {
+        let (accounts, subaddresses) = lookahead;
+        let indices: Vec<(u32, u32)> = (0..accounts)
+            .flat_map(|major| (0..subaddresses).map(move |minor| (major, minor)))
+            .collect();
+        let scanner = MoneroScanner::<N>::from_view_key(
+            &Self::decode_key(private_view_key)?,
+            &Self::decode_key(public_spend_key)?,
+            &indices,
+        )?;
+
+        let mut wallets = vec![];
+        for transaction in Self::read_transactions::<N>(transactions)? {
+            let transaction_id = transaction.to_transaction_id()?.to_string();
+            for output in scanner.scan(&transaction)? {
+                wallets.push(Self {
+                    transaction_id: Some(transaction_id.clone()),
+                    output_index: Some(output.index),
+                    output_public_key: Some(hex::encode(output.public_key)),
+                    account_index: Some(output.subaddress.0),
+                    subaddress_index: Some(output.subaddress.1),
+                    amount: Some(output.amount),
+                    network: Some(N::NAME.to_string()),
+                    ..Default::default()
+                });
+            }
+        }
+        Ok(wallets)
+    }
+
+    pub fn from_subaddresses<N: MoneroNetwork>(
+        private_view_key: &str,
+        public_spend_key: &str,
+        accounts: u32,
+        indices: u32,
+    ) -> Result<Vec<Self>, CLIError> {
+        let table = MoneroSubaddressTable::<N>::new(
+            &Self::decode_key(private_view_key)?,
+            &Self::decode_key(public_spend_key)?,
+            accounts,
+            indices,
+        )?;
+
+        let mut wallets = vec![];
+        for ((major, minor), _) in table.subaddresses() {
+            let public_key = table.to_public_key(major, minor)?;
+            let address = table.to_address(major, minor)?;
+            wallets.push(Self {
+                public_spend_key: public_key.to_public_spend_key().map(hex::encode),
+                public_view_key: public_key.to_public_view_key().map(hex::encode),
+                address: Some(address.to_string()),
+                format: Some(public_key.format().to_string()),
+                network: Some(N::NAME.to_string()),
+                account_index: Some(major),
+                subaddress_index: Some(minor),
+                ..Default::default()
+            });
+        }
+        Ok(wallets)
+    }
+
+    /// Returns a 32-byte key given its hex encoding.
+    fn decode_key(key: &str) -> Result<[u8; 32], CLIError> {
+        let bytes = hex::decode(key)?;
+        if bytes.len() != 32 {
+            return Err(PrivateKeyError::InvalidByteLength(bytes.len()).into());
+        }
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&bytes);
+        Ok(key)
+    }
+
+    /// Returns the transactions given whitespace-separated hex blobs, a JSON array of hex blobs
+    /// or transactions, a JSON transaction, or a `get_transactions` response of the Monero daemon.
+    fn read_transactions<N: MoneroNetwork>(transactions: &str) -> Result<Vec<MoneroTransaction<N>>, CLIError> {
+        let from_hex = |transaction_hex: &str| -> Result<MoneroTransaction<N>, CLIError> {
+            Ok(MoneroTransaction::<N>::from_transaction_bytes(&hex::decode(
+                transaction_hex.trim_start_matches("0x"),
+            )?)?)
+        };
+        let from_value = |value: &Value| -> Result<MoneroTransaction<N>, CLIError> {
+            match value {
+                Value::String(transaction_hex) => from_hex(transaction_hex),
+                _ => Ok(MoneroTransaction::<N>::from_json(value)?),
+            }
+        };
+
+        let value = match serde_json::from_str::<Value>(transactions) {
+            Ok(value) => value,
+            Err(_) => return transactions.split_whitespace().map(from_hex).collect(),
+        };
+        match (&value, value.get("txs")) {
+            (Value::Array(transactions), _) => transactions.iter().map(from_value).collect(),
+            (_, Some(Value::Array(transactions))) => transactions
+                .iter()
+                .map(
+                    |transaction| match (transaction.get("as_hex"), transaction.get("as_json")) {
+                        (Some(Value::String(transaction_hex)), _) if !transaction_hex.is_empty() => {
+                            from_hex(transaction_hex)
+                        }
+                        (_, Some(Value::String(transaction_json))) => {
+                            from_value(&serde_json::from_str::<Value>(transaction_json)?)
+                        }
+                        _ => Err(TransactionError::Message("missing transaction in the daemon response".into()).into()),
+                    },
+                )
+                .collect(),
+            _ => Ok(vec![from_value(&value)?]),
+        }
+    }
 }
 
 #[cfg_attr(tarpaulin, skip)]
@@ -173,6 +418,10 @@ impl Display for MoneroWallet {
                 Some(mnemonic) => format!("      {}             {}\n", "Mnemonic".cyan().bold(), mnemonic),
                 _ => "".to_owned(),
             },
+            match &self.birthday {
+                Some(birthday) => format!("      {}             {}\n", "Birthday".cyan().bold(), birthday),
+                _ => "".to_owned(),
+            },
             match &self.private_spend_key {
                 Some(private_spend_key) => {
                     format!("      {}    {}\n", "Private Spend Key".cyan().bold(), private_spend_key)
@@ -213,6 +462,42 @@ impl Display for MoneroWallet {
                 Some(network) => format!("      {}              {}\n", "Network".cyan().bold(), network),
                 _ => "".to_owned(),
             },
+            match &self.transaction_id {
+                Some(transaction_id) => format!("      {}       {}\n", "Transaction Id".cyan().bold(), transaction_id),
+                _ => "".to_owned(),
+            },
+            match &self.output_index {
+                Some(output_index) => format!("      {}         {}\n", "Output Index".cyan().bold(), output_index),
+                _ => "".to_owned(),
+            },
+            match &self.output_public_key {
+                Some(output_public_key) => {
+                    format!("      {}    {}\n", "Output Public Key".cyan().bold(), output_public_key)
+                }
+                _ => "".to_owned(),
+            },
+            match &self.account_index {
+                Some(account_index) => format!("      {}        {}\n", "Account Index".cyan().bold(), account_index),
+                _ => "".to_owned(),
+            },
+            match &self.subaddress_index {
+                Some(subaddress_index) => {
+                    format!("      {}     {}\n", "Subaddress Index".cyan().bold(), subaddress_index)
+                }
+                _ => "".to_owned(),
+            },
+            match &self.amount {
+                Some(amount) => format!("      {}               {} piconero\n", "Amount".cyan().bold(), amount),
+                _ => "".to_owned(),
+            },
+            match &self.transaction {
+                Some(transaction) => format!(
+                    "      {}          {}\n",
+                    "Transaction".cyan().bold(),
+                    serde_json::to_string_pretty(transaction).unwrap_or_default()
+                ),
+                _ => "".to_owned(),
+            },
         ]
         .concat();
 
@@ -235,10 +520,19 @@ pub struct MoneroOptions {
     // Import subcommand
     address: Option<String>,
     mnemonic: Option<String>,
+    password: Option<String>,
     private_spend_key: Option<String>,
     private_view_key: Option<String>,
     public_spend_key: Option<String>,
     public_view_key: Option<String>,
+    // Scan subcommand
+    lookahead: (u32, u32),
+    transactions: Option<String>,
+    // Subaddresses subcommand
+    accounts: u32,
+    indices: u32,
+    // Transaction subcommand
+    transaction_hex: Option<String>,
 }
 
 impl Default for MoneroOptions {
@@ -254,10 +548,19 @@ impl Default for MoneroOptions {
             // Import subcommand
             address: None,
             mnemonic: None,
+            password: None,
             private_spend_key: None,
             private_view_key: None,
             public_spend_key: None,
             public_view_key: None,
+            // Scan subcommand
+            lookahead: (50, 200),
+            transactions: None,
+            // Subaddresses subcommand
+            accounts: 1,
+            indices: 10,
+            // Transaction subcommand
+            transaction_hex: None,
         }
     }
 }
@@ -265,22 +568,35 @@ impl Default for MoneroOptions {
 impl MoneroOptions {
     fn parse(&mut self, arguments: &ArgMatches, options: &[&str]) {
         options.iter().for_each(|option| match *option {
+            "accounts" => self.accounts(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "address" => self.address(arguments.value_of(option)),
             "count" => self.count(clap::value_t!(arguments.value_of(*option), usize).ok()),
+            "decoderawtransaction" => self.decode_raw_transaction(arguments.value_of(option)),
+            "indices" => self.indices(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "integrated" => self.integrated(arguments.value_of(option)),
             "json" => self.json(arguments.is_present(option)),
             "language" => self.language(arguments.value_of(option)),
+            "lookahead" => self.lookahead(arguments.values_of(option)),
             "mnemonic" => self.mnemonic(arguments.value_of(option)),
             "network" => self.network(arguments.value_of(option)),
+            "password" => self.password(arguments.value_of(option)),
             "private spend" => self.private_spend(arguments.value_of(option)),
             "private view" => self.private_view(arguments.value_of(option)),
             "public spend" => self.public_spend(arguments.value_of(option)),
             "public view" => self.public_view(arguments.value_of(option)),
             "subaddress" => self.subaddress(arguments.values_of(option)),
+            "transactions" => self.transactions(arguments.value_of(option)),
             _ => (),
         });
     }
 
+    /// Sets `accounts` to the specified number of accounts, overriding its previous state.
+    fn accounts(&mut self, argument: Option<u32>) {
+        if let Some(accounts) = argument {
+            self.accounts = accounts;
+        }
+    }
+
     /// Sets `address` to the specified address, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn address(&mut self, argument: Option<&str>) {
@@ -296,6 +612,21 @@ impl MoneroOptions {
         }
     }
 
+    /// Sets `transaction_hex` to the specified transaction hex, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn decode_raw_transaction(&mut self, argument: Option<&str>) {
+        if let Some(transaction_hex) = argument {
+            self.transaction_hex = Some(transaction_hex.trim_start_matches("0x").to_string());
+        }
+    }
+
+    /// Sets `indices` to the specified number of subaddresses of each account, overriding its previous state.
+    fn indices(&mut self, argument: Option<u32>) {
+        if let Some(indices) = argument {
+            self.indices = indices;
+        }
+    }
+
     /// Sets `format` to an integrated address with the specified payment ID, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn integrated(&mut self, argument: Option<&str>) {
@@ -332,6 +663,17 @@ impl MoneroOptions {
         };
     }
 
+    /// Sets `lookahead` to the specified number of accounts and subaddresses, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn lookahead(&mut self, arguments: Option<Values>) {
+        if let Some(values) = arguments {
+            let counts: Vec<u32> = values.filter_map(|count| count.parse::<u32>().ok()).collect();
+            if let [accounts, subaddresses] = counts.as_slice() {
+                self.lookahead = (*accounts, *subaddresses);
+            }
+        }
+    }
+
     /// Sets `mnemonic` to the specified mnemonic, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn mnemonic(&mut self, argument: Option<&str>) {
@@ -351,6 +693,14 @@ impl MoneroOptions {
         };
     }
 
+    /// Sets `password` to the specified password, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn password(&mut self, argument: Option<&str>) {
+        if let Some(password) = argument {
+            self.password = Some(password.to_string());
+        }
+    }
+
     /// Sets `private_spend_key` to the specified private spend key, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn private_spend(&mut self, argument: Option<&str>) {
@@ -394,6 +744,14 @@ impl MoneroOptions {
             self.format = MoneroFormat::Subaddress(index[0], index[1]);
         }
     }
+
+    /// Sets `transactions` to the specified path of a transactions file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn transactions(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.transactions = Some(path.to_string());
+        }
+    }
 }
 
 pub struct MoneroCLI;
@@ -411,7 +769,12 @@ impl CLI for MoneroCLI {
         option::NETWORK_MONERO,
         option::SUBADDRESS_MONERO,
     ];
-    const SUBCOMMANDS: &'static [SubCommandType] = &[subcommand::IMPORT_MONERO];
+    const SUBCOMMANDS: &'static [SubCommandType] = &[
+        subcommand::IMPORT_MONERO,
+        subcommand::SCAN_MONERO,
+        subcommand::SUBADDRESSES_MONERO,
+        subcommand::TRANSACTION_MONERO,
+    ];
 
     /// Handle all CLI arguments and flags for Monero
     #[cfg_attr(tarpaulin, skip)]
@@ -442,6 +805,7 @@ impl CLI for MoneroCLI {
                     &[
                         "address",
                         "mnemonic",
+                        "password",
                         "private spend",
                         "private view",
                         "public spend",
@@ -449,6 +813,32 @@ impl CLI for MoneroCLI {
                     ],
                 );
             }
+            ("scan", Some(arguments)) => {
+                options.subcommand = Some("scan".into());
+                options.parse(
+                    arguments,
+                    &["json", "lookahead", "private view", "public spend", "transactions"],
+                );
+            }
+            ("subaddresses", Some(arguments)) => {
+                options.subcommand = Some("subaddresses".into());
+                options.parse(
+                    arguments,
+                    &[
+                        "accounts",
+                        "indices",
+                        "json",
+                        "mnemonic",
+                        "private spend",
+                        "private view",
+                        "public spend",
+                    ],
+                );
+            }
+            ("transaction", Some(arguments)) => {
+                options.subcommand = Some("transaction".into());
+                options.parse(arguments, &["decoderawtransaction", "json"]);
+            }
             _ => {}
         };
 
@@ -463,21 +853,11 @@ impl CLI for MoneroCLI {
                 match options.subcommand.as_ref().map(String::as_str) {
                     Some("import") => {
                         if let Some(mnemonic) = options.mnemonic {
-                            vec![
-                                MoneroWallet::from_mnemonic::<N, ChineseSimplified>(&mnemonic, &options.format)
-                                    .or(MoneroWallet::from_mnemonic::<N, Dutch>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, English>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, EnglishOld>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Esperanto>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, French>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, German>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Italian>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Japanese>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Lojban>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Portuguese>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Russian>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Spanish>(&mnemonic, &options.format))?,
-                            ]
+                            vec![MoneroWallet::from_mnemonic_phrase::<N>(
+                                &mnemonic,
+                                options.password.as_ref().map(String::as_str),
+                                &options.format,
+                            )?]
                         } else if let Some(private_spend_key) = options.private_spend_key {
                             vec![MoneroWallet::from_private_spend_key::<N, W>(
                                 &private_spend_key,
@@ -505,6 +885,52 @@ impl CLI for MoneroCLI {
                             vec![]
                         }
                     }
+                    Some("scan") => match (
+                        options.transactions.clone(),
+                        options.private_view_key.clone(),
+                        options.public_spend_key.clone(),
+                    ) {
+                        (Some(path), Some(private_view_key), Some(public_spend_key)) => MoneroWallet::from_scan::<N>(
+                            &std::fs::read_to_string(path)?,
+                            &private_view_key,
+                            &public_spend_key,
+                            options.lookahead,
+                        )?,
+                        _ => vec![],
+                    },
+                    Some("subaddresses") => {
+                        let wallet = if let Some(mnemonic) = options.mnemonic.clone() {
+                            Some(MoneroWallet::from_mnemonic_phrase::<N>(
+                                &mnemonic,
+                                None,
+                                &MoneroFormat::Standard,
+                            )?)
+                        } else if let Some(private_spend_key) = options.private_spend_key.clone() {
+                            Some(MoneroWallet::from_private_spend_key::<N, W>(
+                                &private_spend_key,
+                                &MoneroFormat::Standard,
+                            )?)
+                        } else {
+                            None
+                        };
+                        let keys = match wallet {
+                            Some(wallet) => (wallet.private_view_key, wallet.public_spend_key),
+                            None => (options.private_view_key.clone(), options.public_spend_key.clone()),
+                        };
+                        match keys {
+                            (Some(private_view_key), Some(public_spend_key)) => MoneroWallet::from_subaddresses::<N>(
+                                &private_view_key,
+                                &public_spend_key,
+                                options.accounts,
+                                options.indices,
+                            )?,
+                            _ => vec![],
+                        }
+                    }
+                    Some("transaction") => match options.transaction_hex {
+                        Some(transaction_hex) => vec![MoneroWallet::from_transaction_hex::<N>(&transaction_hex)?],
+                        None => vec![],
+                    },
                     _ => (0..options.count)
                         .flat_map(|_| {
                             match MoneroWallet::new::<N, W, _>(&mut StdRng::from_entropy(), &options.format) {
@@ -23,6 +23,18 @@ pub const FORMAT_BITCOIN: OptionType = (
     &["bech32", "legacy", "segwit"],
     &[],
 );
+pub const FORMAT_BITCOIN_CASH: OptionType = (
+    "[format] -f --format=[format] 'Generates a wallet with a specified format'",
+    &[],
+    &["legacy"],
+    &[],
+);
+pub const FORMAT_DOGECOIN: OptionType = (
+    "[format] -f --format=[format] 'Generates a wallet with a specified format'",
+    &[],
+    &["legacy"],
+    &[],
+);
 pub const FORMAT_ZCASH: OptionType = (
     "[format] -f --format=[format] 'Generates a wallet with a specified format'",
     &[],
@@ -99,6 +111,18 @@ pub const FORMAT_IMPORT_BITCOIN: OptionType = (
     &["bech32", "legacy", "segwit"],
     &[],
 );
+pub const FORMAT_IMPORT_BITCOIN_CASH: OptionType = (
+    "[format] -f --format=[format] 'Imports a wallet with a specified format'",
+    &[],
+    &["legacy"],
+    &[],
+);
+pub const FORMAT_IMPORT_DOGECOIN: OptionType = (
+    "[format] -f --format=[format] 'Imports a wallet with a specified format'",
+    &[],
+    &["legacy"],
+    &[],
+);
 pub const INTEGRATED_IMPORT_MONERO: OptionType = (
     "[integrated] -i --integrated=[PaymentID] 'Imports a wallet with a specified payment ID'",
     &["address", "private view", "subaddress"],
@@ -136,6 +160,12 @@ pub const NETWORK_IMPORT_MONERO: OptionType = (
     &["mainnet", "stagenet", "testnet"],
     &[],
 );
+pub const PASSWORD_IMPORT_MONERO: OptionType = (
+    "[password] -p --password=[password] 'Imports a wallet for a specified mnemonic with a password (Polyseed only)'",
+    &["address", "private spend", "private view", "public spend", "public view"],
+    &[],
+    &["mnemonic"],
+);
 pub const PRIVATE: OptionType = (
     "[private] --private=[private key] 'Imports a wallet for a specified private key'",
     &["address", "count", "network", "public"],
@@ -187,6 +217,12 @@ pub const DERIVATION_BITCOIN: OptionType = (
     &[],
     &[],
 );
+pub const DERIVATION_DOGECOIN: OptionType = (
+    "[derivation] -d --derivation=[\"path\"] 'Generates an HD wallet for a specified derivation path (in quotes) [possible values: bip32, bip44, \"<custom path>\"]'",
+    &[],
+    &[],
+    &[],
+);
 pub const DERIVATION_ETHEREUM: OptionType = (
     "[derivation] -d --derivation=[\"path\"] 'Generates an HD wallet for a specified derivation path (in quotes) [possible values: ethereum, keepkey, ledger-legacy, ledger-live, trezor, \"<custom path>\"]'",
     &[],
@@ -277,6 +313,12 @@ pub const DERIVATION_IMPORT_BITCOIN: OptionType = (
     &[],
     &[],
 );
+pub const DERIVATION_IMPORT_DOGECOIN: OptionType = (
+    "[derivation] -d --derivation=[\"path\"] 'Imports an HD wallet for a specified derivation path (in quotes) [possible values: bip32, bip44, \"<custom path>\"]'",
+    &[],
+    &[],
+    &[],
+);
 pub const DERIVATION_IMPORT_ETHEREUM: OptionType = (
     "[derivation] -d --derivation=[\"path\"] 'Imports an HD wallet for a specified derivation path (in quotes) [possible values: ethereum, keepkey, ledger-legacy, ledger-live, trezor, \"<custom path>\"]'",
     &[],
@@ -361,6 +403,12 @@ pub const SIGN_RAW_TRANSACTION_BITCOIN: OptionType = (
     &[],
 );
 
+pub const TRANSACTION_DATA_BITCOIN: OptionType = (
+    "[data] --data=[data] 'Specify data to embed in an OP_RETURN output (0x-prefixed hex, otherwise UTF-8)'",
+    &["signrawtransaction"],
+    &[],
+    &["createrawtransaction"],
+);
 pub const TRANSACTION_LOCK_TIME_BITCOIN: OptionType = (
     "[lock time] --lock-time=[lock time] 'Specify a Bitcoin transaction lock time'",
     &["signrawtransaction"],
@@ -377,7 +425,11 @@ pub const TRANSACTION_VERSION_BITCOIN: OptionType = (
 
 pub const CREATE_RAW_TRANSACTION_ETHEREUM: OptionType = (
     "[createrawtransaction] --createrawtransaction= ['{\"to\":\"address\", \"value\":\"value\", \"gas\":\"gas\", \"gasPrice\":\"gas_price\", \"nonce\":nonce, \"network\":\"network\"}'] 'Generates a raw Ethereum transaction
-    (Optional: Add a data field)'",
+    (Optional: Add a data field, 0x-prefixed hex is decoded as calldata)
+    (Optional: Omit the \"to\" field to deploy a contract with the data as its init code)
+    (Optional: Add \"maxFeePerGas\" and \"maxPriorityFeePerGas\" fields for an EIP-1559 transaction)
+    (Optional: Add an \"accessList\" field of [{\"address\":\"address\", \"storageKeys\":[\"key\",...]},...] for an EIP-2930 transaction)
+    (Optional: Add a \"chainId\" field for a custom EVM chain, such as 42161 for Arbitrum One, required by EIP-1559 and EIP-2930 transactions)'",
     &["network", "signrawtransaction"],
     &[],
     &[],
@@ -391,10 +443,17 @@ pub const SIGN_RAW_TRANSACTION_ETHEREUM: OptionType = (
 );
 
 pub const TRANSACTION_NETWORK_ETHEREUM: OptionType = (
-    "[network] --network=[network] 'Specify an Ethereum transaction network'",
+    "[network] --network=[network] 'Specify an Ethereum transaction network, or the chain id of a custom EVM chain'",
+    &["createrawtransaction"],
+    &[],
     &["signrawtransaction"],
+);
+
+pub const DECODE_RAW_TRANSACTION_MONERO: OptionType = (
+    "[decoderawtransaction] --decoderawtransaction=[transaction hex] 'Decodes a raw Monero transaction into JSON'",
+    &[],
+    &[],
     &[],
-    &["createrawtransaction"],
 );
 
 pub const CREATE_RAW_TRANSACTION_ZCASH: OptionType = (
@@ -437,3 +496,197 @@ pub const TRANSACTION_VERSION_ZCASH: OptionType = (
     &["sapling"],
     &["createrawtransaction"],
 );
+
+// Scan
+
+pub const SCAN_TRANSACTIONS_MONERO: OptionType = (
+    "<transactions> 'Specify the path to a file of Monero transactions, as hex blobs or JSON'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const SCAN_PRIVATE_VIEW_KEY_MONERO: OptionType = (
+    "<private view> --private-view=<private view key> 'Scans the transactions with a specified private view key'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const SCAN_PUBLIC_SPEND_KEY_MONERO: OptionType = (
+    "<public spend> --public-spend=<public spend key> 'Scans the transactions for a specified public spend key'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const SCAN_LOOKAHEAD_MONERO: OptionType = (
+    "[lookahead] --lookahead=[accounts] [subaddresses] 'Scans the first subaddresses of the first accounts (default: 50 200)'",
+    &[],
+    &[],
+    &[],
+);
+
+// Subaddresses
+
+pub const SUBADDRESSES_ACCOUNTS_MONERO: OptionType = (
+    "[accounts] --accounts=[accounts] 'Lists the subaddresses of a specified number of accounts (default: 1)'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const SUBADDRESSES_INDICES_MONERO: OptionType = (
+    "[indices] --indices=[indices] 'Lists a specified number of subaddresses of each account (default: 10)'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const SUBADDRESSES_MNEMONIC_MONERO: OptionType = (
+    "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Lists the subaddresses of a specified mnemonic (in quotes)'",
+    &["private spend", "private view", "public spend"],
+    &[],
+    &[],
+);
+
+pub const SUBADDRESSES_PRIVATE_SPEND_KEY_MONERO: OptionType = (
+    "[private spend] --private-spend=[private spend key] 'Lists the subaddresses of a specified private spend key'",
+    &["mnemonic", "private view", "public spend"],
+    &[],
+    &[],
+);
+
+pub const SUBADDRESSES_PRIVATE_VIEW_KEY_MONERO: OptionType = (
+    "[private view] --private-view=[private view key] 'Lists the subaddresses of a specified private view key'",
+    &["mnemonic", "private spend"],
+    &[],
+    &["public spend"],
+);
+
+pub const SUBADDRESSES_PUBLIC_SPEND_KEY_MONERO: OptionType = (
+    "[public spend] --public-spend=[public spend key] 'Lists the subaddresses of a specified public spend key'",
+    &["mnemonic", "private spend"],
+    &[],
+    &["private view"],
+);
+
+// Sign Typed Data
+
+pub const TYPED_DATA_ETHEREUM: OptionType = (
+    "<typed data> 'Specify the path to a JSON file of EIP-712 typed data'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const TYPED_DATA_PRIVATE_KEY_ETHEREUM: OptionType = (
+    "[private] --private=[private key] 'Signs the typed data with a specified private key'",
+    &[],
+    &[],
+    &[],
+);
+
+// Sign User Operation
+
+pub const USER_OPERATION_ETHEREUM: OptionType = (
+    "<user operation> 'Specify the path to a JSON file of an ERC-4337 user operation, as sent to a bundler'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const USER_OPERATION_CHAIN_ID_ETHEREUM: OptionType = (
+    "[chain id] --chain-id=[chain id] 'Specify the chain id of the user operation (default: 1)'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const USER_OPERATION_ENTRY_POINT_ETHEREUM: OptionType = (
+    "[entry point] --entry-point=[address] 'Specify the entry point of the user operation (default: the v0.6 or v0.7 entry point)'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const USER_OPERATION_PRIVATE_KEY_ETHEREUM: OptionType = (
+    "[private] --private=[private key] 'Signs the user operation with a specified private key'",
+    &[],
+    &[],
+    &[],
+);
+
+// ABI
+
+pub const ENCODE_ABI_ETHEREUM: OptionType = (
+    "[encode] --encode=[function signature] [arguments] 'Encodes a function call with a JSON array of arguments, such as \"transfer(address,uint256)\" [\"0x...\", \"1000\"]'",
+    &["decode", "erc20-approve", "erc20-transfer", "erc721-transfer"],
+    &[],
+    &[],
+);
+
+pub const DECODE_ABI_ETHEREUM: OptionType = (
+    "[decode] --decode=[abi file] [calldata] 'Decodes a function call with the path to a JSON ABI file'",
+    &["encode", "erc20-approve", "erc20-transfer", "erc721-transfer"],
+    &[],
+    &[],
+);
+
+pub const ERC20_APPROVE_ETHEREUM: OptionType = (
+    "[erc20-approve] --erc20-approve=[spender] [amount] 'Encodes an ERC-20 approve call'",
+    &["decode", "encode", "erc20-transfer", "erc721-transfer"],
+    &[],
+    &[],
+);
+
+pub const ERC20_TRANSFER_ETHEREUM: OptionType = (
+    "[erc20-transfer] --erc20-transfer=[receiver] [amount] 'Encodes an ERC-20 transfer call'",
+    &["decode", "encode", "erc20-approve", "erc721-transfer"],
+    &[],
+    &[],
+);
+
+pub const ERC721_TRANSFER_ETHEREUM: OptionType = (
+    "[erc721-transfer] --erc721-transfer=[owner] [receiver] [token id] 'Encodes an ERC-721 safeTransferFrom call'",
+    &["decode", "encode", "erc20-approve", "erc20-transfer"],
+    &[],
+    &[],
+);
+
+// Vanity
+
+pub const CASE_SENSITIVE_VANITY_ETHEREUM: OptionType = (
+    "[case sensitive] --case-sensitive 'Matches the letters of the prefix to the EIP-55 checksum of the address'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const FORMAT_VANITY_BITCOIN: OptionType = (
+    "[format] -f --format=[format] 'Searches for addresses of a specified format'",
+    &[],
+    &["bech32", "legacy", "p2pkh", "segwit"],
+    &[],
+);
+
+pub const PREFIX_VANITY: OptionType = (
+    "<prefix> -p --prefix=<prefix> 'Searches for addresses that begin with a specified prefix'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const PUBLIC_VANITY: OptionType = (
+    "[public] --public=[public key] 'Searches for a partial private key to add to the private key of a specified public key'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const THREADS_VANITY: OptionType = (
+    "[threads] --threads=[threads] 'Searches on a specified number of threads (defaults to the number of CPUs)'",
+    &[],
+    &[],
+    &[],
+);
@@ -5,6 +5,24 @@ use clap::AppSettings;
 // Format
 // (name, about, options, settings)
 
+pub const ABI_ETHEREUM: SubCommandType = (
+    "abi",
+    "Encodes and decodes contract calls (include -h for more options)",
+    &[
+        option::DECODE_ABI_ETHEREUM,
+        option::ENCODE_ABI_ETHEREUM,
+        option::ERC20_APPROVE_ETHEREUM,
+        option::ERC20_TRANSFER_ETHEREUM,
+        option::ERC721_TRANSFER_ETHEREUM,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
 pub const HD_BITCOIN: SubCommandType = (
     "hd",
     "Generates an HD wallet (include -h for more options)",
@@ -23,6 +41,24 @@ pub const HD_BITCOIN: SubCommandType = (
     ],
 );
 
+pub const HD_DOGECOIN: SubCommandType = (
+    "hd",
+    "Generates an HD wallet (include -h for more options)",
+    &[
+        option::COUNT,
+        option::DERIVATION_DOGECOIN,
+        option::LANGUAGE_HD,
+        option::NETWORK_HD_BITCOIN,
+        option::PASSWORD_HD,
+        option::WORD_COUNT,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+    ],
+);
+
 pub const HD_ETHEREUM: SubCommandType = (
     "hd",
     "Generates an HD wallet (include -h for more options)",
@@ -76,6 +112,42 @@ pub const IMPORT_BITCOIN: SubCommandType = (
     ],
 );
 
+pub const IMPORT_BITCOIN_CASH: SubCommandType = (
+    "import",
+    "Imports a wallet (include -h for more options)",
+    &[
+        option::ADDRESS,
+        option::FORMAT_IMPORT_BITCOIN_CASH,
+        option::NETWORK_IMPORT_BITCOIN,
+        option::PRIVATE,
+        option::PUBLIC,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const IMPORT_DOGECOIN: SubCommandType = (
+    "import",
+    "Imports a wallet (include -h for more options)",
+    &[
+        option::ADDRESS,
+        option::FORMAT_IMPORT_DOGECOIN,
+        option::NETWORK_IMPORT_BITCOIN,
+        option::PRIVATE,
+        option::PUBLIC,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
 pub const IMPORT_ETHEREUM: SubCommandType = (
     "import",
     "Imports a wallet (include -h for more options)",
@@ -97,6 +169,7 @@ pub const IMPORT_MONERO: SubCommandType = (
         option::LANGUAGE_IMPORT_MONERO,
         option::MNEMONIC_IMPORT_MONERO,
         option::NETWORK_IMPORT_MONERO,
+        option::PASSWORD_IMPORT_MONERO,
         option::PRIVATE_SPEND_KEY_MONERO,
         option::PRIVATE_VIEW_KEY_MONERO,
         option::PUBLIC_SPEND_KEY_MONERO,
@@ -150,6 +223,28 @@ pub const IMPORT_HD_BITCOIN: SubCommandType = (
     ],
 );
 
+pub const IMPORT_HD_DOGECOIN: SubCommandType = (
+    "import-hd",
+    "Imports an HD wallet (include -h for more options)",
+    &[
+        option::ACCOUNT,
+        option::CHAIN,
+        option::DERIVATION_IMPORT_DOGECOIN,
+        option::EXTENDED_PUBLIC,
+        option::EXTENDED_PRIVATE,
+        option::NETWORK_IMPORT_HD_BITCOIN,
+        option::INDEX_IMPORT_HD,
+        option::MNEMONIC,
+        option::PASSWORD_IMPORT_HD,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
 pub const IMPORT_HD_ETHEREUM: SubCommandType = (
     "import-hd",
     "Imports an HD wallet (include -h for more options)",
@@ -189,12 +284,78 @@ pub const IMPORT_HD_ZCASH: SubCommandType = (
     ],
 );
 
+pub const SCAN_MONERO: SubCommandType = (
+    "scan",
+    "Scans Monero transactions for received outputs (include -h for more options)",
+    &[
+        option::SCAN_TRANSACTIONS_MONERO,
+        option::SCAN_LOOKAHEAD_MONERO,
+        option::SCAN_PRIVATE_VIEW_KEY_MONERO,
+        option::SCAN_PUBLIC_SPEND_KEY_MONERO,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const SIGN_TYPED_DATA_ETHEREUM: SubCommandType = (
+    "sign-typed-data",
+    "Hashes and signs EIP-712 typed data (include -h for more options)",
+    &[option::TYPED_DATA_ETHEREUM, option::TYPED_DATA_PRIVATE_KEY_ETHEREUM],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const SIGN_USER_OPERATION_ETHEREUM: SubCommandType = (
+    "sign-user-operation",
+    "Hashes and signs an ERC-4337 user operation (include -h for more options)",
+    &[
+        option::USER_OPERATION_ETHEREUM,
+        option::USER_OPERATION_CHAIN_ID_ETHEREUM,
+        option::USER_OPERATION_ENTRY_POINT_ETHEREUM,
+        option::USER_OPERATION_PRIVATE_KEY_ETHEREUM,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const SUBADDRESSES_MONERO: SubCommandType = (
+    "subaddresses",
+    "Lists the subaddresses of a Monero wallet (include -h for more options)",
+    &[
+        option::SUBADDRESSES_ACCOUNTS_MONERO,
+        option::SUBADDRESSES_INDICES_MONERO,
+        option::SUBADDRESSES_MNEMONIC_MONERO,
+        option::SUBADDRESSES_PRIVATE_SPEND_KEY_MONERO,
+        option::SUBADDRESSES_PRIVATE_VIEW_KEY_MONERO,
+        option::SUBADDRESSES_PUBLIC_SPEND_KEY_MONERO,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
 pub const TRANSACTION_BITCOIN: SubCommandType = (
     "transaction",
     "Generates a Bitcoin transaction (include -h for more options)",
     &[
         option::CREATE_RAW_TRANSACTION_BITCOIN,
         option::SIGN_RAW_TRANSACTION_BITCOIN,
+        option::TRANSACTION_DATA_BITCOIN,
         option::TRANSACTION_LOCK_TIME_BITCOIN,
         option::TRANSACTION_VERSION_BITCOIN,
     ],
@@ -222,6 +383,18 @@ pub const TRANSACTION_ETHEREUM: SubCommandType = (
     ],
 );
 
+pub const TRANSACTION_MONERO: SubCommandType = (
+    "transaction",
+    "Decodes a Monero transaction (include -h for more options)",
+    &[option::DECODE_RAW_TRANSACTION_MONERO],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
 pub const TRANSACTION_ZCASH: SubCommandType = (
     "transaction",
     "Generates a Zcash transaction (include -h for more options)",
@@ -239,3 +412,38 @@ pub const TRANSACTION_ZCASH: SubCommandType = (
         AppSettings::ArgRequiredElseHelp,
     ],
 );
+
+pub const VANITY_BITCOIN: SubCommandType = (
+    "vanity",
+    "Searches for a wallet with an address that begins with a prefix (include -h for more options)",
+    &[
+        option::FORMAT_VANITY_BITCOIN,
+        option::NETWORK_BITCOIN,
+        option::PREFIX_VANITY,
+        option::PUBLIC_VANITY,
+        option::THREADS_VANITY,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const VANITY_ETHEREUM: SubCommandType = (
+    "vanity",
+    "Searches for a wallet with an address that begins with a prefix (include -h for more options)",
+    &[
+        option::CASE_SENSITIVE_VANITY_ETHEREUM,
+        option::PREFIX_VANITY,
+        option::PUBLIC_VANITY,
+        option::THREADS_VANITY,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
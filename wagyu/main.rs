@@ -3,7 +3,10 @@
 //! A command-line tool to generate cryptocurrency wallets.
 
 use wagyu::cli::bitcoin::BitcoinCLI;
+use wagyu::cli::bitcoin_cash::BitcoinCashCLI;
+use wagyu::cli::dogecoin::DogecoinCLI;
 use wagyu::cli::ethereum::EthereumCLI;
+use wagyu::cli::litecoin::LitecoinCLI;
 use wagyu::cli::monero::MoneroCLI;
 use wagyu::cli::zcash::ZcashCLI;
 use wagyu::cli::{CLIError, CLI};
@@ -14,7 +17,7 @@ use clap::{App, AppSettings};
 fn main() -> Result<(), CLIError> {
     let arguments = App::new("wagyu")
         .version("v0.6.3")
-        .about("Generate a wallet for Bitcoin, Ethereum, Monero, and Zcash")
+        .about("Generate a wallet for Bitcoin, Bitcoin Cash, Dogecoin, Ethereum, Litecoin, Monero, and Zcash")
         .author("Aleo <hello@aleo.org>")
         .settings(&[
             AppSettings::ColoredHelp,
@@ -24,7 +27,10 @@ fn main() -> Result<(), CLIError> {
         ])
         .subcommands(vec![
             BitcoinCLI::new(),
+            BitcoinCashCLI::new(),
+            DogecoinCLI::new(),
             EthereumCLI::new(),
+            LitecoinCLI::new(),
             MoneroCLI::new(),
             ZcashCLI::new(),
         ])
@@ -33,7 +39,10 @@ fn main() -> Result<(), CLIError> {
 
     match arguments.subcommand() {
         ("bitcoin", Some(arguments)) => BitcoinCLI::print(BitcoinCLI::parse(arguments)?),
+        ("bitcoin-cash", Some(arguments)) => BitcoinCashCLI::print(BitcoinCashCLI::parse(arguments)?),
+        ("dogecoin", Some(arguments)) => DogecoinCLI::print(DogecoinCLI::parse(arguments)?),
         ("ethereum", Some(arguments)) => EthereumCLI::print(EthereumCLI::parse(arguments)?),
+        ("litecoin", Some(arguments)) => LitecoinCLI::print(LitecoinCLI::parse(arguments)?),
         ("monero", Some(arguments)) => MoneroCLI::print(MoneroCLI::parse(arguments)?),
         ("zcash", Some(arguments)) => ZcashCLI::print(ZcashCLI::parse(arguments)?),
         _ => unreachable!(),
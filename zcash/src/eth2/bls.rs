@@ -0,0 +1,499 @@
+//!
+//! BLS Signatures
+//!
+//! This module contains the BLS keys and signatures of Ethereum validators, which use the
+//! proof-of-possession ciphersuite with public keys in G1 and signatures in G2, and the EIP-2333
+//! tree KDF that derives validator keys from a seed.
+//! https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05
+//! https://eips.ethereum.org/EIPS/eip-2333
+//!
+
+use crate::eth2::derivation_path::ValidatorDerivationPath;
+use crate::eth2::hash_to_curve::hash_to_g2;
+use crate::librustzcash::algebra::curve::bls12_381::{
+    Bls12, Fq12, Fr, FrRepr, G1Affine, G1Compressed, G2Affine, G2Compressed,
+};
+use crate::librustzcash::algebra::curve::{Engine, PairingCurveAffine};
+use crate::librustzcash::algebra::field::{Field, PrimeField, PrimeFieldRepr};
+use crate::librustzcash::algebra::group::{CurveAffine, CurveProjective, EncodedPoint, GroupDecodingError};
+
+use crypto::{
+    hkdf::{hkdf_expand, hkdf_extract},
+    hmac::Hmac,
+    pbkdf2::pbkdf2,
+};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::{fmt, str::FromStr};
+use unicode_normalization::UnicodeNormalization;
+
+/// The domain separation tag of signatures in the proof-of-possession ciphersuite
+pub const SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// The domain separation tag of proofs of possession in the proof-of-possession ciphersuite
+pub const PROOF_OF_POSSESSION_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// The initial salt of HKDF_mod_r
+const KEYGEN_SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+
+/// The number of bytes of key material reduced to a secret key, ceil(3 * ceil(log2(r)) / 16)
+const KEYGEN_LENGTH: usize = 48;
+
+/// The number of 32-byte chunks of a Lamport secret key
+const LAMPORT_CHUNKS: usize = 255;
+
+/// The minimum length of a seed
+const MINIMUM_SEED_LENGTH: usize = 32;
+
+/// The number of PBKDF2 rounds of a BIP39 seed
+const BIP39_PBKDF2_ROUNDS: u32 = 2048;
+
+#[derive(Debug, Fail)]
+pub enum BlsError {
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "invalid byte length: {}", _0)]
+    InvalidByteLength(usize),
+
+    #[fail(display = "invalid public key: {}", _0)]
+    InvalidPublicKey(String),
+
+    #[fail(display = "invalid secret key: {}", _0)]
+    InvalidSecretKey(String),
+
+    #[fail(display = "invalid seed length: {}", _0)]
+    InvalidSeedLength(usize),
+}
+
+impl From<GroupDecodingError> for BlsError {
+    fn from(error: GroupDecodingError) -> Self {
+        BlsError::Crate("bls12_381", format!("{:?}", error))
+    }
+}
+
+impl From<hex::FromHexError> for BlsError {
+    fn from(error: hex::FromHexError) -> Self {
+        BlsError::Crate("hex", format!("{:?}", error))
+    }
+}
+
+/// Represents a BLS secret key, a nonzero scalar of BLS12-381
+#[derive(Clone, PartialEq, Eq)]
+pub struct BlsSecretKey(Fr);
+
+impl BlsSecretKey {
+    /// Returns a randomly-generated secret key.
+    pub fn new<R: Rng>(rng: &mut R) -> Self {
+        let seed: [u8; 32] = rng.gen();
+        Self::from_seed(&seed).unwrap()
+    }
+
+    /// Returns the master secret key of the given seed, which is at least 32 bytes.
+    /// Adheres to EIP-2333 derive_master_SK.
+    pub fn from_seed(seed: &[u8]) -> Result<Self, BlsError> {
+        if seed.len() < MINIMUM_SEED_LENGTH {
+            return Err(BlsError::InvalidSeedLength(seed.len()));
+        }
+        Ok(Self(hkdf_mod_r(seed)))
+    }
+
+    /// Returns the secret key of the given derivation path from the master secret key of the given seed.
+    pub fn from_seed_with_path(seed: &[u8], path: &ValidatorDerivationPath) -> Result<Self, BlsError> {
+        Ok(path.to_vec().iter().fold(Self::from_seed(seed)?, |secret_key, index| {
+            secret_key.derive_child(*index)
+        }))
+    }
+
+    /// Returns the secret key of the given derivation path from the BIP39 seed of the given mnemonic
+    /// phrase and password. The words and checksum of the phrase are expected to be validated by the caller.
+    pub fn from_mnemonic(
+        phrase: &str,
+        password: Option<&str>,
+        path: &ValidatorDerivationPath,
+    ) -> Result<Self, BlsError> {
+        Self::from_seed_with_path(&mnemonic_to_seed(phrase, password), path)
+    }
+
+    /// Returns the secret key of the given 32-byte big-endian scalar.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        if bytes.len() != 32 {
+            return Err(BlsError::InvalidByteLength(bytes.len()));
+        }
+
+        let mut repr = FrRepr::default();
+        repr.read_be(bytes)
+            .map_err(|error| BlsError::InvalidSecretKey(error.to_string()))?;
+        match Fr::from_repr(repr) {
+            Ok(scalar) if !scalar.is_zero() => Ok(Self(scalar)),
+            Ok(_) => Err(BlsError::InvalidSecretKey("the secret key is zero".into())),
+            Err(error) => Err(BlsError::InvalidSecretKey(error.to_string())),
+        }
+    }
+
+    /// Returns the 32-byte big-endian scalar of the secret key.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        self.0.into_repr().write_be(&mut bytes[..]).unwrap();
+        bytes
+    }
+
+    /// Returns the public key of the secret key.
+    pub fn to_public_key(&self) -> BlsPublicKey {
+        BlsPublicKey(G1Affine::one().mul(self.0).into_affine())
+    }
+
+    /// Returns the child secret key of the given index.
+    /// Adheres to EIP-2333 derive_child_SK.
+    pub fn derive_child(&self, index: u32) -> Self {
+        Self(hkdf_mod_r(&self.to_lamport_public_key(index)))
+    }
+
+    /// Returns the signature of the given message.
+    pub fn sign(&self, message: &[u8]) -> BlsSignature {
+        self.sign_with_dst(message, SIGNATURE_DST)
+    }
+
+    /// Returns the proof of possession of the secret key, a signature of its public key.
+    pub fn prove_possession(&self) -> BlsSignature {
+        self.sign_with_dst(&self.to_public_key().to_bytes(), PROOF_OF_POSSESSION_DST)
+    }
+
+    /// Returns the signature of the given message under the given domain separation tag.
+    fn sign_with_dst(&self, message: &[u8], dst: &[u8]) -> BlsSignature {
+        let mut point = hash_to_g2(message, dst);
+        point.mul_assign(self.0);
+        BlsSignature(point.into_affine())
+    }
+
+    /// Returns the compressed Lamport public key of the given index.
+    /// Adheres to EIP-2333 parent_SK_to_lamport_PK.
+    fn to_lamport_public_key(&self, index: u32) -> [u8; 32] {
+        let salt = index.to_be_bytes();
+        let ikm = self.to_bytes();
+        let not_ikm: Vec<u8> = ikm.iter().map(|byte| !byte).collect();
+
+        let mut lamport_public_key = Sha256::new();
+        for ikm in [&ikm[..], &not_ikm[..]].iter() {
+            let mut prk = [0u8; 32];
+            hkdf_extract(crypto::sha2::Sha256::new(), &salt, ikm, &mut prk);
+            let mut lamport_secret_key = [0u8; 32 * LAMPORT_CHUNKS];
+            hkdf_expand(crypto::sha2::Sha256::new(), &prk, &[], &mut lamport_secret_key);
+
+            for chunk in lamport_secret_key.chunks(32) {
+                lamport_public_key.input(Sha256::digest(chunk));
+            }
+        }
+
+        let mut compressed = [0u8; 32];
+        compressed.copy_from_slice(&lamport_public_key.result());
+        compressed
+    }
+}
+
+impl FromStr for BlsSecretKey {
+    type Err = BlsError;
+
+    fn from_str(secret_key: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(&hex::decode(secret_key.trim_start_matches("0x"))?)
+    }
+}
+
+impl fmt::Debug for BlsSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BlsSecretKey({})", self)
+    }
+}
+
+impl fmt::Display for BlsSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.to_bytes()))
+    }
+}
+
+/// Represents a BLS public key, a point of G1 other than the identity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlsPublicKey(G1Affine);
+
+impl BlsPublicKey {
+    /// Returns the public key of the given 48-byte compressed point,
+    /// after validating that it is a point of G1 other than the identity.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        let mut compressed = G1Compressed::empty();
+        if bytes.len() != compressed.as_ref().len() {
+            return Err(BlsError::InvalidByteLength(bytes.len()));
+        }
+        compressed.as_mut().copy_from_slice(bytes);
+
+        match compressed.into_affine()? {
+            point if point.is_zero() => Err(BlsError::InvalidPublicKey("the public key is the identity".into())),
+            point => Ok(Self(point)),
+        }
+    }
+
+    /// Returns the 48-byte compressed point of the public key.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.into_compressed().as_ref().to_vec()
+    }
+
+    /// Returns `true` if the given signature is a valid signature of the given message by the public key.
+    pub fn verify(&self, message: &[u8], signature: &BlsSignature) -> bool {
+        self.verify_with_dst(message, signature, SIGNATURE_DST)
+    }
+
+    /// Returns `true` if the given signature is a valid proof of possession of the public key.
+    pub fn verify_possession(&self, proof: &BlsSignature) -> bool {
+        self.verify_with_dst(&self.to_bytes(), proof, PROOF_OF_POSSESSION_DST)
+    }
+
+    /// Returns `true` if e(public key, H(message)) = e(G1 generator, signature).
+    fn verify_with_dst(&self, message: &[u8], signature: &BlsSignature, dst: &[u8]) -> bool {
+        let mut generator = G1Affine::one();
+        generator.negate();
+
+        let message = hash_to_g2(message, dst).into_affine().prepare();
+        let result = Bls12::final_exponentiation(&Bls12::miller_loop(&[
+            (&self.0.prepare(), &message),
+            (&generator.prepare(), &signature.0.prepare()),
+        ]));
+        result == Some(Fq12::one())
+    }
+}
+
+impl FromStr for BlsPublicKey {
+    type Err = BlsError;
+
+    fn from_str(public_key: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(&hex::decode(public_key.trim_start_matches("0x"))?)
+    }
+}
+
+impl fmt::Display for BlsPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.to_bytes()))
+    }
+}
+
+/// Represents a BLS signature, a point of G2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlsSignature(G2Affine);
+
+impl BlsSignature {
+    /// Returns the signature of the given 96-byte compressed point,
+    /// after validating that it is a point of G2.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        let mut compressed = G2Compressed::empty();
+        if bytes.len() != compressed.as_ref().len() {
+            return Err(BlsError::InvalidByteLength(bytes.len()));
+        }
+        compressed.as_mut().copy_from_slice(bytes);
+        Ok(Self(compressed.into_affine()?))
+    }
+
+    /// Returns the 96-byte compressed point of the signature.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.into_compressed().as_ref().to_vec()
+    }
+}
+
+impl FromStr for BlsSignature {
+    type Err = BlsError;
+
+    fn from_str(signature: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(&hex::decode(signature.trim_start_matches("0x"))?)
+    }
+}
+
+impl fmt::Display for BlsSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.to_bytes()))
+    }
+}
+
+/// Returns the BIP39 seed of the given mnemonic phrase and password, PBKDF2-HMAC-SHA512 of their NFKD forms.
+pub fn mnemonic_to_seed(phrase: &str, password: Option<&str>) -> Vec<u8> {
+    let phrase: String = phrase.nfkd().collect();
+    let salt: String = format!("mnemonic{}", password.unwrap_or("")).nfkd().collect();
+
+    let mut seed = vec![0u8; 64];
+    let mut mac = Hmac::new(crypto::sha2::Sha512::new(), phrase.as_bytes());
+    pbkdf2(&mut mac, salt.as_bytes(), BIP39_PBKDF2_ROUNDS, &mut seed);
+    seed
+}
+
+/// Returns a nonzero scalar derived from the given key material.
+/// Adheres to EIP-2333 HKDF_mod_r, with an empty key info.
+fn hkdf_mod_r(ikm: &[u8]) -> Fr {
+    let ikm = [ikm, &[0u8]].concat();
+    let mut salt = Sha256::digest(KEYGEN_SALT);
+    loop {
+        let mut prk = [0u8; 32];
+        hkdf_extract(crypto::sha2::Sha256::new(), &salt, &ikm, &mut prk);
+        let mut okm = [0u8; KEYGEN_LENGTH];
+        hkdf_expand(
+            crypto::sha2::Sha256::new(),
+            &prk,
+            &(KEYGEN_LENGTH as u16).to_be_bytes(),
+            &mut okm,
+        );
+
+        // The 384-bit key material is reduced as three 128-bit limbs, each smaller than r.
+        let limb = |bytes: &[u8]| {
+            let mut repr = FrRepr::default();
+            repr.read_be(&[&[0u8; 16][..], bytes].concat()[..]).unwrap();
+            Fr::from_repr(repr).unwrap()
+        };
+        let mut shift = FrRepr::default();
+        shift.0[2] = 1;
+        let shift = Fr::from_repr(shift).unwrap();
+
+        let mut scalar = Fr::zero();
+        for bytes in okm.chunks(16) {
+            scalar.mul_assign(&shift);
+            scalar.add_assign(&limb(bytes));
+        }
+
+        if !scalar.is_zero() {
+            return scalar;
+        }
+        salt = Sha256::digest(&salt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    // (seed, master secret key, child index, child secret key)
+    const EIP2333: [(&str, &str, u32, &str); 4] = [
+        (
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            "6083874454709270928345386274498605044986640685124978867557563392430687146096",
+            0,
+            "20397789859736650942317412262472558107875392172444076792671091975210932703118",
+        ),
+        (
+            "3141592653589793238462643383279502884197169399375105820974944592",
+            "29757020647961307431480504535336562678282505419141012933316116377660817309383",
+            3141592653,
+            "25457201688850691947727629385191704516744796114925897962676248250929345014287",
+        ),
+        (
+            "0099FF991111002299DD7744EE3355BBDD8844115566CC55663355668888CC00",
+            "27580842291869792442942448775674722299803720648445448686099262467207037398656",
+            4294967295,
+            "29358610794459428860402234341874281240803786294062035874021252734817515685787",
+        ),
+        (
+            "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+            "19022158461524446591288038168518313374041767046816487870552872741050760015818",
+            42,
+            "31372231650479070279774297061823572166496564838472787488249775572789064611981",
+        ),
+    ];
+
+    // (secret key, message, public key, signature)
+    const SIGNATURES: [(&str, &str, &str, &str); 2] = [
+        (
+            "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a",
+            "b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55",
+        ),
+        (
+            "47b8192d77bf871b62e87859d653922725724a5c031afeabc60bcef5ff665138",
+            "5656565656565656565656565656565656565656565656565656565656565656",
+            "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81",
+            "af1390c3c47acdb37131a51216da683c509fce0e954328a59f93aebda7e4ff974ba208d9a4a2a2389f892a9d418d618418dd7f7a6bc7aa0da999a9d3a5b815bc085e14fd001f6a1948768a3f4afefc8b8240dda329f984cb345c6363272ba4fe",
+        ),
+    ];
+
+    #[test]
+    fn from_seed() {
+        EIP2333
+            .iter()
+            .for_each(|(seed, master_secret_key, index, child_secret_key)| {
+                let master = BlsSecretKey::from_seed(&hex::decode(seed).unwrap()).unwrap();
+                assert_eq!(Fr::from_str(master_secret_key).unwrap(), master.0);
+                assert_eq!(Fr::from_str(child_secret_key).unwrap(), master.derive_child(*index).0);
+            });
+    }
+
+    #[test]
+    fn from_seed_with_path() {
+        let (seed, _, index, child_secret_key) = EIP2333[3];
+        let path = ValidatorDerivationPath::from_str(&format!("m/{}", index)).unwrap();
+        let secret_key = BlsSecretKey::from_seed_with_path(&hex::decode(seed).unwrap(), &path).unwrap();
+        assert_eq!(Fr::from_str(child_secret_key).unwrap(), secret_key.0);
+    }
+
+    #[test]
+    fn from_mnemonic() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let (seed, master_secret_key, _, _) = EIP2333[0];
+        assert_eq!(seed, hex::encode(mnemonic_to_seed(phrase, Some("TREZOR"))));
+
+        let secret_key =
+            BlsSecretKey::from_mnemonic(phrase, Some("TREZOR"), &ValidatorDerivationPath::from(vec![])).unwrap();
+        assert_eq!(Fr::from_str(master_secret_key).unwrap(), secret_key.0);
+    }
+
+    #[test]
+    fn invalid_seed() {
+        assert!(BlsSecretKey::from_seed(&[0u8; 31]).is_err());
+    }
+
+    #[test]
+    fn sign() {
+        SIGNATURES
+            .iter()
+            .for_each(|(secret_key, message, public_key, signature)| {
+                let secret_key = BlsSecretKey::from_str(secret_key).unwrap();
+                let message = hex::decode(message).unwrap();
+                let expected_signature = BlsSignature::from_str(signature).unwrap();
+
+                assert_eq!(*public_key, secret_key.to_public_key().to_string());
+                assert_eq!(*signature, secret_key.sign(&message).to_string());
+                assert!(secret_key.to_public_key().verify(&message, &expected_signature));
+                assert!(!secret_key.to_public_key().verify(&[0u8; 32][..31], &expected_signature));
+            });
+    }
+
+    #[test]
+    fn prove_possession() {
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        let secret_key = BlsSecretKey::new(&mut rng);
+        let public_key = secret_key.to_public_key();
+        let proof = secret_key.prove_possession();
+
+        assert!(public_key.verify_possession(&proof));
+        assert!(!public_key.verify(&public_key.to_bytes(), &proof));
+        assert!(!BlsSecretKey::new(&mut rng).to_public_key().verify_possession(&proof));
+    }
+
+    #[test]
+    fn secret_key_bytes() {
+        let mut rng = XorShiftRng::seed_from_u64(1);
+        let secret_key = BlsSecretKey::new(&mut rng);
+        assert_eq!(secret_key, BlsSecretKey::from_bytes(&secret_key.to_bytes()).unwrap());
+        assert_eq!(secret_key, BlsSecretKey::from_str(&secret_key.to_string()).unwrap());
+    }
+
+    #[test]
+    fn invalid_secret_key() {
+        assert!(BlsSecretKey::from_bytes(&[0u8; 32]).is_err());
+        assert!(BlsSecretKey::from_bytes(&[0xffu8; 32]).is_err());
+        assert!(BlsSecretKey::from_bytes(&[1u8; 31]).is_err());
+    }
+
+    #[test]
+    fn invalid_public_key() {
+        // The compressed identity
+        let mut identity = [0u8; 48];
+        identity[0] = 0xc0;
+        assert!(BlsPublicKey::from_bytes(&identity).is_err());
+        assert!(BlsPublicKey::from_bytes(&[0u8; 47]).is_err());
+    }
+}
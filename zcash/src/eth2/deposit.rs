@@ -0,0 +1,451 @@
+//!
+//! Validator Deposit Data
+//!
+//! This module contains the deposit data of validators, in the deposit_data.json format of the
+//! staking deposit CLI, with its signing root and roots computed as SSZ hash tree roots.
+//! https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/beacon-chain.md#deposits
+//!
+
+use crate::eth2::bls::{BlsError, BlsPublicKey, BlsSecretKey, BlsSignature};
+use crate::eth2::derivation_path::ValidatorDerivationPath;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{fmt, str::FromStr};
+
+/// The domain type of deposits
+pub const DOMAIN_DEPOSIT: [u8; 4] = [0x03, 0x00, 0x00, 0x00];
+
+/// The prefix of withdrawal credentials of a BLS withdrawal key
+pub const BLS_WITHDRAWAL_PREFIX: u8 = 0x00;
+
+/// The prefix of withdrawal credentials of an execution address
+pub const ETH1_ADDRESS_WITHDRAWAL_PREFIX: u8 = 0x01;
+
+/// The minimum deposit amount in Gwei, 1 ETH
+pub const MIN_DEPOSIT_AMOUNT: u64 = 1_000_000_000;
+
+/// The deposit amount of a validator in Gwei, 32 ETH
+pub const MAX_EFFECTIVE_BALANCE: u64 = 32_000_000_000;
+
+/// The version of the staking deposit CLI whose format the deposit data follows
+pub const DEPOSIT_CLI_VERSION: &str = "2.7.0";
+
+#[derive(Debug, Fail)]
+pub enum DepositError {
+    #[fail(display = "{}", _0)]
+    BlsError(BlsError),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "invalid deposit amount: {} Gwei", _0)]
+    InvalidAmount(u64),
+
+    #[fail(display = "invalid deposit root: expected {}, found {}", _0, _1)]
+    InvalidRoot(String, String),
+
+    #[fail(display = "invalid deposit signature")]
+    InvalidSignature,
+
+    #[fail(display = "invalid withdrawal credentials: {}", _0)]
+    InvalidWithdrawalCredentials(String),
+
+    #[fail(display = "unsupported network: {}", _0)]
+    UnsupportedNetwork(String),
+}
+
+impl From<BlsError> for DepositError {
+    fn from(error: BlsError) -> Self {
+        DepositError::BlsError(error)
+    }
+}
+
+impl From<hex::FromHexError> for DepositError {
+    fn from(error: hex::FromHexError) -> Self {
+        DepositError::Crate("hex", format!("{:?}", error))
+    }
+}
+
+impl From<serde_json::error::Error> for DepositError {
+    fn from(error: serde_json::error::Error) -> Self {
+        DepositError::Crate("serde_json", format!("{:?}", error))
+    }
+}
+
+/// Represents a consensus-layer network that accepts deposits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepositNetwork {
+    Mainnet,
+    Sepolia,
+    Holesky,
+    Hoodi,
+}
+
+impl DepositNetwork {
+    /// Returns the genesis fork version of the network.
+    pub fn fork_version(&self) -> [u8; 4] {
+        match self {
+            DepositNetwork::Mainnet => [0x00, 0x00, 0x00, 0x00],
+            DepositNetwork::Sepolia => [0x90, 0x00, 0x00, 0x69],
+            DepositNetwork::Holesky => [0x01, 0x01, 0x70, 0x00],
+            DepositNetwork::Hoodi => [0x10, 0x00, 0x09, 0x10],
+        }
+    }
+}
+
+impl FromStr for DepositNetwork {
+    type Err = DepositError;
+
+    fn from_str(network: &str) -> Result<Self, Self::Err> {
+        match network {
+            "mainnet" => Ok(DepositNetwork::Mainnet),
+            "sepolia" => Ok(DepositNetwork::Sepolia),
+            "holesky" => Ok(DepositNetwork::Holesky),
+            "hoodi" => Ok(DepositNetwork::Hoodi),
+            _ => Err(DepositError::UnsupportedNetwork(network.into())),
+        }
+    }
+}
+
+impl fmt::Display for DepositNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DepositNetwork::Mainnet => write!(f, "mainnet"),
+            DepositNetwork::Sepolia => write!(f, "sepolia"),
+            DepositNetwork::Holesky => write!(f, "holesky"),
+            DepositNetwork::Hoodi => write!(f, "hoodi"),
+        }
+    }
+}
+
+/// Represents the 32-byte withdrawal credentials of a validator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WithdrawalCredentials([u8; 32]);
+
+impl WithdrawalCredentials {
+    /// Returns the withdrawal credentials of the given BLS withdrawal key, 0x00 || SHA256(public key)[1..].
+    pub fn from_bls_public_key(public_key: &BlsPublicKey) -> Self {
+        let mut credentials = [0u8; 32];
+        credentials.copy_from_slice(&Sha256::digest(&public_key.to_bytes()));
+        credentials[0] = BLS_WITHDRAWAL_PREFIX;
+        Self(credentials)
+    }
+
+    /// Returns the withdrawal credentials of the given execution address, 0x01 || 0x00 * 11 || address.
+    pub fn from_execution_address(address: &[u8; 20]) -> Self {
+        let mut credentials = [0u8; 32];
+        credentials[0] = ETH1_ADDRESS_WITHDRAWAL_PREFIX;
+        credentials[12..].copy_from_slice(address);
+        Self(credentials)
+    }
+
+    /// Returns the 32 bytes of the withdrawal credentials.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl FromStr for WithdrawalCredentials {
+    type Err = DepositError;
+
+    /// Returns the withdrawal credentials of the given 20-byte execution address or 32-byte credentials.
+    fn from_str(credentials: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(credentials.trim_start_matches("0x"))?;
+        match bytes.len() {
+            20 => {
+                let mut address = [0u8; 20];
+                address.copy_from_slice(&bytes);
+                Ok(Self::from_execution_address(&address))
+            }
+            32 => {
+                let mut credentials = [0u8; 32];
+                credentials.copy_from_slice(&bytes);
+                Ok(Self(credentials))
+            }
+            _ => Err(DepositError::InvalidWithdrawalCredentials(credentials.into())),
+        }
+    }
+}
+
+impl fmt::Display for WithdrawalCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+/// Represents the deposit data of a validator, as an entry of deposit_data.json
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DepositData {
+    pub pubkey: String,
+    pub withdrawal_credentials: String,
+    pub amount: u64,
+    pub signature: String,
+    pub deposit_message_root: String,
+    pub deposit_data_root: String,
+    pub fork_version: String,
+    pub network_name: String,
+    pub deposit_cli_version: String,
+}
+
+impl DepositData {
+    /// Returns the deposit data of the given validator secret key, withdrawal credentials,
+    /// and amount in Gwei, signed for the given network.
+    pub fn new(
+        secret_key: &BlsSecretKey,
+        withdrawal_credentials: &WithdrawalCredentials,
+        amount: u64,
+        network: &DepositNetwork,
+    ) -> Result<Self, DepositError> {
+        if amount < MIN_DEPOSIT_AMOUNT {
+            return Err(DepositError::InvalidAmount(amount));
+        }
+
+        let public_key = secret_key.to_public_key();
+        let deposit_message_root = deposit_message_root(&public_key, withdrawal_credentials, amount);
+        let signature = secret_key.sign(&signing_root(&deposit_message_root, &network.fork_version()));
+        let deposit_data_root = deposit_data_root(&public_key, withdrawal_credentials, amount, &signature);
+
+        Ok(Self {
+            pubkey: public_key.to_string(),
+            withdrawal_credentials: withdrawal_credentials.to_string(),
+            amount,
+            signature: signature.to_string(),
+            deposit_message_root: hex::encode(deposit_message_root),
+            deposit_data_root: hex::encode(deposit_data_root),
+            fork_version: hex::encode(network.fork_version()),
+            network_name: network.to_string(),
+            deposit_cli_version: DEPOSIT_CLI_VERSION.into(),
+        })
+    }
+
+    /// Returns the deposit data of the validators of the given indices, derived from the given seed
+    /// at their EIP-2334 signing paths, in the format of deposit_data.json.
+    pub fn from_seed(
+        seed: &[u8],
+        indices: std::ops::Range<u32>,
+        withdrawal_credentials: &WithdrawalCredentials,
+        amount: u64,
+        network: &DepositNetwork,
+    ) -> Result<Vec<Self>, DepositError> {
+        indices
+            .map(|index| {
+                let secret_key = BlsSecretKey::from_seed_with_path(seed, &ValidatorDerivationPath::signing(index))?;
+                Self::new(&secret_key, withdrawal_credentials, amount, network)
+            })
+            .collect()
+    }
+
+    /// Verifies the roots of the deposit data and its signature for its fork version.
+    pub fn verify(&self) -> Result<(), DepositError> {
+        let public_key = BlsPublicKey::from_str(&self.pubkey)?;
+        let withdrawal_credentials = WithdrawalCredentials::from_str(&self.withdrawal_credentials)?;
+        let signature = BlsSignature::from_str(&self.signature)?;
+
+        let mut fork_version = [0u8; 4];
+        match hex::decode(&self.fork_version)? {
+            ref bytes if bytes.len() == 4 => fork_version.copy_from_slice(bytes),
+            _ => return Err(DepositError::UnsupportedNetwork(self.fork_version.clone())),
+        }
+
+        let deposit_message_root = deposit_message_root(&public_key, &withdrawal_credentials, self.amount);
+        let deposit_data_root = deposit_data_root(&public_key, &withdrawal_credentials, self.amount, &signature);
+        for (expected, root) in [
+            (&self.deposit_message_root, deposit_message_root),
+            (&self.deposit_data_root, deposit_data_root),
+        ]
+        .iter()
+        {
+            if expected.as_str() != hex::encode(root) {
+                return Err(DepositError::InvalidRoot(expected.to_string(), hex::encode(root)));
+            }
+        }
+
+        match public_key.verify(&signing_root(&deposit_message_root, &fork_version), &signature) {
+            true => Ok(()),
+            false => Err(DepositError::InvalidSignature),
+        }
+    }
+}
+
+/// Returns the hash tree root of DepositMessage(pubkey, withdrawal_credentials, amount).
+fn deposit_message_root(
+    public_key: &BlsPublicKey,
+    withdrawal_credentials: &WithdrawalCredentials,
+    amount: u64,
+) -> [u8; 32] {
+    merkleize(&[
+        merkleize(&chunks(&public_key.to_bytes())),
+        withdrawal_credentials.to_bytes(),
+        chunks(&amount.to_le_bytes())[0],
+    ])
+}
+
+/// Returns the hash tree root of DepositData(pubkey, withdrawal_credentials, amount, signature).
+fn deposit_data_root(
+    public_key: &BlsPublicKey,
+    withdrawal_credentials: &WithdrawalCredentials,
+    amount: u64,
+    signature: &BlsSignature,
+) -> [u8; 32] {
+    merkleize(&[
+        merkleize(&chunks(&public_key.to_bytes())),
+        withdrawal_credentials.to_bytes(),
+        chunks(&amount.to_le_bytes())[0],
+        merkleize(&chunks(&signature.to_bytes())),
+    ])
+}
+
+/// Returns the hash tree root of SigningData(object_root, domain), where the domain is the deposit
+/// domain of the given fork version. Deposits are valid across forks, so the domain is computed
+/// with an empty genesis validators root.
+fn signing_root(object_root: &[u8; 32], fork_version: &[u8; 4]) -> [u8; 32] {
+    let fork_data_root = merkleize(&[chunks(fork_version)[0], [0u8; 32]]);
+
+    let mut domain = [0u8; 32];
+    domain[..4].copy_from_slice(&DOMAIN_DEPOSIT);
+    domain[4..].copy_from_slice(&fork_data_root[..28]);
+
+    merkleize(&[*object_root, domain])
+}
+
+/// Returns the given bytes as 32-byte chunks, with the last chunk padded with zeros.
+fn chunks(bytes: &[u8]) -> Vec<[u8; 32]> {
+    bytes
+        .chunks(32)
+        .map(|bytes| {
+            let mut chunk = [0u8; 32];
+            chunk[..bytes.len()].copy_from_slice(bytes);
+            chunk
+        })
+        .collect()
+}
+
+/// Returns the root of the binary Merkle tree of the given chunks,
+/// padded with zero chunks to the next power of two.
+fn merkleize(chunks: &[[u8; 32]]) -> [u8; 32] {
+    let mut layer = chunks.to_vec();
+    layer.resize(chunks.len().next_power_of_two(), [0u8; 32]);
+    while layer.len() > 1 {
+        layer = layer
+            .chunks(2)
+            .map(|pair| {
+                let mut node = [0u8; 32];
+                node.copy_from_slice(&Sha256::new().chain(&pair[0]).chain(&pair[1]).result());
+                node
+            })
+            .collect();
+    }
+    layer[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: &str = "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04";
+
+    const EXECUTION_ADDRESS: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+    // (pubkey, withdrawal credentials, signature, deposit message root, deposit data root)
+    const MAINNET_DEPOSIT: (&str, &str, &str, &str, &str) = (
+        "b37247817d65f235d0053fa179be32aa86e37f0ddb05586146f0e3e9c418c06c6aec0c0ba3799b3e1357870caf7b4aa7",
+        "010000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa96045",
+        "b8cf01ff64df5cdbd2ceca9382353a306a699a1fb8c82de456c9936c2593e02a4f5ce10111e1350c00825a88e11a5b3f00ef0c87e12b0e8bec2ba2919f4aa4568c6aff6f4dbe49eaab423ed6ad773535cf67673a6b9a45bfefe31b09ded16246",
+        "2a9a30d12dc471f08afcd7898a5a29b9625376c87a3d21fb18047a2b838634fa",
+        "af5bec101858717a749bb5b633c8b8f184ce7ca1602c9f1002de547cfdc00444",
+    );
+
+    const HOLESKY_DEPOSIT: (&str, &str, &str, &str, &str) = (
+        "b0639f63f1518fff936c574afea99c0980c29a0837c29c055458c4d65a11c7e239d9c6e4dba172ac2b6932577cf3d0f3",
+        "00f524bfa8f2106351ea565a1506e46e8d28a363350d5187b935aef418a6f8f8",
+        "8b4046de329fc570f37dcd96bc6c9ee1c64872dc4147d6dbcaa73b5d5e4a0137dd557c55e283b4853472b9d53654cd1d0d4060bd645dfc8a8689044250cbcdc7ed78298bc428e4aa072fb7ec3317630fa45ba1b167bec74cd8915160397a778f",
+        "befe3f827eec97c29b70d90e1b18ec4240f4ce1b08c188c606bfea03504029e3",
+        "9b3d57c0ef0108d033818510fe807a04878ae6139d3049d140b96505746ca6be",
+    );
+
+    fn test_deposit(deposit: &DepositData, expected: &(&str, &str, &str, &str, &str), network: &DepositNetwork) {
+        let (pubkey, withdrawal_credentials, signature, deposit_message_root, deposit_data_root) = *expected;
+        assert_eq!(pubkey, deposit.pubkey);
+        assert_eq!(withdrawal_credentials, deposit.withdrawal_credentials);
+        assert_eq!(MAX_EFFECTIVE_BALANCE, deposit.amount);
+        assert_eq!(signature, deposit.signature);
+        assert_eq!(deposit_message_root, deposit.deposit_message_root);
+        assert_eq!(deposit_data_root, deposit.deposit_data_root);
+        assert_eq!(hex::encode(network.fork_version()), deposit.fork_version);
+        assert_eq!(network.to_string(), deposit.network_name);
+        deposit.verify().unwrap();
+    }
+
+    #[test]
+    fn from_seed() {
+        let seed = hex::decode(SEED).unwrap();
+        let withdrawal_credentials = WithdrawalCredentials::from_str(EXECUTION_ADDRESS).unwrap();
+        let network = DepositNetwork::Mainnet;
+
+        let deposits =
+            DepositData::from_seed(&seed, 0..2, &withdrawal_credentials, MAX_EFFECTIVE_BALANCE, &network).unwrap();
+        assert_eq!(2, deposits.len());
+        test_deposit(&deposits[0], &MAINNET_DEPOSIT, &network);
+        deposits[1].verify().unwrap();
+    }
+
+    #[test]
+    fn new_with_bls_withdrawal_credentials() {
+        let seed = hex::decode(SEED).unwrap();
+        let secret_key = BlsSecretKey::from_seed_with_path(&seed, &ValidatorDerivationPath::signing(1)).unwrap();
+        let withdrawal_key = BlsSecretKey::from_seed_with_path(&seed, &ValidatorDerivationPath::withdrawal(1)).unwrap();
+        let withdrawal_credentials = WithdrawalCredentials::from_bls_public_key(&withdrawal_key.to_public_key());
+        let network = DepositNetwork::from_str("holesky").unwrap();
+
+        let deposit = DepositData::new(&secret_key, &withdrawal_credentials, MAX_EFFECTIVE_BALANCE, &network).unwrap();
+        test_deposit(&deposit, &HOLESKY_DEPOSIT, &network);
+    }
+
+    #[test]
+    fn deposit_data_json() {
+        let seed = hex::decode(SEED).unwrap();
+        let withdrawal_credentials = WithdrawalCredentials::from_str(EXECUTION_ADDRESS).unwrap();
+        let deposits = DepositData::from_seed(
+            &seed,
+            0..1,
+            &withdrawal_credentials,
+            MAX_EFFECTIVE_BALANCE,
+            &DepositNetwork::Mainnet,
+        )
+        .unwrap();
+
+        let json = serde_json::to_string(&deposits).unwrap();
+        assert!(json.starts_with(&format!("[{{\"pubkey\":\"{}\"", MAINNET_DEPOSIT.0)));
+        assert!(json.ends_with("\"network_name\":\"mainnet\",\"deposit_cli_version\":\"2.7.0\"}]"));
+        assert_eq!(deposits, serde_json::from_str::<Vec<DepositData>>(&json).unwrap());
+    }
+
+    #[test]
+    fn invalid_deposit() {
+        let seed = hex::decode(SEED).unwrap();
+        let secret_key = BlsSecretKey::from_seed_with_path(&seed, &ValidatorDerivationPath::signing(0)).unwrap();
+        let withdrawal_credentials = WithdrawalCredentials::from_str(EXECUTION_ADDRESS).unwrap();
+        let network = DepositNetwork::Mainnet;
+        assert!(DepositData::new(&secret_key, &withdrawal_credentials, MIN_DEPOSIT_AMOUNT - 1, &network).is_err());
+
+        let deposit = DepositData::new(&secret_key, &withdrawal_credentials, MAX_EFFECTIVE_BALANCE, &network).unwrap();
+
+        let mut amount = deposit.clone();
+        amount.amount = MIN_DEPOSIT_AMOUNT;
+        assert!(amount.verify().is_err());
+
+        let mut fork_version = deposit.clone();
+        fork_version.fork_version = hex::encode(DepositNetwork::Holesky.fork_version());
+        match fork_version.verify() {
+            Err(DepositError::InvalidSignature) => (),
+            result => panic!("expected an invalid signature, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn invalid_withdrawal_credentials() {
+        assert!(WithdrawalCredentials::from_str("0x0102").is_err());
+        assert!(WithdrawalCredentials::from_str("zz").is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use wagyu_model::derivation_path::DerivationPathError;
+
+use std::{fmt, str::FromStr};
+
+/// The purpose of EIP-2334 paths
+pub const VALIDATOR_PURPOSE: u32 = 12381;
+
+/// The coin type of Ethereum in EIP-2334 paths
+pub const VALIDATOR_COIN_TYPE: u32 = 3600;
+
+/// Represents an EIP-2333 derivation path, a sequence of 32-bit indices without hardening
+/// https://eips.ethereum.org/EIPS/eip-2334
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorDerivationPath(Vec<u32>);
+
+impl ValidatorDerivationPath {
+    /// Returns the EIP-2334 path of the signing key of the given validator, m/12381/3600/{index}/0/0.
+    pub fn signing(index: u32) -> Self {
+        Self(vec![VALIDATOR_PURPOSE, VALIDATOR_COIN_TYPE, index, 0, 0])
+    }
+
+    /// Returns the EIP-2334 path of the withdrawal key of the given validator, m/12381/3600/{index}/0.
+    pub fn withdrawal(index: u32) -> Self {
+        Self(vec![VALIDATOR_PURPOSE, VALIDATOR_COIN_TYPE, index, 0])
+    }
+
+    /// Returns the indices of the path.
+    pub fn to_vec(&self) -> Vec<u32> {
+        self.0.clone()
+    }
+
+    /// Returns `true` if the path is an EIP-2334 path of Ethereum, m/12381/3600/{account}/...
+    pub fn is_eip2334(&self) -> bool {
+        self.0.len() >= 3 && self.0[0] == VALIDATOR_PURPOSE && self.0[1] == VALIDATOR_COIN_TYPE
+    }
+}
+
+impl From<Vec<u32>> for ValidatorDerivationPath {
+    fn from(path: Vec<u32>) -> Self {
+        Self(path)
+    }
+}
+
+impl FromStr for ValidatorDerivationPath {
+    type Err = DerivationPathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let mut parts = path.split('/');
+
+        if parts.next() != Some("m") {
+            return Err(DerivationPathError::InvalidDerivationPath(path.to_string()));
+        }
+
+        let path: Result<Vec<u32>, Self::Err> = parts
+            .map(|index| u32::from_str(index).map_err(|_| DerivationPathError::InvalidChildNumberFormat))
+            .collect();
+        Ok(Self(path?))
+    }
+}
+
+impl fmt::Display for ValidatorDerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for index in self.0.iter() {
+            write!(f, "/{}", index)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signing() {
+        assert_eq!("m/12381/3600/0/0/0", ValidatorDerivationPath::signing(0).to_string());
+        assert_eq!("m/12381/3600/7/0/0", ValidatorDerivationPath::signing(7).to_string());
+        assert!(ValidatorDerivationPath::signing(7).is_eip2334());
+    }
+
+    #[test]
+    fn withdrawal() {
+        assert_eq!("m/12381/3600/0/0", ValidatorDerivationPath::withdrawal(0).to_string());
+        assert!(ValidatorDerivationPath::withdrawal(0).is_eip2334());
+    }
+
+    #[test]
+    fn from_str() {
+        const PATHS: [&str; 4] = ["m", "m/0", "m/12381/3600/0/0/0", "m/12381/60/4294967295/0"];
+
+        PATHS.iter().for_each(|path| {
+            assert_eq!(*path, ValidatorDerivationPath::from_str(path).unwrap().to_string());
+        });
+        assert!(!ValidatorDerivationPath::from_str("m/12381/60/0/0")
+            .unwrap()
+            .is_eip2334());
+    }
+
+    #[test]
+    fn invalid_path() {
+        const PATHS: [&str; 5] = ["", "n/0", "m/0'", "m/4294967296", "m//0"];
+
+        PATHS.iter().for_each(|path| {
+            assert!(ValidatorDerivationPath::from_str(path).is_err());
+        });
+    }
+}
@@ -0,0 +1,403 @@
+//!
+//! Hashing to BLS12-381 G2
+//!
+//! This module contains the BLS12381G2_XMD:SHA-256_SSWU_RO_ suite of RFC 9380, which hashes messages
+//! to points of G2 with the simplified SWU map to a 3-isogenous curve.
+//! https://www.rfc-editor.org/rfc/rfc9380
+//!
+
+use crate::librustzcash::algebra::curve::bls12_381::{Fq, Fq2, FqRepr, G2Affine, G2};
+use crate::librustzcash::algebra::field::{BitIterator, Field, PrimeField, PrimeFieldRepr, SqrtField};
+use crate::librustzcash::algebra::group::{CurveAffine, CurveProjective};
+
+use sha2::{Digest, Sha256};
+
+/// The input block size of SHA-256
+const SHA256_BLOCK_SIZE: usize = 64;
+
+/// The output size of SHA-256
+const SHA256_OUTPUT_SIZE: usize = 32;
+
+/// The number of uniform bytes reduced to each element of Fq, for 128 bits of security
+const FIELD_ELEMENT_SIZE: usize = 64;
+
+/// The prefix of the hash of domain separation tags longer than 255 bytes
+const OVERSIZE_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+/// The scalar h_eff that clears the cofactor of points on the twist, in little-endian 64-bit limbs
+const H_EFF: [u64; 10] = [
+    0xe8020005aaa95551,
+    0x59894c0adebbf6b4,
+    0xe954cbc06689f6a3,
+    0x2ec0ec69d7477c1a,
+    0x6d82bf015d1212b0,
+    0x329c2f178731db95,
+    0x9986ff031508ffe1,
+    0x88e2a8e9145ad768,
+    0x584c6a0ea91b3528,
+    0x0bc69f08f2ee75b3,
+];
+
+/// Represents the constants of the simplified SWU map to the curve E2': y^2 = x^3 + A'x + B',
+/// and of the 3-isogeny from E2' to the twist E2, with coefficients in ascending degree
+struct SimplifiedSWU {
+    a: Fq2,
+    b: Fq2,
+    z: Fq2,
+    /// -B' / A'
+    minus_b_over_a: Fq2,
+    /// B' / (Z * A')
+    b_over_za: Fq2,
+    x_numerator: [Fq2; 4],
+    x_denominator: [Fq2; 3],
+    y_numerator: [Fq2; 4],
+    y_denominator: [Fq2; 4],
+}
+
+lazy_static! {
+    static ref SSWU: SimplifiedSWU = {
+        let a = fq2("0", "240");
+        let b = fq2("1012", "1012");
+        let mut z = fq2("2", "1");
+        z.negate();
+
+        let a_inverse = a.inverse().unwrap();
+        let mut minus_b_over_a = b;
+        minus_b_over_a.mul_assign(&a_inverse);
+        minus_b_over_a.negate();
+
+        let mut b_over_za = b;
+        b_over_za.mul_assign(&a_inverse);
+        b_over_za.mul_assign(&z.inverse().unwrap());
+
+        SimplifiedSWU {
+            a,
+            b,
+            z,
+            minus_b_over_a,
+            b_over_za,
+            x_numerator: [
+                fq2(
+                    "889424345604814976315064405719089812568196182208668418962679585805340366775741747653930584250892369786198727235542",
+                    "889424345604814976315064405719089812568196182208668418962679585805340366775741747653930584250892369786198727235542",
+                ),
+                fq2(
+                    "0",
+                    "2668273036814444928945193217157269437704588546626005256888038757416021100327225242961791752752677109358596181706522",
+                ),
+                fq2(
+                    "2668273036814444928945193217157269437704588546626005256888038757416021100327225242961791752752677109358596181706526",
+                    "1334136518407222464472596608578634718852294273313002628444019378708010550163612621480895876376338554679298090853261",
+                ),
+                fq2(
+                    "3557697382419259905260257622876359250272784728834673675850718343221361467102966990615722337003569479144794908942033",
+                    "0",
+                ),
+            ],
+            x_denominator: [
+                fq2(
+                    "0",
+                    "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559715",
+                ),
+                fq2(
+                    "12",
+                    "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559775",
+                ),
+                fq2("1", "0"),
+            ],
+            y_numerator: [
+                fq2(
+                    "3261222600550988246488569487636662646083386001431784202863158481286248011511053074731078808919938689216061999863558",
+                    "3261222600550988246488569487636662646083386001431784202863158481286248011511053074731078808919938689216061999863558",
+                ),
+                fq2(
+                    "0",
+                    "889424345604814976315064405719089812568196182208668418962679585805340366775741747653930584250892369786198727235518",
+                ),
+                fq2(
+                    "2668273036814444928945193217157269437704588546626005256888038757416021100327225242961791752752677109358596181706524",
+                    "1334136518407222464472596608578634718852294273313002628444019378708010550163612621480895876376338554679298090853263",
+                ),
+                fq2(
+                    "2816510427748580758331037284777117739799287910327449993381818688383577828123182200904113516794492504322962636245776",
+                    "0",
+                ),
+            ],
+            y_denominator: [
+                fq2(
+                    "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559355",
+                    "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559355",
+                ),
+                fq2(
+                    "0",
+                    "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559571",
+                ),
+                fq2(
+                    "18",
+                    "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559769",
+                ),
+                fq2("1", "0"),
+            ],
+        }
+    };
+}
+
+/// Returns the element of Fq2 with the given decimal coefficients.
+fn fq2(c0: &str, c1: &str) -> Fq2 {
+    Fq2 {
+        c0: Fq::from_str(c0).unwrap(),
+        c1: Fq::from_str(c1).unwrap(),
+    }
+}
+
+/// Returns the given number of uniform bytes derived from the message and domain separation tag,
+/// with expand_message_xmd and SHA-256.
+pub fn expand_message_xmd(message: &[u8], dst: &[u8], length: usize) -> Vec<u8> {
+    assert!(
+        length <= 255 * SHA256_OUTPUT_SIZE && length <= 0xffff,
+        "invalid expand_message_xmd length"
+    );
+
+    let dst = match dst.len() > 255 {
+        true => Sha256::digest(&[OVERSIZE_DST_PREFIX, dst].concat()).to_vec(),
+        false => dst.to_vec(),
+    };
+    let dst_prime = [&dst[..], &[dst.len() as u8]].concat();
+
+    let b_0 = Sha256::new()
+        .chain(&[0u8; SHA256_BLOCK_SIZE][..])
+        .chain(message)
+        .chain(&(length as u16).to_be_bytes())
+        .chain(&[0u8])
+        .chain(&dst_prime)
+        .result();
+
+    let mut uniform_bytes = Vec::with_capacity(length);
+    let mut b_i = Sha256::new().chain(&b_0).chain(&[1u8]).chain(&dst_prime).result();
+    for i in 2..=(length + SHA256_OUTPUT_SIZE - 1) / SHA256_OUTPUT_SIZE {
+        uniform_bytes.extend_from_slice(&b_i);
+        let xor: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+        b_i = Sha256::new().chain(&xor).chain(&[i as u8]).chain(&dst_prime).result();
+    }
+    uniform_bytes.extend_from_slice(&b_i);
+    uniform_bytes.truncate(length);
+    uniform_bytes
+}
+
+/// Returns the point of G2 that the given message hashes to under the given domain separation tag.
+pub fn hash_to_g2(message: &[u8], dst: &[u8]) -> G2 {
+    let uniform_bytes = expand_message_xmd(message, dst, 4 * FIELD_ELEMENT_SIZE);
+    let mut elements = uniform_bytes.chunks(FIELD_ELEMENT_SIZE).map(reduce);
+    let mut u = || Fq2 {
+        c0: elements.next().unwrap(),
+        c1: elements.next().unwrap(),
+    };
+
+    let mut point = map_to_curve(&u());
+    point.add_assign(&map_to_curve(&u()));
+    clear_cofactor(&point)
+}
+
+/// Returns the big-endian integer of the given 64 bytes, reduced modulo the characteristic of Fq.
+fn reduce(bytes: &[u8]) -> Fq {
+    let element = |bytes: &[u8]| {
+        let mut repr = FqRepr::default();
+        repr.read_be(&[&[0u8; 16][..], bytes].concat()[..]).unwrap();
+        Fq::from_repr(repr).unwrap()
+    };
+
+    // 2^256
+    let mut shift = FqRepr::default();
+    shift.0[4] = 1;
+
+    let mut result = element(&bytes[..32]);
+    result.mul_assign(&Fq::from_repr(shift).unwrap());
+    result.add_assign(&element(&bytes[32..]));
+    result
+}
+
+/// Returns the sign of the given element, which is the parity of its first nonzero coefficient.
+fn sgn0(element: &Fq2) -> bool {
+    let c0 = element.c0.into_repr();
+    c0.is_odd() || (c0.is_zero() && element.c1.into_repr().is_odd())
+}
+
+/// Returns x^3 + A'x + B'.
+fn curve_equation(x: &Fq2) -> Fq2 {
+    let mut result = *x;
+    result.square();
+    result.add_assign(&SSWU.a);
+    result.mul_assign(x);
+    result.add_assign(&SSWU.b);
+    result
+}
+
+/// Returns the evaluation of the polynomial with the given coefficients, in ascending degree, at x.
+fn evaluate(coefficients: &[Fq2], x: &Fq2) -> Fq2 {
+    coefficients.iter().rev().fold(Fq2::zero(), |mut result, coefficient| {
+        result.mul_assign(x);
+        result.add_assign(coefficient);
+        result
+    })
+}
+
+/// Returns the point of the twist E2 that the given element maps to, with the simplified SWU map
+/// to E2' followed by the 3-isogeny from E2' to E2.
+fn map_to_curve(u: &Fq2) -> G2 {
+    // Z * u^2
+    let mut z_u2 = *u;
+    z_u2.square();
+    z_u2.mul_assign(&SSWU.z);
+
+    // Z^2 * u^4 + Z * u^2
+    let mut denominator = z_u2;
+    denominator.square();
+    denominator.add_assign(&z_u2);
+
+    let x1 = match denominator.inverse() {
+        Some(mut x1) => {
+            x1.add_assign(&Fq2::one());
+            x1.mul_assign(&SSWU.minus_b_over_a);
+            x1
+        }
+        None => SSWU.b_over_za,
+    };
+
+    let (x, mut y) = match curve_equation(&x1).sqrt() {
+        Some(y1) => (x1, y1),
+        None => {
+            // When x1^3 + A'x1 + B' is a non-square, x2^3 + A'x2 + B' is a square.
+            let mut x2 = z_u2;
+            x2.mul_assign(&x1);
+            (x2, curve_equation(&x2).sqrt().unwrap())
+        }
+    };
+    if sgn0(u) != sgn0(&y) {
+        y.negate();
+    }
+
+    let (x_denominator, y_denominator) = match (
+        evaluate(&SSWU.x_denominator, &x).inverse(),
+        evaluate(&SSWU.y_denominator, &x).inverse(),
+    ) {
+        (Some(x_denominator), Some(y_denominator)) => (x_denominator, y_denominator),
+        // The kernel of the isogeny maps to the point at infinity.
+        _ => return G2::zero(),
+    };
+
+    let mut x_numerator = evaluate(&SSWU.x_numerator, &x);
+    x_numerator.mul_assign(&x_denominator);
+    let mut y_numerator = evaluate(&SSWU.y_numerator, &x);
+    y_numerator.mul_assign(&y_denominator);
+    y_numerator.mul_assign(&y);
+
+    G2Affine {
+        x: x_numerator,
+        y: y_numerator,
+        infinity: false,
+    }
+    .into_projective()
+}
+
+/// Returns the given point of the twist multiplied by h_eff, which is a point of G2.
+fn clear_cofactor(point: &G2) -> G2 {
+    let mut result = G2::zero();
+    for bit in BitIterator::new(H_EFF) {
+        result.double();
+        if bit {
+            result.add_assign(point);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::librustzcash::algebra::curve::bls12_381::G2Uncompressed;
+    use crate::librustzcash::algebra::group::EncodedPoint;
+
+    const EXPANDER_DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHA256-128";
+
+    const HASH_TO_CURVE_DST: &[u8] = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+
+    const Q128: &str = "q128_qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq";
+
+    // (message, length, uniform bytes)
+    const EXPAND_MESSAGE_XMD: [(&str, usize, &str); 5] = [
+        (
+            "",
+            0x20,
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+        ),
+        (
+            "abc",
+            0x20,
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+        ),
+        (
+            "abcdef0123456789",
+            0x20,
+            "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+        ),
+        (
+            Q128,
+            0x20,
+            "b23a1d2b4d97b2ef7785562a7e8bac7eed54ed6e97e29aa51bfe3f12ddad1ff9",
+        ),
+        (
+            "",
+            0x80,
+            "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced",
+        ),
+    ];
+
+    // (message, uncompressed point)
+    const HASH_TO_G2: [(&str, &str); 4] = [
+        (
+            "",
+            "05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d60503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92",
+        ),
+        (
+            "abc",
+            "139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd802c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e600aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd161787327b68159716a37440985269cf584bcb1e621d3a7202be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48",
+        ),
+        (
+            "abcdef0123456789",
+            "190d119345b94fbd15497bcba94ecf7db2cbfd1e1fe7da034d26cbba169fb3968288b3fafb265f9ebd380512a71c3f2c121982811d2491fde9ba7ed31ef9ca474f0e1501297f68c298e9f4c0028add35aea8bb83d53c08cfc007c1e005723cd00bb5e7572275c567462d91807de765611490205a941a5a6af3b1691bfe596c31225d3aabdf15faff860cb4ef17c7c3be05571a0f8d3c08d094576981f4a3b8eda0a8e771fcdcc8ecceaf1356a6acf17574518acb506e435b639353c2e14827c8",
+        ),
+        (
+            Q128,
+            "0934aba516a52d8ae479939a91998299c76d39cc0c035cd18813bec433f587e2d7a4fef038260eef0cef4d02aae3eb9119a84dd7248a1066f737cc34502ee5555bd3c19f2ecdb3c7d9e24dc65d4e25e50d83f0f77105e955d78f4762d33c17da09bcccfa036b4847c9950780733633f13619994394c23ff0b32fa6b795844f4a0673e20282d07bc69641cee04f5e566214f81cd421617428bc3b9fe25afbb751d934a00493524bc4e065635b0555084dd54679df1536101b2c979c0152d09192",
+        ),
+    ];
+
+    #[test]
+    fn test_expand_message_xmd() {
+        EXPAND_MESSAGE_XMD.iter().for_each(|(message, length, expected)| {
+            let uniform_bytes = expand_message_xmd(message.as_bytes(), EXPANDER_DST, *length);
+            assert_eq!(*expected, hex::encode(uniform_bytes));
+        });
+    }
+
+    #[test]
+    fn test_expand_message_xmd_long_dst() {
+        let dst = [
+            &b"QUUX-V01-CS02-with-expander-SHA256-128-long-DST-"[..],
+            &[b'1'; 208][..],
+        ]
+        .concat();
+        assert_eq!(
+            "e8dc0c8b686b7ef2074086fbdd2f30e3f8bfbd3bdf177f73f04b97ce618a3ed3",
+            hex::encode(expand_message_xmd(b"", &dst, 0x20))
+        );
+    }
+
+    #[test]
+    fn test_hash_to_g2() {
+        HASH_TO_G2.iter().for_each(|(message, expected)| {
+            let point = hash_to_g2(message.as_bytes(), HASH_TO_CURVE_DST).into_affine();
+            assert_eq!(*expected, hex::encode(G2Uncompressed::from_affine(point).as_ref()));
+        });
+    }
+}
@@ -0,0 +1,518 @@
+//!
+//! Validator Keystore
+//!
+//! This module contains the EIP-2335 keystore, which encrypts a BLS secret key under a password
+//! with scrypt or PBKDF2 and AES-128-CTR, in the JSON format read by consensus-layer clients.
+//! https://eips.ethereum.org/EIPS/eip-2335
+//!
+
+use crate::eth2::bls::{BlsError, BlsSecretKey};
+use crate::eth2::derivation_path::ValidatorDerivationPath;
+
+use crypto::{
+    aes::{ctr, KeySize},
+    hmac::Hmac,
+    pbkdf2::pbkdf2,
+    scrypt::{scrypt, ScryptParams},
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{fmt, str::FromStr};
+use unicode_normalization::UnicodeNormalization;
+
+/// The version of EIP-2335 keystores
+const KEYSTORE_VERSION: u32 = 4;
+
+/// The length of the derived key, of which the first half is the cipher key
+const DERIVED_KEY_LENGTH: usize = 32;
+
+/// The default scrypt cost parameter, 2^18
+pub const SCRYPT_N: u32 = 262144;
+
+/// The default scrypt block size parameter
+pub const SCRYPT_R: u32 = 8;
+
+/// The default scrypt parallelization parameter
+pub const SCRYPT_P: u32 = 1;
+
+/// The default PBKDF2 iteration count
+pub const PBKDF2_C: u32 = 262144;
+
+#[derive(Debug, Fail)]
+pub enum KeystoreError {
+    #[fail(display = "{}", _0)]
+    BlsError(BlsError),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "invalid keystore parameter: {}", _0)]
+    InvalidParameter(String),
+
+    #[fail(display = "invalid password")]
+    InvalidPassword,
+
+    #[fail(display = "invalid public key: expected {}, found {}", _0, _1)]
+    InvalidPublicKey(String, String),
+
+    #[fail(display = "unsupported keystore function: {}", _0)]
+    UnsupportedFunction(String),
+
+    #[fail(display = "unsupported keystore version: {}", _0)]
+    UnsupportedVersion(u32),
+}
+
+impl From<BlsError> for KeystoreError {
+    fn from(error: BlsError) -> Self {
+        KeystoreError::BlsError(error)
+    }
+}
+
+impl From<hex::FromHexError> for KeystoreError {
+    fn from(error: hex::FromHexError) -> Self {
+        KeystoreError::Crate("hex", format!("{:?}", error))
+    }
+}
+
+impl From<serde_json::error::Error> for KeystoreError {
+    fn from(error: serde_json::error::Error) -> Self {
+        KeystoreError::Crate("serde_json", format!("{:?}", error))
+    }
+}
+
+/// Represents the key derivation function of a keystore and its cost parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeystoreKdf {
+    Scrypt { n: u32, r: u32, p: u32 },
+    Pbkdf2 { c: u32 },
+}
+
+impl KeystoreKdf {
+    /// Returns scrypt with the default parameters, n = 2^18, r = 8, p = 1.
+    pub fn scrypt() -> Self {
+        KeystoreKdf::Scrypt {
+            n: SCRYPT_N,
+            r: SCRYPT_R,
+            p: SCRYPT_P,
+        }
+    }
+
+    /// Returns PBKDF2 with HMAC-SHA256 and the default iteration count of 2^18.
+    pub fn pbkdf2() -> Self {
+        KeystoreKdf::Pbkdf2 { c: PBKDF2_C }
+    }
+
+    /// Returns the key derived from the given password and salt.
+    fn derive_key(&self, password: &[u8], salt: &[u8]) -> Result<[u8; DERIVED_KEY_LENGTH], KeystoreError> {
+        let mut key = [0u8; DERIVED_KEY_LENGTH];
+        match *self {
+            KeystoreKdf::Scrypt { n, r, p } => {
+                if n < 2 || !n.is_power_of_two() || r == 0 || p == 0 {
+                    return Err(KeystoreError::InvalidParameter(format!(
+                        "scrypt n = {}, r = {}, p = {}",
+                        n, r, p
+                    )));
+                }
+                scrypt(
+                    password,
+                    salt,
+                    &ScryptParams::new(n.trailing_zeros() as u8, r, p),
+                    &mut key,
+                );
+            }
+            KeystoreKdf::Pbkdf2 { c } => {
+                if c == 0 {
+                    return Err(KeystoreError::InvalidParameter("pbkdf2 c = 0".into()));
+                }
+                pbkdf2(&mut Hmac::new(crypto::sha2::Sha256::new(), password), salt, c, &mut key);
+            }
+        }
+        Ok(key)
+    }
+}
+
+/// Represents a function of a keystore, with its parameters and message
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct KeystoreModule<P> {
+    pub function: String,
+    pub params: P,
+    pub message: String,
+}
+
+/// Represents the parameters of a key derivation function of a keystore
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct KdfParams {
+    pub dklen: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub c: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prf: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r: Option<u32>,
+    pub salt: String,
+}
+
+/// Represents the parameters of the cipher of a keystore
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CipherParams {
+    pub iv: String,
+}
+
+/// Represents the encrypted secret key of a keystore
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct KeystoreCrypto {
+    pub kdf: KeystoreModule<KdfParams>,
+    pub checksum: KeystoreModule<serde_json::Map<String, serde_json::Value>>,
+    pub cipher: KeystoreModule<CipherParams>,
+}
+
+/// Represents an EIP-2335 keystore of a validator secret key
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ValidatorKeystore {
+    pub crypto: KeystoreCrypto,
+    #[serde(default)]
+    pub description: String,
+    pub pubkey: String,
+    pub path: String,
+    pub uuid: String,
+    pub version: u32,
+}
+
+impl ValidatorKeystore {
+    /// Returns a keystore of the given secret key encrypted under the given password,
+    /// with a random salt, initialization vector, and UUID.
+    pub fn encrypt<R: Rng>(
+        secret_key: &BlsSecretKey,
+        password: &str,
+        path: Option<&ValidatorDerivationPath>,
+        kdf: KeystoreKdf,
+        rng: &mut R,
+    ) -> Result<Self, KeystoreError> {
+        let salt: [u8; 32] = rng.gen();
+        let iv: [u8; 16] = rng.gen();
+        let mut uuid: [u8; 16] = rng.gen();
+        // Version 4 and variant 1 of RFC 4122
+        uuid[6] = 0x40 | (uuid[6] & 0x0f);
+        uuid[8] = 0x80 | (uuid[8] & 0x3f);
+
+        Self::encrypt_with_parameters(secret_key, password, path, kdf, &salt, &iv, &uuid)
+    }
+
+    /// Returns a keystore of the given secret key encrypted under the given password,
+    /// with the given salt, initialization vector, and UUID.
+    pub fn encrypt_with_parameters(
+        secret_key: &BlsSecretKey,
+        password: &str,
+        path: Option<&ValidatorDerivationPath>,
+        kdf: KeystoreKdf,
+        salt: &[u8],
+        iv: &[u8; 16],
+        uuid: &[u8; 16],
+    ) -> Result<Self, KeystoreError> {
+        let key = kdf.derive_key(&normalize_password(password), salt)?;
+
+        let mut ciphertext = [0u8; 32];
+        ctr(KeySize::KeySize128, &key[..16], iv).process(&secret_key.to_bytes(), &mut ciphertext);
+        let checksum = Sha256::new().chain(&key[16..]).chain(&ciphertext).result();
+
+        let (function, params) = match kdf {
+            KeystoreKdf::Scrypt { n, r, p } => (
+                "scrypt",
+                KdfParams {
+                    dklen: DERIVED_KEY_LENGTH as u32,
+                    c: None,
+                    n: Some(n),
+                    p: Some(p),
+                    prf: None,
+                    r: Some(r),
+                    salt: hex::encode(salt),
+                },
+            ),
+            KeystoreKdf::Pbkdf2 { c } => (
+                "pbkdf2",
+                KdfParams {
+                    dklen: DERIVED_KEY_LENGTH as u32,
+                    c: Some(c),
+                    n: None,
+                    p: None,
+                    prf: Some("hmac-sha256".into()),
+                    r: None,
+                    salt: hex::encode(salt),
+                },
+            ),
+        };
+
+        let uuid = hex::encode(uuid);
+        Ok(Self {
+            crypto: KeystoreCrypto {
+                kdf: KeystoreModule {
+                    function: function.into(),
+                    params,
+                    message: String::new(),
+                },
+                checksum: KeystoreModule {
+                    function: "sha256".into(),
+                    params: serde_json::Map::new(),
+                    message: hex::encode(checksum),
+                },
+                cipher: KeystoreModule {
+                    function: "aes-128-ctr".into(),
+                    params: CipherParams { iv: hex::encode(iv) },
+                    message: hex::encode(ciphertext),
+                },
+            },
+            description: String::new(),
+            pubkey: secret_key.to_public_key().to_string(),
+            path: path.map(|path| path.to_string()).unwrap_or_default(),
+            uuid: format!(
+                "{}-{}-{}-{}-{}",
+                &uuid[..8],
+                &uuid[8..12],
+                &uuid[12..16],
+                &uuid[16..20],
+                &uuid[20..]
+            ),
+            version: KEYSTORE_VERSION,
+        })
+    }
+
+    /// Returns the secret key of the keystore decrypted with the given password.
+    pub fn decrypt(&self, password: &str) -> Result<BlsSecretKey, KeystoreError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(KeystoreError::UnsupportedVersion(self.version));
+        }
+
+        let crypto = &self.crypto;
+        for (function, expected) in [
+            (&crypto.checksum.function, "sha256"),
+            (&crypto.cipher.function, "aes-128-ctr"),
+        ]
+        .iter()
+        {
+            if function.as_str() != *expected {
+                return Err(KeystoreError::UnsupportedFunction(function.to_string()));
+            }
+        }
+
+        let params = &crypto.kdf.params;
+        let missing = |name: &str| KeystoreError::InvalidParameter(format!("missing {}", name));
+        let kdf = match crypto.kdf.function.as_str() {
+            "scrypt" => KeystoreKdf::Scrypt {
+                n: params.n.ok_or_else(|| missing("n"))?,
+                r: params.r.ok_or_else(|| missing("r"))?,
+                p: params.p.ok_or_else(|| missing("p"))?,
+            },
+            "pbkdf2" => match params.prf.as_ref().map(String::as_str) {
+                Some("hmac-sha256") => KeystoreKdf::Pbkdf2 {
+                    c: params.c.ok_or_else(|| missing("c"))?,
+                },
+                prf => return Err(KeystoreError::UnsupportedFunction(prf.unwrap_or_default().into())),
+            },
+            function => return Err(KeystoreError::UnsupportedFunction(function.into())),
+        };
+        if params.dklen as usize != DERIVED_KEY_LENGTH {
+            return Err(KeystoreError::InvalidParameter(format!("dklen = {}", params.dklen)));
+        }
+
+        let iv = hex::decode(&crypto.cipher.params.iv)?;
+        if iv.len() != 16 {
+            return Err(KeystoreError::InvalidParameter(format!(
+                "iv = {}",
+                crypto.cipher.params.iv
+            )));
+        }
+
+        let key = kdf.derive_key(&normalize_password(password), &hex::decode(&params.salt)?)?;
+        let ciphertext = hex::decode(&crypto.cipher.message)?;
+        let checksum = Sha256::new().chain(&key[16..]).chain(&ciphertext).result();
+        if hex::decode(&crypto.checksum.message)? != checksum.as_slice() {
+            return Err(KeystoreError::InvalidPassword);
+        }
+
+        let mut plaintext = vec![0u8; ciphertext.len()];
+        ctr(KeySize::KeySize128, &key[..16], &iv).process(&ciphertext, &mut plaintext);
+        let secret_key = BlsSecretKey::from_bytes(&plaintext)?;
+
+        let public_key = secret_key.to_public_key().to_string();
+        if !self.pubkey.is_empty() && self.pubkey.trim_start_matches("0x") != public_key {
+            return Err(KeystoreError::InvalidPublicKey(self.pubkey.clone(), public_key));
+        }
+        Ok(secret_key)
+    }
+}
+
+impl FromStr for ValidatorKeystore {
+    type Err = KeystoreError;
+
+    fn from_str(keystore: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(keystore)?)
+    }
+}
+
+impl fmt::Display for ValidatorKeystore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match serde_json::to_string(self) {
+            Ok(keystore) => write!(f, "{}", keystore),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+/// Returns the bytes of the given password, in NFKD with the C0, C1, and Delete control codes removed.
+fn normalize_password(password: &str) -> Vec<u8> {
+    password
+        .nfkd()
+        .filter(|c| !matches!(*c as u32, 0x00..=0x1f | 0x7f..=0x9f))
+        .collect::<String>()
+        .into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    const PASSWORD: &str = "𝔱𝔢𝔰𝔱𝔭𝔞𝔰𝔰𝔴𝔬𝔯𝔡🔑";
+
+    const SECRET_KEY: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+
+    const SCRYPT_KEYSTORE: &str = r#"{
+        "crypto": {
+            "kdf": {
+                "function": "scrypt",
+                "params": {
+                    "dklen": 32,
+                    "n": 262144,
+                    "p": 1,
+                    "r": 8,
+                    "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                },
+                "message": ""
+            },
+            "checksum": {
+                "function": "sha256",
+                "params": {},
+                "message": "d2217fe5f3e9a1e34581ef8a78f7c9928e436d36dacc5e846690a5581e8ea484"
+            },
+            "cipher": {
+                "function": "aes-128-ctr",
+                "params": {
+                    "iv": "264daa3f303d7259501c93d997d84fe6"
+                },
+                "message": "06ae90d55fe0a6e9c5c3bc5b170827b2e5cce3929ed3f116c2811e6366dfe20f"
+            }
+        },
+        "description": "This is a test keystore that uses scrypt to secure the secret.",
+        "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+        "path": "m/12381/60/3141592653/589793238",
+        "uuid": "1d85ae20-35c5-4611-98e8-aa14a633906f",
+        "version": 4
+    }"#;
+
+    const PBKDF2_KEYSTORE: &str = r#"{
+        "crypto": {
+            "kdf": {
+                "function": "pbkdf2",
+                "params": {
+                    "dklen": 32,
+                    "c": 262144,
+                    "prf": "hmac-sha256",
+                    "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                },
+                "message": ""
+            },
+            "checksum": {
+                "function": "sha256",
+                "params": {},
+                "message": "8a9f5d9912ed7e75ea794bc5a89bca5f193721d30868ade6f73043c6ea6febf1"
+            },
+            "cipher": {
+                "function": "aes-128-ctr",
+                "params": {
+                    "iv": "264daa3f303d7259501c93d997d84fe6"
+                },
+                "message": "cee03fde2af33149775b7223e7845e4fb2c8ae1792e5f99fe9ecf474cc8c16ad"
+            }
+        },
+        "description": "This is a test keystore that uses PBKDF2 to secure the secret.",
+        "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+        "path": "m/12381/60/0/0",
+        "uuid": "64625def-3331-4eea-ab6f-782f3ed16a83",
+        "version": 4
+    }"#;
+
+    #[test]
+    fn decrypt() {
+        [SCRYPT_KEYSTORE, PBKDF2_KEYSTORE].iter().for_each(|keystore| {
+            let keystore = ValidatorKeystore::from_str(keystore).unwrap();
+            assert_eq!(SECRET_KEY, keystore.decrypt(PASSWORD).unwrap().to_string());
+            assert_eq!(SECRET_KEY, keystore.decrypt("testpassword🔑").unwrap().to_string());
+        });
+    }
+
+    #[test]
+    fn encrypt_with_parameters() {
+        let keystore = ValidatorKeystore::from_str(PBKDF2_KEYSTORE).unwrap();
+        let secret_key = BlsSecretKey::from_str(SECRET_KEY).unwrap();
+        let path = ValidatorDerivationPath::from_str("m/12381/60/0/0").unwrap();
+
+        let mut uuid = [0u8; 16];
+        uuid.copy_from_slice(&hex::decode("64625def33314eeaab6f782f3ed16a83").unwrap());
+        let mut iv = [0u8; 16];
+        iv.copy_from_slice(&hex::decode("264daa3f303d7259501c93d997d84fe6").unwrap());
+        let salt = hex::decode("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3").unwrap();
+
+        let encrypted = ValidatorKeystore::encrypt_with_parameters(
+            &secret_key,
+            PASSWORD,
+            Some(&path),
+            KeystoreKdf::pbkdf2(),
+            &salt,
+            &iv,
+            &uuid,
+        )
+        .unwrap();
+        assert_eq!(keystore.crypto, encrypted.crypto);
+        assert_eq!(keystore.pubkey, encrypted.pubkey);
+        assert_eq!(keystore.path, encrypted.path);
+        assert_eq!(keystore.uuid, encrypted.uuid);
+    }
+
+    #[test]
+    fn encrypt() {
+        let mut rng = XorShiftRng::seed_from_u64(0);
+        let secret_key = BlsSecretKey::new(&mut rng);
+        let kdf = KeystoreKdf::Scrypt { n: 1024, r: 8, p: 1 };
+
+        let keystore = ValidatorKeystore::encrypt(&secret_key, "password", None, kdf, &mut rng).unwrap();
+        let keystore = ValidatorKeystore::from_str(&keystore.to_string()).unwrap();
+        assert_eq!(secret_key, keystore.decrypt("password").unwrap());
+        assert_eq!('4', keystore.uuid.chars().nth(14).unwrap());
+        assert_eq!("", keystore.path);
+    }
+
+    #[test]
+    fn invalid_password() {
+        let mut rng = XorShiftRng::seed_from_u64(1);
+        let secret_key = BlsSecretKey::new(&mut rng);
+        let kdf = KeystoreKdf::Pbkdf2 { c: 1024 };
+
+        let keystore = ValidatorKeystore::encrypt(&secret_key, "password", None, kdf, &mut rng).unwrap();
+        match keystore.decrypt("passwort") {
+            Err(KeystoreError::InvalidPassword) => (),
+            result => panic!("expected an invalid password, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(b"testpassword\xf0\x9f\x94\x91".to_vec(), normalize_password(PASSWORD));
+        assert_eq!(b"password".to_vec(), normalize_password("pass\u{0}\u{7f}\u{85}word\n"));
+    }
+}
@@ -0,0 +1,21 @@
+//!
+//! Ethereum Validator Keys
+//!
+//! This module contains the keys of Ethereum consensus-layer validators on the BLS12-381 curve:
+//! EIP-2333 key derivation, EIP-2334 paths, BLS signatures, EIP-2335 keystores, and deposit data.
+//!
+
+pub mod bls;
+pub use self::bls::*;
+
+pub mod deposit;
+pub use self::deposit::*;
+
+pub mod derivation_path;
+pub use self::derivation_path::*;
+
+pub mod hash_to_curve;
+pub use self::hash_to_curve::*;
+
+pub mod keystore;
+pub use self::keystore::*;
@@ -8,6 +8,9 @@
 #[macro_use]
 extern crate hex_literal;
 
+#[macro_use]
+extern crate failure;
+
 #[macro_use]
 extern crate lazy_static;
 
@@ -20,6 +23,8 @@ pub use self::amount::*;
 pub mod derivation_path;
 pub use self::derivation_path::*;
 
+pub mod eth2;
+
 pub mod extended_private_key;
 pub use self::extended_private_key::*;
 